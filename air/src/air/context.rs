@@ -3,7 +3,7 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::{air::TransitionConstraintDegree, ProofOptions, TraceInfo};
+use crate::{air::TransitionConstraintDegree, DomainError, ProofOptions, TraceInfo};
 use math::{log2, StarkField};
 use utils::collections::Vec;
 
@@ -72,18 +72,28 @@ impl<B: StarkField> AirContext<B> {
     /// computed by [Air::evaluate_aux_transition()](crate::Air::evaluate_aux_transition) function
     /// are expected to be in the order defined by `aux_transition_constraint_degrees` list.
     ///
+    /// For multi-segment traces, the main trace segment is not required to have any transition
+    /// constraints or assertions placed against it. This is useful when all witness data of a
+    /// computation lives in the extension field, and thus, is placed into auxiliary trace segments.
+    ///
     /// # Panics
     /// Panics if
-    /// * `main_transition_constraint_degrees` is an empty vector.
-    /// * `num_main_assertions` is zero.
     /// * `trace_info.is_multi_segment() == true` but:
     ///   - `aux_transition_constraint_degrees` is an empty vector.
     ///   - `num_aux_assertions` is zero.
     /// * `trace_info.is_multi_segment() == false` but:
+    ///   - `main_transition_constraint_degrees` is an empty vector.
+    ///   - `num_main_assertions` is zero.
     ///   - `aux_transition_constraint_degrees` is a non-empty vector.
     ///   - `num_aux_assertions` is greater than zero.
     /// * Blowup factor specified by the provided `options` is too small to accommodate degrees
     ///   of the specified transition constraints.
+    /// * The low-degree extension domain implied by `trace_info` and `options` cannot be built
+    ///   in the base field (see [AirContext::validate_domains()]).
+    ///
+    /// If the blowup factor of `options` is selected automatically, the options are resolved via
+    /// [ProofOptions::resolve_blowup_factor()] using the blowup factor required by the highest
+    /// degree transition constraint.
     pub fn new_multi_segment(
         trace_info: TraceInfo,
         main_transition_constraint_degrees: Vec<TransitionConstraintDegree>,
@@ -92,15 +102,6 @@ impl<B: StarkField> AirContext<B> {
         num_aux_assertions: usize,
         options: ProofOptions,
     ) -> Self {
-        assert!(
-            !main_transition_constraint_degrees.is_empty(),
-            "at least one transition constraint degree must be specified"
-        );
-        assert!(
-            num_main_assertions > 0,
-            "at least one assertion must be specified"
-        );
-
         if trace_info.is_multi_segment() {
             assert!(
                 !aux_transition_constraint_degrees.is_empty(),
@@ -111,6 +112,14 @@ impl<B: StarkField> AirContext<B> {
                 "at least one assertion must be specified against auxiliary trace segments"
             );
         } else {
+            assert!(
+                !main_transition_constraint_degrees.is_empty(),
+                "at least one transition constraint degree must be specified"
+            );
+            assert!(
+                num_main_assertions > 0,
+                "at least one assertion must be specified"
+            );
             assert!(
                 aux_transition_constraint_degrees.is_empty(),
                 "auxiliary transition constraint degrees specified for a single-segment trace"
@@ -122,20 +131,19 @@ impl<B: StarkField> AirContext<B> {
         }
 
         // determine minimum blowup factor needed to evaluate transition constraints by taking
-        // the blowup factor of the highest degree constraint
-        let mut ce_blowup_factor = 0;
-        for degree in main_transition_constraint_degrees.iter() {
-            if degree.min_blowup_factor() > ce_blowup_factor {
-                ce_blowup_factor = degree.min_blowup_factor();
-            }
-        }
-
-        for degree in aux_transition_constraint_degrees.iter() {
-            if degree.min_blowup_factor() > ce_blowup_factor {
-                ce_blowup_factor = degree.min_blowup_factor();
-            }
-        }
+        // the blowup factor of the highest degree constraint; the blowup factor is derived from
+        // exact evaluation degrees of constraints for the trace length of this computation
+        let ce_blowup_factor = get_ce_blowup_factor(
+            main_transition_constraint_degrees
+                .iter()
+                .chain(aux_transition_constraint_degrees.iter()),
+            trace_info.length(),
+            1,
+        );
 
+        // if the blowup factor is selected automatically, select the smallest one which can
+        // accommodate constraint degrees
+        let options = options.resolve_blowup_factor(ce_blowup_factor);
         assert!(
             options.blowup_factor() >= ce_blowup_factor,
             "blowup factor too small; expected at least {}, but was {}",
@@ -143,6 +151,10 @@ impl<B: StarkField> AirContext<B> {
             options.blowup_factor()
         );
 
+        if let Err(err) = Self::validate_domains(&trace_info, &options) {
+            panic!("{err}");
+        }
+
         let trace_length = trace_info.length();
         let lde_domain_size = trace_length * options.blowup_factor();
 
@@ -160,6 +172,50 @@ impl<B: StarkField> AirContext<B> {
         }
     }
 
+    // VALIDATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Checks that all domains of a computation described by the provided `trace_info` and
+    /// `options` can be built in the base field.
+    ///
+    /// Trace, constraint evaluation, and low-degree extension domains are multiplicative
+    /// subgroups of the base field, and thus, their sizes cannot exceed 2^`TWO_ADICITY` of the
+    /// field. Since the low-degree extension domain is the largest of these domains, only its
+    /// size needs to be checked.
+    ///
+    /// Additionally, the offset by which the low-degree extension domain is shifted must not be an
+    /// element of this domain; otherwise, the shifted domain would not be disjoint from the
+    /// trace domain.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The size of the low-degree extension domain exceeds the size of the largest power-of-two
+    ///   subgroup of the base field.
+    /// * The domain offset is zero or an element of the low-degree extension domain.
+    pub fn validate_domains(
+        trace_info: &TraceInfo,
+        options: &ProofOptions,
+    ) -> Result<(), DomainError> {
+        // trace length and blowup factor are guaranteed to be powers of two
+        let log_lde_domain_size = log2(trace_info.length()) + log2(options.blowup_factor());
+        if log_lde_domain_size > B::TWO_ADICITY {
+            return Err(DomainError::DomainSizeTooLarge(
+                log_lde_domain_size,
+                B::TWO_ADICITY,
+                core::any::type_name::<B>(),
+            ));
+        }
+
+        // the offset is in the domain if and only if raising it to the power of the domain size
+        // yields one
+        let offset = options.domain_offset::<B>();
+        let lde_domain_size = 1u64 << log_lde_domain_size;
+        if offset == B::ZERO || offset.exp(lde_domain_size.into()) == B::ONE {
+            return Err(DomainError::InvalidDomainOffset(log_lde_domain_size));
+        }
+        Ok(())
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -242,6 +298,10 @@ impl<B: StarkField> AirContext<B> {
 
     /// Sets the number of transition exemptions for this context.
     ///
+    /// Exemptions reduce the degree of transition constraint divisors; thus, if needed, the
+    /// constraint evaluation domain is extended so that the constraint composition polynomial
+    /// can still be computed correctly.
+    ///
     /// # Panics
     /// Panics if:
     /// * The number of exemptions is zero.
@@ -261,6 +321,19 @@ impl<B: StarkField> AirContext<B> {
             self.trace_len() / 2 + 1,
             n
         );
+        // extend the constraint evaluation domain if the current one is too small for the
+        // specified number of exemptions; the domain cannot be larger than the LDE domain
+        let ce_blowup_factor = get_ce_blowup_factor(
+            self.main_transition_constraint_degrees
+                .iter()
+                .chain(self.aux_transition_constraint_degrees.iter()),
+            self.trace_len(),
+            n,
+        );
+        if ce_blowup_factor <= self.options.blowup_factor() {
+            self.ce_blowup_factor = core::cmp::max(self.ce_blowup_factor, ce_blowup_factor);
+        }
+
         // make sure the composition polynomial can be computed correctly with the specified
         // number of exemptions
         for degree in self
@@ -280,3 +353,19 @@ impl<B: StarkField> AirContext<B> {
         self
     }
 }
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the blowup factor of the constraint evaluation domain needed to evaluate transition
+/// constraints of the specified degrees over a trace of the specified length with the specified
+/// number of transition exemptions.
+fn get_ce_blowup_factor<'a, I>(degrees: I, trace_length: usize, num_exemptions: usize) -> usize
+where
+    I: Iterator<Item = &'a TransitionConstraintDegree>,
+{
+    degrees
+        .map(|degree| degree.get_min_blowup_factor(trace_length, num_exemptions))
+        .max()
+        .unwrap_or(ProofOptions::MIN_BLOWUP_FACTOR)
+}
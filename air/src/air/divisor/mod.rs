@@ -8,6 +8,9 @@ use core::fmt::{Display, Formatter};
 use math::{log2, FieldElement, StarkField};
 use utils::collections::Vec;
 
+mod rational;
+pub use rational::RationalFunction;
+
 // CONSTRAINT DIVISOR
 // ================================================================================================
 /// The denominator portion of boundary and transition constraints.
@@ -20,12 +23,13 @@ use utils::collections::Vec;
 /// For example divisor $(x^a - 1) \cdot (x^b - 2) / (x - 3)$ can be represented as:
 /// numerator: `[(a, 1), (b, 2)]`, exemptions: `[3]`.
 ///
+/// Internally, a divisor is described by a [RationalFunction].
+///
 /// A divisor cannot be instantiated directly, and instead must be created either for an
 /// [Assertion] or for a transition constraint.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ConstraintDivisor<B: StarkField> {
-    pub(super) numerator: Vec<(usize, B)>,
-    pub(super) exemptions: Vec<B>,
+    function: RationalFunction<B>,
 }
 
 impl<B: StarkField> ConstraintDivisor<B> {
@@ -35,8 +39,7 @@ impl<B: StarkField> ConstraintDivisor<B> {
     /// Returns a new divisor instantiated from the provided parameters.
     fn new(numerator: Vec<(usize, B)>, exemptions: Vec<B>) -> Self {
         ConstraintDivisor {
-            numerator,
-            exemptions,
+            function: RationalFunction::new(numerator, exemptions),
         }
     }
 
@@ -106,64 +109,42 @@ impl<B: StarkField> ConstraintDivisor<B> {
 
     /// Returns the numerator portion of this constraint divisor.
     pub fn numerator(&self) -> &[(usize, B)] {
-        &self.numerator
+        self.function.numerator()
     }
 
     /// Returns exemption points (the denominator portion) of this constraints divisor.
     pub fn exemptions(&self) -> &[B] {
-        &self.exemptions
+        self.function.denominator()
     }
 
     /// Returns the degree of the divisor polynomial
     pub fn degree(&self) -> usize {
-        let numerator_degree = self
-            .numerator
-            .iter()
-            .fold(0, |degree, term| degree + term.0);
-        let denominator_degree = self.exemptions.len();
-        numerator_degree - denominator_degree
+        self.function.degree()
+    }
+
+    /// Returns the rational function describing this divisor.
+    pub fn as_rational_function(&self) -> &RationalFunction<B> {
+        &self.function
     }
 
     // EVALUATORS
     // --------------------------------------------------------------------------------------------
     /// Evaluates the divisor polynomial at the provided `x` coordinate.
     pub fn evaluate_at<E: FieldElement<BaseField = B>>(&self, x: E) -> E {
-        // compute the numerator value
-        let mut numerator = E::ONE;
-        for (degree, constant) in self.numerator.iter() {
-            let v = x.exp((*degree as u32).into());
-            let v = v - E::from(*constant);
-            numerator *= v;
-        }
-
-        // compute the denominator value
-        let denominator = self.evaluate_exemptions_at(x);
-
-        numerator / denominator
+        self.function.evaluate_at(x)
     }
 
     /// Evaluates the denominator of this divisor (the exemption points) at the provided `x`
     /// coordinate.
     #[inline(always)]
     pub fn evaluate_exemptions_at<E: FieldElement<BaseField = B>>(&self, x: E) -> E {
-        self.exemptions
-            .iter()
-            .fold(E::ONE, |r, &e| r * (x - E::from(e)))
+        self.function.evaluate_denominator_at(x)
     }
 }
 
 impl<B: StarkField> Display for ConstraintDivisor<B> {
     fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
-        for (degree, offset) in self.numerator.iter() {
-            write!(f, "(x^{degree} - {offset})")?;
-        }
-        if !self.exemptions.is_empty() {
-            write!(f, " / ")?;
-            for x in self.exemptions.iter() {
-                write!(f, "(x - {x})")?;
-            }
-        }
-        Ok(())
+        write!(f, "{}", self.function)
     }
 }
 
@@ -0,0 +1,358 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use core::{
+    fmt::{Display, Formatter},
+    ops::Mul,
+};
+use math::{FieldElement, StarkField};
+use utils::collections::Vec;
+
+// RATIONAL FUNCTION
+// ================================================================================================
+/// A rational function with a sparse numerator and a fully-factored denominator.
+///
+/// The numerator is described as a product of terms where each term is a tuple `(a, b)` which
+/// encodes an expression $(x^a - b)$. The denominator is described as a list of roots where each
+/// root $c$ encodes an expression $(x - c)$. For example, function
+/// $(x^a - 1) \cdot (x^b - 2) / (x - 3)$ is represented as: numerator: `[(a, 1), (b, 2)]`,
+/// denominator: `[3]`.
+///
+/// This representation is closed under multiplication, and it is sufficient to describe all
+/// divisors of boundary and transition constraints. Whenever a function is created, linear terms
+/// of the numerator are cancelled against matching roots of the denominator. Equality of two
+/// functions is structural: the same function with terms listed in a different order is not
+/// considered equal.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RationalFunction<B: StarkField> {
+    numerator: Vec<(usize, B)>,
+    denominator: Vec<B>,
+}
+
+impl<B: StarkField> RationalFunction<B> {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new rational function instantiated from the provided numerator terms and
+    /// denominator roots.
+    ///
+    /// Linear numerator terms $(x - c)$ are cancelled against denominator roots $c$.
+    ///
+    /// # Panics
+    /// Panics if degree of any of the numerator terms is zero.
+    pub fn new(numerator: Vec<(usize, B)>, denominator: Vec<B>) -> Self {
+        assert!(
+            numerator.iter().all(|&(degree, _)| degree > 0),
+            "degree of all numerator terms must be greater than zero"
+        );
+        let mut result = RationalFunction {
+            numerator,
+            denominator,
+        };
+        result.simplify();
+        result
+    }
+
+    /// Returns a rational function which is a constant $1$.
+    pub fn one() -> Self {
+        RationalFunction {
+            numerator: Vec::new(),
+            denominator: Vec::new(),
+        }
+    }
+
+    /// Returns a polynomial $(x^a - b)$.
+    ///
+    /// # Panics
+    /// Panics if `a` is zero.
+    pub fn from_term(a: usize, b: B) -> Self {
+        Self::new(vec![(a, b)], Vec::new())
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the terms of the numerator of this function.
+    pub fn numerator(&self) -> &[(usize, B)] {
+        &self.numerator
+    }
+
+    /// Returns the roots of the denominator of this function.
+    pub fn denominator(&self) -> &[B] {
+        &self.denominator
+    }
+
+    /// Returns the degree of the numerator of this function.
+    pub fn numerator_degree(&self) -> usize {
+        self.numerator.iter().map(|&(degree, _)| degree).sum()
+    }
+
+    /// Returns the degree of the denominator of this function.
+    pub fn denominator_degree(&self) -> usize {
+        self.denominator.len()
+    }
+
+    /// Returns the degree of this function, assuming it is a polynomial.
+    ///
+    /// # Panics
+    /// Panics if the degree of the denominator is greater than the degree of the numerator.
+    pub fn degree(&self) -> usize {
+        let numerator_degree = self.numerator_degree();
+        let denominator_degree = self.denominator_degree();
+        assert!(
+            numerator_degree >= denominator_degree,
+            "denominator degree ({denominator_degree}) exceeds numerator degree ({numerator_degree})"
+        );
+        numerator_degree - denominator_degree
+    }
+
+    /// Returns true if this function is a polynomial.
+    ///
+    /// This is the case when every root of the denominator is also a root of the numerator, and
+    /// multiplicities of numerator roots are sufficient to cancel out all denominator roots.
+    /// Roots of a single numerator term $(x^a - b)$ are assumed to be distinct, which is always
+    /// the case for STARK fields and $a$ smaller than the field modulus.
+    pub fn is_polynomial(&self) -> bool {
+        // every (term, root) pair can be used to cancel a denominator root only once
+        let mut used: Vec<(usize, B)> = Vec::with_capacity(self.denominator.len());
+        for &root in self.denominator.iter() {
+            let term = self.numerator.iter().enumerate().position(|(i, &(a, b))| {
+                root.exp((a as u64).into()) == b && !used.contains(&(i, root))
+            });
+            match term {
+                Some(i) => used.push((i, root)),
+                None => return false,
+            }
+        }
+        true
+    }
+
+    // ALGEBRA
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a common divisor of the numerators of this and the other function.
+    ///
+    /// The divisor is computed term-wise: term $(x^a - b)$ divides term $(x^c - d)$ if $a$
+    /// divides $c$ and $b^{c / a} = d$. For every term of this function, a matching term of the
+    /// other function is found such that one of the terms divides the other, and the smaller
+    /// term is included in the result.
+    ///
+    /// For terms which are pairwise equal, coprime, or related by divisibility (which covers
+    /// all divisors of boundary and transition constraints), this is the greatest common
+    /// divisor. However, common factors which are split across several terms are not detected.
+    pub fn gcd(&self, other: &Self) -> Self {
+        let mut pool = other
+            .numerator
+            .iter()
+            .copied()
+            .map(Some)
+            .collect::<Vec<_>>();
+        let mut result = Vec::new();
+        for &term in self.numerator.iter() {
+            let divisor = pool.iter_mut().find_map(|slot| match *slot {
+                Some(other_term) if term_divides(term, other_term) => slot.take().map(|_| term),
+                Some(other_term) if term_divides(other_term, term) => slot.take(),
+                _ => None,
+            });
+            if let Some(divisor) = divisor {
+                result.push(divisor);
+            }
+        }
+        Self::new(result, Vec::new())
+    }
+
+    // EVALUATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Evaluates this function at the provided `x` coordinate.
+    ///
+    /// The result is computed as a quotient of numerator and denominator evaluations, and thus,
+    /// is meaningless if `x` is a root of the denominator.
+    pub fn evaluate_at<E: FieldElement<BaseField = B>>(&self, x: E) -> E {
+        self.evaluate_numerator_at(x) / self.evaluate_denominator_at(x)
+    }
+
+    /// Evaluates the numerator of this function at the provided `x` coordinate.
+    pub fn evaluate_numerator_at<E: FieldElement<BaseField = B>>(&self, x: E) -> E {
+        self.numerator
+            .iter()
+            .fold(E::ONE, |r, &(degree, constant)| {
+                r * (x.exp((degree as u64).into()) - E::from(constant))
+            })
+    }
+
+    /// Evaluates the denominator of this function at the provided `x` coordinate.
+    #[inline(always)]
+    pub fn evaluate_denominator_at<E: FieldElement<BaseField = B>>(&self, x: E) -> E {
+        self.denominator
+            .iter()
+            .fold(E::ONE, |r, &c| r * (x - E::from(c)))
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Cancels linear numerator terms against matching denominator roots.
+    fn simplify(&mut self) {
+        let mut i = 0;
+        while i < self.denominator.len() {
+            let root = self.denominator[i];
+            match self.numerator.iter().position(|&term| term == (1, root)) {
+                Some(j) => {
+                    self.numerator.remove(j);
+                    self.denominator.remove(i);
+                }
+                None => i += 1,
+            }
+        }
+    }
+}
+
+impl<B: StarkField> Mul for RationalFunction<B> {
+    type Output = Self;
+
+    fn mul(mut self, rhs: Self) -> Self {
+        self.numerator.extend_from_slice(&rhs.numerator);
+        self.denominator.extend_from_slice(&rhs.denominator);
+        self.simplify();
+        self
+    }
+}
+
+impl<B: StarkField> Display for RationalFunction<B> {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        if self.numerator.is_empty() {
+            write!(f, "1")?;
+        }
+        for (degree, offset) in self.numerator.iter() {
+            write!(f, "(x^{degree} - {offset})")?;
+        }
+        if !self.denominator.is_empty() {
+            write!(f, " / ")?;
+            for x in self.denominator.iter() {
+                write!(f, "(x - {x})")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns true if term $(x^a - b)$ divides term $(x^c - d)$.
+fn term_divides<B: StarkField>((a, b): (usize, B), (c, d): (usize, B)) -> bool {
+    c % a == 0 && b.exp(((c / a) as u64).into()) == d
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use math::{fields::f128::BaseElement, polynom};
+
+    #[test]
+    fn rational_function_simplification() {
+        let f = RationalFunction::new(
+            vec![(1, BaseElement::new(3)), (4, BaseElement::ONE)],
+            vec![BaseElement::new(2), BaseElement::new(3)],
+        );
+        assert_eq!(&[(4, BaseElement::ONE)], f.numerator());
+        assert_eq!(&[BaseElement::new(2)], f.denominator());
+        assert_eq!(3, f.degree());
+
+        // linear terms introduced by multiplication are cancelled as well
+        let g = RationalFunction::from_term(1, BaseElement::new(2));
+        let h = f * g;
+        assert_eq!(&[(4, BaseElement::ONE)], h.numerator());
+        assert!(h.denominator().is_empty());
+        assert_eq!(RationalFunction::from_term(4, BaseElement::ONE), h);
+    }
+
+    #[test]
+    fn rational_function_multiplication() {
+        let f = RationalFunction::new(vec![(4, BaseElement::ONE)], vec![BaseElement::ONE]);
+        let g = RationalFunction::new(vec![(2, BaseElement::new(4))], vec![BaseElement::new(2)]);
+        let h = f.clone() * g.clone();
+        assert_eq!(4, h.degree());
+
+        let one = RationalFunction::one();
+        assert_eq!(f, f.clone() * one);
+
+        for i in 5..20u128 {
+            let x = BaseElement::new(i);
+            assert_eq!(f.evaluate_at(x) * g.evaluate_at(x), h.evaluate_at(x));
+        }
+    }
+
+    #[test]
+    fn rational_function_gcd() {
+        let n = 16_usize;
+        let g = BaseElement::get_root_of_unity(n.trailing_zeros());
+
+        // (x^4 - 1) divides (x^16 - 1)
+        let f = RationalFunction::from_term(n, BaseElement::ONE);
+        let h = RationalFunction::from_term(4, BaseElement::ONE);
+        assert_eq!(h, f.gcd(&h));
+        assert_eq!(h, h.gcd(&f));
+
+        // (x^2 - g^2) divides (x^8 - g^8) but not (x^8 - 1)
+        let f = RationalFunction::new(
+            vec![(8, BaseElement::ONE), (8, g.exp(8))],
+            vec![BaseElement::ONE],
+        );
+        let h = RationalFunction::new(vec![(2, g.exp(2)), (3, BaseElement::new(5))], vec![]);
+        assert_eq!(RationalFunction::from_term(2, g.exp(2)), f.gcd(&h));
+
+        // coprime terms have a trivial gcd
+        let h = RationalFunction::from_term(3, BaseElement::new(5));
+        assert_eq!(RationalFunction::one(), f.gcd(&h));
+    }
+
+    #[test]
+    fn rational_function_is_polynomial() {
+        let n = 8_usize;
+        let g = BaseElement::get_root_of_unity(n.trailing_zeros());
+
+        // (x^8 - 1) / ((x - g^6) * (x - g^7)) is a polynomial
+        let f = RationalFunction::new(vec![(n, BaseElement::ONE)], vec![g.exp(6), g.exp(7)]);
+        assert!(f.is_polynomial());
+
+        // (x^8 - 1) / (x - g^7)^2 is not a polynomial
+        let f = RationalFunction::new(vec![(n, BaseElement::ONE)], vec![g.exp(7), g.exp(7)]);
+        assert!(!f.is_polynomial());
+
+        // (x^8 - 1) * (x^4 - 1) / (x - g^4)^2 is a polynomial since g^4 is a root of both terms
+        let f = RationalFunction::new(
+            vec![(n, BaseElement::ONE), (4, BaseElement::ONE)],
+            vec![g.exp(4), g.exp(4)],
+        );
+        assert!(f.is_polynomial());
+
+        // (x^4 - 1) / (x - g) is not a polynomial
+        let f = RationalFunction::new(vec![(4, BaseElement::ONE)], vec![g]);
+        assert!(!f.is_polynomial());
+    }
+
+    #[test]
+    fn rational_function_evaluation() {
+        let n = 8_usize;
+        let g = BaseElement::get_root_of_unity(n.trailing_zeros());
+
+        // z(x) = (x^8 - 1) / ((x - g^6) * (x - g^7)) = (x - 1) * (x - g) * ... * (x - g^5)
+        let f = RationalFunction::new(vec![(n, BaseElement::ONE)], vec![g.exp(6), g.exp(7)]);
+        let poly = (0..6u64).fold(vec![BaseElement::ONE], |poly, i| {
+            polynom::mul(&poly, &[-g.exp(i.into()), BaseElement::ONE])
+        });
+        assert_eq!(polynom::degree_of(&poly), f.degree());
+
+        for i in 0..32u128 {
+            let x = BaseElement::new(i + 3);
+            assert_eq!(polynom::eval(&poly, x), f.evaluate_at(x));
+        }
+    }
+}
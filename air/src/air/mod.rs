@@ -7,6 +7,7 @@ use crate::ProofOptions;
 use crypto::{Hasher, RandomCoin, RandomCoinError};
 use math::{fft, ExtensibleField, ExtensionOf, FieldElement, StarkField};
 use utils::{
+    boxed::Box,
     collections::{BTreeMap, Vec},
     Serializable,
 };
@@ -25,7 +26,8 @@ pub use boundary::{BoundaryConstraint, BoundaryConstraintGroup, BoundaryConstrai
 
 mod transition;
 pub use transition::{
-    EvaluationFrame, TransitionConstraintDegree, TransitionConstraintGroup, TransitionConstraints,
+    ConstraintEvaluator, EvaluationFrame, TransitionConstraintDegree, TransitionConstraintGroup,
+    TransitionConstraints, TransitionFragment,
 };
 
 mod coefficients;
@@ -34,7 +36,10 @@ pub use coefficients::{
 };
 
 mod divisor;
-pub use divisor::ConstraintDivisor;
+pub use divisor::{ConstraintDivisor, RationalFunction};
+
+mod params;
+pub use params::VerifierParams;
 
 #[cfg(test)]
 mod tests;
@@ -290,6 +295,21 @@ pub trait Air: Send + Sync {
     // PROVIDED METHODS
     // --------------------------------------------------------------------------------------------
 
+    /// Returns an evaluator which the prover uses to evaluate transition constraints of the main
+    /// trace segment instead of invoking [evaluate_transition()](Air::evaluate_transition) for
+    /// every step of the constraint evaluation domain.
+    ///
+    /// The default implementation returns None. This method should be overridden only for
+    /// computations with very hot transition functions for which a hand-optimized evaluator is
+    /// available (see [ConstraintEvaluator] for the requirements the evaluator must satisfy).
+    /// The evaluator is used only by the prover; the verifier always evaluates constraints via
+    /// [evaluate_transition()](Air::evaluate_transition).
+    fn get_constraint_evaluator(
+        &self,
+    ) -> Option<Box<dyn ConstraintEvaluator<Self::BaseField> + '_>> {
+        None
+    }
+
     /// Returns values for all periodic columns used in the computation.
     ///
     /// These values will be used to compute column values at specific states of the computation
@@ -493,6 +513,7 @@ pub trait Air: Send + Sync {
             .trace_info()
             .layout()
             .get_aux_segment_rand_elements(aux_segment_idx);
+        public_coin.reseed_with_label(b"aux-rand-elements");
         let mut result = Vec::with_capacity(num_elements);
         for _ in 0..num_elements {
             result.push(public_coin.draw()?);
@@ -513,6 +534,7 @@ pub trait Air: Send + Sync {
         E: FieldElement<BaseField = Self::BaseField>,
         H: Hasher,
     {
+        public_coin.reseed_with_label(b"constraint-coeffs");
         let mut t_coefficients = Vec::new();
         for _ in 0..self.context().num_transition_constraints() {
             t_coefficients.push(public_coin.draw_pair()?);
@@ -539,6 +561,7 @@ pub trait Air: Send + Sync {
         E: FieldElement<BaseField = Self::BaseField>,
         H: Hasher,
     {
+        public_coin.reseed_with_label(b"deep-coeffs");
         let mut t_coefficients = Vec::new();
         for _ in 0..self.trace_info().width() {
             t_coefficients.push(public_coin.draw_triple()?);
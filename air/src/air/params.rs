@@ -0,0 +1,313 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{Air, TransitionConstraintDegree};
+use core::fmt::Write;
+use crypto::Hasher;
+use math::StarkField;
+use utils::{
+    collections::Vec,
+    string::{String, ToString},
+    Serializable,
+};
+
+// VERIFIER PARAMETERS
+// ================================================================================================
+
+/// Constant parameters of a computation needed by an external (e.g., Solidity or Cairo) verifier.
+///
+/// These parameters are derived from an [Air] instance and its [ProofOptions](crate::ProofOptions),
+/// and can be exported via [to_json()](VerifierParams::to_json) to keep external verifiers in
+/// sync with the Rust definition of a computation. Field elements are encoded as hex strings of
+/// their canonical little-endian byte representation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifierParams {
+    // field
+    /// Little-endian bytes of the base field modulus.
+    pub field_modulus: Vec<u8>,
+    /// Degree of the field extension used for random challenges (1 if no extension is used).
+    pub field_extension_degree: usize,
+
+    // trace
+    /// Number of steps in the execution trace.
+    pub trace_length: usize,
+    /// Number of columns in the main segment of the execution trace.
+    pub main_trace_width: usize,
+    /// Number of columns in each auxiliary segment of the execution trace.
+    pub aux_segment_widths: Vec<usize>,
+    /// Number of random elements needed to build each auxiliary segment of the execution trace.
+    pub aux_segment_rand_elements: Vec<usize>,
+    /// Cycle lengths of periodic columns.
+    pub periodic_column_lengths: Vec<usize>,
+
+    // domains
+    /// Generator of the trace domain.
+    pub trace_domain_generator: Vec<u8>,
+    /// Blowup factor of the constraint evaluation domain.
+    pub ce_blowup_factor: usize,
+    /// Size of the constraint evaluation domain.
+    pub ce_domain_size: usize,
+    /// Blowup factor of the low-degree extension domain.
+    pub lde_blowup_factor: usize,
+    /// Size of the low-degree extension domain.
+    pub lde_domain_size: usize,
+    /// Generator of the low-degree extension domain.
+    pub lde_domain_generator: Vec<u8>,
+    /// Offset by which the low-degree extension domain is shifted.
+    pub domain_offset: Vec<u8>,
+
+    // constraints
+    /// Evaluation degrees of transition constraints placed against the main trace segment.
+    pub main_constraint_degrees: Vec<usize>,
+    /// Evaluation degrees of transition constraints placed against auxiliary trace segments.
+    pub aux_constraint_degrees: Vec<usize>,
+    /// Number of assertions placed against the main trace segment.
+    pub num_main_assertions: usize,
+    /// Number of assertions placed against auxiliary trace segments.
+    pub num_aux_assertions: usize,
+    /// Number of rows at the end of the trace to which transition constraints do not apply.
+    pub num_transition_exemptions: usize,
+    /// Degree of the constraint composition polynomial.
+    pub composition_degree: usize,
+    /// Number of columns into which the constraint composition polynomial is split.
+    pub num_composition_columns: usize,
+
+    // composition coefficients
+    /// Number of coefficient pairs drawn for transition constraints.
+    pub num_transition_coefficients: usize,
+    /// Number of coefficient pairs drawn for boundary constraints.
+    pub num_boundary_coefficients: usize,
+    /// Number of coefficient triples drawn for trace columns of the DEEP composition polynomial.
+    pub num_deep_trace_coefficients: usize,
+    /// Number of coefficients drawn for composition columns of the DEEP composition polynomial.
+    pub num_deep_constraint_coefficients: usize,
+
+    // protocol
+    /// Number of queries made by the verifier.
+    pub num_queries: usize,
+    /// Number of leading zeros required in the proof-of-work hash.
+    pub grinding_factor: u32,
+    /// Folding factor of the FRI protocol.
+    pub fri_folding_factor: usize,
+    /// Maximum size of the FRI remainder.
+    pub fri_max_remainder_size: usize,
+    /// Number of FRI layers (excluding the remainder).
+    pub num_fri_layers: usize,
+    /// Size of the FRI remainder.
+    pub fri_remainder_size: usize,
+
+    // hashing
+    /// Name of the hash function used for commitments and the public coin.
+    pub hash_function: String,
+    /// Size of hash digests in bytes.
+    pub digest_size: usize,
+    /// Arity of Merkle trees used for commitments.
+    pub merkle_arity: usize,
+    /// Number of adjacent trace rows committed to in a single leaf of trace Merkle trees.
+    pub trace_rows_per_leaf: usize,
+    /// Identifier of the public coin transcript mode.
+    pub transcript_mode: u8,
+}
+
+impl VerifierParams {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns verifier parameters for the computation described by the specified `air`, with
+    /// proofs generated using hash function `H`.
+    pub fn new<A: Air, H: Hasher>(air: &A) -> Self {
+        let context = air.context();
+        let options = air.options();
+        let layout = air.trace_layout();
+        let fri_options = options.to_fri_options();
+        let trace_length = air.trace_length();
+        let evaluation_degrees = |degrees: &[TransitionConstraintDegree]| {
+            degrees
+                .iter()
+                .map(|degree| degree.get_evaluation_degree(trace_length))
+                .collect()
+        };
+        let num_aux_segments = layout.num_aux_segments();
+        let hash_digest_size = H::hash(&[]).to_bytes().len();
+
+        VerifierParams {
+            field_modulus: A::BaseField::get_modulus_le_bytes(),
+            field_extension_degree: options.field_extension().degree() as usize,
+
+            trace_length,
+            main_trace_width: layout.main_trace_width(),
+            aux_segment_widths: (0..num_aux_segments)
+                .map(|i| layout.get_aux_segment_width(i))
+                .collect(),
+            aux_segment_rand_elements: (0..num_aux_segments)
+                .map(|i| layout.get_aux_segment_rand_elements(i))
+                .collect(),
+            periodic_column_lengths: air
+                .get_periodic_column_values()
+                .iter()
+                .map(|column| column.len())
+                .collect(),
+
+            trace_domain_generator: air.trace_domain_generator().to_bytes(),
+            ce_blowup_factor: air.ce_blowup_factor(),
+            ce_domain_size: air.ce_domain_size(),
+            lde_blowup_factor: air.lde_blowup_factor(),
+            lde_domain_size: air.lde_domain_size(),
+            lde_domain_generator: air.lde_domain_generator().to_bytes(),
+            domain_offset: air.domain_offset().to_bytes(),
+
+            main_constraint_degrees: evaluation_degrees(
+                &context.main_transition_constraint_degrees,
+            ),
+            aux_constraint_degrees: evaluation_degrees(&context.aux_transition_constraint_degrees),
+            num_main_assertions: context.num_main_assertions,
+            num_aux_assertions: context.num_aux_assertions,
+            num_transition_exemptions: context.num_transition_exemptions(),
+            composition_degree: air.composition_degree(),
+            num_composition_columns: air.ce_blowup_factor(),
+
+            num_transition_coefficients: context.num_transition_constraints(),
+            num_boundary_coefficients: context.num_assertions(),
+            num_deep_trace_coefficients: air.trace_info().width(),
+            num_deep_constraint_coefficients: air.ce_blowup_factor(),
+
+            num_queries: options.num_queries(),
+            grinding_factor: options.grinding_factor(),
+            fri_folding_factor: fri_options.folding_factor(),
+            fri_max_remainder_size: fri_options.max_remainder_size(),
+            num_fri_layers: fri_options.num_fri_layers(air.lde_domain_size()),
+            fri_remainder_size: fri_options.fri_remainder_size(air.lde_domain_size()),
+
+            hash_function: short_type_name(core::any::type_name::<H>()),
+            digest_size: options.digest_size().unwrap_or(hash_digest_size),
+            merkle_arity: options.merkle_arity(),
+            trace_rows_per_leaf: options.trace_rows_per_leaf(),
+            transcript_mode: options.transcript_mode() as u8,
+        }
+    }
+
+    // EXPORT
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns these parameters as a JSON object with one field per parameter.
+    ///
+    /// Field names are the same as the names of the fields of this struct; numbers are written
+    /// as JSON numbers, byte strings (field modulus and field elements) as hex strings, and lists
+    /// as JSON arrays.
+    pub fn to_json(&self) -> String {
+        let mut json = JsonObject::default();
+        json.hex("field_modulus", &self.field_modulus);
+        json.number("field_extension_degree", self.field_extension_degree);
+
+        json.number("trace_length", self.trace_length);
+        json.number("main_trace_width", self.main_trace_width);
+        json.numbers("aux_segment_widths", &self.aux_segment_widths);
+        json.numbers("aux_segment_rand_elements", &self.aux_segment_rand_elements);
+        json.numbers("periodic_column_lengths", &self.periodic_column_lengths);
+
+        json.hex("trace_domain_generator", &self.trace_domain_generator);
+        json.number("ce_blowup_factor", self.ce_blowup_factor);
+        json.number("ce_domain_size", self.ce_domain_size);
+        json.number("lde_blowup_factor", self.lde_blowup_factor);
+        json.number("lde_domain_size", self.lde_domain_size);
+        json.hex("lde_domain_generator", &self.lde_domain_generator);
+        json.hex("domain_offset", &self.domain_offset);
+
+        json.numbers("main_constraint_degrees", &self.main_constraint_degrees);
+        json.numbers("aux_constraint_degrees", &self.aux_constraint_degrees);
+        json.number("num_main_assertions", self.num_main_assertions);
+        json.number("num_aux_assertions", self.num_aux_assertions);
+        json.number("num_transition_exemptions", self.num_transition_exemptions);
+        json.number("composition_degree", self.composition_degree);
+        json.number("num_composition_columns", self.num_composition_columns);
+
+        json.number(
+            "num_transition_coefficients",
+            self.num_transition_coefficients,
+        );
+        json.number("num_boundary_coefficients", self.num_boundary_coefficients);
+        json.number(
+            "num_deep_trace_coefficients",
+            self.num_deep_trace_coefficients,
+        );
+        json.number(
+            "num_deep_constraint_coefficients",
+            self.num_deep_constraint_coefficients,
+        );
+
+        json.number("num_queries", self.num_queries);
+        json.number("grinding_factor", self.grinding_factor);
+        json.number("fri_folding_factor", self.fri_folding_factor);
+        json.number("fri_max_remainder_size", self.fri_max_remainder_size);
+        json.number("num_fri_layers", self.num_fri_layers);
+        json.number("fri_remainder_size", self.fri_remainder_size);
+
+        json.string("hash_function", &self.hash_function);
+        json.number("digest_size", self.digest_size);
+        json.number("merkle_arity", self.merkle_arity);
+        json.number("trace_rows_per_leaf", self.trace_rows_per_leaf);
+        json.number("transcript_mode", self.transcript_mode);
+        json.finish()
+    }
+}
+
+// JSON OBJECT
+// ================================================================================================
+
+/// A writer for a flat JSON object with one field per line.
+#[derive(Default)]
+struct JsonObject {
+    fields: Vec<String>,
+}
+
+impl JsonObject {
+    fn number<T: core::fmt::Display>(&mut self, name: &str, value: T) {
+        self.fields.push(format!("\"{}\": {}", name, value));
+    }
+
+    fn numbers(&mut self, name: &str, values: &[usize]) {
+        let values = values.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+        self.fields
+            .push(format!("\"{}\": [{}]", name, values.join(", ")));
+    }
+
+    fn string(&mut self, name: &str, value: &str) {
+        self.fields.push(format!("\"{}\": \"{}\"", name, value));
+    }
+
+    fn hex(&mut self, name: &str, bytes: &[u8]) {
+        let mut value = String::with_capacity(bytes.len() * 2);
+        for byte in bytes {
+            write!(value, "{:02x}", byte).expect("writing to a string cannot fail");
+        }
+        self.string(name, &value);
+    }
+
+    fn finish(self) -> String {
+        format!("{{\n  {}\n}}\n", self.fields.join(",\n  "))
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Removes module paths from a type name, e.g. `a::b::Foo<c::Bar>` becomes `Foo<Bar>`.
+fn short_type_name(name: &str) -> String {
+    let mut result = String::new();
+    let mut segment_start = 0;
+    for (i, c) in name.char_indices() {
+        if c.is_alphanumeric() || c == '_' || c == ':' {
+            continue;
+        }
+        result.push_str(last_segment(&name[segment_start..i]));
+        result.push(c);
+        segment_start = i + c.len_utf8();
+    }
+    result.push_str(last_segment(&name[segment_start..]));
+    result
+}
+
+fn last_segment(path: &str) -> &str {
+    path.rsplit("::").next().unwrap_or(path)
+}
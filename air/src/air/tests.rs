@@ -5,12 +5,15 @@
 
 use super::{
     Air, AirContext, Assertion, EvaluationFrame, ProofOptions, TraceInfo,
-    TransitionConstraintDegree,
+    TransitionConstraintDegree, TransitionFragment, VerifierParams,
 };
-use crate::{AuxTraceRandElements, FieldExtension};
+use crate::{AuxTraceRandElements, DomainError, FieldExtension};
 use crypto::{hashers::Blake3_256, RandomCoin};
 use math::{fields::f128::BaseElement, get_power_series, log2, polynom, FieldElement, StarkField};
-use utils::collections::{BTreeMap, Vec};
+use utils::{
+    collections::{BTreeMap, Vec},
+    Serializable,
+};
 
 // PERIODIC COLUMNS
 // ================================================================================================
@@ -61,6 +64,156 @@ fn get_periodic_column_polys_num_values_not_power_of_two() {
 
 // TODO
 
+// TRANSITION FRAGMENTS
+// ================================================================================================
+
+#[test]
+fn transition_fragment() {
+    // 2 columns over an LDE domain of 32 steps; the trace has 8 steps, and the constraint
+    // evaluation domain has 16 steps
+    let col0 = (0..32).map(BaseElement::new).collect::<Vec<_>>();
+    let col1 = (100..132).map(BaseElement::new).collect::<Vec<_>>();
+    let periodic_values = [1u128, 2, 3, 4].map(BaseElement::new);
+    let mut fragment = TransitionFragment::new(vec![&col0, &col1], 2, 4)
+        .with_domain_offset(BaseElement::GENERATOR)
+        .with_periodic_values(&periodic_values, 2);
+    assert_eq!(16, fragment.num_rows());
+
+    fragment.set_rows(14, 2);
+    assert_eq!(14, fragment.offset());
+    assert_eq!(2, fragment.num_columns());
+    assert_eq!(28, fragment.current_lde_step(0));
+    assert_eq!(0, fragment.next_lde_step(0));
+    assert_eq!(2, fragment.next_lde_step(1));
+
+    let mut frame = EvaluationFrame::new(2);
+    fragment.read_frame_into(1, &mut frame);
+    assert_eq!(
+        &[BaseElement::new(30), BaseElement::new(130)],
+        frame.current()
+    );
+    assert_eq!(&[BaseElement::new(2), BaseElement::new(102)], frame.next());
+
+    assert_eq!(&periodic_values[..2], fragment.periodic_values(0));
+    assert_eq!(&periodic_values[2..], fragment.periodic_values(1));
+
+    let g = BaseElement::get_root_of_unity(4);
+    let expected = get_power_series(g, 16)[14..]
+        .iter()
+        .map(|&x| x * BaseElement::GENERATOR)
+        .collect::<Vec<_>>();
+    assert_eq!(expected, fragment.x_values().collect::<Vec<_>>());
+}
+
+// CONSTRAINT DEGREES
+// ================================================================================================
+
+#[test]
+fn transition_constraint_degree_blowup() {
+    // without periodic columns, the exact blowup factor matches the estimate
+    let degree = TransitionConstraintDegree::new(4);
+    assert_eq!(4, degree.min_blowup_factor());
+    assert_eq!(4, degree.get_min_blowup_factor(64, 1));
+    assert_eq!(
+        2,
+        TransitionConstraintDegree::new(1).get_min_blowup_factor(64, 1)
+    );
+
+    // periodic columns with short cycles add less than a full trace column to the degree:
+    // 4 * 63 + 2 * 32 = 316, and 316 - 63 < 4 * 64
+    let degree = TransitionConstraintDegree::with_cycles(4, vec![2, 2]);
+    assert_eq!(8, degree.min_blowup_factor());
+    assert_eq!(4, degree.get_min_blowup_factor(64, 1));
+
+    // 316 - 61 is still smaller than 4 * 64, but 316 - 60 is not
+    assert_eq!(4, degree.get_min_blowup_factor(64, 3));
+    assert_eq!(8, degree.get_min_blowup_factor(64, 4));
+
+    // periodic columns with long cycles are almost as expensive as trace columns
+    let degree = TransitionConstraintDegree::with_cycles(3, vec![32]);
+    assert_eq!(4, degree.get_min_blowup_factor(64, 1));
+
+    // the constraint evaluation domain is derived from the exact degree, and is extended when
+    // more exemptions are needed
+    let options = ProofOptions::new(32, 8, 0, FieldExtension::None, 4, 256);
+    let trace_info = TraceInfo::new(4, 64);
+    let degrees = vec![TransitionConstraintDegree::with_cycles(4, vec![2, 2])];
+    let context = AirContext::<BaseElement>::new(trace_info, degrees, 1, options);
+    assert_eq!(4, context.ce_blowup_factor);
+    let context = context.set_num_transition_exemptions(8);
+    assert_eq!(8, context.ce_blowup_factor);
+    assert_eq!(8 * 64 - 1, context.composition_degree());
+}
+
+// DOMAIN VALIDATION
+// ================================================================================================
+
+#[test]
+fn validate_domains() {
+    let options = ProofOptions::new(32, 8, 0, FieldExtension::None, 4, 256);
+
+    // 2^37 * 8 = 2^40 is the largest domain supported by the f128 field
+    let trace_info = TraceInfo::new(4, 1 << 37);
+    assert_eq!(
+        Ok(()),
+        AirContext::<BaseElement>::validate_domains(&trace_info, &options)
+    );
+
+    let trace_info = TraceInfo::new(4, 1 << 38);
+    let err = AirContext::<BaseElement>::validate_domains(&trace_info, &options).unwrap_err();
+    assert_eq!(
+        DomainError::DomainSizeTooLarge(41, 40, core::any::type_name::<BaseElement>()),
+        err
+    );
+
+    // a custom domain offset is valid only if it is outside of the LDE domain
+    let trace_info = TraceInfo::new(4, 1 << 10);
+    let options2 = options.clone().with_domain_offset(3);
+    assert_eq!(
+        Ok(()),
+        AirContext::<BaseElement>::validate_domains(&trace_info, &options2)
+    );
+    let options2 = options.with_domain_offset(1);
+    assert_eq!(
+        Err(DomainError::InvalidDomainOffset(13)),
+        AirContext::<BaseElement>::validate_domains(&trace_info, &options2)
+    );
+}
+
+// VERIFIER PARAMETERS
+// ================================================================================================
+
+#[test]
+fn verifier_params() {
+    let column = vec![BaseElement::ONE; 8];
+    let air = MockAir::with_periodic_columns(vec![column], 16);
+    let params = VerifierParams::new::<_, Blake3_256<BaseElement>>(&air);
+
+    assert_eq!(BaseElement::get_modulus_le_bytes(), params.field_modulus);
+    assert_eq!(16, params.trace_length);
+    assert_eq!(4, params.main_trace_width);
+    assert_eq!(vec![8], params.periodic_column_lengths);
+    assert_eq!(air.ce_domain_size(), params.ce_domain_size);
+    assert_eq!(16 * 8, params.lde_domain_size);
+    assert_eq!(
+        air.lde_domain_generator().to_bytes(),
+        params.lde_domain_generator
+    );
+    assert_eq!(vec![2 * 15], params.main_constraint_degrees);
+    assert!(params.aux_constraint_degrees.is_empty());
+    assert_eq!(1, params.num_transition_coefficients);
+    assert_eq!(4, params.num_deep_trace_coefficients);
+    assert_eq!("Blake3_256<BaseElement>", params.hash_function);
+    assert_eq!(32, params.digest_size);
+
+    let json = params.to_json();
+    assert!(json.starts_with("{\n  \"field_modulus\": \""));
+    assert!(json.contains("\n  \"trace_length\": 16,\n"));
+    assert!(json.contains("\n  \"periodic_column_lengths\": [8],\n"));
+    assert!(json.contains("\n  \"aux_segment_widths\": [],\n"));
+    assert!(json.ends_with("\n  \"transcript_mode\": 0\n}\n"));
+}
+
 // BOUNDARY CONSTRAINTS
 // ================================================================================================
 
@@ -153,6 +153,14 @@ impl TraceInfo {
 /// The number of random elements may be different from the number of columns in a given auxiliary
 /// segment. For example, an auxiliary segment may contain just one column, but may require many
 /// random elements.
+///
+/// An auxiliary segment may also require no random elements at all. Columns of auxiliary segments
+/// are defined over the extension field used by the protocol, and thus, such a segment can be used
+/// to describe parts of an execution trace which are defined over the extension field (e.g., for
+/// computations whose witnesses are extension field elements). These columns are committed to,
+/// evaluated out of domain, and included in the DEEP composition polynomial just like any other
+/// auxiliary columns. The main segment must still contain at least one column; if all witness
+/// data lives in the extension field, this column can be left unconstrained.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct TraceLayout {
     main_segment_width: usize,
@@ -171,7 +179,6 @@ impl TraceLayout {
     /// * Width of the main trace segment is set to zero.
     /// * Sum of all segment widths exceeds 255.
     /// * A zero entry in auxiliary segment width array is followed by a non-zero entry.
-    /// * Number of random elements for an auxiliary trace segment of non-zero width is set to zero.
     /// * Number of random elements for an auxiliary trace segment of zero width is set to non-zero.
     /// * Number of random elements for any auxiliary trace segment is greater than 255.
     pub fn new(
@@ -201,10 +208,6 @@ impl TraceLayout {
                     !was_zero_width,
                     "a non-empty trace segment cannot follow an empty segment"
                 );
-                assert!(
-                    num_rand_elements > 0,
-                    "number of random elements for a non-empty trace segment must be greater than zero"
-                );
                 num_aux_segments += 1;
             } else {
                 assert!(
@@ -336,11 +339,6 @@ impl Deserializable for TraceLayout {
                 return Err(DeserializationError::InvalidValue(
                     "an empty trace segment cannot require random elements".to_string(),
                 ));
-            } else if width != 0 && *num_rand_elements == 0 {
-                return Err(DeserializationError::InvalidValue(
-                    "a non-empty trace segment must require at least one random element"
-                        .to_string(),
-                ));
             } else if *num_rand_elements > TraceInfo::MAX_RAND_SEGMENT_ELEMENTS {
                 return Err(DeserializationError::InvalidValue(format!(
                     "number of random elements required by a segment cannot exceed {}, but was {}",
@@ -15,6 +15,17 @@ use core::cmp;
 /// columns can be described as: `base: 2, cycles: []`. A constraint which requires
 /// multiplication of 3 trace columns and a periodic column with a period of 32 steps can be
 /// described as: `base: 3, cycles: [32]`.
+///
+/// Selectors are described in the same way: a selector defined by a trace column (e.g., a binary
+/// flag) contributes one to the base degree, while a selector defined by a periodic column (e.g.,
+/// a flag set on the last step of every 8-step cycle) should be listed among the cycles. A
+/// periodic column with a period of `c` steps is interpolated into a polynomial of degree
+/// `n / c * (c - 1)` over a trace of `n` steps; thus, periodic columns with short periods add much
+/// less to the constraint degree than trace columns do, and the degree of constraint composition
+/// is derived from the exact evaluation degree (see
+/// [get_min_blowup_factor()](TransitionConstraintDegree::get_min_blowup_factor)). Constraint
+/// degrees should not be padded to account for periodic multiplicands manually, as this may
+/// inflate the constraint evaluation domain unnecessarily.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct TransitionConstraintDegree {
     base: usize,
@@ -102,7 +113,29 @@ impl TransitionConstraintDegree {
         result
     }
 
-    /// Returns a minimum blowup factor needed to evaluate constraint of this degree.
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the degree of this descriptor with respect to trace columns.
+    pub fn base(&self) -> usize {
+        self.base
+    }
+
+    /// Returns cycle lengths of periodic columns involved in constraints of this degree.
+    pub fn cycles(&self) -> &[usize] {
+        &self.cycles
+    }
+
+    // DEGREE COMPUTATIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a minimum blowup factor needed to evaluate constraint of this degree over an
+    /// execution trace of any length.
+    ///
+    /// This estimate counts every periodic column as a full trace column, and thus, may be
+    /// greater than the blowup factor which is actually needed for a specific trace length; the
+    /// latter is returned by
+    /// [get_min_blowup_factor()](TransitionConstraintDegree::get_min_blowup_factor).
     ///
     /// This is guaranteed to be a power of two, greater than one.
     pub fn min_blowup_factor(&self) -> usize {
@@ -123,4 +156,36 @@ impl TransitionConstraintDegree {
             ProofOptions::MIN_BLOWUP_FACTOR,
         )
     }
+    /// Returns a minimum blowup factor needed to evaluate constraint of this degree over an
+    /// execution trace of the specified length when transition constraints do not need to hold
+    /// on the last `num_exemptions` steps of the trace.
+    ///
+    /// The constraint polynomial of evaluation degree $d$ (see
+    /// [get_evaluation_degree()](TransitionConstraintDegree::get_evaluation_degree)) is divided
+    /// by a divisor of degree $n - e$, where $n$ is the `trace_length` and $e$ is the number of
+    /// exemptions, and the quotient must be of degree smaller than $b \cdot n$ where $b$ is the
+    /// blowup factor. Thus, $b$ is the smallest power of two (but at least two) such that:
+    ///
+    /// $$
+    /// d - (n - e) < b \cdot n
+    /// $$
+    ///
+    /// The result is never greater than [min_blowup_factor()](Self::min_blowup_factor) for a
+    /// single exemption.
+    ///
+    /// # Panics
+    /// Panics if `trace_length` is not a power of two.
+    pub fn get_min_blowup_factor(&self, trace_length: usize, num_exemptions: usize) -> usize {
+        assert!(
+            trace_length.is_power_of_two(),
+            "trace length must be a power of two"
+        );
+        let quotient_degree = (self.get_evaluation_degree(trace_length) + num_exemptions)
+            .saturating_sub(trace_length);
+        let degree_bound = quotient_degree / trace_length + 1;
+        cmp::max(
+            degree_bound.next_power_of_two(),
+            ProofOptions::MIN_BLOWUP_FACTOR,
+        )
+    }
 }
@@ -0,0 +1,218 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{EvaluationFrame, Vec};
+use math::{log2, StarkField};
+
+// CONSTRAINT EVALUATOR
+// ================================================================================================
+
+/// Evaluates transition constraints of the main trace segment over batches of consecutive steps
+/// of the constraint evaluation domain.
+///
+/// By default, the prover evaluates transition constraints by invoking
+/// [Air::evaluate_transition()](crate::Air::evaluate_transition) once for every step of the
+/// constraint evaluation domain, copying the current and the next rows of the extended trace into
+/// an [EvaluationFrame] before each invocation. For AIRs with very hot transition functions (e.g.,
+/// hash functions), the overhead of these calls can be significant. Such AIRs can return an
+/// implementation of this trait from
+/// [Air::get_constraint_evaluator()](crate::Air::get_constraint_evaluator); the prover then
+/// passes entire fragments of the extended trace to the evaluator, which can read the trace
+/// columns directly and process many steps at once.
+///
+/// The evaluator must produce exactly the same values as
+/// [Air::evaluate_transition()](crate::Air::evaluate_transition) does for every step of the
+/// fragment; otherwise, the prover will generate invalid proofs. When debug assertions are
+/// enabled, the prover checks this for every step. Transition constraints of auxiliary trace
+/// segments and boundary constraints are always evaluated by the prover itself.
+pub trait ConstraintEvaluator<B: StarkField>: Send + Sync {
+    /// Evaluates transition constraints of the main trace segment at every step of the specified
+    /// fragment.
+    ///
+    /// Evaluations for the i-th step of the fragment must be written into
+    /// `result[i * n..(i + 1) * n]` where `n` is the number of main transition constraints, in the
+    /// same order as the evaluations written by
+    /// [Air::evaluate_transition()](crate::Air::evaluate_transition). When this method is
+    /// invoked, all values in `result` are set to zero.
+    fn evaluate_main_transitions(&self, fragment: &TransitionFragment<B>, result: &mut [B]);
+}
+
+// TRANSITION FRAGMENT
+// ================================================================================================
+
+/// A range of consecutive steps of the constraint evaluation domain together with the main
+/// segment of the extended execution trace over which constraints are evaluated.
+///
+/// Columns of the extended trace are defined over the low-degree extension (LDE) domain which may
+/// be larger than the constraint evaluation domain. Thus, the trace row at a given step of the
+/// fragment is located at [current_lde_step()](TransitionFragment::current_lde_step), and the next
+/// row of the evaluation frame for this step is located at
+/// [next_lde_step()](TransitionFragment::next_lde_step).
+#[derive(Debug, Clone)]
+pub struct TransitionFragment<'a, B: StarkField> {
+    columns: Vec<&'a [B]>,
+    offset: usize,
+    num_rows: usize,
+    ce_domain_size: usize,
+    lde_shift: u32,
+    frame_offset: usize,
+    domain_offset: B,
+    periodic_values: &'a [B],
+    num_periodic_columns: usize,
+}
+
+impl<'a, B: StarkField> TransitionFragment<'a, B> {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a fragment covering the entire constraint evaluation domain of a computation with
+    /// the specified extended trace columns.
+    ///
+    /// `ce_to_lde_blowup` is the ratio between the sizes of the LDE domain and the constraint
+    /// evaluation domain, and `trace_to_lde_blowup` is the ratio between the sizes of the LDE
+    /// domain and the trace domain. The domain offset is set to one, and the fragment does not
+    /// contain periodic values.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * No columns were provided or the columns do not all have the same length.
+    /// * Either of the blowup factors is not a power of two or is greater than the length of the
+    ///   columns.
+    pub fn new(columns: Vec<&'a [B]>, ce_to_lde_blowup: usize, trace_to_lde_blowup: usize) -> Self {
+        assert!(!columns.is_empty(), "at least one column must be provided");
+        let lde_domain_size = columns[0].len();
+        assert!(
+            columns.iter().all(|column| column.len() == lde_domain_size),
+            "all columns must have the same length"
+        );
+        assert!(
+            ce_to_lde_blowup.is_power_of_two() && ce_to_lde_blowup <= lde_domain_size,
+            "constraint evaluation to LDE blowup must be a power of two"
+        );
+        assert!(
+            trace_to_lde_blowup.is_power_of_two() && trace_to_lde_blowup <= lde_domain_size,
+            "trace to LDE blowup must be a power of two"
+        );
+
+        let ce_domain_size = lde_domain_size / ce_to_lde_blowup;
+        TransitionFragment {
+            columns,
+            offset: 0,
+            num_rows: ce_domain_size,
+            ce_domain_size,
+            lde_shift: ce_to_lde_blowup.trailing_zeros(),
+            frame_offset: trace_to_lde_blowup,
+            domain_offset: B::ONE,
+            periodic_values: &[],
+            num_periodic_columns: 0,
+        }
+    }
+
+    /// Returns this fragment with the domain offset set to the specified value.
+    pub fn with_domain_offset(mut self, domain_offset: B) -> Self {
+        self.domain_offset = domain_offset;
+        self
+    }
+
+    /// Returns this fragment with the specified periodic values.
+    ///
+    /// `values` must contain values of all periodic columns for a contiguous range of steps of the
+    /// constraint evaluation domain starting with step 0, with values for the same step adjacent
+    /// to each other; the values repeat with the period of `values.len() / num_columns` steps.
+    ///
+    /// # Panics
+    /// Panics if the length of `values` is not a multiple of `num_columns`.
+    pub fn with_periodic_values(mut self, values: &'a [B], num_columns: usize) -> Self {
+        assert!(
+            num_columns == 0 && values.is_empty()
+                || num_columns > 0 && values.len() % num_columns == 0,
+            "number of periodic values must be a multiple of the number of periodic columns"
+        );
+        self.periodic_values = values;
+        self.num_periodic_columns = num_columns;
+        self
+    }
+
+    /// Limits this fragment to `num_rows` consecutive steps of the constraint evaluation domain
+    /// starting with the step at the specified `offset`.
+    ///
+    /// # Panics
+    /// Panics if the range of steps extends beyond the end of the constraint evaluation domain.
+    pub fn set_rows(&mut self, offset: usize, num_rows: usize) {
+        assert!(
+            offset + num_rows <= self.ce_domain_size,
+            "fragment must be within the constraint evaluation domain"
+        );
+        self.offset = offset;
+        self.num_rows = num_rows;
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the step of the constraint evaluation domain at which this fragment starts.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the number of steps in this fragment.
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    /// Returns the number of columns in the main segment of the extended trace.
+    pub fn num_columns(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// Returns the column of the extended trace at the specified index; the column contains
+    /// values over the entire LDE domain.
+    pub fn column(&self, col_idx: usize) -> &'a [B] {
+        self.columns[col_idx]
+    }
+
+    /// Returns the position in the extended trace of the current row for the i-th step of this
+    /// fragment.
+    #[inline(always)]
+    pub fn current_lde_step(&self, row_idx: usize) -> usize {
+        (self.offset + row_idx) << self.lde_shift
+    }
+
+    /// Returns the position in the extended trace of the next row for the i-th step of this
+    /// fragment.
+    #[inline(always)]
+    pub fn next_lde_step(&self, row_idx: usize) -> usize {
+        let lde_domain_size = self.ce_domain_size << self.lde_shift;
+        (self.current_lde_step(row_idx) + self.frame_offset) % lde_domain_size
+    }
+
+    /// Copies the evaluation frame for the i-th step of this fragment into the specified frame.
+    pub fn read_frame_into(&self, row_idx: usize, frame: &mut EvaluationFrame<B>) {
+        let (current, next) = (self.current_lde_step(row_idx), self.next_lde_step(row_idx));
+        for (col_idx, column) in self.columns.iter().enumerate() {
+            frame.current_mut()[col_idx] = column[current];
+            frame.next_mut()[col_idx] = column[next];
+        }
+    }
+
+    /// Returns values of periodic columns for the i-th step of this fragment.
+    pub fn periodic_values(&self, row_idx: usize) -> &'a [B] {
+        if self.num_periodic_columns == 0 {
+            return &[];
+        }
+        let period = self.periodic_values.len() / self.num_periodic_columns;
+        let start = ((self.offset + row_idx) % period) * self.num_periodic_columns;
+        &self.periodic_values[start..start + self.num_periodic_columns]
+    }
+
+    /// Returns an iterator over the points of the constraint evaluation domain at the steps of
+    /// this fragment; the point at step i is s * g^i where g is the generator of the constraint
+    /// evaluation domain and s is the domain offset.
+    pub fn x_values(&self) -> impl Iterator<Item = B> {
+        let g = B::get_root_of_unity(log2(self.ce_domain_size));
+        let first = self.domain_offset * g.exp((self.offset as u64).into());
+        core::iter::successors(Some(first), move |&x| Some(x * g)).take(self.num_rows)
+    }
+}
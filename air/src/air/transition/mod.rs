@@ -4,6 +4,7 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{AirContext, BTreeMap, ConstraintDivisor, ExtensionOf, FieldElement, Vec};
+use math::batch_exp;
 
 mod frame;
 pub use frame::EvaluationFrame;
@@ -11,6 +12,9 @@ pub use frame::EvaluationFrame;
 mod degree;
 pub use degree::TransitionConstraintDegree;
 
+mod evaluator;
+pub use evaluator::{ConstraintEvaluator, TransitionFragment};
+
 // CONSTANTS
 // ================================================================================================
 
@@ -169,18 +173,34 @@ impl<E: FieldElement> TransitionConstraints<E> {
         F: FieldElement<BaseField = E::BaseField>,
         E: ExtensionOf<F>,
     {
+        // compute powers of x needed for degree adjustment of all groups at once
+        let degree_adjustments = self
+            .main_constraints()
+            .iter()
+            .chain(self.aux_constraints().iter())
+            .map(|group| group.degree_adjustment)
+            .collect::<Vec<_>>();
+        let xp = batch_exp(x, &degree_adjustments);
+        let (main_xp, aux_xp) = xp.split_at(self.main_constraints().len());
+
         // merge constraint evaluations for the main trace segment
-        let mut result = self.main_constraints().iter().fold(E::ZERO, |acc, group| {
-            let xp = x.exp(group.degree_adjustment.into());
-            acc + group.merge_evaluations::<F, F>(main_evaluations, xp)
-        });
+        let mut result = self
+            .main_constraints()
+            .iter()
+            .zip(main_xp.iter())
+            .fold(E::ZERO, |acc, (group, &xp)| {
+                acc + group.merge_evaluations::<F, F>(main_evaluations, xp)
+            });
 
         // merge constraint evaluations for auxiliary trace segments (if any)
         if self.num_aux_constraints() > 0 {
-            result += self.aux_constraints().iter().fold(E::ZERO, |acc, group| {
-                let xp = x.exp(group.degree_adjustment.into());
-                acc + group.merge_evaluations::<F, E>(aux_evaluations, xp)
-            });
+            result += self
+                .aux_constraints()
+                .iter()
+                .zip(aux_xp.iter())
+                .fold(E::ZERO, |acc, (group, &xp)| {
+                    acc + group.merge_evaluations::<F, E>(aux_evaluations, xp)
+                });
         }
 
         // divide out the evaluation of divisor at x and return the result
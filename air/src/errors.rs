@@ -3,7 +3,9 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use crate::FieldExtension;
 use core::fmt;
+use crypto::HashFunction;
 
 // ASSERTION ERROR
 // ================================================================================================
@@ -43,3 +45,238 @@ impl fmt::Display for AssertionError {
         }
     }
 }
+
+// TRACE ERROR
+// ================================================================================================
+/// Represents an error returned when an execution trace is built with invalid dimensions.
+#[derive(Debug, PartialEq, Eq)]
+pub enum TraceError {
+    /// This error occurs when the number of trace columns is zero or greater than 255.
+    InvalidTraceWidth(usize),
+    /// This error occurs when the trace length is smaller than the minimum allowed trace length.
+    /// The error contains the minimum and the actual trace length.
+    TraceLengthTooShort(usize, usize),
+    /// This error occurs when the trace length is not a power of two.
+    TraceLengthNotPowerOfTwo(usize),
+    /// This error occurs when the trace length exceeds the size of the largest multiplicative
+    /// subgroup of the base field with a power-of-two order. The error contains base 2 logarithms
+    /// of the maximum and the actual trace length.
+    TraceLengthTooLarge(u32, u32),
+    /// This error occurs when trace metadata is longer than allowed. The error contains the
+    /// maximum and the actual number of metadata bytes.
+    MetadataTooLong(usize, usize),
+    /// This error occurs when trace columns have different lengths. The error contains the
+    /// length of the first column and the length of the mismatched column.
+    InconsistentColumnLengths(usize, usize),
+}
+
+impl fmt::Display for TraceError {
+    #[rustfmt::skip]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidTraceWidth(width) => {
+                write!(f, "execution trace width must be between 1 and 255, but was {width}")
+            }
+            Self::TraceLengthTooShort(expected, actual) => {
+                write!(f, "execution trace must be at least {expected} steps long, but was {actual}")
+            }
+            Self::TraceLengthNotPowerOfTwo(length) => {
+                write!(f, "execution trace length must be a power of 2, but was {length}")
+            }
+            Self::TraceLengthTooLarge(max, actual) => {
+                write!(f, "execution trace length cannot exceed 2^{max} steps, but was 2^{actual}")
+            }
+            Self::MetadataTooLong(max, actual) => {
+                write!(f, "number of metadata bytes cannot be greater than {max}, but was {actual}")
+            }
+            Self::InconsistentColumnLengths(expected, actual) => {
+                write!(f, "all trace columns must have the same length; expected {expected}, but found a column of length {actual}")
+            }
+        }
+    }
+}
+
+// DOMAIN ERROR
+// ================================================================================================
+/// Represents an error returned when domains of a computation cannot be built in a given field.
+#[derive(Debug, PartialEq, Eq)]
+pub enum DomainError {
+    /// This error occurs when the size of a domain exceeds the size of the largest multiplicative
+    /// subgroup of the field with a power-of-two order. The error contains base 2 logarithm of
+    /// the domain size, the two-adicity of the field, and the name of the field type.
+    DomainSizeTooLarge(u32, u32, &'static str),
+    /// This error occurs when the domain offset is zero or is an element of the low-degree
+    /// extension domain, in which case the shifted domain would intersect the trace domain. The
+    /// error contains base 2 logarithm of the domain size.
+    InvalidDomainOffset(u32),
+}
+
+impl fmt::Display for DomainError {
+    #[rustfmt::skip]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DomainSizeTooLarge(log_size, two_adicity, field) => {
+                write!(f, "cannot build a domain of 2^{log_size} elements in field {field}; the field supports domains of at most 2^{two_adicity} elements")
+            }
+            Self::InvalidDomainOffset(log_size) => {
+                write!(f, "domain offset is invalid for a domain of 2^{log_size} elements; the offset must be a non-zero element outside of the domain")
+            }
+        }
+    }
+}
+
+// PROOF OPTIONS ERROR
+// ================================================================================================
+/// Represents an error returned when [ProofOptions](crate::ProofOptions) are built from an invalid
+/// combination of parameters.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ProofOptionsError {
+    /// This error occurs when the number of queries is zero or greater than 1024.
+    InvalidNumQueries(usize),
+    /// This error occurs when the blowup factor is not a power of two, or is outside of the
+    /// allowed range.
+    InvalidBlowupFactor(usize),
+    /// This error occurs when the grinding factor is greater than 32.
+    InvalidGrindingFactor(u32),
+    /// This error occurs when the FRI folding factor is not 4, 8, or 16.
+    InvalidFriFoldingFactor(usize),
+    /// This error occurs when the FRI max remainder size is not a power of two between 32 and
+    /// 1024.
+    InvalidFriMaxRemainderSize(usize),
+    /// This error occurs when the Merkle tree arity is not 2, 4, 8, or 16.
+    InvalidMerkleArity(usize),
+    /// This error occurs when the number of trace rows per Merkle tree leaf is not a power of
+    /// two or is greater than 16.
+    InvalidTraceRowsPerLeaf(usize),
+    /// This error occurs when the digest size is smaller than 16 or greater than 32 bytes.
+    InvalidDigestSize(usize),
+    /// This error occurs when the domain offset is zero.
+    InvalidDomainOffset(u64),
+    /// This error occurs when the blowup factor is too small to evaluate constraints of the
+    /// specified degree. The error contains the minimum and the actual blowup factor.
+    BlowupFactorTooSmall(usize, usize),
+    /// This error occurs when the number of queries is greater than the size of the low-degree
+    /// extension domain. The error contains the domain size and the number of queries.
+    TooManyQueries(usize, usize),
+}
+
+impl fmt::Display for ProofOptionsError {
+    #[rustfmt::skip]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidNumQueries(value) => {
+                write!(f, "number of queries must be between 1 and 1024, but was {value}")
+            }
+            Self::InvalidBlowupFactor(value) => {
+                write!(f, "blowup factor must be a power of two between 2 and 128, but was {value}")
+            }
+            Self::InvalidGrindingFactor(value) => {
+                write!(f, "grinding factor cannot be greater than 32, but was {value}")
+            }
+            Self::InvalidFriFoldingFactor(value) => {
+                write!(f, "FRI folding factor must be 4, 8, or 16, but was {value}")
+            }
+            Self::InvalidFriMaxRemainderSize(value) => {
+                write!(f, "FRI max remainder size must be a power of two between 32 and 1024, but was {value}")
+            }
+            Self::InvalidMerkleArity(value) => {
+                write!(f, "Merkle tree arity must be 2, 4, 8, or 16, but was {value}")
+            }
+            Self::InvalidTraceRowsPerLeaf(value) => {
+                write!(f, "number of trace rows per leaf must be a power of two smaller than or equal to 16, but was {value}")
+            }
+            Self::InvalidDigestSize(value) => {
+                write!(f, "digest size must be between 16 and 32 bytes, but was {value}")
+            }
+            Self::InvalidDomainOffset(value) => {
+                write!(f, "domain offset cannot be zero, but was {value}")
+            }
+            Self::BlowupFactorTooSmall(expected, actual) => {
+                write!(f, "blowup factor too small for constraint degree; expected at least {expected}, but was {actual}")
+            }
+            Self::TooManyQueries(domain_size, num_queries) => {
+                write!(f, "number of queries cannot exceed the size of the LDE domain ({domain_size}), but was {num_queries}")
+            }
+        }
+    }
+}
+
+// POLICY ERROR
+// ================================================================================================
+/// Represents an error returned when a proof does not satisfy a
+/// [VerifierPolicy](crate::proof::VerifierPolicy).
+#[derive(Debug, PartialEq, Eq)]
+pub enum PolicyError {
+    /// This error occurs when the hash function used to check the policy is not the hash function
+    /// recorded in the proof. The error contains the recorded and the specified hash functions.
+    HashFunctionMismatch(HashFunction, HashFunction),
+    /// This error occurs when the proof was generated with a hash function which is not allowed
+    /// by the policy.
+    HashFunctionNotAllowed(HashFunction),
+    /// This error occurs when the proof was generated in a base field which is not allowed by the
+    /// policy.
+    FieldNotAllowed,
+    /// This error occurs when the proof was generated with a field extension which is not allowed
+    /// by the policy.
+    FieldExtensionNotAllowed(FieldExtension),
+    /// This error occurs when the conjectured security level of the proof is lower than the
+    /// minimum required by the policy. The error contains the required and the actual level.
+    InsufficientConjecturedSecurity(u32, u32),
+    /// This error occurs when the proven security level of the proof is lower than the minimum
+    /// required by the policy. The error contains the required and the actual level.
+    InsufficientProvenSecurity(u32, u32),
+}
+
+impl fmt::Display for PolicyError {
+    #[rustfmt::skip]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::HashFunctionMismatch(recorded, specified) => {
+                write!(f, "proof was generated with hash function {recorded:?}, but the policy was checked with {specified:?}")
+            }
+            Self::HashFunctionNotAllowed(hash_function) => {
+                write!(f, "hash function {hash_function:?} is not allowed by the policy")
+            }
+            Self::FieldNotAllowed => {
+                write!(f, "base field of the proof is not allowed by the policy")
+            }
+            Self::FieldExtensionNotAllowed(extension) => {
+                write!(f, "field extension {extension:?} is not allowed by the policy")
+            }
+            Self::InsufficientConjecturedSecurity(required, actual) => {
+                write!(f, "policy requires {required} bits of conjectured security, but the proof provides {actual} bits")
+            }
+            Self::InsufficientProvenSecurity(required, actual) => {
+                write!(f, "policy requires {required} bits of proven security, but the proof provides {actual} bits")
+            }
+        }
+    }
+}
+
+// TUNING ERROR
+// ================================================================================================
+/// Represents an error returned when an [OptionsTuner](crate::proof::OptionsTuner) cannot find
+/// proof options for a computation.
+#[derive(Debug, PartialEq, Eq)]
+pub enum TuningError {
+    /// This error occurs when the constraint evaluation blowup factor of the computation is
+    /// greater than the largest allowed blowup factor of 128.
+    ConstraintDegreeTooHigh(usize),
+    /// This error occurs when none of the considered options achieve the target security level.
+    /// The error contains the target and the highest achievable level.
+    UnattainableSecurity(u32, u32),
+}
+
+impl fmt::Display for TuningError {
+    #[rustfmt::skip]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ConstraintDegreeTooHigh(value) => {
+                write!(f, "constraint evaluation blowup factor cannot be greater than 128, but was {value}")
+            }
+            Self::UnattainableSecurity(target, max) => {
+                write!(f, "target security level of {target} bits cannot be achieved; at most {max} bits can be achieved")
+            }
+        }
+    }
+}
@@ -36,15 +36,18 @@ extern crate alloc;
 pub mod proof;
 
 mod errors;
-pub use errors::AssertionError;
+pub use errors::{
+    AssertionError, DomainError, PolicyError, ProofOptionsError, TraceError, TuningError,
+};
 
 mod options;
-pub use options::{FieldExtension, ProofOptions};
+pub use options::{FieldExtension, ProofOptions, ProofOptionsBuilder};
 
 mod air;
 pub use air::{
     Air, AirContext, Assertion, AuxTraceRandElements, BoundaryConstraint, BoundaryConstraintGroup,
-    BoundaryConstraints, ConstraintCompositionCoefficients, ConstraintDivisor,
-    DeepCompositionCoefficients, EvaluationFrame, TraceInfo, TraceLayout,
+    BoundaryConstraints, ConstraintCompositionCoefficients, ConstraintDivisor, ConstraintEvaluator,
+    DeepCompositionCoefficients, EvaluationFrame, RationalFunction, TraceInfo, TraceLayout,
     TransitionConstraintDegree, TransitionConstraintGroup, TransitionConstraints,
+    TransitionFragment, VerifierParams,
 };
@@ -3,9 +3,14 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use crate::ProofOptionsError;
+use core::cmp;
+use crypto::{TranscriptMode, MERKLE_TREE_ARITIES};
 use fri::FriOptions;
-use math::StarkField;
-use utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+use math::{log2, StarkField};
+use utils::{
+    string::ToString, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+};
 
 // TYPES AND INTERFACES
 // ================================================================================================
@@ -47,10 +52,31 @@ pub enum FieldExtension {
 /// 3. Blowup factor - higher values increase proof soundness, but also increase proof generation
 ///    time and proof size. However, higher blowup factors require fewer queries for the same
 ///    security level. Thus, it is frequently possible to increase blowup factor and at the same
-///    time decrease the number of queries in such a way that the proofs become smaller.
+///    time decrease the number of queries in such a way that the proofs become smaller. The
+///    blowup factor can also be selected automatically from degrees of constraints of a
+///    computation (see [with_auto_blowup_factor()](ProofOptions::with_auto_blowup_factor)).
 /// 4. Grinding factor - higher values increase proof soundness, but also may increase proof
 ///    generation time. More precisely, proof soundness is bounded by
 ///    `num_queries * log2(blowup_factor) + grinding_factor`.
+/// 5. Merkle tree arity - the arity of Merkle trees used to commit to trace, constraint, and FRI
+///    layer evaluations. Binary trees are used by default; higher arities result in fewer hash
+///    invocations per authentication path, but usually also in larger proofs.
+///    Additionally, several adjacent rows of the extended execution trace can be committed to in
+///    a single leaf of the trace commitment Merkle tree. Each query then opens a larger leaf, but
+///    the trees are shallower, and queries at adjacent positions share a leaf; for wide traces,
+///    this reduces the size of trace queries and the amount of hashing done by the verifier.
+/// 6. Transcript mode - defines how the prover's messages are absorbed into the public coin
+///    from which the verifier's challenges are drawn. This does not affect proof soundness, but
+///    a sponge-based transcript is cheaper to verify recursively.
+/// 7. Digest size - the number of bytes in digests of the hash function used by the protocol
+///    (e.g., when digests are truncated via [Truncated](crypto::hashers::Truncated)). Smaller
+///    digests result in shorter Merkle authentication paths, but collision resistance of the
+///    hash function, and thus proof soundness, is bounded by `digest_size * 4` bits. When
+///    specified, the prover and the verifier make sure that the hash function they are
+///    instantiated with produces digests of this size.
+/// 8. Domain offset - the element by which the low-degree extension domain is shifted. This does
+///    not affect proof soundness, but proofs generated with different offsets are distinct, which
+///    can be used for domain separation. By default, the primitive element of the field is used.
 ///
 /// Another important parameter in defining STARK security level, which is not a part of [ProofOptions]
 /// is the hash function used in the protocol. The soundness of a STARK proof is limited by the
@@ -58,12 +84,18 @@ pub enum FieldExtension {
 /// with 128-bit collision resistance is used, soundness of a STARK proof cannot exceed 128 bits.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct ProofOptions {
-    num_queries: u8,
+    num_queries: u16,
     blowup_factor: u8,
+    auto_blowup_factor: bool,
     grinding_factor: u8,
     field_extension: FieldExtension,
     fri_folding_factor: u8,
     fri_max_remainder_size: u8, // stored as power of 2
+    merkle_arity: u8,
+    trace_rows_per_leaf: u8,
+    transcript_mode: TranscriptMode,
+    digest_size: u8,    // 0 if not specified
+    domain_offset: u64, // 0 if not specified
 }
 
 // PROOF OPTIONS IMPLEMENTATION
@@ -79,13 +111,25 @@ impl ProofOptions {
     /// have a blowup factor smaller than 2.
     pub const MIN_BLOWUP_FACTOR: usize = 2;
 
+    /// Smallest digest size (in bytes) which can be specified in proof options.
+    pub const MIN_DIGEST_SIZE: usize = 16;
+
+    /// Largest digest size (in bytes) which can be specified in proof options.
+    pub const MAX_DIGEST_SIZE: usize = 32;
+
+    /// Largest number of queries which can be specified in proof options.
+    pub const MAX_NUM_QUERIES: usize = 1024;
+
+    /// Largest number of trace rows which can be committed to in a single Merkle tree leaf.
+    pub const MAX_TRACE_ROWS_PER_LEAF: usize = 16;
+
     // CONSTRUCTORS
     // --------------------------------------------------------------------------------------------
     /// Returns a new instance of [ProofOptions] struct constructed from the specified parameters.
     ///
     /// # Panics
     /// Panics if:
-    /// * `num_queries` is zero or greater than 128.
+    /// * `num_queries` is zero or greater than 1024.
     /// * `blowup_factor` is smaller than 4, greater than 256, or is not a power of two.
     /// * `grinding_factor` is greater than 32.
     /// * `fri_folding_factor` is not 4, 8, or 16.
@@ -99,9 +143,9 @@ impl ProofOptions {
         fri_folding_factor: usize,
         fri_max_remainder_size: usize,
     ) -> ProofOptions {
-        // TODO: return errors instead of panicking
         assert!(num_queries > 0, "number of queries must be greater than 0");
-        assert!(num_queries <= 128, "number of queries cannot be greater than 128");
+        assert!(num_queries <= Self::MAX_NUM_QUERIES,
+            "number of queries cannot be greater than {}", Self::MAX_NUM_QUERIES);
 
         assert!(blowup_factor.is_power_of_two(), "blowup factor must be a power of 2");
         assert!(blowup_factor >= Self::MIN_BLOWUP_FACTOR,
@@ -119,15 +163,186 @@ impl ProofOptions {
         assert!(fri_max_remainder_size <= 1024, "FRI max remainder size cannot be greater than 1024");
 
         ProofOptions {
-            num_queries: num_queries as u8,
+            num_queries: num_queries as u16,
             blowup_factor: blowup_factor as u8,
+            auto_blowup_factor: false,
             grinding_factor: grinding_factor as u8,
             field_extension,
             fri_folding_factor: fri_folding_factor as u8,
             fri_max_remainder_size: fri_max_remainder_size.trailing_zeros() as u8,
+            merkle_arity: 2,
+            trace_rows_per_leaf: 1,
+            transcript_mode: TranscriptMode::HashChain,
+            digest_size: 0,
+            domain_offset: 0,
         }
     }
 
+    /// Returns a new instance of [ProofOptions] struct constructed from the specified parameters.
+    ///
+    /// This is a fallible version of [ProofOptions::new()] which can be used to build options
+    /// from untrusted input; other parameters can be validated in the same way via
+    /// [builder()](ProofOptions::builder).
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * `num_queries` is zero or greater than 1024.
+    /// * `blowup_factor` is smaller than 2, greater than 128, or is not a power of two.
+    /// * `grinding_factor` is greater than 32.
+    /// * `fri_folding_factor` is not 4, 8, or 16.
+    /// * `fri_max_remainder_size` is smaller than 32, greater than 1024, or is not a power of two.
+    pub fn try_new(
+        num_queries: usize,
+        blowup_factor: usize,
+        grinding_factor: u32,
+        field_extension: FieldExtension,
+        fri_folding_factor: usize,
+        fri_max_remainder_size: usize,
+    ) -> Result<ProofOptions, ProofOptionsError> {
+        ProofOptionsBuilder::new()
+            .num_queries(num_queries)
+            .blowup_factor(blowup_factor)
+            .grinding_factor(grinding_factor)
+            .field_extension(field_extension)
+            .fri_folding_factor(fri_folding_factor)
+            .fri_max_remainder_size(fri_max_remainder_size)
+            .build()
+    }
+
+    /// Returns a new [ProofOptionsBuilder] which can be used to build [ProofOptions] from named
+    /// parameters with all parameter combinations validated.
+    pub fn builder() -> ProofOptionsBuilder {
+        ProofOptionsBuilder::new()
+    }
+
+    // PRESETS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns proof options which provide 96 bits of conjectured security for computations over
+    /// a ~64-bit base field (e.g., [f64](math::fields::f64)).
+    ///
+    /// The options use 27 queries, blowup factor of 8, grinding factor of 16, quadratic field
+    /// extension, and FRI folding factor of 8. Computations with constraints of degree greater
+    /// than 9 need a larger blowup factor; options for such computations can be built via
+    /// [builder()](ProofOptions::builder).
+    pub fn recommended_96_bit() -> ProofOptions {
+        ProofOptions::new(27, 8, 16, FieldExtension::Quadratic, 8, 256)
+    }
+
+    /// Returns proof options which provide 128 bits of conjectured security for computations over
+    /// a ~64-bit base field (e.g., [f64](math::fields::f64)).
+    ///
+    /// The options use 38 queries, blowup factor of 8, grinding factor of 16, cubic field
+    /// extension, and FRI folding factor of 8. Security of proofs generated with these options is
+    /// also bounded by the collision resistance of the hash function, and thus, a hash function
+    /// with at least 128-bit collision resistance must be used.
+    pub fn recommended_128_bit() -> ProofOptions {
+        ProofOptions::new(38, 8, 16, FieldExtension::Cubic, 8, 256)
+    }
+
+    /// Returns proof options which make proof generation and verification as fast as possible.
+    ///
+    /// The options use 8 queries, blowup factor of 8, no grinding, no field extension, and FRI
+    /// folding factor of 8. Proofs generated with these options provide negligible security, and
+    /// thus, these options must be used only in tests.
+    pub fn fast_testing() -> ProofOptions {
+        ProofOptions::new(8, 8, 0, FieldExtension::None, 8, 256)
+    }
+
+    /// Returns a new [ProofOptions] struct with the arity of Merkle trees set to the specified
+    /// value; by default, binary Merkle trees are used.
+    ///
+    /// # Panics
+    /// Panics if `merkle_arity` is not 2, 4, 8, or 16.
+    pub fn with_merkle_arity(mut self, merkle_arity: usize) -> ProofOptions {
+        assert!(
+            MERKLE_TREE_ARITIES.contains(&merkle_arity),
+            "Merkle tree arity must be 2, 4, 8, or 16, but was {merkle_arity}"
+        );
+        self.merkle_arity = merkle_arity as u8;
+        self
+    }
+
+    /// Returns a new [ProofOptions] struct in which each leaf of trace commitment Merkle trees
+    /// contains the specified number of adjacent rows of the extended execution trace; by
+    /// default, each leaf contains a single row.
+    ///
+    /// Leaf `i` contains rows `i * trace_rows_per_leaf..(i + 1) * trace_rows_per_leaf` of a
+    /// trace segment, and a query for a given row opens the entire leaf containing it.
+    ///
+    /// # Panics
+    /// Panics if `trace_rows_per_leaf` is not a power of two or is greater than 16.
+    pub fn with_trace_rows_per_leaf(mut self, trace_rows_per_leaf: usize) -> ProofOptions {
+        assert!(
+            trace_rows_per_leaf.is_power_of_two()
+                && trace_rows_per_leaf <= Self::MAX_TRACE_ROWS_PER_LEAF,
+            "number of trace rows per leaf must be a power of two smaller than or equal to {}, but was {trace_rows_per_leaf}",
+            Self::MAX_TRACE_ROWS_PER_LEAF
+        );
+        self.trace_rows_per_leaf = trace_rows_per_leaf as u8;
+        self
+    }
+
+    /// Returns a new [ProofOptions] struct with the blowup factor selected automatically for every
+    /// computation.
+    ///
+    /// The blowup factor of these options becomes the reference for the security level: when an
+    /// [AirContext](crate::AirContext) is built for a computation, the blowup factor is set to the
+    /// smallest value which accommodates degrees of the computation's transition constraints, and
+    /// if this value is smaller than the reference blowup factor, the number of queries is
+    /// increased so that `num_queries * log2(blowup_factor)` does not drop below its reference
+    /// value (up to the maximum of 1024 queries). The selected values are recorded in the context
+    /// of the generated proof, and thus, the verifier does not need to repeat the selection.
+    ///
+    /// Since most computations have constraints of degree 2 or 3, this frequently halves the size
+    /// of the low-degree extension domain (and thus, prover time) compared to a manually chosen
+    /// blowup factor of 8, at the cost of larger proofs.
+    pub fn with_auto_blowup_factor(mut self) -> ProofOptions {
+        self.auto_blowup_factor = true;
+        self
+    }
+
+    /// Returns a new [ProofOptions] struct with the transcript mode of the public coin set to the
+    /// specified value; by default, [TranscriptMode::HashChain] is used.
+    pub fn with_transcript_mode(mut self, transcript_mode: TranscriptMode) -> ProofOptions {
+        self.transcript_mode = transcript_mode;
+        self
+    }
+
+    /// Returns a new [ProofOptions] struct with the size of hash function digests set to the
+    /// specified number of bytes; by default, the digest size is not specified.
+    ///
+    /// # Panics
+    /// Panics if `digest_size` is smaller than 16 or greater than 32.
+    pub fn with_digest_size(mut self, digest_size: usize) -> ProofOptions {
+        assert!(
+            (Self::MIN_DIGEST_SIZE..=Self::MAX_DIGEST_SIZE).contains(&digest_size),
+            "digest size must be between {} and {} bytes, but was {digest_size}",
+            Self::MIN_DIGEST_SIZE,
+            Self::MAX_DIGEST_SIZE
+        );
+        self.digest_size = digest_size as u8;
+        self
+    }
+
+    /// Returns a new [ProofOptions] struct with the offset of the low-degree extension domain set
+    /// to the field element with the specified integer value; by default, the primitive element
+    /// of the base field is used.
+    ///
+    /// Proofs for the same computation generated with different domain offsets are different,
+    /// which can be used for domain separation between proofs. The offset must not be in the
+    /// low-degree extension domain itself; this is checked by the prover and the verifier via
+    /// [AirContext::validate_domains()](crate::AirContext::validate_domains) once the base field
+    /// and the size of the domain are known.
+    ///
+    /// # Panics
+    /// Panics if `domain_offset` is zero.
+    pub fn with_domain_offset(mut self, domain_offset: u64) -> ProofOptions {
+        assert_ne!(domain_offset, 0, "domain offset cannot be zero");
+        self.domain_offset = domain_offset;
+        self
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -150,6 +365,15 @@ impl ProofOptions {
         self.blowup_factor as usize
     }
 
+    /// Returns true if the blowup factor is selected automatically for every computation.
+    ///
+    /// For such options, [blowup_factor()](ProofOptions::blowup_factor) returns the reference
+    /// blowup factor until the options are resolved via
+    /// [resolve_blowup_factor()](ProofOptions::resolve_blowup_factor).
+    pub fn is_blowup_factor_auto(&self) -> bool {
+        self.auto_blowup_factor
+    }
+
     /// Returns query seed grinding factor for a STARK proof.
     ///
     /// Grinding applies Proof-of-Work/ to the query position seed. An honest prover needs to
@@ -170,31 +394,122 @@ impl ProofOptions {
         self.field_extension
     }
 
+    /// Returns the arity of Merkle trees used to commit to trace, constraint, and FRI layer
+    /// evaluations.
+    pub fn merkle_arity(&self) -> usize {
+        self.merkle_arity as usize
+    }
+
+    /// Returns the number of adjacent rows of the extended execution trace committed to in a
+    /// single leaf of trace commitment Merkle trees.
+    pub fn trace_rows_per_leaf(&self) -> usize {
+        self.trace_rows_per_leaf as usize
+    }
+
+    /// Returns the mode in which the public coin absorbs prover's messages.
+    pub fn transcript_mode(&self) -> TranscriptMode {
+        self.transcript_mode
+    }
+
+    /// Returns the size (in bytes) of hash function digests used by the protocol, or None if the
+    /// digest size was not specified.
+    pub fn digest_size(&self) -> Option<usize> {
+        match self.digest_size {
+            0 => None,
+            size => Some(size as usize),
+        }
+    }
+
     /// Returns the offset by which the low-degree extension domain is shifted in relation to the
     /// trace domain.
     ///
-    /// Currently, this is hard-coded to the primitive element of the underlying base field.
+    /// Unless specified via [with_domain_offset()](ProofOptions::with_domain_offset), this is the
+    /// primitive element of the underlying base field.
     pub fn domain_offset<B: StarkField>(&self) -> B {
-        B::GENERATOR
+        match self.domain_offset {
+            0 => B::GENERATOR,
+            offset => B::from(offset),
+        }
+    }
+
+    /// Returns options with the blowup factor selected for a computation which requires a blowup
+    /// factor of at least `min_blowup_factor`; if the blowup factor of these options is not
+    /// selected automatically, a copy of these options is returned.
+    ///
+    /// The blowup factor is set to `min_blowup_factor` (but not less than 2), and the number of
+    /// queries is adjusted as described in
+    /// [with_auto_blowup_factor()](ProofOptions::with_auto_blowup_factor).
+    ///
+    /// # Panics
+    /// Panics if the blowup factor is selected automatically and `min_blowup_factor` is not a
+    /// power of two or is greater than 128.
+    pub fn resolve_blowup_factor(&self, min_blowup_factor: usize) -> ProofOptions {
+        let mut options = self.clone();
+        if !self.auto_blowup_factor {
+            return options;
+        }
+
+        let blowup_factor = cmp::max(min_blowup_factor, Self::MIN_BLOWUP_FACTOR);
+        assert!(
+            blowup_factor.is_power_of_two(),
+            "blowup factor must be a power of 2"
+        );
+        assert!(
+            blowup_factor <= 128,
+            "blowup factor cannot be greater than 128, but computation requires {blowup_factor}"
+        );
+
+        // a smaller blowup factor provides fewer bits of security per query; thus, to preserve
+        // the security level, queries are added to make up for the difference
+        if blowup_factor < self.blowup_factor() {
+            let query_security = self.num_queries() * log2(self.blowup_factor()) as usize;
+            let security_per_query = log2(blowup_factor) as usize;
+            let num_queries = (query_security + security_per_query - 1) / security_per_query;
+            options.num_queries = cmp::min(num_queries, Self::MAX_NUM_QUERIES) as u16;
+        }
+        options.blowup_factor = blowup_factor as u8;
+        options.auto_blowup_factor = false;
+        options
     }
 
     /// Returns options for FRI protocol instantiated with parameters from this proof options.
     pub fn to_fri_options(&self) -> FriOptions {
         let folding_factor = self.fri_folding_factor as usize;
         let max_remainder_size = 2usize.pow(self.fri_max_remainder_size as u32);
-        FriOptions::new(self.blowup_factor(), folding_factor, max_remainder_size)
+        let options = FriOptions::new(self.blowup_factor(), folding_factor, max_remainder_size)
+            .with_merkle_arity(self.merkle_arity());
+        match self.domain_offset {
+            0 => options,
+            offset => options.with_domain_offset(offset),
+        }
     }
 }
 
 impl Serializable for ProofOptions {
     /// Serializes `self` and writes the resulting bytes into the `target`.
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
-        target.write_u8(self.num_queries);
+        target.write_usize(self.num_queries as usize);
+        // an automatically selected blowup factor is written as 0 followed by the reference
+        // blowup factor
+        if self.auto_blowup_factor {
+            target.write_u8(0);
+        }
         target.write_u8(self.blowup_factor);
         target.write_u8(self.grinding_factor);
         target.write(self.field_extension);
         target.write_u8(self.fri_folding_factor);
         target.write_u8(self.fri_max_remainder_size);
+        target.write_u8(self.merkle_arity);
+        target.write_u8(self.trace_rows_per_leaf);
+        target.write(self.transcript_mode);
+        target.write_u8(self.digest_size);
+        // the domain offset is written only if it was specified
+        if self.domain_offset == 0 {
+            target.write_u8(0);
+        } else {
+            target.write_u8(1);
+            target.write_u64(self.domain_offset);
+        }
     }
 }
 
@@ -204,14 +519,332 @@ impl Deserializable for ProofOptions {
     /// # Errors
     /// Returns an error of a valid proof options could not be read from the specified `source`.
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
-        Ok(ProofOptions::new(
-            source.read_u8()? as usize,
-            source.read_u8()? as usize,
-            source.read_u8()? as u32,
-            FieldExtension::read_from(source)?,
-            source.read_u8()? as usize,
-            2usize.pow(source.read_u8()? as u32),
-        ))
+        // all parameters are validated via the builder so that malformed options result in an
+        // error rather than a panic
+        let mut builder = ProofOptions::builder().num_queries(source.read_usize()?);
+        builder = match source.read_u8()? {
+            0 => builder
+                .blowup_factor(source.read_u8()? as usize)
+                .auto_blowup_factor(),
+            blowup_factor => builder.blowup_factor(blowup_factor as usize),
+        };
+        builder = builder
+            .grinding_factor(source.read_u8()? as u32)
+            .field_extension(FieldExtension::read_from(source)?)
+            .fri_folding_factor(source.read_u8()? as usize);
+
+        let fri_max_remainder_size = source.read_u8()? as u32;
+        if fri_max_remainder_size >= usize::BITS {
+            return Err(DeserializationError::InvalidValue(format!(
+                "FRI max remainder size cannot be 2^{fri_max_remainder_size}"
+            )));
+        }
+        builder = builder
+            .fri_max_remainder_size(1 << fri_max_remainder_size)
+            .merkle_arity(source.read_u8()? as usize)
+            .trace_rows_per_leaf(source.read_u8()? as usize)
+            .transcript_mode(TranscriptMode::read_from(source)?);
+
+        let digest_size = source.read_u8()? as usize;
+        if digest_size != 0 {
+            builder = builder.digest_size(digest_size);
+        }
+
+        match source.read_u8()? {
+            0 => (),
+            1 => builder = builder.domain_offset(source.read_u64()?),
+            value => {
+                return Err(DeserializationError::InvalidValue(format!(
+                    "domain offset flag must be 0 or 1, but was {value}"
+                )))
+            }
+        }
+
+        builder
+            .build()
+            .map_err(|err| DeserializationError::InvalidValue(err.to_string()))
+    }
+}
+
+// PROOF OPTIONS BUILDER
+// ================================================================================================
+
+/// A builder of [ProofOptions] with named setters.
+///
+/// Unlike [ProofOptions::new()], which panics on invalid parameters, [build()](Self::build)
+/// returns a [ProofOptionsError] describing the problem. Parameters which are not set explicitly
+/// take the following default values:
+///
+/// | parameter              | default value         |
+/// | ---------------------- | --------------------- |
+/// | num_queries            | 32                    |
+/// | blowup_factor          | 8                     |
+/// | auto_blowup_factor     | false                 |
+/// | grinding_factor        | 0                     |
+/// | field_extension        | [FieldExtension::None] |
+/// | fri_folding_factor     | 4                     |
+/// | fri_max_remainder_size | 256                   |
+/// | merkle_arity           | 2                     |
+/// | trace_rows_per_leaf    | 1                     |
+/// | transcript_mode        | [TranscriptMode::HashChain] |
+/// | digest_size            | not specified         |
+/// | domain_offset          | field generator       |
+///
+/// Additionally, the degree of the highest-degree constraint and the length of the execution
+/// trace can be provided to validate the options against a specific computation.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ProofOptionsBuilder {
+    num_queries: usize,
+    blowup_factor: usize,
+    auto_blowup_factor: bool,
+    grinding_factor: u32,
+    field_extension: FieldExtension,
+    fri_folding_factor: usize,
+    fri_max_remainder_size: usize,
+    merkle_arity: usize,
+    trace_rows_per_leaf: usize,
+    transcript_mode: TranscriptMode,
+    digest_size: Option<usize>,
+    domain_offset: Option<u64>,
+    max_constraint_degree: Option<usize>,
+    trace_length: Option<usize>,
+}
+
+impl ProofOptionsBuilder {
+    /// Returns a new builder with all parameters set to their default values.
+    pub fn new() -> Self {
+        Self {
+            num_queries: 32,
+            blowup_factor: 8,
+            auto_blowup_factor: false,
+            grinding_factor: 0,
+            field_extension: FieldExtension::None,
+            fri_folding_factor: 4,
+            fri_max_remainder_size: 256,
+            merkle_arity: 2,
+            trace_rows_per_leaf: 1,
+            transcript_mode: TranscriptMode::HashChain,
+            digest_size: None,
+            domain_offset: None,
+            max_constraint_degree: None,
+            trace_length: None,
+        }
+    }
+
+    /// Sets the number of queries.
+    pub fn num_queries(mut self, num_queries: usize) -> Self {
+        self.num_queries = num_queries;
+        self
+    }
+
+    /// Sets the blowup factor.
+    pub fn blowup_factor(mut self, blowup_factor: usize) -> Self {
+        self.blowup_factor = blowup_factor;
+        self
+    }
+
+    /// Specifies that the blowup factor is to be selected automatically from degrees of
+    /// constraints of a computation; the blowup factor set via
+    /// [blowup_factor()](Self::blowup_factor) is then used as the reference for the security
+    /// level (see [ProofOptions::with_auto_blowup_factor()]).
+    pub fn auto_blowup_factor(mut self) -> Self {
+        self.auto_blowup_factor = true;
+        self
+    }
+
+    /// Sets the query seed grinding factor.
+    pub fn grinding_factor(mut self, grinding_factor: u32) -> Self {
+        self.grinding_factor = grinding_factor;
+        self
+    }
+
+    /// Sets the field extension used for the composition polynomial.
+    pub fn field_extension(mut self, field_extension: FieldExtension) -> Self {
+        self.field_extension = field_extension;
+        self
+    }
+
+    /// Sets the factor by which the degree of a polynomial is reduced in each FRI layer.
+    pub fn fri_folding_factor(mut self, fri_folding_factor: usize) -> Self {
+        self.fri_folding_factor = fri_folding_factor;
+        self
+    }
+
+    /// Sets the maximum size of the last FRI layer.
+    pub fn fri_max_remainder_size(mut self, fri_max_remainder_size: usize) -> Self {
+        self.fri_max_remainder_size = fri_max_remainder_size;
+        self
+    }
+
+    /// Sets the arity of Merkle trees used to commit to trace, constraint, and FRI layer
+    /// evaluations.
+    pub fn merkle_arity(mut self, merkle_arity: usize) -> Self {
+        self.merkle_arity = merkle_arity;
+        self
+    }
+
+    /// Sets the number of adjacent trace rows committed to in a single leaf of trace commitment
+    /// Merkle trees.
+    pub fn trace_rows_per_leaf(mut self, trace_rows_per_leaf: usize) -> Self {
+        self.trace_rows_per_leaf = trace_rows_per_leaf;
+        self
+    }
+
+    /// Sets the mode in which the public coin absorbs prover's messages.
+    pub fn transcript_mode(mut self, transcript_mode: TranscriptMode) -> Self {
+        self.transcript_mode = transcript_mode;
+        self
+    }
+
+    /// Sets the size (in bytes) of hash function digests used by the protocol.
+    pub fn digest_size(mut self, digest_size: usize) -> Self {
+        self.digest_size = Some(digest_size);
+        self
+    }
+
+    /// Sets the offset of the low-degree extension domain to the field element with the specified
+    /// integer value.
+    pub fn domain_offset(mut self, domain_offset: u64) -> Self {
+        self.domain_offset = Some(domain_offset);
+        self
+    }
+
+    /// Sets the degree of the highest-degree transition constraint of the computation for which
+    /// the options are built; this is used only to validate the blowup factor.
+    pub fn max_constraint_degree(mut self, degree: usize) -> Self {
+        self.max_constraint_degree = Some(degree);
+        self
+    }
+
+    /// Sets the length of the execution trace of the computation for which the options are
+    /// built; this is used only to validate the number of queries.
+    pub fn trace_length(mut self, trace_length: usize) -> Self {
+        self.trace_length = Some(trace_length);
+        self
+    }
+
+    /// Returns [ProofOptions] built from the parameters of this builder.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * `num_queries` is zero or greater than 1024.
+    /// * `blowup_factor` is smaller than 2, greater than 128, or is not a power of two.
+    /// * `grinding_factor` is greater than 32.
+    /// * `fri_folding_factor` is not 4, 8, or 16.
+    /// * `fri_max_remainder_size` is smaller than 32, greater than 1024, or is not a power of two.
+    /// * `merkle_arity` is not 2, 4, 8, or 16.
+    /// * `trace_rows_per_leaf` is not a power of two or is greater than 16.
+    /// * `digest_size` is specified and is smaller than 16 or greater than 32.
+    /// * `domain_offset` is specified and is zero.
+    /// * `max_constraint_degree` is specified and the blowup factor is too small to evaluate
+    ///   constraints of this degree.
+    /// * `trace_length` is specified and the number of queries exceeds the size of the LDE
+    ///   domain.
+    pub fn build(self) -> Result<ProofOptions, ProofOptionsError> {
+        if self.num_queries == 0 || self.num_queries > ProofOptions::MAX_NUM_QUERIES {
+            return Err(ProofOptionsError::InvalidNumQueries(self.num_queries));
+        }
+        if !self.blowup_factor.is_power_of_two()
+            || self.blowup_factor < ProofOptions::MIN_BLOWUP_FACTOR
+            || self.blowup_factor > 128
+        {
+            return Err(ProofOptionsError::InvalidBlowupFactor(self.blowup_factor));
+        }
+        if self.grinding_factor > 32 {
+            return Err(ProofOptionsError::InvalidGrindingFactor(
+                self.grinding_factor,
+            ));
+        }
+        if ![4, 8, 16].contains(&self.fri_folding_factor) {
+            return Err(ProofOptionsError::InvalidFriFoldingFactor(
+                self.fri_folding_factor,
+            ));
+        }
+        if !self.fri_max_remainder_size.is_power_of_two()
+            || !(32..=1024).contains(&self.fri_max_remainder_size)
+        {
+            return Err(ProofOptionsError::InvalidFriMaxRemainderSize(
+                self.fri_max_remainder_size,
+            ));
+        }
+        if !MERKLE_TREE_ARITIES.contains(&self.merkle_arity) {
+            return Err(ProofOptionsError::InvalidMerkleArity(self.merkle_arity));
+        }
+        if !self.trace_rows_per_leaf.is_power_of_two()
+            || self.trace_rows_per_leaf > ProofOptions::MAX_TRACE_ROWS_PER_LEAF
+        {
+            return Err(ProofOptionsError::InvalidTraceRowsPerLeaf(
+                self.trace_rows_per_leaf,
+            ));
+        }
+        if let Some(digest_size) = self.digest_size {
+            if !(ProofOptions::MIN_DIGEST_SIZE..=ProofOptions::MAX_DIGEST_SIZE)
+                .contains(&digest_size)
+            {
+                return Err(ProofOptionsError::InvalidDigestSize(digest_size));
+            }
+        }
+        if self.domain_offset == Some(0) {
+            return Err(ProofOptionsError::InvalidDomainOffset(0));
+        }
+
+        // validate the options against the computation, if it was described; an automatically
+        // selected blowup factor is never too small, but it cannot exceed 128
+        if let Some(degree) = self.max_constraint_degree {
+            let min_blowup_factor = core::cmp::max(
+                degree.saturating_sub(1).next_power_of_two(),
+                ProofOptions::MIN_BLOWUP_FACTOR,
+            );
+            if self.auto_blowup_factor && min_blowup_factor > 128 {
+                return Err(ProofOptionsError::InvalidBlowupFactor(min_blowup_factor));
+            }
+            if !self.auto_blowup_factor && self.blowup_factor < min_blowup_factor {
+                return Err(ProofOptionsError::BlowupFactorTooSmall(
+                    min_blowup_factor,
+                    self.blowup_factor,
+                ));
+            }
+        }
+        if let Some(trace_length) = self.trace_length {
+            let lde_domain_size = trace_length.saturating_mul(self.blowup_factor);
+            if self.num_queries > lde_domain_size {
+                return Err(ProofOptionsError::TooManyQueries(
+                    lde_domain_size,
+                    self.num_queries,
+                ));
+            }
+        }
+
+        let options = ProofOptions::new(
+            self.num_queries,
+            self.blowup_factor,
+            self.grinding_factor,
+            self.field_extension,
+            self.fri_folding_factor,
+            self.fri_max_remainder_size,
+        )
+        .with_merkle_arity(self.merkle_arity)
+        .with_trace_rows_per_leaf(self.trace_rows_per_leaf)
+        .with_transcript_mode(self.transcript_mode);
+
+        let options = match self.auto_blowup_factor {
+            true => options.with_auto_blowup_factor(),
+            false => options,
+        };
+        let options = match self.digest_size {
+            Some(digest_size) => options.with_digest_size(digest_size),
+            None => options,
+        };
+        Ok(match self.domain_offset {
+            Some(domain_offset) => options.with_domain_offset(domain_offset),
+            None => options,
+        })
+    }
+}
+
+impl Default for ProofOptionsBuilder {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -254,3 +887,150 @@ impl Deserializable for FieldExtension {
         }
     }
 }
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{FieldExtension, ProofOptions, ProofOptionsError};
+    use crypto::TranscriptMode;
+    use utils::{Deserializable, Serializable, SliceReader};
+
+    #[test]
+    fn builder() {
+        let options = ProofOptions::builder()
+            .num_queries(28)
+            .blowup_factor(16)
+            .grinding_factor(4)
+            .field_extension(FieldExtension::Quadratic)
+            .fri_folding_factor(8)
+            .fri_max_remainder_size(128)
+            .merkle_arity(4)
+            .trace_rows_per_leaf(4)
+            .transcript_mode(TranscriptMode::Sponge)
+            .digest_size(24)
+            .build()
+            .unwrap();
+        let expected = ProofOptions::new(28, 16, 4, FieldExtension::Quadratic, 8, 128)
+            .with_merkle_arity(4)
+            .with_trace_rows_per_leaf(4)
+            .with_transcript_mode(TranscriptMode::Sponge)
+            .with_digest_size(24);
+        assert_eq!(expected, options);
+    }
+
+    #[test]
+    fn builder_errors() {
+        let builder = ProofOptions::builder();
+        assert_eq!(
+            Err(ProofOptionsError::InvalidNumQueries(0)),
+            builder.clone().num_queries(0).build()
+        );
+        assert_eq!(
+            Err(ProofOptionsError::InvalidBlowupFactor(6)),
+            builder.clone().blowup_factor(6).build()
+        );
+        assert_eq!(
+            Err(ProofOptionsError::InvalidGrindingFactor(33)),
+            builder.clone().grinding_factor(33).build()
+        );
+        assert_eq!(
+            Err(ProofOptionsError::InvalidFriFoldingFactor(2)),
+            builder.clone().fri_folding_factor(2).build()
+        );
+        assert_eq!(
+            Err(ProofOptionsError::InvalidFriMaxRemainderSize(16)),
+            builder.clone().fri_max_remainder_size(16).build()
+        );
+        assert_eq!(
+            Err(ProofOptionsError::InvalidMerkleArity(3)),
+            builder.clone().merkle_arity(3).build()
+        );
+        assert_eq!(
+            Err(ProofOptionsError::InvalidTraceRowsPerLeaf(32)),
+            builder.clone().trace_rows_per_leaf(32).build()
+        );
+        assert_eq!(
+            Err(ProofOptionsError::InvalidDigestSize(8)),
+            builder.clone().digest_size(8).build()
+        );
+        assert_eq!(
+            Err(ProofOptionsError::InvalidDomainOffset(0)),
+            builder.clone().domain_offset(0).build()
+        );
+
+        // try_new() performs the same validation as the builder
+        assert_eq!(
+            Ok(ProofOptions::new(
+                28,
+                16,
+                4,
+                FieldExtension::Quadratic,
+                8,
+                128
+            )),
+            ProofOptions::try_new(28, 16, 4, FieldExtension::Quadratic, 8, 128)
+        );
+        assert_eq!(
+            Err(ProofOptionsError::InvalidBlowupFactor(6)),
+            ProofOptions::try_new(28, 6, 4, FieldExtension::Quadratic, 8, 128)
+        );
+
+        // degree 9 constraints require a blowup factor of at least 8
+        assert!(builder.clone().max_constraint_degree(9).build().is_ok());
+        assert_eq!(
+            Err(ProofOptionsError::BlowupFactorTooSmall(16, 8)),
+            builder.clone().max_constraint_degree(10).build()
+        );
+
+        // an LDE domain of 8 * 8 = 64 elements cannot accommodate more than 64 queries
+        assert_eq!(
+            Err(ProofOptionsError::TooManyQueries(64, 100)),
+            builder.clone().num_queries(100).trace_length(8).build()
+        );
+
+        // an automatically selected blowup factor is never too small, but cannot exceed 128
+        let builder = builder.auto_blowup_factor();
+        assert!(builder.clone().max_constraint_degree(10).build().is_ok());
+        assert_eq!(
+            Err(ProofOptionsError::InvalidBlowupFactor(256)),
+            builder.max_constraint_degree(200).build()
+        );
+    }
+
+    #[test]
+    fn auto_blowup_factor() {
+        let options = ProofOptions::new(27, 8, 16, FieldExtension::Quadratic, 8, 256)
+            .with_auto_blowup_factor();
+        assert!(options.is_blowup_factor_auto());
+
+        // a smaller blowup factor is compensated for by additional queries: 27 * 3 = 81 bits of
+        // query security require 81 queries at blowup factor 2 and 41 queries at blowup factor 4
+        let resolved = options.resolve_blowup_factor(2);
+        assert!(!resolved.is_blowup_factor_auto());
+        assert_eq!(2, resolved.blowup_factor());
+        assert_eq!(81, resolved.num_queries());
+        let resolved = options.resolve_blowup_factor(4);
+        assert_eq!(4, resolved.blowup_factor());
+        assert_eq!(41, resolved.num_queries());
+        assert_eq!(16, resolved.grinding_factor());
+
+        // a larger blowup factor does not reduce the number of queries
+        let resolved = options.resolve_blowup_factor(16);
+        assert_eq!(16, resolved.blowup_factor());
+        assert_eq!(27, resolved.num_queries());
+
+        // options with a fixed blowup factor are not affected
+        let fixed = ProofOptions::new(27, 8, 16, FieldExtension::Quadratic, 8, 256);
+        assert_eq!(fixed, fixed.resolve_blowup_factor(2));
+
+        // the automatic selection survives a serialization round trip
+        let bytes = options.to_bytes();
+        assert_eq!(
+            options,
+            ProofOptions::read_from(&mut SliceReader::new(&bytes)).unwrap()
+        );
+        assert_eq!(fixed.to_bytes().len() + 1, bytes.len());
+    }
+}
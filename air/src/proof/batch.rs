@@ -0,0 +1,220 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{Context, StarkProof, PROOF_BATCH_FORMAT_VERSION};
+use core::slice;
+use utils::{
+    collections::{vec, Vec},
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable, SliceReader,
+};
+
+// PROOF BATCH
+// ================================================================================================
+/// A container for multiple STARK proofs.
+///
+/// Proofs in a batch are usually generated for the same computation with different public inputs
+/// (e.g., proofs of validity for a sequence of blocks). Contexts of such proofs differ only in
+/// their public input digests, and thus, when a batch is serialized, every distinct context is
+/// written only once, and each proof refers to its context by index. This makes a serialized
+/// batch noticeably smaller than the sum of its serialized proofs.
+///
+/// The binary format of a batch starts with a [PROOF_BATCH_FORMAT_VERSION] byte followed by:
+/// * The number of distinct contexts, followed by the contexts themselves with empty public
+///   input digests.
+/// * The number of proofs, followed by the proofs themselves. Each proof is written as an index
+///   of its context, its public input digest, and all other proof components in the same format
+///   as used by [StarkProof::to_bytes()].
+///
+/// Contexts are written in the order in which they first appear in the batch; thus, the same
+/// batch is always serialized into the same sequence of bytes.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct ProofBatch {
+    proofs: Vec<StarkProof>,
+}
+
+impl ProofBatch {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new empty batch of proofs.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends the specified `proof` to the end of this batch.
+    pub fn push(&mut self, proof: StarkProof) {
+        self.proofs.push(proof);
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of proofs in this batch.
+    pub fn len(&self) -> usize {
+        self.proofs.len()
+    }
+
+    /// Returns true if this batch does not contain any proofs.
+    pub fn is_empty(&self) -> bool {
+        self.proofs.is_empty()
+    }
+
+    /// Returns a proof at the specified `index`, or None if the index is out of bounds.
+    pub fn get(&self, index: usize) -> Option<&StarkProof> {
+        self.proofs.get(index)
+    }
+
+    /// Returns an iterator over proofs in this batch.
+    pub fn iter(&self) -> slice::Iter<'_, StarkProof> {
+        self.proofs.iter()
+    }
+
+    /// Returns the number of distinct contexts (ignoring public input digests) of proofs in this
+    /// batch; this is the number of contexts written when this batch is serialized.
+    pub fn num_contexts(&self) -> usize {
+        self.shared_contexts().0.len()
+    }
+
+    /// Consumes this batch and returns the proofs it contains.
+    pub fn into_proofs(self) -> Vec<StarkProof> {
+        self.proofs
+    }
+
+    // SERIALIZATION / DESERIALIZATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Serializes this batch into a vector of bytes.
+    ///
+    /// The first byte of the result is always [PROOF_BATCH_FORMAT_VERSION].
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let (contexts, context_indexes) = self.shared_contexts();
+
+        let mut result = vec![PROOF_BATCH_FORMAT_VERSION];
+        result.write_usize(contexts.len());
+        for context in contexts.iter() {
+            context.write_into(&mut result);
+        }
+        result.write_usize(self.proofs.len());
+        for (proof, &context_idx) in self.proofs.iter().zip(context_indexes.iter()) {
+            let pub_inputs_digest = proof.context.pub_inputs_digest();
+            result.write_usize(context_idx);
+            result.write_u8(pub_inputs_digest.len() as u8);
+            result.write_u8_slice(pub_inputs_digest);
+            proof.write_body(&mut result);
+        }
+        result
+    }
+
+    /// Returns a batch of proofs read from the specified `source`.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The batch was serialized using a format version other than
+    ///   [PROOF_BATCH_FORMAT_VERSION].
+    /// * A shared context contains a public input digest, or a proof refers to a context which
+    ///   is not in the batch.
+    /// * A valid batch of STARK proofs could not be read from the specified `source`.
+    pub fn from_bytes(source: &[u8]) -> Result<Self, DeserializationError> {
+        let mut source = SliceReader::new(source);
+
+        // make sure the batch was serialized using the current format
+        let version = source.read_u8()?;
+        if version != PROOF_BATCH_FORMAT_VERSION {
+            return Err(DeserializationError::InvalidValue(format!(
+                "proof batch format version {} is not supported; expected version {}",
+                version, PROOF_BATCH_FORMAT_VERSION
+            )));
+        }
+
+        // parse shared contexts; the number of contexts is not used to pre-allocate memory as it
+        // comes from an untrusted source
+        let num_contexts = source.read_usize()?;
+        let mut contexts = Vec::new();
+        for _ in 0..num_contexts {
+            let context = Context::read_from(&mut source)?;
+            if !context.pub_inputs_digest().is_empty() {
+                return Err(DeserializationError::InvalidValue(
+                    "shared proof context cannot contain public inputs digest".into(),
+                ));
+            }
+            contexts.push(context);
+        }
+
+        // parse the proofs
+        let num_proofs = source.read_usize()?;
+        let mut proofs = Vec::new();
+        for _ in 0..num_proofs {
+            let context_idx = source.read_usize()?;
+            let mut context = contexts.get(context_idx).cloned().ok_or_else(|| {
+                DeserializationError::InvalidValue(format!(
+                    "proof context index {} is out of bounds; batch contains {} contexts",
+                    context_idx, num_contexts
+                ))
+            })?;
+            let digest_len = source.read_u8()? as usize;
+            context.set_pub_inputs_digest(source.read_u8_vec(digest_len)?);
+            proofs.push(StarkProof::read_body(context, &mut source)?);
+        }
+
+        if source.has_more_bytes() {
+            return Err(DeserializationError::UnconsumedBytes);
+        }
+        Ok(ProofBatch { proofs })
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a list of distinct contexts of proofs in this batch with public input digests
+    /// removed, together with the index of the context for every proof.
+    fn shared_contexts(&self) -> (Vec<Context>, Vec<usize>) {
+        let mut contexts: Vec<Context> = Vec::new();
+        let mut context_indexes = Vec::with_capacity(self.proofs.len());
+        for proof in self.proofs.iter() {
+            let mut context = proof.context.clone();
+            context.set_pub_inputs_digest(Vec::new());
+            let context_idx = match contexts.iter().position(|c| *c == context) {
+                Some(idx) => idx,
+                None => {
+                    contexts.push(context);
+                    contexts.len() - 1
+                }
+            };
+            context_indexes.push(context_idx);
+        }
+        (contexts, context_indexes)
+    }
+}
+
+impl From<Vec<StarkProof>> for ProofBatch {
+    fn from(proofs: Vec<StarkProof>) -> Self {
+        ProofBatch { proofs }
+    }
+}
+
+impl FromIterator<StarkProof> for ProofBatch {
+    fn from_iter<I: IntoIterator<Item = StarkProof>>(iter: I) -> Self {
+        ProofBatch {
+            proofs: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl IntoIterator for ProofBatch {
+    type Item = StarkProof;
+    type IntoIter = vec::IntoIter<StarkProof>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.proofs.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a ProofBatch {
+    type Item = &'a StarkProof;
+    type IntoIter = slice::Iter<'a, StarkProof>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.proofs.iter()
+    }
+}
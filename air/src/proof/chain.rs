@@ -0,0 +1,156 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{ProofBatch, StarkProof};
+use utils::{
+    collections::Vec, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+    SliceReader,
+};
+
+// STEP PUBLIC INPUTS
+// ================================================================================================
+/// Public inputs of a computation which executes a single step of a step function.
+///
+/// A step function transforms an input state into an output state (e.g., applies a block of
+/// transactions to a rollup state). Public inputs of a proof of a single step include both
+/// states; thus, proofs of consecutive steps can be chained together: the output state of each
+/// proof must be the input state of the next proof.
+pub trait StepPublicInputs {
+    /// State consumed and produced by the step function.
+    type State: PartialEq;
+
+    /// Returns the state from which the step starts.
+    fn input_state(&self) -> Self::State;
+
+    /// Returns the state produced by the step.
+    fn output_state(&self) -> Self::State;
+}
+
+// PROOF CHAIN
+// ================================================================================================
+/// A container for proofs of consecutive executions of a step function.
+///
+/// Each proof in a chain is stored together with its public inputs. The chain is linked if the
+/// output state of every proof is equal to the input state of the next proof; a linked chain of
+/// valid proofs attests that the output state of the chain was obtained by applying the step
+/// function to the input state of the chain [len()](ProofChain::len) times. Links are not checked
+/// when proofs are added to the chain; a chain is checked, together with all of its proofs, by
+/// the verifier.
+///
+/// The binary format of a chain consists of the length-prefixed proofs of the chain serialized as
+/// a [ProofBatch], followed by the public inputs of every proof.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ProofChain<I> {
+    proofs: ProofBatch,
+    pub_inputs: Vec<I>,
+}
+
+impl<I: StepPublicInputs> ProofChain<I> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new empty chain of proofs.
+    pub fn new() -> Self {
+        Self {
+            proofs: ProofBatch::new(),
+            pub_inputs: Vec::new(),
+        }
+    }
+
+    /// Appends the specified `proof` generated for the specified public inputs to the end of
+    /// this chain.
+    pub fn push(&mut self, proof: StarkProof, pub_inputs: I) {
+        self.proofs.push(proof);
+        self.pub_inputs.push(pub_inputs);
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of proofs in this chain.
+    pub fn len(&self) -> usize {
+        self.proofs.len()
+    }
+
+    /// Returns true if this chain does not contain any proofs.
+    pub fn is_empty(&self) -> bool {
+        self.proofs.is_empty()
+    }
+
+    /// Returns the proofs of this chain.
+    pub fn proofs(&self) -> &ProofBatch {
+        &self.proofs
+    }
+
+    /// Returns public inputs of the proofs of this chain.
+    pub fn pub_inputs(&self) -> &[I] {
+        &self.pub_inputs
+    }
+
+    /// Returns the input state of the first proof in this chain, or None if the chain is empty.
+    pub fn input_state(&self) -> Option<I::State> {
+        self.pub_inputs.first().map(|inputs| inputs.input_state())
+    }
+
+    /// Returns the output state of the last proof in this chain, or None if the chain is empty.
+    pub fn output_state(&self) -> Option<I::State> {
+        self.pub_inputs.last().map(|inputs| inputs.output_state())
+    }
+
+    /// Returns the index of the first proof whose input state is different from the output state
+    /// of the previous proof, or None if all proofs in this chain are linked.
+    pub fn find_broken_link(&self) -> Option<usize> {
+        self.pub_inputs
+            .windows(2)
+            .position(|pair| pair[0].output_state() != pair[1].input_state())
+            .map(|idx| idx + 1)
+    }
+
+    /// Consumes this chain and returns the proofs it contains together with their public inputs.
+    pub fn into_parts(self) -> (ProofBatch, Vec<I>) {
+        (self.proofs, self.pub_inputs)
+    }
+}
+
+impl<I: StepPublicInputs + Serializable> ProofChain<I> {
+    // SERIALIZATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Serializes this chain into a vector of bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let proofs = self.proofs.to_bytes();
+        let mut result = Vec::new();
+        result.write_usize(proofs.len());
+        result.write_u8_slice(&proofs);
+        I::write_batch_into(&self.pub_inputs, &mut result);
+        result
+    }
+}
+
+impl<I: StepPublicInputs + Deserializable> ProofChain<I> {
+    // DESERIALIZATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a chain of proofs read from the specified `source`.
+    ///
+    /// # Errors
+    /// Returns an error if a valid batch of proofs followed by public inputs for every proof in
+    /// the batch could not be read from the specified `source`.
+    pub fn from_bytes(source: &[u8]) -> Result<Self, DeserializationError> {
+        let mut source = SliceReader::new(source);
+        let num_proof_bytes = source.read_usize()?;
+        let proofs = ProofBatch::from_bytes(&source.read_u8_vec(num_proof_bytes)?)?;
+        let pub_inputs = I::read_batch_from(&mut source, proofs.len())?;
+        if source.has_more_bytes() {
+            return Err(DeserializationError::UnconsumedBytes);
+        }
+        Ok(Self { proofs, pub_inputs })
+    }
+}
+
+impl<I: StepPublicInputs> Default for ProofChain<I> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -48,6 +48,11 @@ impl Commitments {
         commitment.write_into(&mut self.0);
     }
 
+    /// Returns serialized commitments (without the length prefix).
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
     // PARSING
     // --------------------------------------------------------------------------------------------
 
@@ -89,8 +94,7 @@ impl Commitments {
 impl Serializable for Commitments {
     /// Serializes `self` and writes the resulting bytes into the `target`.
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
-        assert!(self.0.len() < u16::MAX as usize);
-        target.write_u16(self.0.len() as u16);
+        target.write_usize(self.0.len());
         target.write_u8_slice(&self.0);
     }
 }
@@ -102,7 +106,7 @@ impl Deserializable for Commitments {
     /// Returns an error of a valid Commitments struct could not be read from the specified
     /// `source`.
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
-        let num_bytes = source.read_u16()? as usize;
+        let num_bytes = source.read_usize()?;
         let result = source.read_u8_vec(num_bytes)?;
         Ok(Commitments(result))
     }
@@ -4,6 +4,7 @@
 // LICENSE file in the root directory of this source tree.
 
 use crate::{ProofOptions, TraceInfo, TraceLayout};
+use crypto::HashFunction;
 use math::StarkField;
 use utils::{
     collections::Vec, string::ToString, ByteReader, ByteWriter, Deserializable,
@@ -20,6 +21,8 @@ pub struct Context {
     trace_meta: Vec<u8>,
     field_modulus_bytes: Vec<u8>,
     options: ProofOptions,
+    hash_function: HashFunction,
+    pub_inputs_digest: Vec<u8>,
 }
 
 impl Context {
@@ -27,13 +30,39 @@ impl Context {
     // --------------------------------------------------------------------------------------------
     /// Creates a new context for a computation described by the specified field, trace info, and
     /// proof options.
-    pub fn new<B: StarkField>(trace_info: &TraceInfo, options: ProofOptions) -> Self {
+    ///
+    /// `hash_function` is the identifier of the hash function used to generate the proof; this
+    /// lets verifiers reject proofs generated with a different hash function.
+    ///
+    /// `pub_inputs_digest` is a digest of the serialized public inputs of the computation; this
+    /// binds the context (and thus, the proof) to a specific set of public inputs.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `pub_inputs_digest` is longer than 255 bytes.
+    /// * The blowup factor of `options` is selected automatically and has not been resolved yet.
+    pub fn new<B: StarkField>(
+        trace_info: &TraceInfo,
+        options: ProofOptions,
+        hash_function: HashFunction,
+        pub_inputs_digest: Vec<u8>,
+    ) -> Self {
+        assert!(
+            pub_inputs_digest.len() <= u8::MAX as usize,
+            "public inputs digest cannot be longer than 255 bytes"
+        );
+        assert!(
+            !options.is_blowup_factor_auto(),
+            "blowup factor must be resolved before a proof context is created"
+        );
         Context {
             trace_layout: trace_info.layout().clone(),
             trace_length: trace_info.length(),
             trace_meta: trace_info.meta().to_vec(),
             field_modulus_bytes: B::get_modulus_le_bytes(),
             options,
+            hash_function,
+            pub_inputs_digest,
         }
     }
 
@@ -65,6 +94,11 @@ impl Context {
         self.trace_length() * self.options.blowup_factor()
     }
 
+    /// Returns the digest of public inputs of the computation described by this context.
+    pub fn pub_inputs_digest(&self) -> &[u8] {
+        &self.pub_inputs_digest
+    }
+
     /// Returns modulus of the field for the computation described by this context.
     pub fn field_modulus_bytes(&self) -> &[u8] {
         &self.field_modulus_bytes
@@ -91,6 +125,25 @@ impl Context {
     pub fn options(&self) -> &ProofOptions {
         &self.options
     }
+
+    /// Returns the identifier of the hash function which was used to generate a proof in this
+    /// context.
+    ///
+    /// Arity of Merkle trees used for commitments is specified by the proof options.
+    pub fn hash_function(&self) -> HashFunction {
+        self.hash_function
+    }
+
+    // PUBLIC INPUTS DIGEST
+    // --------------------------------------------------------------------------------------------
+
+    /// Sets the digest of public inputs for this context.
+    ///
+    /// Contexts of proofs for the same computation differ only in their public input digests;
+    /// thus, clearing the digest makes such contexts identical.
+    pub(super) fn set_pub_inputs_digest(&mut self, pub_inputs_digest: Vec<u8>) {
+        self.pub_inputs_digest = pub_inputs_digest;
+    }
 }
 
 impl Serializable for Context {
@@ -98,12 +151,15 @@ impl Serializable for Context {
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
         self.trace_layout.write_into(target);
         target.write_u8(math::log2(self.trace_length) as u8); // store as power of two
-        target.write_u16(self.trace_meta.len() as u16);
+        target.write_usize(self.trace_meta.len());
         target.write_u8_slice(&self.trace_meta);
         assert!(self.field_modulus_bytes.len() < u8::MAX as usize);
         target.write_u8(self.field_modulus_bytes.len() as u8);
         target.write_u8_slice(&self.field_modulus_bytes);
         self.options.write_into(target);
+        self.hash_function.write_into(target);
+        target.write_u8(self.pub_inputs_digest.len() as u8);
+        target.write_u8_slice(&self.pub_inputs_digest);
     }
 }
 
@@ -125,10 +181,22 @@ impl Deserializable for Context {
                 trace_length
             )));
         }
+        if trace_length as u32 >= usize::BITS {
+            return Err(DeserializationError::InvalidValue(format!(
+                "trace length cannot be 2^{trace_length}"
+            )));
+        }
         let trace_length = 2_usize.pow(trace_length as u32);
 
         // read trace metadata
-        let num_meta_bytes = source.read_u16()? as usize;
+        let num_meta_bytes = source.read_usize()?;
+        if num_meta_bytes > TraceInfo::MAX_META_LENGTH {
+            return Err(DeserializationError::InvalidValue(format!(
+                "trace metadata cannot be longer than {} bytes, but was {} bytes",
+                TraceInfo::MAX_META_LENGTH,
+                num_meta_bytes
+            )));
+        }
         let trace_meta = if num_meta_bytes != 0 {
             source.read_u8_vec(num_meta_bytes)?
         } else {
@@ -144,8 +212,26 @@ impl Deserializable for Context {
         }
         let field_modulus_bytes = source.read_u8_vec(num_modulus_bytes)?;
 
-        // read options
+        // read options and make sure the LDE domain size is representable; the blowup factor
+        // recorded in a context must have been already selected for the computation
         let options = ProofOptions::read_from(source)?;
+        if options.is_blowup_factor_auto() {
+            return Err(DeserializationError::InvalidValue(
+                "blowup factor in proof context must be resolved".to_string(),
+            ));
+        }
+        if trace_length.checked_mul(options.blowup_factor()).is_none() {
+            return Err(DeserializationError::InvalidValue(format!(
+                "LDE domain size for trace length {} and blowup factor {} is too large",
+                trace_length,
+                options.blowup_factor()
+            )));
+        }
+
+        // read hash function identifier and public inputs digest
+        let hash_function = HashFunction::read_from(source)?;
+        let num_digest_bytes = source.read_u8()? as usize;
+        let pub_inputs_digest = source.read_u8_vec(num_digest_bytes)?;
 
         Ok(Context {
             trace_layout,
@@ -153,6 +239,8 @@ impl Deserializable for Context {
             trace_meta,
             field_modulus_bytes,
             options,
+            hash_function,
+            pub_inputs_digest,
         })
     }
 }
@@ -0,0 +1,453 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{read_component, Commitments, Context, OodFrame, Queries, StarkProof, Vec};
+use crate::FieldExtension;
+use crypto::Hasher;
+use fri::FriProof;
+use math::StarkField;
+use utils::{
+    string::{String, ToString},
+    ByteReader, ByteWriter, DeserializationError, Serializable, SliceReader,
+};
+
+// ETHSTARK CHANNEL MESSAGES
+// ================================================================================================
+
+/// Type of a message sent from the prover to the verifier over an ethSTARK channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageKind {
+    /// A Merkle tree root.
+    Commitment,
+    /// A sequence of field elements, each serialized in big-endian byte order.
+    FieldElements,
+    /// A proof-of-work nonce serialized as 8 big-endian bytes.
+    ProofOfWork,
+    /// Query values (serialized as field elements) or Merkle authentication paths for a set of
+    /// queries.
+    Decommitment,
+}
+
+/// A single message sent from the prover to the verifier over an ethSTARK channel.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChannelMessage {
+    /// Type of this message.
+    pub kind: MessageKind,
+    /// Annotation scope of this message, e.g. `"STARK/Original/Commit on Trace"`.
+    pub label: String,
+    /// Message bytes exactly as they would be sent over the channel.
+    pub data: Vec<u8>,
+}
+
+/// A STARK proof laid out as a sequence of prover-to-verifier messages following the conventions
+/// of the ethSTARK reference implementation.
+///
+/// The messages appear in the order in which they are sent over the channel: trace commitments,
+/// composition polynomial commitment, out-of-domain evaluations, FRI layer commitments, FRI last
+/// layer, proof-of-work nonce, and finally decommitments for trace, composition polynomial, and
+/// FRI layer queries. Field elements and the nonce are written in big-endian byte order, as is
+/// done by ethSTARK.
+///
+/// The layouts align only partially; in particular:
+/// * The last FRI layer is committed to before it is sent (as is done by this crate), and thus,
+///   an extra commitment precedes the last layer values.
+/// * Merkle authentication paths are kept in the batch encoding used by this crate, rather than
+///   as a flat list of sibling nodes.
+/// * Verifier-to-prover messages (i.e., random challenges) are not included as they can be
+///   re-derived from the prover messages.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EthStarkProof {
+    messages: Vec<ChannelMessage>,
+}
+
+impl EthStarkProof {
+    /// Returns a new proof consisting of the specified channel messages.
+    pub fn new(messages: Vec<ChannelMessage>) -> Self {
+        EthStarkProof { messages }
+    }
+
+    /// Returns the channel messages of this proof.
+    pub fn messages(&self) -> &[ChannelMessage] {
+        &self.messages
+    }
+
+    /// Returns a concatenation of all channel messages of this proof in ethSTARK order.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.messages
+            .iter()
+            .flat_map(|m| m.data.iter().copied())
+            .collect()
+    }
+
+    /// Returns ethSTARK-style annotations for the messages of this proof (one per message), e.g.
+    /// `P->V[0:32]: /STARK/Original/Commit on Trace: Commitment`.
+    ///
+    /// Offsets in the annotations refer to positions in the vector returned by
+    /// [to_bytes()](EthStarkProof::to_bytes).
+    pub fn annotations(&self) -> Vec<String> {
+        let mut offset = 0;
+        self.messages
+            .iter()
+            .map(|message| {
+                let start = offset;
+                offset += message.data.len();
+                format!(
+                    "P->V[{}:{}]: /{}: {:?}",
+                    start, offset, message.label, message.kind
+                )
+            })
+            .collect()
+    }
+}
+
+// CONVERSION
+// ================================================================================================
+
+impl StarkProof {
+    /// Converts this proof into a sequence of ethSTARK channel messages.
+    ///
+    /// `H` must be the hash function used to generate this proof, and `B` must be the base field
+    /// of the computation.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * Parameters of this proof have no ethSTARK counterpart; this is the case for proofs
+    ///   generated over a field extension, with Merkle trees of arity other than two, or with
+    ///   FRI proofs split into multiple partitions.
+    /// * Any of the proof components is malformed.
+    pub fn to_ethstark<H: Hasher, B: StarkField>(
+        &self,
+    ) -> Result<EthStarkProof, DeserializationError> {
+        check_context::<B>(&self.context)?;
+        if self.fri_proof.num_partitions() != 1 {
+            return Err(unsupported("partitioned FRI proofs"));
+        }
+
+        let num_trace_segments = self.context.trace_layout().num_segments();
+        let num_fri_layers = self.fri_proof.num_layers();
+        let (trace_roots, constraint_root, fri_roots) = self
+            .commitments
+            .clone()
+            .parse::<H>(num_trace_segments, num_fri_layers)?;
+
+        let mut messages = Vec::new();
+        let mut push =
+            |kind, label: String, data| messages.push(ChannelMessage { kind, label, data });
+
+        // commitments to the trace and the constraint composition polynomial
+        for (i, root) in trace_roots.iter().enumerate() {
+            push(
+                MessageKind::Commitment,
+                trace_scope(i, "Commit on Trace"),
+                root.to_bytes(),
+            );
+        }
+        push(
+            MessageKind::Commitment,
+            "STARK/Out Of Domain Sampling/Commit on Trace".to_string(),
+            constraint_root.to_bytes(),
+        );
+
+        // out-of-domain evaluations
+        let (trace_states, evaluations) = split_parts(&self.ood_frame.to_bytes())?;
+        push(
+            MessageKind::FieldElements,
+            "STARK/Out Of Domain Sampling/OODS values/Trace".to_string(),
+            to_big_endian::<B>(&trace_states)?,
+        );
+        push(
+            MessageKind::FieldElements,
+            "STARK/Out Of Domain Sampling/OODS values/Composition".to_string(),
+            to_big_endian::<B>(&evaluations)?,
+        );
+
+        // FRI commitments and the last FRI layer
+        let (fri_layers, remainder) = split_fri_proof(&self.fri_proof)?;
+        for (i, root) in fri_roots.iter().enumerate() {
+            let label = if i < num_fri_layers {
+                format!("STARK/FRI/Commitment/Layer {}", i + 1)
+            } else {
+                "STARK/FRI/Commitment/Last Layer".to_string()
+            };
+            push(MessageKind::Commitment, label, root.to_bytes());
+        }
+        push(
+            MessageKind::FieldElements,
+            "STARK/FRI/Commitment/Last Layer".to_string(),
+            to_big_endian::<B>(&remainder)?,
+        );
+
+        // proof-of-work nonce
+        push(
+            MessageKind::ProofOfWork,
+            "STARK/FRI/Proof of Work".to_string(),
+            self.pow_nonce.to_be_bytes().to_vec(),
+        );
+
+        // decommitments
+        let mut push_decommitment = |label: String, bytes: &[u8]| {
+            let (values, paths) = split_parts(bytes)?;
+            push(
+                MessageKind::Decommitment,
+                format!("{}/Values", label),
+                to_big_endian::<B>(&values)?,
+            );
+            push(MessageKind::Decommitment, format!("{}/Paths", label), paths);
+            Ok::<(), DeserializationError>(())
+        };
+        for (i, queries) in self.trace_queries.iter().enumerate() {
+            push_decommitment(trace_scope(i, "Decommit on Trace"), &queries.to_bytes())?;
+        }
+        push_decommitment(
+            "STARK/Out Of Domain Sampling/Decommit on Trace".to_string(),
+            &self.constraint_queries.to_bytes(),
+        )?;
+        for (i, layer) in fri_layers.iter().enumerate() {
+            push_decommitment(format!("STARK/FRI/Decommitment/Layer {}", i), layer)?;
+        }
+
+        Ok(EthStarkProof::new(messages))
+    }
+
+    /// Returns a STARK proof built from a sequence of ethSTARK channel messages produced by
+    /// [to_ethstark()](StarkProof::to_ethstark).
+    ///
+    /// Since ethSTARK proofs do not carry proof parameters, the `context` of the original proof
+    /// must be provided. `H` must be the hash function used to generate the proof, and `B` must
+    /// be the base field of the computation.
+    ///
+    /// # Errors
+    /// Returns an error if the `context` has no ethSTARK counterpart, or if the messages do not
+    /// form a valid proof for the specified `context`.
+    pub fn from_ethstark<H: Hasher, B: StarkField>(
+        context: Context,
+        proof: &EthStarkProof,
+    ) -> Result<Self, DeserializationError> {
+        check_context::<B>(&context)?;
+        let mut messages = MessageReader::new(proof.messages());
+
+        // commitments to the trace and the constraint composition polynomial
+        let num_trace_segments = context.trace_layout().num_segments();
+        let mut trace_roots = Vec::with_capacity(num_trace_segments);
+        for _ in 0..num_trace_segments {
+            trace_roots.push(messages.read_digest::<H>()?);
+        }
+        let constraint_root = messages.read_digest::<H>()?;
+
+        // out-of-domain evaluations
+        let trace_states = from_big_endian::<B>(messages.read(MessageKind::FieldElements)?)?;
+        let evaluations = from_big_endian::<B>(messages.read(MessageKind::FieldElements)?)?;
+        let ood_frame = join_parts(&trace_states, &evaluations);
+
+        // FRI commitments and the last FRI layer
+        let mut fri_roots = Vec::new();
+        while messages.peek_kind() == Some(MessageKind::Commitment) {
+            fri_roots.push(messages.read_digest::<H>()?);
+        }
+        if fri_roots.is_empty() {
+            return Err(DeserializationError::InvalidValue(
+                "FRI remainder commitment is missing".to_string(),
+            ));
+        }
+        let num_fri_layers = fri_roots.len() - 1;
+        let remainder = from_big_endian::<B>(messages.read(MessageKind::FieldElements)?)?;
+
+        // proof-of-work nonce
+        let nonce_bytes = messages.read(MessageKind::ProofOfWork)?;
+        let pow_nonce = match <[u8; 8]>::try_from(nonce_bytes) {
+            Ok(bytes) => u64::from_be_bytes(bytes),
+            Err(_) => {
+                return Err(DeserializationError::InvalidValue(format!(
+                    "proof-of-work nonce must be 8 bytes long, but was {} bytes long",
+                    nonce_bytes.len()
+                )))
+            }
+        };
+
+        // decommitments
+        let mut trace_queries = Vec::with_capacity(num_trace_segments);
+        for _ in 0..num_trace_segments {
+            trace_queries.push(read_component::<Queries>(
+                &messages.read_decommitment::<B>()?,
+            )?);
+        }
+        let constraint_queries = read_component::<Queries>(&messages.read_decommitment::<B>()?)?;
+
+        let mut fri_proof = Vec::new();
+        fri_proof.write_usize(num_fri_layers);
+        for _ in 0..num_fri_layers {
+            fri_proof.write_u8_slice(&messages.read_decommitment::<B>()?);
+        }
+        fri_proof.write_usize(remainder.len());
+        fri_proof.write_u8_slice(&remainder);
+        fri_proof.write_u8(0);
+
+        if messages.peek_kind().is_some() {
+            return Err(DeserializationError::UnconsumedBytes);
+        }
+
+        Ok(StarkProof {
+            context,
+            commitments: Commitments::new::<H>(trace_roots, constraint_root, fri_roots),
+            trace_queries,
+            constraint_queries,
+            ood_frame: read_component::<OodFrame>(&ood_frame)?,
+            fri_proof: read_component::<FriProof>(&fri_proof)?,
+            pow_nonce,
+        })
+    }
+}
+
+// MESSAGE READER
+// ================================================================================================
+
+struct MessageReader<'a> {
+    messages: &'a [ChannelMessage],
+    pos: usize,
+}
+
+impl<'a> MessageReader<'a> {
+    fn new(messages: &'a [ChannelMessage]) -> Self {
+        MessageReader { messages, pos: 0 }
+    }
+
+    fn peek_kind(&self) -> Option<MessageKind> {
+        self.messages.get(self.pos).map(|message| message.kind)
+    }
+
+    fn read(&mut self, kind: MessageKind) -> Result<&'a [u8], DeserializationError> {
+        let message = self
+            .messages
+            .get(self.pos)
+            .ok_or(DeserializationError::UnexpectedEOF)?;
+        if message.kind != kind {
+            return Err(DeserializationError::InvalidValue(format!(
+                "expected message {} to be {:?}, but was {:?}",
+                self.pos, kind, message.kind
+            )));
+        }
+        self.pos += 1;
+        Ok(&message.data)
+    }
+
+    fn read_digest<H: Hasher>(&mut self) -> Result<H::Digest, DeserializationError> {
+        read_component::<H::Digest>(self.read(MessageKind::Commitment)?)
+    }
+
+    /// Reads query values and authentication paths, and returns them in the serialization format
+    /// of [Queries] and FRI proof layers.
+    fn read_decommitment<B: StarkField>(&mut self) -> Result<Vec<u8>, DeserializationError> {
+        let values = from_big_endian::<B>(self.read(MessageKind::Decommitment)?)?;
+        let paths = self.read(MessageKind::Decommitment)?;
+        Ok(join_parts(&values, paths))
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Makes sure that a proof with the specified context can be represented in ethSTARK format.
+fn check_context<B: StarkField>(context: &Context) -> Result<(), DeserializationError> {
+    if context.field_modulus_bytes() != B::get_modulus_le_bytes() {
+        return Err(DeserializationError::InvalidValue(
+            "proof was generated over a different base field".to_string(),
+        ));
+    }
+    if context.options().field_extension() != FieldExtension::None {
+        return Err(unsupported("proofs generated over field extensions"));
+    }
+    if context.options().merkle_arity() != 2 {
+        return Err(unsupported("Merkle trees of arity other than two"));
+    }
+    Ok(())
+}
+
+fn unsupported(feature: &str) -> DeserializationError {
+    DeserializationError::InvalidValue(format!("ethSTARK format does not support {}", feature))
+}
+
+/// Returns the annotation scope for the specified trace segment; the main segment is called
+/// "Original" and auxiliary segments are called "Interaction" in ethSTARK.
+fn trace_scope(segment: usize, name: &str) -> String {
+    match segment {
+        0 => format!("STARK/Original/{}", name),
+        1 => format!("STARK/Interaction/{}", name),
+        _ => format!("STARK/Interaction {}/{}", segment, name),
+    }
+}
+
+/// Splits a serialized component consisting of two length-prefixed byte strings (i.e., queries,
+/// FRI proof layers, and out-of-domain frames) into these byte strings.
+fn split_parts(bytes: &[u8]) -> Result<(Vec<u8>, Vec<u8>), DeserializationError> {
+    let mut source = SliceReader::new(bytes);
+    let num_first_bytes = source.read_usize()?;
+    let first = source.read_u8_vec(num_first_bytes)?;
+    let num_second_bytes = source.read_usize()?;
+    let second = source.read_u8_vec(num_second_bytes)?;
+    if source.has_more_bytes() {
+        return Err(DeserializationError::UnconsumedBytes);
+    }
+    Ok((first, second))
+}
+
+/// Inverse of [split_parts()].
+fn join_parts(first: &[u8], second: &[u8]) -> Vec<u8> {
+    let mut result = Vec::new();
+    result.write_usize(first.len());
+    result.write_u8_slice(first);
+    result.write_usize(second.len());
+    result.write_u8_slice(second);
+    result
+}
+
+/// Splits a FRI proof into serialized layers and remainder bytes.
+fn split_fri_proof(proof: &FriProof) -> Result<(Vec<Vec<u8>>, Vec<u8>), DeserializationError> {
+    let bytes = proof.to_bytes();
+    let mut source = SliceReader::new(&bytes);
+    let num_layers = source.read_usize()?;
+    let mut layers = Vec::with_capacity(num_layers);
+    for _ in 0..num_layers {
+        let num_value_bytes = source.read_usize()?;
+        let values = source.read_u8_vec(num_value_bytes)?;
+        let num_path_bytes = source.read_usize()?;
+        let paths = source.read_u8_vec(num_path_bytes)?;
+        layers.push(join_parts(&values, &paths));
+    }
+    let num_remainder_bytes = source.read_usize()?;
+    let remainder = source.read_u8_vec(num_remainder_bytes)?;
+    Ok((layers, remainder))
+}
+
+/// Converts a sequence of field elements serialized in little-endian byte order into big-endian
+/// byte order.
+fn to_big_endian<B: StarkField>(bytes: &[u8]) -> Result<Vec<u8>, DeserializationError> {
+    reverse_elements::<B>(bytes)
+}
+
+/// Converts a sequence of field elements serialized in big-endian byte order into little-endian
+/// byte order, making sure all elements are valid.
+fn from_big_endian<B: StarkField>(bytes: &[u8]) -> Result<Vec<u8>, DeserializationError> {
+    let result = reverse_elements::<B>(bytes)?;
+    B::read_batch_from(
+        &mut SliceReader::new(&result),
+        bytes.len() / B::ELEMENT_BYTES,
+    )?;
+    Ok(result)
+}
+
+fn reverse_elements<B: StarkField>(bytes: &[u8]) -> Result<Vec<u8>, DeserializationError> {
+    if bytes.len() % B::ELEMENT_BYTES != 0 {
+        return Err(DeserializationError::InvalidValue(format!(
+            "number of bytes must be a multiple of {}, but was {}",
+            B::ELEMENT_BYTES,
+            bytes.len()
+        )));
+    }
+    let mut result = bytes.to_vec();
+    for element in result.chunks_mut(B::ELEMENT_BYTES) {
+        element.reverse();
+    }
+    Ok(result)
+}
@@ -0,0 +1,358 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    read_component, Commitments, Context, OodFrame, Queries, StarkProof, Vec, PROOF_FORMAT_VERSION,
+};
+use fri::FriProof;
+use utils::{
+    string::{String, ToString},
+    DeserializationError, Serializable,
+};
+
+// JSON EXPORT / IMPORT
+// ================================================================================================
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+impl StarkProof {
+    /// Serializes this proof into a JSON string.
+    ///
+    /// The result is an object with the following fields:
+    /// * `version` - proof format version as a number (always [PROOF_FORMAT_VERSION]).
+    /// * `context`, `commitments`, `constraint_queries`, `ood_frame`, `fri_proof` - hex-encoded
+    ///   serializations of the corresponding proof components.
+    /// * `trace_queries` - an array with hex-encoded serialization of queries for each trace
+    ///   segment.
+    /// * `pow_nonce` - hex-encoded little-endian bytes of the proof-of-work nonce.
+    ///
+    /// Every byte of the proof is preserved exactly: concatenating the version byte with the
+    /// decoded components in the order listed above yields the output of
+    /// [to_bytes()](StarkProof::to_bytes).
+    pub fn to_json(&self) -> String {
+        let mut result = String::from("{");
+        result.push_str(&format!("\"version\":{},", PROOF_FORMAT_VERSION));
+        write_hex_field(&mut result, "context", &self.context.to_bytes());
+        write_hex_field(&mut result, "commitments", &self.commitments.to_bytes());
+        result.push_str("\"trace_queries\":[");
+        for (i, queries) in self.trace_queries.iter().enumerate() {
+            if i > 0 {
+                result.push(',');
+            }
+            write_hex_string(&mut result, &queries.to_bytes());
+        }
+        result.push_str("],");
+        write_hex_field(
+            &mut result,
+            "constraint_queries",
+            &self.constraint_queries.to_bytes(),
+        );
+        write_hex_field(&mut result, "ood_frame", &self.ood_frame.to_bytes());
+        write_hex_field(&mut result, "fri_proof", &self.fri_proof.to_bytes());
+        result.push_str("\"pow_nonce\":");
+        write_hex_string(&mut result, &self.pow_nonce.to_le_bytes());
+        result.push('}');
+        result
+    }
+
+    /// Returns a STARK proof read from a JSON string produced by
+    /// [to_json()](StarkProof::to_json).
+    ///
+    /// Fields may appear in any order and may be separated by arbitrary whitespace; hex digits
+    /// may be in either case.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The `source` is not a JSON object with exactly the fields listed in
+    ///   [to_json()](StarkProof::to_json).
+    /// * The proof was serialized using a format version other than [PROOF_FORMAT_VERSION].
+    /// * Any of the proof components could not be read from its hex encoding, or the encoding
+    ///   contains bytes which do not belong to the component.
+    pub fn from_json(source: &str) -> Result<Self, DeserializationError> {
+        let mut reader = JsonReader::new(source);
+        let mut version = None;
+        let mut context = None;
+        let mut commitments = None;
+        let mut trace_queries = None;
+        let mut constraint_queries = None;
+        let mut ood_frame = None;
+        let mut fri_proof = None;
+        let mut pow_nonce = None;
+
+        reader.expect(b'{')?;
+        loop {
+            let key = reader.read_string()?;
+            reader.expect(b':')?;
+            match key {
+                "version" => set_field(&mut version, key, reader.read_number()?)?,
+                "context" => set_field(&mut context, key, reader.read_hex()?)?,
+                "commitments" => set_field(&mut commitments, key, reader.read_hex()?)?,
+                "trace_queries" => set_field(&mut trace_queries, key, reader.read_hex_array()?)?,
+                "constraint_queries" => {
+                    set_field(&mut constraint_queries, key, reader.read_hex()?)?
+                }
+                "ood_frame" => set_field(&mut ood_frame, key, reader.read_hex()?)?,
+                "fri_proof" => set_field(&mut fri_proof, key, reader.read_hex()?)?,
+                "pow_nonce" => set_field(&mut pow_nonce, key, reader.read_hex()?)?,
+                _ => {
+                    return Err(DeserializationError::InvalidValue(format!(
+                        "unexpected proof field '{}'",
+                        key
+                    )))
+                }
+            }
+            if !reader.next_list_item(b'}')? {
+                break;
+            }
+        }
+        reader.expect_end()?;
+
+        // make sure the proof was serialized using the current format
+        let version = required_field(version, "version")?;
+        if version != PROOF_FORMAT_VERSION as u64 {
+            return Err(DeserializationError::InvalidValue(format!(
+                "proof format version {} is not supported; expected version {}",
+                version, PROOF_FORMAT_VERSION
+            )));
+        }
+
+        // parse proof components
+        let context: Context = read_component(&required_field(context, "context")?)?;
+        let commitments: Commitments =
+            read_component(&required_field(commitments, "commitments")?)?;
+
+        let trace_queries = required_field(trace_queries, "trace_queries")?;
+        let num_trace_segments = context.trace_layout().num_segments();
+        if trace_queries.len() != num_trace_segments {
+            return Err(DeserializationError::InvalidValue(format!(
+                "expected queries for {} trace segments, but found {}",
+                num_trace_segments,
+                trace_queries.len()
+            )));
+        }
+        let trace_queries = trace_queries
+            .iter()
+            .map(|bytes| read_component::<Queries>(bytes))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let constraint_queries =
+            read_component(&required_field(constraint_queries, "constraint_queries")?)?;
+        let ood_frame: OodFrame = read_component(&required_field(ood_frame, "ood_frame")?)?;
+        let fri_proof: FriProof = read_component(&required_field(fri_proof, "fri_proof")?)?;
+
+        let pow_nonce = required_field(pow_nonce, "pow_nonce")?;
+        let pow_nonce = match <[u8; 8]>::try_from(pow_nonce.as_slice()) {
+            Ok(bytes) => u64::from_le_bytes(bytes),
+            Err(_) => {
+                return Err(DeserializationError::InvalidValue(format!(
+                    "proof-of-work nonce must be 8 bytes long, but was {} bytes long",
+                    pow_nonce.len()
+                )))
+            }
+        };
+
+        let proof = StarkProof {
+            context,
+            commitments,
+            trace_queries,
+            constraint_queries,
+            ood_frame,
+            fri_proof,
+            pow_nonce,
+        };
+        proof.validate_shape()?;
+        Ok(proof)
+    }
+}
+
+// JSON READER
+// ================================================================================================
+
+/// A minimal reader for the subset of JSON produced by [StarkProof::to_json()] and
+/// [VerifierPolicy::to_json()](super::VerifierPolicy::to_json): a single object with string keys
+/// and values which are numbers, strings without escape sequences, or arrays of such values.
+pub(super) struct JsonReader<'a> {
+    source: &'a str,
+    pos: usize,
+}
+
+impl<'a> JsonReader<'a> {
+    pub fn new(source: &'a str) -> Self {
+        JsonReader { source, pos: 0 }
+    }
+
+    /// Skips whitespace and returns the next byte without consuming it.
+    fn peek(&mut self) -> Result<u8, DeserializationError> {
+        let bytes = self.source.as_bytes();
+        while self.pos < bytes.len() && bytes[self.pos].is_ascii_whitespace() {
+            self.pos += 1;
+        }
+        bytes
+            .get(self.pos)
+            .copied()
+            .ok_or(DeserializationError::UnexpectedEOF)
+    }
+
+    pub fn expect(&mut self, expected: u8) -> Result<(), DeserializationError> {
+        let actual = self.peek()?;
+        if actual != expected {
+            return Err(self.unexpected(actual));
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    pub fn expect_end(&mut self) -> Result<(), DeserializationError> {
+        match self.peek() {
+            Err(DeserializationError::UnexpectedEOF) => Ok(()),
+            Err(err) => Err(err),
+            Ok(_) => Err(DeserializationError::UnconsumedBytes),
+        }
+    }
+
+    /// Consumes a separator between list items and returns true if another item follows, or
+    /// consumes the `closing` delimiter and returns false.
+    pub fn next_list_item(&mut self, closing: u8) -> Result<bool, DeserializationError> {
+        match self.peek()? {
+            b',' => {
+                self.pos += 1;
+                Ok(true)
+            }
+            c if c == closing => {
+                self.pos += 1;
+                Ok(false)
+            }
+            c => Err(self.unexpected(c)),
+        }
+    }
+
+    pub fn read_string(&mut self) -> Result<&'a str, DeserializationError> {
+        self.expect(b'"')?;
+        let start = self.pos;
+        let end = match self.source[start..].find(['"', '\\']) {
+            Some(offset) if self.source.as_bytes()[start + offset] == b'"' => start + offset,
+            Some(_) => {
+                return Err(DeserializationError::InvalidValue(
+                    "escape sequences are not supported in proof strings".to_string(),
+                ))
+            }
+            None => return Err(DeserializationError::UnexpectedEOF),
+        };
+        self.pos = end + 1;
+        Ok(&self.source[start..end])
+    }
+
+    pub fn read_number(&mut self) -> Result<u64, DeserializationError> {
+        self.peek()?;
+        let start = self.pos;
+        let digits = self.source[start..]
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(self.source.len() - start);
+        self.pos += digits;
+        self.source[start..self.pos].parse().map_err(|_| {
+            DeserializationError::InvalidValue(format!("invalid number at position {}", start))
+        })
+    }
+
+    pub fn read_hex(&mut self) -> Result<Vec<u8>, DeserializationError> {
+        decode_hex(self.read_string()?)
+    }
+
+    pub fn read_hex_array(&mut self) -> Result<Vec<Vec<u8>>, DeserializationError> {
+        self.read_array(Self::read_hex)
+    }
+
+    pub fn read_number_array(&mut self) -> Result<Vec<u64>, DeserializationError> {
+        self.read_array(Self::read_number)
+    }
+
+    fn read_array<T>(
+        &mut self,
+        read_item: fn(&mut Self) -> Result<T, DeserializationError>,
+    ) -> Result<Vec<T>, DeserializationError> {
+        let mut result = Vec::new();
+        self.expect(b'[')?;
+        if self.peek()? == b']' {
+            self.pos += 1;
+            return Ok(result);
+        }
+        loop {
+            result.push(read_item(self)?);
+            if !self.next_list_item(b']')? {
+                return Ok(result);
+            }
+        }
+    }
+
+    fn unexpected(&self, c: u8) -> DeserializationError {
+        DeserializationError::InvalidValue(format!(
+            "unexpected character '{}' at position {}",
+            c as char, self.pos
+        ))
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+pub(super) fn write_hex_string(target: &mut String, bytes: &[u8]) {
+    target.push('"');
+    for &byte in bytes {
+        target.push(HEX_DIGITS[(byte >> 4) as usize] as char);
+        target.push(HEX_DIGITS[(byte & 0xf) as usize] as char);
+    }
+    target.push('"');
+}
+
+fn write_hex_field(target: &mut String, name: &str, bytes: &[u8]) {
+    target.push('"');
+    target.push_str(name);
+    target.push_str("\":");
+    write_hex_string(target, bytes);
+    target.push(',');
+}
+
+fn decode_hex(source: &str) -> Result<Vec<u8>, DeserializationError> {
+    fn digit(c: u8) -> Result<u8, DeserializationError> {
+        match c {
+            b'0'..=b'9' => Ok(c - b'0'),
+            b'a'..=b'f' => Ok(c - b'a' + 10),
+            b'A'..=b'F' => Ok(c - b'A' + 10),
+            _ => Err(DeserializationError::InvalidValue(format!(
+                "'{}' is not a hex digit",
+                c as char
+            ))),
+        }
+    }
+
+    let source = source.as_bytes();
+    if source.len() % 2 != 0 {
+        return Err(DeserializationError::InvalidValue(
+            "hex string must contain an even number of digits".to_string(),
+        ));
+    }
+    source
+        .chunks(2)
+        .map(|pair| Ok((digit(pair[0])? << 4) | digit(pair[1])?))
+        .collect()
+}
+
+pub(super) fn set_field<T>(
+    field: &mut Option<T>,
+    name: &str,
+    value: T,
+) -> Result<(), DeserializationError> {
+    if field.replace(value).is_some() {
+        return Err(DeserializationError::InvalidValue(format!(
+            "field '{}' is specified more than once",
+            name
+        )));
+    }
+    Ok(())
+}
+
+pub(super) fn required_field<T>(field: Option<T>, name: &str) -> Result<T, DeserializationError> {
+    field.ok_or_else(|| DeserializationError::InvalidValue(format!("field '{}' is missing", name)))
+}
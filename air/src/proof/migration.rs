@@ -0,0 +1,274 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{StarkProof, PROOF_FORMAT_VERSION};
+use crate::TraceLayout;
+use crypto::{HashFunction, Hasher, TranscriptMode};
+use utils::{
+    collections::Vec, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+    SliceReader,
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Version of the binary format produced by the 0.4 releases of the crate; proofs in this format
+/// are not prefixed with a version byte.
+pub const LEGACY_PROOF_FORMAT_VERSION: u8 = 0;
+
+/// Version of the binary format in which the number of queries and the number of FRI layers were
+/// encoded as single bytes.
+const NARROW_COUNTS_PROOF_FORMAT_VERSION: u8 = 1;
+
+/// Version of the binary format in which proof options did not include the number of trace rows
+/// per Merkle tree leaf.
+const SINGLE_ROW_LEAVES_PROOF_FORMAT_VERSION: u8 = 2;
+
+/// Number of bytes in serialized proof options of a legacy proof.
+const LEGACY_OPTIONS_SIZE: usize = 6;
+
+// PROOF MIGRATION
+// ================================================================================================
+
+impl StarkProof {
+    /// Returns a STARK proof read from the specified `source` serialized using the specified
+    /// format version.
+    ///
+    /// This can be used to read proofs produced by previous releases of the crate; the result
+    /// can be re-encoded in the current format via [to_bytes()](StarkProof::to_bytes). The
+    /// following versions are supported:
+    /// * `1` - format in which the number of queries, the number of FRI layers, and the number of
+    ///   paths in batch Merkle proofs were encoded as single bytes rather than variable-length
+    ///   integers. Only proofs with fewer than 128 queries can be migrated from this version and
+    ///   from the legacy format.
+    /// * `2` - format in which proof options did not include the number of trace rows per Merkle
+    ///   tree leaf. Proofs in this format and in format `1` are migrated with one trace row per
+    ///   leaf.
+    /// * [PROOF_FORMAT_VERSION] - the current format; this is the same as
+    ///   [from_bytes()](StarkProof::from_bytes).
+    ///
+    /// Proofs in [LEGACY_PROOF_FORMAT_VERSION] do not record all data needed by the verifier,
+    /// and thus, they must be migrated via [migrate_legacy()](StarkProof::migrate_legacy).
+    ///
+    /// Migration preserves all proof data exactly. However, a proof is verified against the
+    /// protocol implemented by the current version of the verifier; thus, a migrated proof
+    /// verifies only if it was generated by a prover implementing the same protocol.
+    ///
+    /// # Errors
+    /// Returns an error if `from_version` is not one of the supported versions, or if a valid
+    /// STARK proof could not be read from the specified `source`.
+    pub fn migrate(source: &[u8], from_version: u8) -> Result<Self, DeserializationError> {
+        match from_version {
+            NARROW_COUNTS_PROOF_FORMAT_VERSION | SINGLE_ROW_LEAVES_PROOF_FORMAT_VERSION => {
+                Self::from_bytes(&migrate_single_row_leaves_proof(source, from_version)?)
+            }
+            PROOF_FORMAT_VERSION => Self::from_bytes(source),
+            _ => Err(DeserializationError::InvalidValue(format!(
+                "migration from proof format version {} is not supported",
+                from_version
+            ))),
+        }
+    }
+
+    /// Returns a STARK proof read from the specified `source` serialized in the format used by
+    /// the 0.4 releases of the crate (i.e., [LEGACY_PROOF_FORMAT_VERSION]).
+    ///
+    /// Legacy proofs did not record the hash function used to generate them, and were not bound
+    /// to a digest of public inputs; thus, these are supplied by the caller: the hash function
+    /// of the migrated proof is `H`, and its public inputs digest is computed by hashing the
+    /// serialized `pub_inputs` with `H` (in the same way as the prover does). Proof options are
+    /// migrated with the defaults of the options added since the 0.4 releases (binary Merkle
+    /// trees with one trace row per leaf, hash chain transcript, unspecified digest size and
+    /// domain offset).
+    ///
+    /// As with [migrate()](StarkProof::migrate), the migrated proof verifies only if it was
+    /// generated by a prover implementing the protocol of the current version of the verifier.
+    ///
+    /// # Errors
+    /// Returns an error if a valid STARK proof could not be read from the specified `source`.
+    pub fn migrate_legacy<H, P>(source: &[u8], pub_inputs: &P) -> Result<Self, DeserializationError>
+    where
+        H: Hasher,
+        P: Serializable,
+    {
+        let pub_inputs_digest = H::hash(&pub_inputs.to_bytes()).to_bytes();
+        Self::from_bytes(&migrate_legacy_proof(source, H::ID, &pub_inputs_digest)?)
+    }
+}
+
+// LEGACY FORMAT
+// ================================================================================================
+
+/// Re-encodes a proof serialized in the legacy format into the current format.
+///
+/// The two formats contain the same components in the same order; they differ as follows:
+/// * Legacy proofs do not start with a version byte.
+/// * Length prefixes in legacy proofs are fixed-width (u16 or u32) integers rather than
+///   variable-length integers; the number of queries and the number of FRI layers are single
+///   bytes. As in format version 1, only proofs with fewer than 128 queries can be migrated.
+/// * Legacy proof options do not include Merkle tree arity, number of trace rows per leaf,
+///   transcript mode, digest size, and domain offset; legacy contexts do not include hash
+///   function identifier and public inputs digest, and thus, these are set to the specified
+///   values.
+fn migrate_legacy_proof(
+    source: &[u8],
+    hash_function: HashFunction,
+    pub_inputs_digest: &[u8],
+) -> Result<Vec<u8>, DeserializationError> {
+    let mut source = SliceReader::new(source);
+    let mut result = vec![PROOF_FORMAT_VERSION];
+
+    // context
+    let trace_layout = TraceLayout::read_from(&mut source)?;
+    trace_layout.write_into(&mut result);
+    result.write_u8(source.read_u8()?); // trace length
+    copy_bytes(&mut source, &mut result, LengthPrefix::U16)?; // trace meta
+    let num_modulus_bytes = source.read_u8()?;
+    result.write_u8(num_modulus_bytes);
+    result.write_u8_slice(&source.read_u8_vec(num_modulus_bytes as usize)?);
+    let num_queries = source.read_u8()?;
+    check_narrow_num_queries(num_queries)?;
+    result.write_usize(num_queries as usize);
+    result.write_u8_slice(&source.read_u8_vec(LEGACY_OPTIONS_SIZE - 1)?);
+    result.write_u8(2); // binary Merkle trees
+    result.write_u8(1); // one trace row per Merkle tree leaf
+    TranscriptMode::HashChain.write_into(&mut result);
+    result.write_u8(0); // digest size is not specified
+    result.write_u8(0); // domain offset is not specified
+    hash_function.write_into(&mut result);
+    result.write_u8(pub_inputs_digest.len() as u8);
+    result.write_u8_slice(pub_inputs_digest);
+
+    // commitments
+    copy_bytes(&mut source, &mut result, LengthPrefix::U16)?;
+
+    // trace queries and constraint queries
+    for _ in 0..trace_layout.num_segments() + 1 {
+        copy_bytes(&mut source, &mut result, LengthPrefix::U32)?;
+        copy_bytes(&mut source, &mut result, LengthPrefix::U32)?;
+    }
+
+    // out-of-domain frame
+    copy_bytes(&mut source, &mut result, LengthPrefix::U16)?;
+    copy_bytes(&mut source, &mut result, LengthPrefix::U16)?;
+
+    // FRI proof
+    let num_fri_layers = source.read_u8()?;
+    result.write_usize(num_fri_layers as usize);
+    for _ in 0..num_fri_layers {
+        copy_bytes(&mut source, &mut result, LengthPrefix::U32)?;
+        copy_bytes(&mut source, &mut result, LengthPrefix::U32)?;
+    }
+    copy_bytes(&mut source, &mut result, LengthPrefix::U16)?; // remainder
+    result.write_u8(source.read_u8()?); // number of partitions
+
+    // proof-of-work nonce
+    result.write_u64(source.read_u64()?);
+
+    if source.has_more_bytes() {
+        return Err(DeserializationError::UnconsumedBytes);
+    }
+    Ok(result)
+}
+
+// FORMAT VERSIONS 1 AND 2
+// ================================================================================================
+
+/// Re-encodes a proof serialized in format version 1 or 2 into the current format.
+///
+/// Proof options in these formats do not include the number of trace rows per Merkle tree leaf;
+/// the migrated proof commits to one trace row per leaf. Otherwise, the formats differ from the
+/// current one as follows:
+/// * In format version 1, the number of queries, the number of FRI layers, and the number of
+///   paths in batch Merkle proofs were encoded as single bytes; these are now variable-length
+///   integers. Variable-length integers smaller than 128 are encoded as a single byte equal to
+///   the value, and none of these counts can reach 128 in a proof with fewer than 128 queries;
+///   thus, such proofs need no other changes.
+/// * Format version 2 is the same as the current format.
+fn migrate_single_row_leaves_proof(
+    source: &[u8],
+    from_version: u8,
+) -> Result<Vec<u8>, DeserializationError> {
+    let mut reader = SliceReader::new(source);
+    let version = reader.read_u8()?;
+    if version != from_version {
+        return Err(DeserializationError::InvalidValue(format!(
+            "expected proof format version {}, but was {}",
+            from_version, version
+        )));
+    }
+    let mut result = vec![PROOF_FORMAT_VERSION];
+
+    // context up to proof options: trace layout, trace length, trace meta, and field modulus
+    TraceLayout::read_from(&mut reader)?.write_into(&mut result);
+    result.write_u8(reader.read_u8()?); // trace length
+    let num_meta_bytes = reader.read_usize()?;
+    result.write_usize(num_meta_bytes);
+    result.write_u8_slice(&reader.read_u8_vec(num_meta_bytes)?);
+    let num_modulus_bytes = reader.read_u8()?;
+    result.write_u8(num_modulus_bytes);
+    result.write_u8_slice(&reader.read_u8_vec(num_modulus_bytes as usize)?);
+
+    // proof options up to Merkle tree arity
+    let num_queries = if from_version == NARROW_COUNTS_PROOF_FORMAT_VERSION {
+        let num_queries = reader.read_u8()?;
+        check_narrow_num_queries(num_queries)?;
+        num_queries as usize
+    } else {
+        reader.read_usize()?
+    };
+    result.write_usize(num_queries);
+    let blowup_factor = reader.read_u8()?;
+    result.write_u8(blowup_factor);
+    if blowup_factor == 0 {
+        // automatically selected blowup factor is followed by the reference blowup factor
+        result.write_u8(reader.read_u8()?);
+    }
+    // grinding factor, field extension, FRI folding factor, FRI max remainder size, and Merkle
+    // tree arity
+    result.write_u8_slice(&reader.read_u8_vec(5)?);
+
+    // all bytes read so far were written into the result without changes in their sizes (as
+    // variable-length integers are always minimally encoded); thus, the remaining bytes start at
+    // the current length of the result
+    let num_read_bytes = result.len();
+    result.write_u8(1); // one trace row per Merkle tree leaf
+    result.write_u8_slice(&source[num_read_bytes..]);
+    Ok(result)
+}
+
+/// Returns an error if a proof with the specified number of queries encoded as a single byte
+/// cannot be migrated without re-encoding its Merkle authentication paths.
+fn check_narrow_num_queries(num_queries: u8) -> Result<(), DeserializationError> {
+    if num_queries >= 128 {
+        return Err(DeserializationError::InvalidValue(format!(
+            "proofs with {} queries cannot be migrated; at most 127 queries are supported",
+            num_queries
+        )));
+    }
+    Ok(())
+}
+
+/// Width of a length prefix in the legacy proof format.
+enum LengthPrefix {
+    U16,
+    U32,
+}
+
+/// Reads a byte string prefixed with a fixed-width length from the `source`, and writes it into
+/// the `target` prefixed with a variable-length integer.
+fn copy_bytes(
+    source: &mut SliceReader,
+    target: &mut Vec<u8>,
+    prefix: LengthPrefix,
+) -> Result<(), DeserializationError> {
+    let num_bytes = match prefix {
+        LengthPrefix::U16 => source.read_u16()? as usize,
+        LengthPrefix::U32 => source.read_u32()? as usize,
+    };
+    target.write_usize(num_bytes);
+    target.write_u8_slice(&source.read_u8_vec(num_bytes)?);
+    Ok(())
+}
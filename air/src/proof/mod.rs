@@ -11,7 +11,8 @@ use crypto::Hasher;
 use fri::FriProof;
 use math::log2;
 use utils::{
-    collections::Vec, ByteReader, Deserializable, DeserializationError, Serializable, SliceReader,
+    collections::Vec, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+    SliceReader,
 };
 
 mod context;
@@ -21,19 +22,57 @@ mod commitments;
 pub use commitments::Commitments;
 
 mod queries;
-pub use queries::Queries;
+pub use queries::{get_leaf_positions, Queries};
 
 mod ood_frame;
 pub use ood_frame::OodFrame;
 
+mod json;
+
+mod ethstark;
+pub use ethstark::{ChannelMessage, EthStarkProof, MessageKind};
+
+mod stats;
+pub use stats::ProofStats;
+
+mod batch;
+pub use batch::ProofBatch;
+
+mod chain;
+pub use chain::{ProofChain, StepPublicInputs};
+
+mod migration;
+pub use migration::LEGACY_PROOF_FORMAT_VERSION;
+
+mod policy;
+pub use policy::VerifierPolicy;
+
 mod table;
 pub use table::Table;
 
+mod tuner;
+pub use tuner::{OptionsTuner, TuningObjective};
+
+#[cfg(test)]
+mod tests;
+
 // CONSTANTS
 // ================================================================================================
 
 const GRINDING_CONTRIBUTION_FLOOR: u32 = 80;
 
+/// Range of list-decoding parameters over which proven security is optimized.
+const MIN_LIST_DECODING_PARAM: u32 = 3;
+const MAX_LIST_DECODING_PARAM: u32 = 100;
+
+/// Version of the binary format produced by [StarkProof::to_bytes()]; this is written as the first
+/// byte of every serialized proof.
+pub const PROOF_FORMAT_VERSION: u8 = 3;
+
+/// Version of the binary format produced by [ProofBatch::to_bytes()]; this is written as the first
+/// byte of every serialized batch of proofs.
+pub const PROOF_BATCH_FORMAT_VERSION: u8 = 0x84;
+
 // STARK PROOF
 // ================================================================================================
 /// A proof generated by Winterfell prover.
@@ -44,7 +83,9 @@ const GRINDING_CONTRIBUTION_FLOOR: u32 = 80;
 ///
 /// A proof can be serialized into a sequence of bytes using [to_bytes()](StarkProof::to_bytes)
 /// function, and deserialized from a sequence of bytes using [from_bytes()](StarkProof::from_bytes)
-/// function.
+/// function. The binary format starts with a [PROOF_FORMAT_VERSION] byte followed by the proof
+/// components; variable-length components are prefixed with their lengths encoded as LEB128
+/// variable-length integers.
 ///
 /// To estimate soundness of a proof (in bits), [security_level()](StarkProof::security_level)
 /// function can be used.
@@ -103,6 +144,10 @@ impl StarkProof {
     /// security level is returned. Usually, the number of queries needed for provable security is
     /// 2x - 3x higher than the number of queries needed for conjectured security at the same
     /// security level.
+    ///
+    /// Both estimates are computed solely from the parameters embedded in the proof: size of the
+    /// base field, degree of the field extension, blowup factor, number of queries, grinding
+    /// factor, trace length, and collision resistance of the hash function `H`.
     pub fn security_level<H: Hasher>(&self, conjectured: bool) -> u32 {
         if conjectured {
             get_conjectured_security(
@@ -112,45 +157,157 @@ impl StarkProof {
                 H::COLLISION_RESISTANCE,
             )
         } else {
-            // TODO: implement provable security estimation
-            unimplemented!("proven security estimation has not been implement yet")
+            get_proven_security(
+                self.context.options(),
+                self.context.num_modulus_bits(),
+                self.trace_length() as u64,
+                self.lde_domain_size() as u64,
+                H::COLLISION_RESISTANCE,
+            )
         }
     }
 
+    /// Returns a digest of this proof computed using hash function `H`.
+    ///
+    /// The digest is a hash of the canonical binary encoding of the proof (i.e., the output of
+    /// [to_bytes()](StarkProof::to_bytes)), and thus, it does not depend on the encoding in which
+    /// the proof was transported: a proof read from its JSON or batch encoding has the same digest
+    /// as the original proof. The digest can be used as a short identifier of the proof, e.g., to
+    /// deduplicate proofs or to reference them on-chain.
+    ///
+    /// The digest is unique: field elements and hash digests have a single valid encoding, and
+    /// proofs which contain non-canonical encodings of them are rejected by the verifier. Thus,
+    /// a valid proof cannot be re-encoded to obtain a different digest.
+    ///
+    /// `H` does not need to be the hash function used to generate the proof.
+    pub fn digest<H: Hasher>(&self) -> H::Digest {
+        H::hash(&self.to_bytes())
+    }
+
+    /// Returns a breakdown of the size of this proof (when serialized via
+    /// [to_bytes()](StarkProof::to_bytes)) by proof component.
+    pub fn stats(&self) -> ProofStats {
+        let trace_queries = self
+            .trace_queries
+            .iter()
+            .map(|queries| queries.to_bytes().len())
+            .collect::<Vec<_>>();
+        let fri_layers = self.fri_proof.layer_sizes();
+        let mut stats = ProofStats {
+            total: self.to_bytes().len(),
+            context: self.context.to_bytes().len(),
+            commitments: self.commitments.to_bytes().len(),
+            trace_queries,
+            constraint_queries: self.constraint_queries.to_bytes().len(),
+            ood_frame: self.ood_frame.to_bytes().len(),
+            fri_layers,
+            fri_remainder: self.fri_proof.remainder_size(),
+            other: 0,
+            num_queries: self.options().num_queries(),
+        };
+        stats.other = stats.total
+            - stats.context
+            - stats.commitments
+            - stats.trace_queries_total()
+            - stats.constraint_queries
+            - stats.ood_frame
+            - stats.fri_total();
+        stats
+    }
+
     // SERIALIZATION / DESERIALIZATION
     // --------------------------------------------------------------------------------------------
 
     /// Serializes this proof into a vector of bytes.
+    ///
+    /// The first byte of the result is always [PROOF_FORMAT_VERSION].
     pub fn to_bytes(&self) -> Vec<u8> {
-        let mut result = Vec::new();
+        let mut result = vec![PROOF_FORMAT_VERSION];
         self.context.write_into(&mut result);
-        self.commitments.write_into(&mut result);
-        self.trace_queries.write_into(&mut result);
-        self.constraint_queries.write_into(&mut result);
-        self.ood_frame.write_into(&mut result);
-        self.fri_proof.write_into(&mut result);
-        result.extend_from_slice(&self.pow_nonce.to_le_bytes());
+        self.write_body(&mut result);
         result
     }
 
     /// Returns a STARK proof read from the specified `source`.
     ///
     /// # Errors
-    /// Returns an error of a valid STARK proof could not be read from the specified `source`.
+    /// Returns an error if:
+    /// * The proof was serialized using a format version other than [PROOF_FORMAT_VERSION].
+    /// * A valid STARK proof could not be read from the specified `source`.
     pub fn from_bytes(source: &[u8]) -> Result<Self, DeserializationError> {
         let mut source = SliceReader::new(source);
 
-        // parse the context
+        // make sure the proof was serialized using the current format
+        let version = source.read_u8()?;
+        if version != PROOF_FORMAT_VERSION {
+            return Err(DeserializationError::InvalidValue(format!(
+                "proof format version {} is not supported; expected version {}",
+                version, PROOF_FORMAT_VERSION
+            )));
+        }
+
+        // parse the context and the rest of the proof
         let context = Context::read_from(&mut source)?;
+        let proof = Self::read_body(context, &mut source)?;
+        if source.has_more_bytes() {
+            return Err(DeserializationError::UnconsumedBytes);
+        }
+        Ok(proof)
+    }
+
+    /// Checks that this proof survives a round trip through its binary and JSON encodings.
+    ///
+    /// For each encoding, the proof is serialized, deserialized back, and serialized again; the
+    /// check succeeds if the deserialized proof is equal to this proof and both serializations
+    /// are identical. Proof encodings do not depend on the byte order or the pointer width of the
+    /// target, and thus, a proof passing this check can be verified on any other target.
+    ///
+    /// # Errors
+    /// Returns an error if the proof could not be read back from either of its encodings, or if
+    /// the proof read back differs from this proof.
+    pub fn check_encoding(&self) -> Result<(), DeserializationError> {
+        let bytes = self.to_bytes();
+        let decoded = Self::from_bytes(&bytes)?;
+        if decoded != *self || decoded.to_bytes() != bytes {
+            return Err(DeserializationError::InvalidValue(
+                "proof changed after a round trip through its binary encoding".into(),
+            ));
+        }
 
+        let json = self.to_json();
+        let decoded = Self::from_json(&json)?;
+        if decoded != *self || decoded.to_json() != json {
+            return Err(DeserializationError::InvalidValue(
+                "proof changed after a round trip through its JSON encoding".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Writes all components of this proof except for the context into the specified `target`.
+    fn write_body<W: ByteWriter>(&self, target: &mut W) {
+        self.commitments.write_into(target);
+        self.trace_queries.write_into(target);
+        self.constraint_queries.write_into(target);
+        self.ood_frame.write_into(target);
+        self.fri_proof.write_into(target);
+        target.write_u64(self.pow_nonce);
+    }
+
+    /// Reads all components of a proof except for the context from the specified `source`, and
+    /// returns a proof assembled from these components and the specified `context`.
+    fn read_body<R: ByteReader>(
+        context: Context,
+        source: &mut R,
+    ) -> Result<Self, DeserializationError> {
         // parse the commitments
-        let commitments = Commitments::read_from(&mut source)?;
+        let commitments = Commitments::read_from(source)?;
 
         // parse trace queries
         let num_trace_segments = context.trace_layout().num_segments();
         let mut trace_queries = Vec::with_capacity(num_trace_segments);
         for _ in 0..num_trace_segments {
-            trace_queries.push(Queries::read_from(&mut source)?);
+            trace_queries.push(Queries::read_from(source)?);
         }
 
         // parse the rest of the proof
@@ -158,16 +315,37 @@ impl StarkProof {
             context,
             commitments,
             trace_queries,
-            constraint_queries: Queries::read_from(&mut source)?,
-            ood_frame: OodFrame::read_from(&mut source)?,
-            fri_proof: FriProof::read_from(&mut source)?,
+            constraint_queries: Queries::read_from(source)?,
+            ood_frame: OodFrame::read_from(source)?,
+            fri_proof: FriProof::read_from(source)?,
             pow_nonce: source.read_u64()?,
         };
-        if source.has_more_bytes() {
-            return Err(DeserializationError::UnconsumedBytes);
-        }
+        proof.validate_shape()?;
         Ok(proof)
     }
+
+    /// Makes sure that the number of proof components is consistent with the proof context.
+    fn validate_shape(&self) -> Result<(), DeserializationError> {
+        let lde_domain_size = self.lde_domain_size();
+        let fri_options = self.options().to_fri_options();
+        let num_fri_layers = fri_options.num_fri_layers(lde_domain_size);
+        if self.fri_proof.num_layers() != num_fri_layers {
+            return Err(DeserializationError::InvalidValue(format!(
+                "expected {} FRI layers for LDE domain of size {}, but was {}",
+                num_fri_layers,
+                lde_domain_size,
+                self.fri_proof.num_layers()
+            )));
+        }
+        if self.fri_proof.num_partitions() > fri_options.fri_remainder_size(lde_domain_size) {
+            return Err(DeserializationError::InvalidValue(format!(
+                "number of FRI partitions cannot exceed {}, but was {}",
+                fri_options.fri_remainder_size(lde_domain_size),
+                self.fri_proof.num_partitions()
+            )));
+        }
+        Ok(())
+    }
 }
 
 // HELPER FUNCTIONS
@@ -198,3 +376,100 @@ fn get_conjectured_security(
         collision_resistance,
     )
 }
+
+/// Computes proven security level for the specified proof parameters.
+///
+/// The estimate follows the soundness analysis of the ethSTARK protocol (Theorem 2 in
+/// <https://eprint.iacr.org/2021/582>) in the list-decoding regime: for a list-decoding parameter
+/// `m`, the protocol error is bounded by the largest of the ALI, DEEP, FRI commit phase, and FRI
+/// query phase errors. The parameter `m` is chosen to maximize the resulting security level.
+fn get_proven_security(
+    options: &ProofOptions,
+    base_field_bits: u32,
+    trace_length: u64,
+    lde_domain_size: u64,
+    collision_resistance: u32,
+) -> u32 {
+    let protocol_security = (MIN_LIST_DECODING_PARAM..=MAX_LIST_DECODING_PARAM)
+        .map(|m| {
+            get_proven_security_for_m(options, base_field_bits, trace_length, lde_domain_size, m)
+        })
+        .fold(0.0, f64::max);
+
+    cmp::min(protocol_security as u32, collision_resistance)
+}
+
+/// Computes proven security level (in bits, possibly fractional or negative) for the specified
+/// proof parameters and list-decoding parameter `m`.
+fn get_proven_security_for_m(
+    options: &ProofOptions,
+    base_field_bits: u32,
+    trace_length: u64,
+    lde_domain_size: u64,
+    m: u32,
+) -> f64 {
+    let field_bits = (base_field_bits * options.field_extension().degree()) as f64;
+    let m = m as f64;
+    let log_rho = -(log2(options.blowup_factor()) as f64);
+
+    // list size implied by the Johnson bound: l = (m + 1/2) / sqrt(rho)
+    let log_list_size = log2_f64(m + 0.5) - log_rho / 2.0;
+
+    // ALI error: l / |F|
+    let ali_security = field_bits - log_list_size;
+
+    // DEEP error: l * (trace_length + 1) / |F|; the +1 accounts for the two out-of-domain
+    // openings of each trace polynomial
+    let deep_security = field_bits - log_list_size - log2_f64((trace_length + 1) as f64);
+
+    // FRI commit phase error: (m + 1/2)^7 / (3 * rho^(3/2)) * n^2 / |F|
+    let commit_security = field_bits
+        - (7.0 * log2_f64(m + 0.5) - log2_f64(3.0) - 1.5 * log_rho
+            + 2.0 * log2_f64(lde_domain_size as f64));
+
+    // FRI query phase error: alpha^q with alpha = (1 + 1/(2m)) * sqrt(rho); grinding increases
+    // the cost of each query attempt
+    let log_alpha = log2_f64(1.0 + 0.5 / m) + log_rho / 2.0;
+    let query_security =
+        -log_alpha * options.num_queries() as f64 + options.grinding_factor() as f64;
+
+    ali_security
+        .min(deep_security)
+        .min(commit_security)
+        .min(query_security)
+}
+
+/// Returns base 2 logarithm of a positive `x`.
+///
+/// This is used instead of `f64::log2()` which is not available in `no_std` environments. The
+/// value is split into an exponent and a mantissa in [1, 2), and the logarithm of the mantissa is
+/// computed from the rapidly converging series ln(v) = 2 * atanh((v - 1) / (v + 1)).
+fn log2_f64(x: f64) -> f64 {
+    debug_assert!(x > 0.0, "logarithm is defined only for positive values");
+    let bits = x.to_bits();
+    let exponent = ((bits >> 52) & 0x7ff) as i64 - 1023;
+    let mantissa = f64::from_bits((bits & 0x000f_ffff_ffff_ffff) | 0x3ff0_0000_0000_0000);
+
+    let t = (mantissa - 1.0) / (mantissa + 1.0);
+    let t2 = t * t;
+    let mut term = t;
+    let mut ln = 0.0;
+    let mut k = 1.0;
+    while term.abs() > 1e-17 {
+        ln += term / k;
+        term *= t2;
+        k += 2.0;
+    }
+
+    exponent as f64 + 2.0 * ln / core::f64::consts::LN_2
+}
+
+/// Reads a proof component from `bytes` making sure all bytes have been consumed.
+fn read_component<T: Deserializable>(bytes: &[u8]) -> Result<T, DeserializationError> {
+    let mut source = SliceReader::new(bytes);
+    let result = T::read_from(&mut source)?;
+    if source.has_more_bytes() {
+        return Err(DeserializationError::UnconsumedBytes);
+    }
+    Ok(result)
+}
@@ -0,0 +1,372 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    json::{required_field, set_field, write_hex_string, JsonReader},
+    StarkProof,
+};
+use crate::{FieldExtension, PolicyError};
+use crypto::{HashFunction, Hasher};
+use math::StarkField;
+use utils::{
+    collections::Vec,
+    string::{String, ToString},
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable, SliceReader,
+};
+
+// VERIFIER POLICY
+// ================================================================================================
+
+/// A set of requirements which a proof must satisfy to be accepted by a verifier.
+///
+/// A policy specifies minimum conjectured and proven security levels of a proof, as well as the
+/// hash functions, base fields, and field extensions with which a proof may be generated. An empty
+/// list of hash functions, fields, or field extensions allows any value; thus, a policy created
+/// via [new()](VerifierPolicy::new) accepts all proofs.
+///
+/// Policies can be serialized into bytes (via [Serializable]) or into JSON (via
+/// [to_json()](VerifierPolicy::to_json)), and thus, can be distributed to verifiers as
+/// configuration files rather than compiled into them. Serialized policies do not contain any
+/// authentication data; if a policy file needs to be signed, the signature should be computed
+/// over the bytes returned by [to_bytes()](Serializable::to_bytes).
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct VerifierPolicy {
+    min_conjectured_security: u32,
+    min_proven_security: u32,
+    hash_functions: Vec<HashFunction>,
+    field_moduli: Vec<Vec<u8>>,
+    field_extensions: Vec<FieldExtension>,
+}
+
+impl VerifierPolicy {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new policy which accepts all proofs.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a new policy with the minimum conjectured security level set to the specified
+    /// number of bits.
+    pub fn with_min_conjectured_security(mut self, bits: u32) -> Self {
+        self.min_conjectured_security = bits;
+        self
+    }
+
+    /// Returns a new policy with the minimum proven security level set to the specified number of
+    /// bits.
+    pub fn with_min_proven_security(mut self, bits: u32) -> Self {
+        self.min_proven_security = bits;
+        self
+    }
+
+    /// Returns a new policy which accepts only proofs generated with one of the specified hash
+    /// functions.
+    pub fn with_hash_functions(mut self, hash_functions: &[HashFunction]) -> Self {
+        self.hash_functions = hash_functions.to_vec();
+        self
+    }
+
+    /// Returns a new policy which, in addition to the fields already allowed by this policy,
+    /// accepts proofs generated in the base field `B`.
+    pub fn with_field<B: StarkField>(mut self) -> Self {
+        let modulus = B::get_modulus_le_bytes();
+        if !self.field_moduli.contains(&modulus) {
+            self.field_moduli.push(modulus);
+        }
+        self
+    }
+
+    /// Returns a new policy which accepts only proofs generated with one of the specified field
+    /// extensions.
+    pub fn with_field_extensions(mut self, field_extensions: &[FieldExtension]) -> Self {
+        self.field_extensions = field_extensions.to_vec();
+        self
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the minimum conjectured security level (in bits) required by this policy.
+    pub fn min_conjectured_security(&self) -> u32 {
+        self.min_conjectured_security
+    }
+
+    /// Returns the minimum proven security level (in bits) required by this policy.
+    pub fn min_proven_security(&self) -> u32 {
+        self.min_proven_security
+    }
+
+    /// Returns the hash functions allowed by this policy; an empty list allows any hash function.
+    pub fn hash_functions(&self) -> &[HashFunction] {
+        &self.hash_functions
+    }
+
+    /// Returns little-endian bytes of moduli of the base fields allowed by this policy; an empty
+    /// list allows any field.
+    pub fn field_moduli(&self) -> &[Vec<u8>] {
+        &self.field_moduli
+    }
+
+    /// Returns the field extensions allowed by this policy; an empty list allows any extension.
+    pub fn field_extensions(&self) -> &[FieldExtension] {
+        &self.field_extensions
+    }
+
+    // POLICY ENFORCEMENT
+    // --------------------------------------------------------------------------------------------
+
+    /// Checks whether the specified `proof` satisfies this policy.
+    ///
+    /// `H` must be the hash function used to generate the proof; its collision resistance bounds
+    /// the security level of the proof.
+    ///
+    /// # Errors
+    /// Returns an error if `H` is not the hash function recorded in the proof, if the proof was
+    /// generated with a hash function, a base field, or a field extension not allowed by this
+    /// policy, or if the security level of the proof is lower than the minimum required by this
+    /// policy.
+    pub fn check<H: Hasher>(&self, proof: &StarkProof) -> Result<(), PolicyError> {
+        let hash_function = proof.context.hash_function();
+        if hash_function != H::ID {
+            return Err(PolicyError::HashFunctionMismatch(hash_function, H::ID));
+        }
+        if !self.hash_functions.is_empty() && !self.hash_functions.contains(&hash_function) {
+            return Err(PolicyError::HashFunctionNotAllowed(hash_function));
+        }
+
+        let modulus = proof.context.field_modulus_bytes();
+        if !self.field_moduli.is_empty() && !self.field_moduli.iter().any(|m| m == modulus) {
+            return Err(PolicyError::FieldNotAllowed);
+        }
+
+        let field_extension = proof.options().field_extension();
+        if !self.field_extensions.is_empty() && !self.field_extensions.contains(&field_extension) {
+            return Err(PolicyError::FieldExtensionNotAllowed(field_extension));
+        }
+
+        let conjectured_security = proof.security_level::<H>(true);
+        if conjectured_security < self.min_conjectured_security {
+            return Err(PolicyError::InsufficientConjecturedSecurity(
+                self.min_conjectured_security,
+                conjectured_security,
+            ));
+        }
+
+        // proven security is more expensive to estimate, and thus, it is computed only when
+        // required by the policy
+        if self.min_proven_security > 0 {
+            let proven_security = proof.security_level::<H>(false);
+            if proven_security < self.min_proven_security {
+                return Err(PolicyError::InsufficientProvenSecurity(
+                    self.min_proven_security,
+                    proven_security,
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    // JSON EXPORT / IMPORT
+    // --------------------------------------------------------------------------------------------
+
+    /// Serializes this policy into a JSON string.
+    ///
+    /// The result is an object with the following fields:
+    /// * `min_conjectured_security`, `min_proven_security` - minimum security levels in bits.
+    /// * `hash_functions` - an array of identifiers of allowed hash functions (as defined by
+    ///   [HashFunction]).
+    /// * `field_moduli` - an array of hex-encoded little-endian bytes of moduli of allowed base
+    ///   fields.
+    /// * `field_extensions` - an array of degrees of allowed field extensions.
+    pub fn to_json(&self) -> String {
+        let mut result = String::from("{");
+        result.push_str(&format!(
+            "\"min_conjectured_security\":{},\"min_proven_security\":{},",
+            self.min_conjectured_security, self.min_proven_security
+        ));
+        let ids = self.hash_functions.iter().map(|h| h.id());
+        write_number_array(&mut result, "hash_functions", ids);
+        result.push_str(",\"field_moduli\":[");
+        for (i, modulus) in self.field_moduli.iter().enumerate() {
+            if i > 0 {
+                result.push(',');
+            }
+            write_hex_string(&mut result, modulus);
+        }
+        result.push_str("],");
+        let degrees = self.field_extensions.iter().map(|&e| e as u8);
+        write_number_array(&mut result, "field_extensions", degrees);
+        result.push('}');
+        result
+    }
+
+    /// Returns a policy read from a JSON string in the format produced by
+    /// [to_json()](VerifierPolicy::to_json).
+    ///
+    /// All fields must be present; fields may appear in any order and may be separated by
+    /// arbitrary whitespace.
+    ///
+    /// # Errors
+    /// Returns an error if the `source` is not a JSON object with exactly the fields listed in
+    /// [to_json()](VerifierPolicy::to_json), or if any of the fields contains an invalid value.
+    pub fn from_json(source: &str) -> Result<Self, DeserializationError> {
+        let mut reader = JsonReader::new(source);
+        let mut min_conjectured_security = None;
+        let mut min_proven_security = None;
+        let mut hash_functions = None;
+        let mut field_moduli = None;
+        let mut field_extensions = None;
+
+        reader.expect(b'{')?;
+        loop {
+            let key = reader.read_string()?;
+            reader.expect(b':')?;
+            match key {
+                "min_conjectured_security" => {
+                    set_field(&mut min_conjectured_security, key, reader.read_number()?)?
+                }
+                "min_proven_security" => {
+                    set_field(&mut min_proven_security, key, reader.read_number()?)?
+                }
+                "hash_functions" => {
+                    set_field(&mut hash_functions, key, reader.read_number_array()?)?
+                }
+                "field_moduli" => set_field(&mut field_moduli, key, reader.read_hex_array()?)?,
+                "field_extensions" => {
+                    set_field(&mut field_extensions, key, reader.read_number_array()?)?
+                }
+                _ => {
+                    return Err(DeserializationError::InvalidValue(format!(
+                        "unexpected policy field '{}'",
+                        key
+                    )))
+                }
+            }
+            if !reader.next_list_item(b'}')? {
+                break;
+            }
+        }
+        reader.expect_end()?;
+
+        // re-use binary deserialization to validate all values
+        let mut bytes = Vec::new();
+        for value in [
+            required_field(min_conjectured_security, "min_conjectured_security")?,
+            required_field(min_proven_security, "min_proven_security")?,
+        ] {
+            let value = u32::try_from(value).map_err(|_| {
+                DeserializationError::InvalidValue(format!("security level {value} is too large"))
+            })?;
+            bytes.write_u32(value);
+        }
+        write_enum_values(
+            &mut bytes,
+            required_field(hash_functions, "hash_functions")?,
+        )?;
+        let field_moduli = required_field(field_moduli, "field_moduli")?;
+        bytes.write_usize(field_moduli.len());
+        for modulus in field_moduli {
+            if modulus.len() > u8::MAX as usize {
+                return Err(DeserializationError::InvalidValue(
+                    "field modulus cannot be longer than 255 bytes".to_string(),
+                ));
+            }
+            bytes.write_u8(modulus.len() as u8);
+            bytes.write_u8_slice(&modulus);
+        }
+        write_enum_values(
+            &mut bytes,
+            required_field(field_extensions, "field_extensions")?,
+        )?;
+
+        let mut source = SliceReader::new(&bytes);
+        Self::read_from(&mut source)
+    }
+}
+
+impl Serializable for VerifierPolicy {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u32(self.min_conjectured_security);
+        target.write_u32(self.min_proven_security);
+        target.write_usize(self.hash_functions.len());
+        for hash_function in self.hash_functions.iter() {
+            hash_function.write_into(target);
+        }
+        target.write_usize(self.field_moduli.len());
+        for modulus in self.field_moduli.iter() {
+            target.write_u8(modulus.len() as u8);
+            target.write_u8_slice(modulus);
+        }
+        target.write_usize(self.field_extensions.len());
+        for field_extension in self.field_extensions.iter() {
+            field_extension.write_into(target);
+        }
+    }
+}
+
+impl Deserializable for VerifierPolicy {
+    /// Reads a verifier policy from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if a valid policy could not be read from the specified `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let min_conjectured_security = source.read_u32()?;
+        let min_proven_security = source.read_u32()?;
+
+        // list lengths are not used to pre-allocate memory as they come from an untrusted source
+        let mut hash_functions = Vec::new();
+        for _ in 0..source.read_usize()? {
+            hash_functions.push(HashFunction::read_from(source)?);
+        }
+
+        let mut field_moduli = Vec::new();
+        for _ in 0..source.read_usize()? {
+            let num_modulus_bytes = source.read_u8()? as usize;
+            if num_modulus_bytes == 0 {
+                return Err(DeserializationError::InvalidValue(
+                    "field modulus cannot be an empty value".to_string(),
+                ));
+            }
+            field_moduli.push(source.read_u8_vec(num_modulus_bytes)?);
+        }
+
+        let mut field_extensions = Vec::new();
+        for _ in 0..source.read_usize()? {
+            field_extensions.push(FieldExtension::read_from(source)?);
+        }
+
+        Ok(VerifierPolicy {
+            min_conjectured_security,
+            min_proven_security,
+            hash_functions,
+            field_moduli,
+            field_extensions,
+        })
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn write_number_array(target: &mut String, name: &str, values: impl Iterator<Item = u8>) {
+    let values = values.map(|v| v.to_string()).collect::<Vec<_>>();
+    target.push_str(&format!("\"{}\":[{}]", name, values.join(",")));
+}
+
+/// Writes a list of single-byte enum values parsed from JSON numbers into the `target` in the
+/// binary format of the policy.
+fn write_enum_values(target: &mut Vec<u8>, values: Vec<u64>) -> Result<(), DeserializationError> {
+    target.write_usize(values.len());
+    for value in values {
+        let value = u8::try_from(value).map_err(|_| {
+            DeserializationError::InvalidValue(format!("policy value {value} is out of range"))
+        })?;
+        target.write_u8(value);
+    }
+    Ok(())
+}
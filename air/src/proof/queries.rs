@@ -79,11 +79,25 @@ impl Queries {
         Queries { paths, values }
     }
 
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of bytes used to encode query values.
+    ///
+    /// This can be used to infer the number of queries before the queries are parsed, e.g., when
+    /// the number of distinct leaves opened by a query depends on query positions.
+    pub fn num_value_bytes(&self) -> usize {
+        self.values.len()
+    }
+
     // PARSER
     // --------------------------------------------------------------------------------------------
     /// Convert internally stored bytes into a set of query values and the corresponding Merkle
     /// authentication paths.
     ///
+    /// The `merkle_arity` specifies the arity of the Merkle tree from which the authentication
+    /// paths were generated.
+    ///
     /// # Panics
     /// Panics if:
     /// * `domain_size` is not a power of two.
@@ -94,6 +108,7 @@ impl Queries {
         domain_size: usize,
         num_queries: usize,
         values_per_query: usize,
+        merkle_arity: usize,
     ) -> Result<(BatchMerkleProof<H>, Table<E>), DeserializationError>
     where
         E: FieldElement,
@@ -131,7 +146,12 @@ impl Queries {
         // build batch Merkle proof
         let mut reader = SliceReader::new(&self.paths);
         let tree_depth = log2(domain_size) as u8;
-        let merkle_proof = BatchMerkleProof::deserialize(&mut reader, hashed_queries, tree_depth)?;
+        let merkle_proof = BatchMerkleProof::deserialize_with_arity(
+            &mut reader,
+            hashed_queries,
+            tree_depth,
+            merkle_arity as u8,
+        )?;
         if reader.has_more_bytes() {
             return Err(DeserializationError::UnconsumedBytes);
         }
@@ -144,11 +164,11 @@ impl Serializable for Queries {
     /// Serializes `self` and writes the resulting bytes into the `target`.
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
         // write value bytes
-        target.write_u32(self.values.len() as u32);
+        target.write_usize(self.values.len());
         target.write_u8_slice(&self.values);
 
         // write path bytes
-        target.write_u32(self.paths.len() as u32);
+        target.write_usize(self.paths.len());
         target.write_u8_slice(&self.paths);
     }
 }
@@ -160,13 +180,34 @@ impl Deserializable for Queries {
     /// Returns an error of a valid query struct could not be read from the specified source.
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
         // read values
-        let num_value_bytes = source.read_u32()?;
-        let values = source.read_u8_vec(num_value_bytes as usize)?;
+        let num_value_bytes = source.read_usize()?;
+        let values = source.read_u8_vec(num_value_bytes)?;
 
         // read paths
-        let num_paths_bytes = source.read_u32()?;
-        let paths = source.read_u8_vec(num_paths_bytes as usize)?;
+        let num_paths_bytes = source.read_usize()?;
+        let paths = source.read_u8_vec(num_paths_bytes)?;
 
         Ok(Queries { paths, values })
     }
 }
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns positions of Merkle tree leaves which need to be opened to decommit to rows at the
+/// specified `positions` when each leaf commits to `rows_per_leaf` adjacent rows.
+///
+/// The row at position *i* is contained in the leaf at position *i* / `rows_per_leaf`. Each leaf
+/// position is listed only once, in the order in which it is first referenced by `positions`;
+/// thus, when `rows_per_leaf` is one, the returned positions are the same as `positions`
+/// (assuming `positions` does not contain duplicates).
+pub fn get_leaf_positions(positions: &[usize], rows_per_leaf: usize) -> Vec<usize> {
+    let mut result = Vec::with_capacity(positions.len());
+    for &position in positions.iter() {
+        let leaf_idx = position / rows_per_leaf;
+        if !result.contains(&leaf_idx) {
+            result.push(leaf_idx);
+        }
+    }
+    result
+}
@@ -0,0 +1,293 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{Context, Vec};
+use crate::{ProofOptions, TraceInfo};
+use core::fmt;
+use crypto::HashFunction;
+use math::{log2, StarkField};
+use utils::Serializable;
+
+// PROOF STATS
+// ================================================================================================
+/// Breakdown of the size of a serialized STARK proof by proof component.
+///
+/// All sizes are in bytes and include length prefixes of the corresponding components. The sizes
+/// add up to the length of the vector returned by [StarkProof::to_bytes()](super::StarkProof::to_bytes).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofStats {
+    /// Total size of the serialized proof.
+    pub total: usize,
+    /// Size of the proof context (trace info, proof options, and public inputs digest).
+    pub context: usize,
+    /// Size of commitments to the trace, constraint evaluations, and FRI layers.
+    pub commitments: usize,
+    /// Size of trace queries for each trace segment.
+    pub trace_queries: Vec<usize>,
+    /// Size of constraint evaluation queries.
+    pub constraint_queries: usize,
+    /// Size of the out-of-domain evaluation frame.
+    pub ood_frame: usize,
+    /// Size of each FRI layer (query values and Merkle authentication paths).
+    pub fri_layers: Vec<usize>,
+    /// Size of the FRI remainder.
+    pub fri_remainder: usize,
+    /// Size of everything else: format version, FRI layer count and number of partitions, and
+    /// proof-of-work nonce.
+    pub other: usize,
+    /// Number of queries made by the verifier.
+    pub num_queries: usize,
+}
+
+impl ProofStats {
+    // ESTIMATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns an estimate of the size of a proof for a computation with the specified trace
+    /// and constraint composition blowup factor, generated with the specified `options` over
+    /// base field `B` using a hash function with digests of `digest_size` bytes.
+    ///
+    /// This does not require generating a proof. The sizes of the context, commitments, OOD
+    /// frame, and FRI remainder are exact; the sizes of queries depend on how many Merkle
+    /// authentication path nodes are shared between queries, and are estimated assuming that
+    /// query positions are uniformly random. The actual size deviates from the estimate by the
+    /// variation in the number of shared nodes; relative to the proof size, this deviation is
+    /// small for all but the smallest proofs.
+    ///
+    /// `ce_blowup_factor` is the blowup factor of the constraint evaluation domain (i.e., the
+    /// number of columns into which the constraint composition polynomial is split); for an
+    /// existing [Air](crate::Air) this is returned by
+    /// [Air::ce_blowup_factor()](crate::Air::ce_blowup_factor).
+    pub fn estimate<B: StarkField>(
+        trace_info: &TraceInfo,
+        ce_blowup_factor: usize,
+        options: &ProofOptions,
+        digest_size: usize,
+    ) -> Self {
+        let layout = trace_info.layout();
+        let base_bytes = B::ELEMENT_BYTES;
+        let ext_bytes = B::ELEMENT_BYTES * options.field_extension().degree() as usize;
+        let num_queries = options.num_queries();
+        let merkle_arity = options.merkle_arity();
+        let lde_domain_size = trace_info.length() * options.blowup_factor();
+        let fri_options = options.to_fri_options();
+        let num_fri_layers = fri_options.num_fri_layers(lde_domain_size);
+
+        // context is computed exactly by serializing a context of the same shape
+        let context = Context::new::<B>(
+            trace_info,
+            options.clone(),
+            HashFunction::Unspecified,
+            vec![0; digest_size],
+        );
+        let context = context.to_bytes().len();
+
+        // one commitment per trace segment, constraint evaluations, each FRI layer, and the FRI
+        // remainder
+        let num_commitments = layout.num_segments() + num_fri_layers + 2;
+        let commitments = length_prefixed(num_commitments * digest_size);
+
+        // trace and constraint queries are made against the same positions in the LDE domain
+        let paths = estimate_paths(
+            lde_domain_size,
+            lde_domain_size,
+            num_queries,
+            merkle_arity,
+            digest_size,
+        );
+        let num_positions = num_queries as f64;
+        let queries = |value_bytes: usize| {
+            length_prefixed(round(num_positions * value_bytes as f64)) + length_prefixed(paths)
+        };
+
+        // each trace leaf may contain several adjacent rows, and positions falling into the same
+        // leaf are opened only once
+        let rows_per_leaf = options.trace_rows_per_leaf();
+        let num_trace_leaves = lde_domain_size / rows_per_leaf;
+        let num_trace_positions = expected_distinct(num_trace_leaves, lde_domain_size, num_queries);
+        let trace_paths = estimate_paths(
+            num_trace_leaves,
+            lde_domain_size,
+            num_queries,
+            merkle_arity,
+            digest_size,
+        );
+        let trace_segment_queries = |row_bytes: usize| {
+            let values = round(num_trace_positions * (rows_per_leaf * row_bytes) as f64);
+            length_prefixed(values) + length_prefixed(trace_paths)
+        };
+        let mut trace_queries = vec![trace_segment_queries(
+            layout.main_trace_width() * base_bytes,
+        )];
+        for i in 0..layout.num_aux_segments() {
+            trace_queries.push(trace_segment_queries(
+                layout.get_aux_segment_width(i) * ext_bytes,
+            ));
+        }
+        let constraint_queries = queries(ce_blowup_factor * ext_bytes);
+
+        // the OOD frame contains two rows of the trace and a row of constraint evaluations
+        let ood_frame = length_prefixed(2 * trace_info.width() * ext_bytes)
+            + length_prefixed(ce_blowup_factor * ext_bytes);
+
+        // each FRI layer contains folding_factor values per distinct folded position; positions
+        // queried in the LDE domain are distinct, but folded positions may coincide
+        let folding_factor = fri_options.folding_factor();
+        let mut fri_layers = Vec::with_capacity(num_fri_layers);
+        let mut domain_size = lde_domain_size;
+        for _ in 0..num_fri_layers {
+            let num_leaves = domain_size / folding_factor;
+            let num_positions = expected_distinct(num_leaves, lde_domain_size, num_queries);
+            let values = round(num_positions * (folding_factor * ext_bytes) as f64);
+            let paths = estimate_paths(
+                num_leaves,
+                lde_domain_size,
+                num_queries,
+                merkle_arity,
+                digest_size,
+            );
+            fri_layers.push(length_prefixed(values) + length_prefixed(paths));
+            domain_size = num_leaves;
+        }
+        let fri_remainder = length_prefixed(domain_size * ext_bytes);
+
+        // format version, number of FRI layers, number of FRI partitions, and proof-of-work nonce
+        let other = 1 + 1 + 1 + 8;
+
+        let mut stats = ProofStats {
+            total: 0,
+            context,
+            commitments,
+            trace_queries,
+            constraint_queries,
+            ood_frame,
+            fri_layers,
+            fri_remainder,
+            other,
+            num_queries,
+        };
+        stats.total = stats.context
+            + stats.commitments
+            + stats.trace_queries_total()
+            + stats.constraint_queries
+            + stats.ood_frame
+            + stats.fri_total()
+            + stats.other;
+        stats
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of FRI layers in the proof.
+    pub fn num_fri_layers(&self) -> usize {
+        self.fri_layers.len()
+    }
+
+    /// Returns the combined size of all trace segment queries.
+    pub fn trace_queries_total(&self) -> usize {
+        self.trace_queries.iter().sum()
+    }
+
+    /// Returns the combined size of all FRI layers and the FRI remainder.
+    pub fn fri_total(&self) -> usize {
+        self.fri_layers.iter().sum::<usize>() + self.fri_remainder
+    }
+}
+
+impl fmt::Display for ProofStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut line = |name: &str, size: usize| {
+            let percent = size as f64 * 100.0 / self.total as f64;
+            writeln!(f, "{:<24}{:>10} bytes ({:>5.1}%)", name, size, percent)
+        };
+        line("context", self.context)?;
+        line("commitments", self.commitments)?;
+        for (i, &size) in self.trace_queries.iter().enumerate() {
+            line(&format!("trace queries [{}]", i), size)?;
+        }
+        line("constraint queries", self.constraint_queries)?;
+        line("ood frame", self.ood_frame)?;
+        for (i, &size) in self.fri_layers.iter().enumerate() {
+            line(&format!("fri layer [{}]", i), size)?;
+        }
+        line("fri remainder", self.fri_remainder)?;
+        line("other", self.other)?;
+        write!(
+            f,
+            "{:<24}{:>10} bytes ({} queries, {} FRI layers)",
+            "total",
+            self.total,
+            self.num_queries,
+            self.num_fri_layers()
+        )
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the size of a byte string of the specified length together with its length prefix.
+fn length_prefixed(num_bytes: usize) -> usize {
+    let num_bits = (usize::BITS - num_bytes.leading_zeros()).max(1) as usize;
+    (num_bits + 6) / 7 + num_bytes
+}
+
+/// Returns the expected number of groups hit by `num_draws` distinct values drawn uniformly at
+/// random from a domain of `domain_size` values split into `num_groups` groups of equal size.
+fn expected_distinct(num_groups: usize, domain_size: usize, num_draws: usize) -> f64 {
+    // probability that none of the draws falls into a given group
+    let group_size = domain_size / num_groups;
+    let mut p_missed = 1.0;
+    for i in 0..num_draws {
+        if domain_size < group_size + i + 1 {
+            p_missed = 0.0;
+            break;
+        }
+        p_missed *= (domain_size - group_size - i) as f64 / (domain_size - i) as f64;
+    }
+    num_groups as f64 * (1.0 - p_missed)
+}
+
+/// Returns the expected size of serialized internal nodes of a batch Merkle proof for a tree with
+/// `num_leaves` leaves, where the queried leaves are determined by `num_queries` distinct random
+/// positions in a domain of `domain_size` values (each leaf covering an equal part of the domain).
+///
+/// At every level of the tree, a proof must include all siblings of the nodes on the paths from
+/// the queried leaves to the root which are not themselves on one of these paths.
+fn estimate_paths(
+    num_leaves: usize,
+    domain_size: usize,
+    num_queries: usize,
+    arity: usize,
+    digest_size: usize,
+) -> usize {
+    let depth = (log2(num_leaves) / log2(arity)) as usize;
+    let num_distinct_leaves = expected_distinct(num_leaves, domain_size, num_queries);
+
+    let mut num_nodes = 0.0;
+    let mut num_children = num_distinct_leaves;
+    let mut level_size = num_leaves;
+    for _ in 0..depth {
+        level_size /= arity;
+        let num_parents = expected_distinct(level_size, domain_size, num_queries);
+        num_nodes += arity as f64 * num_parents - num_children;
+        num_children = num_parents;
+    }
+
+    // binary proofs record the number of nodes for each distinct leaf in a single byte, while
+    // proofs for trees of higher arity record the number of nodes for each level in two bytes
+    let num_lengths = if arity == 2 {
+        num_distinct_leaves
+    } else {
+        2.0 * depth as f64
+    };
+    1 + round(num_nodes * digest_size as f64 + num_lengths)
+}
+
+/// Rounds a non-negative value to the nearest integer; f64::round() is not available in no_std.
+fn round(value: f64) -> usize {
+    (value + 0.5) as usize
+}
@@ -10,8 +10,9 @@ use math::FieldElement;
 // CONSTANTS
 // ================================================================================================
 
-const MAX_ROWS: usize = 255;
-const MAX_COLS: usize = 255;
+/// Trace segments can have up to 255 columns, and up to 16 adjacent trace rows can be committed
+/// to in a single Merkle tree leaf.
+const MAX_COLS: usize = 255 * 16;
 
 // TABLE
 // ================================================================================================
@@ -34,8 +35,8 @@ impl<E: FieldElement> Table<E> {
     ///
     /// # Panics
     /// Panics if:
-    /// * Specified number of rows is 0 or greater than 255.
-    /// * Specified number of columns is 0 or greater than 255.
+    /// * Specified number of rows is 0.
+    /// * Specified number of columns is 0 or greater than 4080.
     /// * Provided bytes do not encode valid field elements required to fill the table.
     pub fn from_bytes(
         bytes: &[u8],
@@ -43,13 +44,9 @@ impl<E: FieldElement> Table<E> {
         num_cols: usize,
     ) -> Result<Self, DeserializationError> {
         assert!(num_rows > 0, "number of rows must be greater than 0");
-        assert!(
-            num_rows < MAX_ROWS,
-            "number of rows cannot exceed {MAX_ROWS}, but was {num_rows}"
-        );
         assert!(num_cols > 0, "number of columns must be greater than 0");
         assert!(
-            num_cols < MAX_ROWS,
+            num_cols <= MAX_COLS,
             "number of columns cannot exceed {MAX_COLS}, but was {num_cols}"
         );
 
@@ -81,13 +78,49 @@ impl<E: FieldElement> Table<E> {
     }
 
     /// Returns an iterator over rows of this table.
-    pub fn rows(&self) -> RowIterator<E> {
+    pub fn rows(&self) -> RowIterator<'_, E> {
         RowIterator::new(self)
     }
 
     // TABLE PROCESSING
     // --------------------------------------------------------------------------------------------
 
+    /// Returns a table in which each row of this table is split into `num_parts` rows of equal
+    /// width; thus, row `i` of this table becomes rows `i * num_parts..(i + 1) * num_parts` of
+    /// the returned table.
+    ///
+    /// This is used to unpack queried Merkle tree leaves which contain multiple adjacent rows of
+    /// the execution trace.
+    ///
+    /// # Panics
+    /// Panics if `num_parts` is zero or does not divide the number of columns of this table.
+    pub fn split_rows(self, num_parts: usize) -> Table<E> {
+        assert!(
+            num_parts > 0 && self.row_width % num_parts == 0,
+            "number of columns must be divisible by the number of parts"
+        );
+        Table {
+            data: self.data,
+            row_width: self.row_width / num_parts,
+        }
+    }
+
+    /// Returns a table consisting of rows of this table at the specified indexes, in the order
+    /// of the indexes.
+    ///
+    /// # Panics
+    /// Panics if any of the indexes is out of bounds.
+    pub fn select_rows(&self, row_indexes: &[usize]) -> Table<E> {
+        let mut data = Vec::with_capacity(row_indexes.len() * self.row_width);
+        for &row_idx in row_indexes {
+            data.extend_from_slice(self.get_row(row_idx));
+        }
+        Table {
+            data,
+            row_width: self.row_width,
+        }
+    }
+
     /// Combines multiple tables together into a single table by stacking tables column-wise (e.g.
     /// the number of rows remains the same but the number of columns changes).
     ///
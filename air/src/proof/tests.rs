@@ -0,0 +1,600 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    get_conjectured_security, get_proven_security, log2_f64, EthStarkProof, MessageKind,
+    OptionsTuner, ProofBatch, ProofStats, StarkProof, TuningObjective, VerifierPolicy,
+};
+use crate::{
+    Air, AirContext, Assertion, EvaluationFrame, FieldExtension, PolicyError, ProofOptions,
+    TraceInfo, TransitionConstraintDegree, TuningError,
+};
+use crypto::{
+    hashers::{Blake3_192, Blake3_256, Rp64_256, Sha3_256, Truncated},
+    HashFunction, Hasher,
+};
+use math::{
+    fields::{f128, f64::BaseElement},
+    FieldElement,
+};
+use utils::{collections::Vec, Deserializable, Serializable, SliceReader};
+
+#[test]
+fn log2_approximation() {
+    for x in [1.0, 2.0, 3.0, 0.75, 1.5, 1000.0, 1e-3, 123456789.0] {
+        assert!(
+            (log2_f64(x) - x.log2()).abs() < 1e-12,
+            "log2({}) is inaccurate",
+            x
+        );
+    }
+}
+
+#[test]
+fn proven_security() {
+    let options = ProofOptions::new(80, 8, 0, FieldExtension::Cubic, 8, 256);
+    let conjectured = get_conjectured_security(&options, 64, 1 << 23, 256);
+    let proven = get_proven_security(&options, 64, 1 << 20, 1 << 23, 256);
+
+    // proven security should be lower than conjectured security for the same parameters
+    assert!(proven > 0);
+    assert!(proven < conjectured);
+
+    // more queries and grinding should never reduce proven security
+    let options2 = ProofOptions::new(120, 8, 16, FieldExtension::Cubic, 8, 256);
+    let proven2 = get_proven_security(&options2, 64, 1 << 20, 1 << 23, 256);
+    assert!(proven2 > proven);
+
+    // proven security is bounded by the size of the field and by the collision resistance of
+    // the hash function
+    let options3 = ProofOptions::new(120, 8, 16, FieldExtension::None, 8, 256);
+    assert!(get_proven_security(&options3, 64, 1 << 20, 1 << 23, 256) < 64);
+    assert_eq!(
+        96,
+        get_proven_security(&options2, 128, 1 << 20, 1 << 23, 96)
+    );
+}
+
+#[test]
+fn preset_security() {
+    // presets should provide the advertised security for computations over a 64-bit field with
+    // up to 2^20 steps, regardless of the hash function used
+    let lde_domain_size = 1 << 23;
+    let options = ProofOptions::recommended_96_bit();
+    assert_eq!(
+        96,
+        get_conjectured_security(&options, 64, lde_domain_size, 256)
+    );
+
+    let options = ProofOptions::recommended_128_bit();
+    assert!(get_conjectured_security(&options, 64, lde_domain_size, 256) >= 128);
+    assert_eq!(
+        128,
+        get_conjectured_security(&options, 64, lde_domain_size, 128)
+    );
+
+    let options = ProofOptions::fast_testing();
+    assert!(get_conjectured_security(&options, 64, lde_domain_size, 256) < 32);
+}
+
+#[test]
+fn tuned_options() {
+    type H = Blake3_256<BaseElement>;
+    let trace_info = TraceInfo::new(8, 1 << 16);
+    let lde_domain_size = |options: &ProofOptions| (1 << 16) * options.blowup_factor() as u64;
+
+    // tuned options should achieve the target security level with the smallest number of queries
+    let options = OptionsTuner::new(100, true)
+        .tune::<BaseElement, H>(&trace_info, 4)
+        .unwrap();
+    assert!(options.blowup_factor() >= 4);
+    assert!(!options.field_extension().is_none());
+    assert!(get_conjectured_security(&options, 64, lde_domain_size(&options), 128) >= 100);
+    let fewer_queries = ProofOptions::new(
+        options.num_queries() - 1,
+        options.blowup_factor(),
+        options.grinding_factor(),
+        options.field_extension(),
+        8,
+        256,
+    );
+    assert!(get_conjectured_security(&fewer_queries, 64, lde_domain_size(&options), 128) < 100);
+
+    // tuned options should lead to proofs no larger than the recommended options
+    let size = |options: &ProofOptions| {
+        ProofStats::estimate::<BaseElement>(&trace_info, 4, options, 32).total
+    };
+    let recommended = ProofOptions::recommended_96_bit();
+    assert!(size(&options) <= size(&recommended));
+
+    // options tuned for prover time should not use grinding, which only adds work for the prover
+    let fast = OptionsTuner::new(100, true)
+        .with_objective(TuningObjective::ProverTime)
+        .tune::<BaseElement, H>(&trace_info, 4)
+        .unwrap();
+    assert_eq!(0, fast.grinding_factor());
+    let cost = |options: &ProofOptions| {
+        OptionsTuner::estimate_prover_cost::<BaseElement>(&trace_info, 4, options, 32)
+    };
+    assert!(cost(&fast) <= cost(&options));
+    assert!(size(&fast) >= size(&options));
+
+    // proven security requires more queries than conjectured security
+    let proven = OptionsTuner::new(100, false)
+        .with_max_grinding_factor(0)
+        .tune::<BaseElement, H>(&trace_info, 4)
+        .unwrap();
+    assert!(get_proven_security(&proven, 64, 1 << 16, lde_domain_size(&proven), 128) >= 100);
+    let conjectured = OptionsTuner::new(100, true)
+        .with_max_grinding_factor(0)
+        .tune::<BaseElement, H>(&trace_info, 4)
+        .unwrap();
+    assert!(
+        proven.num_queries() * log2_blowup(&proven)
+            > conjectured.num_queries() * log2_blowup(&conjectured)
+    );
+}
+
+#[test]
+fn tuned_options_errors() {
+    let trace_info = TraceInfo::new(8, 1 << 16);
+
+    // security is bounded by the collision resistance of the hash function
+    let result =
+        OptionsTuner::new(128, true).tune::<BaseElement, Blake3_192<BaseElement>>(&trace_info, 4);
+    assert_eq!(Err(TuningError::UnattainableSecurity(128, 96)), result);
+
+    // security is bounded by the size of the field; the smallest LDE domain has 2^18 elements
+    let result = OptionsTuner::new(100, true)
+        .with_field_extensions(&[FieldExtension::None])
+        .tune::<BaseElement, Blake3_256<BaseElement>>(&trace_info, 4);
+    assert_eq!(Err(TuningError::UnattainableSecurity(100, 45)), result);
+
+    // extensions not supported by the field are skipped
+    let result = OptionsTuner::new(100, true)
+        .with_field_extensions(&[FieldExtension::Cubic])
+        .tune::<f128::BaseElement, Blake3_256<f128::BaseElement>>(&trace_info, 4);
+    assert_eq!(Err(TuningError::UnattainableSecurity(100, 0)), result);
+
+    let result =
+        OptionsTuner::new(100, true).tune::<BaseElement, Blake3_256<BaseElement>>(&trace_info, 256);
+    assert_eq!(Err(TuningError::ConstraintDegreeTooHigh(256)), result);
+}
+
+#[test]
+fn json_proofs() {
+    let proof = read_proof(SHA3_QUADRATIC_PROOF);
+    let json = proof.to_json();
+
+    // decoded components should add up to the binary serialization of the proof
+    let hex = json
+        .split('"')
+        .filter(|s| s.len() > 1 && s.bytes().all(|b| b.is_ascii_hexdigit()))
+        .collect::<String>();
+    assert_eq!(format!("03{}", hex), SHA3_QUADRATIC_PROOF);
+
+    // whitespace, field order, and case of hex digits should not matter
+    let reordered = json
+        .replacen("{\"version\":3,", "{\n  ", 1)
+        .replacen('}', ",\n  \"version\" : 3\n}", 1)
+        .replace(',', ",\n  ")
+        .to_uppercase()
+        .replace("\"CONTEXT\"", "\"context\"")
+        .replace("\"COMMITMENTS\"", "\"commitments\"")
+        .replace("\"TRACE_QUERIES\"", "\"trace_queries\"")
+        .replace("\"CONSTRAINT_QUERIES\"", "\"constraint_queries\"")
+        .replace("\"OOD_FRAME\"", "\"ood_frame\"")
+        .replace("\"FRI_PROOF\"", "\"fri_proof\"")
+        .replace("\"POW_NONCE\"", "\"pow_nonce\"")
+        .replace("\"VERSION\"", "\"version\"");
+    assert_eq!(proof, StarkProof::from_json(&reordered).unwrap());
+
+    // malformed documents should be rejected
+    assert!(StarkProof::from_json(&json.replacen("\"version\":3", "\"version\":4", 1)).is_err());
+    assert!(StarkProof::from_json(&json.replacen("\"pow_nonce\"", "\"nonce\"", 1)).is_err());
+    assert!(StarkProof::from_json(&json.replacen(
+        ",\"pow_nonce\"",
+        ",\"pow_nonce\":\"00\",\"pow_nonce\"",
+        1
+    ))
+    .is_err());
+    assert!(StarkProof::from_json(&json.replacen("\"context\":\"", "\"context\":\"0", 1)).is_err());
+    assert!(
+        StarkProof::from_json(&json.replacen("\"ood_frame\":\"", "\"ood_frame\":\"00", 1)).is_err()
+    );
+    assert!(StarkProof::from_json(&format!("{} {{}}", json)).is_err());
+    assert!(StarkProof::from_json(&json[..json.len() - 1]).is_err());
+}
+
+#[test]
+fn ethstark_proofs() {
+    type H = Blake3_256<BaseElement>;
+    let proof = read_proof(BLAKE3_PROOF);
+
+    // conversion should be lossless
+    let converted = proof.to_ethstark::<H, BaseElement>().unwrap();
+    let restored =
+        StarkProof::from_ethstark::<H, BaseElement>(proof.context.clone(), &converted).unwrap();
+    assert_eq!(proof, restored);
+
+    // messages should follow the channel order and use big-endian encoding
+    let messages = converted.messages();
+    assert_eq!(MessageKind::Commitment, messages[0].kind);
+    assert_eq!("STARK/Original/Commit on Trace", messages[0].label);
+    let nonce = messages
+        .iter()
+        .find(|m| m.kind == MessageKind::ProofOfWork)
+        .unwrap();
+    assert_eq!(proof.pow_nonce.to_be_bytes().to_vec(), nonce.data);
+    assert_eq!(
+        converted.to_bytes().len(),
+        messages.iter().map(|m| m.data.len()).sum()
+    );
+    let annotations = converted.annotations();
+    assert_eq!(messages.len(), annotations.len());
+    assert!(annotations[0].starts_with("P->V[0:32]: /STARK/Original/Commit on Trace"));
+
+    // missing or out-of-order messages should be rejected
+    let mut truncated = messages.to_vec();
+    truncated.pop();
+    assert!(StarkProof::from_ethstark::<H, BaseElement>(
+        proof.context.clone(),
+        &EthStarkProof::new(truncated)
+    )
+    .is_err());
+    let mut reordered = messages.to_vec();
+    reordered.swap(0, 2);
+    assert!(StarkProof::from_ethstark::<H, BaseElement>(
+        proof.context.clone(),
+        &EthStarkProof::new(reordered)
+    )
+    .is_err());
+
+    // proofs over field extensions and with non-binary Merkle trees have no ethSTARK counterpart
+    assert!(read_proof(SHA3_QUADRATIC_PROOF)
+        .to_ethstark::<H, BaseElement>()
+        .is_err());
+    assert!(read_proof(RP64_ARITY4_PROOF)
+        .to_ethstark::<Rp64_256, BaseElement>()
+        .is_err());
+}
+
+#[test]
+fn proof_size_estimates() {
+    for hex in [BLAKE3_PROOF, SHA3_QUADRATIC_PROOF, RP64_ARITY4_PROOF] {
+        let proof = read_proof(hex);
+        let stats = proof.stats();
+        let trace_info = proof.get_trace_info();
+        let options = proof.options();
+        let air = FibAir::new(trace_info.clone(), BaseElement::ONE, options.clone());
+        let estimate =
+            ProofStats::estimate::<BaseElement>(&trace_info, air.ce_blowup_factor(), options, 32);
+
+        // sizes of components which do not depend on query positions should be exact
+        assert_eq!(stats.context, estimate.context);
+        assert_eq!(stats.commitments, estimate.commitments);
+        assert_eq!(stats.ood_frame, estimate.ood_frame);
+        assert_eq!(stats.fri_remainder, estimate.fri_remainder);
+        assert_eq!(stats.other, estimate.other);
+        assert_eq!(stats.num_fri_layers(), estimate.num_fri_layers());
+
+        // the total size should be close to the actual size; for proofs this small, the number
+        // of shared Merkle path nodes varies noticeably with query positions, and this variation
+        // is too large to bound for the 4 queries into trees of arity 4
+        if options.merkle_arity() == 2 {
+            let error = (estimate.total as f64 - stats.total as f64).abs() / stats.total as f64;
+            assert!(error < 0.1, "estimate is off by {:.1}%", error * 100.0);
+        }
+    }
+}
+
+#[test]
+fn proof_batches() {
+    let proof0 = read_proof(BLAKE3_PROOF);
+    let proof1 = read_proof(SHA3_QUADRATIC_PROOF);
+
+    // proofs with the same context should share it in the serialized batch
+    let batch = ProofBatch::from(vec![proof0.clone(), proof1, proof0.clone()]);
+    assert_eq!(3, batch.len());
+    assert_eq!(2, batch.num_contexts());
+
+    let bytes = batch.to_bytes();
+    assert_eq!(batch, ProofBatch::from_bytes(&bytes).unwrap());
+    let proof_sizes: usize = batch.iter().map(|proof| proof.to_bytes().len()).sum();
+    assert!(bytes.len() < proof_sizes);
+
+    // an empty batch should survive a round trip as well
+    let empty = ProofBatch::new();
+    assert_eq!(empty, ProofBatch::from_bytes(&empty.to_bytes()).unwrap());
+
+    // references to missing contexts and truncated batches should be rejected
+    let single = ProofBatch::from(vec![proof0.clone()]).to_bytes();
+    // the index follows the version byte, the number of contexts, the shared context (without
+    // public inputs digest), and the number of proofs
+    let context = &proof0.context;
+    let shared_context_size = context.to_bytes().len() - context.pub_inputs_digest().len();
+    let context_idx_offset = 2 + shared_context_size + 1;
+    assert_eq!(0, single[context_idx_offset]);
+    let mut corrupted = single.clone();
+    corrupted[context_idx_offset] = 1;
+    assert!(ProofBatch::from_bytes(&corrupted).is_err());
+    assert!(ProofBatch::from_bytes(&single[..single.len() - 1]).is_err());
+}
+
+#[test]
+fn portable_encodings() {
+    // proof encodings use explicit byte order and pointer-width independent integers, and thus,
+    // the proofs below, which were generated on a 64-bit little-endian target, should be decoded
+    // identically on all targets
+    for hex in [BLAKE3_PROOF, SHA3_QUADRATIC_PROOF, RP64_ARITY4_PROOF] {
+        assert!(read_proof(hex).check_encoding().is_ok());
+    }
+
+    // a proof which does not survive a round trip should be reported; here, the proof contains
+    // queries for more trace segments than specified by its context
+    let mut proof = read_proof(BLAKE3_PROOF);
+    proof.trace_queries.push(proof.trace_queries[0].clone());
+    assert!(proof.check_encoding().is_err());
+}
+
+#[test]
+fn proof_digests() {
+    let proof = read_proof(BLAKE3_PROOF);
+    let digest = proof.digest::<Blake3_256<BaseElement>>();
+    assert_eq!(Blake3_256::<BaseElement>::hash(&proof.to_bytes()), digest);
+
+    // the digest should not depend on the encoding in which the proof was transported
+    let decoded = StarkProof::from_json(&proof.to_json()).unwrap();
+    assert_eq!(digest, decoded.digest::<Blake3_256<BaseElement>>());
+    let batch = ProofBatch::from_bytes(&ProofBatch::from(vec![proof.clone()]).to_bytes()).unwrap();
+    assert_eq!(
+        digest,
+        batch.get(0).unwrap().digest::<Blake3_256<BaseElement>>()
+    );
+
+    // different proofs should have different digests
+    let other = read_proof(SHA3_QUADRATIC_PROOF);
+    assert_ne!(digest, other.digest::<Blake3_256<BaseElement>>());
+    assert_ne!(
+        proof.digest::<Sha3_256<BaseElement>>(),
+        other.digest::<Sha3_256<BaseElement>>()
+    );
+}
+
+#[test]
+fn verifier_policies() {
+    type H = Blake3_256<BaseElement>;
+    let proof = read_proof(BLAKE3_PROOF);
+    let security = proof.security_level::<H>(true);
+
+    // a proof satisfying the policy should pass the check
+    let policy = VerifierPolicy::new()
+        .with_min_conjectured_security(security)
+        .with_hash_functions(&[HashFunction::Blake3_256, HashFunction::Rp64_256])
+        .with_field::<BaseElement>()
+        .with_field_extensions(&[proof.options().field_extension()]);
+    assert_eq!(Ok(()), policy.check::<H>(&proof));
+    assert_eq!(
+        Err(PolicyError::InsufficientConjecturedSecurity(
+            security + 1,
+            security
+        )),
+        policy
+            .clone()
+            .with_min_conjectured_security(security + 1)
+            .check::<H>(&proof)
+    );
+
+    // the policy must be checked with the hash function recorded in the proof
+    assert_eq!(
+        Err(PolicyError::HashFunctionMismatch(
+            HashFunction::Blake3_256,
+            HashFunction::Sha3_256
+        )),
+        VerifierPolicy::new().check::<Sha3_256<BaseElement>>(&proof)
+    );
+    assert_eq!(
+        Err(PolicyError::HashFunctionMismatch(
+            HashFunction::Blake3_256,
+            HashFunction::truncated(HashFunction::Blake3_256, 20)
+        )),
+        VerifierPolicy::new().check::<Truncated<H, 20>>(&proof)
+    );
+
+    // policies should survive a round trip through their binary and JSON encodings
+    let policy = policy
+        .with_min_proven_security(20)
+        .with_field::<f128::BaseElement>();
+    assert_eq!(
+        policy,
+        VerifierPolicy::read_from(&mut SliceReader::new(&policy.to_bytes())).unwrap()
+    );
+    let json = policy.to_json();
+    assert_eq!(policy, VerifierPolicy::from_json(&json).unwrap());
+    assert_eq!(
+        VerifierPolicy::new(),
+        VerifierPolicy::from_json(&VerifierPolicy::new().to_json()).unwrap()
+    );
+
+    // malformed policies should be rejected
+    assert!(
+        VerifierPolicy::from_json(&json.replacen("\"min_proven_security\":20,", "", 1)).is_err()
+    );
+    assert!(VerifierPolicy::from_json(&json.replacen("[1,6]", "[1,60]", 1)).is_err());
+    assert!(VerifierPolicy::from_json(&json.replacen("[1,6]", "[1,256]", 1)).is_err());
+    assert!(VerifierPolicy::from_json(&json.replacen(
+        "\"field_moduli\":[\"",
+        "\"field_moduli\":[\"\",\"",
+        1
+    ))
+    .is_err());
+}
+
+fn log2_blowup(options: &ProofOptions) -> usize {
+    options.blowup_factor().trailing_zeros() as usize
+}
+
+fn read_proof(hex: &str) -> StarkProof {
+    let bytes = (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect::<Vec<_>>();
+    StarkProof::from_bytes(&bytes).unwrap()
+}
+
+// STORED PROOFS
+// ================================================================================================
+
+/// AIR for a Fibonacci sequence which advances by two terms per trace row; all proofs below
+/// were generated for this AIR.
+struct FibAir {
+    context: AirContext<BaseElement>,
+    result: BaseElement,
+}
+
+impl Air for FibAir {
+    type BaseField = BaseElement;
+    type PublicInputs = BaseElement;
+
+    fn new(trace_info: TraceInfo, pub_inputs: BaseElement, options: ProofOptions) -> Self {
+        let degrees = vec![
+            TransitionConstraintDegree::new(1),
+            TransitionConstraintDegree::new(1),
+        ];
+        FibAir {
+            context: AirContext::new(trace_info, degrees, 3, options),
+            result: pub_inputs,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        result[0] = next[0] - (current[0] + current[1]);
+        result[1] = next[1] - (current[1] + next[0]);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        let last_step = self.trace_length() - 1;
+        vec![
+            Assertion::single(0, 0, BaseElement::ONE),
+            Assertion::single(1, 0, BaseElement::ONE),
+            Assertion::single(1, last_step, self.result),
+        ]
+    }
+}
+
+// The proofs below attest to the 16th Fibonacci number (987) and were generated with options
+// (4, 4, 0, _, 4, 32), i.e., with 4 queries and a blowup factor of 4. They are serialized in the
+// current proof format, and thus, they must be regenerated whenever the proof format changes.
+
+/// A proof generated with Blake3_256 in the base field.
+const BLAKE3_PROOF: &str = "\
+0302000003000801000000ffffffff04040001040502010000000120d63d569be41b3ce36da6d753268b5243c82d3d4d\
+e67a112b66f0a87fb7911db960b60fc61a212e484c8ddf85816e77cd70aa672e0610d1a484ef2c1104fdf5ab86978aa2\
+6c2f3912723bc9e2b9dd2583abbd978efd114383f1c4ce1908d30f62f6778fc9b518668f646f355137706e4b8ea1d4cb\
+d778c2d05ff71a69ea9192a1d040e05d859443f1618d6baf84a3b4f9a9aa2af19337d5c2c8b58a8a2dfb7248381c371a\
+2571e696a1dab7742e938df203f5d29de68ca651c74610c8d86f87e20517e5020403a0e5d01cbc0faec611159c2a69a2\
+7c44ef58e923375ff3d250c7f77ee3d18104556fba6b27a56fd48b44dc8e469692bd8b40ccf5855e93fdb9eab38e3298\
+fc2cdf9bf2ffd0fde6c85e0b20d3f74026e612abd22aa75e367d82871b4a15f3ff4202fdf5bd271da2c6eaf1de010306\
+49ecdd0c9c3518fac11827af9d1f4f5732246ca1852f673cdde5695150312356441b039b452fbf9c43c40403a2546f38\
+64f42203ae6012363b03ace7a2f07c9e98c8d532ce689df529d27e3bdc8087558d76f0ca092c0662ed2ff0a30cbdf589\
+cc2ee02910c6bcfcee7abee0acac49b7e47465268090efd7324c7e180775e7dee448060627be001c31d953f4401c4355\
+6224b9db0388fbad61c4e3749d932eb1d92fa7b23197dc3a3e35649aa0470be20300b06efdf4cd3ac462b1243d027c6c\
+c5a843244e38a1753750e8f52c975b519e136785d54752bf2e232fb756145f3c2f5c01beffaa420ab65264a4517bb473\
+2e3996fda540b23dda532616d5a05b7caacda928c38fa5547ca3de538e3a285b2957b15fbde7ffc66499584d92597918\
+430f03b43c4367aaf26408bf60e058fce1247a052b6ce50204030935efedd47a29754db7e9d9cc33aedd564b9fcd6ec0\
+0c09e65eeaaef064b79a241dc66582ac62ab3c8733c6c2258ca2488eb082ee796e5df0fbd1b575ad70bdccc92aa46c49\
+a6309f8ff62b2d1c9e6a56d90cd6ebbc86b60af8912525ae2f990237c83eeff2838991136aa8dd9a08818359e520a104\
+b1f10dd0dcef51b378e14802cadce9fd505fe53fa1d63587ca4efae10dc362a7f8c06342cb046b0500e77c03dacf0a96\
+1fe894f7a914ddcb231bbe7d01b450b93fb2a80dcc13285608c8a6fefe2e1419070bdac8060ec0f664f76d0d85a07bc6\
+ca1d9baf91757d2894ac61c22a342048b3e71a32c76da3ea6c1e85ebb2dcabda5db3f207e5c10ec5c284a23a032358d1\
+ffee935c66897d968ec50e2cd2699da1a6404d82c09d11c3a4a6fa20f6388fe4752ccc0625a1a3b51cd2edd81a898289\
+5d21c37989e63f7e407bebcf09aa579138a5a11da781a245ca09025048a1f237c7ea06ddde0e142f3a99f48626200881\
+91fb9fffad143c393c79e9a36e917a830bdcc133fe3983bc9922557e675910a181b4fe14a79820b16abb02c0ff32b400\
+80021455824599860d7aa8d240e3b6e94ce1db087a87071d2a359f2c96bacf7e593abcdc84fcc5bd50d0a4245a5a24d4\
+eaf832566b8c233599c6322f43b02032caaa2901a668bd15c4351ed50cee65a009a31f95a60484d91d1c56183cc5edbb\
+511a87e8752f356d00d7d3269a449c8755117309cd4385c71bc4ef7527a2092da08cae11f5ae45498537b714c8fe90a6\
+72ec994221df4340d192448c3ba6e1666cce2c807872cf0eb998189d69f523636c33afc519fd9d934252a2890df9f897\
+8694db310d37867a0b31263e337f1a0d671fece77e08bc1ae4045ac40b700a6b6804e1d2e4e354f714c9e4cedb329594\
+a45342c46fd570af8c5bbfedf034768d102e000100000000000000";
+
+/// A proof generated with Sha3_256 in the quadratic extension field.
+const SHA3_QUADRATIC_PROOF: &str = "\
+0302000003000801000000ffffffff04040002040502010000000320ce04195b885312473a4b59970c7bdd0cc00865ef\
+f20f22c2911551a81ecce7f660ec641478e38c517e0627ba9d0177f16d36ad4d2cd15b95c119b0d58dbf0aa34aff8b98\
+2170291e94c3627b83c8a193a293be380edfb2bf24475b78555493b8985bd75301ff1cdbbd704898210527fd336e44ca\
+e55a161429368eaa60ede35ad040b33db9266fa122a750e42663e88366bbd8b0e0f6d04961fa805ff4619daa55bf5397\
+6eb0b3b570ab9de88163a183f2611b2dcef88aad2402a5a8638845d4e58ee5020404e366eefd52118fb3df8003d98625\
+d189d84f6a556238e4513d5f43e8c7be398ad44e996d3ae3f47c45b182902905e2e4363890e0d178b12b36c0fd2863c5\
+01d9df225242c11303f09959cd1703a6d61bc90df36a41e7b8352ae1ffba55564243604c9a09dfa7f34f801e5b33bcda\
+206174df6c156906fb67d9016f9f23f205de02e537739f681746278d57b560641cb8b61b0421092162a5c1c41c59d49f\
+2bb4107d512b97635760c9faac1f3db8493bdc15d08e81fd67dc6fe8981830e82510b40211dd211270c4f981873e4eb8\
+7209ad13762c9a55dbe54de4b570b60e363579e6a3f57300439a46bd6a3d1241909960cf04f043e5811e752d572bb456\
+b7348831039d9fc3ccde920d1d2e8be631a666f1b7294ef18d4ace42a4ae1db9a9bfa4d6a902ce416429e058c1fbb001\
+0d8f20774f6c574be752179d7ae1fd873f36e79e0411e2120f7b55cb2ab5e8bb4c186aecb9382849e9e5592043edaae2\
+f7ee83dddc80012874ec5e73d337a1a6d9902ef0bc590e83a377ad10c579b30e57d211ee89206f61eb873565ba1a3d45\
+880ef54d696d0e0c5a80324ed803358f6824a85a465716a74173d65ee06f762850a55e017cbf478b06ccf28d79b0d557\
+063a9735188459f3da7b6ac8222f732a560283ac9bedb66aaf2f587c2ec6ec03821a600e31905ee502040472bdbd6d87\
+1d4230090835feb9f860141f179b7a8bffd429ae1b9d7446e241532f7462d547495177d06d8c31d505761661b47d8caf\
+d504bb8ce6bf64aea33d13a2b505e25883e13ceabc64f7c6e2662388ccf9f3ed4ed8519e0c545906a936f080e31112af\
+f986d7bfafdc95679874218737e837ecacfb1d36b13e65360e692102907eea3fa3a9ce1a09c57597a3dd4baf13c96f6c\
+1b9590908ab3e3a322775e0aef900b1c766053687e84763f42abc4a4212511d696a01c886a54a230e08adef70254e2d7\
+ed0de871fa9f54a07137e9696159263416752bf2b63de63c4d16b9b5cb3b178cbaaa7d7bb95415314e2511ba057cf59b\
+8612f3afae7bc44226e3a0e1030348e5011ca9b73c33a693f7162e9fa4ad150e85c7b1182a8be3cc3654064912271651\
+5e2e05c0c1bc7cf309dd3147ebbc760572d27a830ede5e7091c70d0991c8367d101743b5f725263ec5cd31bec4e22421\
+d1b38bc796c23b982da5f1cc70e040dc93a4cd14444060a0e7aa85f200361d0eb41a6c6efde248ef76dfad81f9843b93\
+b84cd517d3cfca371e8f58128b3a969d50a7c65896426f251989312c4d847e20a870226adf4a80898946a6aa256d7179\
+cb2bc1242e6cb1e594f1db566c422bcf00800421e7605a46c881c8f42489f023d3ac3a9291acfda5c1ad95bc93cc3354\
+7708cd8d7949cb5e3965c173c4e3a744e890ae5d60548e188b44ae7651c7df5d56d9f1f8cdb322fb4b067d22a15359d3\
+b4d68cccbce12b4f51fe742817a016a99198a76bf7a5929b46c5a49231a2bb22c6818afd2da66002a2e12203f493bbb7\
+e639bf3a368f8200a2f7d0a27a5842b789616877ede2195069c4ded1eec878a50ed669bd371637b0513f2be6429002c7\
+1c37ab4398f430fa27d6bf6a8dde81e9dddaacc16b459dfdbc229a8f4810e440ee038372d48d437353111c52c4a89977\
+62d3184def9237e2e1db99724a676ef129a405d600f6324ed507e545e149f1c7d9740e7791c23a4b23cca7345f2eaefb\
+0425ab513bc26bad1e0c7254a13aae5dd27bc943db34ecc872883b80b932963c2442cae8e5c95fde4b7cbf6b442fb092\
+bc88cfa073c25326814e9a649f46e83752ec9828dcd3b5fc6512df59499583554a6b76d123cf8674ada40d38d93cde2d\
+09e581b973d8b13fbe1f5fbd32e250f0ab3c4add8f2d381fa499704a469064f3eb5a25b287749ac2ce504f91ccb2a3df\
+de5a9665a5b5d1a44450598256ff605a04cf04950183ba7011f83d071863a6aaf5664f9244c49bc62814d4f57c229c5c\
+943132278156bc72c17991da350cd5c461fa801df40dee29cca76987de805d8eb0a213f0ad55e0a59ed264c80775517d\
+84f778000100000000000000";
+
+/// A proof generated with Rp64_256 and Merkle trees of arity 4.
+const RP64_ARITY4_PROOF: &str = "\
+0302000003000801000000ffffffff0404000104050401000000062030c7dddc4b3ddb7f935c11c5ac48ee581e009af5\
+59dd9a9e3af954d28a8bd402604dabba0804747f8c8135e841d5e7144c5539949363c346567f8a6dd26086980da62277\
+4e3512af0497600d05e03a97ca018376cba10f5f62a48d1b0bfb95a0ba955851e1c89d36a016258037431b850484b140\
+bfcb81e5ed14481d875199213140bf8490537b8df11e01b06a71d99eaafb6cbb2a616a3e9cf186e1b52d790b4c6c371a\
+2571e696a1dab7742e938df203f564c6ddcf30119996167f8d1d0176044ac702030400f85f42e3099aaf6151f48e64fd\
+b90b54a9aeed5230c2bb737191362923e8cbd282c45c247ac5936236337141ca0ed376ff83f484371bf7e2a662bcd4af\
+4e21e6ebb8bdbbce7c96fd4ca4540f875d0ee2862ee79f21b264238d5dce0bc04398a33e34660ec372e526cf955b9b74\
+f7404f75246188885e4d87b1e426e4696298c90600faa6209988434373f036d5187419da2297af5e2d10320cf6e36d4c\
+79e84aa9899e42ac13fe128a899856d398cfb5c98147aac5c06d785cecbe9655be845391a89ce922110eee69bc87d0f7\
+daf0f39a4948535da805ff674f28248c1b76bcbf9cc53d3fbd8cca7ef13516d1f6dc346909b31a5794b2a3780db57d6a\
+2dd452468c31a730eba6f135367cd73ccf761940cde939e8779efc43b37b023f0df11bccbce01509b383b65e56978a88\
+8812cccc87910da79ccbc362ccb54a8c1c81642383000040b1220e099d45f712e792ceb86545b2f245a02d9092462dbe\
+84990be90d046a489f705561f1d8169b27b8b9f4f7dd13f3a178d8af6e21e586f2028de462c18eaec70203040057c4d0\
+5cee3ed3e438e5b0c5dc101b0ad362932ce5985bb19347bf833091b240fd9a55fa259a115cc02d471c86e0755bfcebf3\
+bc318766adf19177916044231dd6fbea9c2d2bf66cd63519f259f6777f44cd858de502b7f90ecc8b1ed5d6e4836756f6\
+872bc834a47edc6a44ae008e95cd962794e50194e6221284f59cdc326006000c46d4330b49e4d451eba6b458ad60c523\
+1b89af16c90993abd3042c7be5fa2b8fa66e3bebb95186443b4a48b3d9ff92e0be223c13336330c12c23355fdfb9a6a2\
+0e8b7318ba6d003ee42304557bcb75deb9d0aa43df4fd4453c3d4303002cd07c3b7aafc7f6bd3aac93e95a55aedbdc8f\
+757992d3d726d14dcaddeb547b862fc049aedd0a70e8fb60652efd377a69805e61bff27f8e375a7b46cbc5bd4788d74c\
+b476c7cb48170ea72ccaf392f25fe14146f6b6de4042c236affa01ba21e586000020013b76edfb717bca25f23f8a5faa\
+8897c63156aa66473d609f77bc649ae0408e10d8e42d6494b62d241acdda46d4634aad008002402c9964c87f4eace72d\
+80a7b4ef3657e1d66ad3db5b5fc64ef225b2eebd791efb8c0dfa282eba74d15be484057818964957dc95241e3605fe24\
+2e3e6eaa6998e25493822b1c5a2cf4e19e169ada64c7bd77368094c460a3bee31868bd09585b9a7973d166c7b5181fcc\
+a074e226408b6a46ac69c936c208dfa835c91fc633805fe5f375e0a1fe4685a20cd3ccdaa99e53e4c9d15a021cad787a\
+d6e56c68974fefa0933ec2fd7dabea115a55d929a33a9d6a1cce69703b7adbc55eaa67f1ee42c8ca7f0342a064ced06a\
+7a62522c456e336978e7c229debc1a7b76197caf8fe9ad62569480051f7470e485bcb93c92130697822403c52a3f24dc\
+bc335e95938c000100000000000000";
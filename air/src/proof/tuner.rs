@@ -0,0 +1,364 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{get_conjectured_security, get_proven_security, ProofStats};
+use crate::{FieldExtension, ProofOptions, TraceInfo, TuningError};
+use core::cmp;
+use crypto::Hasher;
+use math::{
+    fields::{CubeExtension, QuadExtension},
+    log2, ExtensibleField, StarkField,
+};
+use utils::{collections::Vec, Serializable};
+
+// CONSTANTS
+// ================================================================================================
+
+/// FRI folding factors considered by the tuner.
+const FRI_FOLDING_FACTORS: [usize; 3] = [4, 8, 16];
+
+/// Largest blowup factor considered by the tuner.
+const MAX_BLOWUP_FACTOR: usize = 128;
+
+/// Maximum size of the FRI remainder in options returned by the tuner.
+const FRI_MAX_REMAINDER_SIZE: usize = 256;
+
+// TUNING OBJECTIVE
+// ================================================================================================
+
+/// Defines the quantity minimized by an [OptionsTuner].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum TuningObjective {
+    /// Minimize the size of the proof as estimated by [ProofStats::estimate()].
+    ProofSize,
+    /// Minimize the work performed by the prover; see [OptionsTuner::estimate_prover_cost()] for
+    /// the cost model.
+    ProverTime,
+}
+
+// OPTIONS TUNER
+// ================================================================================================
+
+/// Searches for proof options which achieve a target security level at the lowest cost.
+///
+/// Given the dimensions of a computation (its trace info and constraint evaluation blowup factor),
+/// the tuner enumerates combinations of field extension, blowup factor, grinding factor, and FRI
+/// folding factor. For each combination, it selects the smallest number of queries which achieves
+/// the target security level, and then returns the combination with the lowest cost according to
+/// the [TuningObjective]. Security levels are computed in the same way as by
+/// [StarkProof::security_level()](super::StarkProof::security_level), and thus, a proof
+/// generated with the returned options has at least the target security level.
+///
+/// Options not covered by the search are left at their default values (e.g., binary Merkle trees
+/// and FRI remainder of at most 256 elements).
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct OptionsTuner {
+    security_level: u32,
+    conjectured: bool,
+    objective: TuningObjective,
+    field_extensions: Vec<FieldExtension>,
+    max_grinding_factor: u32,
+}
+
+impl OptionsTuner {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new tuner which searches for options providing at least `security_level` bits of
+    /// security; when `conjectured` is true, conjectured security is targeted, otherwise, proven
+    /// security is targeted.
+    ///
+    /// By default, the tuner minimizes proof size, considers all field extensions supported by the
+    /// base field, and grinding factors of up to 20 bits.
+    pub fn new(security_level: u32, conjectured: bool) -> Self {
+        Self {
+            security_level,
+            conjectured,
+            objective: TuningObjective::ProofSize,
+            field_extensions: vec![
+                FieldExtension::None,
+                FieldExtension::Quadratic,
+                FieldExtension::Cubic,
+            ],
+            max_grinding_factor: 20,
+        }
+    }
+
+    /// Returns a new tuner which minimizes the specified objective.
+    pub fn with_objective(mut self, objective: TuningObjective) -> Self {
+        self.objective = objective;
+        self
+    }
+
+    /// Returns a new tuner which considers only the specified field extensions; extensions not
+    /// supported by the base field are always skipped.
+    pub fn with_field_extensions(mut self, field_extensions: &[FieldExtension]) -> Self {
+        self.field_extensions = field_extensions.to_vec();
+        self
+    }
+
+    /// Returns a new tuner which considers grinding factors of up to `max_grinding_factor` bits.
+    ///
+    /// # Panics
+    /// Panics if `max_grinding_factor` is greater than 32.
+    pub fn with_max_grinding_factor(mut self, max_grinding_factor: u32) -> Self {
+        assert!(
+            max_grinding_factor <= 32,
+            "grinding factor cannot be greater than 32, but was {max_grinding_factor}"
+        );
+        self.max_grinding_factor = max_grinding_factor;
+        self
+    }
+
+    // TUNING
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns proof options which minimize the objective of this tuner for a computation with
+    /// the specified trace and constraint evaluation blowup factor, while achieving the target
+    /// security level for proofs generated over base field `B` using hash function `H`.
+    ///
+    /// `ce_blowup_factor` has the same meaning as for [ProofStats::estimate()]; only blowup
+    /// factors greater than or equal to it are considered.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * `ce_blowup_factor` is greater than 128.
+    /// * None of the considered options achieve the target security level; the error contains
+    ///   the highest security level achievable by the considered options.
+    pub fn tune<B, H>(
+        &self,
+        trace_info: &TraceInfo,
+        ce_blowup_factor: usize,
+    ) -> Result<ProofOptions, TuningError>
+    where
+        B: StarkField + ExtensibleField<2> + ExtensibleField<3>,
+        H: Hasher,
+    {
+        if ce_blowup_factor > MAX_BLOWUP_FACTOR {
+            return Err(TuningError::ConstraintDegreeTooHigh(ce_blowup_factor));
+        }
+        let digest_size = H::Digest::default().to_bytes().len();
+        let min_blowup_factor = cmp::max(
+            ce_blowup_factor.next_power_of_two(),
+            ProofOptions::MIN_BLOWUP_FACTOR,
+        );
+
+        let mut best: Option<(ProofOptions, u64)> = None;
+        let mut max_security = 0;
+        for &field_extension in self.field_extensions.iter() {
+            if !is_extension_supported::<B>(field_extension) {
+                continue;
+            }
+
+            let mut blowup_factor = min_blowup_factor;
+            while blowup_factor <= MAX_BLOWUP_FACTOR {
+                for grinding_factor in 0..=self.max_grinding_factor {
+                    let options = |num_queries| {
+                        ProofOptions::new(
+                            num_queries,
+                            blowup_factor,
+                            grinding_factor,
+                            field_extension,
+                            FRI_FOLDING_FACTORS[0],
+                            FRI_MAX_REMAINDER_SIZE,
+                        )
+                    };
+
+                    // the number of queries cannot exceed the size of the LDE domain
+                    let lde_domain_size = trace_info.length() * blowup_factor;
+                    let max_queries = cmp::min(ProofOptions::MAX_NUM_QUERIES, lde_domain_size);
+                    let security = self.security_level::<B, H>(&options(max_queries), trace_info);
+                    max_security = cmp::max(max_security, security);
+                    if security < self.security_level {
+                        continue;
+                    }
+
+                    // security grows with the number of queries; the smallest number of queries
+                    // achieving the target is found via binary search
+                    let (mut low, mut high) = (1, max_queries);
+                    while low < high {
+                        let mid = (low + high) / 2;
+                        if self.security_level::<B, H>(&options(mid), trace_info)
+                            >= self.security_level
+                        {
+                            high = mid;
+                        } else {
+                            low = mid + 1;
+                        }
+                    }
+
+                    // the FRI folding factor does not affect security, but does affect the cost
+                    for &folding_factor in FRI_FOLDING_FACTORS.iter() {
+                        let options = ProofOptions::new(
+                            low,
+                            blowup_factor,
+                            grinding_factor,
+                            field_extension,
+                            folding_factor,
+                            FRI_MAX_REMAINDER_SIZE,
+                        );
+                        let cost = match self.objective {
+                            TuningObjective::ProofSize => {
+                                ProofStats::estimate::<B>(
+                                    trace_info,
+                                    ce_blowup_factor,
+                                    &options,
+                                    digest_size,
+                                )
+                                .total as u64
+                            }
+                            TuningObjective::ProverTime => Self::estimate_prover_cost::<B>(
+                                trace_info,
+                                ce_blowup_factor,
+                                &options,
+                                digest_size,
+                            ),
+                        };
+                        if best
+                            .as_ref()
+                            .map_or(true, |(_, best_cost)| cost < *best_cost)
+                        {
+                            best = Some((options, cost));
+                        }
+                    }
+                }
+                blowup_factor *= 2;
+            }
+        }
+
+        best.map(|(options, _)| options)
+            .ok_or(TuningError::UnattainableSecurity(
+                self.security_level,
+                max_security,
+            ))
+    }
+
+    /// Returns an estimate of the work performed by the prover to generate a proof for a
+    /// computation with the specified trace and constraint evaluation blowup factor using the
+    /// specified `options` over base field `B` and a hash function with digests of `digest_size`
+    /// bytes.
+    ///
+    /// The work is measured in bytes processed by the prover, accounted for in the same way as by
+    /// the prover's proving stats:
+    /// * Every FFT over a domain of size `n` processes each of its `n` elements `log2(n)` times.
+    ///   Trace columns are interpolated and evaluated over the LDE domain, and columns of the
+    ///   composition polynomial are evaluated over the LDE domain.
+    /// * All bytes hashed to commit to the extended trace, the constraint evaluations, and the FRI
+    ///   layers, including the internal nodes of Merkle trees, are counted once.
+    /// * Grinding is expected to require `2^grinding_factor` attempts, each hashing a digest and
+    ///   a nonce.
+    ///
+    /// Constraint evaluation is not included because its cost depends on the constraints
+    /// themselves rather than on the proof options.
+    pub fn estimate_prover_cost<B: StarkField>(
+        trace_info: &TraceInfo,
+        ce_blowup_factor: usize,
+        options: &ProofOptions,
+        digest_size: usize,
+    ) -> u64 {
+        let layout = trace_info.layout();
+        let base_bytes = B::ELEMENT_BYTES as u64;
+        let ext_bytes = base_bytes * options.field_extension().degree() as u64;
+        let trace_length = trace_info.length() as u64;
+        let lde_domain_size = trace_length * options.blowup_factor() as u64;
+        let fft_cost = |domain_size: u64| domain_size * log2(domain_size as usize) as u64;
+        let arity = options.merkle_arity() as u64;
+        let digest_size = digest_size as u64;
+
+        // trace segments: interpolation and evaluation of every column, and a commitment to the
+        // extended segment
+        let rows_per_leaf = options.trace_rows_per_leaf() as u64;
+        let mut segments = vec![layout.main_trace_width() as u64 * base_bytes];
+        for i in 0..layout.num_aux_segments() {
+            segments.push(layout.get_aux_segment_width(i) as u64 * ext_bytes);
+        }
+        let mut cost = 0;
+        for row_bytes in segments {
+            cost += row_bytes * (fft_cost(trace_length) + fft_cost(lde_domain_size));
+            cost += commitment_cost(
+                lde_domain_size / rows_per_leaf,
+                rows_per_leaf * row_bytes,
+                arity,
+                digest_size,
+            );
+        }
+
+        // constraint evaluations: evaluation of composition polynomial columns over the LDE domain
+        // and a commitment to them
+        let row_bytes = ce_blowup_factor as u64 * ext_bytes;
+        cost += row_bytes * fft_cost(lde_domain_size);
+        cost += commitment_cost(lde_domain_size, row_bytes, arity, digest_size);
+
+        // FRI layers and the remainder
+        let fri_options = options.to_fri_options();
+        let folding_factor = fri_options.folding_factor() as u64;
+        let mut domain_size = lde_domain_size;
+        for _ in 0..fri_options.num_fri_layers(lde_domain_size as usize) {
+            let num_leaves = domain_size / folding_factor;
+            cost += commitment_cost(num_leaves, folding_factor * ext_bytes, arity, digest_size);
+            domain_size = num_leaves;
+        }
+        cost += domain_size * ext_bytes;
+
+        // proof-of-work: each attempt hashes a digest and a nonce
+        cost + (1u64 << options.grinding_factor()) * (digest_size + 8)
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the security level targeted by this tuner of proofs generated with the specified
+    /// options.
+    fn security_level<B: StarkField, H: Hasher>(
+        &self,
+        options: &ProofOptions,
+        trace_info: &TraceInfo,
+    ) -> u32 {
+        let lde_domain_size = (trace_info.length() * options.blowup_factor()) as u64;
+        if self.conjectured {
+            get_conjectured_security(
+                options,
+                B::MODULUS_BITS,
+                lde_domain_size,
+                H::COLLISION_RESISTANCE,
+            )
+        } else {
+            get_proven_security(
+                options,
+                B::MODULUS_BITS,
+                trace_info.length() as u64,
+                lde_domain_size,
+                H::COLLISION_RESISTANCE,
+            )
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns true if the base field `B` supports the specified field extension.
+fn is_extension_supported<B>(field_extension: FieldExtension) -> bool
+where
+    B: StarkField + ExtensibleField<2> + ExtensibleField<3>,
+{
+    match field_extension {
+        FieldExtension::None => true,
+        FieldExtension::Quadratic => <QuadExtension<B>>::is_supported(),
+        FieldExtension::Cubic => <CubeExtension<B>>::is_supported(),
+    }
+}
+
+/// Returns the number of bytes hashed to commit to `num_leaves` leaves of `leaf_size` bytes each
+/// via a Merkle tree of the specified arity; every node except for the root is hashed once as a
+/// child of its parent.
+fn commitment_cost(num_leaves: u64, leaf_size: u64, arity: u64, digest_size: u64) -> u64 {
+    let mut result = num_leaves * leaf_size;
+    let mut num_nodes = num_leaves;
+    while num_nodes > 1 {
+        result += num_nodes * digest_size;
+        num_nodes /= cmp::min(arity, num_nodes);
+    }
+    result
+}
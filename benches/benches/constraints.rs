@@ -0,0 +1,68 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use rand_utils::rand_vector;
+use std::time::Duration;
+use winter_benches::workload::{WorkloadAir, TRACE_WIDTH};
+use winterfell::{
+    math::{
+        fields::{f64::BaseElement, CubeExtension, QuadExtension},
+        FieldElement,
+    },
+    Air, EvaluationFrame, ProofOptions, TraceInfo,
+};
+
+const TRACE_LENGTHS: [usize; 2] = [1 << 12, 1 << 16];
+
+// CONSTRAINT EVALUATION
+// ================================================================================================
+
+/// Evaluates transition constraints of the workload AIR at every point of the constraint
+/// evaluation domain, with the trace state at each point drawn at random from field `E`.
+fn evaluate_transition<E>(c: &mut Criterion, field_name: &str)
+where
+    E: FieldElement<BaseField = BaseElement>,
+{
+    let mut group = c.benchmark_group(format!("constraints/{field_name}"));
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(10));
+
+    for &trace_length in TRACE_LENGTHS.iter() {
+        let trace_info = TraceInfo::new(TRACE_WIDTH, trace_length);
+        let air = WorkloadAir::new(trace_info, BaseElement::ONE, ProofOptions::fast_testing());
+        let domain_size = air.ce_domain_size();
+        let columns: Vec<Vec<E>> = (0..TRACE_WIDTH).map(|_| rand_vector(domain_size)).collect();
+
+        group.bench_function(BenchmarkId::new("transition", domain_size), |bench| {
+            bench.iter_with_large_drop(|| {
+                let mut frame = EvaluationFrame::<E>::new(TRACE_WIDTH);
+                let mut evaluations = vec![E::ZERO; TRACE_WIDTH];
+                let mut result = E::ZERO;
+                for step in 0..domain_size {
+                    let next_step = (step + air.ce_blowup_factor()) % domain_size;
+                    for (i, column) in columns.iter().enumerate() {
+                        frame.current_mut()[i] = column[step];
+                        frame.next_mut()[i] = column[next_step];
+                    }
+                    air.evaluate_transition(&frame, &[], &mut evaluations);
+                    result += evaluations.iter().fold(E::ZERO, |acc, &value| acc + value);
+                }
+                result
+            });
+        });
+    }
+
+    group.finish();
+}
+
+fn constraints(c: &mut Criterion) {
+    evaluate_transition::<BaseElement>(c, "f64");
+    evaluate_transition::<QuadExtension<BaseElement>>(c, "f64_quad");
+    evaluate_transition::<CubeExtension<BaseElement>>(c, "f64_cube");
+}
+
+criterion_group!(constraints_group, constraints);
+criterion_main!(constraints_group);
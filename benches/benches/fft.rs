@@ -0,0 +1,60 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use rand_utils::rand_vector;
+use std::time::Duration;
+use winterfell::math::{
+    fft,
+    fields::{f128, f64, QuadExtension},
+    FieldElement, StarkField,
+};
+
+const SIZES: [usize; 4] = [1 << 12, 1 << 14, 1 << 16, 1 << 18];
+
+// FFT
+// ================================================================================================
+
+fn fft_ops<B, E>(c: &mut Criterion, field_name: &str)
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+{
+    let mut group = c.benchmark_group(format!("fft/{field_name}"));
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(10));
+
+    for &size in SIZES.iter() {
+        let values: Vec<E> = rand_vector(size);
+        let twiddles = fft::get_twiddles::<B>(size);
+        group.bench_function(BenchmarkId::new("evaluate_poly", size), |bench| {
+            bench.iter_with_large_drop(|| {
+                let mut result = values.clone();
+                fft::evaluate_poly(&mut result, &twiddles);
+                result
+            });
+        });
+
+        let inv_twiddles = fft::get_inv_twiddles::<B>(size);
+        group.bench_function(BenchmarkId::new("interpolate_poly", size), |bench| {
+            bench.iter_with_large_drop(|| {
+                let mut result = values.clone();
+                fft::interpolate_poly(&mut result, &inv_twiddles);
+                result
+            });
+        });
+    }
+
+    group.finish();
+}
+
+fn fft(c: &mut Criterion) {
+    fft_ops::<f64::BaseElement, f64::BaseElement>(c, "f64");
+    fft_ops::<f64::BaseElement, QuadExtension<f64::BaseElement>>(c, "f64_quad");
+    fft_ops::<f128::BaseElement, f128::BaseElement>(c, "f128");
+}
+
+criterion_group!(fft_group, fft);
+criterion_main!(fft_group);
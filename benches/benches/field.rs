@@ -0,0 +1,65 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use rand_utils::{rand_value, rand_vector};
+use std::time::Duration;
+use winterfell::math::{
+    batch_inversion,
+    fields::{f128, f64, CubeExtension, QuadExtension},
+    FieldElement,
+};
+
+const BATCH_SIZES: [usize; 2] = [65_536, 1_048_576];
+
+// FIELD OPERATIONS
+// ================================================================================================
+
+fn field_ops<E: FieldElement>(c: &mut Criterion, field_name: &str) {
+    let mut group = c.benchmark_group(format!("field/{field_name}"));
+
+    let x = rand_value::<E>();
+    let y = rand_value::<E>();
+    let power = E::PositiveInteger::from(rand_value::<u64>());
+
+    group.bench_function("add", |bench| bench.iter(|| black_box(x) + black_box(y)));
+    group.bench_function("sub", |bench| bench.iter(|| black_box(x) - black_box(y)));
+    group.bench_function("mul", |bench| bench.iter(|| black_box(x) * black_box(y)));
+    group.bench_function("square", |bench| bench.iter(|| black_box(x).square()));
+    group.bench_function("exp", |bench| {
+        bench.iter(|| black_box(x).exp(black_box(power)))
+    });
+    group.bench_function("inv", |bench| bench.iter(|| black_box(x).inv()));
+
+    group.finish();
+}
+
+fn batch_inv<E: FieldElement>(c: &mut Criterion, field_name: &str) {
+    let mut group = c.benchmark_group(format!("field/{field_name}/batch_inversion"));
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(10));
+
+    for &size in BATCH_SIZES.iter() {
+        let values = rand_vector::<E>(size);
+        group.bench_function(BenchmarkId::from_parameter(size), |bench| {
+            bench.iter_with_large_drop(|| batch_inversion(&values));
+        });
+    }
+
+    group.finish();
+}
+
+fn field(c: &mut Criterion) {
+    field_ops::<f64::BaseElement>(c, "f64");
+    field_ops::<QuadExtension<f64::BaseElement>>(c, "f64_quad");
+    field_ops::<CubeExtension<f64::BaseElement>>(c, "f64_cube");
+    field_ops::<f128::BaseElement>(c, "f128");
+
+    batch_inv::<f64::BaseElement>(c, "f64");
+    batch_inv::<f128::BaseElement>(c, "f128");
+}
+
+criterion_group!(field_group, field);
+criterion_main!(field_group);
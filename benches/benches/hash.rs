@@ -0,0 +1,47 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use rand_utils::rand_vector;
+use winterfell::{
+    crypto::{
+        hashers::{Blake3_256, Rp64_256, RpJive64_256, Sha3_256},
+        ElementHasher,
+    },
+    math::fields::f64::BaseElement,
+};
+
+const NUM_ELEMENTS: [usize; 3] = [8, 100, 4096];
+
+// HASHING
+// ================================================================================================
+
+fn hash_ops<H: ElementHasher<BaseField = BaseElement>>(c: &mut Criterion, hash_name: &str) {
+    let mut group = c.benchmark_group(format!("hash/{hash_name}"));
+
+    let digests = [H::hash(&[1, 2, 3]), H::hash(&[4, 5, 6])];
+    group.bench_function("merge", |bench| {
+        bench.iter(|| H::merge(black_box(&digests)))
+    });
+
+    for &num_elements in NUM_ELEMENTS.iter() {
+        let elements: Vec<BaseElement> = rand_vector(num_elements);
+        group.bench_function(BenchmarkId::new("hash_elements", num_elements), |bench| {
+            bench.iter(|| H::hash_elements(black_box(&elements)))
+        });
+    }
+
+    group.finish();
+}
+
+fn hash(c: &mut Criterion) {
+    hash_ops::<Blake3_256<BaseElement>>(c, "blake3_256");
+    hash_ops::<Sha3_256<BaseElement>>(c, "sha3_256");
+    hash_ops::<Rp64_256>(c, "rp64_256");
+    hash_ops::<RpJive64_256>(c, "rp_jive64_256");
+}
+
+criterion_group!(hash_group, hash);
+criterion_main!(hash_group);
@@ -0,0 +1,52 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use rand_utils::rand_vector;
+use std::time::Duration;
+use winterfell::{
+    crypto::{
+        hashers::{Blake3_256, Rp64_256},
+        ElementHasher, MerkleTree,
+    },
+    math::fields::f64::BaseElement,
+};
+
+const NUM_LEAVES: [usize; 3] = [1 << 12, 1 << 15, 1 << 18];
+const ARITIES: [usize; 2] = [2, 4];
+
+// MERKLE TREE CONSTRUCTION
+// ================================================================================================
+
+fn merkle_ops<H: ElementHasher<BaseField = BaseElement>>(c: &mut Criterion, hash_name: &str) {
+    let mut group = c.benchmark_group(format!("merkle/{hash_name}"));
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(10));
+
+    for &num_leaves in NUM_LEAVES.iter() {
+        let leaves = rand_vector::<BaseElement>(num_leaves)
+            .into_iter()
+            .map(|value| H::hash_elements(&[value]))
+            .collect::<Vec<_>>();
+        for &arity in ARITIES.iter() {
+            let id = BenchmarkId::new(format!("arity_{arity}"), num_leaves);
+            group.bench_function(id, |bench| {
+                bench.iter_with_large_drop(|| {
+                    MerkleTree::<H>::with_arity(leaves.clone(), arity).unwrap()
+                });
+            });
+        }
+    }
+
+    group.finish();
+}
+
+fn merkle(c: &mut Criterion) {
+    merkle_ops::<Blake3_256<BaseElement>>(c, "blake3_256");
+    merkle_ops::<Rp64_256>(c, "rp64_256");
+}
+
+criterion_group!(merkle_group, merkle);
+criterion_main!(merkle_group);
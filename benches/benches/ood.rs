@@ -0,0 +1,44 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use rand_utils::{rand_value, rand_vector};
+use std::time::Duration;
+use winterfell::{
+    math::fields::{f64::BaseElement, QuadExtension},
+    Matrix,
+};
+
+const TRACE_LENGTHS: [usize; 2] = [1 << 12, 1 << 16];
+const NUM_COLUMNS: [usize; 3] = [4, 16, 64];
+
+// OUT-OF-DOMAIN EVALUATION
+// ================================================================================================
+
+/// Evaluates trace polynomials at a single out-of-domain point in the quadratic extension of
+/// `f64`, as done by the prover when building the out-of-domain frame.
+fn ood(c: &mut Criterion) {
+    let mut group = c.benchmark_group("ood/f64_quad");
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(10));
+
+    for &trace_length in TRACE_LENGTHS.iter() {
+        for &num_columns in NUM_COLUMNS.iter() {
+            let columns: Vec<Vec<BaseElement>> = (0..num_columns)
+                .map(|_| rand_vector(trace_length))
+                .collect();
+            let polys = Matrix::new(columns);
+            let z: QuadExtension<BaseElement> = rand_value();
+
+            let id = BenchmarkId::new(format!("evaluate_columns_at/{num_columns}"), trace_length);
+            group.bench_function(id, |bench| bench.iter(|| polys.evaluate_columns_at(z)));
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group!(ood_group, ood);
+criterion_main!(ood_group);
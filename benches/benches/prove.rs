@@ -0,0 +1,43 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use std::time::Duration;
+use winter_benches::{option_presets, workload};
+use winterfell::{
+    crypto::hashers::Blake3_256,
+    math::{fields::f64::BaseElement, FieldElement},
+};
+
+const TRACE_LENGTHS: [usize; 2] = [1 << 12, 1 << 16];
+
+// PROOF GENERATION
+// ================================================================================================
+
+fn prove(c: &mut Criterion) {
+    let mut group = c.benchmark_group("prove");
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(20));
+
+    for preset in option_presets() {
+        for &trace_length in TRACE_LENGTHS.iter() {
+            let id = BenchmarkId::new(preset.name, trace_length);
+            group.bench_function(id, |bench| {
+                bench.iter_batched(
+                    || workload::build_trace(trace_length, BaseElement::ONE),
+                    |trace| {
+                        workload::prove::<Blake3_256<BaseElement>>(trace, preset.options.clone())
+                    },
+                    BatchSize::LargeInput,
+                );
+            });
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group!(prove_group, prove);
+criterion_main!(prove_group);
@@ -0,0 +1,42 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use winter_benches::{option_presets, workload};
+use winterfell::{
+    crypto::hashers::Blake3_256,
+    math::{fields::f64::BaseElement, FieldElement},
+};
+
+const TRACE_LENGTHS: [usize; 2] = [1 << 12, 1 << 16];
+
+type Blake3 = Blake3_256<BaseElement>;
+
+// PROOF VERIFICATION
+// ================================================================================================
+
+fn verify(c: &mut Criterion) {
+    let mut group = c.benchmark_group("verify");
+
+    for preset in option_presets() {
+        for &trace_length in TRACE_LENGTHS.iter() {
+            let trace = workload::build_trace(trace_length, BaseElement::ONE);
+            let proof = workload::prove::<Blake3>(trace, preset.options.clone());
+            let id = BenchmarkId::new(preset.name, trace_length);
+            group.bench_function(id, |bench| {
+                bench.iter_batched(
+                    || proof.clone(),
+                    |proof| workload::verify::<Blake3>(proof, BaseElement::ONE).unwrap(),
+                    BatchSize::SmallInput,
+                );
+            });
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group!(verify_group, verify);
+criterion_main!(verify_group);
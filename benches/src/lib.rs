@@ -0,0 +1,28 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Shared fixtures for the Winterfell benchmark suite.
+//!
+//! The benchmarks themselves live in the `benches` directory of this crate and cover every stage
+//! of the proof system: field arithmetic, FFT, hashing, Merkle tree construction, constraint
+//! evaluation, and end-to-end proof generation and verification. This library defines the inputs
+//! which are shared between the benchmarks so that numbers reported by different benchmarks
+//! describe the same workload:
+//!
+//! * [option_presets()] - a set of named proof options against which the end-to-end benchmarks
+//!   are run.
+//! * [workload] - a computation with a non-trivial transition function used by the constraint
+//!   evaluation, proving, and verification benchmarks.
+//!
+//! The benchmarks are not run as a part of the regular test suite; they can be run on demand via
+//! `cargo bench -p winter-benches`.
+
+pub mod workload;
+
+mod presets;
+pub use presets::{option_presets, OptionPreset};
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,39 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use winterfell::ProofOptions;
+
+// OPTION PRESETS
+// ================================================================================================
+
+/// Proof options identified by a name under which benchmark results are reported.
+#[derive(Clone, Debug)]
+pub struct OptionPreset {
+    /// Name of the preset; used as a parameter of benchmark IDs.
+    pub name: &'static str,
+    /// Proof options of the preset.
+    pub options: ProofOptions,
+}
+
+/// Returns presets against which proof generation and verification are benchmarked.
+///
+/// The presets are ordered from the fastest to the most secure: the options used in tests, and
+/// the options recommended for 96-bit and 128-bit conjectured security over a 64-bit field.
+pub fn option_presets() -> Vec<OptionPreset> {
+    vec![
+        OptionPreset {
+            name: "fast_testing",
+            options: ProofOptions::fast_testing(),
+        },
+        OptionPreset {
+            name: "96_bit",
+            options: ProofOptions::recommended_96_bit(),
+        },
+        OptionPreset {
+            name: "128_bit",
+            options: ProofOptions::recommended_128_bit(),
+        },
+    ]
+}
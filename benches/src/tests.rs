@@ -0,0 +1,23 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{option_presets, workload};
+use winterfell::{
+    crypto::hashers::Blake3_256,
+    math::{fields::f64::BaseElement, FieldElement},
+};
+
+type Blake3 = Blake3_256<BaseElement>;
+
+#[test]
+fn workload_prove_verify() {
+    let seed = BaseElement::new(42);
+    for preset in option_presets() {
+        let trace = workload::build_trace(64, seed);
+        let proof = workload::prove::<Blake3>(trace, preset.options);
+        assert!(workload::verify::<Blake3>(proof.clone(), seed).is_ok());
+        assert!(workload::verify::<Blake3>(proof, seed + BaseElement::ONE).is_err());
+    }
+}
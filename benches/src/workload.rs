@@ -0,0 +1,165 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! A computation used as the workload of the constraint evaluation and end-to-end benchmarks.
+//!
+//! The execution trace of the computation consists of [TRACE_WIDTH] columns; at every step, each
+//! column is updated as:
+//!
+//! $$
+//! s_i' = s_i^3 + s_{i+1}
+//! $$
+//!
+//! where the index $i + 1$ wraps around the width of the trace. Thus, the AIR of the computation
+//! contains one transition constraint of degree 3 per column, and an assertion against the initial
+//! value of every column. The initial state is derived from a single public seed.
+
+use core::marker::PhantomData;
+use winterfell::{
+    crypto::ElementHasher,
+    math::{fields::f64::BaseElement, FieldElement},
+    Air, AirContext, Assertion, EvaluationFrame, ProofOptions, Prover, StarkProof, TraceInfo,
+    TraceTable, TransitionConstraintDegree, VerifierError,
+};
+
+/// Number of columns in the execution trace of the computation.
+pub const TRACE_WIDTH: usize = 8;
+
+// PUBLIC FUNCTIONS
+// ================================================================================================
+
+/// Builds an execution trace of the specified length starting from the state derived from the
+/// specified seed.
+///
+/// # Panics
+/// Panics if `trace_length` is not a power of two or is smaller than 8.
+pub fn build_trace(trace_length: usize, seed: BaseElement) -> TraceTable<BaseElement> {
+    let mut trace = TraceTable::new(TRACE_WIDTH, trace_length);
+    trace.fill(
+        |state| {
+            for (i, value) in state.iter_mut().enumerate() {
+                *value = initial_value(seed, i);
+            }
+        },
+        |_, state| {
+            let first = state[0];
+            for i in 0..TRACE_WIDTH {
+                let next = if i + 1 < TRACE_WIDTH {
+                    state[i + 1]
+                } else {
+                    first
+                };
+                state[i] = state[i].cube() + next;
+            }
+        },
+    );
+    trace
+}
+
+/// Generates a proof of the computation for the specified trace using hash function `H`.
+pub fn prove<H>(trace: TraceTable<BaseElement>, options: ProofOptions) -> StarkProof
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    WorkloadProver::<H>::new(options)
+        .prove(trace)
+        .expect("failed to generate a proof")
+}
+
+/// Verifies a proof of the computation started from the specified seed using hash function `H`.
+pub fn verify<H>(proof: StarkProof, seed: BaseElement) -> Result<(), VerifierError>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    winterfell::verify::<WorkloadAir, H>(proof, seed)
+}
+
+// AIR
+// ================================================================================================
+
+/// AIR of the computation; public inputs consist of the seed from which the initial state of the
+/// computation is derived.
+pub struct WorkloadAir {
+    context: AirContext<BaseElement>,
+    seed: BaseElement,
+}
+
+impl Air for WorkloadAir {
+    type BaseField = BaseElement;
+    type PublicInputs = BaseElement;
+
+    fn new(trace_info: TraceInfo, pub_inputs: BaseElement, options: ProofOptions) -> Self {
+        let degrees = vec![TransitionConstraintDegree::new(3); TRACE_WIDTH];
+        WorkloadAir {
+            context: AirContext::new(trace_info, degrees, TRACE_WIDTH, options),
+            seed: pub_inputs,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        for i in 0..TRACE_WIDTH {
+            let neighbor = current[(i + 1) % TRACE_WIDTH];
+            result[i] = next[i] - (current[i].cube() + neighbor);
+        }
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        (0..TRACE_WIDTH)
+            .map(|i| Assertion::single(i, 0, initial_value(self.seed, i)))
+            .collect()
+    }
+}
+
+// PROVER
+// ================================================================================================
+
+struct WorkloadProver<H: ElementHasher> {
+    options: ProofOptions,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> WorkloadProver<H> {
+    fn new(options: ProofOptions) -> Self {
+        Self {
+            options,
+            _hasher: PhantomData,
+        }
+    }
+}
+
+impl<H: ElementHasher<BaseField = BaseElement>> Prover for WorkloadProver<H> {
+    type BaseField = BaseElement;
+    type Air = WorkloadAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = H;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> BaseElement {
+        trace.get(0, 0)
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the initial value of the specified column; the value of the first column is the seed
+/// itself.
+fn initial_value(seed: BaseElement, column: usize) -> BaseElement {
+    seed + BaseElement::from(column as u64)
+}
@@ -0,0 +1,78 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::Hasher;
+use core::fmt::{Debug, Display};
+use utils::collections::Vec;
+
+// VECTOR COMMITMENT
+// ================================================================================================
+
+/// A vector commitment scheme.
+///
+/// A vector commitment scheme allows a prover to commit to a vector of items (digests produced
+/// by the hash function `H`) with a single value, and then to open the committed vector at one
+/// or more positions. A verifier can check that the opened items are indeed the items at the
+/// specified positions of the committed vector.
+///
+/// [MerkleTree](crate::MerkleTree) is the canonical implementation of this trait. Currently, the
+/// trait is Merkle-only in practice: STARK proofs store openings as
+/// [BatchMerkleProof](crate::BatchMerkleProof)s, and thus, the prover accepts only commitment
+/// schemes with `MultiProof = BatchMerkleProof<H>`, while the verifier always checks openings
+/// against Merkle tree roots. Other schemes can implement the trait, but cannot yet be used to
+/// generate or verify proofs.
+pub trait VectorCommitment<H: Hasher>: Sized {
+    /// Parameters of the commitment scheme (e.g., the arity of a Merkle tree).
+    type Options: Clone;
+
+    /// Proof that a single item is located at a given position of the committed vector.
+    type Proof;
+
+    /// Proof that a set of items is located at a given set of positions of the committed vector.
+    type MultiProof;
+
+    /// Error returned when committing, opening, or verifying fails.
+    type Error: Debug + Display;
+
+    /// Commits to the provided vector of items using the specified options.
+    fn with_options(items: Vec<H::Digest>, options: Self::Options) -> Result<Self, Self::Error>;
+
+    /// Returns the commitment to the vector.
+    fn commitment(&self) -> H::Digest;
+
+    /// Returns the number of items in the committed vector.
+    fn domain_len(&self) -> usize;
+
+    /// Opens the committed vector at the specified `index`, and returns the item at this index
+    /// together with a proof of its inclusion.
+    fn open(&self, index: usize) -> Result<(H::Digest, Self::Proof), Self::Error>;
+
+    /// Opens the committed vector at the specified `indexes`, and returns the items at these
+    /// indexes (in the same order as the indexes) together with a single proof of their
+    /// inclusion.
+    #[allow(clippy::type_complexity)]
+    fn open_many(
+        &self,
+        indexes: &[usize],
+    ) -> Result<(Vec<H::Digest>, Self::MultiProof), Self::Error>;
+
+    /// Checks that the specified `item` is located at the specified `index` of the vector
+    /// committed to by `commitment`.
+    fn verify(
+        commitment: H::Digest,
+        index: usize,
+        item: H::Digest,
+        proof: &Self::Proof,
+    ) -> Result<(), Self::Error>;
+
+    /// Checks that the specified `items` are located at the specified `indexes` of the vector
+    /// committed to by `commitment`.
+    fn verify_many(
+        commitment: H::Digest,
+        indexes: &[usize],
+        items: &[H::Digest],
+        proof: &Self::MultiProof,
+    ) -> Result<(), Self::Error>;
+}
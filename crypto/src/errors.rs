@@ -4,6 +4,7 @@
 // LICENSE file in the root directory of this source tree.
 
 use core::fmt;
+use utils::{collections::Vec, string::String};
 
 // MERKLE TREE ERROR
 // ================================================================================================
@@ -21,10 +22,32 @@ pub enum MerkleTreeError {
     DuplicateLeafIndex,
     /// No leaf indexes were provided for a batch Merkle proof.
     TooFewLeafIndexes,
-    /// Too many leaf index were provided for a batch Merkle proof.
-    TooManyLeafIndexes(usize, usize),
+    /// Number of leaves (or Merkle paths) did not match the number of leaf indexes: the first
+    /// value is the expected number, the second value is the actual number.
+    WrongNumberOfLeaves(usize, usize),
+    /// Merkle paths combined into a single proof did not all have the same depth: the first value
+    /// is the expected depth, the second value is the actual depth.
+    DepthMismatch(usize, usize),
+    /// Length of a Merkle path did not correspond to any valid tree.
+    InvalidPathLength(usize),
     /// Merkle proof is not valid for the specified position(s).
     InvalidProof,
+    /// Leaves opened by a Merkle proof did not match the expected leaves at the specified
+    /// positions.
+    InvalidLeaves(Vec<usize>),
+    /// Arity of a Merkle tree was not one of the supported values.
+    InvalidArity(usize),
+    /// Partition trees of a partitioned Merkle commitment did not all have the same depth and
+    /// arity.
+    InconsistentPartitions,
+    /// Depth of a partial Merkle tree was not smaller than the depth of the original tree: the
+    /// first value is the depth of the original tree, the second value is the requested depth.
+    InvalidPartialDepth(usize, usize),
+    /// Leaves provided for the subtree at the specified index did not resolve to the subtree
+    /// root kept by a partial Merkle tree.
+    InvalidSubtree(usize),
+    /// Nodes of a Merkle tree could not be written to or read from external storage.
+    StorageError(String),
 }
 
 impl fmt::Display for MerkleTreeError {
@@ -51,15 +74,54 @@ impl fmt::Display for MerkleTreeError {
             Self::TooFewLeafIndexes => {
                 write!(f, "at least one leaf index must be provided")
             }
-            Self::TooManyLeafIndexes(max_indexes, num_indexes) => {
+            Self::WrongNumberOfLeaves(expected, actual) => {
                 write!(
                     f,
-                    "number of leaf indexes cannot exceed {max_indexes}, but {num_indexes} was provided"
+                    "expected {expected} leaves for the provided leaf indexes, but {actual} were provided"
                 )
             }
+            Self::DepthMismatch(expected, actual) => {
+                write!(
+                    f,
+                    "all Merkle paths must have depth {expected}, but a path of depth {actual} was provided"
+                )
+            }
+            Self::InvalidPathLength(length) => {
+                write!(f, "a Merkle path of length {length} is not valid")
+            }
             Self::InvalidProof => {
                 write!(f, "Merkle proof is invalid")
             }
+            Self::InvalidLeaves(indexes) => {
+                write!(
+                    f,
+                    "Merkle proof is invalid for leaves at indexes {indexes:?}"
+                )
+            }
+            Self::InvalidArity(arity) => {
+                write!(
+                    f,
+                    "Merkle tree arity must be 2, 4, 8, or 16, but was {arity}"
+                )
+            }
+            Self::InconsistentPartitions => {
+                write!(f, "all partition trees must have the same depth and arity")
+            }
+            Self::InvalidPartialDepth(tree_depth, depth) => {
+                write!(
+                    f,
+                    "partial tree depth must be smaller than {tree_depth}, but was {depth}"
+                )
+            }
+            Self::InvalidSubtree(index) => {
+                write!(
+                    f,
+                    "leaves provided for subtree {index} do not match the committed subtree root"
+                )
+            }
+            Self::StorageError(err_msg) => {
+                write!(f, "Merkle tree storage operation failed: {err_msg}")
+            }
         }
     }
 }
@@ -3,10 +3,10 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{ByteDigest, ElementHasher, Hasher};
+use super::{serialized_element_bytes, ByteDigest, Digest, ElementHasher, HashFunction, Hasher};
 use core::{convert::TryInto, fmt::Debug, marker::PhantomData};
 use math::{FieldElement, StarkField};
-use utils::ByteWriter;
+use utils::{collections::Vec, ByteWriter};
 
 #[cfg(test)]
 mod tests;
@@ -24,6 +24,8 @@ impl<B: StarkField> Hasher for Blake3_256<B> {
 
     const COLLISION_RESISTANCE: u32 = 128;
 
+    const ID: HashFunction = HashFunction::Blake3_256;
+
     fn hash(bytes: &[u8]) -> Self::Digest {
         ByteDigest(*blake3::hash(bytes).as_bytes())
     }
@@ -32,30 +34,57 @@ impl<B: StarkField> Hasher for Blake3_256<B> {
         ByteDigest(blake3::hash(ByteDigest::digests_as_bytes(values)).into())
     }
 
+    fn merge_many(values: &[Self::Digest]) -> Self::Digest {
+        ByteDigest(blake3::hash(ByteDigest::digests_as_bytes(values)).into())
+    }
+
     fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
         let mut data = [0; 40];
         data[..32].copy_from_slice(&seed.0);
         data[32..].copy_from_slice(&value.to_le_bytes());
         ByteDigest(*blake3::hash(&data).as_bytes())
     }
+
+    fn hash_keyed(key: &Self::Digest, bytes: &[u8]) -> Self::Digest {
+        ByteDigest(*blake3::keyed_hash(&key.0, bytes).as_bytes())
+    }
 }
 
 impl<B: StarkField> ElementHasher for Blake3_256<B> {
     type BaseField = B;
 
     fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
-        if B::IS_CANONICAL {
-            // when element's internal and canonical representations are the same, we can hash
-            // element bytes directly
-            let bytes = E::elements_as_bytes(elements);
-            ByteDigest(*blake3::hash(bytes).as_bytes())
-        } else {
-            // when elements' internal and canonical representations differ, we need to serialize
-            // them before hashing
-            let mut hasher = BlakeHasher::new();
-            hasher.write(elements);
-            ByteDigest(hasher.finalize())
+        match serialized_element_bytes(elements) {
+            Some(bytes) => ByteDigest(*blake3::hash(bytes).as_bytes()),
+            None => {
+                let mut hasher = BlakeHasher::new(elements.len() * E::ELEMENT_BYTES);
+                hasher.write(elements);
+                ByteDigest(hasher.finalize())
+            }
+        }
+    }
+
+    fn hash_iter<E, I>(elements: I) -> Self::Digest
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+        I: IntoIterator<Item = E>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        let elements = elements.into_iter();
+        let mut hasher = BlakeHasher::new(elements.len() * E::ELEMENT_BYTES);
+        for element in elements {
+            hasher.write(element);
         }
+        ByteDigest(hasher.finalize())
+    }
+
+    fn hash_elements_keyed<E: FieldElement<BaseField = Self::BaseField>>(
+        key: &Self::Digest,
+        elements: &[E],
+    ) -> Self::Digest {
+        let mut hasher = BlakeHasher::new_keyed(&key.0, elements.len() * E::ELEMENT_BYTES);
+        hasher.write(elements);
+        ByteDigest(hasher.finalize())
     }
 }
 
@@ -72,6 +101,8 @@ impl<B: StarkField> Hasher for Blake3_192<B> {
 
     const COLLISION_RESISTANCE: u32 = 96;
 
+    const ID: HashFunction = HashFunction::Blake3_192;
+
     fn hash(bytes: &[u8]) -> Self::Digest {
         let result = blake3::hash(bytes);
         ByteDigest(result.as_bytes()[..24].try_into().unwrap())
@@ -82,6 +113,11 @@ impl<B: StarkField> Hasher for Blake3_192<B> {
         ByteDigest(result.as_bytes()[..24].try_into().unwrap())
     }
 
+    fn merge_many(values: &[Self::Digest]) -> Self::Digest {
+        let result = blake3::hash(ByteDigest::digests_as_bytes(values));
+        ByteDigest(result.as_bytes()[..24].try_into().unwrap())
+    }
+
     fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
         let mut data = [0; 32];
         data[..24].copy_from_slice(&seed.0);
@@ -90,51 +126,132 @@ impl<B: StarkField> Hasher for Blake3_192<B> {
         let result = blake3::hash(&data);
         ByteDigest(result.as_bytes()[..24].try_into().unwrap())
     }
+
+    fn hash_keyed(key: &Self::Digest, bytes: &[u8]) -> Self::Digest {
+        let result = blake3::keyed_hash(&key.as_bytes(), bytes);
+        ByteDigest(result.as_bytes()[..24].try_into().unwrap())
+    }
 }
 
 impl<B: StarkField> ElementHasher for Blake3_192<B> {
     type BaseField = B;
 
     fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
-        if B::IS_CANONICAL {
-            // when element's internal and canonical representations are the same, we can hash
-            // element bytes directly
-            let bytes = E::elements_as_bytes(elements);
-            let result = blake3::hash(bytes);
-            ByteDigest(result.as_bytes()[..24].try_into().unwrap())
-        } else {
-            // when elements' internal and canonical representations differ, we need to serialize
-            // them before hashing
-            let mut hasher = BlakeHasher::new();
-            hasher.write(elements);
-            let result = hasher.finalize();
-            ByteDigest(result[..24].try_into().unwrap())
+        match serialized_element_bytes(elements) {
+            Some(bytes) => {
+                let result = blake3::hash(bytes);
+                ByteDigest(result.as_bytes()[..24].try_into().unwrap())
+            }
+            None => {
+                let mut hasher = BlakeHasher::new(elements.len() * E::ELEMENT_BYTES);
+                hasher.write(elements);
+                let result = hasher.finalize();
+                ByteDigest(result[..24].try_into().unwrap())
+            }
         }
     }
+
+    fn hash_iter<E, I>(elements: I) -> Self::Digest
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+        I: IntoIterator<Item = E>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        let elements = elements.into_iter();
+        let mut hasher = BlakeHasher::new(elements.len() * E::ELEMENT_BYTES);
+        for element in elements {
+            hasher.write(element);
+        }
+        let result = hasher.finalize();
+        ByteDigest(result[..24].try_into().unwrap())
+    }
+
+    fn hash_elements_keyed<E: FieldElement<BaseField = Self::BaseField>>(
+        key: &Self::Digest,
+        elements: &[E],
+    ) -> Self::Digest {
+        let mut hasher = BlakeHasher::new_keyed(&key.as_bytes(), elements.len() * E::ELEMENT_BYTES);
+        hasher.write(elements);
+        let result = hasher.finalize();
+        ByteDigest(result[..24].try_into().unwrap())
+    }
 }
 
 // BLAKE HASHER
 // ================================================================================================
 
+/// Maximum number of bytes accumulated by [BlakeHasher] before they are fed into the BLAKE3
+/// hasher.
+///
+/// BLAKE3 can compress several 1 KiB chunks in parallel using SIMD instructions, but only when
+/// multiple chunks are passed to a single `update()` call. Thus, instead of feeding serialized
+/// elements into the hasher one at a time, we buffer them and pass them in batches of 16 chunks.
+const BUFFER_SIZE: usize = 16 * 1024;
+
 /// Wrapper around BLAKE3 hasher to implement [ByteWriter] trait for it.
-struct BlakeHasher(blake3::Hasher);
+///
+/// Written bytes are accumulated in an internal buffer and are passed to the BLAKE3 streaming API
+/// in large batches; the resulting digest is the same as the digest of all written bytes hashed
+/// at once. The buffer is sized to the expected input and is never zeroed, so that hashing small
+/// inputs (e.g., single rows of a trace) does not pay for the full [BUFFER_SIZE] buffer.
+struct BlakeHasher {
+    hasher: blake3::Hasher,
+    buffer: Vec<u8>,
+}
 
 impl BlakeHasher {
-    pub fn new() -> Self {
-        Self(blake3::Hasher::new())
+    /// Returns a new hasher for an input of the specified number of bytes.
+    pub fn new(num_bytes: usize) -> Self {
+        Self {
+            hasher: blake3::Hasher::new(),
+            buffer: Self::new_buffer(num_bytes),
+        }
     }
 
-    pub fn finalize(&self) -> [u8; 32] {
-        *self.0.finalize().as_bytes()
+    /// Returns a new hasher in BLAKE3 keyed hashing mode for an input of the specified number of
+    /// bytes.
+    pub fn new_keyed(key: &[u8; 32], num_bytes: usize) -> Self {
+        Self {
+            hasher: blake3::Hasher::new_keyed(key),
+            buffer: Self::new_buffer(num_bytes),
+        }
+    }
+
+    pub fn finalize(mut self) -> [u8; 32] {
+        self.flush();
+        *self.hasher.finalize().as_bytes()
+    }
+
+    /// Returns an empty buffer large enough to hold the input of the specified number of bytes
+    /// but no larger than [BUFFER_SIZE].
+    fn new_buffer(num_bytes: usize) -> Vec<u8> {
+        Vec::with_capacity(num_bytes.min(BUFFER_SIZE))
+    }
+
+    /// Passes all buffered bytes to the underlying BLAKE3 hasher and clears the buffer.
+    fn flush(&mut self) {
+        if !self.buffer.is_empty() {
+            self.hasher.update(&self.buffer);
+            self.buffer.clear();
+        }
     }
 }
 
 impl ByteWriter for BlakeHasher {
     fn write_u8(&mut self, value: u8) {
-        self.0.update(&[value]);
+        self.write_u8_slice(&[value]);
     }
 
     fn write_u8_slice(&mut self, values: &[u8]) {
-        self.0.update(values);
+        let capacity = self.buffer.capacity();
+        if self.buffer.len() + values.len() > capacity {
+            self.flush();
+            // slices which do not fit into the buffer are passed to the hasher directly
+            if values.len() >= capacity {
+                self.hasher.update(values);
+                return;
+            }
+        }
+        self.buffer.extend_from_slice(values);
     }
 }
@@ -3,9 +3,13 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{Blake3_256, ElementHasher, Hasher};
-use math::{fields::f62::BaseElement, FieldElement};
-use rand_utils::rand_array;
+use super::{Blake3_192, Blake3_256, ElementHasher, Hasher};
+use math::{
+    fields::{f128, f62::BaseElement, QuadExtension},
+    FieldElement,
+};
+use rand_utils::{rand_array, rand_vector};
+use utils::Serializable;
 
 #[test]
 fn hash_padding() {
@@ -28,3 +32,97 @@ fn hash_elements_padding() {
     let r2 = Blake3_256::hash_elements(&e2);
     assert_ne!(r1, r2);
 }
+
+#[test]
+fn hash_elements_streaming() {
+    // f62 elements are not in canonical representation, and thus, they are serialized and fed
+    // into the hasher in batches; the result should be the same as hashing serialized bytes
+    for num_elements in [1, 7, 2048, 2049, 5000] {
+        let elements = rand_vector::<BaseElement>(num_elements);
+        let bytes = elements.to_bytes();
+
+        let expected = Blake3_256::<BaseElement>::hash(&bytes);
+        assert_eq!(expected, Blake3_256::hash_elements(&elements));
+
+        let expected = Blake3_192::<BaseElement>::hash(&bytes);
+        assert_eq!(expected, Blake3_192::hash_elements(&elements));
+    }
+}
+
+#[test]
+fn hash_extension_elements() {
+    // extension field elements are hashed natively as sequences of their base field elements;
+    // this should be the same as hashing serialized extension elements
+    let elements = rand_vector::<QuadExtension<BaseElement>>(7);
+    let base_elements = QuadExtension::<BaseElement>::as_base_elements(&elements);
+    assert_eq!(
+        Blake3_256::<BaseElement>::hash(&elements.to_bytes()),
+        Blake3_256::hash_elements(&elements)
+    );
+    assert_eq!(
+        Blake3_256::hash_elements(base_elements),
+        Blake3_256::hash_elements(&elements)
+    );
+
+    let elements = rand_vector::<QuadExtension<f128::BaseElement>>(7);
+    let base_elements = QuadExtension::<f128::BaseElement>::as_base_elements(&elements);
+    assert_eq!(
+        Blake3_256::<f128::BaseElement>::hash(&elements.to_bytes()),
+        Blake3_256::hash_elements(&elements)
+    );
+    assert_eq!(
+        Blake3_256::hash_elements(base_elements),
+        Blake3_256::hash_elements(&elements)
+    );
+}
+
+#[test]
+fn hash_iter() {
+    // hashing elements via an iterator should be the same as hashing a slice of elements for
+    // both canonical and non-canonical field elements
+    for num_elements in [1, 7, 2049] {
+        let elements = rand_vector::<BaseElement>(num_elements);
+        assert_eq!(
+            Blake3_256::hash_elements(&elements),
+            Blake3_256::hash_iter(elements.iter().copied())
+        );
+        assert_eq!(
+            Blake3_192::hash_elements(&elements),
+            Blake3_192::hash_iter(elements.iter().copied())
+        );
+
+        let elements = rand_vector::<f128::BaseElement>(num_elements);
+        assert_eq!(
+            Blake3_256::hash_elements(&elements),
+            Blake3_256::hash_iter(elements.iter().copied())
+        );
+    }
+}
+
+#[test]
+fn hash_keyed() {
+    let key1 = Blake3_256::<BaseElement>::derive_key(b"trace");
+    let key2 = Blake3_256::<BaseElement>::derive_key(b"constraints");
+    let bytes = [1_u8, 2, 3, 4];
+
+    // the same bytes hashed with different keys (or without a key) should result in different
+    // digests
+    let r1 = Blake3_256::<BaseElement>::hash_keyed(&key1, &bytes);
+    let r2 = Blake3_256::<BaseElement>::hash_keyed(&key2, &bytes);
+    assert_ne!(r1, r2);
+    assert_ne!(r1, Blake3_256::<BaseElement>::hash(&bytes));
+
+    // hashing elements with a key should be the same as hashing their serialized bytes with
+    // the same key
+    let elements = rand_vector::<BaseElement>(17);
+    assert_eq!(
+        Blake3_256::<BaseElement>::hash_keyed(&key1, &elements.to_bytes()),
+        Blake3_256::hash_elements_keyed(&key1, &elements)
+    );
+
+    let key = Blake3_192::<BaseElement>::derive_key(b"trace");
+    assert_eq!(
+        Blake3_192::<BaseElement>::hash_keyed(&key, &elements.to_bytes()),
+        Blake3_192::hash_elements_keyed(&key, &elements)
+    );
+}
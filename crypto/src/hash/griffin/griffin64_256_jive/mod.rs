@@ -4,7 +4,7 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::super::mds::mds_f64_8x8::mds_multiply;
-use super::{Digest, ElementHasher, Hasher};
+use super::{Digest, DigestToElements, ElementHasher, HashFunction, Hasher};
 use core::convert::TryInto;
 use core::ops::Range;
 use math::{fields::f64::BaseElement, FieldElement, StarkField};
@@ -48,12 +48,12 @@ const NUM_ROUNDS: usize = 7;
 /// Implementation of [Hasher] trait for Griffin hash function with 256-bit output.
 ///
 /// The hash function is implemented according to the Griffin
-/// [specifications](https://eprint.iacr.org/2020/1143.pdf) with the following caveats:
+/// [specifications](https://eprint.iacr.org/2022/403.pdf) with the following caveats:
 /// * We set the number of rounds to 7, which implies a 15% security margin instead of the 20%
 ///   margin used in the specifications (a 20% margin rounds up to 8 rounds). The primary
 ///   motivation for this is that having the number of rounds be one less than a power of two
 ///   simplifies AIR design for computations involving the hash function.
-/// *  When hashing a sequence of elements, implement the Hirose padding rule. However, it also
+/// * When hashing a sequence of elements, implement the Hirose padding rule. However, it also
 ///   means that our instantiation of Griffin cannot be used in a stream mode as the number
 ///   of elements to be hashed must be known upfront.
 /// * Instead of using the suggested matrix as described in Griffin paper, we use a methodology
@@ -98,6 +98,8 @@ impl Hasher for GriffinJive64_256 {
 
     const COLLISION_RESISTANCE: u32 = 128;
 
+    const ID: HashFunction = HashFunction::GriffinJive64_256;
+
     fn hash(bytes: &[u8]) -> Self::Digest {
         // compute the number of elements required to represent the string; we will be processing
         // the string in 7-byte chunks, thus the number of elements will be equal to the number
@@ -180,6 +182,15 @@ impl Hasher for GriffinJive64_256 {
 
     // We do not rely on the sponge construction to build our compression function. Instead, we use
     // the Jive compression mode designed in https://eprint.iacr.org/2022/840.pdf.
+    // Merging two digests uses the Jive compression mode; for any other number of digests, we
+    // fall back onto the sponge construction.
+    fn merge_many(values: &[Self::Digest]) -> Self::Digest {
+        if let [a, b] = values {
+            return Self::merge(&[*a, *b]);
+        }
+        Self::hash_elements(ElementDigest::digests_as_elements(values))
+    }
+
     fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
         // initialize the state as follows:
         // - seed is copied into the first 4 elements of the state.
@@ -210,13 +221,24 @@ impl ElementHasher for GriffinJive64_256 {
     type BaseField = BaseElement;
 
     fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
-        // convert the elements into a list of base field elements
-        let elements = E::as_base_elements(elements);
+        Self::hash_iter(elements.iter().copied())
+    }
+
+    fn hash_iter<E, I>(elements: I) -> Self::Digest
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+        I: IntoIterator<Item = E>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        // elements are absorbed as sequences of their base field elements; thus, the number of
+        // absorbed base field elements is known in advance
+        let elements = elements.into_iter();
+        let num_elements = elements.len() * (E::ELEMENT_BYTES / BaseElement::ELEMENT_BYTES);
 
         // initialize state to all zeros, except for the first element of the capacity part, which
         // is set to 1 if the number of elements is not a multiple of RATE_WIDTH.
         let mut state = [BaseElement::ZERO; STATE_WIDTH];
-        if elements.len() % RATE_WIDTH != 0 {
+        if num_elements % RATE_WIDTH != 0 {
             state[CAPACITY_RANGE.start] = BaseElement::ONE;
         }
 
@@ -224,12 +246,14 @@ impl ElementHasher for GriffinJive64_256 {
         // up; then apply the Griffin permutation and start absorbing again; repeat until all
         // elements have been absorbed
         let mut i = 0;
-        for &element in elements.iter() {
-            state[RATE_RANGE.start + i] += element;
-            i += 1;
-            if i % RATE_WIDTH == 0 {
-                Self::apply_permutation(&mut state);
-                i = 0;
+        for element in elements {
+            for &element in E::as_base_elements(&[element]) {
+                state[RATE_RANGE.start + i] += element;
+                i += 1;
+                if i % RATE_WIDTH == 0 {
+                    Self::apply_permutation(&mut state);
+                    i = 0;
+                }
             }
         }
 
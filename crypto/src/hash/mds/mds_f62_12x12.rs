@@ -0,0 +1,105 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+// DELAYED-REDUCTION MDS MULTIPLICATION HELPER FUNCTIONS
+// ================================================================================================
+
+use math::{fields::f62::BaseElement, StarkField};
+
+// This module contains helper functions to perform a 12x12 matrix-vector multiplication over the
+// 62-bit field for an arbitrary (i.e., unstructured) MDS matrix. Instead of performing a full
+// Montgomery multiplication for every one of the 144 products, we multiply inner Montgomery
+// representations of canonical values, accumulate the 128-bit products, and perform a single
+// Montgomery reduction for every four products.
+//
+// This is sound because for canonical values a, b < M, we have a * b < M^2, and thus, a sum of
+// four such products is smaller than 4 * M^2 < M * 2^64 (since M < 2^62). Montgomery reduction of
+// a value smaller than M * 2^64 is guaranteed to produce a result in the range [0, 2M), which is
+// a valid internal representation of a field element.
+
+/// Field modulus.
+const M: u64 = BaseElement::MODULUS;
+
+/// -M^{-1} mod 2^64.
+const U: u64 = 4611624995532046335;
+
+/// Number of products accumulated before a Montgomery reduction is performed.
+const ACC_SIZE: usize = 4;
+
+/// Converts the provided matrix into a matrix of canonical Montgomery representations of its
+/// elements (i.e., all values are guaranteed to be in the range [0, M)).
+pub(crate) const fn to_canonical_matrix(matrix: &[[BaseElement; 12]; 12]) -> [[u64; 12]; 12] {
+    let mut result = [[0u64; 12]; 12];
+    let mut i = 0;
+    while i < 12 {
+        let mut j = 0;
+        while j < 12 {
+            result[i][j] = canonicalize(matrix[i][j].inner());
+            j += 1;
+        }
+        i += 1;
+    }
+    result
+}
+
+/// Multiplies the `state` by the provided `matrix`; the matrix is assumed to be produced by the
+/// [to_canonical_matrix()] function.
+#[inline(always)]
+pub(crate) fn mds_multiply(state: &mut [BaseElement; 12], matrix: &[[u64; 12]; 12]) {
+    let mut values = [0u64; 12];
+    for (v, s) in values.iter_mut().zip(state.iter()) {
+        *v = canonicalize(s.inner());
+    }
+
+    for (s, row) in state.iter_mut().zip(matrix) {
+        let mut result = BaseElement::from_mont(0);
+        for (values, coeffs) in values
+            .chunks_exact(ACC_SIZE)
+            .zip(row.chunks_exact(ACC_SIZE))
+        {
+            let mut acc = 0u128;
+            for (&v, &c) in values.iter().zip(coeffs) {
+                acc += (v as u128) * (c as u128);
+            }
+            result += BaseElement::from_mont(mont_reduce(acc));
+        }
+        *s = result;
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Reduces a value in the range [0, 2M) into the range [0, M).
+#[inline(always)]
+const fn canonicalize(value: u64) -> u64 {
+    if value >= M {
+        value - M
+    } else {
+        value
+    }
+}
+
+/// Computes x * 2^-64 mod M; x is assumed to be smaller than M * 2^64, and the result is in the
+/// range [0, 2M).
+#[inline(always)]
+fn mont_reduce(x: u128) -> u64 {
+    let q = (x as u64).wrapping_mul(U);
+    let z = x + (q as u128) * (M as u128);
+    (z >> 64) as u64
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{M, U};
+
+    #[test]
+    fn mont_constants() {
+        assert_eq!(u64::MAX, M.wrapping_mul(U));
+    }
+}
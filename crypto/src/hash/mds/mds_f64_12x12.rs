@@ -9,17 +9,17 @@
 use math::fft::real_u64::{fft4_real, ifft4_real_unreduced};
 use math::{fields::f64::BaseElement, FieldElement};
 
-/// This module contains helper functions as well as constants used to perform a 12x12 vector-matrix
-/// multiplication. The special form of our MDS matrix i.e. being circular, allows us to reduce
-/// the vector-matrix multiplication to a Hadamard product of two vectors in "frequency domain".
-/// This follows from the simple fact that every circulant matrix has the columns of the discrete
-/// Fourier transform matrix as orthogonal eigenvectors.
-/// The implementation also avoids the use of 3-point FFTs, and 3-point iFFTs, and substitutes that
-/// with explicit expressions. It also avoids, due to the form of our matrix in the frequency domain,
-/// divisions by 2 and repeated modular reductions. This is because of our explicit choice of
-/// an MDS matrix that has small powers of 2 entries in frequency domain.
-/// The following implementation has benefited greatly from the discussions and insights of
-/// Hamish Ivey-Law and Jacqueline Nabaglo of Polygon Zero.
+// This module contains helper functions as well as constants used to perform a 12x12 vector-matrix
+// multiplication. The special form of our MDS matrix i.e. being circular, allows us to reduce
+// the vector-matrix multiplication to a Hadamard product of two vectors in "frequency domain".
+// This follows from the simple fact that every circulant matrix has the columns of the discrete
+// Fourier transform matrix as orthogonal eigenvectors.
+// The implementation also avoids the use of 3-point FFTs, and 3-point iFFTs, and substitutes that
+// with explicit expressions. It also avoids, due to the form of our matrix in the frequency domain,
+// divisions by 2 and repeated modular reductions. This is because of our explicit choice of
+// an MDS matrix that has small powers of 2 entries in frequency domain.
+// The following implementation has benefited greatly from the discussions and insights of
+// Hamish Ivey-Law and Jacqueline Nabaglo of Polygon Zero.
 
 // MDS matrix in frequency domain.
 // More precisely, this is the output of the three 4-point (real) FFTs of the first column of
@@ -5,6 +5,8 @@
 
 //! An implementation of matrix-vector products for several MDS matrices.
 
+pub mod mds_f62_12x12;
+
 pub mod mds_f64_8x8;
 
 pub mod mds_f64_12x12;
@@ -5,13 +5,16 @@
 
 use core::{fmt::Debug, slice};
 use math::{FieldElement, StarkField};
-use utils::{ByteReader, Deserializable, DeserializationError, Serializable};
+use utils::{
+    borrow::Cow, collections::Vec, ByteReader, ByteWriter, Deserializable, DeserializationError,
+    Serializable, SliceReader,
+};
 
 mod blake;
 pub use blake::{Blake3_192, Blake3_256};
 
 mod sha;
-pub use sha::Sha3_256;
+pub use sha::{Keccak256, Sha2_256, Sha3_256};
 
 mod mds;
 
@@ -21,6 +24,15 @@ pub use rescue::{Rp62_248, Rp64_256, RpJive64_256};
 mod griffin;
 pub use griffin::GriffinJive64_256;
 
+mod poseidon;
+pub use poseidon::Poseidon64_256;
+
+mod truncated;
+pub use truncated::Truncated;
+
+#[cfg(feature = "std")]
+pub mod params;
+
 // HASHER TRAITS
 // ================================================================================================
 
@@ -30,6 +42,8 @@ pub use griffin::GriffinJive64_256;
 /// * A sequence of bytes.
 /// * Two digests - this is intended for use in Merkle tree constructions.
 /// * A digests and a u64 value - this intended for use in PRNG or PoW contexts.
+/// * A key and a sequence of bytes - this is intended for domain separation of hashes computed
+///   for different purposes (e.g., leaves of different Merkle trees, or different applications).
 pub trait Hasher {
     /// Specifies a digest type returned by this hasher.
     type Digest: Digest;
@@ -37,6 +51,13 @@ pub trait Hasher {
     /// Collision resistance of the hash function measured in bits.
     const COLLISION_RESISTANCE: u32;
 
+    /// Registered identifier of the hash function.
+    ///
+    /// This identifier is recorded in proof contexts so that a verifier instantiated with a
+    /// different hash function can reject a proof before verifying it. Hash functions defined
+    /// outside of this crate should keep the default [HashFunction::Unspecified] identifier.
+    const ID: HashFunction = HashFunction::Unspecified;
+
     /// Returns a hash of the provided sequence of bytes.
     fn hash(bytes: &[u8]) -> Self::Digest;
 
@@ -44,14 +65,78 @@ pub trait Hasher {
     /// Merkle trees.
     fn merge(values: &[Self::Digest; 2]) -> Self::Digest;
 
+    /// Returns a hash of the provided digests. This method is intended for use in construction of
+    /// Merkle trees with arity greater than two.
+    ///
+    /// When exactly two digests are provided, the result must be the same as the result of
+    /// [Hasher::merge()]. The default implementation hashes a concatenation of byte
+    /// representations of the digests; hashers are expected to override it with a more efficient
+    /// implementation where possible.
+    fn merge_many(values: &[Self::Digest]) -> Self::Digest {
+        if let [a, b] = values {
+            return Self::merge(&[*a, *b]);
+        }
+        let mut bytes = Vec::with_capacity(values.len() * 32);
+        for value in values {
+            bytes.extend_from_slice(&value.as_bytes());
+        }
+        Self::hash(&bytes)
+    }
+
+    /// Computes hashes of multiple independent pairs of digests and writes them into `result`
+    /// such that `result[i] = merge(values[i])`.
+    ///
+    /// This method is intended for use in construction of Merkle trees, where all nodes at a
+    /// given level can be computed independently of each other. The default implementation merges
+    /// one pair at a time, and none of the hash functions provided by this crate override it;
+    /// thus, for now, this method serves only as an extension point for hashers which can
+    /// compress several inputs at once (e.g., using SIMD instructions). Leaves of Merkle trees are
+    /// always hashed one at a time.
+    ///
+    /// # Panics
+    /// Panics if `values` and `result` have different lengths.
+    fn merge_batch(values: &[[Self::Digest; 2]], result: &mut [Self::Digest]) {
+        assert_eq!(
+            values.len(),
+            result.len(),
+            "number of inputs must be the same as the number of outputs"
+        );
+        for (value, result) in values.iter().zip(result.iter_mut()) {
+            *result = Self::merge(value);
+        }
+    }
+
     /// Returns hash(`seed` || `value`). This method is intended for use in PRNG and PoW contexts.
     fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest;
+
+    /// Returns a key derived from the specified personalization string.
+    ///
+    /// The key can be passed to [Hasher::hash_keyed()] and [ElementHasher::hash_elements_keyed()]
+    /// to make sure that hashes computed for different purposes (e.g., leaves of trace and
+    /// constraint commitment trees, or leaves of trees belonging to different applications) can
+    /// never collide with each other. The default implementation returns hash(`personalization`).
+    fn derive_key(personalization: &[u8]) -> Self::Digest {
+        Self::hash(personalization)
+    }
+
+    /// Returns a hash of the provided sequence of bytes keyed with the specified `key`.
+    ///
+    /// The default implementation returns hash(`key` || hash(`bytes`)); hash functions which
+    /// support keyed hashing natively should override it.
+    fn hash_keyed(key: &Self::Digest, bytes: &[u8]) -> Self::Digest {
+        Self::merge(&[*key, Self::hash(bytes)])
+    }
 }
 
 /// Defines a cryptographic hash function for hashing field elements.
 ///
 /// This trait defines a hash procedure for a sequence of field elements. The elements can be
 /// either in the base field specified for this hasher, or in an extension of the base field.
+///
+/// Extension field elements are hashed natively: a hash of a slice of extension field elements
+/// is the same as a hash of the slice of base field elements into which the extension elements
+/// decompose. Thus, extension field elements (e.g., rows of composition polynomial evaluations)
+/// never need to be serialized into an intermediate buffer of bytes before being hashed.
 pub trait ElementHasher: Hasher {
     /// Specifies a base field for elements which can be hashed with this hasher.
     type BaseField: StarkField;
@@ -60,6 +145,196 @@ pub trait ElementHasher: Hasher {
     fn hash_elements<E>(elements: &[E]) -> Self::Digest
     where
         E: FieldElement<BaseField = Self::BaseField>;
+
+    /// Returns a hash of field elements yielded by the provided iterator.
+    ///
+    /// The result is the same as the result of [hash_elements()](ElementHasher::hash_elements)
+    /// applied to a slice of the same elements. However, the elements do not need to be stored
+    /// contiguously in memory; for example, this can be used to hash a row of a column-major
+    /// matrix without copying the row into a separate buffer first.
+    ///
+    /// The default implementation collects the elements into a vector; hash functions which can
+    /// absorb elements one at a time should override it.
+    fn hash_iter<E, I>(elements: I) -> Self::Digest
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+        I: IntoIterator<Item = E>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        let elements = elements.into_iter().collect::<Vec<_>>();
+        Self::hash_elements(&elements)
+    }
+
+    /// Returns a hash of the provided field elements keyed with the specified `key`.
+    ///
+    /// The default implementation returns hash(`key` || hash_elements(`elements`)); hash
+    /// functions which support keyed hashing natively should override it.
+    fn hash_elements_keyed<E>(key: &Self::Digest, elements: &[E]) -> Self::Digest
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        Self::merge(&[*key, Self::hash_elements(elements)])
+    }
+}
+
+/// Returns in-memory bytes of the provided elements if these bytes are the same as the serialized
+/// bytes of the elements, and None otherwise.
+///
+/// This is the case when internal and canonical representations of the elements are the same,
+/// and the target is little-endian. Hash functions which operate on bytes use this to hash
+/// elements without serializing them first; otherwise, elements are serialized into the hasher
+/// (all at once, or one at a time when hashing an iterator), which produces the same bytes.
+fn serialized_element_bytes<E: FieldElement>(elements: &[E]) -> Option<&[u8]> {
+    if E::BaseField::IS_CANONICAL && cfg!(target_endian = "little") {
+        Some(E::elements_as_bytes(elements))
+    } else {
+        None
+    }
+}
+
+// HASH FUNCTION IDENTIFIERS
+// ================================================================================================
+
+/// Registered identifiers of hash functions which can be used to generate STARK proofs.
+///
+/// Identifiers are serialized as a single byte; values of existing identifiers never change, and
+/// new identifiers are always assigned the next unused value. Byte values with the most
+/// significant bit set are reserved for [truncated](HashFunction::Truncated) hash functions.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum HashFunction {
+    /// A hash function which does not have a registered identifier (0).
+    Unspecified,
+    /// BLAKE3 hash function with 256-bit output (1).
+    Blake3_256,
+    /// BLAKE3 hash function with 192-bit output (2).
+    Blake3_192,
+    /// SHA3 hash function with 256-bit output (3).
+    Sha3_256,
+    /// Keccak hash function with 256-bit output (4).
+    Keccak256,
+    /// SHA2 hash function with 256-bit output (5).
+    Sha2_256,
+    /// Rescue Prime hash function over the 64-bit field with 256-bit output (6).
+    Rp64_256,
+    /// Rescue Prime hash function over the 64-bit field with 256-bit output and Jive compression
+    /// (7).
+    RpJive64_256,
+    /// Rescue Prime hash function over the 62-bit field with 248-bit output (8).
+    Rp62_248,
+    /// Griffin hash function over the 64-bit field with 256-bit output and Jive compression (9).
+    GriffinJive64_256,
+    /// Poseidon hash function over the 64-bit field with 256-bit output (10).
+    Poseidon64_256,
+    /// One of [Blake3_256](HashFunction::Blake3_256), [Sha3_256](HashFunction::Sha3_256),
+    /// [Keccak256](HashFunction::Keccak256), or [Sha2_256](HashFunction::Sha2_256) hash
+    /// functions with digests truncated to the specified number of bytes (between 1 and 32).
+    ///
+    /// The identifier is `0x80 | (i << 5) | (digest_size - 1)`, where `i` is 0, 1, 2, or 3
+    /// respectively for the hash functions listed above.
+    Truncated(&'static HashFunction, u8),
+}
+
+/// Hash functions with 256-bit byte digests which have identifiers for truncated digests.
+const TRUNCATABLE_HASH_FUNCTIONS: [&HashFunction; 4] = [
+    &HashFunction::Blake3_256,
+    &HashFunction::Sha3_256,
+    &HashFunction::Keccak256,
+    &HashFunction::Sha2_256,
+];
+
+/// Flag which marks identifiers of truncated hash functions.
+const TRUNCATED_ID_FLAG: u8 = 0x80;
+
+impl HashFunction {
+    /// Returns the identifier of the specified hash function with digests truncated to
+    /// `digest_size` bytes.
+    ///
+    /// Returns [HashFunction::Unspecified] if `hash_function` does not have identifiers for
+    /// truncated digests, or if `digest_size` is not between 1 and 32.
+    pub const fn truncated(hash_function: HashFunction, digest_size: usize) -> Self {
+        if digest_size == 0 || digest_size > 32 {
+            return HashFunction::Unspecified;
+        }
+        let index = match hash_function {
+            HashFunction::Blake3_256 => 0,
+            HashFunction::Sha3_256 => 1,
+            HashFunction::Keccak256 => 2,
+            HashFunction::Sha2_256 => 3,
+            _ => return HashFunction::Unspecified,
+        };
+        HashFunction::Truncated(TRUNCATABLE_HASH_FUNCTIONS[index], digest_size as u8)
+    }
+
+    /// Returns the single-byte identifier of this hash function.
+    pub const fn id(&self) -> u8 {
+        match self {
+            HashFunction::Unspecified => 0,
+            HashFunction::Blake3_256 => 1,
+            HashFunction::Blake3_192 => 2,
+            HashFunction::Sha3_256 => 3,
+            HashFunction::Keccak256 => 4,
+            HashFunction::Sha2_256 => 5,
+            HashFunction::Rp64_256 => 6,
+            HashFunction::RpJive64_256 => 7,
+            HashFunction::Rp62_248 => 8,
+            HashFunction::GriffinJive64_256 => 9,
+            HashFunction::Poseidon64_256 => 10,
+            HashFunction::Truncated(hash_function, digest_size) => {
+                let index = match hash_function {
+                    HashFunction::Blake3_256 => 0,
+                    HashFunction::Sha3_256 => 1,
+                    HashFunction::Keccak256 => 2,
+                    _ => 3,
+                };
+                TRUNCATED_ID_FLAG | (index << 5) | (*digest_size - 1)
+            }
+        }
+    }
+
+    /// Returns the hash function with the specified identifier.
+    ///
+    /// # Errors
+    /// Returns an error if `id` is not a registered hash function identifier.
+    pub fn from_id(id: u8) -> Result<Self, DeserializationError> {
+        match id {
+            0 => Ok(HashFunction::Unspecified),
+            1 => Ok(HashFunction::Blake3_256),
+            2 => Ok(HashFunction::Blake3_192),
+            3 => Ok(HashFunction::Sha3_256),
+            4 => Ok(HashFunction::Keccak256),
+            5 => Ok(HashFunction::Sha2_256),
+            6 => Ok(HashFunction::Rp64_256),
+            7 => Ok(HashFunction::RpJive64_256),
+            8 => Ok(HashFunction::Rp62_248),
+            9 => Ok(HashFunction::GriffinJive64_256),
+            10 => Ok(HashFunction::Poseidon64_256),
+            id if id & TRUNCATED_ID_FLAG != 0 => {
+                let index = ((id >> 5) & 0b11) as usize;
+                let digest_size = (id & 0b11111) + 1;
+                Ok(HashFunction::Truncated(
+                    TRUNCATABLE_HASH_FUNCTIONS[index],
+                    digest_size,
+                ))
+            }
+            value => Err(DeserializationError::InvalidValue(format!(
+                "value {value} cannot be deserialized as HashFunction enum"
+            ))),
+        }
+    }
+}
+
+impl Serializable for HashFunction {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(self.id());
+    }
+}
+
+impl Deserializable for HashFunction {
+    /// Reads a hash function identifier from the specified `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Self::from_id(source.read_u8()?)
+    }
 }
 
 // DIGEST TRAIT
@@ -76,6 +351,110 @@ pub trait Digest:
     /// upper limit on the possible digest size. For digests which are smaller than 32 bytes, the
     /// unused bytes should be set to 0.
     fn as_bytes(&self) -> [u8; 32];
+
+    /// Returns true if this digest is equal to the `other` digest.
+    ///
+    /// The digests are compared via their byte representations in constant time; that is, the
+    /// time it takes to compare the digests does not depend on the position of the first byte at
+    /// which they differ.
+    fn ct_eq(&self, other: &Self) -> bool {
+        utils::ct_eq(&self.as_bytes(), &other.as_bytes())
+    }
+}
+
+// DIGEST CONVERSIONS
+// ================================================================================================
+
+/// Defines conversions between a digest and a list of elements in the field `B`.
+///
+/// Conversions are injective: a digest is always converted into the same number of elements
+/// ([NUM_ELEMENTS](DigestToElements::NUM_ELEMENTS)), and converting these elements back yields
+/// the original digest. This makes it possible to use digests as values inside arithmetic
+/// circuits (e.g., when verifying STARK proofs recursively), or to absorb them into algebraic
+/// transcripts.
+///
+/// For digests which consist of field elements (e.g., digests of Rescue or Poseidon hash
+/// functions), the conversion simply returns these elements. Byte digests are split into chunks
+/// of `(B::MODULUS_BITS - 1) / 8` bytes, and each chunk is interpreted as a little-endian integer.
+/// Such an integer is always smaller than the field modulus, and thus, no modular reduction is
+/// needed. Note, however, that this also means that the resulting elements are not uniformly
+/// distributed over the field: each of them is uniformly distributed over a range of
+/// `2^(8 * chunk_size)` values. To draw uniformly random field elements from a digest, use
+/// [RandomCoin](crate::RandomCoin) instead.
+pub trait DigestToElements<B: StarkField>: Digest {
+    /// Number of field elements a digest is converted into.
+    const NUM_ELEMENTS: usize;
+
+    /// Returns this digest converted into a list of field elements.
+    fn to_elements(&self) -> Vec<B>;
+
+    /// Returns a digest converted from the provided field elements.
+    ///
+    /// # Errors
+    /// Returns an error if the number of elements is not [NUM_ELEMENTS](Self::NUM_ELEMENTS), or
+    /// if the elements could not have been produced by [to_elements()](Self::to_elements).
+    fn from_elements(elements: &[B]) -> Result<Self, DeserializationError>;
+}
+
+// HASHABLE VALUES
+// ================================================================================================
+
+/// Defines how a value is hashed into a leaf of a Merkle tree built with hash function `H`.
+///
+/// This trait is the single place which defines how values committed to by a [MerkleTree] are
+/// turned into leaf digests:
+/// * Digests of `H` are used as leaves as is.
+/// * Vectors and arrays of field elements (e.g., groups of FRI layer evaluations) are hashed via
+///   [ElementHasher::hash_elements()].
+///
+/// Other crates implement this trait for their own leaf types (e.g., the prover implements it for
+/// groups of rows of trace and constraint evaluation matrices).
+///
+/// [MerkleTree]: crate::MerkleTree
+pub trait Hashable<H: Hasher> {
+    /// Returns the digest of this value.
+    fn hash_leaf(&self) -> H::Digest;
+
+    /// Returns digests of all specified values.
+    ///
+    /// The default implementation hashes the values one by one; values which are digests of `H`
+    /// already are returned as is, without being copied.
+    fn hash_leaves(values: &[Self]) -> Cow<'_, [H::Digest]>
+    where
+        Self: Sized,
+    {
+        Cow::Owned(values.iter().map(Self::hash_leaf).collect())
+    }
+}
+
+impl<D: Digest, H: Hasher<Digest = D>> Hashable<H> for D {
+    fn hash_leaf(&self) -> D {
+        *self
+    }
+
+    fn hash_leaves(values: &[Self]) -> Cow<'_, [D]> {
+        Cow::Borrowed(values)
+    }
+}
+
+impl<E, H> Hashable<H> for Vec<E>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    fn hash_leaf(&self) -> H::Digest {
+        H::hash_elements(self)
+    }
+}
+
+impl<E, H, const N: usize> Hashable<H> for [E; N]
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    fn hash_leaf(&self) -> H::Digest {
+        H::hash_elements(self)
+    }
 }
 
 // BYTE DIGEST
@@ -130,9 +509,63 @@ impl<const N: usize> Deserializable for ByteDigest<N> {
     }
 }
 
+impl<B: StarkField, const N: usize> DigestToElements<B> for ByteDigest<N> {
+    const NUM_ELEMENTS: usize = (N + byte_chunk_size::<B>() - 1) / byte_chunk_size::<B>();
+
+    fn to_elements(&self) -> Vec<B> {
+        let mut buf = vec![0u8; B::ELEMENT_BYTES];
+        self.0
+            .chunks(byte_chunk_size::<B>())
+            .map(|chunk| {
+                // the chunk is always smaller than the modulus, and thus, reading it as a field
+                // element cannot fail
+                buf.fill(0);
+                buf[..chunk.len()].copy_from_slice(chunk);
+                B::read_from(&mut SliceReader::new(&buf)).expect("invalid field element")
+            })
+            .collect()
+    }
+
+    fn from_elements(elements: &[B]) -> Result<Self, DeserializationError> {
+        let num_elements = <Self as DigestToElements<B>>::NUM_ELEMENTS;
+        if elements.len() != num_elements {
+            return Err(DeserializationError::InvalidValue(format!(
+                "expected {num_elements} elements, but received {}",
+                elements.len()
+            )));
+        }
+
+        let mut result = [0u8; N];
+        for (chunk, element) in result.chunks_mut(byte_chunk_size::<B>()).zip(elements) {
+            let bytes = element.to_bytes();
+            if bytes[chunk.len()..].iter().any(|&byte| byte != 0) {
+                return Err(DeserializationError::InvalidValue(format!(
+                    "element {element} does not fit into {} bytes",
+                    chunk.len()
+                )));
+            }
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+        Ok(ByteDigest(result))
+    }
+}
+
+/// Returns the number of digest bytes encoded into a single element of field `B`.
+const fn byte_chunk_size<B: StarkField>() -> usize {
+    ((B::MODULUS_BITS - 1) / 8) as usize
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{ByteDigest, Digest};
+    use super::{
+        Blake3_256, ByteDigest, Digest, DigestToElements, ElementHasher, Hashable, Hasher,
+    };
+    use math::{
+        fields::{f128, f62, f64},
+        FieldElement, StarkField,
+    };
+    use rand_utils::rand_array;
+    use utils::{borrow::Cow, collections::Vec};
 
     #[test]
     fn byte_digest_as_bytes() {
@@ -144,4 +577,76 @@ mod tests {
         expected[31] = 0;
         assert_eq!(expected, d.as_bytes());
     }
+
+    #[test]
+    fn byte_digest_to_elements() {
+        fn check<B: StarkField, const N: usize>(expected_num_elements: usize) {
+            let d = ByteDigest::<N>::new(rand_array());
+            let elements = DigestToElements::<B>::to_elements(&d);
+            assert_eq!(expected_num_elements, elements.len());
+            assert_eq!(
+                expected_num_elements,
+                <ByteDigest<N> as DigestToElements<B>>::NUM_ELEMENTS
+            );
+            assert_eq!(d, ByteDigest::<N>::from_elements(&elements).unwrap());
+        }
+
+        // 7 bytes per element for 64-bit and 62-bit fields, 15 bytes per element for 128-bit field
+        check::<f64::BaseElement, 32>(5);
+        check::<f62::BaseElement, 32>(5);
+        check::<f128::BaseElement, 32>(3);
+        check::<f64::BaseElement, 24>(4);
+        check::<f128::BaseElement, 24>(2);
+    }
+
+    #[test]
+    fn byte_digest_from_invalid_elements() {
+        // wrong number of elements
+        let elements = [f64::BaseElement::ONE; 4];
+        assert!(ByteDigest::<32>::from_elements(&elements).is_err());
+
+        // an element which does not fit into 7 bytes
+        let mut elements = [f64::BaseElement::ONE; 5];
+        elements[0] = f64::BaseElement::new(1 << 56);
+        assert!(ByteDigest::<32>::from_elements(&elements).is_err());
+
+        // the last element encodes only 4 bytes of the digest
+        let mut elements = [f64::BaseElement::ONE; 5];
+        elements[4] = f64::BaseElement::new(1 << 32);
+        assert!(ByteDigest::<32>::from_elements(&elements).is_err());
+    }
+
+    #[test]
+    fn merge_batch() {
+        type Blake3 = Blake3_256<f128::BaseElement>;
+        let values = (0..9_u8)
+            .map(|i| [Blake3::hash(&[i]), Blake3::hash(&[i + 100])])
+            .collect::<Vec<_>>();
+
+        let mut result = vec![ByteDigest::default(); values.len()];
+        Blake3::merge_batch(&values, &mut result);
+        assert_eq!(values.iter().map(Blake3::merge).collect::<Vec<_>>(), result);
+    }
+
+    #[test]
+    fn hash_leaves() {
+        type Blake3 = Blake3_256<f128::BaseElement>;
+
+        // digests are used as leaves without being copied
+        let digests = (0..4_u8).map(|i| Blake3::hash(&[i])).collect::<Vec<_>>();
+        let leaves = <ByteDigest<32> as Hashable<Blake3>>::hash_leaves(&digests);
+        assert!(matches!(leaves, Cow::Borrowed(_)));
+        assert_eq!(digests, leaves.as_ref());
+
+        // other values are hashed one by one
+        let rows = (0..4_u8)
+            .map(|i| [f128::BaseElement::from(i), f128::BaseElement::ONE])
+            .collect::<Vec<_>>();
+        let leaves = <[f128::BaseElement; 2] as Hashable<Blake3>>::hash_leaves(&rows);
+        let expected = rows
+            .iter()
+            .map(|row| Blake3::hash_elements(row))
+            .collect::<Vec<_>>();
+        assert_eq!(expected, leaves.as_ref());
+    }
 }
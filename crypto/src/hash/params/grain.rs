@@ -0,0 +1,93 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+// GRAIN LFSR
+// ================================================================================================
+
+/// Number of bits in the state of the LFSR.
+const STATE_SIZE: usize = 80;
+
+/// Number of initial outputs of the LFSR which are discarded.
+const NUM_DISCARDED_BITS: usize = 160;
+
+/// Self-shrinking Grain LFSR used to generate Poseidon round constants as specified in Appendix F
+/// of <https://eprint.iacr.org/2019/458.pdf>.
+pub struct GrainLfsr {
+    state: [bool; STATE_SIZE],
+    head: usize,
+}
+
+impl GrainLfsr {
+    /// Returns a new LFSR initialized for a Poseidon instance with an `x^alpha` S-box over a
+    /// prime field with elements of `field_bits` bits, state of `state_width` elements, and the
+    /// specified number of full and partial rounds.
+    pub fn new(
+        field_bits: u32,
+        state_width: usize,
+        num_full_rounds: usize,
+        num_partial_rounds: usize,
+    ) -> Self {
+        let mut state = [true; STATE_SIZE];
+        let mut offset = 0;
+        // field type: 1 for prime fields
+        append_bits(&mut state, &mut offset, 1, 2);
+        // S-box type: 0 for x^alpha
+        append_bits(&mut state, &mut offset, 0, 4);
+        append_bits(&mut state, &mut offset, field_bits as u64, 12);
+        append_bits(&mut state, &mut offset, state_width as u64, 12);
+        append_bits(&mut state, &mut offset, num_full_rounds as u64, 10);
+        append_bits(&mut state, &mut offset, num_partial_rounds as u64, 10);
+        // the remaining 30 bits are set to ones
+
+        let mut lfsr = Self { state, head: 0 };
+        for _ in 0..NUM_DISCARDED_BITS {
+            lfsr.update();
+        }
+        lfsr
+    }
+
+    /// Returns a `num_bits`-bit integer assembled from the next outputs of the LFSR with the
+    /// first output in the most significant position.
+    pub fn next_int(&mut self, num_bits: u32) -> u128 {
+        debug_assert!(
+            num_bits <= 128,
+            "cannot sample more than 128 bits at a time"
+        );
+        (0..num_bits).fold(0, |acc, _| (acc << 1) | self.next_bit() as u128)
+    }
+
+    /// Returns the next output of the self-shrinking generator: bits are produced in pairs, and
+    /// the second bit of a pair is output only if the first bit is set.
+    fn next_bit(&mut self) -> bool {
+        loop {
+            let select = self.update();
+            let bit = self.update();
+            if select {
+                return bit;
+            }
+        }
+    }
+
+    /// Advances the LFSR by one step and returns the new bit.
+    fn update(&mut self) -> bool {
+        let bit = |i: usize| self.state[(self.head + i) % STATE_SIZE];
+        let new_bit = bit(62) ^ bit(51) ^ bit(38) ^ bit(23) ^ bit(13) ^ bit(0);
+        self.state[self.head] = new_bit;
+        self.head = (self.head + 1) % STATE_SIZE;
+        new_bit
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Writes `num_bits` least significant bits of `value` into the state starting at the specified
+/// offset, most significant bit first.
+fn append_bits(state: &mut [bool], offset: &mut usize, value: u64, num_bits: usize) {
+    for i in (0..num_bits).rev() {
+        state[*offset] = (value >> i) & 1 == 1;
+        *offset += 1;
+    }
+}
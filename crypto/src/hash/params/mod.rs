@@ -0,0 +1,524 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Parameter generation for algebraic hash functions.
+//!
+//! This module contains utilities which derive parameters of Rescue Prime and Poseidon instances
+//! (S-box powers, number of rounds, round constants, and MDS matrices) for an arbitrary
+//! [StarkField] and a target security level. Hashers defined in this crate hard-code their
+//! parameters; these utilities can be used to generate matching parameters for new fields.
+
+use math::StarkField;
+use sha3::{
+    digest::{ExtendableOutput, Update, XofReader},
+    Shake256,
+};
+use utils::collections::Vec;
+
+mod grain;
+pub use grain::GrainLfsr;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Largest S-box power which is considered when searching for an invertible power map.
+const MAX_ALPHA: u64 = 255;
+
+/// Maximum number of Rescue Prime rounds considered by the round-number search.
+const MAX_RESCUE_ROUNDS: usize = 25;
+
+/// Security margin (in percent) used by the Rescue Prime specification; hashers in this crate
+/// use a 40% margin instead.
+pub const RESCUE_SPEC_SECURITY_MARGIN: u32 = 50;
+
+// RESCUE PRIME PARAMETERS
+// ================================================================================================
+
+/// Parameters of a Rescue Prime instance.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RescueParams<B: StarkField> {
+    /// Number of field elements in the hasher state.
+    pub state_width: usize,
+    /// Number of field elements in the capacity portion of the state.
+    pub capacity: usize,
+    /// Power of the S-box.
+    pub alpha: u64,
+    /// Power of the inverse S-box.
+    pub inv_alpha: u128,
+    /// Number of rounds.
+    pub num_rounds: usize,
+    /// MDS matrix applied to the state in each half of a round.
+    pub mds: Vec<Vec<B>>,
+    /// Round constants added to the state in the first half of each round.
+    pub ark1: Vec<Vec<B>>,
+    /// Round constants added to the state in the second half of each round.
+    pub ark2: Vec<Vec<B>>,
+}
+
+impl<B: StarkField> RescueParams<B> {
+    /// Returns parameters of a Rescue Prime instance with the specified state width and capacity
+    /// targeting the specified security level (in bits) with the specified security margin (in
+    /// percent).
+    ///
+    /// Parameters are derived following algorithms 6 - 8 of
+    /// <https://eprint.iacr.org/2020/1143.pdf>: the S-box power is the smallest invertible power
+    /// map, round constants are sampled from SHAKE256 seeded with the instance description, and
+    /// the MDS matrix is a Cauchy matrix.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `state_width` is smaller than 2 or `capacity` is not in the range [1, `state_width`).
+    /// * No suitable S-box power or number of rounds exists for the specified parameters.
+    pub fn new(
+        state_width: usize,
+        capacity: usize,
+        security_level: u32,
+        security_margin: u32,
+    ) -> Self {
+        assert!(
+            state_width >= 2,
+            "state width must be at least 2, but was {}",
+            state_width
+        );
+        assert!(
+            capacity > 0 && capacity < state_width,
+            "capacity must be between 1 and {}, but was {}",
+            state_width - 1,
+            capacity
+        );
+
+        let alpha = sbox_power::<B>();
+        let inv_alpha = inv_sbox_power::<B>(alpha);
+        let num_rounds = rescue_num_rounds(
+            state_width,
+            capacity,
+            alpha,
+            security_level,
+            security_margin,
+        );
+
+        let seed = rescue_seed::<B>(state_width, capacity, security_level);
+        let constants = shake_round_constants::<B>(&seed, 2 * num_rounds, state_width);
+        let (ark1, ark2) = constants
+            .chunks(2)
+            .map(|round| (round[0].clone(), round[1].clone()))
+            .unzip();
+
+        Self {
+            state_width,
+            capacity,
+            alpha,
+            inv_alpha,
+            num_rounds,
+            mds: cauchy_mds(state_width),
+            ark1,
+            ark2,
+        }
+    }
+}
+
+// POSEIDON PARAMETERS
+// ================================================================================================
+
+/// Parameters of a Poseidon instance with an `x^alpha` S-box.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PoseidonParams<B: StarkField> {
+    /// Number of field elements in the hasher state.
+    pub state_width: usize,
+    /// Power of the S-box.
+    pub alpha: u64,
+    /// Total number of full rounds; half of these are applied before the partial rounds.
+    pub num_full_rounds: usize,
+    /// Number of partial rounds.
+    pub num_partial_rounds: usize,
+    /// MDS matrix applied to the state in each round.
+    pub mds: Vec<Vec<B>>,
+    /// Round constants added to the state at the beginning of each round.
+    pub ark: Vec<Vec<B>>,
+}
+
+impl<B: StarkField> PoseidonParams<B> {
+    /// Returns parameters of a Poseidon instance with the specified state width targeting the
+    /// specified security level (in bits).
+    ///
+    /// Parameters are derived following <https://eprint.iacr.org/2019/458.pdf>: the number of
+    /// rounds is the cheapest (in terms of S-boxes) configuration resisting statistical,
+    /// interpolation, and Gröbner basis attacks with the recommended security margin. Round
+    /// constants and the MDS matrix are sampled from the Grain LFSR in the same way as done by
+    /// the reference script `generate_parameters_grain.sage` (see [grain_round_constants()] and
+    /// [grain_mds()]).
+    ///
+    /// Unlike the reference script, this function does not check the MDS matrix for invariant
+    /// subspace trails; this check must be performed separately before the parameters are used.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `state_width` is smaller than 2.
+    /// * No suitable S-box power or number of rounds exists for the specified parameters.
+    pub fn new(state_width: usize, security_level: u32) -> Self {
+        assert!(
+            state_width >= 2,
+            "state width must be at least 2, but was {}",
+            state_width
+        );
+
+        let alpha = sbox_power::<B>();
+        let (num_full_rounds, num_partial_rounds) =
+            poseidon_num_rounds::<B>(state_width, alpha, security_level);
+        let mut lfsr = GrainLfsr::new(
+            B::MODULUS_BITS,
+            state_width,
+            num_full_rounds,
+            num_partial_rounds,
+        );
+        let ark =
+            sample_round_constants(&mut lfsr, state_width, num_full_rounds + num_partial_rounds);
+        let mds = sample_cauchy_mds(&mut lfsr, state_width);
+
+        Self {
+            state_width,
+            alpha,
+            num_full_rounds,
+            num_partial_rounds,
+            mds,
+            ark,
+        }
+    }
+}
+
+// S-BOX POWERS
+// ================================================================================================
+
+/// Returns the smallest `alpha` >= 3 such that `x^alpha` is a permutation of field `B`, i.e.,
+/// such that `alpha` is co-prime with `p - 1`.
+///
+/// # Panics
+/// Panics if no such power smaller than 256 exists.
+pub fn sbox_power<B: StarkField>() -> u64 {
+    let order = modulus::<B>() - 1;
+    (3..=MAX_ALPHA)
+        .find(|&alpha| gcd(alpha as u128, order) == 1)
+        .expect("no invertible power map with a small exponent exists for this field")
+}
+
+/// Returns the inverse of `alpha` modulo `p - 1`; i.e., the power of the inverse S-box of an
+/// `x^alpha` S-box in field `B`.
+///
+/// # Panics
+/// Panics if `alpha` is not co-prime with `p - 1`.
+pub fn inv_sbox_power<B: StarkField>(alpha: u64) -> u128 {
+    let order = modulus::<B>() - 1;
+    let alpha = alpha as u128;
+    assert!(
+        alpha > 1 && gcd(alpha, order) == 1,
+        "{} is not co-prime with the multiplicative group order",
+        alpha
+    );
+
+    // we need k * (p - 1) + 1 to be divisible by alpha; writing p - 1 = q * alpha + r, this
+    // reduces to k * r + 1 being divisible by alpha, and the inverse is then computed as
+    // (k * (p - 1) + 1) / alpha without overflowing 128-bit integers
+    let (q, r) = (order / alpha, order % alpha);
+    let k = (1..alpha)
+        .find(|k| (k * r + 1) % alpha == 0)
+        .expect("alpha must be invertible");
+    k * q + (k * r + 1) / alpha
+}
+
+// NUMBER OF ROUNDS
+// ================================================================================================
+
+/// Returns the number of Rescue Prime rounds for the specified state width, capacity, S-box
+/// power and security level (in bits) with the specified security margin (in percent).
+///
+/// The base number of rounds is the smallest number resisting Gröbner basis attacks (but no
+/// fewer than 5) as computed by algorithm 7 of <https://eprint.iacr.org/2020/1143.pdf>.
+///
+/// # Panics
+/// Panics if no number of rounds smaller than 25 provides the requested security level.
+pub fn rescue_num_rounds(
+    state_width: usize,
+    capacity: usize,
+    alpha: u64,
+    security_level: u32,
+    security_margin: u32,
+) -> usize {
+    let m = state_width as u64;
+    let rate = (state_width - capacity) as u64;
+    let num_rounds = (1..MAX_RESCUE_ROUNDS)
+        .find(|&n| {
+            let n = n as u64;
+            let v = m * (n - 1) + rate;
+            let dcon = (alpha - 1) * m * (n - 1) / 2 + 2;
+            2.0 * log2_binomial(v + dcon, v) > security_level as f64
+        })
+        .expect("no suitable number of rounds found");
+
+    let num_rounds = num_rounds.max(5);
+    (num_rounds * (100 + security_margin as usize) + 99) / 100
+}
+
+/// Returns the number of full and partial Poseidon rounds for the specified state width, S-box
+/// power and security level (in bits).
+///
+/// The rounds are selected to minimize the number of S-boxes while satisfying the security
+/// inequalities of <https://eprint.iacr.org/2019/458.pdf>; the recommended security margin
+/// (2 extra full rounds and 7.5% extra partial rounds) is included in the result.
+///
+/// # Panics
+/// Panics if no suitable number of rounds is found.
+pub fn poseidon_num_rounds<B: StarkField>(
+    state_width: usize,
+    alpha: u64,
+    security_level: u32,
+) -> (usize, usize) {
+    let log2_p = (modulus::<B>() as f64).log2();
+    let mut result = None;
+    let mut min_cost = usize::MAX;
+    for num_partial_rounds in 1..500 {
+        for num_full_rounds in (4..100).step_by(2) {
+            if !is_poseidon_secure(
+                log2_p,
+                state_width,
+                alpha,
+                security_level,
+                num_full_rounds,
+                num_partial_rounds,
+            ) {
+                continue;
+            }
+
+            let full_rounds = num_full_rounds + 2;
+            let partial_rounds = (num_partial_rounds as f64 * 1.075).ceil() as usize;
+            let cost = state_width * full_rounds + partial_rounds;
+            if cost < min_cost {
+                min_cost = cost;
+                result = Some((full_rounds, partial_rounds));
+            }
+            // adding more full rounds only increases the cost
+            break;
+        }
+    }
+    result.expect("no suitable number of rounds found")
+}
+
+// ROUND CONSTANTS
+// ================================================================================================
+
+/// Returns `num_rounds` vectors of `state_width` round constants sampled from SHAKE256 seeded
+/// with the specified `seed` as described in algorithm 8 of
+/// <https://eprint.iacr.org/2020/1143.pdf>.
+///
+/// Each constant is built from `ceil(log2(p) / 8) + 1` bytes of the output interpreted as a
+/// little-endian integer and reduced modulo `p`.
+pub fn shake_round_constants<B: StarkField>(
+    seed: &[u8],
+    num_rounds: usize,
+    state_width: usize,
+) -> Vec<Vec<B>> {
+    let mut hasher = Shake256::default();
+    hasher.update(seed);
+    let mut reader = hasher.finalize_xof();
+
+    let bytes_per_element = (B::MODULUS_BITS as usize + 7) / 8 + 1;
+    let base = B::from(256u32);
+    let mut bytes = vec![0u8; bytes_per_element];
+    (0..num_rounds)
+        .map(|_| {
+            (0..state_width)
+                .map(|_| {
+                    reader.read(&mut bytes);
+                    bytes
+                        .iter()
+                        .rev()
+                        .fold(B::ZERO, |acc, &byte| acc * base + B::from(byte))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Returns round constants for a Poseidon instance with the specified state width and number
+/// of rounds generated by the Grain LFSR as described in Appendix F of
+/// <https://eprint.iacr.org/2019/458.pdf>.
+///
+/// Each constant is sampled as a `MODULUS_BITS`-bit integer; integers which are not smaller
+/// than the field modulus are rejected.
+pub fn grain_round_constants<B: StarkField>(
+    state_width: usize,
+    num_full_rounds: usize,
+    num_partial_rounds: usize,
+) -> Vec<Vec<B>> {
+    let mut lfsr = GrainLfsr::new(
+        B::MODULUS_BITS,
+        state_width,
+        num_full_rounds,
+        num_partial_rounds,
+    );
+    sample_round_constants(&mut lfsr, state_width, num_full_rounds + num_partial_rounds)
+}
+
+// MDS MATRIX
+// ================================================================================================
+
+/// Returns a `state_width` x `state_width` Cauchy matrix with entries `1 / (x_i + y_j)` where
+/// `x_i = i` and `y_j = state_width + j`; such matrices are MDS.
+pub fn cauchy_mds<B: StarkField>(state_width: usize) -> Vec<Vec<B>> {
+    (0..state_width)
+        .map(|i| {
+            (0..state_width)
+                .map(|j| B::from((i + state_width + j) as u64).inv())
+                .collect()
+        })
+        .collect()
+}
+
+/// Returns an MDS matrix for a Poseidon instance with the specified state width and number of
+/// rounds generated by the Grain LFSR as done by the reference script
+/// `generate_parameters_grain.sage` from <https://extgit.iaik.tugraz.at/krypto/hadeshash>.
+///
+/// After all round constants have been sampled, the LFSR is used to sample `2 * state_width`
+/// values `x_0, ..., x_{t-1}, y_0, ..., y_{t-1}`, each as a `MODULUS_BITS`-bit integer reduced
+/// modulo `p`; the result is a Cauchy matrix with entries `1 / (x_i + y_j)`. Samples which are
+/// not pairwise distinct, or for which some `x_i + y_j` is zero, are discarded.
+///
+/// The matrix is not checked for invariant subspace trails.
+pub fn grain_mds<B: StarkField>(
+    state_width: usize,
+    num_full_rounds: usize,
+    num_partial_rounds: usize,
+) -> Vec<Vec<B>> {
+    let mut lfsr = GrainLfsr::new(
+        B::MODULUS_BITS,
+        state_width,
+        num_full_rounds,
+        num_partial_rounds,
+    );
+    sample_round_constants::<B>(&mut lfsr, state_width, num_full_rounds + num_partial_rounds);
+    sample_cauchy_mds(&mut lfsr, state_width)
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Samples `num_rounds` vectors of `state_width` round constants from the provided LFSR; values
+/// which are not smaller than the field modulus are rejected.
+fn sample_round_constants<B: StarkField>(
+    lfsr: &mut GrainLfsr,
+    state_width: usize,
+    num_rounds: usize,
+) -> Vec<Vec<B>> {
+    let modulus = modulus::<B>();
+    (0..num_rounds)
+        .map(|_| {
+            (0..state_width)
+                .map(|_| loop {
+                    let value = lfsr.next_int(B::MODULUS_BITS);
+                    if value < modulus {
+                        break B::from(value);
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Samples a `state_width` x `state_width` Cauchy matrix from the provided LFSR as described in
+/// [grain_mds()].
+fn sample_cauchy_mds<B: StarkField>(lfsr: &mut GrainLfsr, state_width: usize) -> Vec<Vec<B>> {
+    let modulus = modulus::<B>();
+    loop {
+        let values: Vec<B> = (0..2 * state_width)
+            .map(|_| B::from(lfsr.next_int(B::MODULUS_BITS) % modulus))
+            .collect();
+        if (1..values.len()).any(|i| values[..i].contains(&values[i])) {
+            continue;
+        }
+
+        let (xs, ys) = values.split_at(state_width);
+        if xs.iter().any(|&x| ys.iter().any(|&y| x + y == B::ZERO)) {
+            continue;
+        }
+        return xs
+            .iter()
+            .map(|&x| ys.iter().map(|&y| (x + y).inv()).collect())
+            .collect();
+    }
+}
+
+/// Returns the modulus of field `B` as a 128-bit integer.
+fn modulus<B: StarkField>() -> u128 {
+    let bytes = B::get_modulus_le_bytes();
+    bytes
+        .iter()
+        .rev()
+        .fold(0, |acc, &byte| (acc << 8) | byte as u128)
+}
+
+/// Returns the greatest common divisor of `a` and `b`.
+fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Returns log2 of the binomial coefficient `n` choose `k`.
+fn log2_binomial(n: u64, k: u64) -> f64 {
+    let k = k.min(n - k);
+    (0..k)
+        .map(|i| ((n - i) as f64).log2() - ((i + 1) as f64).log2())
+        .sum()
+}
+
+/// Returns true if a Poseidon instance with the specified parameters resists statistical,
+/// interpolation, and Gröbner basis attacks for the specified security level.
+fn is_poseidon_secure(
+    log2_p: f64,
+    state_width: usize,
+    alpha: u64,
+    security_level: u32,
+    num_full_rounds: usize,
+    num_partial_rounds: usize,
+) -> bool {
+    let t = state_width as f64;
+    let m = security_level as f64;
+    let r_p = num_partial_rounds as f64;
+    let log_alpha = |x: f64| x.log2() / (alpha as f64).log2();
+
+    // statistical attacks
+    let r_f_1 = if m <= (log2_p - (alpha - 1) as f64 / 2.0).floor() * (t + 1.0) {
+        6.0
+    } else {
+        10.0
+    };
+    // interpolation attacks
+    let r_f_2 = 1.0 + (log_alpha(2.0) * m.min(log2_p.ceil())).ceil() + log_alpha(t).ceil() - r_p;
+    // Gröbner basis attacks
+    let r_f_3 = log_alpha(2.0) * m.min(log2_p) - r_p;
+    let r_f_4 = t - 1.0 + log_alpha(2.0) * (m / (t + 1.0)).min(log2_p / 2.0) - r_p;
+    let r_f_5 = (t - 2.0 + m / (2.0 * (alpha as f64).log2()) - r_p) / (t - 1.0);
+
+    let r_f_max = [r_f_1, r_f_2, r_f_3, r_f_4, r_f_5]
+        .iter()
+        .fold(f64::MIN, |acc, r| acc.max(r.ceil()));
+    num_full_rounds as f64 >= r_f_max
+}
+
+/// Returns the seed for Rescue Prime round constants as defined in algorithm 8 of
+/// <https://eprint.iacr.org/2020/1143.pdf>.
+fn rescue_seed<B: StarkField>(state_width: usize, capacity: usize, security_level: u32) -> Vec<u8> {
+    format!(
+        "Rescue-XLIX({},{},{},{})",
+        modulus::<B>(),
+        state_width,
+        capacity,
+        security_level
+    )
+    .into_bytes()
+}
@@ -0,0 +1,98 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    cauchy_mds, grain_mds, grain_round_constants, inv_sbox_power, poseidon_num_rounds,
+    rescue_num_rounds, sbox_power, PoseidonParams, RescueParams, RESCUE_SPEC_SECURITY_MARGIN,
+};
+use crate::hash::{Poseidon64_256, Rp64_256};
+use math::{
+    fields::{f128, f62, f64::BaseElement},
+    FieldElement,
+};
+
+#[test]
+fn sbox_powers() {
+    // S-box powers and their inverses should match the ones used by Rescue Prime hashers
+    assert_eq!(7, sbox_power::<BaseElement>());
+    assert_eq!(10540996611094048183, inv_sbox_power::<BaseElement>(7));
+    assert_eq!(3, sbox_power::<f62::BaseElement>());
+    assert_eq!(3074416663688030891, inv_sbox_power::<f62::BaseElement>(3));
+
+    // x^alpha followed by x^inv_alpha should be the identity map
+    let alpha = sbox_power::<f128::BaseElement>();
+    let inv_alpha = inv_sbox_power::<f128::BaseElement>(alpha);
+    let x = f128::BaseElement::new(123456789);
+    assert_eq!(x, x.exp(alpha as u128).exp(inv_alpha));
+}
+
+#[test]
+fn rescue_rounds() {
+    // number of rounds should match the ones used by Rescue Prime hashers
+    assert_eq!(Rp64_256::NUM_ROUNDS, rescue_num_rounds(12, 4, 7, 128, 40));
+    assert_eq!(7, rescue_num_rounds(12, 4, 3, 124, 40));
+
+    let params = RescueParams::<BaseElement>::new(12, 4, 128, RESCUE_SPEC_SECURITY_MARGIN);
+    assert_eq!(8, params.num_rounds);
+    assert_eq!(params.num_rounds, params.ark1.len());
+    assert_eq!(params.num_rounds, params.ark2.len());
+    assert!(params.ark1.iter().all(|round| round.len() == 12));
+    assert_ne!(params.ark1, params.ark2);
+
+    // generation should be deterministic
+    assert_eq!(
+        params,
+        RescueParams::<BaseElement>::new(12, 4, 128, RESCUE_SPEC_SECURITY_MARGIN)
+    );
+}
+
+#[test]
+fn poseidon_params() {
+    // number of rounds and round constants should match the ones used by Poseidon64_256
+    assert_eq!(
+        (
+            Poseidon64_256::NUM_FULL_ROUNDS,
+            Poseidon64_256::NUM_PARTIAL_ROUNDS
+        ),
+        poseidon_num_rounds::<BaseElement>(12, 7, 128)
+    );
+
+    let params = PoseidonParams::<BaseElement>::new(12, 128);
+    assert_eq!(7, params.alpha);
+    let expected = Poseidon64_256::ARK
+        .iter()
+        .map(|round| round.to_vec())
+        .collect::<Vec<_>>();
+    assert_eq!(expected, params.ark);
+    assert_eq!(expected, grain_round_constants::<BaseElement>(12, 8, 22));
+
+    // the MDS matrix should match the one used by Poseidon64_256
+    let expected = Poseidon64_256::MDS
+        .iter()
+        .map(|row| row.to_vec())
+        .collect::<Vec<_>>();
+    assert_eq!(expected, params.mds);
+    assert_eq!(expected, grain_mds::<BaseElement>(12, 8, 22));
+}
+
+#[test]
+fn cauchy_mds_is_mds() {
+    // a matrix is MDS if all of its square sub-matrices are non-singular; for a 3x3 matrix we
+    // check all entries, all 2x2 minors, and the determinant
+    let m = cauchy_mds::<BaseElement>(3);
+    assert!(m.iter().flatten().all(|&v| v != BaseElement::ZERO));
+
+    for (r1, r2) in [(0, 1), (0, 2), (1, 2)] {
+        for (c1, c2) in [(0, 1), (0, 2), (1, 2)] {
+            let minor = m[r1][c1] * m[r2][c2] - m[r1][c2] * m[r2][c1];
+            assert_ne!(BaseElement::ZERO, minor);
+        }
+    }
+
+    let det = m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+        - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+        + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0]);
+    assert_ne!(BaseElement::ZERO, det);
+}
@@ -0,0 +1,9 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{Digest, DigestToElements, ElementHasher, HashFunction, Hasher};
+
+mod p64_256;
+pub use p64_256::Poseidon64_256;
@@ -0,0 +1,148 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{Digest, DigestToElements, DIGEST_SIZE};
+use core::{convert::TryInto, slice};
+use math::{fields::f64::BaseElement, StarkField};
+use utils::{
+    collections::Vec, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+};
+
+// DIGEST TRAIT IMPLEMENTATIONS
+// ================================================================================================
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct ElementDigest([BaseElement; DIGEST_SIZE]);
+
+impl ElementDigest {
+    pub fn new(value: [BaseElement; DIGEST_SIZE]) -> Self {
+        Self(value)
+    }
+
+    pub fn as_elements(&self) -> &[BaseElement] {
+        &self.0
+    }
+
+    pub fn digests_as_elements(digests: &[Self]) -> &[BaseElement] {
+        let p = digests.as_ptr();
+        let len = digests.len() * DIGEST_SIZE;
+        unsafe { slice::from_raw_parts(p as *const BaseElement, len) }
+    }
+}
+
+impl Digest for ElementDigest {
+    fn as_bytes(&self) -> [u8; 32] {
+        let mut result = [0; 32];
+
+        result[..8].copy_from_slice(&self.0[0].as_int().to_le_bytes());
+        result[8..16].copy_from_slice(&self.0[1].as_int().to_le_bytes());
+        result[16..24].copy_from_slice(&self.0[2].as_int().to_le_bytes());
+        result[24..].copy_from_slice(&self.0[3].as_int().to_le_bytes());
+
+        result
+    }
+}
+
+impl DigestToElements<BaseElement> for ElementDigest {
+    const NUM_ELEMENTS: usize = DIGEST_SIZE;
+
+    fn to_elements(&self) -> Vec<BaseElement> {
+        self.0.to_vec()
+    }
+
+    fn from_elements(elements: &[BaseElement]) -> Result<Self, DeserializationError> {
+        let elements = elements.try_into().map_err(|_| {
+            DeserializationError::InvalidValue(format!(
+                "expected {DIGEST_SIZE} elements, but received {}",
+                elements.len()
+            ))
+        })?;
+        Ok(Self(elements))
+    }
+}
+
+impl Default for ElementDigest {
+    fn default() -> Self {
+        ElementDigest([BaseElement::default(); DIGEST_SIZE])
+    }
+}
+
+impl Serializable for ElementDigest {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8_slice(&self.as_bytes());
+    }
+}
+
+impl Deserializable for ElementDigest {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        // field elements are read in canonical form; this fails for limbs which are not smaller
+        // than the field modulus
+        let e1 = BaseElement::read_from(source)?;
+        let e2 = BaseElement::read_from(source)?;
+        let e3 = BaseElement::read_from(source)?;
+        let e4 = BaseElement::read_from(source)?;
+
+        Ok(Self([e1, e2, e3, e4]))
+    }
+}
+
+impl From<[BaseElement; DIGEST_SIZE]> for ElementDigest {
+    fn from(value: [BaseElement; DIGEST_SIZE]) -> Self {
+        Self(value)
+    }
+}
+
+impl From<ElementDigest> for [BaseElement; DIGEST_SIZE] {
+    fn from(value: ElementDigest) -> Self {
+        value.0
+    }
+}
+
+impl From<ElementDigest> for [u8; 32] {
+    fn from(value: ElementDigest) -> Self {
+        value.as_bytes()
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+
+    use super::ElementDigest;
+    use math::{fields::f64::BaseElement, StarkField};
+    use rand_utils::rand_array;
+    use utils::{Deserializable, DeserializationError, Serializable, SliceReader};
+
+    #[test]
+    fn digest_serialization() {
+        let d1 = ElementDigest(rand_array());
+
+        let mut bytes = vec![];
+        d1.write_into(&mut bytes);
+        assert_eq!(32, bytes.len());
+
+        let mut reader = SliceReader::new(&bytes);
+        let d2 = ElementDigest::read_from(&mut reader).unwrap();
+
+        assert_eq!(d1, d2);
+    }
+
+    #[test]
+    fn digest_deserialization_rejects_invalid_limbs() {
+        let d1 = ElementDigest(rand_array());
+        let mut bytes = vec![];
+        d1.write_into(&mut bytes);
+
+        // a limb equal to the field modulus is a non-canonical encoding of zero
+        bytes[16..24].copy_from_slice(&BaseElement::MODULUS.to_le_bytes());
+        let mut reader = SliceReader::new(&bytes);
+        assert!(matches!(
+            ElementDigest::read_from(&mut reader),
+            Err(DeserializationError::InvalidValue(_))
+        ));
+    }
+}
@@ -0,0 +1,230 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    BaseElement, ElementDigest, ElementHasher, FieldElement, Hasher, Poseidon64_256, StarkField,
+    ALPHA, ARK, HALF_FULL_ROUNDS, NUM_PARTIAL_ROUNDS, RATE_WIDTH, STATE_WIDTH,
+};
+use core::convert::TryInto;
+use rand_utils::{rand_array, rand_value};
+
+#[test]
+fn test_sbox() {
+    let state: [BaseElement; STATE_WIDTH] = rand_array();
+
+    let mut expected = state;
+    expected.iter_mut().for_each(|v| *v = v.exp(ALPHA));
+
+    let mut actual = state;
+    Poseidon64_256::apply_sbox(&mut actual);
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn round_constants() {
+    // all round constants must be valid canonical field elements
+    for round in ARK.iter() {
+        for &c in round.iter() {
+            assert!(c.as_int() < BaseElement::MODULUS);
+        }
+    }
+    assert_eq!(ARK[0][0], BaseElement::new(1431286215153372998));
+    assert_eq!(ARK[0][1], BaseElement::new(3509349009260703107));
+}
+
+#[test]
+fn partial_round() {
+    let state: [BaseElement; STATE_WIDTH] = rand_array();
+    let round = HALF_FULL_ROUNDS + NUM_PARTIAL_ROUNDS / 2;
+
+    // in a partial round only the first state element goes through the S-Box
+    let mut expected = state;
+    expected
+        .iter_mut()
+        .zip(ARK[round])
+        .for_each(|(s, k)| *s += k);
+    expected[0] = expected[0].exp(ALPHA);
+    Poseidon64_256::apply_mds(&mut expected);
+
+    let mut actual = state;
+    Poseidon64_256::apply_partial_round(&mut actual, round);
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn apply_permutation() {
+    let mut state: [BaseElement; STATE_WIDTH] = [
+        BaseElement::new(0),
+        BaseElement::new(1),
+        BaseElement::new(2),
+        BaseElement::new(3),
+        BaseElement::new(4),
+        BaseElement::new(5),
+        BaseElement::new(6),
+        BaseElement::new(7),
+        BaseElement::new(8),
+        BaseElement::new(9),
+        BaseElement::new(10),
+        BaseElement::new(11),
+    ];
+
+    Poseidon64_256::apply_permutation(&mut state);
+
+    // expected values are obtained by executing a python transcription of the reference
+    // permutation from the hadeshash repository instantiated with the parameters of this hasher
+    let expected = vec![
+        BaseElement::new(390645729656344184),
+        BaseElement::new(2249711026011950288),
+        BaseElement::new(9277123011786256726),
+        BaseElement::new(2863099990776158604),
+        BaseElement::new(11236391181490653619),
+        BaseElement::new(11659719701336181918),
+        BaseElement::new(11657697956015657720),
+        BaseElement::new(9221719776340960687),
+        BaseElement::new(15876970241823259038),
+        BaseElement::new(5443477214455571398),
+        BaseElement::new(17899191838257322372),
+        BaseElement::new(8860123492321957630),
+    ];
+
+    assert_eq!(expected, state);
+}
+
+#[test]
+fn apply_permutation_max_values() {
+    let mut state = [BaseElement::new(BaseElement::MODULUS - 1); STATE_WIDTH];
+    Poseidon64_256::apply_permutation(&mut state);
+
+    // expected values are obtained in the same way as in the test above
+    let expected = vec![
+        BaseElement::new(2565957324096489159),
+        BaseElement::new(14636943789722428626),
+        BaseElement::new(17093271240232344640),
+        BaseElement::new(12425652993514403215),
+        BaseElement::new(1509857486151884167),
+        BaseElement::new(16791640971313392666),
+        BaseElement::new(3146108240318768101),
+        BaseElement::new(4632891092727397755),
+        BaseElement::new(9211418188818460031),
+        BaseElement::new(7609053436273288957),
+        BaseElement::new(5562264777486607653),
+        BaseElement::new(10755105292608294364),
+    ];
+
+    assert_eq!(expected, state);
+}
+
+#[test]
+fn hash_elements_known_answer() {
+    let elements = (0..10).map(BaseElement::new).collect::<Vec<_>>();
+
+    // expected values are obtained by absorbing the elements into the sponge described in the
+    // hasher documentation using the python reference permutation
+    let expected = ElementDigest::new([
+        BaseElement::new(17963471463592311703),
+        BaseElement::new(7185655576354257153),
+        BaseElement::new(15146551347887696423),
+        BaseElement::new(12283630558797225917),
+    ]);
+
+    assert_eq!(expected, Poseidon64_256::hash_elements(&elements));
+}
+
+#[test]
+fn hash_elements_vs_merge() {
+    let elements: [BaseElement; 8] = rand_array();
+
+    let digests: [ElementDigest; 2] = [
+        ElementDigest::new(elements[..4].try_into().unwrap()),
+        ElementDigest::new(elements[4..].try_into().unwrap()),
+    ];
+
+    let m_result = Poseidon64_256::merge(&digests);
+    let h_result = Poseidon64_256::hash_elements(&elements);
+    assert_eq!(m_result, h_result);
+}
+
+#[test]
+fn hash_elements_vs_merge_with_int() {
+    let seed = ElementDigest::new(rand_array());
+
+    // ----- value fits into a field element ------------------------------------------------------
+    let val: BaseElement = rand_value();
+    let m_result = Poseidon64_256::merge_with_int(seed, val.as_int());
+
+    let mut elements = seed.as_elements().to_vec();
+    elements.push(val);
+    let h_result = Poseidon64_256::hash_elements(&elements);
+
+    assert_eq!(m_result, h_result);
+
+    // ----- value does not fit into a field element ----------------------------------------------
+    let val = BaseElement::MODULUS + 2;
+    let m_result = Poseidon64_256::merge_with_int(seed, val);
+
+    let mut elements = seed.as_elements().to_vec();
+    elements.push(BaseElement::new(val));
+    elements.push(BaseElement::new(1));
+    let h_result = Poseidon64_256::hash_elements(&elements);
+
+    assert_eq!(m_result, h_result);
+}
+
+#[test]
+fn hash_padding() {
+    // adding a zero bytes at the end of a byte string should result in a different hash
+    let r1 = Poseidon64_256::hash(&[1_u8, 2, 3]);
+    let r2 = Poseidon64_256::hash(&[1_u8, 2, 3, 0]);
+    assert_ne!(r1, r2);
+
+    // same as above but with input splitting over two elements
+    let r1 = Poseidon64_256::hash(&[1_u8, 2, 3, 4, 5, 6, 7]);
+    let r2 = Poseidon64_256::hash(&[1_u8, 2, 3, 4, 5, 6, 7, 0]);
+    assert_ne!(r1, r2);
+}
+
+#[test]
+fn hash_vs_hash_elements() {
+    // a byte string spanning multiple permutations should be hashed as a sequence of elements
+    // built from its 7-byte chunks, with a byte of value 1 appended to the last chunk
+    let bytes: Vec<u8> = (0..(7 * RATE_WIDTH * 2 + 3) as u8).collect();
+    let elements = bytes
+        .chunks(7)
+        .map(|chunk| {
+            let mut buf = [0_u8; 8];
+            buf[..chunk.len()].copy_from_slice(chunk);
+            if chunk.len() < 7 {
+                buf[chunk.len()] = 1;
+            }
+            BaseElement::new(u64::from_le_bytes(buf))
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        Poseidon64_256::hash_elements(&elements),
+        Poseidon64_256::hash(&bytes)
+    );
+
+    // for strings of full chunks, the padding byte is placed into the last byte of the last
+    // element
+    let bytes = &bytes[..7 * RATE_WIDTH * 2];
+    let mut elements = elements[..2 * RATE_WIDTH].to_vec();
+    elements[2 * RATE_WIDTH - 1] += BaseElement::new(1 << 56);
+    assert_eq!(
+        Poseidon64_256::hash_elements(&elements),
+        Poseidon64_256::hash(bytes)
+    );
+}
+
+#[test]
+fn hash_elements_padding() {
+    let e1: [BaseElement; 2] = rand_array();
+    let e2 = [e1[0], e1[1], BaseElement::ZERO];
+
+    let r1 = Poseidon64_256::hash_elements(&e1);
+    let r2 = Poseidon64_256::hash_elements(&e2);
+    assert_ne!(r1, r2);
+}
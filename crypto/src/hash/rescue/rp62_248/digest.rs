@@ -3,10 +3,12 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{Digest, DIGEST_SIZE};
-use core::slice;
+use super::{Digest, DigestToElements, DIGEST_SIZE};
+use core::{convert::TryInto, slice};
 use math::{fields::f62::BaseElement, StarkField};
-use utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+use utils::{
+    collections::Vec, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+};
 
 // DIGEST TRAIT IMPLEMENTATIONS
 // ================================================================================================
@@ -47,6 +49,24 @@ impl Digest for ElementDigest {
     }
 }
 
+impl DigestToElements<BaseElement> for ElementDigest {
+    const NUM_ELEMENTS: usize = DIGEST_SIZE;
+
+    fn to_elements(&self) -> Vec<BaseElement> {
+        self.0.to_vec()
+    }
+
+    fn from_elements(elements: &[BaseElement]) -> Result<Self, DeserializationError> {
+        let elements = elements.try_into().map_err(|_| {
+            DeserializationError::InvalidValue(format!(
+                "expected {DIGEST_SIZE} elements, but received {}",
+                elements.len()
+            ))
+        })?;
+        Ok(Self(elements))
+    }
+}
+
 impl Default for ElementDigest {
     fn default() -> Self {
         ElementDigest([BaseElement::default(); DIGEST_SIZE])
@@ -68,16 +88,29 @@ impl Deserializable for ElementDigest {
         let v5 = source.read_u16()?;
         let v6 = source.read_u8()?;
 
-        let e1 = BaseElement::new(v1 & 0x3FFFFFFFFFFFFFFF);
-        let e2 = BaseElement::new(((v2 << 4) >> 2) | (v1 >> 62) & 0x3FFFFFFFFFFFFFFF);
-        let e3 = BaseElement::new(((v3 << 6) >> 2) | (v2 >> 60) & 0x3FFFFFFFFFFFFFFF);
-        let e4 =
-            BaseElement::new(v3 >> 58 | (v4 as u64) << 6 | (v5 as u64) << 38 | (v6 as u64) << 54);
+        let e1 = read_element(v1 & 0x3FFFFFFFFFFFFFFF)?;
+        let e2 = read_element(((v2 << 4) >> 2) | (v1 >> 62) & 0x3FFFFFFFFFFFFFFF)?;
+        let e3 = read_element(((v3 << 6) >> 2) | (v2 >> 60) & 0x3FFFFFFFFFFFFFFF)?;
+        let e4 = read_element(v3 >> 58 | (v4 as u64) << 6 | (v5 as u64) << 38 | (v6 as u64) << 54)?;
 
         Ok(Self([e1, e2, e3, e4]))
     }
 }
 
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Converts a 62-bit limb into a field element; limbs which are not smaller than the field
+/// modulus are rejected so that every digest has a unique encoding.
+fn read_element(value: u64) -> Result<BaseElement, DeserializationError> {
+    if value >= BaseElement::MODULUS {
+        return Err(DeserializationError::InvalidValue(format!(
+            "invalid field element: value {value} is greater than or equal to the field modulus"
+        )));
+    }
+    Ok(BaseElement::new(value))
+}
+
 // TESTS
 // ================================================================================================
 
@@ -85,8 +118,9 @@ impl Deserializable for ElementDigest {
 mod tests {
 
     use super::ElementDigest;
+    use math::{fields::f62::BaseElement, StarkField};
     use rand_utils::rand_array;
-    use utils::{Deserializable, Serializable, SliceReader};
+    use utils::{Deserializable, DeserializationError, Serializable, SliceReader};
 
     #[test]
     fn digest_serialization() {
@@ -101,4 +135,22 @@ mod tests {
 
         assert_eq!(d1, d2);
     }
+
+    #[test]
+    fn digest_deserialization_rejects_invalid_limbs() {
+        let d1 = ElementDigest(rand_array());
+        let mut bytes = vec![];
+        d1.write_into(&mut bytes);
+
+        // the first limb occupies the lower 62 bits of the first 8 bytes; setting it to the field
+        // modulus produces a non-canonical encoding of zero
+        let v1 = u64::from_le_bytes(bytes[..8].try_into().unwrap());
+        let v1 = (v1 & !0x3FFFFFFFFFFFFFFF) | BaseElement::MODULUS;
+        bytes[..8].copy_from_slice(&v1.to_le_bytes());
+        let mut reader = SliceReader::new(&bytes);
+        assert!(matches!(
+            ElementDigest::read_from(&mut reader),
+            Err(DeserializationError::InvalidValue(_))
+        ));
+    }
 }
@@ -3,7 +3,8 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{exp_acc, Digest, ElementHasher, Hasher};
+use super::super::mds::mds_f62_12x12::{mds_multiply, to_canonical_matrix};
+use super::{exp_acc, Digest, DigestToElements, ElementHasher, HashFunction, Hasher};
 use core::convert::TryInto;
 use math::{fields::f62::BaseElement, FieldElement, StarkField};
 
@@ -94,6 +95,8 @@ impl Hasher for Rp62_248 {
 
     const COLLISION_RESISTANCE: u32 = 124;
 
+    const ID: HashFunction = HashFunction::Rp62_248;
+
     fn hash(bytes: &[u8]) -> Self::Digest {
         // compute the number of elements required to represent the string; we will be processing
         // the string in 7-byte chunks, thus the number of elements will be equal to the number
@@ -115,8 +118,8 @@ impl Hasher for Rp62_248 {
         // every 7-byte chunk is guaranteed to map to some field element.
         let mut i = 0;
         let mut buf = [0_u8; 8];
-        for chunk in bytes.chunks(7) {
-            if i < num_elements - 1 {
+        for (chunk_idx, chunk) in bytes.chunks(7).enumerate() {
+            if chunk_idx < num_elements - 1 {
                 buf[..7].copy_from_slice(chunk);
             } else {
                 // if we are dealing with the last chunk, it may be smaller than 7 bytes long, so
@@ -165,6 +168,12 @@ impl Hasher for Rp62_248 {
         ElementDigest::new(state[..DIGEST_SIZE].try_into().unwrap())
     }
 
+    fn merge_many(values: &[Self::Digest]) -> Self::Digest {
+        // since the number of elements is injected into the capacity portion of the state,
+        // hashing all digest elements as a sequence is consistent with merge()
+        Self::hash_elements(ElementDigest::digests_as_elements(values))
+    }
+
     fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
         // initialize the state as follows:
         // - seed is copied into the first 4 elements of the state.
@@ -193,25 +202,38 @@ impl ElementHasher for Rp62_248 {
     type BaseField = BaseElement;
 
     fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
-        // convert the elements into a list of base field elements
-        let elements = E::as_base_elements(elements);
+        Self::hash_iter(elements.iter().copied())
+    }
+
+    fn hash_iter<E, I>(elements: I) -> Self::Digest
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+        I: IntoIterator<Item = E>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        // elements are absorbed as sequences of their base field elements; thus, the number of
+        // absorbed base field elements is known in advance
+        let elements = elements.into_iter();
+        let num_elements = elements.len() * (E::ELEMENT_BYTES / BaseElement::ELEMENT_BYTES);
 
         // initialize state to all zeros, except for the last element of the capacity part, which
         // is set to the number of elements to be hashed. this is done so that adding zero elements
         // at the end of the list always results in a different hash.
         let mut state = [BaseElement::ZERO; STATE_WIDTH];
-        state[STATE_WIDTH - 1] = BaseElement::new(elements.len() as u64);
+        state[STATE_WIDTH - 1] = BaseElement::new(num_elements as u64);
 
         // absorb elements into the state one by one until the rate portion of the state is filled
         // up; then apply the Rescue permutation and start absorbing again; repeat until all
         // elements have been absorbed
         let mut i = 0;
-        for &element in elements.iter() {
-            state[i] += element;
-            i += 1;
-            if i % RATE_WIDTH == 0 {
-                apply_permutation(&mut state);
-                i = 0;
+        for element in elements {
+            for &element in E::as_base_elements(&[element]) {
+                state[i] += element;
+                i += 1;
+                if i % RATE_WIDTH == 0 {
+                    apply_permutation(&mut state);
+                    i = 0;
+                }
             }
         }
 
@@ -260,13 +282,7 @@ fn apply_round(state: &mut [BaseElement; STATE_WIDTH], round: usize) {
 
 #[inline(always)]
 fn apply_mds(state: &mut [BaseElement; STATE_WIDTH]) {
-    let mut result = [BaseElement::ZERO; STATE_WIDTH];
-    result.iter_mut().zip(MDS).for_each(|(r, mds_row)| {
-        state.iter().zip(mds_row).for_each(|(&s, m)| {
-            *r += m * s;
-        });
-    });
-    *state = result
+    mds_multiply(state, &MDS_CANONICAL)
 }
 
 #[inline(always)]
@@ -315,6 +331,10 @@ fn apply_inv_sbox(state: &mut [BaseElement; STATE_WIDTH]) {
 
 // MDS
 // ================================================================================================
+/// Rescue MDS matrix with all elements in canonical Montgomery representation; this is used for
+/// MDS multiplication with delayed reduction.
+const MDS_CANONICAL: [[u64; STATE_WIDTH]; STATE_WIDTH] = to_canonical_matrix(&MDS);
+
 /// Rescue MDS matrix
 /// Computed using algorithm 4 from <https://eprint.iacr.org/2020/1143.pdf>
 const MDS: [[BaseElement; STATE_WIDTH]; STATE_WIDTH] = [
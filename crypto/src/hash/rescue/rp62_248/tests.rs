@@ -5,7 +5,7 @@
 
 use super::{
     BaseElement, ElementDigest, ElementHasher, FieldElement, Hasher, Rp62_248, ALPHA, INV_ALPHA,
-    STATE_WIDTH,
+    MDS, RATE_WIDTH, STATE_WIDTH,
 };
 use core::convert::TryInto;
 use math::StarkField;
@@ -31,6 +31,35 @@ fn test_inv_sbox() {
     assert_eq!(expected, actual);
 }
 
+#[test]
+fn apply_mds() {
+    let state: [BaseElement; STATE_WIDTH] = rand_array();
+
+    let mut expected = [BaseElement::ZERO; STATE_WIDTH];
+    for (r, mds_row) in expected.iter_mut().zip(MDS) {
+        for (&s, m) in state.iter().zip(mds_row) {
+            *r += m * s;
+        }
+    }
+
+    let mut actual = state;
+    super::apply_mds(&mut actual);
+    assert_eq!(expected, actual);
+
+    // make sure elements at the upper end of the field are handled correctly
+    let state = [BaseElement::new(BaseElement::MODULUS - 1); STATE_WIDTH];
+    let mut expected = [BaseElement::ZERO; STATE_WIDTH];
+    for (r, mds_row) in expected.iter_mut().zip(MDS) {
+        for (&s, m) in state.iter().zip(mds_row) {
+            *r += m * s;
+        }
+    }
+
+    let mut actual = state;
+    super::apply_mds(&mut actual);
+    assert_eq!(expected, actual);
+}
+
 #[test]
 fn apply_permutation() {
     let mut state: [BaseElement; STATE_WIDTH] = [
@@ -132,6 +161,32 @@ fn hash_padding() {
     assert_ne!(r1, r2);
 }
 
+#[test]
+fn hash_vs_hash_elements() {
+    // a byte string spanning multiple permutations should be hashed as a sequence of elements
+    // built from its 7-byte chunks, with a byte of value 1 appended to the last chunk
+    let bytes: Vec<u8> = (0..(7 * RATE_WIDTH * 2 + 3) as u8).collect();
+    let elements = bytes
+        .chunks(7)
+        .map(|chunk| {
+            let mut buf = [0_u8; 8];
+            buf[..chunk.len()].copy_from_slice(chunk);
+            if chunk.len() < 7 {
+                buf[chunk.len()] = 1;
+            }
+            BaseElement::new(u64::from_le_bytes(buf))
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(Rp62_248::hash_elements(&elements), Rp62_248::hash(&bytes));
+
+    // for strings of full chunks, the padding byte is placed into the last byte of the last
+    // element
+    let bytes = &bytes[..7 * RATE_WIDTH * 2];
+    let mut elements = elements[..2 * RATE_WIDTH].to_vec();
+    elements[2 * RATE_WIDTH - 1] += BaseElement::new(1 << 56);
+    assert_eq!(Rp62_248::hash_elements(&elements), Rp62_248::hash(bytes));
+}
+
 #[test]
 fn hash_elements_padding() {
     let e1: [BaseElement; 2] = rand_array();
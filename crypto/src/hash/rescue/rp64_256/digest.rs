@@ -3,10 +3,12 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{Digest, DIGEST_SIZE};
-use core::slice;
+use super::{Digest, DigestToElements, DIGEST_SIZE};
+use core::{convert::TryInto, slice};
 use math::{fields::f64::BaseElement, StarkField};
-use utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+use utils::{
+    collections::Vec, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+};
 
 // DIGEST TRAIT IMPLEMENTATIONS
 // ================================================================================================
@@ -43,6 +45,24 @@ impl Digest for ElementDigest {
     }
 }
 
+impl DigestToElements<BaseElement> for ElementDigest {
+    const NUM_ELEMENTS: usize = DIGEST_SIZE;
+
+    fn to_elements(&self) -> Vec<BaseElement> {
+        self.0.to_vec()
+    }
+
+    fn from_elements(elements: &[BaseElement]) -> Result<Self, DeserializationError> {
+        let elements = elements.try_into().map_err(|_| {
+            DeserializationError::InvalidValue(format!(
+                "expected {DIGEST_SIZE} elements, but received {}",
+                elements.len()
+            ))
+        })?;
+        Ok(Self(elements))
+    }
+}
+
 impl Default for ElementDigest {
     fn default() -> Self {
         ElementDigest([BaseElement::default(); DIGEST_SIZE])
@@ -57,11 +77,12 @@ impl Serializable for ElementDigest {
 
 impl Deserializable for ElementDigest {
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
-        // TODO: check if the field elements are valid?
-        let e1 = BaseElement::new(source.read_u64()?);
-        let e2 = BaseElement::new(source.read_u64()?);
-        let e3 = BaseElement::new(source.read_u64()?);
-        let e4 = BaseElement::new(source.read_u64()?);
+        // field elements are read in canonical form; this fails for limbs which are not smaller
+        // than the field modulus
+        let e1 = BaseElement::read_from(source)?;
+        let e2 = BaseElement::read_from(source)?;
+        let e3 = BaseElement::read_from(source)?;
+        let e4 = BaseElement::read_from(source)?;
 
         Ok(Self([e1, e2, e3, e4]))
     }
@@ -91,9 +112,10 @@ impl From<ElementDigest> for [u8; 32] {
 #[cfg(test)]
 mod tests {
 
-    use super::ElementDigest;
+    use super::{DigestToElements, ElementDigest};
+    use math::{fields::f64::BaseElement, FieldElement, StarkField};
     use rand_utils::rand_array;
-    use utils::{Deserializable, Serializable, SliceReader};
+    use utils::{Deserializable, DeserializationError, Serializable, SliceReader};
 
     #[test]
     fn digest_serialization() {
@@ -108,4 +130,29 @@ mod tests {
 
         assert_eq!(d1, d2);
     }
+
+    #[test]
+    fn digest_to_elements() {
+        let d1 = ElementDigest(rand_array());
+        let elements = d1.to_elements();
+        assert_eq!(d1.as_elements(), &elements[..]);
+        assert_eq!(d1, ElementDigest::from_elements(&elements).unwrap());
+
+        assert!(ElementDigest::from_elements(&[BaseElement::ONE; 3]).is_err());
+    }
+
+    #[test]
+    fn digest_deserialization_rejects_invalid_limbs() {
+        let d1 = ElementDigest(rand_array());
+        let mut bytes = vec![];
+        d1.write_into(&mut bytes);
+
+        // a limb equal to the field modulus is a non-canonical encoding of zero
+        bytes[16..24].copy_from_slice(&BaseElement::MODULUS.to_le_bytes());
+        let mut reader = SliceReader::new(&bytes);
+        assert!(matches!(
+            ElementDigest::read_from(&mut reader),
+            Err(DeserializationError::InvalidValue(_))
+        ));
+    }
 }
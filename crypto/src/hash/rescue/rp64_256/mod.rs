@@ -4,7 +4,7 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::super::mds::mds_f64_12x12::mds_multiply;
-use super::{exp_acc, Digest, ElementHasher, Hasher};
+use super::{exp_acc, Digest, DigestToElements, ElementHasher, HashFunction, Hasher};
 use core::convert::TryInto;
 use core::ops::Range;
 use math::{fields::f64::BaseElement, FieldElement, StarkField};
@@ -120,6 +120,8 @@ impl Hasher for Rp64_256 {
 
     const COLLISION_RESISTANCE: u32 = 128;
 
+    const ID: HashFunction = HashFunction::Rp64_256;
+
     fn hash(bytes: &[u8]) -> Self::Digest {
         // compute the number of elements required to represent the string; we will be processing
         // the string in 7-byte chunks, thus the number of elements will be equal to the number
@@ -141,8 +143,8 @@ impl Hasher for Rp64_256 {
         // every 7-byte chunk is guaranteed to map to some field element.
         let mut i = 0;
         let mut buf = [0_u8; 8];
-        for chunk in bytes.chunks(7) {
-            if i < num_elements - 1 {
+        for (chunk_idx, chunk) in bytes.chunks(7).enumerate() {
+            if chunk_idx < num_elements - 1 {
                 buf[..7].copy_from_slice(chunk);
             } else {
                 // if we are dealing with the last chunk, it may be smaller than 7 bytes long, so
@@ -191,6 +193,12 @@ impl Hasher for Rp64_256 {
         ElementDigest::new(state[DIGEST_RANGE].try_into().unwrap())
     }
 
+    fn merge_many(values: &[Self::Digest]) -> Self::Digest {
+        // since the number of elements is injected into the capacity portion of the state,
+        // hashing all digest elements as a sequence is consistent with merge()
+        Self::hash_elements(ElementDigest::digests_as_elements(values))
+    }
+
     fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
         // initialize the state as follows:
         // - seed is copied into the first 4 elements of the rate portion of the state.
@@ -219,25 +227,38 @@ impl ElementHasher for Rp64_256 {
     type BaseField = BaseElement;
 
     fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
-        // convert the elements into a list of base field elements
-        let elements = E::as_base_elements(elements);
+        Self::hash_iter(elements.iter().copied())
+    }
+
+    fn hash_iter<E, I>(elements: I) -> Self::Digest
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+        I: IntoIterator<Item = E>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        // elements are absorbed as sequences of their base field elements; thus, the number of
+        // absorbed base field elements is known in advance
+        let elements = elements.into_iter();
+        let num_elements = elements.len() * (E::ELEMENT_BYTES / BaseElement::ELEMENT_BYTES);
 
         // initialize state to all zeros, except for the last element of the capacity part, which
         // is set to the number of elements to be hashed. this is done so that adding zero elements
         // at the end of the list always results in a different hash.
         let mut state = [BaseElement::ZERO; STATE_WIDTH];
-        state[CAPACITY_RANGE.start] = BaseElement::new(elements.len() as u64);
+        state[CAPACITY_RANGE.start] = BaseElement::new(num_elements as u64);
 
         // absorb elements into the state one by one until the rate portion of the state is filled
         // up; then apply the Rescue permutation and start absorbing again; repeat until all
         // elements have been absorbed
         let mut i = 0;
-        for &element in elements.iter() {
-            state[RATE_RANGE.start + i] += element;
-            i += 1;
-            if i % RATE_WIDTH == 0 {
-                Self::apply_permutation(&mut state);
-                i = 0;
+        for element in elements {
+            for &element in E::as_base_elements(&[element]) {
+                state[RATE_RANGE.start + i] += element;
+                i += 1;
+                if i % RATE_WIDTH == 0 {
+                    Self::apply_permutation(&mut state);
+                    i = 0;
+                }
             }
         }
 
@@ -5,9 +5,10 @@
 
 use super::{
     BaseElement, ElementDigest, ElementHasher, FieldElement, Hasher, Rp64_256, StarkField, ALPHA,
-    INV_ALPHA, INV_MDS, MDS, STATE_WIDTH,
+    INV_ALPHA, INV_MDS, MDS, RATE_WIDTH, STATE_WIDTH,
 };
 use core::convert::TryInto;
+use math::fields::QuadExtension;
 use proptest::prelude::*;
 
 use rand_utils::{rand_array, rand_value};
@@ -166,6 +167,38 @@ fn hash_padding() {
     let r1 = Rp64_256::hash(&[1_u8, 2, 3, 4, 5, 6, 7, 0, 0]);
     let r2 = Rp64_256::hash(&[1_u8, 2, 3, 4, 5, 6, 7, 0, 0, 0, 0]);
     assert_ne!(r1, r2);
+
+    // same as above but with inputs spanning multiple permutations
+    let bytes = [7_u8; 7 * RATE_WIDTH + 3];
+    let r1 = Rp64_256::hash(&bytes[..7 * RATE_WIDTH + 2]);
+    let r2 = Rp64_256::hash(&bytes);
+    assert_ne!(r1, r2);
+}
+
+#[test]
+fn hash_vs_hash_elements() {
+    // a byte string spanning multiple permutations should be hashed as a sequence of elements
+    // built from its 7-byte chunks, with a byte of value 1 appended to the last chunk
+    let bytes: Vec<u8> = (0..(7 * RATE_WIDTH * 2 + 3) as u8).collect();
+    let elements = bytes
+        .chunks(7)
+        .map(|chunk| {
+            let mut buf = [0_u8; 8];
+            buf[..chunk.len()].copy_from_slice(chunk);
+            if chunk.len() < 7 {
+                buf[chunk.len()] = 1;
+            }
+            BaseElement::new(u64::from_le_bytes(buf))
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(Rp64_256::hash_elements(&elements), Rp64_256::hash(&bytes));
+
+    // for strings of full chunks, the padding byte is placed into the last byte of the last
+    // element
+    let bytes = &bytes[..7 * RATE_WIDTH * 2];
+    let mut elements = elements[..2 * RATE_WIDTH].to_vec();
+    elements[2 * RATE_WIDTH - 1] += BaseElement::new(1 << 56);
+    assert_eq!(Rp64_256::hash_elements(&elements), Rp64_256::hash(bytes));
 }
 
 #[test]
@@ -178,6 +211,30 @@ fn hash_elements_padding() {
     assert_ne!(r1, r2);
 }
 
+#[test]
+fn hash_iter_extension_elements() {
+    // extension field elements should be absorbed as sequences of their base field elements
+    let elements: [QuadExtension<BaseElement>; 3] = rand_array();
+    let base_elements = QuadExtension::<BaseElement>::as_base_elements(&elements);
+    assert_eq!(
+        Rp64_256::hash_elements(base_elements),
+        Rp64_256::hash_iter(elements.iter().copied())
+    );
+}
+
+#[test]
+fn hash_elements_keyed() {
+    let key1 = Rp64_256::derive_key(b"trace");
+    let key2 = Rp64_256::derive_key(b"constraints");
+    let elements: [BaseElement; 8] = rand_array();
+
+    // the same elements hashed with different keys should result in different digests
+    let r1 = Rp64_256::hash_elements_keyed(&key1, &elements);
+    let r2 = Rp64_256::hash_elements_keyed(&key2, &elements);
+    assert_ne!(r1, r2);
+    assert_ne!(r1, Rp64_256::hash_elements(&elements));
+}
+
 #[inline(always)]
 fn apply_mds_naive(state: &mut [BaseElement; STATE_WIDTH]) {
     let mut result = [BaseElement::ZERO; STATE_WIDTH];
@@ -199,7 +256,7 @@ proptest! {
         for i in 0..STATE_WIDTH {
             v1[i] = BaseElement::new(a[i]);
         }
-        v2 = v1.clone();
+        v2 = v1;
 
         apply_mds_naive(&mut v1);
         Rp64_256::apply_mds(&mut v2);
@@ -3,12 +3,15 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{ByteDigest, ElementHasher, Hasher};
+use super::{serialized_element_bytes, ByteDigest, ElementHasher, HashFunction, Hasher};
 use core::marker::PhantomData;
 use math::{FieldElement, StarkField};
 use sha3::Digest;
 use utils::ByteWriter;
 
+#[cfg(test)]
+mod tests;
+
 // SHA3 WITH 256-BIT OUTPUT
 // ================================================================================================
 
@@ -21,6 +24,8 @@ impl<B: StarkField> Hasher for Sha3_256<B> {
 
     const COLLISION_RESISTANCE: u32 = 128;
 
+    const ID: HashFunction = HashFunction::Sha3_256;
+
     fn hash(bytes: &[u8]) -> Self::Digest {
         ByteDigest(sha3::Sha3_256::digest(bytes).into())
     }
@@ -29,6 +34,10 @@ impl<B: StarkField> Hasher for Sha3_256<B> {
         ByteDigest(sha3::Sha3_256::digest(ByteDigest::digests_as_bytes(values)).into())
     }
 
+    fn merge_many(values: &[Self::Digest]) -> Self::Digest {
+        ByteDigest(sha3::Sha3_256::digest(ByteDigest::digests_as_bytes(values)).into())
+    }
+
     fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
         let mut data = [0; 40];
         data[..32].copy_from_slice(&seed.0);
@@ -41,38 +50,182 @@ impl<B: StarkField> ElementHasher for Sha3_256<B> {
     type BaseField = B;
 
     fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
-        if B::IS_CANONICAL {
-            // when element's internal and canonical representations are the same, we can hash
-            // element bytes directly
-            let bytes = E::elements_as_bytes(elements);
-            ByteDigest(sha3::Sha3_256::digest(bytes).into())
-        } else {
-            // when elements' internal and canonical representations differ, we need to serialize
-            // them before hashing
-            let mut hasher = ShaHasher::new();
-            hasher.write(elements);
-            ByteDigest(hasher.finalize())
+        match serialized_element_bytes(elements) {
+            Some(bytes) => ByteDigest(sha3::Sha3_256::digest(bytes).into()),
+            None => {
+                let mut hasher = ShaHasher::<sha3::Sha3_256>::new();
+                hasher.write(elements);
+                ByteDigest(hasher.finalize())
+            }
+        }
+    }
+
+    fn hash_iter<E, I>(elements: I) -> Self::Digest
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+        I: IntoIterator<Item = E>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        let mut hasher = ShaHasher::<sha3::Sha3_256>::new();
+        for element in elements {
+            hasher.write(element);
+        }
+        ByteDigest(hasher.finalize())
+    }
+}
+
+// KECCAK WITH 256-BIT OUTPUT
+// ================================================================================================
+
+/// Implementation of the [Hasher](super::Hasher) trait for Keccak hash function with 256-bit
+/// output.
+///
+/// This is the original Keccak submission (as used by Ethereum) which differs from the
+/// standardized SHA3 in its padding rule. The main use case for this hasher is generating proofs
+/// which are to be verified on EVM-compatible chains, where KECCAK256 is the only inexpensive
+/// hash function.
+pub struct Keccak256<B: StarkField>(PhantomData<B>);
+
+impl<B: StarkField> Hasher for Keccak256<B> {
+    type Digest = ByteDigest<32>;
+
+    const COLLISION_RESISTANCE: u32 = 128;
+
+    const ID: HashFunction = HashFunction::Keccak256;
+
+    fn hash(bytes: &[u8]) -> Self::Digest {
+        ByteDigest(sha3::Keccak256::digest(bytes).into())
+    }
+
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        ByteDigest(sha3::Keccak256::digest(ByteDigest::digests_as_bytes(values)).into())
+    }
+
+    fn merge_many(values: &[Self::Digest]) -> Self::Digest {
+        ByteDigest(sha3::Keccak256::digest(ByteDigest::digests_as_bytes(values)).into())
+    }
+
+    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        let mut data = [0; 40];
+        data[..32].copy_from_slice(&seed.0);
+        data[32..].copy_from_slice(&value.to_le_bytes());
+        ByteDigest(sha3::Keccak256::digest(data).into())
+    }
+}
+
+impl<B: StarkField> ElementHasher for Keccak256<B> {
+    type BaseField = B;
+
+    fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
+        match serialized_element_bytes(elements) {
+            Some(bytes) => ByteDigest(sha3::Keccak256::digest(bytes).into()),
+            None => {
+                let mut hasher = ShaHasher::<sha3::Keccak256>::new();
+                hasher.write(elements);
+                ByteDigest(hasher.finalize())
+            }
+        }
+    }
+
+    fn hash_iter<E, I>(elements: I) -> Self::Digest
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+        I: IntoIterator<Item = E>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        let mut hasher = ShaHasher::<sha3::Keccak256>::new();
+        for element in elements {
+            hasher.write(element);
+        }
+        ByteDigest(hasher.finalize())
+    }
+}
+
+// SHA2 WITH 256-BIT OUTPUT
+// ================================================================================================
+
+/// Implementation of the [Hasher](super::Hasher) trait for SHA-256 hash function (from the SHA2
+/// family) with 256-bit output.
+///
+/// SHA-256 is considerably slower than BLAKE3, but it may be required in deployments which must
+/// rely only on NIST-approved primitives, or where proofs are verified in environments which
+/// provide native support for SHA-256.
+pub struct Sha2_256<B: StarkField>(PhantomData<B>);
+
+impl<B: StarkField> Hasher for Sha2_256<B> {
+    type Digest = ByteDigest<32>;
+
+    const COLLISION_RESISTANCE: u32 = 128;
+
+    const ID: HashFunction = HashFunction::Sha2_256;
+
+    fn hash(bytes: &[u8]) -> Self::Digest {
+        ByteDigest(sha2::Sha256::digest(bytes).into())
+    }
+
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        ByteDigest(sha2::Sha256::digest(ByteDigest::digests_as_bytes(values)).into())
+    }
+
+    fn merge_many(values: &[Self::Digest]) -> Self::Digest {
+        ByteDigest(sha2::Sha256::digest(ByteDigest::digests_as_bytes(values)).into())
+    }
+
+    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        let mut data = [0; 40];
+        data[..32].copy_from_slice(&seed.0);
+        data[32..].copy_from_slice(&value.to_le_bytes());
+        ByteDigest(sha2::Sha256::digest(data).into())
+    }
+}
+
+impl<B: StarkField> ElementHasher for Sha2_256<B> {
+    type BaseField = B;
+
+    fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
+        match serialized_element_bytes(elements) {
+            Some(bytes) => ByteDigest(sha2::Sha256::digest(bytes).into()),
+            None => {
+                let mut hasher = ShaHasher::<sha2::Sha256>::new();
+                hasher.write(elements);
+                ByteDigest(hasher.finalize())
+            }
+        }
+    }
+
+    fn hash_iter<E, I>(elements: I) -> Self::Digest
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+        I: IntoIterator<Item = E>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        let mut hasher = ShaHasher::<sha2::Sha256>::new();
+        for element in elements {
+            hasher.write(element);
         }
+        ByteDigest(hasher.finalize())
     }
 }
 
 // SHA HASHER
 // ================================================================================================
 
-/// Wrapper around SHA3 hasher to implement [ByteWriter] trait for it.
-struct ShaHasher(sha3::Sha3_256);
+/// Wrapper around SHA3, Keccak, and SHA-256 hashers to implement [ByteWriter] trait for them.
+struct ShaHasher<D: Digest>(D);
 
-impl ShaHasher {
+impl<D: Digest> ShaHasher<D> {
     pub fn new() -> Self {
-        Self(sha3::Sha3_256::new())
+        Self(D::new())
     }
 
     pub fn finalize(self) -> [u8; 32] {
-        self.0.finalize().into()
+        let mut result = [0; 32];
+        result.copy_from_slice(&self.0.finalize());
+        result
     }
 }
 
-impl ByteWriter for ShaHasher {
+impl<D: Digest> ByteWriter for ShaHasher<D> {
     fn write_u8(&mut self, value: u8) {
         self.0.update([value]);
     }
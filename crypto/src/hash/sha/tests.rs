@@ -0,0 +1,126 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{ElementHasher, Hasher, Keccak256, Sha2_256, Sha3_256};
+use math::fields::{f128, f62};
+use rand_utils::{rand_value, rand_vector};
+use utils::Serializable;
+
+type Sha2 = Sha2_256<f128::BaseElement>;
+
+#[test]
+fn sha2_test_vectors() {
+    // test vectors from FIPS 180-4 examples and NIST CAVP
+    assert_eq!(
+        "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+        hex(&Sha2::hash(b"").0)
+    );
+    assert_eq!(
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+        hex(&Sha2::hash(b"abc").0)
+    );
+    assert_eq!(
+        "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1",
+        hex(&Sha2::hash(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq").0)
+    );
+    assert_eq!(
+        "cdc76e5c9914fb9281a1c7e284d73e67f1809a48a497200e046d39ccc7112cd0",
+        hex(&Sha2::hash(&[b'a'; 1_000_000]).0)
+    );
+}
+
+#[test]
+fn sha2_merge() {
+    let v1 = Sha2::hash(&rand_value::<u64>().to_le_bytes());
+    let v2 = Sha2::hash(&rand_value::<u64>().to_le_bytes());
+
+    let mut bytes = [0u8; 64];
+    bytes[..32].copy_from_slice(&v1.0);
+    bytes[32..].copy_from_slice(&v2.0);
+    assert_eq!(Sha2::hash(&bytes), Sha2::merge(&[v1, v2]));
+
+    let mut bytes = [0u8; 40];
+    bytes[..32].copy_from_slice(&v1.0);
+    bytes[32..].copy_from_slice(&5u64.to_le_bytes());
+    assert_eq!(Sha2::hash(&bytes), Sha2::merge_with_int(v1, 5));
+}
+
+#[test]
+fn sha2_hash_elements() {
+    // canonical field elements are hashed directly
+    let elements = rand_vector::<f128::BaseElement>(17);
+    assert_eq!(
+        Sha2::hash(&elements.to_bytes()),
+        Sha2::hash_elements(&elements)
+    );
+
+    // non-canonical field elements are serialized before hashing
+    let elements = rand_vector::<f62::BaseElement>(17);
+    assert_eq!(
+        Sha2_256::<f62::BaseElement>::hash(&elements.to_bytes()),
+        Sha2_256::<f62::BaseElement>::hash_elements(&elements)
+    );
+}
+
+#[test]
+fn hash_iter() {
+    // hashing elements via an iterator should be the same as hashing a slice of elements for
+    // both canonical and non-canonical field elements
+    let elements = rand_vector::<f128::BaseElement>(17);
+    assert_eq!(
+        Sha2::hash_elements(&elements),
+        Sha2::hash_iter(elements.iter().copied())
+    );
+    assert_eq!(
+        Sha3_256::<f128::BaseElement>::hash_elements(&elements),
+        Sha3_256::<f128::BaseElement>::hash_iter(elements.iter().copied())
+    );
+
+    let elements = rand_vector::<f62::BaseElement>(17);
+    assert_eq!(
+        Sha2_256::<f62::BaseElement>::hash_elements(&elements),
+        Sha2_256::<f62::BaseElement>::hash_iter(elements.iter().copied())
+    );
+    assert_eq!(
+        Keccak256::<f62::BaseElement>::hash_elements(&elements),
+        Keccak256::<f62::BaseElement>::hash_iter(elements.iter().copied())
+    );
+}
+
+#[test]
+fn keccak256_test_vectors() {
+    type Keccak = Keccak256<f128::BaseElement>;
+
+    // Keccak-256 uses different padding than SHA3-256, and thus, produces different digests
+    assert_eq!(
+        "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470",
+        hex(&Keccak::hash(b"").0)
+    );
+    assert_eq!(
+        "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45",
+        hex(&Keccak::hash(b"abc").0)
+    );
+    assert_ne!(
+        Keccak::hash(b"abc"),
+        Sha3_256::<f128::BaseElement>::hash(b"abc")
+    );
+}
+
+#[test]
+fn keccak256_hash_elements() {
+    // non-canonical field elements are serialized before hashing
+    let elements = rand_vector::<f62::BaseElement>(17);
+    assert_eq!(
+        Keccak256::<f62::BaseElement>::hash(&elements.to_bytes()),
+        Keccak256::<f62::BaseElement>::hash_elements(&elements)
+    );
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
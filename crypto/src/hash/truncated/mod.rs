@@ -0,0 +1,107 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{ByteDigest, ElementHasher, HashFunction, Hasher};
+use core::{convert::TryInto, fmt::Debug, marker::PhantomData};
+use math::FieldElement;
+
+#[cfg(test)]
+mod tests;
+
+// TRUNCATED HASH FUNCTION
+// ================================================================================================
+
+/// A hash function `H` with digests truncated to the first `N` bytes.
+///
+/// Truncated digests make Merkle authentication paths shorter, which can significantly reduce
+/// proof size (and the cost of verifying proofs on-chain). Collision resistance of the truncated
+/// hash function is bounded by `N * 4` bits; thus, for example, 20-byte digests are sufficient
+/// only when the target security level is at most 80 bits.
+///
+/// Merging of digests is done by hashing the concatenation of truncated digests with `H`, and
+/// truncating the result. Thus, a tree built with `Truncated<H, N>` commits to truncated nodes
+/// at every level.
+///
+/// `N` must not be greater than 32; using a larger `N` results in a compilation error:
+///
+/// ```compile_fail
+/// use winter_crypto::{hashers::{Blake3_256, Truncated}, Hasher};
+/// use math::fields::f128::BaseElement;
+///
+/// Truncated::<Blake3_256<BaseElement>, 33>::hash(&[1, 2, 3]);
+/// ```
+#[derive(Debug, PartialEq, Eq)]
+pub struct Truncated<H, const N: usize>(PhantomData<H>);
+
+impl<H, const N: usize> Truncated<H, N> {
+    /// Size of truncated digests in bytes; evaluating this constant fails at compile time when
+    /// `N` is greater than 32.
+    const DIGEST_SIZE: usize = {
+        assert!(N <= 32, "truncated digests cannot exceed 32 bytes");
+        N
+    };
+
+    /// Returns the first `N` bytes of the specified digest.
+    fn truncate(digest: ByteDigest<32>) -> ByteDigest<N> {
+        ByteDigest(digest.0[..Self::DIGEST_SIZE].try_into().unwrap())
+    }
+}
+
+impl<H, const N: usize> Hasher for Truncated<H, N>
+where
+    H: Hasher<Digest = ByteDigest<32>>,
+{
+    type Digest = ByteDigest<N>;
+
+    const COLLISION_RESISTANCE: u32 = if H::COLLISION_RESISTANCE < (N as u32) * 4 {
+        H::COLLISION_RESISTANCE
+    } else {
+        (N as u32) * 4
+    };
+
+    /// Truncated hash functions are identified by both the hash function `H` and the digest size
+    /// `N`; if `H` does not support truncated identifiers, the identifier is
+    /// [HashFunction::Unspecified].
+    const ID: HashFunction = HashFunction::truncated(H::ID, N);
+
+    fn hash(bytes: &[u8]) -> Self::Digest {
+        Self::truncate(H::hash(bytes))
+    }
+
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        Self::truncate(H::hash(ByteDigest::digests_as_bytes(values)))
+    }
+
+    fn merge_many(values: &[Self::Digest]) -> Self::Digest {
+        Self::truncate(H::hash(ByteDigest::digests_as_bytes(values)))
+    }
+
+    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        let mut data = [0; 40];
+        data[..N].copy_from_slice(&seed.0);
+        data[N..N + 8].copy_from_slice(&value.to_le_bytes());
+        Self::truncate(H::hash(&data[..N + 8]))
+    }
+}
+
+impl<H, const N: usize> ElementHasher for Truncated<H, N>
+where
+    H: ElementHasher<Digest = ByteDigest<32>>,
+{
+    type BaseField = H::BaseField;
+
+    fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
+        Self::truncate(H::hash_elements(elements))
+    }
+
+    fn hash_iter<E, I>(elements: I) -> Self::Digest
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+        I: IntoIterator<Item = E>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        Self::truncate(H::hash_iter(elements))
+    }
+}
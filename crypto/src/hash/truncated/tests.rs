@@ -0,0 +1,117 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{ElementHasher, HashFunction, Hasher, Truncated};
+use crate::{
+    hash::{Blake3_256, Rp64_256, Sha3_256},
+    MerkleTree,
+};
+use math::fields::f128::BaseElement;
+use rand_utils::rand_vector;
+use utils::{Deserializable, Serializable, SliceReader};
+
+type Blake3 = Blake3_256<BaseElement>;
+type Blake3_160 = Truncated<Blake3, 20>;
+
+#[test]
+fn truncated_digests() {
+    // digests are prefixes of the digests of the underlying hash function
+    let bytes = [1_u8, 2, 3, 4];
+    assert_eq!(Blake3::hash(&bytes).0[..20], Blake3_160::hash(&bytes).0);
+
+    let elements = rand_vector::<BaseElement>(7);
+    assert_eq!(
+        Blake3::hash_elements(&elements).0[..20],
+        Blake3_160::hash_elements(&elements).0
+    );
+    assert_eq!(
+        Blake3_160::hash_elements(&elements),
+        Blake3_160::hash_iter(elements.iter().copied())
+    );
+
+    // merging hashes truncated digests
+    let d1 = Blake3_160::hash(&[1]);
+    let d2 = Blake3_160::hash(&[2]);
+    let mut data = d1.to_bytes();
+    data.extend_from_slice(&d2.to_bytes());
+    assert_eq!(Blake3_160::hash(&data), Blake3_160::merge(&[d1, d2]));
+    assert_eq!(
+        Blake3_160::merge(&[d1, d2]),
+        Blake3_160::merge_many(&[d1, d2])
+    );
+    assert_ne!(
+        Blake3_160::merge_with_int(d1, 1),
+        Blake3_160::merge_with_int(d1, 2)
+    );
+}
+
+#[test]
+fn truncated_collision_resistance() {
+    assert_eq!(80, Blake3_160::COLLISION_RESISTANCE);
+    assert_eq!(
+        96,
+        Truncated::<Sha3_256<BaseElement>, 24>::COLLISION_RESISTANCE
+    );
+    assert_eq!(128, Truncated::<Blake3, 32>::COLLISION_RESISTANCE);
+}
+
+#[test]
+fn truncated_ids() {
+    // identifiers encode both the underlying hash function and the digest size
+    let id = Blake3_160::ID;
+    assert_eq!(HashFunction::Truncated(&HashFunction::Blake3_256, 20), id);
+    assert_eq!(0x80 | 19, id.id());
+    assert_ne!(Blake3::ID, id);
+    assert_ne!(Truncated::<Blake3, 24>::ID, id);
+    assert_ne!(Truncated::<Sha3_256<BaseElement>, 20>::ID, id);
+    assert_eq!(
+        0x80 | (1 << 5) | 19,
+        Truncated::<Sha3_256<BaseElement>, 20>::ID.id()
+    );
+
+    // identifiers should survive a round trip through their binary encoding
+    let bytes = id.to_bytes();
+    assert_eq!(vec![id.id()], bytes);
+    assert_eq!(
+        id,
+        HashFunction::read_from(&mut SliceReader::new(&bytes)).unwrap()
+    );
+    for id in 0..=u8::MAX {
+        if let Ok(hash_function) = HashFunction::from_id(id) {
+            assert_eq!(id, hash_function.id());
+        }
+    }
+
+    // truncated identifiers are defined only for supported hash functions and digest sizes
+    assert_eq!(
+        HashFunction::Unspecified,
+        HashFunction::truncated(Rp64_256::ID, 20)
+    );
+    assert_eq!(
+        HashFunction::Unspecified,
+        HashFunction::truncated(HashFunction::Blake3_256, 0)
+    );
+    assert_eq!(
+        HashFunction::Unspecified,
+        HashFunction::truncated(HashFunction::Blake3_256, 33)
+    );
+}
+
+#[test]
+fn truncated_merkle_tree() {
+    let leaves = (0..16u8)
+        .map(|i| Blake3_160::hash(&[i]))
+        .collect::<Vec<_>>();
+    let tree = MerkleTree::<Blake3_160>::new(leaves).unwrap();
+
+    // authentication paths consist of 20-byte nodes
+    let proof = tree.prove_batch(&[1, 6, 11]).unwrap();
+    assert!(MerkleTree::verify_batch(tree.root(), &[1, 6, 11], &proof).is_ok());
+    let full_proof = MerkleTree::<Blake3>::new((0..16u8).map(|i| Blake3::hash(&[i])).collect())
+        .unwrap()
+        .prove_batch(&[1, 6, 11])
+        .unwrap();
+    assert!(proof.serialize_nodes().len() < full_proof.serialize_nodes().len());
+}
@@ -12,6 +12,7 @@
 //!   [MerkleTree] implementation supports concurrent tree construction as well as compact
 //!   aggregation of Merkle paths implemented using a variation of the
 //!   [Octopus](https://eprint.iacr.org/2017/933) algorithm.
+//!   Commitment schemes are abstracted behind the [VectorCommitment] trait.
 //! * **PRNG** - which is used to generate pseudo-random elements in a finite field. The
 //!   [RandomCoin] implementation uses a cryptographic hash function to generate pseudo-random
 //!   elements form a seed.
@@ -23,27 +24,46 @@
 extern crate alloc;
 
 mod hash;
-pub use hash::{Digest, ElementHasher, Hasher};
+pub use hash::{Digest, DigestToElements, ElementHasher, HashFunction, Hashable, Hasher};
 pub mod hashers {
     //! Contains implementations of currently supported hash functions.
 
     pub use super::hash::Blake3_192;
     pub use super::hash::Blake3_256;
     pub use super::hash::GriffinJive64_256;
+    pub use super::hash::Keccak256;
+    pub use super::hash::Poseidon64_256;
     pub use super::hash::Rp62_248;
     pub use super::hash::Rp64_256;
     pub use super::hash::RpJive64_256;
+    pub use super::hash::Sha2_256;
     pub use super::hash::Sha3_256;
+    pub use super::hash::Truncated;
 }
 
+#[cfg(feature = "std")]
+pub use hash::params as hash_params;
+
+mod commitment;
+pub use commitment::VectorCommitment;
+
 mod merkle;
-pub use merkle::{build_merkle_nodes, BatchMerkleProof, MerkleTree};
+pub use merkle::{
+    build_kary_merkle_nodes, build_merkle_nodes, BatchMerkleProof, MerkleTree, MerkleTreeBuilder,
+    PartialMerkleTree, PartitionedMerkleProof, PartitionedMerkleTree, SaltedMerkleTree,
+    MAX_SERIALIZED_TREE_DEPTH, MERKLE_TREE_ARITIES,
+};
 
 #[cfg(feature = "concurrent")]
 pub use merkle::concurrent;
 
+#[cfg(feature = "mmap")]
+pub use merkle::DiskMerkleTree;
+
 mod random;
-pub use random::RandomCoin;
+pub use random::{
+    RandomCoin, Transcript, TranscriptEntry, TranscriptMismatch, TranscriptMode, TranscriptOp,
+};
 
 mod errors;
 pub use errors::{MerkleTreeError, RandomCoinError};
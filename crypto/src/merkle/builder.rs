@@ -0,0 +1,184 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{level_fan_ins, MerkleTree, MERKLE_TREE_ARITIES};
+use crate::{errors::MerkleTreeError, hash::Hasher};
+use math::log2;
+use utils::collections::Vec;
+
+// MERKLE TREE BUILDER
+// ================================================================================================
+
+/// A builder which constructs a [MerkleTree] from leaves supplied one at a time.
+///
+/// Unlike [MerkleTree::new()], the builder does not require all leaves to be available upfront.
+/// Internal nodes are computed as soon as all of their children become available, and thus,
+/// by the time the last leaf is added, only a handful of nodes on the path from that leaf to the
+/// root remain to be computed. This makes it possible to overlap tree construction with the
+/// process which produces the leaves (e.g., hashing rows of a matrix as they are computed).
+///
+/// The number of leaves must be specified when the builder is created, and the tree can be
+/// finalized only after exactly this many leaves have been added.
+///
+/// # Examples
+/// ```
+/// # use winter_crypto::{MerkleTree, MerkleTreeBuilder, Hasher, hashers::Blake3_256};
+/// # use math::fields::f128::BaseElement;
+/// type Blake3 = Blake3_256::<BaseElement>;
+///
+/// let leaves = (0..8u8).map(|i| Blake3::hash(&[i])).collect::<Vec<_>>();
+///
+/// let mut builder = MerkleTreeBuilder::<Blake3>::new(8).unwrap();
+/// for &leaf in leaves.iter() {
+///     builder.push(leaf).unwrap();
+/// }
+/// let tree = builder.finalize().unwrap();
+///
+/// assert_eq!(MerkleTree::<Blake3>::new(leaves).unwrap().root(), tree.root());
+/// ```
+#[derive(Debug)]
+pub struct MerkleTreeBuilder<H: Hasher> {
+    num_leaves: usize,
+    arity: usize,
+    fan_ins: Vec<usize>,
+    leaves: Vec<H::Digest>,
+    levels: Vec<Vec<H::Digest>>,
+}
+
+impl<H: Hasher> MerkleTreeBuilder<H> {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new builder for a binary Merkle tree with the specified number of leaves.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * `num_leaves` is smaller than two.
+    /// * `num_leaves` is not a power of two.
+    pub fn new(num_leaves: usize) -> Result<Self, MerkleTreeError> {
+        Self::with_arity(num_leaves, 2)
+    }
+
+    /// Returns a new builder for a Merkle tree with the specified number of leaves and `arity`.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * `arity` is not one of 2, 4, 8, or 16.
+    /// * `num_leaves` is smaller than two.
+    /// * `num_leaves` is not a power of two.
+    pub fn with_arity(num_leaves: usize, arity: usize) -> Result<Self, MerkleTreeError> {
+        if !MERKLE_TREE_ARITIES.contains(&arity) {
+            return Err(MerkleTreeError::InvalidArity(arity));
+        }
+        if num_leaves < 2 {
+            return Err(MerkleTreeError::TooFewLeaves(2, num_leaves));
+        }
+        if !num_leaves.is_power_of_two() {
+            return Err(MerkleTreeError::NumberOfLeavesNotPowerOfTwo(num_leaves));
+        }
+
+        let fan_ins = level_fan_ins(log2(num_leaves) as usize, arity);
+
+        // pre-allocate memory for all levels of the tree
+        let mut levels = Vec::with_capacity(fan_ins.len());
+        let mut level_size = num_leaves;
+        for &fan_in in fan_ins.iter() {
+            level_size /= fan_in;
+            levels.push(Vec::with_capacity(level_size));
+        }
+
+        Ok(Self {
+            num_leaves,
+            arity,
+            fan_ins,
+            leaves: Vec::with_capacity(num_leaves),
+            levels,
+        })
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of leaves in the tree being built.
+    pub fn num_leaves(&self) -> usize {
+        self.num_leaves
+    }
+
+    /// Returns the number of leaves which have been added to this builder so far.
+    pub fn num_pushed_leaves(&self) -> usize {
+        self.leaves.len()
+    }
+
+    // BUILDING METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Appends the specified leaf to the tree, and computes all internal nodes for which this
+    /// leaf is the last missing descendant.
+    ///
+    /// # Errors
+    /// Returns an error if all leaves of the tree have already been added.
+    pub fn push(&mut self, leaf: H::Digest) -> Result<(), MerkleTreeError> {
+        if self.leaves.len() == self.num_leaves {
+            return Err(MerkleTreeError::LeafIndexOutOfBounds(
+                self.num_leaves,
+                self.leaves.len(),
+            ));
+        }
+        self.leaves.push(leaf);
+
+        // move up the tree for as long as the last node at the current level completes a group
+        // of siblings
+        let mut children = &self.leaves;
+        for (&fan_in, level) in self.fan_ins.iter().zip(self.levels.iter_mut()) {
+            if children.len() % fan_in != 0 {
+                break;
+            }
+            level.push(H::merge_many(&children[children.len() - fan_in..]));
+            children = &*level;
+        }
+
+        Ok(())
+    }
+
+    /// Appends all of the specified leaves to the tree.
+    ///
+    /// # Errors
+    /// Returns an error if adding the leaves would exceed the number of leaves in the tree; in
+    /// such a case, the leaves which fit into the tree are still added.
+    pub fn push_leaves(&mut self, leaves: &[H::Digest]) -> Result<(), MerkleTreeError> {
+        for &leaf in leaves {
+            self.push(leaf)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the Merkle tree built from all leaves added to this builder.
+    ///
+    /// # Errors
+    /// Returns an error if fewer leaves than specified at construction time have been added.
+    pub fn finalize(self) -> Result<MerkleTree<H>, MerkleTreeError> {
+        if self.leaves.len() != self.num_leaves {
+            return Err(MerkleTreeError::TooFewLeaves(
+                self.num_leaves,
+                self.leaves.len(),
+            ));
+        }
+
+        // assemble internal nodes into the same layout as the one used by the tree itself: the
+        // first node is unused, and it is followed by all levels of the tree from the top down
+        let num_nodes = self.levels.iter().map(|level| level.len()).sum::<usize>() + 1;
+        let mut nodes = Vec::with_capacity(num_nodes);
+        nodes.push(H::Digest::default());
+        for level in self.levels.into_iter().rev() {
+            nodes.extend_from_slice(&level);
+        }
+
+        Ok(MerkleTree {
+            nodes,
+            leaves: self.leaves,
+            arity: self.arity,
+        })
+    }
+}
@@ -10,7 +10,9 @@ use utils::{collections::Vec, iterators::*, rayon};
 // CONSTANTS
 // ================================================================================================
 
-pub const MIN_CONCURRENT_LEAVES: usize = 1024;
+/// Number of nodes passed to a single invocation of [Hasher::merge_batch()] when leaves are
+/// merged in parallel.
+const MERGE_BATCH_SIZE: usize = 64;
 
 // PUBLIC FUNCTIONS
 // ================================================================================================
@@ -21,6 +23,13 @@ pub const MIN_CONCURRENT_LEAVES: usize = 1024;
 pub fn build_merkle_nodes<H: Hasher>(leaves: &[H::Digest]) -> Vec<H::Digest> {
     let n = leaves.len() / 2;
 
+    // number of sub-trees must always be a power of 2; if there are fewer parents of leaves than
+    // sub-trees, the tree is too small to be split between threads
+    let num_subtrees = rayon::current_num_threads().next_power_of_two();
+    if n < num_subtrees {
+        return super::build_merkle_nodes::<H>(leaves);
+    }
+
     // create un-initialized array to hold all intermediate nodes
     let mut nodes = unsafe { utils::uninit_vector::<H::Digest>(2 * n) };
     nodes[0] = H::Digest::default();
@@ -29,15 +38,12 @@ pub fn build_merkle_nodes<H: Hasher>(leaves: &[H::Digest]) -> Vec<H::Digest> {
     // build first row of internal nodes (parents of leaves)
     let two_leaves = unsafe { slice::from_raw_parts(leaves.as_ptr() as *const [H::Digest; 2], n) };
     nodes[n..]
-        .par_iter_mut()
-        .zip(two_leaves.par_iter())
-        .for_each(|(target, source)| *target = H::merge(source));
+        .par_chunks_mut(MERGE_BATCH_SIZE)
+        .zip(two_leaves.par_chunks(MERGE_BATCH_SIZE))
+        .for_each(|(target, source)| H::merge_batch(source, target));
 
     // calculate all other tree nodes, we can't use regular iterators  here because
     // access patterns are rather complicated - so, we use regular threads instead
-
-    // number of sub-trees must always be a power of 2
-    let num_subtrees = rayon::current_num_threads().next_power_of_two();
     let batch_size = n / num_subtrees;
 
     // re-interpret nodes as an array of two nodes fused together
@@ -51,9 +57,8 @@ pub fn build_merkle_nodes<H: Hasher>(leaves: &[H::Digest]) -> Vec<H::Digest> {
                 let mut batch_size = batch_size / 2;
                 let mut start_idx = n / 2 + batch_size * i;
                 while start_idx >= num_subtrees {
-                    for k in (start_idx..(start_idx + batch_size)).rev() {
-                        nodes[k] = H::merge(&two_nodes[k]);
-                    }
+                    let range = start_idx..(start_idx + batch_size);
+                    H::merge_batch(&two_nodes[range.clone()], &mut nodes[range]);
                     start_idx /= 2;
                     batch_size /= 2;
                 }
@@ -69,6 +74,12 @@ pub fn build_merkle_nodes<H: Hasher>(leaves: &[H::Digest]) -> Vec<H::Digest> {
     nodes
 }
 
+/// Hashes each consecutive group of `fan_in` nodes into a parent node using all available
+/// threads.
+pub fn merge_level<H: Hasher>(nodes: &[H::Digest], fan_in: usize) -> Vec<H::Digest> {
+    nodes.par_chunks(fan_in).map(H::merge_many).collect()
+}
+
 // TESTS
 // ================================================================================================
 
@@ -88,4 +99,23 @@ mod tests {
             assert_eq!(concurrent, sequential);
         }
     }
+
+    #[test]
+    fn build_merkle_nodes_concurrent_small_trees() {
+        // trees with fewer parents of leaves than threads should be built correctly
+        let data = (0..32u8).map(|i| [i; 32]).collect::<Vec<_>>();
+        utils::rayon::ThreadPoolBuilder::new()
+            .num_threads(16)
+            .build()
+            .unwrap()
+            .install(|| {
+                for num_leaves in [2, 4, 8, 16, 32] {
+                    let leaves = ByteDigest::bytes_as_digests(&data[..num_leaves]).to_vec();
+                    let sequential =
+                        super::super::build_merkle_nodes::<Sha3_256<BaseElement>>(&leaves);
+                    let concurrent = super::build_merkle_nodes::<Sha3_256<BaseElement>>(&leaves);
+                    assert_eq!(concurrent, sequential);
+                }
+            });
+    }
 }
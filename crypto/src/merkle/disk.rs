@@ -0,0 +1,219 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{map_indexes, BatchMerkleProof};
+use crate::{errors::MerkleTreeError, hash::Hasher};
+use math::log2;
+use memmap2::MmapMut;
+use std::fs::File;
+use utils::{collections::Vec, string::ToString, Deserializable, Serializable, SliceReader};
+
+// DISK-BACKED MERKLE TREE
+// ================================================================================================
+
+/// A fully-balanced binary Merkle tree with lower levels stored in a memory-mapped file.
+///
+/// For trees with a very large number of leaves (e.g., 2^26 or more), keeping all nodes of the
+/// tree in RAM may be prohibitively expensive. This tree keeps only the specified number of top
+/// levels in memory, while the leaves and all other internal nodes are written into the provided
+/// file. Generating authentication paths then requires reading only a few nodes from the file
+/// for each path.
+///
+/// The nodes are laid out in the same way as in [MerkleTree](super::MerkleTree): the root is at
+/// position 1, its children are at positions 2 and 3 etc., and the leaves occupy the last half of
+/// all positions. Nodes at positions smaller than `2^in_memory_depth` are kept in memory, and
+/// all other nodes are stored in the file. Thus, the root, proofs, and batch proofs produced by
+/// this tree are the same as the ones produced by [MerkleTree](super::MerkleTree) built from the
+/// same leaves, and can be verified using [MerkleTree::verify()](super::MerkleTree::verify) and
+/// [MerkleTree::verify_batch()](super::MerkleTree::verify_batch) functions.
+///
+/// This tree is available only when the crate is compiled with `mmap` feature enabled.
+pub struct DiskMerkleTree<H: Hasher> {
+    nodes: Vec<H::Digest>,
+    storage: MmapMut,
+    depth: usize,
+    digest_size: usize,
+}
+
+impl<H: Hasher> DiskMerkleTree<H> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new Merkle tree built from the provided leaves, with all but the top
+    /// `in_memory_depth` levels of the tree stored in the specified `file`.
+    ///
+    /// The `file` must be opened for both reading and writing; its existing content (if any) is
+    /// overwritten. Leaves are consumed from the `leaves` iterator one at a time, and thus, they
+    /// do not need to be kept in memory.
+    ///
+    /// The `file` is memory-mapped, and it must not be modified or truncated by anyone else
+    /// while the returned tree is alive.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * Fewer than two leaves were provided.
+    /// * Number of leaves is not a power of two.
+    /// * `in_memory_depth` is zero or is greater than the depth of the tree.
+    /// * The file could not be resized or memory-mapped.
+    pub fn new<I>(leaves: I, in_memory_depth: usize, file: &File) -> Result<Self, MerkleTreeError>
+    where
+        I: ExactSizeIterator<Item = H::Digest>,
+    {
+        let num_leaves = leaves.len();
+        if num_leaves < 2 {
+            return Err(MerkleTreeError::TooFewLeaves(2, num_leaves));
+        }
+        if !num_leaves.is_power_of_two() {
+            return Err(MerkleTreeError::NumberOfLeavesNotPowerOfTwo(num_leaves));
+        }
+        let depth = log2(num_leaves) as usize;
+        if in_memory_depth == 0 || in_memory_depth > depth {
+            return Err(MerkleTreeError::StorageError(format!(
+                "number of in-memory levels must be between 1 and {depth}, but was {in_memory_depth}"
+            )));
+        }
+
+        // allocate and map the file; it holds all nodes at positions 2^in_memory_depth and above
+        let digest_size = H::Digest::default().to_bytes().len();
+        let num_stored_nodes = 2 * num_leaves - (1 << in_memory_depth);
+        file.set_len((num_stored_nodes * digest_size) as u64)
+            .map_err(|err| MerkleTreeError::StorageError(err.to_string()))?;
+        // SAFETY: the mapping is only sound as long as no other process or handle truncates or
+        // writes to the file while the tree is alive; doing so would change memory which we read
+        // through a shared reference (or cause SIGBUS on truncation). The file was just resized
+        // by us, and callers must treat it as owned by the tree for the tree's lifetime.
+        let storage = unsafe { MmapMut::map_mut(file) }
+            .map_err(|err| MerkleTreeError::StorageError(err.to_string()))?;
+
+        let mut tree = DiskMerkleTree {
+            nodes: vec![H::Digest::default(); 1 << in_memory_depth],
+            storage,
+            depth,
+            digest_size,
+        };
+
+        // write the leaves, and then compute all internal nodes from the bottom up
+        for (i, leaf) in leaves.enumerate() {
+            tree.set_node(num_leaves + i, leaf);
+        }
+        for i in (1..num_leaves).rev() {
+            let parent = H::merge(&[tree.get_node(2 * i)?, tree.get_node(2 * i + 1)?]);
+            tree.set_node(i, parent);
+        }
+        tree.storage
+            .flush()
+            .map_err(|err| MerkleTreeError::StorageError(err.to_string()))?;
+
+        Ok(tree)
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the root of the tree.
+    pub fn root(&self) -> &H::Digest {
+        &self.nodes[1]
+    }
+
+    /// Returns depth of the tree.
+    ///
+    /// The depth of a tree is zero-based. Thus, a tree with two leaves has depth 1, a tree with
+    /// four leaves has depth 2 etc.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Returns the number of leaves in the tree.
+    pub fn num_leaves(&self) -> usize {
+        1 << self.depth
+    }
+
+    /// Returns a leaf at the specified `index`.
+    ///
+    /// # Errors
+    /// Returns an error if the specified index is greater than or equal to the number of leaves
+    /// in the tree, or if the leaf could not be read from the file.
+    pub fn get_leaf(&self, index: usize) -> Result<H::Digest, MerkleTreeError> {
+        if index >= self.num_leaves() {
+            return Err(MerkleTreeError::LeafIndexOutOfBounds(
+                self.num_leaves(),
+                index,
+            ));
+        }
+        self.get_node(self.num_leaves() + index)
+    }
+
+    // PROVING METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a Merkle path to a leaf at the specified `index`.
+    ///
+    /// The leaf itself will be the first element in the path.
+    ///
+    /// # Errors
+    /// Returns an error if the specified index is greater than or equal to the number of leaves
+    /// in the tree, or if any of the nodes could not be read from the file.
+    pub fn prove(&self, index: usize) -> Result<Vec<H::Digest>, MerkleTreeError> {
+        let mut proof = vec![self.get_leaf(index)?];
+
+        let mut index = index + self.num_leaves();
+        while index > 1 {
+            proof.push(self.get_node(index ^ 1)?);
+            index >>= 1;
+        }
+
+        Ok(proof)
+    }
+
+    /// Computes Merkle paths for the provided indexes and compresses the paths into a single proof.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * No indexes were provided (i.e., `indexes` is an empty slice).
+    /// * Any of the provided indexes are greater than or equal to the number of leaves in the
+    ///   tree.
+    /// * List of indexes contains duplicates.
+    /// * Any of the nodes could not be read from the file.
+    pub fn prove_batch(&self, indexes: &[usize]) -> Result<BatchMerkleProof<H>, MerkleTreeError> {
+        if indexes.is_empty() {
+            return Err(MerkleTreeError::TooFewLeafIndexes);
+        }
+        map_indexes(indexes, self.depth)?;
+
+        let paths = indexes
+            .iter()
+            .map(|&index| self.prove(index))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut proof = BatchMerkleProof::from_paths(&paths, indexes)?;
+
+        // paths are aggregated in the order of their indexes, but leaves in the proof are
+        // expected to be in the same order as the provided indexes
+        proof.leaves = paths.iter().map(|path| path[0]).collect();
+        Ok(proof)
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the node at the specified position, reading it from the file if needed.
+    fn get_node(&self, position: usize) -> Result<H::Digest, MerkleTreeError> {
+        if position < self.nodes.len() {
+            return Ok(self.nodes[position]);
+        }
+        let offset = (position - self.nodes.len()) * self.digest_size;
+        let mut reader = SliceReader::new(&self.storage[offset..offset + self.digest_size]);
+        H::Digest::read_from(&mut reader)
+            .map_err(|err| MerkleTreeError::StorageError(err.to_string()))
+    }
+
+    /// Sets the node at the specified position, writing it into the file if needed.
+    fn set_node(&mut self, position: usize, node: H::Digest) {
+        if position < self.nodes.len() {
+            self.nodes[position] = node;
+        } else {
+            let offset = (position - self.nodes.len()) * self.digest_size;
+            self.storage[offset..offset + self.digest_size].copy_from_slice(&node.to_bytes());
+        }
+    }
+}
@@ -3,17 +3,44 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::{errors::MerkleTreeError, hash::Hasher};
+use crate::{
+    errors::MerkleTreeError,
+    hash::{Hashable, Hasher},
+    VectorCommitment,
+};
 use core::slice;
 use math::log2;
-use utils::collections::{BTreeMap, BTreeSet, Vec};
+use utils::{
+    collections::{BTreeMap, BTreeSet, Vec},
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+};
+
+#[cfg(feature = "concurrent")]
+use utils::ConcurrencyConfig;
+
+mod builder;
+pub use builder::MerkleTreeBuilder;
 
 mod proofs;
 pub use proofs::BatchMerkleProof;
 
+mod partial;
+pub use partial::PartialMerkleTree;
+
+mod partitioned;
+pub use partitioned::{PartitionedMerkleProof, PartitionedMerkleTree};
+
+mod salted;
+pub use salted::SaltedMerkleTree;
+
 #[cfg(feature = "concurrent")]
 pub mod concurrent;
 
+#[cfg(feature = "mmap")]
+mod disk;
+#[cfg(feature = "mmap")]
+pub use disk::DiskMerkleTree;
+
 #[cfg(test)]
 mod tests;
 
@@ -37,14 +64,26 @@ mod tests;
 /// #   #   #   #  <- values
 /// ```
 ///
-/// A tree can be built from a slice of leaves using [MerkleTree::new()] function. Thus, the user
-/// is responsible for performing the first level of hashing (i.e., hashing values into leaf
-/// nodes). The number of leaves must always be a power of two so that the tree is fully balanced,
-/// and a tree must contain at least two leaves.
+/// A tree can be built from a vector of leaves using [MerkleTree::new()] function. By default,
+/// leaves are digests, and thus, the user is responsible for performing the first level of
+/// hashing (i.e., hashing values into leaf nodes). Alternatively, the tree can be built directly
+/// from values of any type `L` which implements [Hashable] (e.g., rows of field elements); in this
+/// case, the values are hashed into leaf nodes as defined by the [Hashable] implementation, and
+/// the tree keeps the values themselves. Leaves can also be supplied one at a time via
+/// [MerkleTreeBuilder]. The number of leaves must always be a power of two so that the tree is
+/// fully balanced, and a tree must contain at least two leaves.
 ///
 /// The depth of a tree is zero-based. Thus, a tree with two leaves has depth 1, a tree with four
 /// leaves has depth 2 etc.
 ///
+/// By default, the tree is binary. A tree with a higher arity (4, 8, or 16) can be built using
+/// [MerkleTree::with_arity()] function. In such a tree, each internal node is computed by hashing
+/// `arity` children together via [Hasher::merge_many()]. If the depth of the tree is not a
+/// multiple of `log2(arity)`, the level directly under the root has a smaller fan-in (e.g., an
+/// 8-ary tree with 16 leaves has a level of 8-node groups under a root with 2 children). Higher
+/// arity reduces the number of hash invocations along a Merkle path at the expense of larger
+/// authentication paths.
+///
 /// When the crate is compiled with `concurrent` feature enabled, tree construction will be
 /// performed in multiple threads (usually, as many threads as there are logical cores on the
 /// machine). The number of threads can be configured via `RAYON_NUM_THREADS` environment variable.
@@ -60,7 +99,7 @@ mod tests;
 ///
 /// # Examples
 /// ```
-/// # use winter_crypto::{MerkleTree, Hasher, hashers::Blake3_256};
+/// # use winter_crypto::{MerkleTree, ElementHasher, Hasher, hashers::Blake3_256};
 /// # use math::fields::f128::BaseElement;
 /// type Blake3 = Blake3_256::<BaseElement>;
 ///
@@ -83,29 +122,65 @@ mod tests;
 /// // verify proof
 /// assert!(MerkleTree::<Blake3>::verify(*tree.root(), 2, &proof).is_ok());
 /// assert!(MerkleTree::<Blake3>::verify(*tree.root(), 1, &proof).is_err());
+///
+/// // build a tree from rows of field elements; the leaves are the hashes of the rows
+/// let rows = (0..4u128)
+///     .map(|i| vec![BaseElement::new(i), BaseElement::new(i + 1)])
+///     .collect::<Vec<_>>();
+/// let tree = MerkleTree::<Blake3, Vec<BaseElement>>::new(rows.clone()).unwrap();
+/// let proof = tree.prove(2).unwrap();
+/// assert_eq!(Blake3::hash_elements(&rows[2]), proof[0]);
+/// assert!(MerkleTree::<Blake3>::verify(*tree.root(), 2, &proof).is_ok());
 /// ```
 #[derive(Debug)]
-pub struct MerkleTree<H: Hasher> {
+pub struct MerkleTree<H: Hasher, L = <H as Hasher>::Digest> {
     nodes: Vec<H::Digest>,
-    leaves: Vec<H::Digest>,
+    leaves: Vec<L>,
+    arity: usize,
 }
 
+// CONSTANTS
+// ================================================================================================
+
+/// Arities supported by [MerkleTree]; arity 2 corresponds to a binary tree.
+pub const MERKLE_TREE_ARITIES: [usize; 4] = [2, 4, 8, 16];
+
+/// Maximum depth of a [MerkleTree] which can be read from a byte source.
+pub const MAX_SERIALIZED_TREE_DEPTH: usize = 32;
+
 // MERKLE TREE IMPLEMENTATION
 // ================================================================================================
 
-impl<H: Hasher> MerkleTree<H> {
+impl<H: Hasher, L: Hashable<H>> MerkleTree<H, L> {
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
-    /// Returns new Merkle tree built from the provide leaves using hash function specified by the
-    /// `H` generic parameter.
+    /// Returns new binary Merkle tree built from the provide leaves using hash function specified
+    /// by the `H` generic parameter.
+    ///
+    /// When `concurrent` feature is enabled, the tree is built using multiple threads.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * Fewer than two leaves were provided.
+    /// * Number of leaves is not a power of two.
+    pub fn new(leaves: Vec<L>) -> Result<Self, MerkleTreeError> {
+        Self::with_arity(leaves, 2)
+    }
+
+    /// Returns new Merkle tree with the specified `arity` built from the provide leaves using
+    /// hash function specified by the `H` generic parameter.
     ///
     /// When `concurrent` feature is enabled, the tree is built using multiple threads.
     ///
     /// # Errors
     /// Returns an error if:
+    /// * `arity` is not one of 2, 4, 8, or 16.
     /// * Fewer than two leaves were provided.
     /// * Number of leaves is not a power of two.
-    pub fn new(leaves: Vec<H::Digest>) -> Result<Self, MerkleTreeError> {
+    pub fn with_arity(leaves: Vec<L>, arity: usize) -> Result<Self, MerkleTreeError> {
+        if !MERKLE_TREE_ARITIES.contains(&arity) {
+            return Err(MerkleTreeError::InvalidArity(arity));
+        }
         if leaves.len() < 2 {
             return Err(MerkleTreeError::TooFewLeaves(2, leaves.len()));
         }
@@ -113,17 +188,32 @@ impl<H: Hasher> MerkleTree<H> {
             return Err(MerkleTreeError::NumberOfLeavesNotPowerOfTwo(leaves.len()));
         }
 
+        // when leaves are digests already, they are used to build the tree without being copied
+        let digests = L::hash_leaves(&leaves);
+        if arity != 2 {
+            let nodes = build_kary_merkle_nodes::<H>(&digests, arity);
+            return Ok(MerkleTree {
+                nodes,
+                leaves,
+                arity,
+            });
+        }
+
         #[cfg(not(feature = "concurrent"))]
-        let nodes = build_merkle_nodes::<H>(&leaves);
+        let nodes = build_merkle_nodes::<H>(&digests);
 
         #[cfg(feature = "concurrent")]
-        let nodes = if leaves.len() <= concurrent::MIN_CONCURRENT_LEAVES {
-            build_merkle_nodes::<H>(&leaves)
+        let nodes = if digests.len() <= ConcurrencyConfig::global().min_merkle_leaves() {
+            build_merkle_nodes::<H>(&digests)
         } else {
-            concurrent::build_merkle_nodes::<H>(&leaves)
+            concurrent::build_merkle_nodes::<H>(&digests)
         };
 
-        Ok(MerkleTree { nodes, leaves })
+        Ok(MerkleTree {
+            nodes,
+            leaves,
+            arity,
+        })
     }
 
     // PUBLIC ACCESSORS
@@ -142,17 +232,35 @@ impl<H: Hasher> MerkleTree<H> {
         log2(self.leaves.len()) as usize
     }
 
-    /// Returns leaf nodes of the tree.
-    pub fn leaves(&self) -> &[H::Digest] {
+    /// Returns the arity of the tree (i.e., the maximum number of children of an internal node).
+    pub fn arity(&self) -> usize {
+        self.arity
+    }
+
+    /// Returns leaves of the tree.
+    ///
+    /// If the tree was built from values other than digests, these are the values, rather than
+    /// their digests.
+    pub fn leaves(&self) -> &[L] {
         &self.leaves
     }
 
+    /// Returns the digest of the leaf at the specified `index`.
+    ///
+    /// # Panics
+    /// Panics if `index` is greater than or equal to the number of leaves in the tree.
+    pub fn leaf_digest(&self, index: usize) -> H::Digest {
+        self.leaves[index].hash_leaf()
+    }
+
     // PROVING METHODS
     // --------------------------------------------------------------------------------------------
 
     /// Returns a Merkle path to a leaf at the specified `index`.
     ///
-    /// The leaf itself will be the first element in the path.
+    /// The leaf itself will be the first element in the path. For trees with arity greater than
+    /// 2, the leaf is followed by all siblings of the node on each level of the tree (from the
+    /// leaf level up), listed in the order of their positions.
     ///
     /// # Errors
     /// Returns an error if the specified index is greater than or equal to the number of leaves
@@ -165,7 +273,11 @@ impl<H: Hasher> MerkleTree<H> {
             ));
         }
 
-        let mut proof = vec![self.leaves[index], self.leaves[index ^ 1]];
+        if self.arity != 2 {
+            return Ok(self.prove_kary(index));
+        }
+
+        let mut proof = vec![self.leaf_digest(index), self.leaf_digest(index ^ 1)];
 
         let mut index = (index + self.nodes.len()) >> 1;
         while index > 1 {
@@ -181,7 +293,6 @@ impl<H: Hasher> MerkleTree<H> {
     /// # Errors
     /// Returns an error if:
     /// * No indexes were provided (i.e., `indexes` is an empty slice).
-    /// * Number of provided indexes is greater than 255.
     /// * Any of the provided indexes are greater than or equal to the number of leaves in the
     ///   tree.
     /// * List of indexes contains duplicates.
@@ -189,14 +300,12 @@ impl<H: Hasher> MerkleTree<H> {
         if indexes.is_empty() {
             return Err(MerkleTreeError::TooFewLeafIndexes);
         }
-        if indexes.len() > proofs::MAX_PATHS {
-            return Err(MerkleTreeError::TooManyLeafIndexes(
-                proofs::MAX_PATHS,
-                indexes.len(),
-            ));
-        }
 
         let index_map = map_indexes(indexes, self.depth())?;
+        if self.arity != 2 {
+            return Ok(self.prove_batch_kary(indexes));
+        }
+
         let indexes = normalize_indexes(indexes);
         let mut leaves = vec![H::Digest::default(); index_map.len()];
         let mut nodes: Vec<Vec<H::Digest>> = Vec::with_capacity(indexes.len());
@@ -207,7 +316,7 @@ impl<H: Hasher> MerkleTree<H> {
         for index in indexes {
             let missing: Vec<H::Digest> = (index..index + 2)
                 .flat_map(|i| {
-                    let v = self.leaves[i];
+                    let v = self.leaf_digest(i);
                     if let Some(idx) = index_map.get(&i) {
                         leaves[*idx] = v;
                         None
@@ -246,26 +355,157 @@ impl<H: Hasher> MerkleTree<H> {
             leaves,
             nodes,
             depth: self.depth() as u8,
+            arity: 2,
         })
     }
 
+    /// Returns indexes of the leaves opened by the batch `proof` which do not match the leaves
+    /// of this tree at the specified `indexes`.
+    ///
+    /// The indexes are returned in the same order as they appear in `indexes`. This is useful
+    /// for debugging: an empty list means that all opened leaves are valid, and thus, if the
+    /// proof still fails verification against the root of this tree, the error is caused by the
+    /// internal nodes of the proof.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * Any of the provided indexes are greater than or equal to the number of leaves in the
+    ///   tree.
+    /// * The number of leaves in the proof is not the same as the number of indexes.
+    pub fn find_invalid_leaves(
+        &self,
+        indexes: &[usize],
+        proof: &BatchMerkleProof<H>,
+    ) -> Result<Vec<usize>, MerkleTreeError> {
+        if let Some(&index) = indexes.iter().find(|&&index| index >= self.leaves.len()) {
+            return Err(MerkleTreeError::LeafIndexOutOfBounds(
+                self.leaves.len(),
+                index,
+            ));
+        }
+        let expected = indexes
+            .iter()
+            .map(|&i| self.leaf_digest(i))
+            .collect::<Vec<_>>();
+        match check_leaves::<H>(indexes, &expected, &proof.leaves) {
+            Ok(()) => Ok(Vec::new()),
+            Err(MerkleTreeError::InvalidLeaves(invalid)) => Ok(invalid),
+            Err(err) => Err(err),
+        }
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the node at the specified `index` within the specified level of a tree with arity
+    /// greater than 2; level 0 contains the leaf digests and the last level contains only the
+    /// root.
+    fn level_node(&self, level: usize, index: usize) -> H::Digest {
+        if level == 0 {
+            return self.leaf_digest(index);
+        }
+
+        // levels are stored top-down, so the first level above the leaves is at the very end
+        let fan_ins = level_fan_ins(self.depth(), self.arity);
+        let mut size = self.leaves.len();
+        let mut end = self.nodes.len();
+        for (i, &fan_in) in fan_ins[..level].iter().enumerate() {
+            if i > 0 {
+                end -= size;
+            }
+            size /= fan_in;
+        }
+        self.nodes[end - size + index]
+    }
+
+    /// Returns a Merkle path to a leaf at the specified `index` in a tree with arity greater
+    /// than 2; the index is assumed to be valid.
+    fn prove_kary(&self, index: usize) -> Vec<H::Digest> {
+        let mut proof = vec![self.leaf_digest(index)];
+        let mut index = index;
+        for (level, fan_in) in level_fan_ins(self.depth(), self.arity)
+            .into_iter()
+            .enumerate()
+        {
+            let start = index - index % fan_in;
+            proof.extend(
+                (start..start + fan_in)
+                    .filter(|&i| i != index)
+                    .map(|i| self.level_node(level, i)),
+            );
+            index /= fan_in;
+        }
+        proof
+    }
+
+    /// Computes a batch Merkle proof for the specified indexes in a tree with arity greater
+    /// than 2; the indexes are assumed to be valid.
+    ///
+    /// In the resulting proof, `nodes` contain a vector per tree level with all nodes which are
+    /// needed to compute the next level but cannot be derived from the proven leaves, listed in
+    /// the order of their positions.
+    fn prove_batch_kary(&self, indexes: &[usize]) -> BatchMerkleProof<H> {
+        let leaves = indexes.iter().map(|&i| self.leaf_digest(i)).collect();
+        let fan_ins = level_fan_ins(self.depth(), self.arity);
+        let mut nodes = Vec::with_capacity(fan_ins.len());
+
+        let mut positions: BTreeSet<usize> = indexes.iter().cloned().collect();
+        for (level, &fan_in) in fan_ins.iter().enumerate() {
+            let parents: BTreeSet<usize> = positions.iter().map(|&p| p / fan_in).collect();
+
+            let mut missing = Vec::new();
+            for &parent in parents.iter() {
+                let start = parent * fan_in;
+                missing.extend(
+                    (start..start + fan_in)
+                        .filter(|i| !positions.contains(i))
+                        .map(|i| self.level_node(level, i)),
+                );
+            }
+            nodes.push(missing);
+            positions = parents;
+        }
+
+        BatchMerkleProof {
+            leaves,
+            nodes,
+            depth: self.depth() as u8,
+            arity: self.arity as u8,
+        }
+    }
+}
+
+impl<H: Hasher> MerkleTree<H> {
     // VERIFICATION METHODS
     // --------------------------------------------------------------------------------------------
 
     /// Checks whether the `proof` for the specified `index` is valid.
     ///
     /// # Errors
-    /// Returns an error if the specified `proof` (which is a Merkle path) does not resolve to the
-    /// specified `root`.
+    /// Returns an error if:
+    /// * The `proof` contains fewer than two nodes or is too long to describe a valid tree.
+    /// * The specified `index` is greater than or equal to the number of leaves in a tree of the
+    ///   depth implied by the length of the `proof`.
+    /// * The specified `proof` (which is a Merkle path) does not resolve to the specified `root`.
     pub fn verify(
         root: H::Digest,
         index: usize,
         proof: &[H::Digest],
     ) -> Result<(), MerkleTreeError> {
+        if proof.len() < 2 {
+            return Err(MerkleTreeError::InvalidPathLength(proof.len()));
+        }
+        let num_leaves = 1usize
+            .checked_shl((proof.len() - 1) as u32)
+            .ok_or(MerkleTreeError::InvalidPathLength(proof.len()))?;
+        if index >= num_leaves {
+            return Err(MerkleTreeError::LeafIndexOutOfBounds(num_leaves, index));
+        }
+
         let r = index & 1;
         let mut v = H::merge(&[proof[r], proof[1 - r]]);
 
-        let mut index = (index + 2usize.pow((proof.len() - 1) as u32)) >> 1;
+        let mut index = (index + num_leaves) >> 1;
         for &p in proof.iter().skip(2) {
             v = if index & 1 == 0 {
                 H::merge(&[v, p])
@@ -281,16 +521,65 @@ impl<H: Hasher> MerkleTree<H> {
         Ok(())
     }
 
+    /// Checks whether the `proof` for the specified `index` is valid for a tree of the specified
+    /// `arity`.
+    ///
+    /// The depth of the tree is inferred from the length of the `proof`.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * `arity` is not one of 2, 4, 8, or 16.
+    /// * The length of the `proof` does not correspond to a valid tree of the specified `arity`.
+    /// * The specified `index` is greater than or equal to the number of leaves in such a tree.
+    /// * The specified `proof` (which is a Merkle path) does not resolve to the specified `root`.
+    pub fn verify_with_arity(
+        root: H::Digest,
+        index: usize,
+        proof: &[H::Digest],
+        arity: usize,
+    ) -> Result<(), MerkleTreeError> {
+        if !MERKLE_TREE_ARITIES.contains(&arity) {
+            return Err(MerkleTreeError::InvalidArity(arity));
+        }
+        let depth = path_depth(proof.len(), arity)
+            .ok_or(MerkleTreeError::InvalidPathLength(proof.len()))?;
+        if index >= 1 << depth {
+            return Err(MerkleTreeError::LeafIndexOutOfBounds(1 << depth, index));
+        }
+
+        let mut v = proof[0];
+        let mut index = index;
+        let mut siblings = &proof[1..];
+        let mut buf = Vec::with_capacity(arity);
+        for fan_in in level_fan_ins(depth, arity) {
+            buf.clear();
+            buf.extend_from_slice(&siblings[..fan_in - 1]);
+            buf.insert(index % fan_in, v);
+            v = H::merge_many(&buf);
+            siblings = &siblings[fan_in - 1..];
+            index /= fan_in;
+        }
+
+        if v != root {
+            return Err(MerkleTreeError::InvalidProof);
+        }
+        Ok(())
+    }
+
     /// Checks whether the batch proof contains Merkle paths for the of the specified `indexes`.
     ///
     /// # Errors
     /// Returns an error if:
     /// * No indexes were provided (i.e., `indexes` is an empty slice).
-    /// * Number of provided indexes is greater than 255.
     /// * Any of the specified `indexes` is greater than or equal to the number of leaves in the
     ///   tree from which the batch proof was generated.
     /// * List of indexes contains duplicates.
     /// * Any of the paths in the batch proof does not resolve to the specified `root`.
+    ///
+    /// Paths in a batch proof share internal nodes, and thus, an invalid leaf affects the nodes
+    /// computed for all other paths. Because of this, a failed check cannot be attributed to
+    /// individual leaves using only the root; to pinpoint invalid leaves, opened leaves can be
+    /// compared against the expected ones (e.g., via [MerkleTree::find_invalid_leaves()]).
     pub fn verify_batch(
         root: &H::Digest,
         indexes: &[usize],
@@ -303,9 +592,160 @@ impl<H: Hasher> MerkleTree<H> {
     }
 }
 
+// SERIALIZATION / DESERIALIZATION
+// ================================================================================================
+
+impl<H: Hasher, L: Hashable<H> + Serializable> Serializable for MerkleTree<H, L> {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    ///
+    /// All nodes of the tree are written, and thus, the tree can be read back without
+    /// recomputing any hashes.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(self.arity as u8);
+        target.write_u8(self.depth() as u8);
+        target.write(&self.leaves[..]);
+        target.write(&self.nodes[1..]);
+    }
+}
+
+impl<H: Hasher, L: Hashable<H> + Deserializable> Deserializable for MerkleTree<H, L> {
+    /// Reads a Merkle tree from the specified `source`.
+    ///
+    /// Internal nodes are not recomputed from the leaves, and thus, trees should be read only from
+    /// trusted storage (e.g., a file written by the same party). Trees deeper than
+    /// [MAX_SERIALIZED_TREE_DEPTH] are rejected before any memory is allocated for them.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let arity = source.read_u8()? as usize;
+        if !MERKLE_TREE_ARITIES.contains(&arity) {
+            return Err(DeserializationError::InvalidValue(format!(
+                "Merkle tree arity must be 2, 4, 8, or 16, but was {arity}"
+            )));
+        }
+        let depth = source.read_u8()? as usize;
+        if depth == 0 || depth > MAX_SERIALIZED_TREE_DEPTH {
+            return Err(DeserializationError::InvalidValue(format!(
+                "Merkle tree depth must be between 1 and {MAX_SERIALIZED_TREE_DEPTH}, but was {depth}"
+            )));
+        }
+
+        let num_leaves = 1 << depth;
+        let leaves = L::read_batch_from(source, num_leaves)?;
+        let num_nodes = level_fan_ins(depth, arity)
+            .iter()
+            .scan(num_leaves, |size, &fan_in| {
+                *size /= fan_in;
+                Some(*size)
+            })
+            .sum::<usize>();
+        let mut nodes = vec![H::Digest::default()];
+        nodes.append(&mut H::Digest::read_batch_from(source, num_nodes)?);
+
+        Ok(MerkleTree {
+            nodes,
+            leaves,
+            arity,
+        })
+    }
+}
+
+// VECTOR COMMITMENT IMPLEMENTATION
+// ================================================================================================
+
+/// Merkle tree implementation of a vector commitment.
+///
+/// Options specify the arity of the tree. Both single and multiple openings are proven using
+/// [BatchMerkleProof] since, unlike plain Merkle paths, such proofs carry the arity and the depth
+/// of the tree.
+impl<H: Hasher> VectorCommitment<H> for MerkleTree<H> {
+    type Options = usize;
+    type Proof = BatchMerkleProof<H>;
+    type MultiProof = BatchMerkleProof<H>;
+    type Error = MerkleTreeError;
+
+    fn with_options(items: Vec<H::Digest>, arity: usize) -> Result<Self, MerkleTreeError> {
+        MerkleTree::with_arity(items, arity)
+    }
+
+    fn commitment(&self) -> H::Digest {
+        *self.root()
+    }
+
+    fn domain_len(&self) -> usize {
+        self.leaves.len()
+    }
+
+    fn open(&self, index: usize) -> Result<(H::Digest, BatchMerkleProof<H>), MerkleTreeError> {
+        let proof = self.prove_batch(&[index])?;
+        Ok((self.leaves[index], proof))
+    }
+
+    fn open_many(
+        &self,
+        indexes: &[usize],
+    ) -> Result<(Vec<H::Digest>, BatchMerkleProof<H>), MerkleTreeError> {
+        let proof = self.prove_batch(indexes)?;
+        Ok((proof.leaves.clone(), proof))
+    }
+
+    fn verify(
+        commitment: H::Digest,
+        index: usize,
+        item: H::Digest,
+        proof: &BatchMerkleProof<H>,
+    ) -> Result<(), MerkleTreeError> {
+        <Self as VectorCommitment<H>>::verify_many(commitment, &[index], &[item], proof)
+    }
+
+    fn verify_many(
+        commitment: H::Digest,
+        indexes: &[usize],
+        items: &[H::Digest],
+        proof: &BatchMerkleProof<H>,
+    ) -> Result<(), MerkleTreeError> {
+        check_leaves::<H>(indexes, items, &proof.leaves)?;
+        MerkleTree::verify_batch(&commitment, indexes, proof)
+    }
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
+/// Checks whether the `opened` leaves match the `expected` leaves at the specified `indexes`.
+///
+/// # Errors
+/// Returns an error if:
+/// * The number of expected or opened leaves is not the same as the number of indexes.
+/// * Any of the opened leaves differs from the expected one; the error lists the indexes of all
+///   such leaves.
+pub(crate) fn check_leaves<H: Hasher>(
+    indexes: &[usize],
+    expected: &[H::Digest],
+    opened: &[H::Digest],
+) -> Result<(), MerkleTreeError> {
+    if expected.len() != indexes.len() {
+        return Err(MerkleTreeError::WrongNumberOfLeaves(
+            indexes.len(),
+            expected.len(),
+        ));
+    }
+    if opened.len() != indexes.len() {
+        return Err(MerkleTreeError::WrongNumberOfLeaves(
+            indexes.len(),
+            opened.len(),
+        ));
+    }
+    let invalid = indexes
+        .iter()
+        .zip(expected.iter().zip(opened))
+        .filter(|(_, (expected, opened))| expected != opened)
+        .map(|(&index, _)| index)
+        .collect::<Vec<_>>();
+    if !invalid.is_empty() {
+        return Err(MerkleTreeError::InvalidLeaves(invalid));
+    }
+    Ok(())
+}
+
 /// Returns the internal nodes of a Merkle tree defined by the specified leaves.
 ///
 /// The internal nodes are turned as a vector where the root is stored at position 1, its children
@@ -323,27 +763,106 @@ pub fn build_merkle_nodes<H: Hasher>(leaves: &[H::Digest]) -> Vec<H::Digest> {
     // re-interpret leaves as an array of two leaves fused together
     let two_leaves = unsafe { slice::from_raw_parts(leaves.as_ptr() as *const [H::Digest; 2], n) };
 
-    // build first row of internal nodes (parents of leaves)
-    for (i, j) in (0..n).zip(n..nodes.len()) {
-        nodes[j] = H::merge(&two_leaves[i]);
+    // build first row of internal nodes (parents of leaves); all nodes in a row are independent
+    // of each other, and thus, they can be computed in a single batch
+    H::merge_batch(two_leaves, &mut nodes[n..]);
+
+    // calculate all other tree nodes one row at a time; the row occupying positions [k, 2k) is
+    // computed from the row occupying positions [2k, 4k)
+    let mut k = n / 2;
+    while k > 0 {
+        let (parents, children) = nodes.split_at_mut(2 * k);
+        let two_children =
+            unsafe { slice::from_raw_parts(children.as_ptr() as *const [H::Digest; 2], k) };
+        H::merge_batch(two_children, &mut parents[k..]);
+        k /= 2;
     }
 
-    // re-interpret nodes as an array of two nodes fused together
-    let two_nodes = unsafe { slice::from_raw_parts(nodes.as_ptr() as *const [H::Digest; 2], n) };
+    nodes
+}
 
-    // calculate all other tree nodes
-    for i in (1..n).rev() {
-        nodes[i] = H::merge(&two_nodes[i]);
+/// Returns the internal nodes of a Merkle tree of the specified `arity` defined by the specified
+/// leaves.
+///
+/// The internal nodes are returned as a vector where position 0 is unused, the root is stored at
+/// position 1, and it is followed by all other levels of the tree from the top down. For binary
+/// trees, this layout is the same as the one produced by [build_merkle_nodes()].
+///
+/// This function is exposed primarily for benchmarking purposes. It is not intended to be used
+/// directly by the end users of the crate.
+pub fn build_kary_merkle_nodes<H: Hasher>(leaves: &[H::Digest], arity: usize) -> Vec<H::Digest> {
+    let fan_ins = level_fan_ins(log2(leaves.len()) as usize, arity);
+
+    let mut levels: Vec<Vec<H::Digest>> = Vec::with_capacity(fan_ins.len());
+    for fan_in in fan_ins {
+        let children = levels.last().map_or(leaves, |level| level.as_slice());
+
+        #[cfg(not(feature = "concurrent"))]
+        let level = merge_level::<H>(children, fan_in);
+
+        #[cfg(feature = "concurrent")]
+        let level = if children.len() <= ConcurrencyConfig::global().min_merkle_leaves() {
+            merge_level::<H>(children, fan_in)
+        } else {
+            concurrent::merge_level::<H>(children, fan_in)
+        };
+
+        levels.push(level);
     }
 
+    let num_nodes = levels.iter().map(|level| level.len()).sum::<usize>() + 1;
+    let mut nodes = Vec::with_capacity(num_nodes);
+    nodes.push(H::Digest::default());
+    for level in levels.into_iter().rev() {
+        nodes.extend_from_slice(&level);
+    }
     nodes
 }
 
+/// Hashes each consecutive group of `fan_in` nodes into a parent node.
+fn merge_level<H: Hasher>(nodes: &[H::Digest], fan_in: usize) -> Vec<H::Digest> {
+    nodes.chunks(fan_in).map(H::merge_many).collect()
+}
+
+/// Returns the number of children of nodes at each level of a tree of the specified `depth` and
+/// `arity`, starting with the parents of the leaves.
+///
+/// All levels have a fan-in equal to `arity`, except for the level right below the root which
+/// may have a smaller fan-in if `depth` is not a multiple of `log2(arity)`.
+fn level_fan_ins(depth: usize, arity: usize) -> Vec<usize> {
+    let log_arity = log2(arity) as usize;
+    let mut result = vec![arity; depth / log_arity];
+    if depth % log_arity != 0 {
+        result.push(1 << (depth % log_arity));
+    }
+    result
+}
+
+/// Returns the depth of a tree of the specified `arity` for which a Merkle path (including the
+/// leaf) has the specified length, or None if no such tree exists.
+fn path_depth(path_len: usize, arity: usize) -> Option<usize> {
+    if path_len < 2 {
+        return None;
+    }
+    let num_full_levels = (path_len - 1) / (arity - 1);
+    let top_fan_in = (path_len - 1) % (arity - 1) + 1;
+    if !top_fan_in.is_power_of_two() {
+        return None;
+    }
+    let depth = num_full_levels * log2(arity) as usize + log2(top_fan_in) as usize;
+    if depth >= usize::BITS as usize {
+        return None;
+    }
+    Some(depth)
+}
+
 fn map_indexes(
     indexes: &[usize],
     tree_depth: usize,
 ) -> Result<BTreeMap<usize, usize>, MerkleTreeError> {
-    let num_leaves = 2usize.pow(tree_depth as u32);
+    let num_leaves = 1usize
+        .checked_shl(tree_depth as u32)
+        .ok_or(MerkleTreeError::InvalidProof)?;
     let mut map = BTreeMap::new();
     for (i, index) in indexes.iter().cloned().enumerate() {
         map.insert(index, i);
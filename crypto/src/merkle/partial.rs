@@ -0,0 +1,230 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{build_merkle_nodes, BatchMerkleProof, MerkleTree};
+use crate::{
+    errors::MerkleTreeError,
+    hash::{Hashable, Hasher},
+};
+use utils::{
+    collections::Vec, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+};
+
+// PARTIAL MERKLE TREE
+// ================================================================================================
+
+/// Upper levels of a committed binary [MerkleTree].
+///
+/// A partial tree keeps all nodes of the original tree from the root down to the specified
+/// `depth`. Each node at this depth is the root of a subtree built from `subtree_size()`
+/// consecutive leaves. Thus, a partial tree takes only a fraction of the memory needed by the full
+/// tree, and can be persisted (via [Serializable]) after the full tree has been discarded.
+///
+/// To open a leaf, the leaves of the subtree containing it must be provided again (e.g., by
+/// recomputing the corresponding rows of the committed matrix). Only this subtree is rebuilt;
+/// its root is checked against the persisted node, and the path within the subtree is combined
+/// with the path through the persisted levels. The resulting proofs are the same as the ones
+/// produced by the original tree, and thus, new query positions can be opened against an old
+/// commitment without recomputing the whole tree.
+pub struct PartialMerkleTree<H: Hasher> {
+    nodes: Vec<H::Digest>,
+    tree_depth: usize,
+    depth: usize,
+}
+
+impl<H: Hasher> PartialMerkleTree<H> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a partial tree containing all nodes of the specified `tree` at depths up to and
+    /// including `depth`.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The arity of the `tree` is not 2.
+    /// * `depth` is not smaller than the depth of the `tree`.
+    pub fn from_tree<L: Hashable<H>>(
+        tree: &MerkleTree<H, L>,
+        depth: usize,
+    ) -> Result<Self, MerkleTreeError> {
+        if tree.arity() != 2 {
+            return Err(MerkleTreeError::InvalidArity(tree.arity()));
+        }
+        if depth >= tree.depth() {
+            return Err(MerkleTreeError::InvalidPartialDepth(tree.depth(), depth));
+        }
+
+        Ok(Self {
+            nodes: tree.nodes[..2 << depth].to_vec(),
+            tree_depth: tree.depth(),
+            depth,
+        })
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the root of the tree.
+    pub fn root(&self) -> &H::Digest {
+        &self.nodes[1]
+    }
+
+    /// Returns the depth of the original tree.
+    pub fn tree_depth(&self) -> usize {
+        self.tree_depth
+    }
+
+    /// Returns the depth down to which nodes of the original tree are kept.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Returns the number of leaves in the original tree.
+    pub fn num_leaves(&self) -> usize {
+        1 << self.tree_depth
+    }
+
+    /// Returns the number of leaves in each of the subtrees rooted at the deepest kept level.
+    pub fn subtree_size(&self) -> usize {
+        1 << (self.tree_depth - self.depth)
+    }
+
+    // PROVING METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a Merkle path to the leaf at the specified `index`.
+    ///
+    /// `subtree_leaves` must contain all leaves of the subtree to which the leaf belongs; i.e.,
+    /// leaves at positions starting at `index - index % subtree_size()`. The path is the same as
+    /// the one which would have been returned by [MerkleTree::prove()] of the original tree.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The specified index is greater than or equal to the number of leaves in the tree.
+    /// * The number of subtree leaves is not equal to `subtree_size()`.
+    /// * The subtree leaves do not resolve to the persisted subtree root.
+    pub fn prove(
+        &self,
+        index: usize,
+        subtree_leaves: &[H::Digest],
+    ) -> Result<Vec<H::Digest>, MerkleTreeError> {
+        if index >= self.num_leaves() {
+            return Err(MerkleTreeError::LeafIndexOutOfBounds(
+                self.num_leaves(),
+                index,
+            ));
+        }
+        let subtree_size = self.subtree_size();
+        if subtree_leaves.len() != subtree_size {
+            return Err(MerkleTreeError::WrongNumberOfLeaves(
+                subtree_size,
+                subtree_leaves.len(),
+            ));
+        }
+
+        // rebuild the subtree and make sure it matches the persisted subtree root
+        let subtree_index = index / subtree_size;
+        let subtree_nodes = build_merkle_nodes::<H>(subtree_leaves);
+        let mut position = (1 << self.depth) + subtree_index;
+        if subtree_nodes[1] != self.nodes[position] {
+            return Err(MerkleTreeError::InvalidSubtree(subtree_index));
+        }
+
+        // build the path within the subtree, and then extend it through the persisted levels
+        let local_index = index % subtree_size;
+        let mut proof = vec![subtree_leaves[local_index], subtree_leaves[local_index ^ 1]];
+        let mut local_index = (local_index + subtree_size) >> 1;
+        while local_index > 1 {
+            proof.push(subtree_nodes[local_index ^ 1]);
+            local_index >>= 1;
+        }
+        while position > 1 {
+            proof.push(self.nodes[position ^ 1]);
+            position >>= 1;
+        }
+
+        Ok(proof)
+    }
+
+    /// Computes Merkle paths for the provided indexes and compresses the paths into a single
+    /// proof.
+    ///
+    /// Leaves of the subtrees containing the specified indexes are requested via the
+    /// `subtree_leaves` closure, which receives the index of a subtree and must return all of
+    /// its leaves. The closure is invoked once per subtree.
+    ///
+    /// # Errors
+    /// Returns an error if any of the errors described in [PartialMerkleTree::prove()] or
+    /// [BatchMerkleProof::from_paths()] occurs.
+    pub fn prove_batch<F>(
+        &self,
+        indexes: &[usize],
+        mut subtree_leaves: F,
+    ) -> Result<BatchMerkleProof<H>, MerkleTreeError>
+    where
+        F: FnMut(usize) -> Vec<H::Digest>,
+    {
+        let subtree_size = self.subtree_size();
+        let mut subtrees: Vec<(usize, Vec<H::Digest>)> = Vec::new();
+        let mut paths = Vec::with_capacity(indexes.len());
+        for &index in indexes {
+            let subtree_index = index / subtree_size;
+            let position = match subtrees.iter().position(|(i, _)| *i == subtree_index) {
+                Some(position) => position,
+                None => {
+                    subtrees.push((subtree_index, subtree_leaves(subtree_index)));
+                    subtrees.len() - 1
+                }
+            };
+            paths.push(self.prove(index, &subtrees[position].1)?);
+        }
+
+        let mut proof = BatchMerkleProof::from_paths(&paths, indexes)?;
+
+        // paths are aggregated in the order of their indexes, but leaves in the proof are
+        // expected to be in the same order as the provided indexes
+        proof.leaves = paths.iter().map(|path| path[0]).collect();
+        Ok(proof)
+    }
+}
+
+// SERIALIZATION / DESERIALIZATION
+// ================================================================================================
+
+impl<H: Hasher> Serializable for PartialMerkleTree<H> {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(self.tree_depth as u8);
+        target.write_u8(self.depth as u8);
+        target.write(&self.nodes[1..]);
+    }
+}
+
+impl<H: Hasher> Deserializable for PartialMerkleTree<H> {
+    /// Reads a partial Merkle tree from the specified `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let tree_depth = source.read_u8()? as usize;
+        let depth = source.read_u8()? as usize;
+        if tree_depth >= usize::BITS as usize {
+            return Err(DeserializationError::InvalidValue(format!(
+                "tree depth must be smaller than {}, but was {tree_depth}",
+                usize::BITS
+            )));
+        }
+        if depth >= tree_depth {
+            return Err(DeserializationError::InvalidValue(format!(
+                "partial tree depth must be smaller than {tree_depth}, but was {depth}"
+            )));
+        }
+
+        let mut nodes = Vec::with_capacity(2 << depth);
+        nodes.push(H::Digest::default());
+        nodes.append(&mut H::Digest::read_batch_from(source, (2 << depth) - 1)?);
+        Ok(Self {
+            nodes,
+            tree_depth,
+            depth,
+        })
+    }
+}
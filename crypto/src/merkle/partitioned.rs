@@ -0,0 +1,248 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{path_depth, MerkleTree, MERKLE_TREE_ARITIES};
+use crate::{errors::MerkleTreeError, hash::Hasher};
+use utils::{
+    collections::Vec, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+};
+
+// PARTITIONED MERKLE TREE
+// ================================================================================================
+
+/// A commitment to a vector of leaves split into equally-sized partitions.
+///
+/// Each partition is committed to by a separate [MerkleTree], and the roots of the partition
+/// trees are in turn committed to by a binary Merkle tree; the root of this tree is the root of
+/// the combined commitment. Such a root-of-roots structure allows the partitions to be committed
+/// to independently (e.g., by different machines), and only the partition roots need to be
+/// gathered in a single place to compute the combined root.
+///
+/// Leaves are indexed globally: the first `partition_size` leaves belong to the first partition,
+/// the next `partition_size` leaves belong to the second partition etc. A proof for a leaf
+/// consists of a Merkle path to the leaf within its partition tree, and a Merkle path to the
+/// partition root within the tree of partition roots. Such proofs can be verified using the
+/// [PartitionedMerkleTree::verify()] function.
+///
+/// When all partition trees are binary, the combined root is the same as the root of a binary
+/// [MerkleTree] built from all leaves.
+pub struct PartitionedMerkleTree<H: Hasher> {
+    partitions: Vec<MerkleTree<H>>,
+    roots: MerkleTree<H>,
+}
+
+impl<H: Hasher> PartitionedMerkleTree<H> {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new partitioned commitment built from the provided partition trees.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * Fewer than two partitions were provided.
+    /// * Number of partitions is not a power of two.
+    /// * Partition trees do not all have the same depth and arity.
+    pub fn new(partitions: Vec<MerkleTree<H>>) -> Result<Self, MerkleTreeError> {
+        if let Some(first) = partitions.first() {
+            let (depth, arity) = (first.depth(), first.arity());
+            if partitions
+                .iter()
+                .any(|partition| partition.depth() != depth || partition.arity() != arity)
+            {
+                return Err(MerkleTreeError::InconsistentPartitions);
+            }
+        }
+
+        let roots = partitions
+            .iter()
+            .map(|partition| *partition.root())
+            .collect();
+        let roots = MerkleTree::new(roots)?;
+        Ok(Self { partitions, roots })
+    }
+
+    /// Returns a new partitioned commitment to the provided leaves split into the specified
+    /// number of partitions; each partition is committed to by a Merkle tree of the specified
+    /// `arity`.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * `num_partitions` is smaller than two or is not a power of two.
+    /// * `arity` is not one of 2, 4, 8, or 16.
+    /// * Number of leaves is not a power of two, or there are fewer than two leaves per partition.
+    pub fn with_partitions(
+        leaves: Vec<H::Digest>,
+        num_partitions: usize,
+        arity: usize,
+    ) -> Result<Self, MerkleTreeError> {
+        if num_partitions < 2 {
+            return Err(MerkleTreeError::TooFewLeaves(2, num_partitions));
+        }
+        if !num_partitions.is_power_of_two() {
+            return Err(MerkleTreeError::NumberOfLeavesNotPowerOfTwo(num_partitions));
+        }
+        if !leaves.len().is_power_of_two() {
+            return Err(MerkleTreeError::NumberOfLeavesNotPowerOfTwo(leaves.len()));
+        }
+
+        let partition_size = leaves.len() / num_partitions;
+        let partitions = leaves
+            .chunks(partition_size.max(1))
+            .map(|chunk| MerkleTree::with_arity(chunk.to_vec(), arity))
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::new(partitions)
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the root of the combined commitment.
+    pub fn root(&self) -> &H::Digest {
+        self.roots.root()
+    }
+
+    /// Returns the number of partitions in this commitment.
+    pub fn num_partitions(&self) -> usize {
+        self.partitions.len()
+    }
+
+    /// Returns the number of leaves in each partition.
+    pub fn partition_size(&self) -> usize {
+        self.partitions[0].leaves().len()
+    }
+
+    /// Returns the total number of leaves in all partitions.
+    pub fn num_leaves(&self) -> usize {
+        self.num_partitions() * self.partition_size()
+    }
+
+    /// Returns the Merkle tree committing to the partition at the specified index.
+    ///
+    /// # Panics
+    /// Panics if `index` is greater than or equal to the number of partitions.
+    pub fn partition(&self, index: usize) -> &MerkleTree<H> {
+        &self.partitions[index]
+    }
+
+    // PROVING METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a proof for the leaf at the specified global `index`.
+    ///
+    /// # Errors
+    /// Returns an error if the specified index is greater than or equal to the total number of
+    /// leaves.
+    pub fn prove(&self, index: usize) -> Result<PartitionedMerkleProof<H>, MerkleTreeError> {
+        if index >= self.num_leaves() {
+            return Err(MerkleTreeError::LeafIndexOutOfBounds(
+                self.num_leaves(),
+                index,
+            ));
+        }
+
+        let partition_size = self.partition_size();
+        let partition = &self.partitions[index / partition_size];
+        Ok(PartitionedMerkleProof {
+            leaf_path: partition.prove(index % partition_size)?,
+            partition_path: self.roots.prove(index / partition_size)?,
+            arity: partition.arity() as u8,
+        })
+    }
+
+    // VERIFICATION METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Checks whether the `proof` for the leaf at the specified global `index` is valid against
+    /// the specified combined `root`.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The arity or the shape of the proof is invalid.
+    /// * The specified `index` is greater than or equal to the number of leaves in a commitment
+    ///   of the shape described by the proof.
+    /// * The path to the leaf does not resolve to the partition root, or the path to the
+    ///   partition root does not resolve to the specified `root`.
+    pub fn verify(
+        root: H::Digest,
+        index: usize,
+        proof: &PartitionedMerkleProof<H>,
+    ) -> Result<(), MerkleTreeError> {
+        let arity = proof.arity as usize;
+        if !MERKLE_TREE_ARITIES.contains(&arity) || proof.partition_path.len() < 2 {
+            return Err(MerkleTreeError::InvalidProof);
+        }
+        let partition_depth =
+            path_depth(proof.leaf_path.len(), arity).ok_or(MerkleTreeError::InvalidProof)?;
+        let num_leaves = 1 << (partition_depth + proof.partition_path.len() - 1);
+        if index >= num_leaves {
+            return Err(MerkleTreeError::LeafIndexOutOfBounds(num_leaves, index));
+        }
+
+        let partition_index = index >> partition_depth;
+        let leaf_index = index & ((1 << partition_depth) - 1);
+        MerkleTree::<H>::verify_with_arity(
+            proof.partition_root(),
+            leaf_index,
+            &proof.leaf_path,
+            arity,
+        )?;
+        MerkleTree::<H>::verify(root, partition_index, &proof.partition_path)
+    }
+}
+
+// PARTITIONED MERKLE PROOF
+// ================================================================================================
+
+/// A proof of inclusion of a leaf in a [PartitionedMerkleTree].
+///
+/// The proof consists of a Merkle path to the leaf within its partition tree (with the leaf as
+/// the first element), and a Merkle path to the partition root within the binary tree of
+/// partition roots (with the partition root as the first element).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartitionedMerkleProof<H: Hasher> {
+    /// Merkle path from the leaf to the root of its partition tree.
+    pub leaf_path: Vec<H::Digest>,
+    /// Merkle path from the partition root to the root of the combined commitment.
+    pub partition_path: Vec<H::Digest>,
+    /// Arity of the partition tree.
+    pub arity: u8,
+}
+
+impl<H: Hasher> PartitionedMerkleProof<H> {
+    /// Returns the leaf opened by this proof.
+    pub fn leaf(&self) -> H::Digest {
+        self.leaf_path[0]
+    }
+
+    /// Returns the root of the partition to which the leaf opened by this proof belongs.
+    pub fn partition_root(&self) -> H::Digest {
+        self.partition_path[0]
+    }
+}
+
+impl<H: Hasher> Serializable for PartitionedMerkleProof<H> {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(self.arity);
+        target.write_u8(self.leaf_path.len() as u8);
+        target.write(&self.leaf_path[..]);
+        target.write_u8(self.partition_path.len() as u8);
+        target.write(&self.partition_path[..]);
+    }
+}
+
+impl<H: Hasher> Deserializable for PartitionedMerkleProof<H> {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let arity = source.read_u8()?;
+        let num_leaf_nodes = source.read_u8()? as usize;
+        let leaf_path = H::Digest::read_batch_from(source, num_leaf_nodes)?;
+        let num_partition_nodes = source.read_u8()? as usize;
+        let partition_path = H::Digest::read_batch_from(source, num_partition_nodes)?;
+        Ok(Self {
+            leaf_path,
+            partition_path,
+            arity,
+        })
+    }
+}
@@ -5,16 +5,11 @@
 
 use crate::{errors::MerkleTreeError, Hasher};
 use utils::{
-    collections::{BTreeMap, Vec},
+    collections::{BTreeMap, BTreeSet, Vec},
     string::ToString,
-    ByteReader, Deserializable, DeserializationError, Serializable,
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
 };
 
-// CONSTANTS
-// ================================================================================================
-
-pub(super) const MAX_PATHS: usize = 255;
-
 // BATCH MERKLE PROOF
 // ================================================================================================
 
@@ -25,8 +20,18 @@ pub(super) const MAX_PATHS: usize = 255;
 /// individual Merkle paths. The algorithm is for aggregation is a variation of
 /// [Octopus](https://eprint.iacr.org/2017/933).
 ///
+/// The resulting set of nodes is minimal: a node is included in the proof only if it cannot be
+/// computed from the proven leaves and other nodes in the proof. Thus, siblings which are
+/// themselves proven leaves (or can be computed from them) are omitted, and nodes shared by
+/// several paths are included only once. Leaves are not serialized at all since they can be
+/// recomputed from the opened values.
+///
 /// Currently, at most 255 paths can be aggregated into a single proof. This limitation is
 /// imposed primarily for serialization purposes.
+///
+/// For binary trees, `nodes` contain a vector of nodes for each aggregated path. For trees with
+/// higher arity, `nodes` contain a vector for each level of the tree (starting with the leaf
+/// level) with all nodes which cannot be derived from the proven leaves.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BatchMerkleProof<H: Hasher> {
     /// The leaves being proven
@@ -35,36 +40,54 @@ pub struct BatchMerkleProof<H: Hasher> {
     pub nodes: Vec<Vec<H::Digest>>,
     /// Depth of the leaves
     pub depth: u8,
+    /// Arity of the tree
+    pub arity: u8,
 }
 
 impl<H: Hasher> BatchMerkleProof<H> {
     /// Constructs a batch Merkle proof from individual Merkle authentication paths.
     ///
-    /// # Panics
-    /// Panics if:
+    /// The paths are assumed to come from a binary Merkle tree.
+    ///
+    /// # Errors
+    /// Returns an error if:
     /// * No paths have been provided (i.e., `paths` is an empty slice).
-    /// * More than 255 paths have been provided.
     /// * Number of paths is not equal to the number of indexes.
+    /// * Any of the paths contains fewer than two nodes.
     /// * Not all paths have the same length.
-    pub fn from_paths(paths: &[Vec<H::Digest>], indexes: &[usize]) -> BatchMerkleProof<H> {
+    /// * Any of the indexes is greater than or equal to the number of leaves in a tree of the
+    ///   depth implied by the length of the paths.
+    /// * List of indexes contains duplicates.
+    pub fn from_paths(
+        paths: &[Vec<H::Digest>],
+        indexes: &[usize],
+    ) -> Result<BatchMerkleProof<H>, MerkleTreeError> {
         // TODO: optimize this to reduce amount of vector cloning.
-        assert!(!paths.is_empty(), "at least one path must be provided");
-        assert!(
-            paths.len() <= MAX_PATHS,
-            "number of paths cannot exceed {MAX_PATHS}"
-        );
-        assert_eq!(
-            paths.len(),
-            indexes.len(),
-            "number of paths must equal number of indexes"
-        );
+        if paths.is_empty() {
+            return Err(MerkleTreeError::TooFewLeafIndexes);
+        }
+        if paths.len() != indexes.len() {
+            return Err(MerkleTreeError::WrongNumberOfLeaves(
+                indexes.len(),
+                paths.len(),
+            ));
+        }
 
         let depth = paths[0].len();
+        if depth < 2 {
+            return Err(MerkleTreeError::InvalidPathLength(depth));
+        }
+        if let Some(path) = paths.iter().find(|path| path.len() != depth) {
+            return Err(MerkleTreeError::DepthMismatch(
+                depth - 1,
+                path.len().saturating_sub(1),
+            ));
+        }
+        super::map_indexes(indexes, depth - 1)?;
 
         // sort indexes in ascending order, and also re-arrange paths accordingly
         let mut path_map = BTreeMap::new();
         for (&index, path) in indexes.iter().zip(paths.iter().cloned()) {
-            assert_eq!(depth, path.len(), "not all paths have the same length");
             path_map.insert(index, path);
         }
         let indexes = path_map.keys().cloned().collect::<Vec<_>>();
@@ -110,11 +133,12 @@ impl<H: Hasher> BatchMerkleProof<H> {
             core::mem::swap(&mut path_map, &mut next_path_map);
         }
 
-        BatchMerkleProof {
+        Ok(BatchMerkleProof {
             leaves,
             nodes,
             depth: (depth - 1) as u8,
-        }
+            arity: 2,
+        })
     }
 
     /// Computes a node to which all Merkle paths aggregated in this proof resolve.
@@ -122,7 +146,6 @@ impl<H: Hasher> BatchMerkleProof<H> {
     /// # Errors
     /// Returns an error if:
     /// * No indexes were provided (i.e., `indexes` is an empty slice).
-    /// * Number of provided indexes is greater than 255.
     /// * Any of the specified `indexes` is greater than or equal to the number of leaves in the
     ///   tree for which this batch proof was generated.
     /// * List of indexes contains duplicates.
@@ -131,12 +154,23 @@ impl<H: Hasher> BatchMerkleProof<H> {
         if indexes.is_empty() {
             return Err(MerkleTreeError::TooFewLeafIndexes);
         }
-        if indexes.len() > MAX_PATHS {
-            return Err(MerkleTreeError::TooManyLeafIndexes(
-                MAX_PATHS,
+        if indexes.len() != self.leaves.len() {
+            return Err(MerkleTreeError::WrongNumberOfLeaves(
                 indexes.len(),
+                self.leaves.len(),
             ));
         }
+        if !super::MERKLE_TREE_ARITIES.contains(&(self.arity as usize)) {
+            return Err(MerkleTreeError::InvalidArity(self.arity as usize));
+        }
+        if self.arity != 2 {
+            let mut levels = self.resolve_kary_levels(indexes)?;
+            let root_level = levels.pop().expect("no tree levels");
+            return root_level
+                .get(&0)
+                .copied()
+                .ok_or(MerkleTreeError::InvalidProof);
+        }
 
         let mut buf = [H::Digest::default(); 2];
         let mut v = BTreeMap::new();
@@ -259,27 +293,58 @@ impl<H: Hasher> BatchMerkleProof<H> {
 
     /// Computes the uncompressed Merkle paths which aggregate to this proof.
     ///
+    /// Paths are returned in the same order as the provided `indexes`, and each path is the same
+    /// as the path which would have been returned by [MerkleTree::prove()](super::MerkleTree::prove)
+    /// for the corresponding index (i.e., the leaf itself is the first element of the path).
+    /// Thus, each path can be verified, or re-packaged into a different proof format,
+    /// independently of other paths.
+    ///
+    /// # Examples
+    /// ```
+    /// # use winter_crypto::{MerkleTree, Hasher, hashers::Blake3_256};
+    /// # use math::fields::f128::BaseElement;
+    /// type Blake3 = Blake3_256::<BaseElement>;
+    ///
+    /// let leaves = (0..16u8).map(|i| Blake3::hash(&[i])).collect::<Vec<_>>();
+    /// let tree = MerkleTree::<Blake3>::with_arity(leaves, 4).unwrap();
+    ///
+    /// let indexes = [9, 2, 14];
+    /// let proof = tree.prove_batch(&indexes).unwrap();
+    /// let paths = proof.into_paths(&indexes).unwrap();
+    ///
+    /// for (&index, path) in indexes.iter().zip(paths.iter()) {
+    ///     assert_eq!(&tree.prove(index).unwrap(), path);
+    ///     assert!(MerkleTree::<Blake3>::verify_with_arity(*tree.root(), index, path, 4).is_ok());
+    /// }
+    /// ```
+    ///
     /// # Errors
     /// Returns an error if:
     /// * No indexes were provided (i.e., `indexes` is an empty slice).
-    /// * Number of provided indexes is greater than 255.
     /// * Number of provided indexes does not match the number of leaf nodes in the proof.
+    /// * Arity of the proof is not one of 2, 4, 8, or 16.
     pub fn into_paths(self, indexes: &[usize]) -> Result<Vec<Vec<H::Digest>>, MerkleTreeError> {
         if indexes.is_empty() {
             return Err(MerkleTreeError::TooFewLeafIndexes);
         }
-        if indexes.len() > MAX_PATHS {
-            return Err(MerkleTreeError::TooManyLeafIndexes(
-                MAX_PATHS,
+        if indexes.len() != self.leaves.len() {
+            return Err(MerkleTreeError::WrongNumberOfLeaves(
                 indexes.len(),
+                self.leaves.len(),
             ));
         }
-        if indexes.len() != self.leaves.len() {
-            return Err(MerkleTreeError::InvalidProof);
+        if !super::MERKLE_TREE_ARITIES.contains(&(self.arity as usize)) {
+            return Err(MerkleTreeError::InvalidArity(self.arity as usize));
+        }
+        if self.arity != 2 {
+            return self.into_kary_paths(indexes);
         }
 
-        let mut partial_tree_map = BTreeMap::new();
+        // replace odd indexes, offset, and sort in ascending order
+        let original_indexes = indexes;
+        let index_map = super::map_indexes(indexes, self.depth as usize)?;
 
+        let mut partial_tree_map = BTreeMap::new();
         for (&i, leaf) in indexes.iter().zip(self.leaves.iter()) {
             partial_tree_map.insert(i + (1 << (self.depth)), *leaf);
         }
@@ -287,9 +352,6 @@ impl<H: Hasher> BatchMerkleProof<H> {
         let mut buf = [H::Digest::default(); 2];
         let mut v = BTreeMap::new();
 
-        // replace odd indexes, offset, and sort in ascending order
-        let original_indexes = indexes;
-        let index_map = super::map_indexes(indexes, self.depth as usize)?;
         let indexes = super::normalize_indexes(indexes);
         if indexes.len() != self.nodes.len() {
             return Err(MerkleTreeError::InvalidProof);
@@ -413,16 +475,32 @@ impl<H: Hasher> BatchMerkleProof<H> {
 
     /// Converts all internal proof nodes into a vector of bytes.
     ///
+    /// The arity of the tree is not serialized, and thus, must be provided separately when the
+    /// proof is deserialized.
+    ///
     /// # Panics
     /// Panics if:
-    /// * The proof contains more than 255 Merkle paths.
     /// * The Merkle paths consist of more than 255 nodes.
+    /// * For trees with arity greater than 2, any level of the proof contains more than 65535
+    ///   nodes.
     pub fn serialize_nodes(&self) -> Vec<u8> {
         let mut result = Vec::new();
 
+        if self.arity != 2 {
+            // record the number of levels, and then the nodes of each level
+            result.write_usize(self.nodes.len());
+            for nodes in self.nodes.iter() {
+                assert!(nodes.len() <= u16::MAX as usize, "too many nodes");
+                result.write_u16(nodes.len() as u16);
+                for node in nodes.iter() {
+                    result.append(&mut node.to_bytes());
+                }
+            }
+            return result;
+        }
+
         // record total number of node vectors
-        assert!(self.nodes.len() <= u8::MAX as usize, "too many paths");
-        result.push(self.nodes.len() as u8);
+        result.write_usize(self.nodes.len());
 
         // record each node vector as individual bytes
         for nodes in self.nodes.iter() {
@@ -440,10 +518,11 @@ impl<H: Hasher> BatchMerkleProof<H> {
     /// Parses internal nodes from the provided `node_bytes`, and constructs a batch Merkle proof
     /// from these nodes, provided `leaves`, and provided tree `depth`.
     ///
+    /// The proof is assumed to be for a binary Merkle tree.
+    ///
     /// # Errors
     /// Returns an error if:
     /// * No leaves were provided (i.e., `leaves` is an empty slice).
-    /// * Number of provided leaves is greater than 255.
     /// * Tree `depth` was set to zero.
     /// * `node_bytes` could not be deserialized into a valid set of internal nodes.
     pub fn deserialize<R: ByteReader>(
@@ -451,6 +530,29 @@ impl<H: Hasher> BatchMerkleProof<H> {
         leaves: Vec<H::Digest>,
         depth: u8,
     ) -> Result<Self, DeserializationError> {
+        Self::deserialize_with_arity(node_bytes, leaves, depth, 2)
+    }
+
+    /// Parses internal nodes from the provided `node_bytes`, and constructs a batch Merkle proof
+    /// from these nodes, provided `leaves`, and provided tree `depth` and `arity`.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * `arity` is not one of 2, 4, 8, or 16.
+    /// * No leaves were provided (i.e., `leaves` is an empty slice).
+    /// * Tree `depth` was set to zero.
+    /// * `node_bytes` could not be deserialized into a valid set of internal nodes.
+    pub fn deserialize_with_arity<R: ByteReader>(
+        node_bytes: &mut R,
+        leaves: Vec<H::Digest>,
+        depth: u8,
+        arity: u8,
+    ) -> Result<Self, DeserializationError> {
+        if !super::MERKLE_TREE_ARITIES.contains(&(arity as usize)) {
+            return Err(DeserializationError::InvalidValue(format!(
+                "Merkle tree arity must be 2, 4, 8, or 16, but was {arity}"
+            )));
+        }
         if depth == 0 {
             return Err(DeserializationError::InvalidValue(
                 "tree depth must be greater than zero".to_string(),
@@ -461,19 +563,19 @@ impl<H: Hasher> BatchMerkleProof<H> {
                 "at lease one leaf must be provided".to_string(),
             ));
         }
-        if leaves.len() > MAX_PATHS {
-            return Err(DeserializationError::InvalidValue(format!(
-                "number of leaves cannot exceed {}, but {} were provided",
-                MAX_PATHS,
-                leaves.len()
-            )));
-        }
 
-        let num_node_vectors = node_bytes.read_u8()? as usize;
-        let mut nodes = Vec::with_capacity(num_node_vectors);
+        // the number of node vectors is not used to pre-allocate memory as it comes from an
+        // untrusted source
+        let num_node_vectors = node_bytes.read_usize()?;
+        let mut nodes = Vec::new();
         for _ in 0..num_node_vectors {
-            // read the number of digests in the vector
-            let num_digests = node_bytes.read_u8()? as usize;
+            // read the number of digests in the vector; for binary trees this number is encoded
+            // in a single byte, while for trees with higher arity it is encoded in two bytes
+            let num_digests = if arity == 2 {
+                node_bytes.read_u8()? as usize
+            } else {
+                node_bytes.read_u16()? as usize
+            };
 
             // read the digests and add them to the node vector
             let digests = H::Digest::read_batch_from(node_bytes, num_digests)?;
@@ -484,8 +586,102 @@ impl<H: Hasher> BatchMerkleProof<H> {
             leaves,
             nodes,
             depth,
+            arity,
         })
     }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Computes all nodes of a tree with arity greater than 2 which can be derived from this
+    /// proof, and returns them grouped by tree level (from the leaves up to the root). Each level
+    /// is a map from node position to node value.
+    ///
+    /// # Errors
+    /// Returns an error if the specified `indexes` are invalid, or if this proof does not contain
+    /// exactly the nodes needed to compute the root of the tree.
+    fn resolve_kary_levels(
+        &self,
+        indexes: &[usize],
+    ) -> Result<Vec<BTreeMap<usize, H::Digest>>, MerkleTreeError> {
+        super::map_indexes(indexes, self.depth as usize)?;
+        if indexes.len() != self.leaves.len() {
+            return Err(MerkleTreeError::WrongNumberOfLeaves(
+                indexes.len(),
+                self.leaves.len(),
+            ));
+        }
+        let fan_ins = super::level_fan_ins(self.depth as usize, self.arity as usize);
+        if fan_ins.len() != self.nodes.len() {
+            return Err(MerkleTreeError::InvalidProof);
+        }
+
+        let mut levels = Vec::with_capacity(fan_ins.len() + 1);
+        let mut current: BTreeMap<usize, H::Digest> = indexes
+            .iter()
+            .cloned()
+            .zip(self.leaves.iter().cloned())
+            .collect();
+        let mut buf = Vec::with_capacity(self.arity as usize);
+        for (&fan_in, proof_nodes) in fan_ins.iter().zip(self.nodes.iter()) {
+            let parents: BTreeSet<usize> = current.keys().map(|&p| p / fan_in).collect();
+            let mut proof_nodes = proof_nodes.iter();
+
+            // compute parent nodes, taking the nodes which are not known yet from the proof
+            let mut next = BTreeMap::new();
+            for parent in parents {
+                buf.clear();
+                for position in parent * fan_in..(parent + 1) * fan_in {
+                    let node = match current.get(&position) {
+                        Some(node) => *node,
+                        None => {
+                            let node = *proof_nodes.next().ok_or(MerkleTreeError::InvalidProof)?;
+                            current.insert(position, node);
+                            node
+                        }
+                    };
+                    buf.push(node);
+                }
+                next.insert(parent, H::merge_many(&buf));
+            }
+
+            // all nodes in the proof must be used
+            if proof_nodes.next().is_some() {
+                return Err(MerkleTreeError::InvalidProof);
+            }
+
+            levels.push(current);
+            current = next;
+        }
+        levels.push(current);
+
+        Ok(levels)
+    }
+
+    /// Computes the uncompressed Merkle paths which aggregate to this proof for a tree with arity
+    /// greater than 2.
+    fn into_kary_paths(self, indexes: &[usize]) -> Result<Vec<Vec<H::Digest>>, MerkleTreeError> {
+        let levels = self.resolve_kary_levels(indexes)?;
+        let fan_ins = super::level_fan_ins(self.depth as usize, self.arity as usize);
+
+        let mut paths = Vec::with_capacity(indexes.len());
+        for &index in indexes {
+            let mut path = vec![levels[0][&index]];
+            let mut position = index;
+            for (level, &fan_in) in levels.iter().zip(fan_ins.iter()) {
+                let start = position - position % fan_in;
+                path.extend(
+                    (start..start + fan_in)
+                        .filter(|&p| p != position)
+                        .map(|p| level[&p]),
+                );
+                position /= fan_in;
+            }
+            paths.push(path);
+        }
+
+        Ok(paths)
+    }
 }
 
 // HELPER FUNCTIONS
@@ -0,0 +1,173 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{check_leaves, BatchMerkleProof, MerkleTree};
+use crate::{errors::MerkleTreeError, hash::Hasher};
+use utils::collections::Vec;
+
+// SALTED MERKLE TREE
+// ================================================================================================
+
+/// A Merkle tree in which every leaf is mixed with a pseudo-random salt before being committed to.
+///
+/// In a regular [MerkleTree], an authentication path for a leaf contains the sibling leaf as is.
+/// Thus, when leaves are hashes of low-entropy values (e.g., rows of an execution trace), opening
+/// a single position may leak information about unopened positions. In a salted tree, the leaf
+/// at position `i` is computed as `merge(leaf_i, salt_i)`, and salts are revealed only for the
+/// opened positions. Without the salts, sibling nodes in authentication paths do not reveal
+/// anything about the values at the unopened positions.
+///
+/// Salts are derived deterministically from a secret `seed` known only to the prover as
+/// `salt_i = merge_with_int(hash(seed), i)`. Thus, the seed must be sampled from a source of
+/// randomness which is not available to the verifier, and it must never be revealed.
+///
+/// To verify openings of a salted tree, [SaltedMerkleTree::verify_batch()] function can be used.
+pub struct SaltedMerkleTree<H: Hasher> {
+    tree: MerkleTree<H>,
+    seed: H::Digest,
+}
+
+impl<H: Hasher> SaltedMerkleTree<H> {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new salted binary Merkle tree built from the provided leaves using salts derived
+    /// from the specified `seed`.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * Fewer than two leaves were provided.
+    /// * Number of leaves is not a power of two.
+    pub fn new(leaves: Vec<H::Digest>, seed: [u8; 32]) -> Result<Self, MerkleTreeError> {
+        Self::with_arity(leaves, seed, 2)
+    }
+
+    /// Returns a new salted Merkle tree with the specified `arity` built from the provided
+    /// leaves using salts derived from the specified `seed`.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * `arity` is not one of 2, 4, 8, or 16.
+    /// * Fewer than two leaves were provided.
+    /// * Number of leaves is not a power of two.
+    pub fn with_arity(
+        leaves: Vec<H::Digest>,
+        seed: [u8; 32],
+        arity: usize,
+    ) -> Result<Self, MerkleTreeError> {
+        let seed = H::hash(&seed);
+        let salted_leaves = leaves
+            .into_iter()
+            .enumerate()
+            .map(|(i, leaf)| salt_leaf::<H>(leaf, H::merge_with_int(seed, i as u64)))
+            .collect();
+        let tree = MerkleTree::with_arity(salted_leaves, arity)?;
+        Ok(Self { tree, seed })
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the root of the tree.
+    pub fn root(&self) -> &H::Digest {
+        self.tree.root()
+    }
+
+    /// Returns depth of the tree.
+    pub fn depth(&self) -> usize {
+        self.tree.depth()
+    }
+
+    /// Returns the underlying tree built from salted leaves.
+    pub fn tree(&self) -> &MerkleTree<H> {
+        &self.tree
+    }
+
+    /// Returns the salt for the leaf at the specified `index`.
+    ///
+    /// # Errors
+    /// Returns an error if the specified index is greater than or equal to the number of leaves
+    /// in the tree.
+    pub fn get_salt(&self, index: usize) -> Result<H::Digest, MerkleTreeError> {
+        let num_leaves = self.tree.leaves().len();
+        if index >= num_leaves {
+            return Err(MerkleTreeError::LeafIndexOutOfBounds(num_leaves, index));
+        }
+        Ok(H::merge_with_int(self.seed, index as u64))
+    }
+
+    // PROVING METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Computes Merkle paths for the provided indexes and compresses the paths into a single
+    /// proof. Salts for the leaves at the specified indexes are returned together with the proof
+    /// in the same order as the indexes.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * No indexes were provided (i.e., `indexes` is an empty slice).
+    /// * Any of the provided indexes are greater than or equal to the number of leaves in the
+    ///   tree.
+    /// * List of indexes contains duplicates.
+    pub fn prove_batch(
+        &self,
+        indexes: &[usize],
+    ) -> Result<(BatchMerkleProof<H>, Vec<H::Digest>), MerkleTreeError> {
+        let proof = self.tree.prove_batch(indexes)?;
+        let salts = indexes
+            .iter()
+            .map(|&index| self.get_salt(index))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok((proof, salts))
+    }
+
+    // VERIFICATION METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Checks whether the batch proof together with the provided salts opens the specified
+    /// (unsalted) `leaves` at the specified `indexes`.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The number of leaves or salts is not the same as the number of indexes.
+    /// * Any of the leaves mixed with the corresponding salt does not match the leaf in the proof;
+    ///   in this case, the error lists the indexes of all such leaves.
+    /// * Any of the errors described in [MerkleTree::verify_batch()] occurs.
+    pub fn verify_batch(
+        root: &H::Digest,
+        indexes: &[usize],
+        leaves: &[H::Digest],
+        salts: &[H::Digest],
+        proof: &BatchMerkleProof<H>,
+    ) -> Result<(), MerkleTreeError> {
+        if leaves.len() != indexes.len() {
+            return Err(MerkleTreeError::WrongNumberOfLeaves(
+                indexes.len(),
+                leaves.len(),
+            ));
+        }
+        if salts.len() != indexes.len() {
+            return Err(MerkleTreeError::WrongNumberOfLeaves(
+                indexes.len(),
+                salts.len(),
+            ));
+        }
+        let salted_leaves = leaves
+            .iter()
+            .zip(salts)
+            .map(|(&leaf, &salt)| salt_leaf::<H>(leaf, salt))
+            .collect::<Vec<_>>();
+        check_leaves::<H>(indexes, &salted_leaves, &proof.leaves)?;
+        MerkleTree::verify_batch(root, indexes, proof)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Mixes the specified salt into the leaf.
+fn salt_leaf<H: Hasher>(leaf: H::Digest, salt: H::Digest) -> H::Digest {
+    H::merge(&[leaf, salt])
+}
@@ -4,8 +4,10 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::*;
+use crate::hash::ElementHasher;
 use math::fields::f128::BaseElement;
 use proptest::prelude::*;
+use utils::{Deserializable, DeserializationError, Serializable, SliceReader};
 
 type Digest256 = crate::hash::ByteDigest<32>;
 type Blake3_256 = crate::hash::Blake3_256<BaseElement>;
@@ -232,6 +234,53 @@ fn verify_batch() {
     assert!(MerkleTree::verify_batch(tree.root(), &[0, 1, 2, 3, 4, 5, 6, 7], &proof).is_ok());
 }
 
+#[test]
+fn find_invalid_leaves() {
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+
+    for arity in [2, 4, 8] {
+        let tree = MerkleTree::<Blake3_256>::with_arity(leaves.clone(), arity).unwrap();
+        let indexes = [6, 1, 2, 4];
+
+        let mut proof = tree.prove_batch(&indexes).unwrap();
+        assert!(tree
+            .find_invalid_leaves(&indexes, &proof)
+            .unwrap()
+            .is_empty());
+
+        // corrupted leaves are reported in the order of the indexes
+        proof.leaves[0] = leaves[0];
+        proof.leaves[3] = leaves[0];
+        assert_eq!(
+            vec![6, 4],
+            tree.find_invalid_leaves(&indexes, &proof).unwrap()
+        );
+        assert_eq!(
+            Err(MerkleTreeError::InvalidProof),
+            MerkleTree::verify_batch(tree.root(), &indexes, &proof)
+        );
+
+        assert_eq!(
+            Err(MerkleTreeError::LeafIndexOutOfBounds(8, 8)),
+            tree.find_invalid_leaves(&[6, 1, 2, 8], &proof)
+        );
+    }
+
+    // vector commitments report mismatching items
+    let tree = MerkleTree::<Blake3_256>::new(leaves.clone()).unwrap();
+    let (items, proof) = tree.open_many(&[3, 5]).unwrap();
+    let wrong_items = [items[0], leaves[0]];
+    assert_eq!(
+        Err(MerkleTreeError::InvalidLeaves(vec![5])),
+        <MerkleTree<_> as VectorCommitment<_>>::verify_many(
+            *tree.root(),
+            &[3, 5],
+            &wrong_items,
+            &proof
+        )
+    );
+}
+
 #[test]
 fn verify_into_paths() {
     let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
@@ -256,6 +305,453 @@ fn verify_into_paths() {
     assert_eq!(proof6, result[2]);
 }
 
+#[test]
+fn new_kary_tree() {
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+
+    // arity 2 is the same as a regular binary tree
+    let tree = MerkleTree::<Blake3_256>::with_arity(leaves.clone(), 2).unwrap();
+    let expected_tree = MerkleTree::<Blake3_256>::new(leaves.clone()).unwrap();
+    assert_eq!(expected_tree.root(), tree.root());
+    assert_eq!(2, tree.arity());
+
+    // in a 4-ary tree with 8 leaves, the root has two children
+    let tree = MerkleTree::<Blake3_256>::with_arity(leaves.clone(), 4).unwrap();
+    let node0 = Blake3_256::merge_many(&leaves[..4]);
+    let node1 = Blake3_256::merge_many(&leaves[4..]);
+    assert_eq!(hash_2x1(node0, node1), *tree.root());
+    assert_eq!(3, tree.depth());
+    assert_eq!(4, tree.arity());
+
+    // in an 8-ary tree with 8 leaves, the root is the hash of all leaves
+    let tree = MerkleTree::<Blake3_256>::with_arity(leaves.clone(), 8).unwrap();
+    assert_eq!(Blake3_256::merge_many(&leaves), *tree.root());
+
+    // in an 16-ary tree with 8 leaves, the root is also the hash of all leaves
+    let tree = MerkleTree::<Blake3_256>::with_arity(leaves.clone(), 16).unwrap();
+    assert_eq!(Blake3_256::merge_many(&leaves), *tree.root());
+
+    // unsupported arity
+    let result = MerkleTree::<Blake3_256>::with_arity(leaves, 3);
+    assert_eq!(Some(MerkleTreeError::InvalidArity(3)), result.err());
+}
+
+#[test]
+fn prove_kary() {
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let tree = MerkleTree::<Blake3_256>::with_arity(leaves.clone(), 4).unwrap();
+
+    let node1 = Blake3_256::merge_many(&leaves[4..]);
+    let proof = vec![leaves[2], leaves[0], leaves[1], leaves[3], node1];
+    assert_eq!(proof, tree.prove(2).unwrap());
+    assert!(MerkleTree::<Blake3_256>::verify_with_arity(*tree.root(), 2, &proof, 4).is_ok());
+    assert!(MerkleTree::<Blake3_256>::verify_with_arity(*tree.root(), 3, &proof, 4).is_err());
+    assert!(MerkleTree::<Blake3_256>::verify_with_arity(*tree.root(), 2, &proof, 8).is_err());
+
+    // the proof must have a length consistent with the arity
+    let proof = &proof[..3];
+    assert_eq!(
+        Err(MerkleTreeError::InvalidPathLength(3)),
+        MerkleTree::<Blake3_256>::verify_with_arity(*tree.root(), 2, proof, 4)
+    );
+}
+
+#[test]
+fn prove_batch_kary() {
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let tree = MerkleTree::<Blake3_256>::with_arity(leaves.clone(), 4).unwrap();
+
+    let proof = tree.prove_batch(&[6, 1, 2]).unwrap();
+    assert_eq!(vec![leaves[6], leaves[1], leaves[2]], proof.leaves);
+    assert_eq!(
+        vec![
+            vec![leaves[0], leaves[3], leaves[4], leaves[5], leaves[7]],
+            vec![]
+        ],
+        proof.nodes
+    );
+    assert_eq!(4, proof.arity);
+    assert!(MerkleTree::verify_batch(tree.root(), &[6, 1, 2], &proof).is_ok());
+    assert!(MerkleTree::verify_batch(tree.root(), &[6, 1, 3], &proof).is_err());
+
+    // a proof with extra nodes is invalid
+    let mut nodes = proof.nodes.clone();
+    nodes[1].push(leaves[0]);
+    let bad_proof = BatchMerkleProof::<Blake3_256> {
+        leaves: proof.leaves.clone(),
+        nodes,
+        depth: proof.depth,
+        arity: proof.arity,
+    };
+    assert!(MerkleTree::verify_batch(tree.root(), &[6, 1, 2], &bad_proof).is_err());
+
+    // serialization round-trip
+    let bytes = proof.serialize_nodes();
+    let mut reader = utils::SliceReader::new(&bytes);
+    let parsed = BatchMerkleProof::<Blake3_256>::deserialize_with_arity(
+        &mut reader,
+        proof.leaves.clone(),
+        proof.depth,
+        4,
+    )
+    .unwrap();
+    assert_eq!(proof, parsed);
+}
+
+#[test]
+fn build_tree_incrementally() {
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+
+    for arity in [2, 4, 8] {
+        let mut builder = MerkleTreeBuilder::<Blake3_256>::with_arity(8, arity).unwrap();
+        for &leaf in leaves[..5].iter() {
+            builder.push(leaf).unwrap();
+        }
+        assert_eq!(5, builder.num_pushed_leaves());
+        builder.push_leaves(&leaves[5..]).unwrap();
+        let tree = builder.finalize().unwrap();
+
+        let expected = MerkleTree::<Blake3_256>::with_arity(leaves.clone(), arity).unwrap();
+        assert_eq!(expected.root(), tree.root());
+        assert_eq!(expected.nodes, tree.nodes);
+        assert_eq!(expected.prove_batch(&[1, 6]), tree.prove_batch(&[1, 6]));
+    }
+
+    // too many leaves
+    let mut builder = MerkleTreeBuilder::<Blake3_256>::new(4).unwrap();
+    builder.push_leaves(&leaves[..4]).unwrap();
+    assert_eq!(
+        Err(MerkleTreeError::LeafIndexOutOfBounds(4, 4)),
+        builder.push(leaves[4])
+    );
+
+    // too few leaves
+    let mut builder = MerkleTreeBuilder::<Blake3_256>::new(8).unwrap();
+    builder.push_leaves(&leaves[..4]).unwrap();
+    assert_eq!(
+        Some(MerkleTreeError::TooFewLeaves(8, 4)),
+        builder.finalize().err()
+    );
+
+    // invalid number of leaves
+    assert_eq!(
+        Some(MerkleTreeError::NumberOfLeavesNotPowerOfTwo(6)),
+        MerkleTreeBuilder::<Blake3_256>::new(6).err()
+    );
+}
+
+#[test]
+fn partitioned_tree() {
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+
+    // with binary partition trees, the combined root is the same as the root of a regular tree
+    let tree = PartitionedMerkleTree::<Blake3_256>::with_partitions(leaves.clone(), 4, 2).unwrap();
+    assert_eq!(4, tree.num_partitions());
+    assert_eq!(2, tree.partition_size());
+    let regular_tree = MerkleTree::<Blake3_256>::new(leaves.clone()).unwrap();
+    assert_eq!(regular_tree.root(), tree.root());
+
+    for arity in [2, 4] {
+        let tree =
+            PartitionedMerkleTree::<Blake3_256>::with_partitions(leaves.clone(), 2, arity).unwrap();
+        assert_eq!(2, tree.num_partitions());
+        assert_eq!(4, tree.partition_size());
+
+        for (index, &leaf) in leaves.iter().enumerate() {
+            let proof = tree.prove(index).unwrap();
+            assert_eq!(leaf, proof.leaf());
+            assert_eq!(*tree.partition(index / 4).root(), proof.partition_root());
+            assert!(PartitionedMerkleTree::verify(*tree.root(), index, &proof).is_ok());
+
+            // proofs survive serialization
+            let bytes = proof.to_bytes();
+            let mut reader = SliceReader::new(&bytes);
+            let parsed = PartitionedMerkleProof::<Blake3_256>::read_from(&mut reader).unwrap();
+            assert_eq!(proof, parsed);
+
+            // a proof is not valid for other positions
+            let other = index ^ 5;
+            assert!(PartitionedMerkleTree::verify(*tree.root(), other, &proof).is_err());
+        }
+
+        assert_eq!(
+            Err(MerkleTreeError::LeafIndexOutOfBounds(8, 8)),
+            tree.prove(8).map(|_| ())
+        );
+    }
+
+    // partition trees must all have the same shape
+    let partitions = vec![
+        MerkleTree::<Blake3_256>::new(leaves[..4].to_vec()).unwrap(),
+        MerkleTree::<Blake3_256>::new(leaves[4..6].to_vec()).unwrap(),
+    ];
+    assert_eq!(
+        Err(MerkleTreeError::InconsistentPartitions),
+        PartitionedMerkleTree::new(partitions).map(|_| ())
+    );
+}
+
+#[test]
+fn salted_tree() {
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let tree = SaltedMerkleTree::<Blake3_256>::new(leaves.clone(), [1; 32]).unwrap();
+
+    // salting changes the commitment, and different seeds result in different commitments
+    let unsalted_tree = MerkleTree::<Blake3_256>::new(leaves.clone()).unwrap();
+    assert_ne!(unsalted_tree.root(), tree.root());
+    let other_tree = SaltedMerkleTree::<Blake3_256>::new(leaves.clone(), [2; 32]).unwrap();
+    assert_ne!(other_tree.root(), tree.root());
+
+    // salted leaves never appear in authentication paths as is
+    let indexes = [6, 1];
+    let (proof, salts) = tree.prove_batch(&indexes).unwrap();
+    assert_eq!(
+        vec![tree.get_salt(6).unwrap(), tree.get_salt(1).unwrap()],
+        salts
+    );
+    for node in proof.nodes.iter().flatten() {
+        assert!(!leaves.contains(node));
+    }
+
+    let opened_leaves = [leaves[6], leaves[1]];
+    assert!(
+        SaltedMerkleTree::verify_batch(tree.root(), &indexes, &opened_leaves, &salts, &proof)
+            .is_ok()
+    );
+
+    // wrong leaves or salts fail verification
+    let wrong_leaves = [leaves[6], leaves[2]];
+    assert_eq!(
+        Err(MerkleTreeError::InvalidLeaves(vec![1])),
+        SaltedMerkleTree::verify_batch(tree.root(), &indexes, &wrong_leaves, &salts, &proof)
+    );
+    let wrong_salts = [salts[1], salts[0]];
+    assert!(SaltedMerkleTree::verify_batch(
+        tree.root(),
+        &indexes,
+        &opened_leaves,
+        &wrong_salts,
+        &proof
+    )
+    .is_err());
+
+    // salted trees of higher arity
+    let tree = SaltedMerkleTree::<Blake3_256>::with_arity(leaves.clone(), [1; 32], 4).unwrap();
+    let (proof, salts) = tree.prove_batch(&indexes).unwrap();
+    assert!(
+        SaltedMerkleTree::verify_batch(tree.root(), &indexes, &opened_leaves, &salts, &proof)
+            .is_ok()
+    );
+}
+
+#[test]
+fn vector_commitment() {
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+
+    for arity in [2, 4] {
+        let tree = <MerkleTree<Blake3_256> as VectorCommitment<Blake3_256>>::with_options(
+            leaves.clone(),
+            arity,
+        )
+        .unwrap();
+        let commitment = tree.commitment();
+        assert_eq!(*tree.root(), commitment);
+        assert_eq!(8, tree.domain_len());
+
+        // single opening
+        let (item, proof) = tree.open(5).unwrap();
+        assert_eq!(leaves[5], item);
+        assert!(
+            <MerkleTree<_> as VectorCommitment<_>>::verify(commitment, 5, item, &proof).is_ok()
+        );
+        assert!(
+            <MerkleTree<_> as VectorCommitment<_>>::verify(commitment, 5, leaves[4], &proof)
+                .is_err()
+        );
+
+        // multiple openings
+        let (items, proof) = tree.open_many(&[7, 2, 3]).unwrap();
+        assert_eq!(vec![leaves[7], leaves[2], leaves[3]], items);
+        assert!(MerkleTree::verify_many(commitment, &[7, 2, 3], &items, &proof).is_ok());
+        assert!(MerkleTree::verify_many(commitment, &[7, 2, 4], &items, &proof).is_err());
+    }
+}
+
+#[test]
+fn descriptive_errors() {
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let tree = MerkleTree::<Blake3_256>::new(leaves.clone()).unwrap();
+    let root = *tree.root();
+
+    // malformed paths are rejected rather than causing a panic
+    assert_eq!(
+        Err(MerkleTreeError::InvalidPathLength(1)),
+        MerkleTree::<Blake3_256>::verify(root, 0, &leaves[..1])
+    );
+    let path = tree.prove(3).unwrap();
+    assert_eq!(
+        Err(MerkleTreeError::LeafIndexOutOfBounds(8, 8)),
+        MerkleTree::<Blake3_256>::verify(root, 8, &path)
+    );
+
+    // paths of different depths cannot be combined into a single proof
+    let mut paths = vec![tree.prove(1).unwrap(), tree.prove(6).unwrap()];
+    paths[1].pop();
+    assert_eq!(
+        Err(MerkleTreeError::DepthMismatch(3, 2)),
+        BatchMerkleProof::<Blake3_256>::from_paths(&paths, &[1, 6])
+    );
+    assert_eq!(
+        Err(MerkleTreeError::WrongNumberOfLeaves(3, 2)),
+        BatchMerkleProof::<Blake3_256>::from_paths(&paths, &[1, 6, 7])
+    );
+
+    // the number of leaves in a batch proof must match the number of indexes
+    let proof = tree.prove_batch(&[1, 6]).unwrap();
+    assert_eq!(
+        Err(MerkleTreeError::WrongNumberOfLeaves(3, 2)),
+        MerkleTree::verify_batch(&root, &[1, 6, 7], &proof)
+    );
+
+    // unsupported arity and excessive depth are reported as errors
+    let bad_proof = BatchMerkleProof::<Blake3_256> {
+        leaves: proof.leaves.clone(),
+        nodes: proof.nodes.clone(),
+        depth: proof.depth,
+        arity: 3,
+    };
+    assert_eq!(
+        Err(MerkleTreeError::InvalidArity(3)),
+        MerkleTree::verify_batch(&root, &[1, 6], &bad_proof)
+    );
+    let mut bad_proof = proof;
+    bad_proof.depth = 200;
+    assert_eq!(
+        Err(MerkleTreeError::InvalidProof),
+        MerkleTree::verify_batch(&root, &[1, 6], &bad_proof)
+    );
+}
+
+#[test]
+fn partial_tree() {
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let tree = MerkleTree::<Blake3_256>::new(leaves.clone()).unwrap();
+
+    for depth in 0..3 {
+        let partial = PartialMerkleTree::from_tree(&tree, depth).unwrap();
+        assert_eq!(tree.root(), partial.root());
+        let subtree_size = partial.subtree_size();
+        assert_eq!(8 >> depth, subtree_size);
+
+        // persist the partial tree and read it back
+        let bytes = partial.to_bytes();
+        let partial =
+            PartialMerkleTree::<Blake3_256>::read_from(&mut SliceReader::new(&bytes)).unwrap();
+
+        // paths and batch proofs should be the same as the ones produced by the full tree
+        for index in 0..8 {
+            let start = index - index % subtree_size;
+            let path = partial
+                .prove(index, &leaves[start..start + subtree_size])
+                .unwrap();
+            assert_eq!(tree.prove(index).unwrap(), path);
+        }
+        let indexes = [6, 1, 2];
+        let proof = partial
+            .prove_batch(&indexes, |i| {
+                leaves[i * subtree_size..(i + 1) * subtree_size].to_vec()
+            })
+            .unwrap();
+        assert_eq!(tree.prove_batch(&indexes).unwrap(), proof);
+
+        // leaves which do not match the committed subtree are rejected
+        let mut subtree = leaves[..subtree_size].to_vec();
+        subtree[0] = leaves[7];
+        assert_eq!(
+            Err(MerkleTreeError::InvalidSubtree(0)),
+            partial.prove(1, &subtree)
+        );
+        assert_eq!(
+            Err(MerkleTreeError::WrongNumberOfLeaves(subtree_size, 1)),
+            partial.prove(1, &leaves[..1])
+        );
+    }
+
+    assert_eq!(
+        Err(MerkleTreeError::InvalidPartialDepth(3, 3)),
+        PartialMerkleTree::from_tree(&tree, 3).map(|_| ())
+    );
+}
+
+#[test]
+fn serialize_tree() {
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    for arity in [2, 4, 8] {
+        let tree = MerkleTree::<Blake3_256>::with_arity(leaves.clone(), arity).unwrap();
+        let bytes = tree.to_bytes();
+        let tree2 = MerkleTree::<Blake3_256>::read_from(&mut SliceReader::new(&bytes)).unwrap();
+        assert_eq!(tree.root(), tree2.root());
+        assert_eq!(tree.leaves(), tree2.leaves());
+        assert_eq!(
+            tree.prove_batch(&[1, 5]).unwrap(),
+            tree2.prove_batch(&[1, 5]).unwrap()
+        );
+    }
+}
+
+#[test]
+fn read_tree_malformed_depth() {
+    // a depth of 63 would require allocating 2^63 leaves
+    let bytes = [2u8, 63];
+    let err = MerkleTree::<Blake3_256>::read_from(&mut SliceReader::new(&bytes)).unwrap_err();
+    assert!(matches!(err, DeserializationError::InvalidValue(_)));
+
+    let bytes = [2u8, MAX_SERIALIZED_TREE_DEPTH as u8 + 1];
+    let err = MerkleTree::<Blake3_256>::read_from(&mut SliceReader::new(&bytes)).unwrap_err();
+    assert!(matches!(err, DeserializationError::InvalidValue(_)));
+
+    // a valid depth with missing data fails without allocating the whole tree
+    let bytes = [2u8, MAX_SERIALIZED_TREE_DEPTH as u8];
+    assert!(MerkleTree::<Blake3_256>::read_from(&mut SliceReader::new(&bytes)).is_err());
+}
+
+#[test]
+fn hashable_leaves() {
+    let rows = (0..8u128)
+        .map(|i| vec![BaseElement::new(i), BaseElement::new(i * i)])
+        .collect::<Vec<_>>();
+    let hashed_rows = rows
+        .iter()
+        .map(|row| Blake3_256::hash_elements(row))
+        .collect::<Vec<_>>();
+
+    for arity in [2, 4] {
+        // a tree built from rows should be the same as a tree built from hashed rows
+        let tree =
+            MerkleTree::<Blake3_256, Vec<BaseElement>>::with_arity(rows.clone(), arity).unwrap();
+        let expected = MerkleTree::<Blake3_256>::with_arity(hashed_rows.clone(), arity).unwrap();
+        assert_eq!(expected.root(), tree.root());
+        assert_eq!(rows, tree.leaves());
+        assert_eq!(hashed_rows[3], tree.leaf_digest(3));
+
+        // proofs should open the hashed rows
+        assert_eq!(expected.prove(5).unwrap(), tree.prove(5).unwrap());
+        let indexes = [1, 6, 2];
+        let proof = tree.prove_batch(&indexes).unwrap();
+        assert_eq!(expected.prove_batch(&indexes).unwrap(), proof);
+        assert!(MerkleTree::verify_batch(tree.root(), &indexes, &proof).is_ok());
+    }
+
+    // arrays of field elements can be committed to directly as well
+    let groups = (0..4u128)
+        .map(|i| [BaseElement::new(i), BaseElement::new(i + 4)])
+        .collect::<Vec<_>>();
+    let tree = MerkleTree::<Blake3_256, [BaseElement; 2]>::new(groups.clone()).unwrap();
+    let proof = tree.prove(1).unwrap();
+    assert_eq!(Blake3_256::hash_elements(&groups[1]), proof[0]);
+    assert!(MerkleTree::<Blake3_256>::verify(*tree.root(), 1, &proof).is_ok());
+}
+
 proptest! {
     #[test]
     fn prove_n_verify(tree in random_blake3_merkle_tree(128),
@@ -289,7 +785,7 @@ proptest! {
         for &idx in indices.iter() {
             paths.push(tree.prove(idx).unwrap());
         }
-        let proof2 = BatchMerkleProof::from_paths(&paths, &indices);
+        let proof2 = BatchMerkleProof::from_paths(&paths, &indices).unwrap();
 
         prop_assert!(proof1 == proof2);
     }
@@ -311,10 +807,80 @@ proptest! {
 
         prop_assert!(paths_expected == paths.unwrap());
     }
+
+    #[test]
+    fn prove_n_verify_kary(tree in random_blake3_kary_merkle_tree(128),
+                      proof_indices in prop::collection::vec(any::<prop::sample::Index>(), 10..20)
+    )  {
+        for proof_index in proof_indices{
+            let index = proof_index.index(128);
+            let proof = tree.prove(index).unwrap();
+            prop_assert!(MerkleTree::<Blake3_256>::verify_with_arity(*tree.root(), index, &proof, tree.arity()).is_ok())
+        }
+    }
+
+    #[test]
+    fn prove_batch_n_verify_kary(tree in random_blake3_kary_merkle_tree(128),
+                      proof_indices in prop::collection::vec(any::<prop::sample::Index>(), 1..30)
+    )  {
+        let mut indices: Vec<usize> = proof_indices.iter().map(|idx| idx.index(128)).collect();
+        indices.sort_unstable(); indices.dedup();
+        let proof = tree.prove_batch(&indices[..]).unwrap();
+        prop_assert!(MerkleTree::verify_batch(tree.root(), &indices[..], &proof).is_ok());
+
+        let bytes = proof.serialize_nodes();
+        let mut reader = utils::SliceReader::new(&bytes);
+        let parsed = BatchMerkleProof::<Blake3_256>::deserialize_with_arity(
+            &mut reader, proof.leaves.clone(), proof.depth, tree.arity() as u8).unwrap();
+        prop_assert!(parsed == proof);
+
+        let mut paths_expected = Vec::new();
+        for &idx in indices.iter() {
+            paths_expected.push(tree.prove(idx).unwrap());
+        }
+        prop_assert!(paths_expected == proof.into_paths(&indices).unwrap());
+    }
+
+    #[test]
+    fn prove_batch_is_minimal(tree in random_blake3_merkle_tree(1024),
+                      proof_indices in prop::collection::vec(any::<prop::sample::Index>(), 1..120)
+    )  {
+        let indices: Vec<usize> = proof_indices.iter().map(|idx| idx.index(1024)).collect();
+        let indices: Vec<usize> = indices.into_iter().collect::<BTreeSet<_>>().into_iter().collect();
+        let proof = tree.prove_batch(&indices[..]).unwrap();
+        let num_proof_nodes: usize = proof.nodes.iter().map(|nodes| nodes.len()).sum();
+        prop_assert_eq!(count_required_nodes(&indices, 2, tree.depth()), num_proof_nodes);
+    }
+
+    #[test]
+    fn prove_batch_kary_is_minimal(tree in random_blake3_kary_merkle_tree(1024),
+                      proof_indices in prop::collection::vec(any::<prop::sample::Index>(), 1..120)
+    )  {
+        let indices: Vec<usize> = proof_indices.iter().map(|idx| idx.index(1024)).collect();
+        let indices: Vec<usize> = indices.into_iter().collect::<BTreeSet<_>>().into_iter().collect();
+        let proof = tree.prove_batch(&indices[..]).unwrap();
+        let num_proof_nodes: usize = proof.nodes.iter().map(|nodes| nodes.len()).sum();
+        prop_assert_eq!(count_required_nodes(&indices, tree.arity(), tree.depth()), num_proof_nodes);
+    }
 }
 
 // HELPER FUNCTIONS
 // --------------------------------------------------------------------------------------------
+
+/// Returns the smallest number of nodes needed to compute the tree root from the leaves at the
+/// specified indexes: at every level, these are the siblings of known nodes which cannot be
+/// computed from the known nodes themselves.
+fn count_required_nodes(indexes: &[usize], arity: usize, depth: usize) -> usize {
+    let mut known: BTreeSet<usize> = indexes.iter().cloned().collect();
+    let mut result = 0;
+    for fan_in in level_fan_ins(depth, arity) {
+        let parents: BTreeSet<usize> = known.iter().map(|&p| p / fan_in).collect();
+        result += parents.len() * fan_in - known.len();
+        known = parents;
+    }
+    result
+}
+
 fn hash_2x1(v1: Digest256, v2: Digest256) -> Digest256 {
     Blake3_256::merge(&[v1, v2])
 }
@@ -327,3 +893,59 @@ pub fn random_blake3_merkle_tree(
         MerkleTree::<Blake3_256>::new(leaves).unwrap()
     })
 }
+
+pub fn random_blake3_kary_merkle_tree(
+    leave_count: usize,
+) -> impl Strategy<Value = MerkleTree<Blake3_256>> {
+    (
+        prop::collection::vec(any::<[u8; 32]>(), leave_count),
+        prop::sample::select(vec![4, 8, 16]),
+    )
+        .prop_map(|(leaves, arity)| {
+            let leaves = Digest256::bytes_as_digests(&leaves).to_vec();
+            MerkleTree::<Blake3_256>::with_arity(leaves, arity).unwrap()
+        })
+}
+
+#[cfg(feature = "mmap")]
+#[test]
+fn disk_merkle_tree() {
+    use std::fs::OpenOptions;
+
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let expected = MerkleTree::<Blake3_256>::new(leaves.clone()).unwrap();
+
+    let path = std::env::temp_dir().join(format!("winter_disk_merkle_{}", std::process::id()));
+    let file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(&path)
+        .unwrap();
+
+    for in_memory_depth in 1..=3 {
+        let tree =
+            DiskMerkleTree::<Blake3_256>::new(leaves.iter().cloned(), in_memory_depth, &file)
+                .unwrap();
+        assert_eq!(expected.root(), tree.root());
+        assert_eq!(expected.depth(), tree.depth());
+        for i in 0..8 {
+            assert_eq!(expected.prove(i).unwrap(), tree.prove(i).unwrap());
+        }
+        assert_eq!(
+            expected.prove_batch(&[6, 1, 2, 3]).unwrap(),
+            tree.prove_batch(&[6, 1, 2, 3]).unwrap()
+        );
+        assert_eq!(
+            Err(MerkleTreeError::LeafIndexOutOfBounds(8, 8)),
+            tree.prove(8)
+        );
+    }
+
+    let result = DiskMerkleTree::<Blake3_256>::new(leaves.iter().cloned(), 4, &file);
+    assert!(matches!(result, Err(MerkleTreeError::StorageError(_))));
+
+    drop(file);
+    std::fs::remove_file(path).unwrap();
+}
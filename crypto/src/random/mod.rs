@@ -6,7 +6,19 @@
 use crate::{errors::RandomCoinError, Digest, Hasher};
 use core::{convert::TryInto, marker::PhantomData};
 use math::{FieldElement, StarkField};
-use utils::collections::Vec;
+use sha3::{
+    digest::{ExtendableOutput, Update, XofReader},
+    Shake256, Shake256Reader,
+};
+use utils::{
+    collections::Vec, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+};
+
+mod transcript;
+pub use transcript::{Transcript, TranscriptEntry, TranscriptMismatch, TranscriptOp};
+
+#[cfg(test)]
+mod tests;
 
 // RANDOM COIN
 // ================================================================================================
@@ -24,7 +36,30 @@ use utils::collections::Vec;
 ///   If the resulting value is a valid field element, we return the result; otherwise we try
 ///   again until a valid element is found or the number of allowed tries is exceeded.
 /// - We can also re-seed the coin with a new value. During the reseeding procedure, the
-///   seed is set to hash(`old_seed` || `new_seed`), and the counter is reset to 0.
+///   seed is set to hash(`old_seed` || `new_seed`), and the counter is reset to 0. How exactly
+///   reseeding is performed depends on the [TranscriptMode] of the coin (see below).
+/// - Before drawing values for a specific purpose (e.g., an out-of-domain point, or a set of
+///   query positions), the coin can be reseeded with a domain separation label. This ensures
+///   that values drawn for different purposes are never derived from the same seed.
+///
+/// By default, the coin operates in [TranscriptMode::HashChain] mode in which every value used to
+/// reseed the coin is immediately merged into the seed. In [TranscriptMode::Sponge] mode, the
+/// coin behaves as a duplex sponge: reseeding values are absorbed into a buffer, and the buffer is
+/// merged into the seed with a single invocation of [Hasher::merge_many()] right before the next
+/// value is squeezed (drawn) from the coin. This reduces the number of hash function invocations,
+/// which is beneficial when the coin needs to be emulated inside an arithmetic circuit (e.g., for
+/// recursive proof verification).
+///
+/// In [TranscriptMode::Blake3Xof] and [TranscriptMode::ShakeXof] modes, reseeding works as in
+/// [TranscriptMode::HashChain] mode, but values are drawn from the output stream of an
+/// extendable-output function (BLAKE3 or SHAKE256 respectively) keyed with the current seed
+/// rather than by computing hash(`seed` || `counter`) for every draw. The stream is restarted
+/// whenever the seed changes. This makes drawing large numbers of values (e.g., hundreds of
+/// composition coefficients) considerably cheaper.
+///
+/// All draws are free of modulo bias: field elements and integers are drawn via rejection
+/// sampling, and thus, are distributed uniformly over the field and the specified range
+/// respectively (assuming the hash function behaves as a random oracle).
 ///
 /// # Examples
 /// ```
@@ -63,18 +98,34 @@ where
 {
     seed: H::Digest,
     counter: u64,
+    mode: TranscriptMode,
+    pending: Vec<H::Digest>,
+    stream: Option<XofStream>,
+    transcript: Option<Transcript>,
     _base_field: PhantomData<B>,
 }
 
 impl<B: StarkField, H: Hasher> RandomCoin<B, H> {
-    // CONSTRUCTOR
+    // CONSTRUCTORS
     // --------------------------------------------------------------------------------------------
     /// Returns a new random coin instantiated with the provided `seed`.
+    ///
+    /// The coin operates in [TranscriptMode::HashChain] mode.
     pub fn new(seed: &[u8]) -> Self {
+        Self::with_mode(seed, TranscriptMode::HashChain)
+    }
+
+    /// Returns a new random coin instantiated with the provided `seed` and operating in the
+    /// specified transcript `mode`.
+    pub fn with_mode(seed: &[u8], mode: TranscriptMode) -> Self {
         let seed = H::hash(seed);
         RandomCoin {
             seed,
             counter: 0,
+            mode,
+            pending: Vec::new(),
+            stream: None,
+            transcript: None,
             _base_field: PhantomData,
         }
     }
@@ -84,6 +135,9 @@ impl<B: StarkField, H: Hasher> RandomCoin<B, H> {
 
     /// Reseeds the coin with the specified data by setting the new seed to hash(`seed` || `data`).
     ///
+    /// In [TranscriptMode::Sponge] mode, the data is only absorbed into a buffer, and the seed is
+    /// updated right before the next value is drawn from the coin.
+    ///
     /// # Examples
     /// ```
     /// # use winter_crypto::{RandomCoin, Hasher, hashers::Blake3_256};
@@ -103,8 +157,11 @@ impl<B: StarkField, H: Hasher> RandomCoin<B, H> {
     /// assert_ne!(e1, e2);
     /// ```
     pub fn reseed(&mut self, data: H::Digest) {
-        self.seed = H::merge(&[self.seed, data]);
-        self.counter = 0;
+        match self.mode {
+            TranscriptMode::Sponge => self.pending.push(data),
+            _ => self.set_seed(H::merge(&[self.seed, data])),
+        }
+        self.record(TranscriptOp::Reseed, &data.as_bytes());
     }
 
     /// Reseeds the coin with the specified value by setting the new seed to hash(`seed` ||
@@ -129,13 +186,62 @@ impl<B: StarkField, H: Hasher> RandomCoin<B, H> {
     /// assert_ne!(e1, e2);
     /// ```
     pub fn reseed_with_int(&mut self, value: u64) {
-        self.seed = H::merge_with_int(self.seed, value);
-        self.counter = 0;
+        self.absorb_pending();
+        self.set_seed(H::merge_with_int(self.seed, value));
+        self.record(TranscriptOp::ReseedWithInt, &value.to_le_bytes());
+    }
+
+    /// Reseeds the coin with the specified domain separation `label` by setting the new seed to
+    /// hash(`seed` || hash(`label`)).
+    ///
+    /// Labels are intended to be applied right before values for a specific purpose are drawn
+    /// from the coin (e.g., `"ood-point"` or `"query-seed"`). This binds the drawn values to
+    /// their purpose, and makes the sequence of operations performed on the coin explicit.
+    ///
+    /// # Examples
+    /// ```
+    /// # use winter_crypto::{RandomCoin, hashers::Blake3_256};
+    /// # use math::fields::f128::BaseElement;
+    /// let mut coin1 = RandomCoin::<BaseElement, Blake3_256<BaseElement>>::new(&[1, 2, 3, 4]);
+    /// let mut coin2 = RandomCoin::<BaseElement, Blake3_256<BaseElement>>::new(&[1, 2, 3, 4]);
+    ///
+    /// // elements drawn after applying different labels should be different
+    /// coin1.reseed_with_label(b"ood-point");
+    /// coin2.reseed_with_label(b"fri-layer-0");
+    /// let e1 = coin1.draw::<BaseElement>().unwrap();
+    /// let e2 = coin2.draw::<BaseElement>().unwrap();
+    /// assert_ne!(e1, e2);
+    /// ```
+    pub fn reseed_with_label(&mut self, label: &[u8]) {
+        self.absorb_pending();
+        self.set_seed(H::merge(&[self.seed, H::hash(label)]));
+        self.record(TranscriptOp::Label, label);
+    }
+
+    /// Merges all values absorbed via [reseed()](RandomCoin::reseed) but not yet merged into the
+    /// seed by setting the new seed to hash(`seed` || `data_1` || ... || `data_n`).
+    ///
+    /// This is done automatically before any value is drawn from the coin; thus, this method
+    /// needs to be called explicitly only before repeated calls to
+    /// [check_leading_zeros()](RandomCoin::check_leading_zeros) to avoid merging the pending
+    /// values for every call. In modes other than [TranscriptMode::Sponge], this method does
+    /// nothing.
+    pub fn absorb_pending(&mut self) {
+        if !self.pending.is_empty() {
+            let seed = self.current_seed();
+            self.pending.clear();
+            self.set_seed(seed);
+        }
     }
 
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
+    /// Returns the transcript mode in which this coin operates.
+    pub fn mode(&self) -> TranscriptMode {
+        self.mode
+    }
+
     /// Returns the number of leading zeros in the seed if it is interpreted as an integer in
     /// big-endian byte order.
     ///
@@ -154,7 +260,7 @@ impl<B: StarkField, H: Hasher> RandomCoin<B, H> {
     /// assert!(coin.leading_zeros() >= 2);
     /// ```
     pub fn leading_zeros(&self) -> u32 {
-        let bytes = self.seed.as_bytes();
+        let bytes = self.current_seed().as_bytes();
         let seed_head = u64::from_le_bytes(bytes[..8].try_into().unwrap());
         seed_head.trailing_zeros()
     }
@@ -162,7 +268,7 @@ impl<B: StarkField, H: Hasher> RandomCoin<B, H> {
     /// Computes hash(`seed` || `value`) and returns the number of leading zeros in the resulting
     /// value if it is interpreted as an integer in big-endian byte order.
     pub fn check_leading_zeros(&self, value: u64) -> u32 {
-        let new_seed = H::merge_with_int(self.seed, value);
+        let new_seed = H::merge_with_int(self.current_seed(), value);
         let bytes = new_seed.as_bytes();
         let seed_head = u64::from_le_bytes(bytes[..8].try_into().unwrap());
         seed_head.trailing_zeros()
@@ -180,14 +286,17 @@ impl<B: StarkField, H: Hasher> RandomCoin<B, H> {
     where
         E: FieldElement<BaseField = B>,
     {
+        // all supported field elements (including extension field elements) fit into 32 bytes
+        let mut buffer = [0u8; 32];
+        let bytes = &mut buffer[..E::ELEMENT_BYTES];
         for _ in 0..1000 {
-            // get the next pseudo-random value and take the first ELEMENT_BYTES from it
-            let value = self.next();
-            let bytes = &value.as_bytes()[..E::ELEMENT_BYTES];
+            // get the next ELEMENT_BYTES pseudo-random bytes
+            self.next_bytes(bytes);
 
             // check if the bytes can be converted into a valid field element; if they can,
             // return; otherwise try again
             if let Some(element) = E::from_random_bytes(bytes) {
+                self.record(TranscriptOp::Draw, E::elements_as_bytes(&[element]));
                 return Ok(element);
             }
         }
@@ -221,14 +330,18 @@ impl<B: StarkField, H: Hasher> RandomCoin<B, H> {
 
     /// Returns a vector of unique integers selected from the range [0, domain_size).
     ///
+    /// Each value is drawn by taking as many low-order bits of a pseudo-random value as needed to
+    /// represent `domain_size - 1`, and rejecting the result if it falls outside of the range.
+    /// Thus, for domains which are powers of two, no values are ever rejected for being out of
+    /// range; the only values skipped are duplicates of values drawn earlier. For all other
+    /// domains, fewer than half of the draws are rejected on average.
+    ///
     /// # Errors
     /// Returns an error if the specified number of unique integers could not be generated
     /// after 1000 calls to the PRNG.
     ///
     /// # Panics
-    /// Panics if:
-    /// - `domain_size` is not a power of two.
-    /// - `num_values` is greater than or equal to `domain_size`.
+    /// Panics if `num_values` is greater than or equal to `domain_size`.
     ///
     /// # Examples
     /// ```
@@ -254,29 +367,29 @@ impl<B: StarkField, H: Hasher> RandomCoin<B, H> {
         num_values: usize,
         domain_size: usize,
     ) -> Result<Vec<usize>, RandomCoinError> {
-        assert!(
-            domain_size.is_power_of_two(),
-            "domain size must be a power of two"
-        );
         assert!(
             num_values < domain_size,
             "number of values must be smaller than domain size"
         );
 
         // determine how many bits are needed to represent valid values in the domain
-        let v_mask = (domain_size - 1) as u64;
+        let v_mask = (domain_size.next_power_of_two() - 1) as u64;
 
         // draw values from PRNG until we get as many unique values as specified by num_queries
         let mut values = Vec::new();
         for _ in 0..1000 {
-            // get the next pseudo-random value and read the first 8 bytes from it
-            let bytes: [u8; 8] = self.next().as_bytes()[..8].try_into().unwrap();
+            // get the next 8 pseudo-random bytes
+            let mut bytes = [0u8; 8];
+            self.next_bytes(&mut bytes);
 
             // convert to integer and limit the integer to the number of bits which can fit
             // into the specified domain
             let value = (u64::from_le_bytes(bytes) & v_mask) as usize;
 
-            if values.contains(&value) {
+            // reject values outside of the domain rather than reducing them to avoid modulo bias;
+            // when domain_size is a power of two, v_mask is exactly domain_size - 1 and so the
+            // first condition never holds - only duplicates are rejected in this case
+            if value >= domain_size || values.contains(&value) {
                 continue;
             }
             values.push(value);
@@ -293,15 +406,189 @@ impl<B: StarkField, H: Hasher> RandomCoin<B, H> {
             ));
         }
 
+        if self.transcript.is_some() {
+            let bytes: Vec<u8> = values
+                .iter()
+                .flat_map(|&value| (value as u64).to_le_bytes())
+                .collect();
+            self.record(TranscriptOp::Draw, &bytes);
+        }
+
         Ok(values)
     }
 
+    // TRANSCRIPT
+    // --------------------------------------------------------------------------------------------
+
+    /// Starts recording all operations performed on this coin into a [Transcript]; if a
+    /// transcript is already being recorded, it is discarded.
+    ///
+    /// Recording requires computing the seed of the coin after every operation, and thus, it
+    /// should be used for debugging purposes only.
+    ///
+    /// # Examples
+    /// ```
+    /// # use winter_crypto::{RandomCoin, TranscriptOp, hashers::Blake3_256};
+    /// # use math::fields::f128::BaseElement;
+    /// let mut coin1 = RandomCoin::<BaseElement, Blake3_256<BaseElement>>::new(&[1, 2, 3, 4]);
+    /// let mut coin2 = RandomCoin::<BaseElement, Blake3_256<BaseElement>>::new(&[1, 2, 3, 4]);
+    /// coin1.start_transcript();
+    /// coin2.start_transcript();
+    ///
+    /// coin1.reseed_with_int(1);
+    /// coin2.reseed_with_int(2);
+    /// coin1.draw::<BaseElement>().unwrap();
+    /// coin2.draw::<BaseElement>().unwrap();
+    ///
+    /// // the transcripts diverge after the coins were reseeded with different values
+    /// let transcript1 = coin1.take_transcript().unwrap();
+    /// let transcript2 = coin2.take_transcript().unwrap();
+    /// assert_eq!(1, transcript1.challenges().count());
+    /// let mismatch = transcript1.diff(&transcript2).unwrap();
+    /// assert_eq!(1, mismatch.index);
+    /// assert_eq!(TranscriptOp::ReseedWithInt, mismatch.expected.unwrap().op);
+    /// ```
+    pub fn start_transcript(&mut self) {
+        self.transcript = Some(Transcript::new());
+        self.record(TranscriptOp::Start, &[]);
+    }
+
+    /// Stops recording operations performed on this coin and returns the recorded transcript, or
+    /// None if the transcript was not being recorded.
+    pub fn take_transcript(&mut self) -> Option<Transcript> {
+        self.transcript.take()
+    }
+
     // HELPER METHODS
     // --------------------------------------------------------------------------------------------
 
-    /// Updates the state by incrementing the counter and returns hash(seed || counter)
-    fn next(&mut self) -> H::Digest {
-        self.counter += 1;
-        H::merge_with_int(self.seed, self.counter)
+    /// Appends an entry for the specified operation to the transcript, if one is being recorded.
+    fn record(&mut self, op: TranscriptOp, data: &[u8]) {
+        if self.transcript.is_some() {
+            let seed = self.current_seed().as_bytes().to_vec();
+            let entry = TranscriptEntry {
+                op,
+                data: data.to_vec(),
+                seed,
+            };
+            if let Some(transcript) = self.transcript.as_mut() {
+                transcript.push(entry);
+            }
+        }
+    }
+
+    /// Returns the seed with all pending values merged into it.
+    fn current_seed(&self) -> H::Digest {
+        if self.pending.is_empty() {
+            return self.seed;
+        }
+        let mut values = Vec::with_capacity(self.pending.len() + 1);
+        values.push(self.seed);
+        values.extend_from_slice(&self.pending);
+        H::merge_many(&values)
+    }
+
+    /// Sets the seed to the specified value and resets the state derived from the old seed.
+    fn set_seed(&mut self, seed: H::Digest) {
+        self.seed = seed;
+        self.counter = 0;
+        self.stream = None;
+    }
+
+    /// Fills `bytes` with the next pseudo-random bytes.
+    ///
+    /// In XOF modes, the bytes are read from the output stream keyed with the seed; otherwise,
+    /// the counter is incremented and the bytes are taken from the start of hash(seed || counter).
+    fn next_bytes(&mut self, bytes: &mut [u8]) {
+        self.absorb_pending();
+        let seed = self.seed;
+        match self.mode {
+            TranscriptMode::Blake3Xof => self
+                .stream
+                .get_or_insert_with(|| {
+                    let mut hasher = blake3::Hasher::new();
+                    hasher.update(&seed.as_bytes());
+                    XofStream::Blake3(hasher.finalize_xof())
+                })
+                .read(bytes),
+            TranscriptMode::ShakeXof => self
+                .stream
+                .get_or_insert_with(|| {
+                    let mut hasher = Shake256::default();
+                    hasher.update(&seed.as_bytes());
+                    XofStream::Shake(hasher.finalize_xof())
+                })
+                .read(bytes),
+            _ => {
+                self.counter += 1;
+                let value = H::merge_with_int(self.seed, self.counter);
+                bytes.copy_from_slice(&value.as_bytes()[..bytes.len()]);
+            }
+        }
+    }
+}
+
+// XOF STREAM
+// ================================================================================================
+
+/// Output stream of an extendable-output function used by a [RandomCoin] in XOF modes.
+///
+/// A coin holds at most one stream, and thus, the size difference between variants is not a
+/// concern.
+#[allow(clippy::large_enum_variant)]
+enum XofStream {
+    Blake3(blake3::OutputReader),
+    Shake(Shake256Reader),
+}
+
+impl XofStream {
+    /// Fills `bytes` with the next bytes of the stream.
+    fn read(&mut self, bytes: &mut [u8]) {
+        match self {
+            XofStream::Blake3(reader) => reader.fill(bytes),
+            XofStream::Shake(reader) => reader.read(bytes),
+        }
+    }
+}
+
+// TRANSCRIPT MODE
+// ================================================================================================
+
+/// Defines how values used to reseed a [RandomCoin] are merged into its seed.
+#[repr(u8)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum TranscriptMode {
+    /// Every value is merged into the seed as soon as it is provided to the coin.
+    HashChain = 0,
+    /// Values are buffered and are merged into the seed all at once right before the next value
+    /// is drawn from the coin.
+    Sponge = 1,
+    /// Values are merged into the seed as in [TranscriptMode::HashChain] mode, and pseudo-random
+    /// values are read from the BLAKE3 extendable output keyed with the seed.
+    Blake3Xof = 2,
+    /// Values are merged into the seed as in [TranscriptMode::HashChain] mode, and pseudo-random
+    /// values are read from the SHAKE256 extendable output keyed with the seed.
+    ShakeXof = 3,
+}
+
+impl Serializable for TranscriptMode {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(*self as u8);
+    }
+}
+
+impl Deserializable for TranscriptMode {
+    /// Reads a transcript mode enum from the specified `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            0 => Ok(TranscriptMode::HashChain),
+            1 => Ok(TranscriptMode::Sponge),
+            2 => Ok(TranscriptMode::Blake3Xof),
+            3 => Ok(TranscriptMode::ShakeXof),
+            value => Err(DeserializationError::InvalidValue(format!(
+                "value {value} cannot be deserialized as TranscriptMode enum"
+            ))),
+        }
     }
 }
@@ -0,0 +1,149 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{RandomCoin, TranscriptMode};
+use crate::hash::{Blake3_256, Hasher};
+use math::{fields::f128::BaseElement, StarkField};
+
+type Coin = RandomCoin<BaseElement, Blake3_256<BaseElement>>;
+
+#[test]
+fn reseed_with_label() {
+    // the same label applied to coins in the same state should result in the same draws
+    let mut coin1 = Coin::new(&[1, 2, 3, 4]);
+    let mut coin2 = Coin::new(&[1, 2, 3, 4]);
+    coin1.reseed_with_label(b"ood-point");
+    coin2.reseed_with_label(b"ood-point");
+    assert_eq!(
+        coin1.draw::<BaseElement>().unwrap(),
+        coin2.draw::<BaseElement>().unwrap()
+    );
+
+    // different labels should result in different draws
+    let mut coin1 = Coin::new(&[1, 2, 3, 4]);
+    let mut coin2 = Coin::new(&[1, 2, 3, 4]);
+    coin1.reseed_with_label(b"fri-layer-0");
+    coin2.reseed_with_label(b"fri-layer-1");
+    assert_ne!(
+        coin1.draw::<BaseElement>().unwrap(),
+        coin2.draw::<BaseElement>().unwrap()
+    );
+
+    // a labeled draw should differ from an unlabeled one
+    let mut coin1 = Coin::new(&[1, 2, 3, 4]);
+    let mut coin2 = Coin::new(&[1, 2, 3, 4]);
+    coin1.reseed_with_label(b"query-seed");
+    assert_ne!(
+        coin1.draw::<BaseElement>().unwrap(),
+        coin2.draw::<BaseElement>().unwrap()
+    );
+}
+
+#[test]
+fn draw_integers_power_of_two_domain() {
+    let mut coin = Coin::new(&[1, 2, 3, 4]);
+    let values = coin.draw_integers(32, 64).unwrap();
+    assert_eq!(32, values.len());
+    assert!(values.iter().all(|&value| value < 64));
+}
+
+#[test]
+fn draw_integers_arbitrary_domain() {
+    // values outside of the domain are rejected rather than reduced, and thus, no value should
+    // fall outside of the domain
+    let mut coin = Coin::new(&[1, 2, 3, 4]);
+    let mut values = coin.draw_integers(50, 100).unwrap();
+    assert!(values.iter().all(|&value| value < 100));
+
+    // all values should be unique
+    values.sort_unstable();
+    values.dedup();
+    assert_eq!(50, values.len());
+}
+
+#[test]
+fn sponge_transcript() {
+    type Blake3 = Blake3_256<BaseElement>;
+    let d1 = Blake3::hash(&[1, 2, 3]);
+    let d2 = Blake3::hash(&[4, 5, 6]);
+
+    // in sponge mode, values are merged into the seed all at once before the next draw
+    let mut sponge = Coin::with_mode(&[1, 2, 3, 4], TranscriptMode::Sponge);
+    sponge.reseed(d1);
+    sponge.reseed(d2);
+
+    let mut expected = Coin::new(&[1, 2, 3, 4]);
+    expected.seed = Blake3::merge_many(&[expected.seed, d1, d2]);
+    assert_eq!(
+        expected.draw::<BaseElement>().unwrap(),
+        sponge.draw::<BaseElement>().unwrap()
+    );
+
+    // this is different from the hash chain mode where values are merged one at a time
+    let mut chain = Coin::new(&[1, 2, 3, 4]);
+    chain.reseed(d1);
+    chain.reseed(d2);
+    let mut sponge = Coin::with_mode(&[1, 2, 3, 4], TranscriptMode::Sponge);
+    sponge.reseed(d1);
+    sponge.reseed(d2);
+    assert_ne!(
+        chain.draw::<BaseElement>().unwrap(),
+        sponge.draw::<BaseElement>().unwrap()
+    );
+
+    // pending values should be taken into account when checking leading zeros
+    let mut sponge = Coin::with_mode(&[1, 2, 3, 4], TranscriptMode::Sponge);
+    sponge.reseed(d1);
+    let leading_zeros = sponge.check_leading_zeros(42);
+    sponge.absorb_pending();
+    assert_eq!(leading_zeros, sponge.check_leading_zeros(42));
+}
+
+#[test]
+fn xof_transcript() {
+    for mode in [TranscriptMode::Blake3Xof, TranscriptMode::ShakeXof] {
+        // coins in the same state should draw the same values
+        let mut coin1 = Coin::with_mode(&[1, 2, 3, 4], mode);
+        let mut coin2 = Coin::with_mode(&[1, 2, 3, 4], mode);
+        let values1 = (0..100)
+            .map(|_| coin1.draw::<BaseElement>().unwrap())
+            .collect::<Vec<_>>();
+        let values2 = (0..100)
+            .map(|_| coin2.draw::<BaseElement>().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(values1, values2);
+
+        // values drawn from the stream should be different from each other
+        let mut unique = values1.clone();
+        unique.sort_by_key(|value| value.as_int());
+        unique.dedup();
+        assert_eq!(values1.len(), unique.len());
+
+        // reseeding should restart the stream from the new seed
+        let d1 = Blake3_256::<BaseElement>::hash(&[4, 5, 6]);
+        coin1.reseed(d1);
+        coin2.reseed(d1);
+        let mut coin3 = Coin::with_mode(&[1, 2, 3, 4], mode);
+        coin3.reseed(d1);
+        let value = coin3.draw::<BaseElement>().unwrap();
+        assert_eq!(value, coin1.draw::<BaseElement>().unwrap());
+        assert_eq!(value, coin2.draw::<BaseElement>().unwrap());
+
+        // integers should be drawn from the stream as well
+        let values = coin1.draw_integers(32, 100).unwrap();
+        assert!(values.iter().all(|&value| value < 100));
+    }
+
+    // XOF draws should differ from hash chain draws and between XOFs
+    let mut chain = Coin::new(&[1, 2, 3, 4]);
+    let mut blake3 = Coin::with_mode(&[1, 2, 3, 4], TranscriptMode::Blake3Xof);
+    let mut shake = Coin::with_mode(&[1, 2, 3, 4], TranscriptMode::ShakeXof);
+    let e1 = chain.draw::<BaseElement>().unwrap();
+    let e2 = blake3.draw::<BaseElement>().unwrap();
+    let e3 = shake.draw::<BaseElement>().unwrap();
+    assert_ne!(e1, e2);
+    assert_ne!(e1, e3);
+    assert_ne!(e2, e3);
+}
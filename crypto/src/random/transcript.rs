@@ -0,0 +1,246 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use core::fmt;
+use utils::{
+    collections::Vec, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+};
+
+// TRANSCRIPT
+// ================================================================================================
+
+/// A record of all operations performed on a [RandomCoin](super::RandomCoin).
+///
+/// A transcript is recorded by a coin after [start_transcript()](super::RandomCoin::start_transcript)
+/// is called on it. Every entry of the transcript contains the operation performed on the coin,
+/// the data passed to or returned by the operation, and the seed of the coin after the operation.
+/// Thus, comparing the transcripts recorded by the prover and the verifier via
+/// [diff()](Transcript::diff) identifies the first operation after which their coins diverged.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Transcript {
+    entries: Vec<TranscriptEntry>,
+}
+
+impl Transcript {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new empty transcript.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends the specified entry to the end of this transcript.
+    pub fn push(&mut self, entry: TranscriptEntry) {
+        self.entries.push(entry);
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns entries of this transcript in the order in which they were recorded.
+    pub fn entries(&self) -> &[TranscriptEntry] {
+        &self.entries
+    }
+
+    /// Returns the number of entries in this transcript.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if this transcript does not contain any entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns an iterator over the values drawn from the coin (i.e., the challenges).
+    pub fn challenges(&self) -> impl Iterator<Item = &TranscriptEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.op == TranscriptOp::Draw)
+    }
+
+    // DIFF
+    // --------------------------------------------------------------------------------------------
+
+    /// Compares this transcript against the `other` transcript and returns the first entry at
+    /// which they differ, or None if the transcripts are identical.
+    ///
+    /// This transcript is treated as the expected one; thus, when comparing transcripts recorded
+    /// by the prover and the verifier, the prover's transcript should usually be the receiver.
+    pub fn diff(&self, other: &Transcript) -> Option<TranscriptMismatch> {
+        let num_entries = core::cmp::max(self.len(), other.len());
+        (0..num_entries).find_map(|index| {
+            let expected = self.entries.get(index);
+            let actual = other.entries.get(index);
+            if expected == actual {
+                return None;
+            }
+            Some(TranscriptMismatch {
+                index,
+                expected: expected.cloned(),
+                actual: actual.cloned(),
+            })
+        })
+    }
+}
+
+impl fmt::Display for Transcript {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, entry) in self.entries.iter().enumerate() {
+            writeln!(f, "{index:>4}: {entry}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Serializable for Transcript {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_usize(self.entries.len());
+        for entry in self.entries.iter() {
+            target.write_u8(entry.op as u8);
+            target.write_usize(entry.data.len());
+            target.write_u8_slice(&entry.data);
+            target.write_usize(entry.seed.len());
+            target.write_u8_slice(&entry.seed);
+        }
+    }
+}
+
+impl Deserializable for Transcript {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let num_entries = source.read_usize()?;
+        let mut entries = Vec::new();
+        for _ in 0..num_entries {
+            let op = TranscriptOp::read_from(source)?;
+            let num_data_bytes = source.read_usize()?;
+            let data = source.read_u8_vec(num_data_bytes)?;
+            let num_seed_bytes = source.read_usize()?;
+            let seed = source.read_u8_vec(num_seed_bytes)?;
+            entries.push(TranscriptEntry { op, data, seed });
+        }
+        Ok(Self { entries })
+    }
+}
+
+// TRANSCRIPT ENTRY
+// ================================================================================================
+
+/// A single operation performed on a [RandomCoin](super::RandomCoin).
+///
+/// When `secret-hygiene` feature is enabled, data and seeds of transcript entries are compared in
+/// constant time.
+#[cfg_attr(not(feature = "secret-hygiene"), derive(PartialEq))]
+#[derive(Debug, Clone, Eq)]
+pub struct TranscriptEntry {
+    /// The operation performed on the coin.
+    pub op: TranscriptOp,
+    /// Data of the operation: the reseeding digest, the little-endian bytes of the reseeding
+    /// integer, the domain separation label, or the bytes of the drawn values. Empty for
+    /// [TranscriptOp::Start].
+    pub data: Vec<u8>,
+    /// Seed of the coin after the operation (with all values pending in the sponge merged into
+    /// it).
+    pub seed: Vec<u8>,
+}
+
+#[cfg(feature = "secret-hygiene")]
+impl PartialEq for TranscriptEntry {
+    fn eq(&self, other: &Self) -> bool {
+        // both comparisons are always evaluated so that the result does not reveal which of them
+        // failed
+        let data_eq = utils::ct_eq(&self.data, &other.data);
+        let seed_eq = utils::ct_eq(&self.seed, &other.seed);
+        (self.op == other.op) & data_eq & seed_eq
+    }
+}
+
+impl fmt::Display for TranscriptEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self.op)?;
+        match core::str::from_utf8(&self.data) {
+            Ok(label) if self.op == TranscriptOp::Label => write!(f, " \"{label}\"")?,
+            _ if !self.data.is_empty() => {
+                f.write_str(" 0x")?;
+                write_hex(f, &self.data)?;
+            }
+            _ => (),
+        }
+        f.write_str(" -> seed 0x")?;
+        write_hex(f, &self.seed)
+    }
+}
+
+// TRANSCRIPT OPERATION
+// ================================================================================================
+
+/// Types of operations recorded in a [Transcript].
+#[repr(u8)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TranscriptOp {
+    /// Recording of the transcript was started.
+    Start = 0,
+    /// The coin was reseeded with a digest.
+    Reseed = 1,
+    /// The coin was reseeded with an integer (e.g., a proof-of-work nonce).
+    ReseedWithInt = 2,
+    /// The coin was reseeded with a domain separation label.
+    Label = 3,
+    /// Values were drawn from the coin; the values are serialized in the entry data as field
+    /// elements or as 8-byte little-endian integers.
+    Draw = 4,
+}
+
+impl Deserializable for TranscriptOp {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            0 => Ok(Self::Start),
+            1 => Ok(Self::Reseed),
+            2 => Ok(Self::ReseedWithInt),
+            3 => Ok(Self::Label),
+            4 => Ok(Self::Draw),
+            value => Err(DeserializationError::InvalidValue(format!(
+                "value {value} cannot be deserialized as TranscriptOp enum"
+            ))),
+        }
+    }
+}
+
+// TRANSCRIPT MISMATCH
+// ================================================================================================
+
+/// The first entry at which two transcripts differ.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TranscriptMismatch {
+    /// Index of the entry.
+    pub index: usize,
+    /// Entry of the expected transcript, or None if the expected transcript is shorter.
+    pub expected: Option<TranscriptEntry>,
+    /// Entry of the actual transcript, or None if the actual transcript is shorter.
+    pub actual: Option<TranscriptEntry>,
+}
+
+impl fmt::Display for TranscriptMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "transcripts diverge at entry {}:", self.index)?;
+        match &self.expected {
+            Some(entry) => writeln!(f, "  expected: {entry}")?,
+            None => writeln!(f, "  expected: end of transcript")?,
+        }
+        match &self.actual {
+            Some(entry) => write!(f, "    actual: {entry}"),
+            None => write!(f, "    actual: end of transcript"),
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn write_hex(f: &mut fmt::Formatter<'_>, bytes: &[u8]) -> fmt::Result {
+    for byte in bytes {
+        write!(f, "{byte:02x}")?;
+    }
+    Ok(())
+}
@@ -20,8 +20,10 @@ fn fibonacci(c: &mut Criterion) {
     let options = ProofOptions::new(32, 8, 0, FieldExtension::None, 4, 256);
 
     for &size in SIZES.iter() {
-        let fib =
-            fibonacci::fib2::FibExample::<Blake3_256<BaseElement>>::new(size, options.clone());
+        let fib = fibonacci::fib2::FibExample::<BaseElement, Blake3_256<BaseElement>>::new(
+            size,
+            options.clone(),
+        );
         group.bench_function(BenchmarkId::from_parameter(size), |bench| {
             bench.iter(|| fib.prove());
         });
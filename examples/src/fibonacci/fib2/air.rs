@@ -3,7 +3,7 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{BaseElement, FieldElement, ProofOptions, TRACE_WIDTH};
+use super::{ExtensibleField, FieldElement, ProofOptions, StarkField, TRACE_WIDTH};
 use crate::utils::are_equal;
 use winterfell::{
     Air, AirContext, Assertion, EvaluationFrame, TraceInfo, TransitionConstraintDegree,
@@ -12,14 +12,17 @@ use winterfell::{
 // FIBONACCI AIR
 // ================================================================================================
 
-pub struct FibAir {
-    context: AirContext<BaseElement>,
-    result: BaseElement,
+pub struct FibAir<B: StarkField> {
+    context: AirContext<B>,
+    result: B,
 }
 
-impl Air for FibAir {
-    type BaseField = BaseElement;
-    type PublicInputs = BaseElement;
+impl<B> Air for FibAir<B>
+where
+    B: StarkField + ExtensibleField<2> + ExtensibleField<3>,
+{
+    type BaseField = B;
+    type PublicInputs = B;
 
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
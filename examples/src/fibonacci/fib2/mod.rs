@@ -4,13 +4,16 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::utils::compute_fib_term;
-use crate::{Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Sha3_256};
+use crate::{
+    instantiation::{GenericExample, Instantiation},
+    Example, ExampleOptions,
+};
 use core::marker::PhantomData;
 use log::debug;
 use std::time::Instant;
 use winterfell::{
     crypto::ElementHasher,
-    math::{fields::f128::BaseElement, log2, FieldElement},
+    math::{log2, ExtensibleField, FieldElement, StarkField},
     ProofOptions, Prover, StarkProof, Trace, TraceTable, VerifierError,
 };
 
@@ -31,37 +34,44 @@ const TRACE_WIDTH: usize = 2;
 // FIBONACCI EXAMPLE
 // ================================================================================================
 
+/// Builds the example in the field and with the hash function selected by the `options`.
 pub fn get_example(
     options: &ExampleOptions,
     sequence_length: usize,
 ) -> Result<Box<dyn Example>, String> {
-    let (options, hash_fn) = options.to_proof_options(28, 8);
+    let (proof_options, hash_fn) = options.to_proof_options(28, 8);
+    let instantiation = Instantiation::new(options.field(), hash_fn)?;
 
-    match hash_fn {
-        HashFunction::Blake3_192 => Ok(Box::new(FibExample::<Blake3_192>::new(
-            sequence_length,
-            options,
-        ))),
-        HashFunction::Blake3_256 => Ok(Box::new(FibExample::<Blake3_256>::new(
-            sequence_length,
-            options,
-        ))),
-        HashFunction::Sha3_256 => Ok(Box::new(FibExample::<Sha3_256>::new(
-            sequence_length,
-            options,
-        ))),
-        _ => Err("The specified hash function cannot be used with this example.".to_string()),
+    Ok(instantiation.build(FibBuilder {
+        sequence_length,
+        options: proof_options,
+    }))
+}
+
+/// Parameters of the example which do not depend on the field or the hash function.
+pub struct FibBuilder {
+    pub sequence_length: usize,
+    pub options: ProofOptions,
+}
+
+impl GenericExample for FibBuilder {
+    fn build<B, H>(self) -> Box<dyn Example>
+    where
+        B: StarkField + ExtensibleField<2> + ExtensibleField<3> + 'static,
+        H: ElementHasher<BaseField = B> + 'static,
+    {
+        Box::new(FibExample::<B, H>::new(self.sequence_length, self.options))
     }
 }
 
-pub struct FibExample<H: ElementHasher> {
+pub struct FibExample<B: StarkField, H: ElementHasher> {
     options: ProofOptions,
     sequence_length: usize,
-    result: BaseElement,
+    result: B,
     _hasher: PhantomData<H>,
 }
 
-impl<H: ElementHasher> FibExample<H> {
+impl<B: StarkField, H: ElementHasher> FibExample<B, H> {
     pub fn new(sequence_length: usize, options: ProofOptions) -> Self {
         assert!(
             sequence_length.is_power_of_two(),
@@ -70,7 +80,7 @@ impl<H: ElementHasher> FibExample<H> {
 
         // compute Fibonacci sequence
         let now = Instant::now();
-        let result = compute_fib_term(sequence_length);
+        let result = compute_fib_term::<B>(sequence_length);
         debug!(
             "Computed Fibonacci sequence up to {}th term in {} ms",
             sequence_length,
@@ -89,9 +99,10 @@ impl<H: ElementHasher> FibExample<H> {
 // EXAMPLE IMPLEMENTATION
 // ================================================================================================
 
-impl<H: ElementHasher> Example for FibExample<H>
+impl<B, H> Example for FibExample<B, H>
 where
-    H: ElementHasher<BaseField = BaseElement>,
+    B: StarkField + ExtensibleField<2> + ExtensibleField<3>,
+    H: ElementHasher<BaseField = B>,
 {
     fn prove(&self) -> StarkProof {
         debug!(
@@ -101,7 +112,7 @@ where
         );
 
         // create a prover
-        let prover = FibProver::<H>::new(self.options.clone());
+        let prover = FibProver::<B, H>::new(self.options.clone());
 
         // generate execution trace
         let now = Instant::now();
@@ -121,10 +132,10 @@ where
     }
 
     fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
-        winterfell::verify::<FibAir, H>(proof, self.result)
+        winterfell::verify::<FibAir<B>, H>(proof, self.result)
     }
 
     fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
-        winterfell::verify::<FibAir, H>(proof, self.result + BaseElement::ONE)
+        winterfell::verify::<FibAir<B>, H>(proof, self.result + B::ONE)
     }
 }
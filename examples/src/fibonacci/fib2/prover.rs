@@ -4,29 +4,31 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{
-    BaseElement, ElementHasher, FibAir, FieldElement, PhantomData, ProofOptions, Prover, Trace,
+    ElementHasher, ExtensibleField, FibAir, PhantomData, ProofOptions, Prover, StarkField, Trace,
     TraceTable, TRACE_WIDTH,
 };
 
 // FIBONACCI PROVER
 // ================================================================================================
 
-pub struct FibProver<H: ElementHasher> {
+pub struct FibProver<B: StarkField, H: ElementHasher> {
     options: ProofOptions,
+    _field: PhantomData<B>,
     _hasher: PhantomData<H>,
 }
 
-impl<H: ElementHasher> FibProver<H> {
+impl<B: StarkField, H: ElementHasher> FibProver<B, H> {
     pub fn new(options: ProofOptions) -> Self {
         Self {
             options,
+            _field: PhantomData,
             _hasher: PhantomData,
         }
     }
 
     /// Builds an execution trace for computing a Fibonacci sequence of the specified length such
     /// that each row advances the sequence by 2 terms.
-    pub fn build_trace(&self, sequence_length: usize) -> TraceTable<BaseElement> {
+    pub fn build_trace(&self, sequence_length: usize) -> TraceTable<B> {
         assert!(
             sequence_length.is_power_of_two(),
             "sequence length must be a power of 2"
@@ -35,8 +37,8 @@ impl<H: ElementHasher> FibProver<H> {
         let mut trace = TraceTable::new(TRACE_WIDTH, sequence_length / 2);
         trace.fill(
             |state| {
-                state[0] = BaseElement::ONE;
-                state[1] = BaseElement::ONE;
+                state[0] = B::ONE;
+                state[1] = B::ONE;
             },
             |_, state| {
                 state[0] += state[1];
@@ -48,16 +50,17 @@ impl<H: ElementHasher> FibProver<H> {
     }
 }
 
-impl<H: ElementHasher> Prover for FibProver<H>
+impl<B, H> Prover for FibProver<B, H>
 where
-    H: ElementHasher<BaseField = BaseElement>,
+    B: StarkField + ExtensibleField<2> + ExtensibleField<3>,
+    H: ElementHasher<BaseField = B>,
 {
-    type BaseField = BaseElement;
-    type Air = FibAir;
-    type Trace = TraceTable<BaseElement>;
+    type BaseField = B;
+    type Air = FibAir<B>;
+    type Trace = TraceTable<B>;
     type HashFn = H;
 
-    fn get_pub_inputs(&self, trace: &Self::Trace) -> BaseElement {
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> B {
         let last_step = trace.length() - 1;
         trace.get(1, last_step)
     }
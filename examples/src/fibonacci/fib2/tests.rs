@@ -3,11 +3,16 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{super::utils::build_proof_options, Blake3_256};
+use super::{super::utils::build_proof_options, FibBuilder};
+use crate::{
+    instantiation::{GenericExample, Instantiation},
+    Blake3_256, FieldType, HashFunction,
+};
+use winterfell::math::fields::{f128::BaseElement, f64::BaseElement as SmallElement};
 
 #[test]
 fn fib2_test_basic_proof_verification() {
-    let fib = Box::new(super::FibExample::<Blake3_256>::new(
+    let fib = Box::new(super::FibExample::<BaseElement, Blake3_256>::new(
         16,
         build_proof_options(false),
     ));
@@ -16,7 +21,7 @@ fn fib2_test_basic_proof_verification() {
 
 #[test]
 fn fib2_test_basic_proof_verification_extension() {
-    let fib = Box::new(super::FibExample::<Blake3_256>::new(
+    let fib = Box::new(super::FibExample::<BaseElement, Blake3_256>::new(
         16,
         build_proof_options(true),
     ));
@@ -25,9 +30,140 @@ fn fib2_test_basic_proof_verification_extension() {
 
 #[test]
 fn fib2_test_basic_proof_verification_fail() {
-    let fib = Box::new(super::FibExample::<Blake3_256>::new(
+    let fib = Box::new(super::FibExample::<BaseElement, Blake3_256>::new(
         16,
         build_proof_options(false),
     ));
     crate::tests::test_basic_proof_verification_fail(fib);
 }
+
+#[test]
+fn fib2_test_trace_is_fully_constrained() {
+    use super::{FibAir, FibProver};
+    use winterfell::{Air, Prover, Trace};
+
+    let prover = FibProver::<BaseElement, Blake3_256>::new(build_proof_options(false));
+    let trace = prover.build_trace(16);
+    let air = FibAir::new(
+        trace.get_info(),
+        prover.get_pub_inputs(&trace),
+        build_proof_options(false),
+    );
+    assert!(trace.find_unconstrained_cells(&air).is_empty());
+}
+
+#[test]
+fn fib2_test_basic_proof_verification_small_field() {
+    type Rp64_256 = winterfell::crypto::hashers::Rp64_256;
+
+    let fib = FibBuilder {
+        sequence_length: 16,
+        options: build_proof_options(true),
+    };
+    crate::tests::test_basic_proof_verification(fib.build::<SmallElement, Rp64_256>());
+}
+
+#[test]
+fn fib2_test_instantiations() {
+    for (field, hash_fn) in [
+        (FieldType::F128, HashFunction::Sha3_256),
+        (FieldType::F64, HashFunction::Blake3_256),
+        (FieldType::F64, HashFunction::RpJive64_256),
+    ] {
+        let instantiation = Instantiation::new(field, hash_fn).unwrap();
+        assert_eq!(field, instantiation.field());
+        let fib = FibBuilder {
+            sequence_length: 16,
+            options: build_proof_options(false),
+        };
+        crate::tests::test_basic_proof_verification_fail(instantiation.build(fib));
+    }
+
+    // hash functions defined over f64 field cannot be used with f128 field
+    assert!(Instantiation::new(FieldType::F128, HashFunction::Rp64_256).is_err());
+}
+
+#[test]
+fn fib2_test_auto_blowup_factor() {
+    use crate::Example;
+
+    // constraints of this computation are of degree 1, and thus, the smallest blowup factor of
+    // 2 is selected; to preserve security of the reference blowup factor of 8, the number of
+    // queries is tripled
+    let fib = super::FibExample::<BaseElement, Blake3_256>::new(
+        256,
+        build_proof_options(false).with_auto_blowup_factor(),
+    );
+    let proof = fib.prove();
+    assert_eq!(2, proof.options().blowup_factor());
+    assert_eq!(84, proof.options().num_queries());
+    assert!(!proof.options().is_blowup_factor_auto());
+    assert!(fib.verify(proof).is_ok());
+}
+
+#[test]
+fn fib2_test_trace_rows_per_leaf() {
+    let options = build_proof_options(false).with_trace_rows_per_leaf(4);
+    let fib = Box::new(super::FibExample::<BaseElement, Blake3_256>::new(
+        64,
+        options.clone(),
+    ));
+    crate::tests::test_basic_proof_verification(fib);
+
+    let fib = Box::new(super::FibExample::<BaseElement, Blake3_256>::new(
+        64, options,
+    ));
+    crate::tests::test_basic_proof_verification_fail(fib);
+}
+
+#[test]
+fn fib2_test_inconsistent_proof_context() {
+    use super::{FibAir, FibProver};
+    use winterfell::{
+        math::FieldElement, Air, AirContext, Assertion, ContextMismatch, EvaluationFrame,
+        FieldExtension, ProofOptions, Prover, TraceInfo, VerifierError,
+    };
+
+    // an AIR which ignores the proof options it is instantiated with
+    struct PinnedOptionsAir(FibAir<BaseElement>);
+
+    impl Air for PinnedOptionsAir {
+        type BaseField = BaseElement;
+        type PublicInputs = BaseElement;
+
+        fn new(trace_info: TraceInfo, pub_inputs: BaseElement, _options: ProofOptions) -> Self {
+            let options = ProofOptions::new(28, 16, 0, FieldExtension::None, 4, 256);
+            Self(FibAir::new(trace_info, pub_inputs, options))
+        }
+
+        fn context(&self) -> &AirContext<BaseElement> {
+            self.0.context()
+        }
+
+        fn evaluate_transition<E: FieldElement<BaseField = BaseElement>>(
+            &self,
+            frame: &EvaluationFrame<E>,
+            periodic_values: &[E],
+            result: &mut [E],
+        ) {
+            self.0.evaluate_transition(frame, periodic_values, result)
+        }
+
+        fn get_assertions(&self) -> Vec<Assertion<BaseElement>> {
+            self.0.get_assertions()
+        }
+    }
+
+    let prover = FibProver::<BaseElement, Blake3_256>::new(build_proof_options(false));
+    let trace = prover.build_trace(16);
+    let result = prover.get_pub_inputs(&trace);
+    let proof = prover.prove(trace).unwrap();
+
+    assert_eq!(
+        Err(VerifierError::InconsistentProofContext(
+            ContextMismatch::Options
+        )),
+        winterfell::verify::<PinnedOptionsAir, Blake3_256>(proof.clone(), result)
+    );
+    assert!(winterfell::verify::<FibAir<BaseElement>, Blake3_256>(proof, result).is_ok());
+}
@@ -30,10 +30,13 @@ const TRACE_WIDTH: usize = 2;
 
 type Blake3_192 = winterfell::crypto::hashers::Blake3_192<BaseElement>;
 type Blake3_256 = winterfell::crypto::hashers::Blake3_256<BaseElement>;
+type Sha2_256 = winterfell::crypto::hashers::Sha2_256<BaseElement>;
 type Sha3_256 = winterfell::crypto::hashers::Sha3_256<BaseElement>;
+type Keccak256 = winterfell::crypto::hashers::Keccak256<BaseElement>;
 type Rp64_256 = winterfell::crypto::hashers::Rp64_256;
 type RpJive64_256 = winterfell::crypto::hashers::RpJive64_256;
 type GriffinJive64_256 = winterfell::crypto::hashers::GriffinJive64_256;
+type Poseidon64_256 = winterfell::crypto::hashers::Poseidon64_256;
 
 // FIBONACCI EXAMPLE
 // ================================================================================================
@@ -53,10 +56,18 @@ pub fn get_example(
             sequence_length,
             options,
         ))),
+        HashFunction::Sha2_256 => Ok(Box::new(FibExample::<Sha2_256>::new(
+            sequence_length,
+            options,
+        ))),
         HashFunction::Sha3_256 => Ok(Box::new(FibExample::<Sha3_256>::new(
             sequence_length,
             options,
         ))),
+        HashFunction::Keccak256 => Ok(Box::new(FibExample::<Keccak256>::new(
+            sequence_length,
+            options,
+        ))),
         HashFunction::Rp64_256 => Ok(Box::new(FibExample::<Rp64_256>::new(
             sequence_length,
             options,
@@ -69,6 +80,10 @@ pub fn get_example(
             sequence_length,
             options,
         ))),
+        HashFunction::Poseidon64_256 => Ok(Box::new(FibExample::<Poseidon64_256>::new(
+            sequence_length,
+            options,
+        ))),
     }
 }
 
@@ -3,7 +3,10 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{super::utils::build_proof_options, Rp64_256};
+use super::{
+    super::utils::build_proof_options, Blake3_256, GriffinJive64_256, Poseidon64_256, Rp64_256,
+};
+use winterfell::crypto::{hashers::Truncated, TranscriptMode};
 
 #[test]
 fn fib_small_test_basic_proof_verification() {
@@ -31,3 +34,84 @@ fn fib_small_test_basic_proof_verification_fail() {
     ));
     crate::tests::test_basic_proof_verification_fail(fib);
 }
+
+#[test]
+fn fib_small_test_basic_proof_verification_poseidon() {
+    let fib = Box::new(super::FibExample::<Poseidon64_256>::new(
+        16,
+        build_proof_options(false),
+    ));
+    crate::tests::test_basic_proof_verification(fib);
+}
+
+#[test]
+fn fib_small_test_basic_proof_verification_griffin() {
+    let fib = Box::new(super::FibExample::<GriffinJive64_256>::new(
+        16,
+        build_proof_options(false),
+    ));
+    crate::tests::test_basic_proof_verification(fib);
+}
+
+#[test]
+fn fib_small_test_basic_proof_verification_merkle_arity_4() {
+    let fib = Box::new(super::FibExample::<Rp64_256>::new(
+        16,
+        build_proof_options(false).with_merkle_arity(4),
+    ));
+    crate::tests::test_basic_proof_verification(fib);
+}
+
+#[test]
+fn fib_small_test_basic_proof_verification_merkle_arity_8() {
+    let fib = Box::new(super::FibExample::<Rp64_256>::new(
+        16,
+        build_proof_options(true).with_merkle_arity(8),
+    ));
+    crate::tests::test_basic_proof_verification(fib);
+}
+
+#[test]
+fn fib_small_test_basic_proof_verification_sponge_transcript() {
+    let fib = Box::new(super::FibExample::<Rp64_256>::new(
+        16,
+        build_proof_options(true).with_transcript_mode(TranscriptMode::Sponge),
+    ));
+    crate::tests::test_basic_proof_verification(fib);
+}
+
+#[test]
+fn fib_small_test_basic_proof_verification_truncated_digests() {
+    let fib = Box::new(super::FibExample::<Truncated<Blake3_256, 20>>::new(
+        16,
+        build_proof_options(true).with_digest_size(20),
+    ));
+    crate::tests::test_basic_proof_verification(fib);
+}
+
+#[test]
+fn fib_small_test_basic_proof_verification_xof_transcript() {
+    let fib = Box::new(super::FibExample::<Rp64_256>::new(
+        16,
+        build_proof_options(true).with_transcript_mode(TranscriptMode::Blake3Xof),
+    ));
+    crate::tests::test_basic_proof_verification(fib);
+}
+
+#[test]
+fn fib_small_test_basic_proof_verification_domain_offset() {
+    let fib = Box::new(super::FibExample::<Rp64_256>::new(
+        16,
+        build_proof_options(true).with_domain_offset(3),
+    ));
+    crate::tests::test_basic_proof_verification(fib);
+}
+
+#[test]
+fn fib_small_test_basic_proof_verification_domain_offset_fail() {
+    let fib = Box::new(super::FibExample::<Rp64_256>::new(
+        16,
+        build_proof_options(true).with_domain_offset(3),
+    ));
+    crate::tests::test_basic_proof_verification_fail(fib);
+}
@@ -8,5 +8,6 @@ pub mod fib8;
 pub mod fib_small;
 pub mod mulfib2;
 pub mod mulfib8;
+pub mod mulfib_ext;
 
 mod utils;
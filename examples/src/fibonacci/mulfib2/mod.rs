@@ -4,7 +4,9 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::utils::compute_mulfib_term;
-use crate::{Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Sha3_256};
+use crate::{
+    Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Keccak256, Sha2_256, Sha3_256,
+};
 use core::marker::PhantomData;
 use log::debug;
 use std::time::Instant;
@@ -41,10 +43,18 @@ pub fn get_example(
             sequence_length,
             options,
         ))),
+        HashFunction::Sha2_256 => Ok(Box::new(MulFib2Example::<Sha2_256>::new(
+            sequence_length,
+            options,
+        ))),
         HashFunction::Sha3_256 => Ok(Box::new(MulFib2Example::<Sha3_256>::new(
             sequence_length,
             options,
         ))),
+        HashFunction::Keccak256 => Ok(Box::new(MulFib2Example::<Keccak256>::new(
+            sequence_length,
+            options,
+        ))),
         _ => Err("The specified hash function cannot be used with this example.".to_string()),
     }
 }
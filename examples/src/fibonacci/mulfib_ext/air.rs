@@ -0,0 +1,112 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{get_initial_terms, to_ext_elements, ExtElement, EXT_TRACE_WIDTH};
+use crate::utils::are_equal;
+use winterfell::{
+    math::{fields::f64::BaseElement, ExtensionOf, FieldElement},
+    Air, AirContext, Assertion, AuxTraceRandElements, EvaluationFrame, FieldExtension,
+    ProofOptions, TraceInfo, TransitionConstraintDegree,
+};
+
+// FIBONACCI AIR
+// ================================================================================================
+
+pub struct MulFibExtAir {
+    context: AirContext<BaseElement>,
+    result: ExtElement,
+}
+
+impl Air for MulFibExtAir {
+    type BaseField = BaseElement;
+    type PublicInputs = ExtElement;
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    fn new(trace_info: TraceInfo, pub_inputs: ExtElement, options: ProofOptions) -> Self {
+        assert_eq!(
+            FieldExtension::Quadratic,
+            options.field_extension(),
+            "this computation requires quadratic field extension"
+        );
+        assert_eq!(EXT_TRACE_WIDTH, trace_info.layout().aux_trace_width());
+
+        // all constraints are placed against the extension field segment of the trace; the main
+        // segment of the trace is not constrained
+        let ext_degrees = vec![
+            TransitionConstraintDegree::new(2),
+            TransitionConstraintDegree::new(2),
+        ];
+        MulFibExtAir {
+            context: AirContext::new_multi_segment(
+                trace_info,
+                Vec::new(),
+                ext_degrees,
+                0,
+                3,
+                options,
+            ),
+            result: pub_inputs,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        _frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        _result: &mut [E],
+    ) {
+        // there are no constraints against the main trace segment
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        Vec::new()
+    }
+
+    fn evaluate_aux_transition<F, E>(
+        &self,
+        _main_frame: &EvaluationFrame<F>,
+        aux_frame: &EvaluationFrame<E>,
+        _periodic_values: &[F],
+        _aux_rand_elements: &AuxTraceRandElements<E>,
+        result: &mut [E],
+    ) where
+        F: FieldElement<BaseField = Self::BaseField>,
+        E: FieldElement<BaseField = Self::BaseField> + ExtensionOf<F>,
+    {
+        let current = aux_frame.current();
+        let next = aux_frame.next();
+        // expected state width is 2 extension field elements
+        debug_assert_eq!(EXT_TRACE_WIDTH, current.len());
+        debug_assert_eq!(EXT_TRACE_WIDTH, next.len());
+
+        // constraints of multiplicative Fibonacci (with 2 registers) which state that:
+        // s_{0, i+1} = s_{0, i} * s_{1, i}
+        // s_{1, i+1} = s_{1, i} * s_{0, i+1}
+        result[0] = are_equal(next[0], current[0] * current[1]);
+        result[1] = are_equal(next[1], current[1] * next[0]);
+    }
+
+    fn get_aux_assertions<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        _aux_rand_elements: &AuxTraceRandElements<E>,
+    ) -> Vec<Assertion<E>> {
+        // a valid sequence should start with the two initial terms and terminate with the
+        // expected result
+        let [t0, t1] = get_initial_terms();
+        let terms = [t0, t1, self.result];
+        let values = to_ext_elements::<E>(&terms);
+        let last_step = self.trace_length() - 1;
+        vec![
+            Assertion::single(0, 0, values[0]),
+            Assertion::single(1, 0, values[1]),
+            Assertion::single(0, last_step, values[2]),
+        ]
+    }
+}
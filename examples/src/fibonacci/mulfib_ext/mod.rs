@@ -0,0 +1,216 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{Example, ExampleOptions, HashFunction};
+use core::marker::PhantomData;
+use log::debug;
+use std::time::Instant;
+use winterfell::{
+    crypto::ElementHasher,
+    math::{fields::f64::BaseElement, fields::QuadExtension, log2, FieldElement},
+    FieldExtension, ProofOptions, Prover, StarkProof, Trace, VerifierError,
+};
+
+mod air;
+use air::MulFibExtAir;
+
+mod prover;
+use prover::MulFibExtProver;
+
+mod trace;
+use trace::MulFibExtTrace;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS AND TYPES
+// ================================================================================================
+
+/// Number of columns in the extension field segment of the execution trace.
+const EXT_TRACE_WIDTH: usize = 2;
+
+type ExtElement = QuadExtension<BaseElement>;
+
+type Blake3_192 = winterfell::crypto::hashers::Blake3_192<BaseElement>;
+type Blake3_256 = winterfell::crypto::hashers::Blake3_256<BaseElement>;
+type Sha2_256 = winterfell::crypto::hashers::Sha2_256<BaseElement>;
+type Sha3_256 = winterfell::crypto::hashers::Sha3_256<BaseElement>;
+type Keccak256 = winterfell::crypto::hashers::Keccak256<BaseElement>;
+type Rp64_256 = winterfell::crypto::hashers::Rp64_256;
+type RpJive64_256 = winterfell::crypto::hashers::RpJive64_256;
+type GriffinJive64_256 = winterfell::crypto::hashers::GriffinJive64_256;
+type Poseidon64_256 = winterfell::crypto::hashers::Poseidon64_256;
+
+// FIBONACCI EXAMPLE
+// ================================================================================================
+
+pub fn get_example(
+    options: &ExampleOptions,
+    sequence_length: usize,
+) -> Result<Box<dyn Example>, String> {
+    let (options, hash_fn) = options.to_proof_options(28, 8);
+    if options.field_extension() != FieldExtension::Quadratic {
+        return Err("This example requires quadratic field extension (use -e 2).".to_string());
+    }
+
+    match hash_fn {
+        HashFunction::Blake3_192 => Ok(Box::new(MulFibExtExample::<Blake3_192>::new(
+            sequence_length,
+            options,
+        ))),
+        HashFunction::Blake3_256 => Ok(Box::new(MulFibExtExample::<Blake3_256>::new(
+            sequence_length,
+            options,
+        ))),
+        HashFunction::Sha2_256 => Ok(Box::new(MulFibExtExample::<Sha2_256>::new(
+            sequence_length,
+            options,
+        ))),
+        HashFunction::Sha3_256 => Ok(Box::new(MulFibExtExample::<Sha3_256>::new(
+            sequence_length,
+            options,
+        ))),
+        HashFunction::Keccak256 => Ok(Box::new(MulFibExtExample::<Keccak256>::new(
+            sequence_length,
+            options,
+        ))),
+        HashFunction::Rp64_256 => Ok(Box::new(MulFibExtExample::<Rp64_256>::new(
+            sequence_length,
+            options,
+        ))),
+        HashFunction::RpJive64_256 => Ok(Box::new(MulFibExtExample::<RpJive64_256>::new(
+            sequence_length,
+            options,
+        ))),
+        HashFunction::GriffinJive64_256 => Ok(Box::new(
+            MulFibExtExample::<GriffinJive64_256>::new(sequence_length, options),
+        )),
+        HashFunction::Poseidon64_256 => Ok(Box::new(MulFibExtExample::<Poseidon64_256>::new(
+            sequence_length,
+            options,
+        ))),
+    }
+}
+
+pub struct MulFibExtExample<H: ElementHasher> {
+    options: ProofOptions,
+    sequence_length: usize,
+    result: ExtElement,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> MulFibExtExample<H> {
+    pub fn new(sequence_length: usize, options: ProofOptions) -> Self {
+        assert!(
+            sequence_length.is_power_of_two(),
+            "sequence length must be a power of 2"
+        );
+
+        // compute multiplicative Fibonacci sequence in the extension field
+        let now = Instant::now();
+        let result = compute_mulfib_ext_term(sequence_length);
+        debug!(
+            "Computed multiplicative Fibonacci sequence in the extension field up to {}th term in {} ms",
+            sequence_length,
+            now.elapsed().as_millis()
+        );
+
+        MulFibExtExample {
+            options,
+            sequence_length,
+            result,
+            _hasher: PhantomData,
+        }
+    }
+}
+
+// EXAMPLE IMPLEMENTATION
+// ================================================================================================
+
+impl<H: ElementHasher> Example for MulFibExtExample<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    fn prove(&self) -> StarkProof {
+        let sequence_length = self.sequence_length;
+        debug!(
+            "Generating proof for computing multiplicative Fibonacci sequence in the extension \
+            field (2 terms per step) up to {}th term\n\
+            ---------------------",
+            sequence_length
+        );
+
+        // create a prover
+        let prover = MulFibExtProver::<H>::new(self.options.clone());
+
+        // generate execution trace
+        let now = Instant::now();
+        let trace = prover.build_trace(sequence_length);
+        let trace_width = trace.layout().main_trace_width() + trace.layout().aux_trace_width();
+        let trace_length = trace.length();
+        debug!(
+            "Generated execution trace of {} registers and 2^{} steps in {} ms",
+            trace_width,
+            log2(trace_length),
+            now.elapsed().as_millis()
+        );
+
+        // generate the proof
+        prover.prove(trace).unwrap()
+    }
+
+    fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        winterfell::verify::<MulFibExtAir, H>(proof, self.result)
+    }
+
+    fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        winterfell::verify::<MulFibExtAir, H>(proof, self.result + ExtElement::ONE)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the first two terms of the sequence.
+///
+/// Both terms have non-zero coefficients in the base and in the extension parts, and thus, the
+/// sequence cannot be computed in the base field alone.
+fn get_initial_terms() -> [ExtElement; 2] {
+    [
+        ExtElement::new(BaseElement::new(1), BaseElement::new(1)),
+        ExtElement::new(BaseElement::new(2), BaseElement::new(1)),
+    ]
+}
+
+/// Returns the (n-1)th term of the multiplicative Fibonacci sequence which starts with the terms
+/// returned by [get_initial_terms()]; this is the term in the first register at the last step of
+/// the execution trace.
+fn compute_mulfib_ext_term(n: usize) -> ExtElement {
+    let [mut t0, mut t1] = get_initial_terms();
+
+    for _ in 0..(n / 2 - 1) {
+        t0 *= t1;
+        t1 *= t0;
+    }
+
+    t0
+}
+
+/// Re-interprets a list of quadratic extension elements as a list of elements in field `E`.
+///
+/// This is needed because the prover and the verifier instantiate the AIR and the trace with the
+/// extension field specified by proof options. Since this example requires the quadratic
+/// extension, `E` is guaranteed to be the same field as [ExtElement].
+///
+/// # Panics
+/// Panics if `E` is not a quadratic extension field.
+fn to_ext_elements<E: FieldElement<BaseField = BaseElement>>(values: &[ExtElement]) -> &[E] {
+    assert_eq!(
+        E::EXTENSION_DEGREE,
+        2,
+        "this example must be instantiated with quadratic extension field"
+    );
+    E::slice_from_base_elements(ExtElement::as_base_elements(values))
+}
@@ -0,0 +1,65 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    get_initial_terms, BaseElement, ElementHasher, ExtElement, MulFibExtAir, MulFibExtTrace,
+    PhantomData, ProofOptions, Prover, Trace,
+};
+
+// FIBONACCI PROVER
+// ================================================================================================
+
+pub struct MulFibExtProver<H: ElementHasher> {
+    options: ProofOptions,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> MulFibExtProver<H> {
+    pub fn new(options: ProofOptions) -> Self {
+        Self {
+            options,
+            _hasher: PhantomData,
+        }
+    }
+
+    /// Builds an execution trace for computing a multiplicative version of a Fibonacci sequence
+    /// over the quadratic extension field such that each row advances the sequence by 2 terms.
+    pub fn build_trace(&self, length: usize) -> MulFibExtTrace {
+        assert!(
+            length.is_power_of_two(),
+            "sequence length must be a power of 2"
+        );
+
+        let [t0, t1] = get_initial_terms();
+        let mut reg0 = vec![t0];
+        let mut reg1 = vec![t1];
+
+        for i in 0..(length / 2 - 1) {
+            reg0.push(reg0[i] * reg1[i]);
+            reg1.push(reg1[i] * reg0[i + 1]);
+        }
+
+        MulFibExtTrace::new(vec![reg0, reg1])
+    }
+}
+
+impl<H: ElementHasher> Prover for MulFibExtProver<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    type BaseField = BaseElement;
+    type Air = MulFibExtAir;
+    type Trace = MulFibExtTrace;
+    type HashFn = H;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> ExtElement {
+        let last_step = trace.length() - 1;
+        trace.get(0, last_step)
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+}
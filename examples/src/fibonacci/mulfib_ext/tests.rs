@@ -0,0 +1,33 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{super::utils::build_proof_options, Blake3_256, Rp64_256};
+
+#[test]
+fn mulfib_ext_test_basic_proof_verification() {
+    let fib = Box::new(super::MulFibExtExample::<Blake3_256>::new(
+        16,
+        build_proof_options(true),
+    ));
+    crate::tests::test_basic_proof_verification(fib);
+}
+
+#[test]
+fn mulfib_ext_test_basic_proof_verification_rp64_256() {
+    let fib = Box::new(super::MulFibExtExample::<Rp64_256>::new(
+        64,
+        build_proof_options(true),
+    ));
+    crate::tests::test_basic_proof_verification(fib);
+}
+
+#[test]
+fn mulfib_ext_test_basic_proof_verification_fail() {
+    let fib = Box::new(super::MulFibExtExample::<Blake3_256>::new(
+        16,
+        build_proof_options(true),
+    ));
+    crate::tests::test_basic_proof_verification_fail(fib);
+}
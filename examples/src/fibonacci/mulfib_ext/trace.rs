@@ -0,0 +1,107 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{to_ext_elements, ExtElement};
+use winterfell::{
+    math::{fields::f64::BaseElement, FieldElement},
+    EvaluationFrame, Matrix, Trace, TraceLayout,
+};
+
+// EXTENSION FIELD TRACE
+// ================================================================================================
+/// An execution trace with columns defined over the quadratic extension field.
+///
+/// The extension field columns are placed into an auxiliary trace segment which does not require
+/// any random elements, and thus, can be built as soon as the main trace segment is committed
+/// to. Since a trace must have a non-empty main segment, the main segment consists of a single
+/// column of ZEROs which is not constrained by the AIR.
+pub struct MulFibExtTrace {
+    layout: TraceLayout,
+    main_segment: Matrix<BaseElement>,
+    ext_columns: Vec<Vec<ExtElement>>,
+}
+
+impl MulFibExtTrace {
+    /// Returns a new trace instantiated with the specified extension field columns.
+    ///
+    /// # Panics
+    /// Panics if `ext_columns` is empty or the columns have different lengths.
+    pub fn new(ext_columns: Vec<Vec<ExtElement>>) -> Self {
+        assert!(
+            !ext_columns.is_empty(),
+            "execution trace must consist of at least one column"
+        );
+        let length = ext_columns[0].len();
+        assert!(
+            ext_columns.iter().all(|column| column.len() == length),
+            "all columns of the execution trace must have the same length"
+        );
+
+        Self {
+            layout: TraceLayout::new(1, [ext_columns.len()], [0]),
+            main_segment: Matrix::new(vec![vec![BaseElement::ZERO; length]]),
+            ext_columns,
+        }
+    }
+
+    /// Returns value of the cell in the specified extension field column at the specified row.
+    pub fn get(&self, column: usize, step: usize) -> ExtElement {
+        self.ext_columns[column][step]
+    }
+}
+
+// TRACE TRAIT IMPLEMENTATION
+// ================================================================================================
+
+impl Trace for MulFibExtTrace {
+    type BaseField = BaseElement;
+
+    fn layout(&self) -> &TraceLayout {
+        &self.layout
+    }
+
+    fn length(&self) -> usize {
+        self.main_segment.num_rows()
+    }
+
+    fn meta(&self) -> &[u8] {
+        &[]
+    }
+
+    fn read_main_frame(&self, row_idx: usize, frame: &mut EvaluationFrame<Self::BaseField>) {
+        let next_row_idx = (row_idx + 1) % self.length();
+        self.main_segment
+            .read_row_into(row_idx, frame.current_mut());
+        self.main_segment
+            .read_row_into(next_row_idx, frame.next_mut());
+    }
+
+    fn main_segment(&self) -> &Matrix<BaseElement> {
+        &self.main_segment
+    }
+
+    fn build_aux_segment<E>(
+        &mut self,
+        aux_segments: &[Matrix<E>],
+        rand_elements: &[E],
+    ) -> Option<Matrix<E>>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        // the extension field columns form a single segment which does not depend on any
+        // randomness
+        if !aux_segments.is_empty() {
+            return None;
+        }
+        debug_assert!(rand_elements.is_empty());
+
+        let columns = self
+            .ext_columns
+            .iter()
+            .map(|column| to_ext_elements::<E>(column).to_vec())
+            .collect();
+        Some(Matrix::new(columns))
+    }
+}
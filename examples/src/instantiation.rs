@@ -0,0 +1,119 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{Example, FieldType, HashFunction};
+use winterfell::{
+    crypto::{hashers, ElementHasher},
+    math::{
+        fields::{f128, f64},
+        ExtensibleField, StarkField,
+    },
+};
+
+// GENERIC EXAMPLE
+// ================================================================================================
+
+/// An example which can be built for any supported combination of a base field and a hash
+/// function.
+///
+/// An implementation describes how to build the example for arbitrary field and hash function
+/// types; [Instantiation::build()] then picks the concrete types at runtime. This way, generic
+/// parameters of the example do not leak into the code which selects the configuration (e.g.,
+/// command-line parsing), and all supported configurations are compiled into a single binary.
+pub trait GenericExample {
+    /// Returns the example instantiated with base field `B` and hash function `H`.
+    fn build<B, H>(self) -> Box<dyn Example>
+    where
+        B: StarkField + ExtensibleField<2> + ExtensibleField<3> + 'static,
+        H: ElementHasher<BaseField = B> + 'static;
+}
+
+// INSTANTIATION
+// ================================================================================================
+
+/// Combinations of a base field and a hash function with which a [GenericExample] can be built.
+///
+/// Hash functions defined over `f64` field (e.g., Rescue Prime) cannot be used with `f128` field;
+/// all other hash functions can be used with either field.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Instantiation {
+    F128Blake3_192,
+    F128Blake3_256,
+    F128Sha2_256,
+    F128Sha3_256,
+    F128Keccak256,
+    F64Blake3_192,
+    F64Blake3_256,
+    F64Sha2_256,
+    F64Sha3_256,
+    F64Keccak256,
+    F64Rp64_256,
+    F64RpJive64_256,
+    F64GriffinJive64_256,
+    F64Poseidon64_256,
+}
+
+impl Instantiation {
+    /// Returns an instantiation for the specified base field and hash function.
+    ///
+    /// # Errors
+    /// Returns an error if the hash function cannot be used with the specified field.
+    pub fn new(field: FieldType, hash_fn: HashFunction) -> Result<Self, String> {
+        match (field, hash_fn) {
+            (FieldType::F128, HashFunction::Blake3_192) => Ok(Self::F128Blake3_192),
+            (FieldType::F128, HashFunction::Blake3_256) => Ok(Self::F128Blake3_256),
+            (FieldType::F128, HashFunction::Sha2_256) => Ok(Self::F128Sha2_256),
+            (FieldType::F128, HashFunction::Sha3_256) => Ok(Self::F128Sha3_256),
+            (FieldType::F128, HashFunction::Keccak256) => Ok(Self::F128Keccak256),
+            (FieldType::F64, HashFunction::Blake3_192) => Ok(Self::F64Blake3_192),
+            (FieldType::F64, HashFunction::Blake3_256) => Ok(Self::F64Blake3_256),
+            (FieldType::F64, HashFunction::Sha2_256) => Ok(Self::F64Sha2_256),
+            (FieldType::F64, HashFunction::Sha3_256) => Ok(Self::F64Sha3_256),
+            (FieldType::F64, HashFunction::Keccak256) => Ok(Self::F64Keccak256),
+            (FieldType::F64, HashFunction::Rp64_256) => Ok(Self::F64Rp64_256),
+            (FieldType::F64, HashFunction::RpJive64_256) => Ok(Self::F64RpJive64_256),
+            (FieldType::F64, HashFunction::GriffinJive64_256) => Ok(Self::F64GriffinJive64_256),
+            (FieldType::F64, HashFunction::Poseidon64_256) => Ok(Self::F64Poseidon64_256),
+            (field, hash_fn) => Err(format!(
+                "hash function {hash_fn:?} cannot be used with {field:?} field"
+            )),
+        }
+    }
+
+    /// Returns the base field of this instantiation.
+    pub fn field(&self) -> FieldType {
+        match self {
+            Self::F128Blake3_192
+            | Self::F128Blake3_256
+            | Self::F128Sha2_256
+            | Self::F128Sha3_256
+            | Self::F128Keccak256 => FieldType::F128,
+            _ => FieldType::F64,
+        }
+    }
+
+    /// Builds the specified example with the base field and hash function of this instantiation.
+    pub fn build<G: GenericExample>(self, example: G) -> Box<dyn Example> {
+        use f128::BaseElement as F128;
+        use f64::BaseElement as F64;
+
+        match self {
+            Self::F128Blake3_192 => example.build::<F128, hashers::Blake3_192<F128>>(),
+            Self::F128Blake3_256 => example.build::<F128, hashers::Blake3_256<F128>>(),
+            Self::F128Sha2_256 => example.build::<F128, hashers::Sha2_256<F128>>(),
+            Self::F128Sha3_256 => example.build::<F128, hashers::Sha3_256<F128>>(),
+            Self::F128Keccak256 => example.build::<F128, hashers::Keccak256<F128>>(),
+            Self::F64Blake3_192 => example.build::<F64, hashers::Blake3_192<F64>>(),
+            Self::F64Blake3_256 => example.build::<F64, hashers::Blake3_256<F64>>(),
+            Self::F64Sha2_256 => example.build::<F64, hashers::Sha2_256<F64>>(),
+            Self::F64Sha3_256 => example.build::<F64, hashers::Sha3_256<F64>>(),
+            Self::F64Keccak256 => example.build::<F64, hashers::Keccak256<F64>>(),
+            Self::F64Rp64_256 => example.build::<F64, hashers::Rp64_256>(),
+            Self::F64RpJive64_256 => example.build::<F64, hashers::RpJive64_256>(),
+            Self::F64GriffinJive64_256 => example.build::<F64, hashers::GriffinJive64_256>(),
+            Self::F64Poseidon64_256 => example.build::<F64, hashers::Poseidon64_256>(),
+        }
+    }
+}
@@ -6,7 +6,7 @@
 use super::{
     message_to_elements, rescue, Example, PrivateKey, Signature, CYCLE_LENGTH, NUM_HASH_ROUNDS,
 };
-use crate::{Blake3_192, Blake3_256, ExampleOptions, HashFunction, Sha3_256};
+use crate::{Blake3_192, Blake3_256, ExampleOptions, HashFunction, Keccak256, Sha2_256, Sha3_256};
 use core::marker::PhantomData;
 use log::debug;
 use std::time::Instant;
@@ -22,6 +22,9 @@ use air::{LamportAggregateAir, PublicInputs};
 mod prover;
 use prover::LamportAggregateProver;
 
+#[cfg(test)]
+mod tests;
+
 // CONSTANTS
 // ================================================================================================
 
@@ -45,10 +48,18 @@ pub fn get_example(
             num_signatures,
             options,
         ))),
+        HashFunction::Sha2_256 => Ok(Box::new(LamportAggregateExample::<Sha2_256>::new(
+            num_signatures,
+            options,
+        ))),
         HashFunction::Sha3_256 => Ok(Box::new(LamportAggregateExample::<Sha3_256>::new(
             num_signatures,
             options,
         ))),
+        HashFunction::Keccak256 => Ok(Box::new(LamportAggregateExample::<Keccak256>::new(
+            num_signatures,
+            options,
+        ))),
         _ => Err("The specified hash function cannot be used with this example.".to_string()),
     }
 }
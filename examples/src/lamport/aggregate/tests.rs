@@ -0,0 +1,34 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::Blake3_256;
+use winterfell::{FieldExtension, ProofOptions};
+
+#[test]
+fn lamport_aggregate_test_basic_proof_verification() {
+    let lamport = Box::new(super::LamportAggregateExample::<Blake3_256>::new(
+        2,
+        build_options(true),
+    ));
+    crate::tests::test_basic_proof_verification(lamport);
+}
+
+#[test]
+fn lamport_aggregate_test_basic_proof_verification_fail() {
+    let lamport = Box::new(super::LamportAggregateExample::<Blake3_256>::new(
+        2,
+        build_options(false),
+    ));
+    crate::tests::test_basic_proof_verification_fail(lamport);
+}
+
+fn build_options(use_extension_field: bool) -> ProofOptions {
+    let extension = if use_extension_field {
+        FieldExtension::Quadratic
+    } else {
+        FieldExtension::None
+    };
+    ProofOptions::new(28, 8, 0, extension, 4, 256)
+}
@@ -3,14 +3,19 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use std::path::PathBuf;
 use structopt::StructOpt;
 use winterfell::{
-    crypto::hashers::{GriffinJive64_256, Rp64_256, RpJive64_256},
+    crypto::{
+        hashers::{GriffinJive64_256, Poseidon64_256, Rp64_256, RpJive64_256},
+        TranscriptMode,
+    },
     math::fields::f128::BaseElement,
     FieldExtension, ProofOptions, StarkProof, VerifierError,
 };
 
 pub mod fibonacci;
+pub mod instantiation;
 #[cfg(feature = "std")]
 pub mod lamport;
 #[cfg(feature = "std")]
@@ -20,6 +25,7 @@ pub mod rescue;
 pub mod rescue_raps;
 pub mod utils;
 pub mod vdf;
+pub mod vm;
 
 #[cfg(test)]
 mod tests;
@@ -29,7 +35,9 @@ mod tests;
 
 pub type Blake3_192 = winterfell::crypto::hashers::Blake3_192<BaseElement>;
 pub type Blake3_256 = winterfell::crypto::hashers::Blake3_256<BaseElement>;
+pub type Sha2_256 = winterfell::crypto::hashers::Sha2_256<BaseElement>;
 pub type Sha3_256 = winterfell::crypto::hashers::Sha3_256<BaseElement>;
+pub type Keccak256 = winterfell::crypto::hashers::Keccak256<BaseElement>;
 
 pub trait Example {
     fn prove(&self) -> StarkProof;
@@ -46,9 +54,21 @@ pub struct ExampleOptions {
     #[structopt(subcommand)]
     pub example: ExampleType,
 
-    /// Hash function used in the protocol
-    #[structopt(short = "h", long = "hash_fn", default_value = "blake3_256")]
-    hash_fn: String,
+    /// Hash function used in the protocol; `blake3` and `rescue` are shorthands for `blake3_256`
+    /// and `rp64_256` respectively
+    #[structopt(
+        short = "h",
+        long = "hash_fn",
+        alias = "hash",
+        default_value = "blake3_256",
+        parse(try_from_str = parse_hash_fn)
+    )]
+    hash_fn: HashFunction,
+
+    /// Base field of the computation (`f128` or `f64`); only examples built via an
+    /// instantiation (currently, `fib`) support fields other than `f128`
+    #[structopt(long = "field", default_value = "f128", parse(try_from_str = parse_field))]
+    field: FieldType,
 
     /// Number of queries to include in a proof
     #[structopt(short = "q", long = "queries")]
@@ -58,6 +78,11 @@ pub struct ExampleOptions {
     #[structopt(short = "b", long = "blowup")]
     blowup_factor: Option<usize>,
 
+    /// Select the smallest blowup factor allowed by constraint degrees, adding queries to keep
+    /// the security level of the specified blowup factor
+    #[structopt(long = "auto_blowup")]
+    auto_blowup_factor: bool,
+
     /// Grinding factor for query seed
     #[structopt(short = "g", long = "grinding", default_value = "16")]
     grinding_factor: u32,
@@ -69,6 +94,30 @@ pub struct ExampleOptions {
     /// Folding factor for FRI protocol
     #[structopt(short = "f", long = "folding", default_value = "8")]
     folding_factor: usize,
+
+    /// Arity of Merkle trees used for commitments
+    #[structopt(short = "m", long = "merkle_arity", default_value = "2")]
+    merkle_arity: usize,
+
+    /// Number of adjacent trace rows committed to in a single Merkle tree leaf
+    #[structopt(long = "trace_rows_per_leaf", default_value = "1")]
+    trace_rows_per_leaf: usize,
+
+    /// Use a sponge-based transcript instead of a hash chain for the public coin
+    #[structopt(long = "sponge")]
+    sponge_transcript: bool,
+
+    /// Draw public coin values from the BLAKE3 extendable output instead of a hash chain
+    #[structopt(long = "xof")]
+    xof_transcript: bool,
+
+    /// Write the generated proof into the specified file
+    #[structopt(long = "out", parse(from_os_str))]
+    pub out: Option<PathBuf>,
+
+    /// Verify the proof read from the specified file instead of generating a new proof
+    #[structopt(long = "proof", parse(from_os_str), conflicts_with = "out")]
+    pub proof: Option<PathBuf>,
 }
 
 impl ExampleOptions {
@@ -82,126 +131,212 @@ impl ExampleOptions {
             val => panic!("'{val}' is not a valid field extension option"),
         };
 
-        let hash_fn = match self.hash_fn.as_str() {
-            "blake3_192" => HashFunction::Blake3_192,
-            "blake3_256" => HashFunction::Blake3_256,
-            "sha3_256" => HashFunction::Sha3_256,
-            "rp64_256" => HashFunction::Rp64_256,
-            "rp_jive64_256" => HashFunction::RpJive64_256,
-            "griffin_jive64_256" => HashFunction::GriffinJive64_256,
-            val => panic!("'{val}' is not a valid hash function option"),
+        let transcript_mode = if self.sponge_transcript {
+            TranscriptMode::Sponge
+        } else if self.xof_transcript {
+            TranscriptMode::Blake3Xof
+        } else {
+            TranscriptMode::HashChain
         };
 
-        (
-            ProofOptions::new(
-                num_queries,
-                blowup_factor,
-                self.grinding_factor,
-                field_extension,
-                self.folding_factor,
-                256,
-            ),
-            hash_fn,
+        let options = ProofOptions::new(
+            num_queries,
+            blowup_factor,
+            self.grinding_factor,
+            field_extension,
+            self.folding_factor,
+            256,
         )
+        .with_merkle_arity(self.merkle_arity)
+        .with_trace_rows_per_leaf(self.trace_rows_per_leaf)
+        .with_transcript_mode(transcript_mode);
+
+        if self.auto_blowup_factor {
+            (options.with_auto_blowup_factor(), self.hash_fn)
+        } else {
+            (options, self.hash_fn)
+        }
+    }
+
+    /// Returns the base field selected for the example.
+    pub fn field(&self) -> FieldType {
+        self.field
     }
 
     /// Returns security level of the input proof in bits.
-    pub fn get_proof_security_level(&self, proof: &StarkProof) -> usize {
-        let security_level = match self.hash_fn.as_str() {
-            "blake3_192" => proof.security_level::<Blake3_192>(true),
-            "blake3_256" => proof.security_level::<Blake3_256>(true),
-            "sha3_256" => proof.security_level::<Sha3_256>(true),
-            "rp64_256" => proof.security_level::<Rp64_256>(true),
-            "rp_jive64_256" => proof.security_level::<RpJive64_256>(true),
-            "griffin_jive64_256" => proof.security_level::<GriffinJive64_256>(true),
-            val => panic!("'{val}' is not a valid hash function option"),
+    ///
+    /// When `conjectured` is true, conjectured security level is returned; otherwise, proven
+    /// security level is returned.
+    pub fn get_proof_security_level(&self, proof: &StarkProof, conjectured: bool) -> usize {
+        let security_level = match self.hash_fn {
+            HashFunction::Blake3_192 => proof.security_level::<Blake3_192>(conjectured),
+            HashFunction::Blake3_256 => proof.security_level::<Blake3_256>(conjectured),
+            HashFunction::Sha2_256 => proof.security_level::<Sha2_256>(conjectured),
+            HashFunction::Sha3_256 => proof.security_level::<Sha3_256>(conjectured),
+            HashFunction::Keccak256 => proof.security_level::<Keccak256>(conjectured),
+            HashFunction::Rp64_256 => proof.security_level::<Rp64_256>(conjectured),
+            HashFunction::RpJive64_256 => proof.security_level::<RpJive64_256>(conjectured),
+            HashFunction::GriffinJive64_256 => {
+                proof.security_level::<GriffinJive64_256>(conjectured)
+            }
+            HashFunction::Poseidon64_256 => proof.security_level::<Poseidon64_256>(conjectured),
         };
 
         security_level as usize
     }
 }
 
+/// Parses a size parameter of an example; the size can be specified either as a decimal number
+/// (e.g. `1048576`) or as a power of two (e.g. `2^20`).
+pub fn parse_size(source: &str) -> Result<usize, String> {
+    let invalid = || format!("'{}' is not a valid size", source);
+    match source.split_once('^') {
+        Some(("2", exponent)) => {
+            let exponent: u32 = exponent.trim().parse().map_err(|_| invalid())?;
+            1usize.checked_shl(exponent).ok_or_else(invalid)
+        }
+        Some(_) => Err(invalid()),
+        None => source.trim().parse().map_err(|_| invalid()),
+    }
+}
+
+/// Parses the name of a hash function used in the protocol.
+pub fn parse_hash_fn(source: &str) -> Result<HashFunction, String> {
+    match source {
+        "blake3" | "blake3_256" => Ok(HashFunction::Blake3_256),
+        "blake3_192" => Ok(HashFunction::Blake3_192),
+        "sha2_256" => Ok(HashFunction::Sha2_256),
+        "sha3_256" => Ok(HashFunction::Sha3_256),
+        "keccak_256" => Ok(HashFunction::Keccak256),
+        "rescue" | "rp64_256" => Ok(HashFunction::Rp64_256),
+        "rp_jive64_256" => Ok(HashFunction::RpJive64_256),
+        "griffin_jive64_256" => Ok(HashFunction::GriffinJive64_256),
+        "poseidon64_256" => Ok(HashFunction::Poseidon64_256),
+        val => Err(format!("'{val}' is not a valid hash function option")),
+    }
+}
+
+/// Parses the name of a base field of an example.
+pub fn parse_field(source: &str) -> Result<FieldType, String> {
+    match source {
+        "f128" => Ok(FieldType::F128),
+        "f64" => Ok(FieldType::F64),
+        val => Err(format!("'{val}' is not a valid field option")),
+    }
+}
+
 #[derive(StructOpt, Debug)]
 //#[structopt(about = "available examples")]
 pub enum ExampleType {
-    /// Compute a Fibonacci sequence using trace table with 2 registers
+    /// Compute a Fibonacci sequence using trace table with 2 registers in the selected field
     Fib {
         /// Length of Fibonacci sequence; must be a power of two
-        #[structopt(short = "n", default_value = "1048576")]
+        #[structopt(short = "n", parse(try_from_str = parse_size), default_value = "1048576")]
         sequence_length: usize,
     },
     /// Compute a Fibonacci sequence using trace table with 8 registers
     Fib8 {
         /// Length of Fibonacci sequence; must be a power of two
-        #[structopt(short = "n", default_value = "1048576")]
+        #[structopt(short = "n", parse(try_from_str = parse_size), default_value = "1048576")]
         sequence_length: usize,
     },
     /// Compute a multiplicative Fibonacci sequence using trace table with 2 registers
     Mulfib {
         /// Length of Fibonacci sequence; must be a power of two
-        #[structopt(short = "n", default_value = "1048576")]
+        #[structopt(short = "n", parse(try_from_str = parse_size), default_value = "1048576")]
         sequence_length: usize,
     },
     /// Compute a multiplicative Fibonacci sequence using trace table with 8 registers
     Mulfib8 {
         /// Length of Fibonacci sequence; must be a power of two
-        #[structopt(short = "n", default_value = "1048576")]
+        #[structopt(short = "n", parse(try_from_str = parse_size), default_value = "1048576")]
+        sequence_length: usize,
+    },
+    /// Compute a multiplicative Fibonacci sequence over the quadratic extension of `f64` field
+    MulfibExt {
+        /// Length of Fibonacci sequence; must be a power of two
+        #[structopt(short = "n", parse(try_from_str = parse_size), default_value = "1048576")]
         sequence_length: usize,
     },
     /// Compute a Fibonacci sequence using trace table with 2 registers in `f64` field.
     FibSmall {
         /// Length of Fibonacci sequence; must be a power of two
-        #[structopt(short = "n", default_value = "65536")]
+        #[structopt(short = "n", parse(try_from_str = parse_size), default_value = "65536")]
         sequence_length: usize,
     },
     /// Execute a simple VDF function
     Vdf {
         /// Number of steps in the VDF function; must be a power of two
-        #[structopt(short = "n", default_value = "1048576")]
+        #[structopt(short = "n", parse(try_from_str = parse_size), default_value = "1048576")]
         num_steps: usize,
     },
     /// Similar to the VDF example, but exempts an extra row from transition constraints.
     VdfExempt {
         /// Number of steps in the VDF function; must be one less than a power of two
-        #[structopt(short = "n", default_value = "1048575")]
+        #[structopt(short = "n", parse(try_from_str = parse_size), default_value = "1048575")]
+        num_steps: usize,
+    },
+    /// Iterate the function x -> x^2 + c for a number of steps
+    VdfSquaring {
+        /// Number of steps; must be a power of two
+        #[structopt(short = "n", parse(try_from_str = parse_size), default_value = "1048576")]
         num_steps: usize,
+        /// Constant c added to the square of the state at every step
+        #[structopt(short = "c", default_value = "42")]
+        constant: u128,
     },
     /// Compute a hash chain using Rescue hash function
     Rescue {
         /// Length of the hash chain; must be a power of two
-        #[structopt(short = "n", default_value = "1024")]
+        #[structopt(short = "n", parse(try_from_str = parse_size), default_value = "1024")]
         chain_length: usize,
     },
     /// Compute two hash chains absorbing sequences that are a permutation of each other
     #[cfg(feature = "std")]
     RescueRaps {
         /// Length of the hash chain; must be a power of two and at least 4
-        #[structopt(short = "n", default_value = "1024")]
+        #[structopt(short = "n", parse(try_from_str = parse_size), default_value = "1024")]
         chain_length: usize,
     },
+    /// Execute a program on a tiny VM with instruction selectors and a program ROM lookup
+    Vm {
+        /// Number of loop iterations of the program computing 3^n; must be greater than zero
+        #[structopt(short = "n", parse(try_from_str = parse_size), default_value = "1024")]
+        exponent: usize,
+    },
     /// Compute a root of a Merkle path using Rescue hash function
     #[cfg(feature = "std")]
     Merkle {
-        /// Depth of the Merkle tree; must be one less than a power of two
-        #[structopt(short = "n", default_value = "7")]
+        /// Depth of the Merkle tree; must be between 1 and 32
+        #[structopt(short = "n", parse(try_from_str = parse_size), default_value = "7")]
         tree_depth: usize,
     },
     /// Compute an aggregate Lamport+ signature
     #[cfg(feature = "std")]
     LamportA {
         /// Number of signatures to aggregate; must be a power of two
-        #[structopt(short = "n", default_value = "4")]
+        #[structopt(short = "n", parse(try_from_str = parse_size), default_value = "4")]
         num_signatures: usize,
     },
     /// Compute a threshold Lamport+ signature
     #[cfg(feature = "std")]
     LamportT {
         /// Number of signers; must be one less than a power of two
-        #[structopt(short = "n", default_value = "3")]
+        #[structopt(short = "n", parse(try_from_str = parse_size), default_value = "3")]
         num_signers: usize,
     },
 }
 
+/// Defines a set of base fields available for the provided examples.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum FieldType {
+    /// Prime field with modulus 2^128 - 45 * 2^40 + 1.
+    F128,
+
+    /// Prime field with modulus 2^64 - 2^32 + 1.
+    F64,
+}
+
 /// Defines a set of hash functions available for the provided examples. Some examples may not
 /// support all listed hash functions.
 ///
@@ -221,11 +356,21 @@ pub enum HashFunction {
     /// When this function is used in the STARK protocol, proof security cannot exceed 128 bits.
     Blake3_256,
 
+    /// SHA2 hash function with 256 bit output.
+    ///
+    /// When this function is used in the STARK protocol, proof security cannot exceed 128 bits.
+    Sha2_256,
+
     /// SHA3 hash function with 256 bit output.
     ///
     /// When this function is used in the STARK protocol, proof security cannot exceed 128 bits.
     Sha3_256,
 
+    /// Keccak hash function with 256 bit output.
+    ///
+    /// When this function is used in the STARK protocol, proof security cannot exceed 128 bits.
+    Keccak256,
+
     /// Rescue Prime hash function with 256 bit output. It only works in `f64` field.
     ///
     /// When this function is used in the STARK protocol, proof security cannot exceed 128 bits.
@@ -242,4 +387,9 @@ pub enum HashFunction {
     ///
     /// When this function is used in the STARK protocol, proof security cannot exceed 128 bits.
     GriffinJive64_256,
+
+    /// Poseidon hash function with 256 bit output. It only works in `f64` field.
+    ///
+    /// When this function is used in the STARK protocol, proof security cannot exceed 128 bits.
+    Poseidon64_256,
 }
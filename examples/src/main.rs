@@ -4,12 +4,13 @@
 // LICENSE file in the root directory of this source tree.
 
 use log::debug;
+use std::fs;
 use std::io::Write;
 use std::time::Instant;
 use structopt::StructOpt;
 use winterfell::StarkProof;
 
-use examples::{fibonacci, rescue, vdf, ExampleOptions, ExampleType};
+use examples::{fibonacci, rescue, vdf, vm, ExampleOptions, ExampleType};
 #[cfg(feature = "std")]
 use examples::{lamport, merkle, rescue_raps};
 
@@ -42,16 +43,24 @@ fn main() {
         ExampleType::Mulfib8 { sequence_length } => {
             fibonacci::mulfib8::get_example(&options, sequence_length)
         }
+        ExampleType::MulfibExt { sequence_length } => {
+            fibonacci::mulfib_ext::get_example(&options, sequence_length)
+        }
         ExampleType::FibSmall { sequence_length } => {
             fibonacci::fib_small::get_example(&options, sequence_length)
         }
         ExampleType::Vdf { num_steps } => vdf::regular::get_example(&options, num_steps),
         ExampleType::VdfExempt { num_steps } => vdf::exempt::get_example(&options, num_steps),
+        ExampleType::VdfSquaring {
+            num_steps,
+            constant,
+        } => vdf::squaring::get_example(&options, num_steps, constant),
         ExampleType::Rescue { chain_length } => rescue::get_example(&options, chain_length),
         #[cfg(feature = "std")]
         ExampleType::RescueRaps { chain_length } => {
             rescue_raps::get_example(&options, chain_length)
         }
+        ExampleType::Vm { exponent } => vm::get_example(&options, exponent),
         #[cfg(feature = "std")]
         ExampleType::Merkle { tree_depth } => merkle::get_example(&options, tree_depth),
         #[cfg(feature = "std")]
@@ -65,25 +74,43 @@ fn main() {
     }
     .expect("The example failed to initialize.");
 
-    // generate proof
-    let now = Instant::now();
+    // generate the proof, or read it from a file
     let example = example.as_ref();
-    let proof = example.prove();
-    debug!(
-        "---------------------\nProof generated in {} ms",
-        now.elapsed().as_millis()
-    );
+    let proof = match options.proof.as_ref() {
+        Some(path) => {
+            let proof_bytes = fs::read(path).expect("failed to read proof file");
+            debug!("Read proof from {}", path.display());
+            StarkProof::from_bytes(&proof_bytes).expect("failed to parse proof")
+        }
+        None => {
+            let now = Instant::now();
+            let proof = example.prove();
+            debug!(
+                "---------------------\nProof generated in {} ms",
+                now.elapsed().as_millis()
+            );
+            proof
+        }
+    };
 
     let proof_bytes = proof.to_bytes();
     debug!("Proof size: {:.1} KB", proof_bytes.len() as f64 / 1024f64);
-    let security_level = options.get_proof_security_level(&proof);
+    debug!("Proof size breakdown:\n{}", proof.stats());
+    let security_level = options.get_proof_security_level(&proof, true);
     debug!("Proof security: {} bits", security_level);
+    let proven_security_level = options.get_proof_security_level(&proof, false);
+    debug!("Proven proof security: {} bits", proven_security_level);
     #[cfg(feature = "std")]
     debug!(
         "Proof hash: {}",
         hex::encode(blake3::hash(&proof_bytes).as_bytes())
     );
 
+    if let Some(path) = options.out.as_ref() {
+        fs::write(path, &proof_bytes).expect("failed to write proof file");
+        debug!("Proof written to {}", path.display());
+    }
+
     // verify the proof
     debug!("---------------------");
     let parsed_proof = StarkProof::from_bytes(&proof_bytes).unwrap();
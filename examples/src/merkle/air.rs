@@ -3,7 +3,9 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{rescue, BaseElement, FieldElement, HASH_CYCLE_LEN, HASH_STATE_WIDTH, TRACE_WIDTH};
+use super::{
+    get_root_step, rescue, BaseElement, FieldElement, HASH_CYCLE_LEN, HASH_STATE_WIDTH, TRACE_WIDTH,
+};
 use crate::utils::{are_equal, is_binary, is_zero, not, EvaluationResult};
 use winterfell::{
     Air, AirContext, Assertion, ByteWriter, EvaluationFrame, ProofOptions, Serializable, TraceInfo,
@@ -15,17 +17,20 @@ use winterfell::{
 
 pub struct PublicInputs {
     pub tree_root: [BaseElement; 2],
+    pub tree_depth: usize,
 }
 
 impl Serializable for PublicInputs {
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
         target.write(&self.tree_root[..]);
+        target.write_u8(self.tree_depth as u8);
     }
 }
 
 pub struct MerkleAir {
     context: AirContext<BaseElement>,
     tree_root: [BaseElement; 2],
+    root_step: usize,
 }
 
 impl Air for MerkleAir {
@@ -45,9 +50,17 @@ impl Air for MerkleAir {
             TransitionConstraintDegree::new(2),
         ];
         assert_eq!(TRACE_WIDTH, trace_info.width());
+        let root_step = get_root_step(pub_inputs.tree_depth);
+        assert!(
+            root_step < trace_info.length(),
+            "trace of {} steps is too short for a Merkle path of depth {}",
+            trace_info.length(),
+            pub_inputs.tree_depth
+        );
         MerkleAir {
             context: AirContext::new(trace_info, degrees, 4, options),
             tree_root: pub_inputs.tree_root,
+            root_step,
         }
     }
 
@@ -100,12 +113,12 @@ impl Air for MerkleAir {
     }
 
     fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
-        // assert that Merkle path resolves to the tree root, and that hash capacity
-        // registers (registers 4 and 5) are reset to ZERO every 8 steps
-        let last_step = self.trace_length() - 1;
+        // assert that Merkle path resolves to the tree root at the end of the last hash cycle of
+        // the path, and that hash capacity registers (registers 4 and 5) are reset to ZERO every
+        // 8 steps
         vec![
-            Assertion::single(0, last_step, self.tree_root[0]),
-            Assertion::single(1, last_step, self.tree_root[1]),
+            Assertion::single(0, self.root_step, self.tree_root[0]),
+            Assertion::single(1, self.root_step, self.tree_root[1]),
             Assertion::periodic(4, 0, HASH_CYCLE_LEN, BaseElement::ZERO),
             Assertion::periodic(5, 0, HASH_CYCLE_LEN, BaseElement::ZERO),
         ]
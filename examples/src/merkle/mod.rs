@@ -9,7 +9,7 @@ use crate::utils::rescue::{
 };
 use crate::{
     utils::rescue::{Hash, Rescue128},
-    Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Sha3_256,
+    Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Keccak256, Sha2_256, Sha3_256,
 };
 use core::marker::PhantomData;
 use log::debug;
@@ -35,6 +35,9 @@ mod tests;
 
 const TRACE_WIDTH: usize = 7;
 
+/// Maximum depth of a Merkle tree supported by this example.
+const MAX_TREE_DEPTH: usize = 32;
+
 // MERKLE AUTHENTICATION PATH EXAMPLE
 // ================================================================================================
 pub fn get_example(
@@ -50,9 +53,15 @@ pub fn get_example(
         HashFunction::Blake3_256 => Ok(Box::new(MerkleExample::<Blake3_256>::new(
             tree_depth, options,
         ))),
+        HashFunction::Sha2_256 => Ok(Box::new(MerkleExample::<Sha2_256>::new(
+            tree_depth, options,
+        ))),
         HashFunction::Sha3_256 => Ok(Box::new(MerkleExample::<Sha3_256>::new(
             tree_depth, options,
         ))),
+        HashFunction::Keccak256 => Ok(Box::new(MerkleExample::<Keccak256>::new(
+            tree_depth, options,
+        ))),
         _ => Err("The specified hash function cannot be used with this example.".to_string()),
     }
 }
@@ -69,8 +78,9 @@ pub struct MerkleExample<H: ElementHasher> {
 impl<H: ElementHasher> MerkleExample<H> {
     pub fn new(tree_depth: usize, options: ProofOptions) -> Self {
         assert!(
-            (tree_depth + 1).is_power_of_two(),
-            "tree depth must be one less than a power of 2"
+            (1..=MAX_TREE_DEPTH).contains(&tree_depth),
+            "tree depth must be between 1 and {}",
+            MAX_TREE_DEPTH
         );
         let value = [BaseElement::new(42), BaseElement::new(43)];
         let index =
@@ -141,6 +151,7 @@ where
     fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
         let pub_inputs = PublicInputs {
             tree_root: self.tree_root.to_elements(),
+            tree_depth: self.path.len() - 1,
         };
         winterfell::verify::<MerkleAir, H>(proof, pub_inputs)
     }
@@ -149,6 +160,7 @@ where
         let tree_root = self.tree_root.to_elements();
         let pub_inputs = PublicInputs {
             tree_root: [tree_root[1], tree_root[0]],
+            tree_depth: self.path.len() - 1,
         };
         winterfell::verify::<MerkleAir, H>(proof, pub_inputs)
     }
@@ -156,6 +168,13 @@ where
 
 // HELPER FUNCTIONS
 // ================================================================================================
+
+/// Returns the step of the execution trace at which the root of a Merkle tree of the specified
+/// depth is computed; this is the last step of the hash cycle for the top level of the tree.
+fn get_root_step(tree_depth: usize) -> usize {
+    (tree_depth + 1) * HASH_CYCLE_LEN - 1
+}
+
 fn build_merkle_tree(depth: usize, value: [BaseElement; 2], index: usize) -> MerkleTree<Rescue128> {
     let num_leaves = usize::pow(2, depth as u32);
     let leaf_elements: Vec<BaseElement> = rand_vector(num_leaves * 2);
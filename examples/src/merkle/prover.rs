@@ -4,9 +4,9 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{
-    rescue, BaseElement, ElementHasher, FieldElement, MerkleAir, PhantomData, ProofOptions, Prover,
-    PublicInputs, Trace, TraceTable, HASH_CYCLE_LEN, HASH_STATE_WIDTH, NUM_HASH_ROUNDS,
-    TRACE_WIDTH,
+    get_root_step, rescue, BaseElement, ElementHasher, FieldElement, MerkleAir, PhantomData,
+    ProofOptions, Prover, PublicInputs, Trace, TraceTable, HASH_CYCLE_LEN, HASH_STATE_WIDTH,
+    NUM_HASH_ROUNDS, TRACE_WIDTH,
 };
 
 // MERKLE PROVER
@@ -31,9 +31,12 @@ impl<H: ElementHasher> MerkleProver<H> {
         branch: &[rescue::Hash],
         index: usize,
     ) -> TraceTable<BaseElement> {
-        // allocate memory to hold the trace table
-        let trace_length = branch.len() * HASH_CYCLE_LEN;
-        let mut trace = TraceTable::new(TRACE_WIDTH, trace_length);
+        // allocate memory to hold the trace table; the path is hashed in the first
+        // branch.len() * 8 steps, and the remaining steps (if any) pad the trace to a power of
+        // two. The depth of the tree is recorded in trace metadata.
+        let tree_depth = branch.len() - 1;
+        let trace_length = (branch.len() * HASH_CYCLE_LEN).next_power_of_two();
+        let mut trace = TraceTable::with_meta(TRACE_WIDTH, trace_length, vec![tree_depth as u8]);
 
         // skip the first node of the branch because it will be computed in the trace as hash(value)
         let branch = &branch[1..];
@@ -52,7 +55,8 @@ impl<H: ElementHasher> MerkleProver<H> {
                 // hash in registers [0..6]. On the 8th step, insert the next branch node into the
                 // trace in the positions defined by the next bit of the leaf index. If the bit is
                 // ZERO, the next node goes into registers [2, 3], if it is ONE, the node goes into
-                // registers [0, 1].
+                // registers [0, 1]. Once the root of the tree has been computed, the padding
+                // steps keep hashing the accumulated value with ZERO nodes.
 
                 let cycle_num = step / HASH_CYCLE_LEN;
                 let cycle_pos = step % HASH_CYCLE_LEN;
@@ -60,8 +64,13 @@ impl<H: ElementHasher> MerkleProver<H> {
                 if cycle_pos < NUM_HASH_ROUNDS {
                     rescue::apply_round(&mut state[..HASH_STATE_WIDTH], step);
                 } else {
-                    let branch_node = branch[cycle_num].to_elements();
-                    let index_bit = BaseElement::new(((index >> cycle_num) & 1) as u128);
+                    let (branch_node, index_bit) = match branch.get(cycle_num) {
+                        Some(node) => (
+                            node.to_elements(),
+                            BaseElement::new(((index >> cycle_num) & 1) as u128),
+                        ),
+                        None => ([BaseElement::ZERO; 2], BaseElement::ZERO),
+                    };
                     if index_bit == BaseElement::ZERO {
                         // if index bit is zero, new branch node goes into registers [2, 3]; values
                         // in registers [0, 1] (the accumulated hash) remain unchanged
@@ -104,9 +113,11 @@ where
     type HashFn = H;
 
     fn get_pub_inputs(&self, trace: &Self::Trace) -> PublicInputs {
-        let last_step = trace.length() - 1;
+        let tree_depth = trace.meta()[0] as usize;
+        let root_step = get_root_step(tree_depth);
         PublicInputs {
-            tree_root: [trace.get(0, last_step), trace.get(1, last_step)],
+            tree_root: [trace.get(0, root_step), trace.get(1, root_step)],
+            tree_depth,
         }
     }
 
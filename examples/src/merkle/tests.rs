@@ -33,6 +33,25 @@ fn merkle_test_basic_proof_verification_fail() {
     crate::tests::test_basic_proof_verification_fail(merkle);
 }
 
+#[test]
+fn merkle_test_padded_trace_proof_verification() {
+    // a path of depth 5 is hashed in 48 steps, and the trace is padded to 64 steps
+    let merkle = Box::new(super::MerkleExample::<Blake3_256>::new(
+        5,
+        build_options(false),
+    ));
+    crate::tests::test_basic_proof_verification(merkle);
+}
+
+#[test]
+fn merkle_test_padded_trace_proof_verification_fail() {
+    let merkle = Box::new(super::MerkleExample::<Blake3_256>::new(
+        5,
+        build_options(false),
+    ));
+    crate::tests::test_basic_proof_verification_fail(merkle);
+}
+
 fn build_options(use_extension_field: bool) -> ProofOptions {
     let extension = if use_extension_field {
         FieldExtension::Quadratic
@@ -6,8 +6,9 @@
 use super::{rescue, BaseElement, FieldElement, ProofOptions, CYCLE_LENGTH, TRACE_WIDTH};
 use crate::utils::{are_equal, is_zero, not, EvaluationResult};
 use winterfell::{
-    Air, AirContext, Assertion, ByteWriter, EvaluationFrame, Serializable, TraceInfo,
-    TransitionConstraintDegree,
+    Air, AirContext, Assertion, ByteReader, ByteWriter, ConstraintEvaluator, Deserializable,
+    DeserializationError, EvaluationFrame, Serializable, StepPublicInputs, TraceInfo,
+    TransitionConstraintDegree, TransitionFragment,
 };
 
 // CONSTANTS
@@ -36,6 +37,7 @@ const CYCLE_MASK: [BaseElement; CYCLE_LENGTH] = [
 // RESCUE AIR
 // ================================================================================================
 
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PublicInputs {
     pub seed: [BaseElement; 2],
     pub result: [BaseElement; 2],
@@ -48,6 +50,31 @@ impl Serializable for PublicInputs {
     }
 }
 
+impl Deserializable for PublicInputs {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let seed = BaseElement::read_batch_from(source, 2)?;
+        let result = BaseElement::read_batch_from(source, 2)?;
+        Ok(PublicInputs {
+            seed: [seed[0], seed[1]],
+            result: [result[0], result[1]],
+        })
+    }
+}
+
+/// A hash chain can be split into consecutive segments, each starting with the result of the
+/// previous segment; thus, proofs of these segments can be chained together.
+impl StepPublicInputs for PublicInputs {
+    type State = [BaseElement; 2];
+
+    fn input_state(&self) -> Self::State {
+        self.seed
+    }
+
+    fn output_state(&self) -> Self::State {
+        self.result
+    }
+}
+
 pub struct RescueAir {
     context: AirContext<BaseElement>,
     seed: [BaseElement; 2],
@@ -120,6 +147,47 @@ impl Air for RescueAir {
         result.append(&mut rescue::get_round_constants());
         result
     }
+
+    fn get_constraint_evaluator(&self) -> Option<Box<dyn ConstraintEvaluator<BaseElement> + '_>> {
+        Some(Box::new(RescueEvaluator))
+    }
+}
+
+// CONSTRAINT EVALUATOR
+// ================================================================================================
+
+/// Evaluates transition constraints of the Rescue hash chain directly over the columns of the
+/// extended execution trace; this way, the prover does not need to copy every pair of trace rows
+/// into an evaluation frame and to invoke [RescueAir::evaluate_transition()] for every step.
+struct RescueEvaluator;
+
+impl ConstraintEvaluator<BaseElement> for RescueEvaluator {
+    fn evaluate_main_transitions(
+        &self,
+        fragment: &TransitionFragment<BaseElement>,
+        result: &mut [BaseElement],
+    ) {
+        let columns = [
+            fragment.column(0),
+            fragment.column(1),
+            fragment.column(2),
+            fragment.column(3),
+        ];
+        for (row_idx, result) in result.chunks_mut(TRACE_WIDTH).enumerate() {
+            let (i, j) = (
+                fragment.current_lde_step(row_idx),
+                fragment.next_lde_step(row_idx),
+            );
+            let current = columns.map(|column| column[i]);
+            let next = columns.map(|column| column[j]);
+
+            // the same constraints as in RescueAir::evaluate_transition()
+            let periodic_values = fragment.periodic_values(row_idx);
+            let hash_flag = periodic_values[0];
+            rescue::enforce_round(result, &current, &next, &periodic_values[1..], hash_flag);
+            enforce_hash_copy(result, &current, &next, not(hash_flag));
+        }
+    }
 }
 
 // HELPER EVALUATORS
@@ -3,7 +3,9 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::{Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Sha3_256};
+use crate::{
+    Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Keccak256, Sha2_256, Sha3_256,
+};
 use core::marker::PhantomData;
 use log::debug;
 use std::time::Instant;
@@ -50,10 +52,18 @@ pub fn get_example(
             chain_length,
             options,
         ))),
+        HashFunction::Sha2_256 => Ok(Box::new(RescueExample::<Sha2_256>::new(
+            chain_length,
+            options,
+        ))),
         HashFunction::Sha3_256 => Ok(Box::new(RescueExample::<Sha3_256>::new(
             chain_length,
             options,
         ))),
+        HashFunction::Keccak256 => Ok(Box::new(RescueExample::<Keccak256>::new(
+            chain_length,
+            options,
+        ))),
         _ => Err("The specified hash function cannot be used with this example.".to_string()),
     }
 }
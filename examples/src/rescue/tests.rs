@@ -3,8 +3,14 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::Blake3_256;
-use winterfell::{FieldExtension, ProofOptions};
+use super::{
+    compute_hash_chain, BaseElement, Blake3_256, FieldElement, PublicInputs, RescueAir,
+    RescueProver,
+};
+use winterfell::{
+    verify_chain, Air, FieldExtension, PeriodicValueTable, ProofChain, ProofOptions, Prover,
+    ProverError, Trace, TraceTable, VerifierError,
+};
 
 #[test]
 fn rescue_test_basic_proof_verification() {
@@ -33,6 +39,88 @@ fn rescue_test_basic_proof_verification_fail() {
     crate::tests::test_basic_proof_verification_fail(rescue_eg);
 }
 
+#[test]
+fn rescue_test_proof_chain() {
+    let prover = RescueProver::<Blake3_256>::new(build_options(false));
+    let seed = [BaseElement::ONE, BaseElement::new(2)];
+    let result = compute_hash_chain(seed, 3 * 8);
+
+    // prove 3 consecutive segments of the hash chain, 8 hashes each
+    let chain = prover
+        .prove_chain(seed, 3, |state| prover.build_trace(*state, 8))
+        .unwrap();
+    assert_eq!(3, chain.len());
+    assert_eq!(Some(result), chain.output_state());
+    let chain = ProofChain::<PublicInputs>::from_bytes(&chain.to_bytes()).unwrap();
+    let verify = |chain: ProofChain<PublicInputs>, input_state, output_state| {
+        verify_chain::<RescueAir, Blake3_256>(chain, &input_state, &output_state)
+    };
+    assert_eq!(Ok(()), verify(chain.clone(), seed, result));
+
+    // the chain should be rejected for different endpoints
+    assert_eq!(
+        Err(VerifierError::InconsistentChainInputState),
+        verify(chain.clone(), result, result)
+    );
+    assert_eq!(
+        Err(VerifierError::InconsistentChainOutputState),
+        verify(chain.clone(), seed, seed)
+    );
+    assert_eq!(
+        Err(VerifierError::EmptyProofChain),
+        verify(ProofChain::new(), seed, result)
+    );
+
+    // proofs of segments which do not follow each other should be rejected
+    let (proofs, pub_inputs) = chain.into_parts();
+    let mut broken = ProofChain::new();
+    for i in [0, 2, 1] {
+        broken.push(proofs.get(i).unwrap().clone(), pub_inputs[i].clone());
+    }
+    assert_eq!(Some(1), broken.find_broken_link());
+    let output_state = pub_inputs[1].result;
+    assert_eq!(
+        Err(VerifierError::BrokenChainLink(1)),
+        verify(broken, seed, output_state)
+    );
+
+    // traces which do not start with the specified state should be rejected by the prover
+    let result = prover.prove_chain(seed, 2, |_| prover.build_trace(seed, 8));
+    assert_eq!(Err(ProverError::BrokenChainLink(1)), result.map(|_| ()));
+}
+
+#[test]
+fn rescue_test_cached_periodic_values() {
+    let options = build_options(false);
+    let prover = RescueProver::<Blake3_256>::new(options.clone());
+    let seed = [BaseElement::ONE, BaseElement::new(2)];
+
+    // build a table of periodic values once for proofs of 8 hashes
+    let trace = prover.build_trace(seed, 8);
+    let air = RescueAir::new(
+        trace.get_info(),
+        prover.get_pub_inputs(&trace),
+        options.clone(),
+    );
+    let cached = CachedRescueProver {
+        prover: RescueProver::new(options),
+        periodic_values: PeriodicValueTable::new(&air),
+    };
+
+    // proofs generated with the cached table should be the same as proofs generated without it,
+    // regardless of the public inputs
+    for seed in [seed, [BaseElement::new(3), BaseElement::new(4)]] {
+        let expected = prover.prove(prover.build_trace(seed, 8)).unwrap();
+        let actual = cached.prove(prover.build_trace(seed, 8)).unwrap();
+        assert_eq!(expected.to_bytes(), actual.to_bytes());
+    }
+
+    // the table should be ignored for proofs of a different length
+    let expected = prover.prove(prover.build_trace(seed, 16)).unwrap();
+    let actual = cached.prove(prover.build_trace(seed, 16)).unwrap();
+    assert_eq!(expected.to_bytes(), actual.to_bytes());
+}
+
 fn build_options(use_extension_field: bool) -> ProofOptions {
     let extension = if use_extension_field {
         FieldExtension::Quadratic
@@ -41,3 +129,28 @@ fn build_options(use_extension_field: bool) -> ProofOptions {
     };
     ProofOptions::new(28, 8, 0, extension, 4, 256)
 }
+
+/// Rescue prover which uses a pre-built table of periodic values for all proofs.
+struct CachedRescueProver {
+    prover: RescueProver<Blake3_256>,
+    periodic_values: PeriodicValueTable<BaseElement>,
+}
+
+impl Prover for CachedRescueProver {
+    type BaseField = BaseElement;
+    type Air = RescueAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = Blake3_256;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> PublicInputs {
+        self.prover.get_pub_inputs(trace)
+    }
+
+    fn options(&self) -> &ProofOptions {
+        self.prover.options()
+    }
+
+    fn periodic_values(&self) -> Option<&PeriodicValueTable<Self::BaseField>> {
+        Some(&self.periodic_values)
+    }
+}
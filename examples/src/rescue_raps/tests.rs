@@ -3,7 +3,8 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::Blake3_256;
+use super::{prover::RescueRapsProver, BaseElement, Blake3_256, FieldElement, Trace};
+use rand_utils::rand_vector;
 use winterfell::{FieldExtension, ProofOptions};
 
 #[test]
@@ -33,6 +34,34 @@ fn rescue_test_basic_proof_verification_fail() {
     crate::tests::test_basic_proof_verification_fail(rescue_eg);
 }
 
+#[test]
+fn rescue_test_trace_rows_per_leaf() {
+    let options = build_options(true).with_trace_rows_per_leaf(8);
+    let rescue_eg = Box::new(super::RescueRapsExample::<Blake3_256>::new(128, options));
+    crate::tests::test_basic_proof_verification(rescue_eg);
+}
+
+#[test]
+fn rescue_test_permutation_argument() {
+    let example = super::RescueRapsExample::<Blake3_256>::new(16, build_options(false));
+    let prover = RescueRapsProver::<Blake3_256>::new(build_options(false));
+    let rand_elements = rand_vector::<BaseElement>(3);
+
+    // the running product in the auxiliary segment should end at one when the second chain
+    // absorbs a permutation of the seeds absorbed by the first chain
+    let mut trace = prover.build_trace(&example.seeds, &example.permuted_seeds, example.result);
+    let aux_segment = trace.build_aux_segment(&[], &rand_elements).unwrap();
+    assert_eq!(BaseElement::ONE, aux_segment.get(2, trace.length() - 1));
+
+    // otherwise, the running product should end at a different value
+    let mut forged_seeds = example.permuted_seeds.clone();
+    forged_seeds[0] = example.seeds[0];
+    let result = super::compute_permuted_hash_chains(&example.seeds, &forged_seeds);
+    let mut trace = prover.build_trace(&example.seeds, &forged_seeds, result);
+    let aux_segment = trace.build_aux_segment(&[], &rand_elements).unwrap();
+    assert_ne!(BaseElement::ONE, aux_segment.get(2, trace.length() - 1));
+}
+
 fn build_options(use_extension_field: bool) -> ProofOptions {
     let extension = if use_extension_field {
         FieldExtension::Quadratic
@@ -4,14 +4,57 @@
 // LICENSE file in the root directory of this source tree.
 
 use crate::Example;
+use winterfell::{StarkProof, VerifierError};
 
 pub fn test_basic_proof_verification(e: Box<dyn Example>) {
     let proof = e.prove();
+
+    // proofs should survive a serialization round trip, and proofs serialized using a different
+    // format version should be rejected
+    let mut proof_bytes = proof.to_bytes();
+    assert_eq!(proof, StarkProof::from_bytes(&proof_bytes).unwrap());
+
+    // JSON export should preserve the proof exactly
+    let proof_json = proof.to_json();
+    assert_eq!(proof, StarkProof::from_json(&proof_json).unwrap());
+
+    // size breakdown should account for every byte of the proof
+    let stats = proof.stats();
+    assert_eq!(proof_bytes.len(), stats.total);
+    assert_eq!(proof.fri_proof.num_layers(), stats.num_fri_layers());
+    assert_eq!(proof.fri_proof.size(), stats.fri_total() + 2);
+    assert_eq!(1 + 2 + 8, stats.other);
+    proof_bytes[0] += 1;
+    assert!(StarkProof::from_bytes(&proof_bytes).is_err());
+
     assert!(e.verify(proof).is_ok());
 }
 
 pub fn test_basic_proof_verification_fail(e: Box<dyn Example>) {
     let proof = e.prove();
     let verified = e.verify_with_wrong_inputs(proof);
-    assert!(verified.is_err());
+    assert_eq!(Err(VerifierError::InconsistentPublicInputs), verified);
+}
+
+#[test]
+fn parse_example_size() {
+    assert_eq!(Ok(1024), crate::parse_size("1024"));
+    assert_eq!(Ok(1 << 20), crate::parse_size("2^20"));
+    assert_eq!(Ok(1), crate::parse_size("2^0"));
+    assert!(crate::parse_size("3^4").is_err());
+    assert!(crate::parse_size("2^64").is_err());
+    assert!(crate::parse_size("abc").is_err());
+}
+
+#[test]
+fn parse_example_field_and_hash_fn() {
+    use crate::{parse_field, parse_hash_fn, FieldType, HashFunction};
+
+    assert_eq!(Ok(FieldType::F64), parse_field("f64"));
+    assert_eq!(Ok(FieldType::F128), parse_field("f128"));
+    assert!(parse_field("f62").is_err());
+    assert_eq!(Ok(HashFunction::Blake3_256), parse_hash_fn("blake3"));
+    assert_eq!(Ok(HashFunction::Rp64_256), parse_hash_fn("rescue"));
+    assert_eq!(Ok(HashFunction::Keccak256), parse_hash_fn("keccak_256"));
+    assert!(parse_hash_fn("md5").is_err());
 }
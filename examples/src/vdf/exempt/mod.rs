@@ -3,7 +3,9 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::{Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Sha3_256};
+use crate::{
+    Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Keccak256, Sha2_256, Sha3_256,
+};
 use core::marker::PhantomData;
 use log::debug;
 use std::time::Instant;
@@ -38,7 +40,9 @@ pub fn get_example(options: &ExampleOptions, num_steps: usize) -> Result<Box<dyn
     match hash_fn {
         HashFunction::Blake3_192 => Ok(Box::new(VdfExample::<Blake3_192>::new(num_steps, options))),
         HashFunction::Blake3_256 => Ok(Box::new(VdfExample::<Blake3_256>::new(num_steps, options))),
+        HashFunction::Sha2_256 => Ok(Box::new(VdfExample::<Sha2_256>::new(num_steps, options))),
         HashFunction::Sha3_256 => Ok(Box::new(VdfExample::<Sha3_256>::new(num_steps, options))),
+        HashFunction::Keccak256 => Ok(Box::new(VdfExample::<Keccak256>::new(num_steps, options))),
         _ => Err("The specified hash function cannot be used with this example.".to_string()),
     }
 }
@@ -5,3 +5,4 @@
 
 pub mod exempt;
 pub mod regular;
+pub mod squaring;
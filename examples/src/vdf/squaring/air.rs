@@ -0,0 +1,77 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{BaseElement, FieldElement, ProofOptions};
+use winterfell::{
+    Air, AirContext, Assertion, ByteWriter, EvaluationFrame, Serializable, TraceInfo,
+    TransitionConstraintDegree,
+};
+
+// PUBLIC INPUTS
+// ================================================================================================
+
+#[derive(Clone)]
+pub struct SquaringInputs {
+    pub seed: BaseElement,
+    pub constant: BaseElement,
+    pub result: BaseElement,
+}
+
+impl Serializable for SquaringInputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write(self.seed);
+        target.write(self.constant);
+        target.write(self.result);
+    }
+}
+
+// ITERATED SQUARING AIR
+// ================================================================================================
+
+pub struct SquaringAir {
+    context: AirContext<BaseElement>,
+    seed: BaseElement,
+    constant: BaseElement,
+    result: BaseElement,
+}
+
+impl Air for SquaringAir {
+    type BaseField = BaseElement;
+    type PublicInputs = SquaringInputs;
+
+    fn new(trace_info: TraceInfo, pub_inputs: SquaringInputs, options: ProofOptions) -> Self {
+        let degrees = vec![TransitionConstraintDegree::new(2)];
+        Self {
+            context: AirContext::new(trace_info, degrees, 2, options),
+            seed: pub_inputs.seed,
+            constant: pub_inputs.constant,
+            result: pub_inputs.result,
+        }
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current_state = frame.current()[0];
+        let next_state = frame.next()[0];
+
+        result[0] = next_state - (current_state.square() + self.constant.into());
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        let last_step = self.trace_length() - 1;
+        vec![
+            Assertion::single(0, 0, self.seed),
+            Assertion::single(0, last_step, self.result),
+        ]
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+}
@@ -0,0 +1,158 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{
+    Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Keccak256, Sha2_256, Sha3_256,
+};
+use core::marker::PhantomData;
+use log::debug;
+use std::time::Instant;
+use winterfell::{
+    crypto::ElementHasher,
+    math::{fields::f128::BaseElement, log2, FieldElement},
+    ProofOptions, Prover, StarkProof, Trace, TraceTable, VerifierError,
+};
+
+mod air;
+use air::{SquaringAir, SquaringInputs};
+
+mod prover;
+use prover::SquaringProver;
+
+#[cfg(test)]
+mod tests;
+
+// ITERATED SQUARING EXAMPLE
+// ================================================================================================
+
+pub fn get_example(
+    options: &ExampleOptions,
+    num_steps: usize,
+    constant: u128,
+) -> Result<Box<dyn Example>, String> {
+    let (options, hash_fn) = options.to_proof_options(85, 2);
+
+    match hash_fn {
+        HashFunction::Blake3_192 => Ok(Box::new(SquaringExample::<Blake3_192>::new(
+            num_steps, constant, options,
+        ))),
+        HashFunction::Blake3_256 => Ok(Box::new(SquaringExample::<Blake3_256>::new(
+            num_steps, constant, options,
+        ))),
+        HashFunction::Sha2_256 => Ok(Box::new(SquaringExample::<Sha2_256>::new(
+            num_steps, constant, options,
+        ))),
+        HashFunction::Sha3_256 => Ok(Box::new(SquaringExample::<Sha3_256>::new(
+            num_steps, constant, options,
+        ))),
+        HashFunction::Keccak256 => Ok(Box::new(SquaringExample::<Keccak256>::new(
+            num_steps, constant, options,
+        ))),
+        _ => Err("The specified hash function cannot be used with this example.".to_string()),
+    }
+}
+
+pub struct SquaringExample<H: ElementHasher> {
+    options: ProofOptions,
+    num_steps: usize,
+    seed: BaseElement,
+    constant: BaseElement,
+    result: BaseElement,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> SquaringExample<H> {
+    pub fn new(num_steps: usize, constant: u128, options: ProofOptions) -> Self {
+        assert!(
+            num_steps.is_power_of_two(),
+            "number of steps must be a power of 2"
+        );
+
+        // iterate the function
+        let now = Instant::now();
+        let seed = BaseElement::new(123);
+        let constant = BaseElement::new(constant);
+        let result = execute_squaring(seed, constant, num_steps);
+        debug!(
+            "Computed {} iterations of x -> x^2 + {} in {} ms",
+            num_steps - 1,
+            constant,
+            now.elapsed().as_millis()
+        );
+
+        Self {
+            options,
+            num_steps,
+            seed,
+            constant,
+            result,
+            _hasher: PhantomData,
+        }
+    }
+}
+
+// EXAMPLE IMPLEMENTATION
+// ================================================================================================
+
+impl<H: ElementHasher> Example for SquaringExample<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    fn prove(&self) -> StarkProof {
+        debug!(
+            "Generating proof for iterated squaring over {} steps\n\
+            ---------------------",
+            self.num_steps
+        );
+
+        // create a prover
+        let prover = SquaringProver::<H>::new(self.constant, self.options.clone());
+
+        // generate execution trace
+        let now = Instant::now();
+        let trace = prover.build_trace(self.seed, self.num_steps);
+
+        let trace_width = trace.width();
+        let trace_length = trace.length();
+        debug!(
+            "Generated execution trace of {} registers and 2^{} steps in {} ms",
+            trace_width,
+            log2(trace_length),
+            now.elapsed().as_millis()
+        );
+
+        // generate the proof
+        prover.prove(trace).unwrap()
+    }
+
+    fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let pub_inputs = SquaringInputs {
+            seed: self.seed,
+            constant: self.constant,
+            result: self.result,
+        };
+        winterfell::verify::<SquaringAir, H>(proof, pub_inputs)
+    }
+
+    fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let pub_inputs = SquaringInputs {
+            seed: self.seed,
+            constant: self.constant + BaseElement::ONE,
+            result: self.result,
+        };
+        winterfell::verify::<SquaringAir, H>(proof, pub_inputs)
+    }
+}
+
+// ITERATED SQUARING FUNCTION
+// ================================================================================================
+
+fn execute_squaring(seed: BaseElement, constant: BaseElement, n: usize) -> BaseElement {
+    let mut state = seed;
+    for _ in 0..(n - 1) {
+        state = state.square() + constant;
+    }
+    state
+}
@@ -0,0 +1,64 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    BaseElement, ElementHasher, FieldElement, PhantomData, ProofOptions, Prover, SquaringAir,
+    SquaringInputs, Trace, TraceTable,
+};
+
+// ITERATED SQUARING PROVER
+// ================================================================================================
+
+pub struct SquaringProver<H: ElementHasher> {
+    options: ProofOptions,
+    constant: BaseElement,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> SquaringProver<H> {
+    pub fn new(constant: BaseElement, options: ProofOptions) -> Self {
+        Self {
+            options,
+            constant,
+            _hasher: PhantomData,
+        }
+    }
+
+    pub fn build_trace(&self, seed: BaseElement, n: usize) -> TraceTable<BaseElement> {
+        let mut trace = Vec::with_capacity(n);
+        let mut state = seed;
+
+        trace.push(state);
+        for _ in 0..(n - 1) {
+            state = state.square() + self.constant;
+            trace.push(state);
+        }
+
+        TraceTable::init(vec![trace])
+    }
+}
+
+impl<H: ElementHasher> Prover for SquaringProver<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    type BaseField = BaseElement;
+    type Air = SquaringAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = H;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> SquaringInputs {
+        let last_step = trace.length() - 1;
+        SquaringInputs {
+            seed: trace.get(0, 0),
+            constant: self.constant,
+            result: trace.get(0, last_step),
+        }
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+}
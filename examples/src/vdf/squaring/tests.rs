@@ -0,0 +1,46 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::Blake3_256;
+use winterfell::{FieldExtension, ProofOptions};
+
+#[test]
+fn squaring_test_basic_proof_verification() {
+    let vdf = Box::new(super::SquaringExample::<Blake3_256>::new(
+        128,
+        42,
+        build_options(false),
+    ));
+    crate::tests::test_basic_proof_verification(vdf);
+}
+
+#[test]
+fn squaring_test_basic_proof_verification_extension() {
+    let vdf = Box::new(super::SquaringExample::<Blake3_256>::new(
+        128,
+        42,
+        build_options(true),
+    ));
+    crate::tests::test_basic_proof_verification(vdf);
+}
+
+#[test]
+fn squaring_test_basic_proof_verification_fail() {
+    let vdf = Box::new(super::SquaringExample::<Blake3_256>::new(
+        128,
+        42,
+        build_options(false),
+    ));
+    crate::tests::test_basic_proof_verification_fail(vdf);
+}
+
+fn build_options(use_extension_field: bool) -> ProofOptions {
+    let extension = if use_extension_field {
+        FieldExtension::Quadratic
+    } else {
+        FieldExtension::None
+    };
+    ProofOptions::new(85, 2, 0, extension, 4, 256)
+}
@@ -0,0 +1,212 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    build_rom, trace_table::fingerprint, BaseElement, FieldElement, Instruction, ProofOptions,
+    ADD_COL, A_COL, B_COL, B_INV_COL, IMM_COL, JNZ_COL, MULTIPLICITY_COL, MUL_COL, PC_COL,
+    TRACE_WIDTH,
+};
+use crate::utils::{are_equal, is_binary, not};
+use winterfell::{
+    math::ExtensionOf, Air, AirContext, Assertion, AuxTraceRandElements, ByteWriter,
+    EvaluationFrame, Serializable, TraceInfo, TransitionConstraintDegree,
+};
+
+// PUBLIC INPUTS
+// ================================================================================================
+
+pub struct VmInputs {
+    pub program: Vec<Instruction>,
+    pub init: [BaseElement; 2],
+    pub result: BaseElement,
+}
+
+impl Serializable for VmInputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u16(self.program.len() as u16);
+        for instruction in self.program.iter() {
+            target.write(instruction.opcode());
+            target.write(instruction.immediate());
+        }
+        target.write(&self.init[..]);
+        target.write(self.result);
+    }
+}
+
+// VM AIR
+// ================================================================================================
+
+pub struct VmAir {
+    context: AirContext<BaseElement>,
+    rom: [Vec<BaseElement>; 3],
+    init: [BaseElement; 2],
+    result: BaseElement,
+}
+
+impl Air for VmAir {
+    type BaseField = BaseElement;
+    type PublicInputs = VmInputs;
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    fn new(trace_info: TraceInfo, pub_inputs: VmInputs, options: ProofOptions) -> Self {
+        let rom = build_rom(&pub_inputs.program);
+        let main_degrees = vec![
+            TransitionConstraintDegree::new(4), // program counter
+            TransitionConstraintDegree::new(3), // register a
+            TransitionConstraintDegree::new(2), // register b
+            TransitionConstraintDegree::new(2), // mul selector
+            TransitionConstraintDegree::new(2), // add selector
+            TransitionConstraintDegree::new(2), // jnz selector
+            TransitionConstraintDegree::new(2), // at most one selector is set
+            TransitionConstraintDegree::new(3), // inverse of register b
+        ];
+        let aux_degrees = vec![TransitionConstraintDegree::with_cycles(
+            2,
+            vec![rom[0].len()],
+        )];
+        assert_eq!(TRACE_WIDTH + 1, trace_info.width());
+        VmAir {
+            context: AirContext::new_multi_segment(
+                trace_info,
+                main_degrees,
+                aux_degrees,
+                7,
+                2,
+                options,
+            ),
+            rom,
+            init: pub_inputs.init,
+            result: pub_inputs.result,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        debug_assert_eq!(TRACE_WIDTH, current.len());
+        debug_assert_eq!(TRACE_WIDTH, next.len());
+
+        let pc = current[PC_COL];
+        let imm = current[IMM_COL];
+        let b = current[B_COL];
+
+        // exactly one instruction is executed at every step; HALT is selected when none of the
+        // selector columns are set
+        let is_mul = current[MUL_COL];
+        let is_add = current[ADD_COL];
+        let is_jnz = current[JNZ_COL];
+        let is_halt = E::ONE - is_mul - is_add - is_jnz;
+
+        // b_is_zero is ONE when register b is ZERO, and ZERO otherwise; this is enforced by the
+        // last constraint below
+        let b_is_zero = not(b * current[B_INV_COL]);
+
+        // HALT keeps the program counter in place, JNZ jumps to the immediate value when register
+        // b is not ZERO, and all other instructions advance the program counter by one
+        let pc_next = pc + E::ONE;
+        let expected_pc = is_halt * pc
+            + (is_mul + is_add) * pc_next
+            + is_jnz * (b_is_zero * pc_next + not(b_is_zero) * imm);
+        result[0] = are_equal(next[PC_COL], expected_pc);
+
+        // MUL multiplies register a by the immediate value, ADD adds the immediate value to
+        // register b; registers are left unchanged by all other instructions
+        let a = current[A_COL];
+        result[1] = are_equal(next[A_COL], a + is_mul * (a * imm - a));
+        result[2] = are_equal(next[B_COL], b + is_add * imm);
+
+        // selectors must be binary, and at most one of them can be set
+        result[3] = is_binary(is_mul);
+        result[4] = is_binary(is_add);
+        result[5] = is_binary(is_jnz);
+        result[6] = is_binary(is_halt);
+
+        // b * b_is_zero = 0 implies that b_is_zero is ZERO whenever b is not ZERO; when b is
+        // ZERO, b_is_zero is ONE regardless of the value in the inverse column
+        result[7] = b * b_is_zero;
+    }
+
+    fn evaluate_aux_transition<F, E>(
+        &self,
+        main_frame: &EvaluationFrame<F>,
+        aux_frame: &EvaluationFrame<E>,
+        periodic_values: &[F],
+        aux_rand_elements: &AuxTraceRandElements<E>,
+        result: &mut [E],
+    ) where
+        F: FieldElement<BaseField = Self::BaseField>,
+        E: FieldElement<BaseField = Self::BaseField> + ExtensionOf<F>,
+    {
+        let current = main_frame.current();
+        let random_elements = aux_rand_elements.get_segment_elements(0);
+        let alpha = random_elements[0];
+        let gamma = random_elements[1];
+
+        // fingerprint of the instruction decoded at the current step
+        let opcode =
+            current[MUL_COL] + current[ADD_COL].double() + current[JNZ_COL] * F::from(3u32);
+        let decoded = fingerprint(gamma, current[PC_COL], opcode, current[IMM_COL]);
+
+        // fingerprint of the ROM entry at the current step
+        let stored = fingerprint(
+            gamma,
+            periodic_values[0],
+            periodic_values[1],
+            periodic_values[2],
+        );
+        let multiplicity = E::from(current[MULTIPLICITY_COL]);
+
+        // the running sum must grow by 1 / (alpha - decoded) - multiplicity / (alpha - stored);
+        // both sides of the equation are multiplied by the denominators
+        let delta = aux_frame.next()[0] - aux_frame.current()[0];
+        result[0] = are_equal(
+            delta * (alpha - decoded) * (alpha - stored),
+            (alpha - stored) - multiplicity * (alpha - decoded),
+        );
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        // execution starts at the first instruction with the specified register values; the
+        // instruction before the last step must be HALT, and thus, the result in register a at
+        // the last step is the value with which the program halted
+        let last_step = self.trace_length() - 1;
+        vec![
+            Assertion::single(PC_COL, 0, BaseElement::ZERO),
+            Assertion::single(A_COL, 0, self.init[0]),
+            Assertion::single(B_COL, 0, self.init[1]),
+            Assertion::single(MUL_COL, last_step - 1, BaseElement::ZERO),
+            Assertion::single(ADD_COL, last_step - 1, BaseElement::ZERO),
+            Assertion::single(JNZ_COL, last_step - 1, BaseElement::ZERO),
+            Assertion::single(A_COL, last_step, self.result),
+        ]
+    }
+
+    fn get_aux_assertions<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        _aux_rand_elements: &AuxTraceRandElements<E>,
+    ) -> Vec<Assertion<E>> {
+        // the running sum of the lookup argument starts and ends at ZERO
+        let last_step = self.trace_length() - 1;
+        vec![
+            Assertion::single(0, 0, E::ZERO),
+            Assertion::single(0, last_step, E::ZERO),
+        ]
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseField>> {
+        // the program ROM is repeated throughout the trace
+        self.rom.to_vec()
+    }
+}
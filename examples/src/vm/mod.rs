@@ -0,0 +1,216 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{
+    Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Keccak256, Sha2_256, Sha3_256,
+};
+use core::marker::PhantomData;
+use log::debug;
+use std::time::Instant;
+use winterfell::{
+    crypto::ElementHasher,
+    math::{fields::f128::BaseElement, log2, FieldElement},
+    ProofOptions, Prover, StarkProof, Trace, VerifierError,
+};
+
+mod air;
+use air::{VmAir, VmInputs};
+
+mod prover;
+use prover::VmProver;
+
+mod trace_table;
+pub use trace_table::VmTraceTable;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of columns in the main segment of the execution trace.
+const TRACE_WIDTH: usize = 9;
+
+// Indexes of the main trace columns
+const PC_COL: usize = 0;
+const A_COL: usize = 1;
+const B_COL: usize = 2;
+const IMM_COL: usize = 3;
+const MUL_COL: usize = 4;
+const ADD_COL: usize = 5;
+const JNZ_COL: usize = 6;
+const B_INV_COL: usize = 7;
+const MULTIPLICITY_COL: usize = 8;
+
+/// Maximum number of steps a program may execute before halting.
+const MAX_STEPS: usize = 1 << 20;
+
+/// Base of the exponentiation computed by the example program.
+const BASE: BaseElement = BaseElement::new(3);
+
+// INSTRUCTIONS
+// ================================================================================================
+
+/// An instruction of a tiny VM with two registers `a` and `b`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Instruction {
+    /// Stops execution; the program counter and both registers remain unchanged.
+    Halt,
+    /// Multiplies register `a` by the immediate value.
+    Mul(BaseElement),
+    /// Adds the immediate value to register `b`.
+    Add(BaseElement),
+    /// Jumps to the specified instruction if register `b` is not zero.
+    Jnz(usize),
+}
+
+impl Instruction {
+    /// Returns the opcode of this instruction; this is the value of the instruction's selector
+    /// columns combined as `mul + 2 * add + 3 * jnz`.
+    pub fn opcode(&self) -> BaseElement {
+        match self {
+            Self::Halt => BaseElement::ZERO,
+            Self::Mul(_) => BaseElement::new(1),
+            Self::Add(_) => BaseElement::new(2),
+            Self::Jnz(_) => BaseElement::new(3),
+        }
+    }
+
+    /// Returns the immediate value of this instruction.
+    pub fn immediate(&self) -> BaseElement {
+        match self {
+            Self::Halt => BaseElement::ZERO,
+            Self::Mul(value) | Self::Add(value) => *value,
+            Self::Jnz(target) => BaseElement::new(*target as u128),
+        }
+    }
+}
+
+/// Returns a program which multiplies register `a` by 3 as many times as the initial value of
+/// register `b`; when `a` is initialized to 1, the result is 3^b.
+pub fn build_pow_program() -> Vec<Instruction> {
+    vec![
+        Instruction::Mul(BASE),
+        Instruction::Add(-BaseElement::ONE),
+        Instruction::Jnz(0),
+        Instruction::Halt,
+    ]
+}
+
+/// Returns the program ROM as three columns: instruction address, opcode, and immediate value.
+///
+/// The program is padded with [Instruction::Halt] to the smallest power of two greater than its
+/// length; thus, the last entry of the ROM is never an instruction of the original program.
+fn build_rom(program: &[Instruction]) -> [Vec<BaseElement>; 3] {
+    let rom_length = (program.len() + 1).next_power_of_two();
+    let mut rom = [
+        Vec::with_capacity(rom_length),
+        Vec::with_capacity(rom_length),
+        Vec::with_capacity(rom_length),
+    ];
+    for i in 0..rom_length {
+        let instruction = program.get(i).copied().unwrap_or(Instruction::Halt);
+        rom[0].push(BaseElement::new(i as u128));
+        rom[1].push(instruction.opcode());
+        rom[2].push(instruction.immediate());
+    }
+    rom
+}
+
+// VM EXAMPLE
+// ================================================================================================
+
+pub fn get_example(options: &ExampleOptions, exponent: usize) -> Result<Box<dyn Example>, String> {
+    let (options, hash_fn) = options.to_proof_options(42, 8);
+
+    match hash_fn {
+        HashFunction::Blake3_192 => Ok(Box::new(VmExample::<Blake3_192>::new(exponent, options))),
+        HashFunction::Blake3_256 => Ok(Box::new(VmExample::<Blake3_256>::new(exponent, options))),
+        HashFunction::Sha2_256 => Ok(Box::new(VmExample::<Sha2_256>::new(exponent, options))),
+        HashFunction::Sha3_256 => Ok(Box::new(VmExample::<Sha3_256>::new(exponent, options))),
+        HashFunction::Keccak256 => Ok(Box::new(VmExample::<Keccak256>::new(exponent, options))),
+        _ => Err("The specified hash function cannot be used with this example.".to_string()),
+    }
+}
+
+pub struct VmExample<H: ElementHasher> {
+    options: ProofOptions,
+    program: Vec<Instruction>,
+    exponent: usize,
+    result: BaseElement,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> VmExample<H> {
+    pub fn new(exponent: usize, options: ProofOptions) -> Self {
+        assert!(exponent > 0, "exponent must be greater than zero");
+        let program = build_pow_program();
+        let result = BASE.exp(exponent as u128);
+
+        Self {
+            options,
+            program,
+            exponent,
+            result,
+            _hasher: PhantomData,
+        }
+    }
+}
+
+// EXAMPLE IMPLEMENTATION
+// ================================================================================================
+
+impl<H: ElementHasher> Example for VmExample<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    fn prove(&self) -> StarkProof {
+        debug!(
+            "Generating proof for executing a program of {} instructions computing 3^{}\n\
+            ---------------------",
+            self.program.len(),
+            self.exponent
+        );
+
+        // create a prover
+        let prover = VmProver::<H>::new(self.options.clone());
+
+        // generate execution trace
+        let now = Instant::now();
+        let trace = prover.build_trace(
+            &self.program,
+            BaseElement::ONE,
+            BaseElement::new(self.exponent as u128),
+        );
+        let trace_length = trace.length();
+        debug!(
+            "Generated execution trace of {} registers and 2^{} steps in {} ms",
+            trace.width(),
+            log2(trace_length),
+            now.elapsed().as_millis()
+        );
+
+        // generate the proof
+        prover.prove(trace).unwrap()
+    }
+
+    fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let pub_inputs = VmInputs {
+            program: self.program.clone(),
+            init: [BaseElement::ONE, BaseElement::new(self.exponent as u128)],
+            result: self.result,
+        };
+        winterfell::verify::<VmAir, H>(proof, pub_inputs)
+    }
+
+    fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let pub_inputs = VmInputs {
+            program: self.program.clone(),
+            init: [BaseElement::ONE, BaseElement::new(self.exponent as u128)],
+            result: self.result + BaseElement::ONE,
+        };
+        winterfell::verify::<VmAir, H>(proof, pub_inputs)
+    }
+}
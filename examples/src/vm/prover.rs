@@ -0,0 +1,124 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    build_rom, BaseElement, ElementHasher, FieldElement, Instruction, PhantomData, ProofOptions,
+    Prover, Trace, VmAir, VmInputs, VmTraceTable, ADD_COL, A_COL, B_COL, B_INV_COL, IMM_COL,
+    JNZ_COL, MAX_STEPS, MULTIPLICITY_COL, MUL_COL, PC_COL, TRACE_WIDTH,
+};
+use winterfell::TraceInfo;
+
+// VM PROVER
+// ================================================================================================
+
+pub struct VmProver<H: ElementHasher> {
+    options: ProofOptions,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> VmProver<H> {
+    pub fn new(options: ProofOptions) -> Self {
+        Self {
+            options,
+            _hasher: PhantomData,
+        }
+    }
+
+    /// Executes the specified program with registers `a` and `b` initialized to the specified
+    /// values, and returns the resulting execution trace.
+    ///
+    /// After the program halts, the trace is padded with HALT steps so that its length is a power
+    /// of two, the last two steps of the trace are HALT steps, and the ROM fits into the trace.
+    ///
+    /// # Panics
+    /// Panics if the program does not halt within [MAX_STEPS](super::MAX_STEPS) steps, or if a
+    /// jump targets an address outside of the program.
+    pub fn build_trace(
+        &self,
+        program: &[Instruction],
+        a: BaseElement,
+        b: BaseElement,
+    ) -> VmTraceTable {
+        // execute the program until it halts, recording the state and the decoded instruction
+        // at every step
+        let mut steps = Vec::new();
+        let (mut pc, mut a, mut b) = (0, a, b);
+        loop {
+            assert!(
+                steps.len() < MAX_STEPS,
+                "program did not halt within {} steps",
+                MAX_STEPS
+            );
+            let instruction = program[pc];
+            steps.push((pc, a, b, instruction));
+            match instruction {
+                Instruction::Halt => break,
+                Instruction::Mul(value) => a *= value,
+                Instruction::Add(value) => b += value,
+                Instruction::Jnz(target) if b != BaseElement::ZERO => {
+                    pc = target;
+                    continue;
+                }
+                Instruction::Jnz(_) => {}
+            }
+            pc += 1;
+        }
+
+        // pad the trace with HALT steps; there must be at least one HALT step before the last
+        // step because the last step is excluded from the lookup argument
+        let rom_length = build_rom(program)[0].len();
+        let trace_length = (steps.len() + 1)
+            .next_power_of_two()
+            .max(rom_length)
+            .max(TraceInfo::MIN_TRACE_LENGTH);
+        let halt_step = steps[steps.len() - 1];
+        steps.resize(trace_length, halt_step);
+
+        // fill the main trace columns; multiplicities of ROM lookups are recorded in the rows of
+        // the first copy of the ROM
+        let mut columns = vec![vec![BaseElement::ZERO; trace_length]; TRACE_WIDTH];
+        for (i, &(pc, a, b, instruction)) in steps.iter().enumerate() {
+            columns[PC_COL][i] = BaseElement::new(pc as u128);
+            columns[A_COL][i] = a;
+            columns[B_COL][i] = b;
+            columns[IMM_COL][i] = instruction.immediate();
+            match instruction {
+                Instruction::Halt => {}
+                Instruction::Mul(_) => columns[MUL_COL][i] = BaseElement::ONE,
+                Instruction::Add(_) => columns[ADD_COL][i] = BaseElement::ONE,
+                Instruction::Jnz(_) => columns[JNZ_COL][i] = BaseElement::ONE,
+            }
+            columns[B_INV_COL][i] = b.inv();
+            if i < trace_length - 1 {
+                columns[MULTIPLICITY_COL][pc] += BaseElement::ONE;
+            }
+        }
+
+        VmTraceTable::new(columns, program.to_vec())
+    }
+}
+
+impl<H: ElementHasher> Prover for VmProver<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    type BaseField = BaseElement;
+    type Air = VmAir;
+    type Trace = VmTraceTable;
+    type HashFn = H;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> VmInputs {
+        let last_step = trace.length() - 1;
+        VmInputs {
+            program: trace.program().to_vec(),
+            init: [trace.get(A_COL, 0), trace.get(B_COL, 0)],
+            result: trace.get(A_COL, last_step),
+        }
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+}
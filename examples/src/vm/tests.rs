@@ -0,0 +1,71 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    build_pow_program, BaseElement, FieldElement, Instruction, Trace, VmProver, A_COL,
+    MULTIPLICITY_COL,
+};
+use crate::Blake3_256;
+use winterfell::{FieldExtension, ProofOptions};
+
+#[test]
+fn vm_test_basic_proof_verification() {
+    let vm = Box::new(super::VmExample::<Blake3_256>::new(
+        10,
+        build_options(false),
+    ));
+    crate::tests::test_basic_proof_verification(vm);
+}
+
+#[test]
+fn vm_test_basic_proof_verification_extension() {
+    let vm = Box::new(super::VmExample::<Blake3_256>::new(10, build_options(true)));
+    crate::tests::test_basic_proof_verification(vm);
+}
+
+#[test]
+fn vm_test_basic_proof_verification_fail() {
+    let vm = Box::new(super::VmExample::<Blake3_256>::new(
+        10,
+        build_options(false),
+    ));
+    crate::tests::test_basic_proof_verification_fail(vm);
+}
+
+#[test]
+fn vm_test_build_trace() {
+    let prover = VmProver::<Blake3_256>::new(build_options(false));
+    let program = build_pow_program();
+    let trace = prover.build_trace(&program, BaseElement::ONE, BaseElement::new(5));
+
+    // 5 loop iterations of 3 instructions each and a HALT step, padded to 32 steps
+    assert_eq!(32, trace.length());
+    assert_eq!(BaseElement::new(243), trace.get(A_COL, trace.length() - 1));
+
+    // every step except the last one is looked up in the ROM exactly once
+    let num_lookups = (0..trace.length())
+        .map(|i| trace.get(MULTIPLICITY_COL, i))
+        .fold(BaseElement::ZERO, |acc, m| acc + m);
+    assert_eq!(BaseElement::new(31), num_lookups);
+    assert_eq!(BaseElement::new(5), trace.get(MULTIPLICITY_COL, 0));
+    assert_eq!(BaseElement::new(16), trace.get(MULTIPLICITY_COL, 3));
+}
+
+#[test]
+#[should_panic(expected = "program did not halt")]
+fn vm_test_build_trace_no_halt() {
+    let prover = VmProver::<Blake3_256>::new(build_options(false));
+    let program = vec![Instruction::Jnz(0)];
+    prover.build_trace(&program, BaseElement::ONE, BaseElement::ONE);
+}
+
+fn build_options(use_extension_field: bool) -> ProofOptions {
+    let extension = if use_extension_field {
+        FieldExtension::Quadratic
+    } else {
+        FieldExtension::None
+    };
+    ProofOptions::new(42, 8, 0, extension, 4, 256)
+}
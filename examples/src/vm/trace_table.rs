@@ -0,0 +1,142 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    build_rom, BaseElement, FieldElement, Instruction, ADD_COL, IMM_COL, JNZ_COL, MULTIPLICITY_COL,
+    MUL_COL, PC_COL, TRACE_WIDTH,
+};
+use winterfell::{EvaluationFrame, Matrix, Trace, TraceLayout};
+
+// VM TRACE TABLE
+// ================================================================================================
+
+/// Execution trace of a program run by the tiny VM.
+///
+/// The main segment of the trace contains the VM state and the decoded instruction at every step;
+/// the auxiliary segment consists of a single column with a running sum of the lookup argument
+/// which ties the decoded instructions to the program ROM. Since the ROM is needed to build the
+/// auxiliary segment, the trace retains the program it was generated from.
+pub struct VmTraceTable {
+    layout: TraceLayout,
+    trace: Matrix<BaseElement>,
+    program: Vec<Instruction>,
+}
+
+impl VmTraceTable {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new trace table consisting of the specified main trace columns generated by
+    /// executing the specified program.
+    pub fn new(columns: Vec<Vec<BaseElement>>, program: Vec<Instruction>) -> Self {
+        assert_eq!(TRACE_WIDTH, columns.len());
+        Self {
+            layout: TraceLayout::new(TRACE_WIDTH, [1], [2]),
+            trace: Matrix::new(columns),
+            program,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of columns in the main segment of this trace.
+    pub fn width(&self) -> usize {
+        self.main_trace_width()
+    }
+
+    /// Returns the value of the specified main trace column at the specified step.
+    pub fn get(&self, column: usize, step: usize) -> BaseElement {
+        self.trace.get(column, step)
+    }
+
+    /// Returns the program which was executed to generate this trace.
+    pub fn program(&self) -> &[Instruction] {
+        &self.program
+    }
+}
+
+// TRACE TRAIT IMPLEMENTATION
+// ================================================================================================
+
+impl Trace for VmTraceTable {
+    type BaseField = BaseElement;
+
+    fn layout(&self) -> &TraceLayout {
+        &self.layout
+    }
+
+    fn length(&self) -> usize {
+        self.trace.num_rows()
+    }
+
+    fn meta(&self) -> &[u8] {
+        &[]
+    }
+
+    fn read_main_frame(&self, row_idx: usize, frame: &mut EvaluationFrame<Self::BaseField>) {
+        let next_row_idx = (row_idx + 1) % self.length();
+        self.trace.read_row_into(row_idx, frame.current_mut());
+        self.trace.read_row_into(next_row_idx, frame.next_mut());
+    }
+
+    fn main_segment(&self) -> &Matrix<BaseElement> {
+        &self.trace
+    }
+
+    /// Builds the running sum column of the lookup argument.
+    ///
+    /// With random elements `alpha` and `gamma`, every step `i` except the last one adds
+    /// 1 / (alpha - e_i) - m_i / (alpha - r_i) to the sum, where `e_i` is the fingerprint of the
+    /// instruction decoded at step `i`, `r_i` is the fingerprint of ROM entry `i mod rom_length`,
+    /// and `m_i` is the value of the multiplicity column. The sum starts and ends at zero if and
+    /// only if (with high probability) every decoded instruction is in the ROM.
+    fn build_aux_segment<E>(
+        &mut self,
+        aux_segments: &[Matrix<E>],
+        rand_elements: &[E],
+    ) -> Option<Matrix<E>>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        // this example has only one auxiliary segment
+        if !aux_segments.is_empty() {
+            return None;
+        }
+
+        let alpha = rand_elements[0];
+        let gamma = rand_elements[1];
+        let rom = build_rom(&self.program);
+        let rom_length = rom[0].len();
+
+        let mut running_sum = vec![E::ZERO; self.length()];
+        for i in 0..self.length() - 1 {
+            let opcode = self.get(MUL_COL, i)
+                + self.get(ADD_COL, i).double()
+                + self.get(JNZ_COL, i) * BaseElement::new(3);
+            let decoded = fingerprint(gamma, self.get(PC_COL, i), opcode, self.get(IMM_COL, i));
+            let j = i % rom_length;
+            let stored = fingerprint(gamma, rom[0][j], rom[1][j], rom[2][j]);
+            let multiplicity = E::from(self.get(MULTIPLICITY_COL, i));
+
+            running_sum[i + 1] =
+                running_sum[i] + (alpha - decoded).inv() - multiplicity * (alpha - stored).inv();
+        }
+
+        Some(Matrix::new(vec![running_sum]))
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns a random linear combination of an instruction address, opcode, and immediate value.
+pub fn fingerprint<E, F>(gamma: E, address: F, opcode: F, immediate: F) -> E
+where
+    F: FieldElement,
+    E: FieldElement + From<F>,
+{
+    E::from(address) + gamma * (E::from(opcode) + gamma * E::from(immediate))
+}
@@ -22,12 +22,15 @@ pub enum VerifierError {
     NumPositionEvaluationMismatch(usize, usize),
     /// Evaluations at queried positions did not match layer commitment made by the prover.
     LayerCommitmentMismatch,
-    /// Degree-respecting projection was not performed correctly at one of the layers.
-    InvalidLayerFolding(usize),
+    /// Degree-respecting projection was not performed correctly at one of the layers. The error
+    /// contains the layer depth and the first query position (in the domain of the layer) at which
+    /// the projection is inconsistent.
+    InvalidLayerFolding(usize, usize),
     /// FRI remainder did not match the commitment.
     RemainderCommitmentMismatch,
-    /// Degree-respecting projection was not performed correctly at the last layer.
-    InvalidRemainderFolding,
+    /// Degree-respecting projection was not performed correctly at the last layer. The error
+    /// contains the first position in the remainder at which the projection is inconsistent.
+    InvalidRemainderFolding(usize),
     /// FRI remainder expected degree is greater than number of remainder values.
     RemainderDegreeNotValid,
     /// FRI remainder degree is greater than the polynomial degree expected for the last layer.
@@ -36,6 +39,35 @@ pub enum VerifierError {
     DegreeTruncation(usize, usize, usize),
 }
 
+impl VerifierError {
+    /// Returns the depth of the FRI layer at which verification failed, if the error is specific
+    /// to a single layer.
+    pub fn layer(&self) -> Option<usize> {
+        match self {
+            Self::InvalidLayerFolding(layer, _) | Self::DegreeTruncation(_, _, layer) => {
+                Some(*layer)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the query position at which verification failed, if the error is specific to a
+    /// single query.
+    ///
+    /// For [InvalidLayerFolding](Self::InvalidLayerFolding), the position is in the evaluation
+    /// domain of the layer returned by [layer()](Self::layer); for
+    /// [InvalidRemainderFolding](Self::InvalidRemainderFolding), the position is in the
+    /// remainder.
+    pub fn query_position(&self) -> Option<usize> {
+        match self {
+            Self::InvalidLayerFolding(_, position) | Self::InvalidRemainderFolding(position) => {
+                Some(*position)
+            }
+            _ => None,
+        }
+    }
+}
+
 impl fmt::Display for VerifierError {
     #[rustfmt::skip]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -52,14 +84,14 @@ impl fmt::Display for VerifierError {
             Self::LayerCommitmentMismatch => {
                 write!(f, "FRI queries did not match layer commitment made by the prover")
             }
-            Self::InvalidLayerFolding(layer) => {
-                write!(f, "degree-respecting projection is not consistent at layer {layer}")
+            Self::InvalidLayerFolding(layer, position) => {
+                write!(f, "degree-respecting projection is not consistent at layer {layer} (query position {position})")
             }
             Self::RemainderCommitmentMismatch => {
                 write!(f, "FRI remainder did not match the commitment")
             }
-            Self::InvalidRemainderFolding => {
-                write!(f, "degree-respecting projection is inconsistent at the last FRI layer")
+            Self::InvalidRemainderFolding(position) => {
+                write!(f, "degree-respecting projection is inconsistent at the last FRI layer (position {position})")
             }
             Self::RemainderDegreeNotValid => {
                 write!(f, "FRI remainder expected degree is greater than number of remainder values")
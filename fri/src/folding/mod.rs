@@ -11,6 +11,7 @@
 #[cfg(feature = "concurrent")]
 use utils::iterators::*;
 
+use crate::{utils::map_positions_to_indexes, FriOptions};
 use math::{
     batch_inversion,
     fft::{get_inv_twiddles, serial_fft},
@@ -175,6 +176,117 @@ pub fn fold_positions(
     result
 }
 
+// QUERY POSITIONS
+// ================================================================================================
+/// Deduplicated sets of positions at which all layers of a FRI proof are queried.
+///
+/// The set for the first layer is the specified set of positions in the evaluation domain; the
+/// set for each subsequent layer is derived from the set of the previous layer via
+/// [fold_positions()], and thus, positions which collide after folding are opened only once. The
+/// prover and the verifier derive the sets in the same way, and thus, the positions do not need
+/// to be recorded in a proof.
+///
+/// ```
+/// # use winter_fri::{folding::QueryPositions, FriOptions};
+/// let options = FriOptions::new(2, 4, 8);
+/// let positions = QueryPositions::new(&[1, 17, 36, 52], 64, &options);
+///
+/// // a domain of size 64 is folded into domains of size 16 and 4; the latter is the remainder
+/// assert_eq!(2, positions.num_layers());
+/// assert_eq!(&[1, 4], positions.layer_positions(0));
+/// assert_eq!(&[1, 0], positions.layer_positions(1));
+/// assert_eq!(4, positions.num_openings());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryPositions {
+    positions: Vec<Vec<usize>>,
+    domain_size: usize,
+    folding_factor: usize,
+}
+
+impl QueryPositions {
+    /// Returns query positions for all layers of a FRI proof generated with the specified
+    /// `options` for a domain of size `domain_size` queried at the specified `positions`.
+    pub fn new(positions: &[usize], domain_size: usize, options: &FriOptions) -> Self {
+        let folding_factor = options.folding_factor();
+        let num_layers = options.num_fri_layers(domain_size);
+
+        let mut result = Vec::with_capacity(num_layers + 1);
+        result.push(positions.to_vec());
+        let mut layer_domain_size = domain_size;
+        for i in 0..num_layers {
+            result.push(fold_positions(
+                &result[i],
+                layer_domain_size,
+                folding_factor,
+            ));
+            layer_domain_size /= folding_factor;
+        }
+
+        QueryPositions {
+            positions: result,
+            domain_size,
+            folding_factor,
+        }
+    }
+
+    /// Returns the number of FRI layers (excluding the remainder) covered by these positions.
+    pub fn num_layers(&self) -> usize {
+        self.positions.len() - 1
+    }
+
+    /// Returns positions in the evaluation domain from which positions of all layers are
+    /// derived.
+    pub fn source_positions(&self) -> &[usize] {
+        &self.positions[0]
+    }
+
+    /// Returns positions at which the specified FRI layer is queried.
+    ///
+    /// Every layer commits to `folding_factor` evaluations per leaf; thus, positions of a layer
+    /// are in the domain reduced by the folding factor as compared to the domain of the layer.
+    /// This is also the set of positions in the domain of the next layer (or the remainder) at
+    /// which the folded evaluations are checked.
+    ///
+    /// # Panics
+    /// Panics if `layer_idx` is greater than or equal to the number of layers.
+    pub fn layer_positions(&self, layer_idx: usize) -> &[usize] {
+        assert!(
+            layer_idx < self.num_layers(),
+            "layer index is out of bounds"
+        );
+        &self.positions[layer_idx + 1]
+    }
+
+    /// Returns indexes of leaves of the commitment Merkle tree for the specified FRI layer at
+    /// which this layer is queried, given that the layer was committed to in `num_partitions`
+    /// partitions.
+    ///
+    /// # Panics
+    /// Panics if `layer_idx` is greater than or equal to the number of layers.
+    pub fn layer_indexes(&self, layer_idx: usize, num_partitions: usize) -> Vec<usize> {
+        let layer_domain_size = self.domain_size / self.folding_factor.pow(layer_idx as u32);
+        map_positions_to_indexes(
+            self.layer_positions(layer_idx),
+            layer_domain_size,
+            self.folding_factor,
+            num_partitions,
+        )
+    }
+
+    /// Returns positions in the remainder domain at which the folded evaluations of the last FRI
+    /// layer are checked.
+    pub fn remainder_positions(&self) -> &[usize] {
+        self.positions.last().expect("no positions")
+    }
+
+    /// Returns the total number of positions at which all FRI layers (excluding the remainder)
+    /// are opened.
+    pub fn num_openings(&self) -> usize {
+        self.positions[1..].iter().map(|p| p.len()).sum()
+    }
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
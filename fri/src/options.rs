@@ -3,6 +3,7 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use crypto::MERKLE_TREE_ARITIES;
 use math::StarkField;
 
 // FRI OPTIONS
@@ -14,6 +15,8 @@ pub struct FriOptions {
     folding_factor: usize,
     max_remainder_size: usize,
     blowup_factor: usize,
+    merkle_arity: usize,
+    domain_offset: u64, // 0 if not specified
 }
 
 impl FriOptions {
@@ -47,17 +50,48 @@ impl FriOptions {
             folding_factor,
             max_remainder_size,
             blowup_factor,
+            merkle_arity: 2,
+            domain_offset: 0,
         }
     }
 
+    /// Returns a new [FriOptions] struct with the arity of FRI layer Merkle trees set to the
+    /// specified value; by default, binary Merkle trees are used.
+    ///
+    /// # Panics
+    /// Panics if `merkle_arity` is not 2, 4, 8, or 16.
+    pub fn with_merkle_arity(mut self, merkle_arity: usize) -> Self {
+        assert!(
+            MERKLE_TREE_ARITIES.contains(&merkle_arity),
+            "Merkle tree arity {merkle_arity} is not supported"
+        );
+        self.merkle_arity = merkle_arity;
+        self
+    }
+
+    /// Returns a new [FriOptions] struct with the evaluation domain offset set to the field
+    /// element with the specified integer value; by default, the primitive element of the field
+    /// is used.
+    ///
+    /// # Panics
+    /// Panics if `domain_offset` is zero.
+    pub fn with_domain_offset(mut self, domain_offset: u64) -> Self {
+        assert_ne!(domain_offset, 0, "domain offset cannot be zero");
+        self.domain_offset = domain_offset;
+        self
+    }
+
     /// Returns the offset by which the evaluation domain is shifted.
     ///
     /// The domain is shifted by multiplying every element in the domain by this offset.
     ///
-    /// Currently, the offset is hard-coded to be the primitive element in the field specified by
-    /// type parameter `B`.
+    /// Unless specified via [with_domain_offset()](FriOptions::with_domain_offset), the offset is
+    /// the primitive element in the field specified by type parameter `B`.
     pub fn domain_offset<B: StarkField>(&self) -> B {
-        B::GENERATOR
+        match self.domain_offset {
+            0 => B::GENERATOR,
+            offset => B::from(offset),
+        }
     }
 
     /// Returns the factor by which the degree of a polynomial is reduced with each FRI layer.
@@ -85,6 +119,11 @@ impl FriOptions {
         self.blowup_factor
     }
 
+    /// Returns the arity of Merkle trees used to commit to FRI layers.
+    pub fn merkle_arity(&self) -> usize {
+        self.merkle_arity
+    }
+
     /// Computes and return the number of FRI layers required for a domain of the specified size.
     ///
     /// The remainder layer (the last FRI layer) is not included in the returned value.
@@ -10,6 +10,9 @@ use utils::{
     DeserializationError, Serializable, SliceReader,
 };
 
+#[cfg(test)]
+mod tests;
+
 // FRI PROOF
 // ================================================================================================
 
@@ -36,6 +39,15 @@ pub struct FriProof {
 }
 
 impl FriProof {
+    // CONSTANTS
+    // --------------------------------------------------------------------------------------------
+
+    /// Largest number of layers a FRI proof can contain.
+    ///
+    /// Every layer reduces the size of the evaluation domain at least by half, and the size of the
+    /// domain fits into a `usize`; thus, no valid proof can have more layers than this.
+    pub const MAX_LAYERS: usize = usize::BITS as usize;
+
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
     /// Creates a new FRI proof from the provided layers and remainder values.
@@ -94,12 +106,25 @@ impl FriProof {
         2usize.pow(self.num_partitions as u32)
     }
 
+    /// Returns the sizes of all layers of this proof in bytes.
+    ///
+    /// The size of a layer includes the size of query values and Merkle authentication paths
+    /// together with their length prefixes.
+    pub fn layer_sizes(&self) -> Vec<usize> {
+        self.layers.iter().map(|layer| layer.size()).collect()
+    }
+
+    /// Returns the size of the remainder (together with its length prefix) in bytes.
+    pub fn remainder_size(&self) -> usize {
+        varint_size(self.remainder.len()) + self.remainder.len()
+    }
+
     /// Returns the size of this proof in bytes.
     pub fn size(&self) -> usize {
-        // +1 for number of layers, +1 for remainder length, +1 for number of partitions
+        // +1 for number of layers, +1 for number of partitions
         self.layers
             .iter()
-            .fold(self.remainder.len() + 3, |acc, layer| acc + layer.size())
+            .fold(self.remainder_size() + 2, |acc, layer| acc + layer.size())
     }
 
     // PARSING
@@ -108,6 +133,8 @@ impl FriProof {
     /// Decomposes this proof into vectors of query values for each layer and corresponding Merkle
     /// authentication paths for each query (grouped into batch Merkle proofs).
     ///
+    /// The `merkle_arity` specifies the arity of Merkle trees used to commit to FRI layers.
+    ///
     /// # Panics
     /// Panics if:
     /// * `domain_size` is not a power of two.
@@ -122,6 +149,7 @@ impl FriProof {
         self,
         mut domain_size: usize,
         folding_factor: usize,
+        merkle_arity: usize,
     ) -> Result<(Vec<Vec<E>>, Vec<BatchMerkleProof<H>>), DeserializationError>
     where
         E: FieldElement,
@@ -144,9 +172,13 @@ impl FriProof {
         // parse all layers
         for (i, layer) in self.layers.into_iter().enumerate() {
             domain_size /= folding_factor;
-            let (qv, mp) = layer.parse(domain_size, folding_factor).map_err(|err| {
-                DeserializationError::InvalidValue(format!("failed to parse FRI layer {i}: {err}"))
-            })?;
+            let (qv, mp) = layer
+                .parse(domain_size, folding_factor, merkle_arity)
+                .map_err(|err| {
+                    DeserializationError::InvalidValue(format!(
+                        "failed to parse FRI layer {i}: {err}"
+                    ))
+                })?;
             layer_proofs.push(mp);
             layer_queries.push(qv);
         }
@@ -194,13 +226,13 @@ impl Serializable for FriProof {
     /// Serializes `self` and writes the resulting bytes into the `target` writer.
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
         // write layers
-        target.write_u8(self.layers.len() as u8);
+        target.write_usize(self.layers.len());
         for layer in self.layers.iter() {
             layer.write_into(target);
         }
 
         // write remainder
-        target.write_u16(self.remainder.len() as u16);
+        target.write_usize(self.remainder.len());
         target.write_u8_slice(&self.remainder);
 
         // write number of partitions
@@ -215,15 +247,20 @@ impl Deserializable for FriProof {
     /// Returns an error if a valid proof could not be read from the source.
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
         // read layers
-        let num_layers = source.read_u8()? as usize;
+        let num_layers = read_num_layers(source)?;
         let layers = FriProofLayer::read_batch_from(source, num_layers)?;
 
         // read remainder
-        let num_remainder_bytes = source.read_u16()? as usize;
+        let num_remainder_bytes = source.read_usize()?;
         let remainder = source.read_u8_vec(num_remainder_bytes)?;
 
         // read number of partitions
         let num_partitions = source.read_u8()?;
+        if num_partitions as u32 >= usize::BITS {
+            return Err(DeserializationError::InvalidValue(format!(
+                "number of partitions cannot be 2^{num_partitions}"
+            )));
+        }
 
         Ok(FriProof {
             layers,
@@ -272,8 +309,10 @@ impl FriProofLayer {
 
     /// Returns the size of this proof layer in bytes.
     pub fn size(&self) -> usize {
-        // +4 for length of values, +4 for length of paths
-        self.values.len() + 4 + self.paths.len() + 4
+        varint_size(self.values.len())
+            + self.values.len()
+            + varint_size(self.paths.len())
+            + self.paths.len()
     }
 
     // PARSING
@@ -281,6 +320,8 @@ impl FriProofLayer {
     /// Decomposes this layer into a combination of query values and corresponding Merkle
     /// authentication paths (grouped together into a single batch Merkle proof).
     ///
+    /// The `merkle_arity` specifies the arity of the Merkle tree used to commit to this layer.
+    ///
     /// # Errors
     /// Returns an error if:
     /// * This layer does not contain at least one query.
@@ -290,6 +331,7 @@ impl FriProofLayer {
         self,
         domain_size: usize,
         folding_factor: usize,
+        merkle_arity: usize,
     ) -> Result<(Vec<E>, BatchMerkleProof<H>), DeserializationError>
     where
         E: FieldElement,
@@ -328,7 +370,12 @@ impl FriProofLayer {
         // build batch Merkle proof
         let mut reader = SliceReader::new(&self.paths);
         let tree_depth = log2(domain_size) as u8;
-        let merkle_proof = BatchMerkleProof::deserialize(&mut reader, hashed_queries, tree_depth)?;
+        let merkle_proof = BatchMerkleProof::deserialize_with_arity(
+            &mut reader,
+            hashed_queries,
+            tree_depth,
+            merkle_arity as u8,
+        )?;
         if reader.has_more_bytes() {
             return Err(DeserializationError::UnconsumedBytes);
         }
@@ -344,11 +391,11 @@ impl Serializable for FriProofLayer {
     /// Serializes this proof layer and writes the resulting bytes to the specified `target`.
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
         // write value bytes
-        target.write_u32(self.values.len() as u32);
+        target.write_usize(self.values.len());
         target.write_u8_slice(&self.values);
 
         // write path bytes
-        target.write_u32(self.paths.len() as u32);
+        target.write_usize(self.paths.len());
         target.write_u8_slice(&self.paths);
     }
 }
@@ -360,18 +407,40 @@ impl Deserializable for FriProofLayer {
     /// Returns an error if a valid layer could not be read from the specified source.
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
         // read values
-        let num_value_bytes = source.read_u32()?;
+        let num_value_bytes = source.read_usize()?;
         if num_value_bytes == 0 {
             return Err(DeserializationError::InvalidValue(
                 "a FRI proof layer must contain at least one queried evaluation".to_string(),
             ));
         }
-        let values = source.read_u8_vec(num_value_bytes as usize)?;
+        let values = source.read_u8_vec(num_value_bytes)?;
 
         // read paths
-        let num_paths_bytes = source.read_u32()?;
-        let paths = source.read_u8_vec(num_paths_bytes as usize)?;
+        let num_paths_bytes = source.read_usize()?;
+        let paths = source.read_u8_vec(num_paths_bytes)?;
 
         Ok(FriProofLayer { values, paths })
     }
 }
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the number of bytes needed to encode the specified value as a variable-length integer.
+fn varint_size(value: usize) -> usize {
+    let num_bits = (usize::BITS - value.leading_zeros()).max(1) as usize;
+    (num_bits + 6) / 7
+}
+
+/// Reads the number of FRI layers from the specified `source` making sure it does not exceed
+/// [FriProof::MAX_LAYERS].
+fn read_num_layers<R: ByteReader>(source: &mut R) -> Result<usize, DeserializationError> {
+    let num_layers = source.read_usize()?;
+    if num_layers > FriProof::MAX_LAYERS {
+        return Err(DeserializationError::InvalidValue(format!(
+            "number of FRI layers cannot exceed {}, but was {num_layers}",
+            FriProof::MAX_LAYERS
+        )));
+    }
+    Ok(num_layers)
+}
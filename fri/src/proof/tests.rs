@@ -0,0 +1,26 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::FriProof;
+use utils::{collections::Vec, ByteWriter, Deserializable, DeserializationError, SliceReader};
+
+// DESERIALIZATION
+// ================================================================================================
+
+#[test]
+fn read_malformed_layer_count() {
+    // a huge number of layers followed by no layer data should result in an error rather than
+    // in an attempt to allocate memory for all layers upfront
+    let mut bytes = Vec::new();
+    bytes.write_usize(usize::MAX);
+    let result = FriProof::read_from(&mut SliceReader::new(&bytes));
+    assert!(result.is_err());
+
+    // the number of layers is checked before any of the layers are read
+    let mut bytes = Vec::new();
+    bytes.write_usize(FriProof::MAX_LAYERS + 1);
+    let result = FriProof::read_from(&mut SliceReader::new(&bytes));
+    assert!(matches!(result, Err(DeserializationError::InvalidValue(_))));
+}
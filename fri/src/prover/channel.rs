@@ -102,6 +102,7 @@ impl<B: StarkField, E: FieldElement<BaseField = B>, H: Hasher> DefaultProverChan
     /// domain. Both number of queried positions and domain size are specified during
     /// construction of the channel.
     pub fn draw_query_positions(&mut self) -> Vec<usize> {
+        self.public_coin.reseed_with_label(b"query-seed");
         self.public_coin
             .draw_integers(self.num_queries, self.domain_size)
             .expect("failed to draw query position")
@@ -127,6 +128,8 @@ where
     }
 
     fn draw_fri_alpha(&mut self) -> E {
+        let label = format!("fri-layer-{}", self.commitments.len() - 1);
+        self.public_coin.reseed_with_label(label.as_bytes());
         self.public_coin.draw().expect("failed to draw FRI alpha")
     }
 }
@@ -4,7 +4,7 @@
 // LICENSE file in the root directory of this source tree.
 
 use crate::{
-    folding::{apply_drp, fold_positions},
+    folding::{apply_drp, QueryPositions},
     proof::{FriProof, FriProofLayer},
     utils::hash_values,
     FriOptions,
@@ -12,7 +12,10 @@ use crate::{
 use core::marker::PhantomData;
 use crypto::{ElementHasher, Hasher, MerkleTree};
 use math::{FieldElement, StarkField};
-use utils::{collections::Vec, flatten_vector_elements, group_slice_elements, transpose_slice};
+use utils::{
+    collections::Vec, flatten_vector_elements, group_slice_elements, transpose_slice, ByteWriter,
+    Serializable,
+};
 
 mod channel;
 pub use channel::{DefaultProverChannel, ProverChannel};
@@ -147,6 +150,28 @@ where
         self.layers.len()
     }
 
+    /// Returns evaluations of the FRI layer at the specified index over the domain of this layer
+    /// in natural order.
+    ///
+    /// # Panics
+    /// Panics if the layer at the specified index has not been built.
+    pub fn layer_evaluations(&self, layer_idx: usize) -> Vec<E> {
+        // layer evaluations are stored transposed into rows of `folding_factor` elements; thus,
+        // the evaluation at position i is located in row i % num_rows
+        let evaluations = &self.layers[layer_idx].evaluations;
+        let folding_factor = self.folding_factor();
+        let num_rows = evaluations.len() / folding_factor;
+        (0..evaluations.len())
+            .map(|i| evaluations[(i % num_rows) * folding_factor + i / num_rows])
+            .collect()
+    }
+
+    /// Returns evaluations of the remainder computed during the last execution of the
+    /// [build_layers()](FriProver::build_layers()) method.
+    pub fn remainder(&self) -> &[E] {
+        &self.remainder.0
+    }
+
     /// Clears a vector of internally stored layers.
     pub fn reset(&mut self) {
         self.layers.clear();
@@ -177,6 +202,11 @@ where
         // reduce the degree by folding_factor at each iteration until the remaining polynomial
         // is small enough
         for _ in 0..self.options.num_fri_layers(evaluations.len()) {
+            let _span = utils::enter_span!(
+                "fri_layer",
+                layer_depth = self.layers.len(),
+                domain_size = evaluations.len()
+            );
             match self.folding_factor() {
                 2 => self.build_layer::<2>(channel, &mut evaluations),
                 4 => self.build_layer::<4>(channel, &mut evaluations),
@@ -186,7 +216,10 @@ where
             }
         }
 
-        self.set_remainder(channel, &mut evaluations);
+        {
+            let _span = utils::enter_span!("fri_remainder", domain_size = evaluations.len());
+            self.set_remainder(channel, &mut evaluations);
+        }
 
         // make sure remainder length does not exceed max allowed value
         let remainder_size = self.remainder.0.len();
@@ -208,7 +241,8 @@ where
         let transposed_evaluations = transpose_slice(evaluations);
         let hashed_evaluations = hash_values::<H, E, N>(&transposed_evaluations);
         let evaluation_tree =
-            MerkleTree::<H>::new(hashed_evaluations).expect("failed to construct FRI layer tree");
+            MerkleTree::<H>::with_arity(hashed_evaluations, self.options.merkle_arity())
+                .expect("failed to construct FRI layer tree");
         channel.commit_fri_layer(*evaluation_tree.root());
 
         // draw a pseudo-random coefficient from the channel, and use it in degree-respecting
@@ -248,30 +282,7 @@ where
         );
 
         let mut layers = Vec::with_capacity(self.layers.len());
-
-        if !self.layers.is_empty() {
-            let mut positions = positions.to_vec();
-            let mut domain_size = self.layers[0].evaluations.len();
-            let folding_factor = self.options.folding_factor();
-
-            // for all FRI layers, except the last one, record tree root, determine a set of query
-            // positions, and query the layer at these positions.
-            for i in 0..self.layers.len() {
-                positions = fold_positions(&positions, domain_size, folding_factor);
-
-                // sort of a static dispatch for folding_factor parameter
-                let proof_layer = match folding_factor {
-                    2 => query_layer::<B, E, H, 2>(&self.layers[i], &positions),
-                    4 => query_layer::<B, E, H, 4>(&self.layers[i], &positions),
-                    8 => query_layer::<B, E, H, 8>(&self.layers[i], &positions),
-                    16 => query_layer::<B, E, H, 16>(&self.layers[i], &positions),
-                    _ => unimplemented!("folding factor {} is not supported", folding_factor),
-                };
-
-                layers.push(proof_layer);
-                domain_size /= folding_factor;
-            }
-        }
+        self.query_layers(positions, |proof_layer| layers.push(proof_layer));
 
         // use the remaining polynomial values directly as proof
         let remainder = self.remainder.0.clone();
@@ -281,6 +292,65 @@ where
 
         FriProof::new(layers, remainder, 1)
     }
+
+    /// Executes query phase of FRI protocol and writes the resulting proof into the `target`.
+    ///
+    /// The bytes written into the `target` are the same as the serialization of the proof
+    /// returned by [build_proof()](FriProver::build_proof) for the same `positions`. However,
+    /// each proof layer is written as soon as it is built and is discarded afterwards, and thus,
+    /// the full proof is never held in memory.
+    ///
+    /// # Panics
+    /// Panics is the prover state is clean (no FRI layers have been build yet).
+    pub fn write_proof<W: ByteWriter>(&mut self, positions: &[usize], target: &mut W) {
+        assert!(
+            !self.remainder.0.is_empty(),
+            "FRI layers have not been built yet"
+        );
+
+        target.write_usize(self.layers.len());
+        self.query_layers(positions, |proof_layer| proof_layer.write_into(target));
+
+        let remainder = self.remainder.0.to_bytes();
+        target.write_usize(remainder.len());
+        target.write_u8_slice(&remainder);
+
+        // proofs generated by this prover always have a single partition (stored as log2)
+        target.write_u8(0);
+
+        // clear layers so that another proof can be generated
+        self.reset();
+    }
+
+    /// Queries all FRI layers (excluding the remainder) at the positions derived from the
+    /// specified `positions`, and passes the resulting proof layers to `on_layer` one by one.
+    fn query_layers<F: FnMut(FriProofLayer)>(&self, positions: &[usize], mut on_layer: F) {
+        if self.layers.is_empty() {
+            return;
+        }
+
+        // determine the set of positions at which each of the FRI layers is queried; positions
+        // which collide after folding are queried only once
+        let domain_size = self.layers[0].evaluations.len();
+        let positions = QueryPositions::new(positions, domain_size, &self.options);
+        debug_assert_eq!(self.layers.len(), positions.num_layers());
+
+        let folding_factor = self.options.folding_factor();
+        for (i, layer) in self.layers.iter().enumerate() {
+            let layer_positions = positions.layer_positions(i);
+
+            // sort of a static dispatch for folding_factor parameter
+            let proof_layer = match folding_factor {
+                2 => query_layer::<B, E, H, 2>(layer, layer_positions),
+                4 => query_layer::<B, E, H, 4>(layer, layer_positions),
+                8 => query_layer::<B, E, H, 8>(layer, layer_positions),
+                16 => query_layer::<B, E, H, 16>(layer, layer_positions),
+                _ => unimplemented!("folding factor {} is not supported", folding_factor),
+            };
+
+            on_layer(proof_layer);
+        }
+    }
 }
 
 // HELPER FUNCTIONS
@@ -5,6 +5,7 @@
 
 use super::{DefaultProverChannel, FriProver};
 use crate::{
+    folding::QueryPositions,
     verifier::{DefaultVerifierChannel, FriVerifier},
     FriOptions, FriProof, VerifierError,
 };
@@ -28,6 +29,7 @@ fn fri_folding_2() {
         lde_blowup_e,
         folding_factor_e,
         max_remainder_size_e,
+        2,
     )
 }
 
@@ -42,6 +44,37 @@ fn fri_folding_4() {
         lde_blowup_e,
         folding_factor_e,
         max_remainder_size_e,
+        2,
+    )
+}
+
+#[test]
+fn fri_folding_4_merkle_arity_4() {
+    let trace_length_e = 12;
+    let lde_blowup_e = 3;
+    let folding_factor_e = 2;
+    let max_remainder_size_e = 8;
+    fri_prove_verify(
+        trace_length_e,
+        lde_blowup_e,
+        folding_factor_e,
+        max_remainder_size_e,
+        4,
+    )
+}
+
+#[test]
+fn fri_folding_8_merkle_arity_8() {
+    let trace_length_e = 12;
+    let lde_blowup_e = 3;
+    let folding_factor_e = 3;
+    let max_remainder_size_e = 6;
+    fri_prove_verify(
+        trace_length_e,
+        lde_blowup_e,
+        folding_factor_e,
+        max_remainder_size_e,
+        8,
     )
 }
 
@@ -90,6 +123,7 @@ pub fn verify_proof(
         commitments,
         domain_size,
         options.folding_factor(),
+        options.merkle_arity(),
     )
     .unwrap();
     let mut coin = RandomCoin::<BaseElement, Blake3>::new(&[]);
@@ -106,13 +140,15 @@ fn fri_prove_verify(
     lde_blowup_e: usize,
     folding_factor_e: usize,
     max_remainder_size_e: usize,
+    merkle_arity: usize,
 ) {
     let trace_length = 1 << trace_length_e;
     let lde_blowup = 1 << lde_blowup_e;
     let folding_factor = 1 << folding_factor_e;
     let max_remainder_size = 1 << max_remainder_size_e;
 
-    let options = FriOptions::new(lde_blowup, folding_factor, max_remainder_size);
+    let options = FriOptions::new(lde_blowup, folding_factor, max_remainder_size)
+        .with_merkle_arity(merkle_arity);
     let mut channel = build_prover_channel(trace_length, &options);
     let evaluations = build_evaluations(trace_length, lde_blowup);
 
@@ -122,6 +158,31 @@ fn fri_prove_verify(
     let positions = channel.draw_query_positions();
     let proof = prover.build_proof(&positions);
 
+    // make sure the same proof is written when it is streamed layer by layer
+    prover.build_layers(
+        &mut build_prover_channel(trace_length, &options),
+        evaluations.clone(),
+    );
+    let mut proof_bytes = Vec::new();
+    prover.write_proof(&positions, &mut proof_bytes);
+    assert_eq!(proof.to_bytes(), proof_bytes);
+
+    // every layer should be opened exactly once at each of its deduplicated query positions
+    let domain_size = trace_length * lde_blowup;
+    let query_positions = QueryPositions::new(&positions, domain_size, &options);
+    let (layer_values, _) = proof
+        .clone()
+        .parse_layers::<Blake3, BaseElement>(domain_size, folding_factor, merkle_arity)
+        .unwrap();
+    assert_eq!(query_positions.num_layers(), layer_values.len());
+    for (i, values) in layer_values.iter().enumerate() {
+        let layer_positions = query_positions.layer_positions(i);
+        assert_eq!(layer_positions.len() * folding_factor, values.len());
+        for (j, position) in layer_positions.iter().enumerate() {
+            assert!(!layer_positions[..j].contains(position));
+        }
+    }
+
     // make sure the proof can be verified
     let commitments = channel.layer_commitments().to_vec();
     let max_degree = trace_length - 1;
@@ -136,6 +197,23 @@ fn fri_prove_verify(
     );
     assert!(result.is_ok(), "{:}", result.err().unwrap());
 
+    // make sure proof fails at the first layer for an inconsistent query evaluation
+    let mut corrupted_evaluations = evaluations.clone();
+    corrupted_evaluations[positions[0]] += BaseElement::ONE;
+    let result = verify_proof(
+        proof.clone(),
+        commitments.clone(),
+        &corrupted_evaluations,
+        max_degree,
+        trace_length * lde_blowup,
+        &positions,
+        &options,
+    );
+    assert_eq!(
+        Err(VerifierError::InvalidLayerFolding(0, positions[0])),
+        result
+    );
+
     // make sure proof fails for invalid degree
     let result = verify_proof(
         proof,
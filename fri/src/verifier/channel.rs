@@ -140,6 +140,8 @@ where
 {
     /// Builds a new verifier channel from the specified [FriProof].
     ///
+    /// The `merkle_arity` specifies the arity of Merkle trees used to commit to FRI layers.
+    ///
     /// # Errors
     /// Returns an error if the specified `proof` could not be parsed correctly.
     pub fn new(
@@ -147,12 +149,13 @@ where
         layer_commitments: Vec<H::Digest>,
         domain_size: usize,
         folding_factor: usize,
+        merkle_arity: usize,
     ) -> Result<Self, DeserializationError> {
         let num_partitions = proof.num_partitions();
 
         let remainder = proof.parse_remainder()?;
         let (layer_queries, layer_proofs) =
-            proof.parse_layers::<H, E>(domain_size, folding_factor)?;
+            proof.parse_layers::<H, E>(domain_size, folding_factor, merkle_arity)?;
 
         Ok(DefaultVerifierChannel {
             layer_commitments,
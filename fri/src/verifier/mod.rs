@@ -5,8 +5,8 @@
 
 //! Contains an implementation of FRI verifier and associated components.
 
-use crate::{folding::fold_positions, utils::map_positions_to_indexes, FriOptions, VerifierError};
-use core::{convert::TryInto, marker::PhantomData, mem};
+use crate::{folding::QueryPositions, FriOptions, VerifierError};
+use core::{convert::TryInto, marker::PhantomData};
 use crypto::{ElementHasher, RandomCoin};
 use math::{fft, log2, polynom, FieldElement, StarkField};
 use utils::collections::Vec;
@@ -116,8 +116,14 @@ where
         let mut max_degree_plus_1 = max_poly_degree + 1;
         for (depth, commitment) in layer_commitments.iter().enumerate() {
             public_coin.reseed(*commitment);
-            let alpha = public_coin.draw().map_err(VerifierError::PublicCoinError)?;
-            layer_alphas.push(alpha);
+
+            // the last commitment is a commitment to the remainder; the prover does not fold the
+            // remainder, and thus, no alpha is drawn for it
+            if depth != layer_commitments.len() - 1 {
+                public_coin.reseed_with_label(format!("fri-layer-{depth}").as_bytes());
+                let alpha = public_coin.draw().map_err(VerifierError::PublicCoinError)?;
+                layer_alphas.push(alpha);
+            }
 
             // make sure the degree can be reduced by the folding factor at all layers
             // but the remainder layer
@@ -174,6 +180,14 @@ where
         &self.options
     }
 
+    /// Returns random values used to fold FRI layers; one value per FRI layer.
+    ///
+    /// These values are drawn from the public coin after each FRI layer commitment is read from
+    /// the channel.
+    pub fn layer_alphas(&self) -> &[E] {
+        &self.layer_alphas
+    }
+
     // VERIFICATION PROCEDURE
     // --------------------------------------------------------------------------------------------
     /// Executes the query phase of the FRI protocol.
@@ -243,28 +257,29 @@ where
         let mut domain_generator = self.domain_generator;
         let mut domain_size = self.domain_size;
         let mut max_degree_plus_1 = self.max_poly_degree + 1;
-        let mut positions = positions.to_vec();
+        let query_positions = QueryPositions::new(positions, self.domain_size, &self.options);
         let mut evaluations = evaluations.to_vec();
 
-        for depth in 0..self.options.num_fri_layers(self.domain_size) {
-            // determine which evaluations were queried in the folded layer
-            let mut folded_positions =
-                fold_positions(&positions, domain_size, self.options.folding_factor());
-            // determine where these evaluations are in the commitment Merkle tree
-            let position_indexes = map_positions_to_indexes(
-                &folded_positions,
-                domain_size,
-                self.options.folding_factor(),
-                self.num_partitions,
-            );
+        for depth in 0..query_positions.num_layers() {
+            let _span = utils::enter_span!("verify_fri_layer", layer_depth = depth, domain_size);
+
+            // determine which evaluations were queried in the folded layer, and where these
+            // evaluations are in the commitment Merkle tree
+            let positions = if depth == 0 {
+                query_positions.source_positions()
+            } else {
+                query_positions.layer_positions(depth - 1)
+            };
+            let folded_positions = query_positions.layer_positions(depth);
+            let position_indexes = query_positions.layer_indexes(depth, self.num_partitions);
             // read query values from the specified indexes in the Merkle tree
             let layer_commitment = self.layer_commitments[depth];
             // TODO: add layer depth to the potential error message
             let layer_values = channel.read_layer_queries(&position_indexes, &layer_commitment)?;
             let query_values =
-                get_query_values::<E, N>(&layer_values, &positions, &folded_positions, domain_size);
-            if evaluations != query_values {
-                return Err(VerifierError::InvalidLayerFolding(depth));
+                get_query_values::<E, N>(&layer_values, positions, folded_positions, domain_size);
+            if let Some(i) = (0..evaluations.len()).find(|&i| evaluations[i] != query_values[i]) {
+                return Err(VerifierError::InvalidLayerFolding(depth, positions[i]));
             }
 
             // build a set of x coordinates for each row polynomial
@@ -300,18 +315,19 @@ where
             domain_generator = domain_generator.exp_vartime((N as u32).into());
             max_degree_plus_1 /= N;
             domain_size /= N;
-            mem::swap(&mut positions, &mut folded_positions);
         }
 
         // 2 ----- verify the remainder of the FRI proof ----------------------------------------------
 
         // read the remainder from the channel and make sure it matches with the columns
         // of the previous layer
+        let _span = utils::enter_span!("verify_fri_remainder", domain_size);
         let remainder_commitment = self.layer_commitments.last().unwrap();
         let remainder = channel.read_remainder::<N>(remainder_commitment)?;
+        let positions = query_positions.remainder_positions();
         for (&position, evaluation) in positions.iter().zip(evaluations) {
             if remainder[position] != evaluation {
-                return Err(VerifierError::InvalidRemainderFolding);
+                return Err(VerifierError::InvalidRemainderFolding(position));
             }
         }
 
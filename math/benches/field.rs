@@ -71,6 +71,12 @@ where
         bench.iter(|| black_box(x) * black_box(y))
     });
 
+    group.bench_function("mul_small", |bench| {
+        let x = rand_value::<B>();
+        let y = rand_value::<u32>();
+        bench.iter(|| black_box(x).mul_small(black_box(y)))
+    });
+
     group.bench_function("exp", |bench| {
         let x = rand_value::<B>();
         let y = rand_value::<B>().as_int();
@@ -47,6 +47,32 @@ pub fn evaluate_poly_with_offset<B: StarkField, E: FieldElement<BaseField = B>>(
     result
 }
 
+/// Evaluates polynomial `p` using FFT algorithm and returns the result. The polynomial is
+/// evaluated over domain specified by `twiddles`, expanded and shifted according to the
+/// precomputed `offset_powers`.
+pub fn evaluate_poly_with_offset_powers<B: StarkField, E: FieldElement<BaseField = B>>(
+    p: &[E],
+    twiddles: &[B],
+    offset_powers: &[B],
+) -> Vec<E> {
+    let mut result = unsafe { uninit_vector(offset_powers.len()) };
+
+    result
+        .as_mut_slice()
+        .par_chunks_mut(p.len())
+        .zip(offset_powers.par_chunks(p.len()))
+        .for_each(|(chunk, powers)| {
+            chunk
+                .par_iter_mut()
+                .zip(p.par_iter().zip(powers.par_iter()))
+                .for_each(|(d, (c, &factor))| *d = (*c).mul_base(factor));
+            split_radix_fft(chunk, twiddles);
+        });
+
+    permute(&mut result);
+    result
+}
+
 // POLYNOMIAL INTERPOLATION
 // ================================================================================================
 
@@ -75,7 +101,7 @@ where
 
     let domain_offset = E::inv(domain_offset.into());
     let inv_len = E::inv((values.len() as u64).into());
-    let batch_size = values.len() / rayon::current_num_threads().next_power_of_two();
+    let batch_size = get_batch_size(values.len());
 
     values
         .par_chunks_mut(batch_size)
@@ -89,13 +115,32 @@ where
         });
 }
 
+/// Uses FFT algorithm to interpolate a polynomial from provided `values` over the domain defined
+/// by `inv_twiddles` and shifted according to the precomputed `inv_offset_powers`.
+pub fn interpolate_poly_with_offset_powers<B, E>(
+    values: &mut [E],
+    inv_twiddles: &[B],
+    inv_offset_powers: &[B],
+) where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+{
+    split_radix_fft(values, inv_twiddles);
+    permute(values);
+
+    values
+        .par_iter_mut()
+        .zip(inv_offset_powers.par_iter())
+        .for_each(|(coeff, &factor)| *coeff = (*coeff).mul_base(factor));
+}
+
 // PERMUTATIONS
 // ================================================================================================
 
 pub fn permute<E: FieldElement>(v: &mut [E]) {
     let n = v.len();
-    let num_batches = rayon::current_num_threads().next_power_of_two();
-    let batch_size = n / num_batches;
+    let batch_size = get_batch_size(n);
+    let num_batches = n / batch_size;
     rayon::scope(|s| {
         for batch_idx in 0..num_batches {
             // create another mutable reference to the slice of values to use in a new thread; this
@@ -124,8 +169,15 @@ pub(super) fn split_radix_fft<B: StarkField, E: FieldElement<BaseField = B>>(
     values: &mut [E],
     twiddles: &[B],
 ) {
-    // generator of the domain should be in the middle of twiddles
+    // the decomposition below requires a matrix with at least two rows and two columns; smaller
+    // inputs are transformed directly
     let n = values.len();
+    if n < 4 {
+        super::fft_inputs::fft_in_place(values, twiddles, 1, 1, 0);
+        return;
+    }
+
+    // generator of the domain should be in the middle of twiddles
     let g = twiddles[twiddles.len() / 2];
     debug_assert_eq!(g.exp((n as u32).into()), E::BaseField::ONE);
 
@@ -214,8 +266,15 @@ fn transpose_square_2<T>(matrix: &mut [T], size: usize) {
 // HELPER FUNCTIONS
 // ================================================================================================
 
+/// Returns the number of elements processed by a single thread when `n` elements are split
+/// between all available threads; the result is at least one, even if there are fewer elements
+/// than threads.
+fn get_batch_size(n: usize) -> usize {
+    (n / rayon::current_num_threads().next_power_of_two()).max(1)
+}
+
 fn clone_and_shift<E: FieldElement>(source: &[E], destination: &mut [E], offset: E::BaseField) {
-    let batch_size = source.len() / rayon::current_num_threads().next_power_of_two();
+    let batch_size = get_batch_size(source.len());
     source
         .par_chunks(batch_size)
         .zip(destination.par_chunks_mut(batch_size))
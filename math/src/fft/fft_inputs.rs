@@ -4,6 +4,7 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{permute_index, FieldElement, StarkField};
+use core::ops::Range;
 
 // CONSTANTS
 // ================================================================================================
@@ -72,6 +73,18 @@ pub trait FftInputs<B: StarkField> {
     fn fft_in_place(&mut self, twiddles: &[B]) {
         fft_in_place(self, twiddles, 1, 1, 0);
     }
+
+    /// Applies the FFT to this input assuming that all elements outside of the `nonzero` range
+    /// are ZEROs.
+    ///
+    /// The result is the same as for [FftInputs::fft_in_place()], but sub-transforms which
+    /// operate only on ZERO elements are skipped.
+    ///
+    /// # Panics
+    /// Panics if length of the `twiddles` parameter is not self.len() / 2.
+    fn sparse_fft_in_place(&mut self, twiddles: &[B], nonzero: Range<usize>) {
+        sparse_fft_in_place(self, twiddles, 1, 0, &nonzero);
+    }
 }
 
 /// Implements FftInputs for a slice of field elements.
@@ -172,3 +185,62 @@ pub(super) fn fft_in_place<B, I>(
         }
     }
 }
+
+/// In-place recursive FFT with permuted output for inputs which are ZERO outside of the
+/// `nonzero` range.
+///
+/// Every sub-transform of the recursive FFT operates on elements at positions
+/// `offset + k * stride`. If none of these positions fall into the `nonzero` range, the output
+/// of the sub-transform is all ZEROs and the sub-transform can be skipped; if all of them fall
+/// into the range, the sub-transform is delegated to the regular FFT algorithm.
+pub(super) fn sparse_fft_in_place<B, I>(
+    values: &mut I,
+    twiddles: &[B],
+    stride: usize,
+    offset: usize,
+    nonzero: &Range<usize>,
+) where
+    B: StarkField,
+    I: FftInputs<B> + ?Sized,
+{
+    let size = values.len() / stride;
+    debug_assert!(size.is_power_of_two());
+    debug_assert!(offset < stride);
+
+    // determine the first and the last positions of this sub-transform
+    let first = offset;
+    let last = offset + (size - 1) * stride;
+    if nonzero.start <= first && last < nonzero.end {
+        fft_in_place(values, twiddles, 1, stride, offset);
+        return;
+    }
+
+    // find the first position at or after the start of the range and check if it is still
+    // within the range; if not, all inputs to this sub-transform are ZEROs
+    let first_nonzero = if nonzero.start <= first {
+        first
+    } else {
+        first + (nonzero.start - first + stride - 1) / stride * stride
+    };
+    if first_nonzero > last || first_nonzero >= nonzero.end {
+        return;
+    }
+
+    if size > 2 {
+        sparse_fft_in_place(values, twiddles, 2 * stride, offset, nonzero);
+        sparse_fft_in_place(values, twiddles, 2 * stride, offset + stride, nonzero);
+    }
+
+    // Apply butterfly operations.
+    I::butterfly(values, offset, stride);
+
+    // Apply butterfly operations with twiddle factors.
+    let last_offset = offset + size * stride;
+    for (i, offset) in (offset..last_offset)
+        .step_by(2 * stride)
+        .enumerate()
+        .skip(1)
+    {
+        I::butterfly_twiddle(values, twiddles[i], offset, stride);
+    }
+}
@@ -14,7 +14,7 @@
 use crate::{
     fft::fft_inputs::FftInputs,
     field::{FieldElement, StarkField},
-    utils::{get_power_series, log2},
+    utils::{fill_power_series, get_power_series, get_power_series_with_offset, log2},
 };
 
 mod fft_inputs;
@@ -26,7 +26,11 @@ mod serial;
 #[cfg(feature = "concurrent")]
 mod concurrent;
 
-use utils::collections::Vec;
+use core::ops::Range;
+use utils::{batch_iter_mut, collections::Vec, uninit_vector, ConcurrencyConfig};
+
+#[cfg(feature = "concurrent")]
+use utils::iterators::*;
 
 #[cfg(test)]
 mod tests;
@@ -34,7 +38,6 @@ mod tests;
 // CONSTANTS
 // ================================================================================================
 const USIZE_BITS: usize = 0_usize.count_zeros() as usize;
-const MIN_CONCURRENT_SIZE: usize = 1024;
 
 // POLYNOMIAL EVALUATION
 // ================================================================================================
@@ -109,7 +112,7 @@ where
 
     // when `concurrent` feature is enabled, run the concurrent version of the function; unless
     // the polynomial is small, then don't bother with the concurrent version
-    if cfg!(feature = "concurrent") && p.len() >= MIN_CONCURRENT_SIZE {
+    if cfg!(feature = "concurrent") && p.len() >= ConcurrencyConfig::global().min_fft_size() {
         #[cfg(feature = "concurrent")]
         concurrent::evaluate_poly(p, twiddles);
     } else {
@@ -209,7 +212,7 @@ where
 
     // when `concurrent` feature is enabled, run the concurrent version of the function; unless
     // the polynomial is small, then don't bother with the concurrent version
-    if cfg!(feature = "concurrent") && p.len() >= MIN_CONCURRENT_SIZE {
+    if cfg!(feature = "concurrent") && p.len() >= ConcurrencyConfig::global().min_fft_size() {
         #[cfg(feature = "concurrent")]
         {
             result =
@@ -222,6 +225,186 @@ where
     result
 }
 
+/// Evaluates a polynomial on all points of a shifted domain using the FFT algorithm and a set of
+/// precomputed offset powers.
+///
+/// This function is equivalent to [evaluate_poly_with_offset()], but instead of computing the
+/// powers of the domain offset for every call, it uses `offset_powers` computed via
+/// [get_offset_powers()]. This is useful when many polynomials of the same size need to be
+/// evaluated over the same shifted domain (e.g., when extending columns of an execution trace).
+///
+/// The blowup factor is implied by the length of `offset_powers`, which must be equal to the
+/// size of the evaluation domain (i.e., `p.len()` * `blowup_factor`).
+///
+/// When `concurrent` feature is enabled, the evaluation is done in multiple threads.
+///
+/// # Panics
+/// Panics if:
+/// * Length of `p` is not a power of two.
+/// * Length of `offset_powers` is not a multiple of `p.len()` which is a power of two.
+/// * Length of `twiddles` is not `p.len()` / 2.
+/// * Field specified by `B` does not contain a multiplicative subgroup of size
+///   `offset_powers.len()`.
+///
+/// # Examples
+/// ```
+/// # use winter_math::{fft::*};
+/// # use winter_math::{fields::{f128::BaseElement}, FieldElement, StarkField};
+/// # use rand_utils::rand_vector;
+/// let n = 2048;
+/// let offset = BaseElement::GENERATOR;
+/// let blowup_factor = 2;
+///
+/// // build a random polynomial
+/// let p: Vec<BaseElement> = rand_vector(n / blowup_factor);
+///
+/// // evaluate the polynomial over the shifted domain without precomputed offset powers
+/// let twiddles = get_twiddles::<BaseElement>(p.len());
+/// let expected = evaluate_poly_with_offset(&p, &twiddles, offset, blowup_factor);
+///
+/// // evaluate the polynomial over the shifted domain using precomputed offset powers
+/// let offset_powers = get_offset_powers(p.len(), blowup_factor, offset);
+/// let actual = evaluate_poly_with_offset_powers(&p, &twiddles, &offset_powers);
+///
+/// assert_eq!(expected, actual);
+/// ```
+pub fn evaluate_poly_with_offset_powers<B, E>(
+    p: &[E],
+    twiddles: &[B],
+    offset_powers: &[B],
+) -> Vec<E>
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+{
+    assert!(
+        p.len().is_power_of_two(),
+        "number of coefficients must be a power of 2"
+    );
+    assert!(
+        offset_powers.len().is_power_of_two() && offset_powers.len() >= p.len(),
+        "invalid number of offset powers: expected a power of 2 which is at least {}, but was {}",
+        p.len(),
+        offset_powers.len()
+    );
+    assert_eq!(
+        p.len(),
+        twiddles.len() * 2,
+        "invalid number of twiddles: expected {} but received {}",
+        p.len() / 2,
+        twiddles.len()
+    );
+    assert!(
+        log2(offset_powers.len()) <= B::TWO_ADICITY,
+        "multiplicative subgroup of size {} does not exist in the specified base field",
+        offset_powers.len()
+    );
+
+    // assign a dummy value here to make the compiler happy
+    #[allow(unused_assignments)]
+    let mut result = Vec::new();
+
+    // when `concurrent` feature is enabled, run the concurrent version of the function; unless
+    // the polynomial is small, then don't bother with the concurrent version
+    if cfg!(feature = "concurrent") && p.len() >= ConcurrencyConfig::global().min_fft_size() {
+        #[cfg(feature = "concurrent")]
+        {
+            result = concurrent::evaluate_poly_with_offset_powers(p, twiddles, offset_powers);
+        }
+    } else {
+        result = serial::evaluate_poly_with_offset_powers(p, twiddles, offset_powers);
+    }
+
+    result
+}
+
+/// Evaluates a polynomial at the specified positions of a shifted domain.
+///
+/// The domain is a multiplicative subgroup of size `domain_size` shifted by `domain_offset`, and
+/// the evaluation at position `i` is the evaluation of the polynomial at `domain_offset * g^i`,
+/// where `g` is the generator of the subgroup. Thus, the result is the same as selecting values
+/// at the specified positions from the result of [evaluate_poly_with_offset()] with blowup factor
+/// `domain_size / p.len()`, but the polynomial is not evaluated over the entire domain.
+///
+/// This is useful when a polynomial needs to be evaluated only at a few points of a large domain
+/// (e.g., at positions of the low-degree extension domain queried by the verifier). Positions are
+/// grouped by the cosets of the subgroup of size `p.len()` into which they fall; positions in a
+/// coset with many queried positions are evaluated using a single FFT over the coset, and the
+/// remaining positions are evaluated one by one. Thus, at most `domain_size / p.len()` FFTs of
+/// size `p.len()` are performed, and the memory needed does not depend on `domain_size`.
+///
+/// Positions do not need to be sorted or distinct; values in the returned vector are in the same
+/// order as `positions`.
+///
+/// This function is always executed in a single thread.
+///
+/// # Panics
+/// Panics if:
+/// * Length of `p` is not a power of two.
+/// * `domain_size` is not a power of two or is smaller than `p.len()`.
+/// * Field specified by `B` does not contain a multiplicative subgroup of size `domain_size`.
+/// * `domain_offset` is ZERO.
+/// * Any of the positions is greater than or equal to `domain_size`.
+///
+/// # Examples
+/// ```
+/// # use winter_math::{fft::*};
+/// # use winter_math::{fields::{f128::BaseElement}, FieldElement, StarkField};
+/// # use rand_utils::rand_vector;
+/// let n = 2048;
+/// let offset = BaseElement::GENERATOR;
+/// let blowup_factor = 8;
+///
+/// // build a random polynomial
+/// let p: Vec<BaseElement> = rand_vector(n);
+///
+/// // evaluate the polynomial over the entire domain and select values at a few positions
+/// let positions = [3, 17, 1000, 16383, 17];
+/// let twiddles = get_twiddles::<BaseElement>(p.len());
+/// let evaluations = evaluate_poly_with_offset(&p, &twiddles, offset, blowup_factor);
+/// let expected = positions.iter().map(|&i| evaluations[i]).collect::<Vec<_>>();
+///
+/// // evaluate the polynomial at the selected positions only
+/// let actual = evaluate_poly_at_positions(&p, n * blowup_factor, offset, &positions);
+///
+/// assert_eq!(expected, actual);
+/// ```
+pub fn evaluate_poly_at_positions<B, E>(
+    p: &[E],
+    domain_size: usize,
+    domain_offset: B,
+    positions: &[usize],
+) -> Vec<E>
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+{
+    assert!(
+        p.len().is_power_of_two(),
+        "number of coefficients must be a power of 2"
+    );
+    assert!(
+        domain_size.is_power_of_two() && domain_size >= p.len(),
+        "domain size must be a power of 2 which is at least {}, but was {}",
+        p.len(),
+        domain_size
+    );
+    assert!(
+        log2(domain_size) <= B::TWO_ADICITY,
+        "multiplicative subgroup of size {} does not exist in the specified base field",
+        domain_size
+    );
+    assert_ne!(domain_offset, B::ZERO, "domain offset cannot be zero");
+    if let Some(&position) = positions.iter().find(|&&position| position >= domain_size) {
+        panic!(
+            "position {} is out of bounds for domain of size {}",
+            position, domain_size
+        );
+    }
+
+    serial::evaluate_poly_at_positions(p, domain_size, domain_offset, positions)
+}
+
 // POLYNOMIAL INTERPOLATION
 // ================================================================================================
 
@@ -299,7 +482,9 @@ where
 
     // when `concurrent` feature is enabled, run the concurrent version of interpolate_poly;
     // unless the number of evaluations is small, then don't bother with the concurrent version
-    if cfg!(feature = "concurrent") && evaluations.len() >= MIN_CONCURRENT_SIZE {
+    if cfg!(feature = "concurrent")
+        && evaluations.len() >= ConcurrencyConfig::global().min_fft_size()
+    {
         #[cfg(feature = "concurrent")]
         concurrent::interpolate_poly(evaluations, inv_twiddles);
     } else {
@@ -391,7 +576,9 @@ pub fn interpolate_poly_with_offset<B, E>(
 
     // when `concurrent` feature is enabled, run the concurrent version of the function; unless
     // the polynomial is small, then don't bother with the concurrent version
-    if cfg!(feature = "concurrent") && evaluations.len() >= MIN_CONCURRENT_SIZE {
+    if cfg!(feature = "concurrent")
+        && evaluations.len() >= ConcurrencyConfig::global().min_fft_size()
+    {
         #[cfg(feature = "concurrent")]
         concurrent::interpolate_poly_with_offset(evaluations, inv_twiddles, domain_offset);
     } else {
@@ -399,6 +586,178 @@ pub fn interpolate_poly_with_offset<B, E>(
     }
 }
 
+/// Interpolates evaluations of a polynomial over a shifted domain into a polynomial in
+/// coefficient form using the FFT algorithm and a set of precomputed inverse offset powers.
+///
+/// This function is equivalent to [interpolate_poly_with_offset()], but instead of computing
+/// the powers of the inverse domain offset for every call, it uses `inv_offset_powers` computed
+/// via [get_inv_offset_powers()]. This is useful when many sets of evaluations need to be
+/// interpolated over the same shifted domain.
+///
+/// When `concurrent` feature is enabled, the interpolation is done in multiple threads.
+///
+/// # Panics
+/// Panics if:
+/// * Length of `evaluations` is not a power of two.
+/// * Length of `inv_twiddles` is not `evaluations.len()` / 2.
+/// * Length of `inv_offset_powers` is not `evaluations.len()`.
+/// * Field specified by `B` does not contain a multiplicative subgroup of size
+///   `evaluations.len()`.
+///
+/// # Examples
+/// ```
+/// # use winter_math::{polynom, fft::*, get_power_series, log2};
+/// # use winter_math::{fields::{f128::BaseElement}, FieldElement, StarkField};
+/// # use rand_utils::rand_vector;
+/// let n = 2048;
+/// let offset = BaseElement::GENERATOR;
+///
+/// // build a random polynomial
+/// let p: Vec<BaseElement> = rand_vector(n);
+///
+/// // evaluate the polynomial over the shifted domain
+/// let twiddles = get_twiddles::<BaseElement>(n);
+/// let mut ys = evaluate_poly_with_offset(&p, &twiddles, offset, 1);
+///
+/// // interpolate the evaluations into a polynomial
+/// let inv_twiddles = get_inv_twiddles::<BaseElement>(n);
+/// let inv_offset_powers = get_inv_offset_powers(n, offset);
+/// interpolate_poly_with_offset_powers(&mut ys, &inv_twiddles, &inv_offset_powers);
+///
+/// assert_eq!(p, ys);
+/// ```
+pub fn interpolate_poly_with_offset_powers<B, E>(
+    evaluations: &mut [E],
+    inv_twiddles: &[B],
+    inv_offset_powers: &[B],
+) where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+{
+    assert!(
+        evaluations.len().is_power_of_two(),
+        "number of evaluations must be a power of 2, but was {}",
+        evaluations.len()
+    );
+    assert_eq!(
+        evaluations.len(),
+        inv_twiddles.len() * 2,
+        "invalid number of twiddles: expected {} but received {}",
+        evaluations.len() / 2,
+        inv_twiddles.len()
+    );
+    assert_eq!(
+        evaluations.len(),
+        inv_offset_powers.len(),
+        "invalid number of offset powers: expected {} but received {}",
+        evaluations.len(),
+        inv_offset_powers.len()
+    );
+    assert!(
+        log2(evaluations.len()) <= B::TWO_ADICITY,
+        "multiplicative subgroup of size {} does not exist in the specified base field",
+        evaluations.len()
+    );
+
+    // when `concurrent` feature is enabled, run the concurrent version of the function; unless
+    // the polynomial is small, then don't bother with the concurrent version
+    if cfg!(feature = "concurrent")
+        && evaluations.len() >= ConcurrencyConfig::global().min_fft_size()
+    {
+        #[cfg(feature = "concurrent")]
+        concurrent::interpolate_poly_with_offset_powers(
+            evaluations,
+            inv_twiddles,
+            inv_offset_powers,
+        );
+    } else {
+        serial::interpolate_poly_with_offset_powers(evaluations, inv_twiddles, inv_offset_powers);
+    }
+}
+
+/// Interpolates evaluations of a polynomial over the specified (shifted) domain into a polynomial
+/// in coefficient form using the FFT algorithm, assuming that most of the evaluations are ZEROs.
+///
+/// `evaluations` must contain the evaluations of the polynomial over the domain
+/// `domain_offset * <g>` in natural order, where `<g>` is the multiplicative subgroup of size
+/// `evaluations.len()`, and every entry at a position outside of the `nonzero` range must be
+/// ZERO. Under this precondition the result is identical to [interpolate_poly_with_offset()];
+/// if an entry outside of the range is not ZERO, the result is undefined.
+///
+/// Butterflies which operate only on positions outside of the `nonzero` range are skipped, which
+/// makes this function beneficial for columns which are ZERO on most of the domain (e.g.,
+/// boundary constraint columns).
+///
+/// This function is always executed in a single thread.
+///
+/// # Panics
+/// Panics if:
+/// * Length of `evaluations` is not a power of two.
+/// * Length of `inv_twiddles` is not `evaluations.len()` / 2.
+/// * Field specified by `B` does not contain a multiplicative subgroup of size
+///   `evaluations.len()`.
+/// * `domain_offset` is ZERO.
+/// * The `nonzero` range extends beyond `evaluations.len()`.
+///
+/// # Examples
+/// ```
+/// # use winter_math::{fft::*};
+/// # use winter_math::{fields::{f128::BaseElement}, FieldElement, StarkField};
+/// # use rand_utils::rand_vector;
+/// let n = 2048;
+/// let offset = BaseElement::GENERATOR;
+///
+/// // build a vector of evaluations with only a few non-zero values
+/// let mut ys = vec![BaseElement::ZERO; n];
+/// ys[100..120].copy_from_slice(&rand_vector::<BaseElement>(20));
+///
+/// // interpolate the evaluations using regular FFT-based interpolation
+/// let inv_twiddles = get_inv_twiddles::<BaseElement>(n);
+/// let mut expected = ys.clone();
+/// interpolate_poly_with_offset(&mut expected, &inv_twiddles, offset);
+///
+/// // interpolate the evaluations using sparse FFT-based interpolation
+/// interpolate_poly_sparse(&mut ys, &inv_twiddles, offset, 100..120);
+///
+/// assert_eq!(expected, ys);
+/// ```
+pub fn interpolate_poly_sparse<B, E>(
+    evaluations: &mut [E],
+    inv_twiddles: &[B],
+    domain_offset: B,
+    nonzero: Range<usize>,
+) where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+{
+    assert!(
+        evaluations.len().is_power_of_two(),
+        "number of evaluations must be a power of 2, but was {}",
+        evaluations.len()
+    );
+    assert_eq!(
+        evaluations.len(),
+        inv_twiddles.len() * 2,
+        "invalid number of twiddles: expected {} but received {}",
+        evaluations.len() / 2,
+        inv_twiddles.len()
+    );
+    assert!(
+        log2(evaluations.len()) <= B::TWO_ADICITY,
+        "multiplicative subgroup of size {} does not exist in the specified base field",
+        evaluations.len()
+    );
+    assert_ne!(domain_offset, B::ZERO, "domain offset cannot be zero");
+    assert!(
+        nonzero.end <= evaluations.len(),
+        "non-zero range {:?} is out of bounds for {} evaluations",
+        nonzero,
+        evaluations.len()
+    );
+
+    serial::interpolate_poly_sparse(evaluations, inv_twiddles, domain_offset, nonzero);
+}
+
 // RAW FFT ALGORITHM
 // ================================================================================================
 
@@ -524,6 +883,113 @@ where
     inv_twiddles
 }
 
+// OFFSET POWERS
+// ================================================================================================
+
+/// Returns a set of offset powers for evaluating polynomials of the specified size over a
+/// shifted domain.
+///
+/// These powers can then be used for FFT-based polynomial evaluation via
+/// [evaluate_poly_with_offset_powers()]. The length of the returned vector will be equal to
+/// `poly_size` * `blowup_factor`.
+///
+/// When `concurrent` feature is enabled, the powers are generated in multiple threads.
+///
+/// # Panics
+/// Panics if:
+/// * `poly_size` or `blowup_factor` is not a power of two.
+/// * Field specified by `B` does not contain a multiplicative subgroup of size
+///   `poly_size` * `blowup_factor`.
+/// * `domain_offset` is ZERO.
+///
+/// # Examples
+/// ```
+/// # use winter_math::fft::*;
+/// # use winter_math::{fields::{f128::BaseElement}, FieldElement, StarkField};
+/// let offset_powers = get_offset_powers(1024, 4, BaseElement::GENERATOR);
+///
+/// assert_eq!(4096, offset_powers.len());
+/// ```
+pub fn get_offset_powers<B>(poly_size: usize, blowup_factor: usize, domain_offset: B) -> Vec<B>
+where
+    B: StarkField,
+{
+    assert!(
+        poly_size.is_power_of_two(),
+        "polynomial size must be a power of 2"
+    );
+    assert!(
+        blowup_factor.is_power_of_two(),
+        "blowup factor must be a power of 2"
+    );
+    assert!(
+        log2(poly_size * blowup_factor) <= B::TWO_ADICITY,
+        "multiplicative subgroup of size {} does not exist in the specified base field",
+        poly_size * blowup_factor
+    );
+    assert_ne!(domain_offset, B::ZERO, "domain offset cannot be zero");
+
+    // the polynomial is evaluated over the domain in blowup_factor chunks, each of which is a
+    // coset of the subgroup of size poly_size; for every chunk we need powers of the coset offset
+    let g = B::get_root_of_unity(log2(poly_size * blowup_factor));
+    let mut result = unsafe { uninit_vector(poly_size * blowup_factor) };
+    batch_iter_mut!(&mut result, 1024, |batch: &mut [B], batch_offset: usize| {
+        // both batch size and polynomial size are powers of two; thus, a batch either lies
+        // within a single chunk or consists of several complete chunks
+        let part_size = poly_size.min(batch.len());
+        for (i, part) in batch.chunks_mut(part_size).enumerate() {
+            let position = batch_offset + i * part_size;
+            let idx = permute_index(blowup_factor, position / poly_size) as u64;
+            let offset = g.exp(idx.into()) * domain_offset;
+            let start = offset.exp(((position % poly_size) as u64).into());
+            fill_power_series(part, offset, start);
+        }
+    });
+    result
+}
+
+/// Returns a set of inverse offset powers for interpolating polynomials from evaluations over
+/// a shifted domain of the specified size.
+///
+/// These powers can then be used for FFT-based polynomial interpolation via
+/// [interpolate_poly_with_offset_powers()]. The length of the returned vector will be equal to
+/// `domain_size`. The powers also include normalization by the domain size.
+///
+/// When `concurrent` feature is enabled, the powers are generated in multiple threads.
+///
+/// # Panics
+/// Panics if:
+/// * `domain_size` is not a power of two.
+/// * Field specified by `B` does not contain a multiplicative subgroup of size `domain_size`.
+/// * `domain_offset` is ZERO.
+///
+/// # Examples
+/// ```
+/// # use winter_math::fft::*;
+/// # use winter_math::{fields::{f128::BaseElement}, FieldElement, StarkField};
+/// let inv_offset_powers = get_inv_offset_powers(2048, BaseElement::GENERATOR);
+///
+/// assert_eq!(2048, inv_offset_powers.len());
+/// ```
+pub fn get_inv_offset_powers<B>(domain_size: usize, domain_offset: B) -> Vec<B>
+where
+    B: StarkField,
+{
+    assert!(
+        domain_size.is_power_of_two(),
+        "domain size must be a power of 2"
+    );
+    assert!(
+        log2(domain_size) <= B::TWO_ADICITY,
+        "multiplicative subgroup of size {domain_size} does not exist in the specified base field"
+    );
+    assert_ne!(domain_offset, B::ZERO, "domain offset cannot be zero");
+
+    let inv_offset = domain_offset.inv();
+    let inv_length = B::inv((domain_size as u64).into());
+    get_power_series_with_offset(inv_offset, inv_length, domain_size)
+}
+
 // DEGREE INFERENCE
 // ================================================================================================
 
@@ -586,11 +1052,165 @@ where
     super::polynom::degree_of(&poly)
 }
 
+/// A probabilistic check of polynomial degree which does not require interpolation.
+///
+/// The check determines whether evaluations of a polynomial over a multiplicative subgroup of a
+/// finite field (possibly shifted by some domain offset) describe a polynomial of exactly the
+/// specified degree `d`. This is done without interpolating the evaluations as follows:
+/// * Coefficients of the polynomial are linear functions of its evaluations. Thus, a random
+///   linear combination of coefficients `d + 1`, ..., `n - 1` (where `n` is the domain size)
+///   with coefficients `r^(d + 1)`, ..., `r^(n - 1)` can be computed as an inner product of
+///   evaluations with a vector of weights which depend only on the domain, `d`, and `r`. If the
+///   inner product is ZERO, then with high probability all these coefficients are ZEROs.
+/// * Similarly, coefficient `d` is computed as an inner product of evaluations with another
+///   vector of weights, and is checked to be non-zero.
+///
+/// The weights are computed once (with cost similar to computing twiddles for an FFT), and can
+/// then be used to check any number of polynomials in O(`n`) time per polynomial, as compared to
+/// O(`n` log(`n`)) time required to interpolate a polynomial using the FFT algorithm.
+///
+/// The probability that a polynomial of degree greater than `d` passes the check is at most
+/// `n` / |F|, where |F| is the size of the base field, as long as `r` is chosen uniformly at
+/// random and independently of the evaluations.
+///
+/// # Examples
+/// ```
+/// # use winter_math::{polynom, fft::*, get_power_series_with_offset, log2};
+/// # use winter_math::{fields::{f128::BaseElement}, FieldElement, StarkField};
+/// # use rand_utils::{rand_value, rand_vector};
+/// let n = 1024;
+/// let offset = BaseElement::GENERATOR;
+///
+/// // evaluate a random polynomial of degree 99 over a shifted domain
+/// let p: Vec<BaseElement> = rand_vector(100);
+/// let g = BaseElement::get_root_of_unity(log2(n));
+/// let domain = get_power_series_with_offset(g, offset, n);
+/// let evaluations = polynom::eval_many(&p, &domain);
+///
+/// let check = DegreeCheck::new(n, offset, 99, rand_value());
+/// assert!(check.is_satisfied_by(&evaluations));
+///
+/// let check = DegreeCheck::new(n, offset, 98, rand_value());
+/// assert!(!check.is_satisfied_by(&evaluations));
+/// ```
+pub struct DegreeCheck<B: StarkField> {
+    degree: usize,
+    high_coeff_weights: Vec<B>,
+    degree_coeff_weights: Vec<B>,
+}
+
+impl<B: StarkField> DegreeCheck<B> {
+    /// Returns a new degree check for polynomials of the specified `degree` evaluated over a
+    /// domain of size `domain_size` shifted by `domain_offset`.
+    ///
+    /// The random value `r` is used to combine coefficients of a polynomial which are expected
+    /// to be ZEROs; it must be chosen independently of the polynomials being checked.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `domain_size` is not a power of two.
+    /// * Field specified by `B` does not contain a multiplicative subgroup of size
+    ///   `domain_size`.
+    /// * `degree` is greater than or equal to `domain_size`.
+    /// * `domain_offset` is ZERO.
+    /// * `r` is ZERO or is in the shifted domain.
+    pub fn new(domain_size: usize, domain_offset: B, degree: usize, r: B) -> Self {
+        assert!(
+            domain_size.is_power_of_two(),
+            "domain size must be a power of 2"
+        );
+        assert!(
+            log2(domain_size) <= B::TWO_ADICITY,
+            "multiplicative subgroup of size {domain_size} does not exist in the specified base field"
+        );
+        assert!(
+            degree < domain_size,
+            "degree must be smaller than domain size {domain_size}, but was {degree}"
+        );
+        assert_ne!(domain_offset, B::ZERO, "domain offset cannot be zero");
+
+        let n = domain_size as u64;
+        let inv_g = B::get_root_of_unity(log2(domain_size)).inv();
+
+        // coefficient d of a polynomial is (1 / n) * c^(-d) * sum(e_i * g^(-i * d)) where c is
+        // the domain offset; since we only need to check that this coefficient is not ZERO, we can
+        // drop the constant factors.
+        let degree_coeff_weights = get_power_series(inv_g.exp((degree as u64).into()), domain_size);
+
+        // the random linear combination sum(r^k * a_k) for k in (d + 1)..n can be written as
+        // (1 / n) * sum(e_i * s_i), where s_i = (y_i^(d + 1) - y_i^n) / (1 - y_i), and
+        // y_i = (r / c) * g^(-i). we drop the constant factor (1 / n) here as well.
+        let high_coeff_weights = if degree + 1 == domain_size {
+            Vec::new()
+        } else {
+            let y0 = r / domain_offset;
+            let y_n = y0.exp(n.into());
+            assert_ne!(r, B::ZERO, "random value cannot be zero");
+            assert_ne!(y_n, B::ONE, "random value cannot be in the domain");
+
+            let ys = get_power_series_with_offset(inv_g, y0, domain_size);
+            let denominators = ys.iter().map(|&y| B::ONE - y).collect::<Vec<_>>();
+            let inv_denominators = crate::batch_inversion(&denominators);
+
+            let ys_d = get_power_series_with_offset(
+                inv_g.exp((degree as u64 + 1).into()),
+                y0.exp((degree as u64 + 1).into()),
+                domain_size,
+            );
+
+            ys_d.into_iter()
+                .zip(inv_denominators)
+                .map(|(y_d, inv_den)| (y_d - y_n) * inv_den)
+                .collect()
+        };
+
+        DegreeCheck {
+            degree,
+            high_coeff_weights,
+            degree_coeff_weights,
+        }
+    }
+
+    /// Returns the degree of polynomials checked by this degree check.
+    pub fn degree(&self) -> usize {
+        self.degree
+    }
+
+    /// Returns true if the provided evaluations describe a polynomial of exactly the degree
+    /// specified for this check.
+    ///
+    /// A false positive is possible with probability at most `n` / |F|, while false negatives
+    /// are not possible.
+    ///
+    /// # Panics
+    /// Panics if the number of evaluations is not equal to the domain size of this check.
+    pub fn is_satisfied_by<E>(&self, evaluations: &[E]) -> bool
+    where
+        E: FieldElement<BaseField = B>,
+    {
+        assert_eq!(
+            evaluations.len(),
+            self.degree_coeff_weights.len(),
+            "expected {} evaluations, but received {}",
+            self.degree_coeff_weights.len(),
+            evaluations.len()
+        );
+
+        let degree_coeff = inner_product(evaluations, &self.degree_coeff_weights);
+        if degree_coeff == E::ZERO {
+            return false;
+        }
+
+        self.high_coeff_weights.is_empty()
+            || inner_product(evaluations, &self.high_coeff_weights) == E::ZERO
+    }
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
 fn permute<E: FieldElement>(v: &mut [E]) {
-    if cfg!(feature = "concurrent") && v.len() >= MIN_CONCURRENT_SIZE {
+    if cfg!(feature = "concurrent") && v.len() >= ConcurrencyConfig::global().min_fft_size() {
         #[cfg(feature = "concurrent")]
         concurrent::permute(v);
     } else {
@@ -607,3 +1227,10 @@ fn permute_index(size: usize, index: usize) -> usize {
     let bits = size.trailing_zeros() as usize;
     index.reverse_bits() >> (USIZE_BITS - bits)
 }
+
+fn inner_product<E: FieldElement>(values: &[E], weights: &[E::BaseField]) -> E {
+    values
+        .iter()
+        .zip(weights.iter())
+        .fold(E::ZERO, |acc, (&v, &w)| acc + v.mul_base(w))
+}
@@ -4,8 +4,12 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::fft_inputs::FftInputs;
-use crate::{field::StarkField, utils::log2, FieldElement};
-use utils::{collections::Vec, uninit_vector};
+use crate::{field::StarkField, polynom, utils::log2, FieldElement};
+use core::ops::Range;
+use utils::{
+    collections::{BTreeMap, Vec},
+    uninit_vector,
+};
 
 // POLYNOMIAL EVALUATION
 // ================================================================================================
@@ -56,6 +60,94 @@ where
     result
 }
 
+/// Evaluates polynomial `p` over the domain of length `offset_powers.len()` in the field
+/// specified `B` using the FFT algorithm and returns the result. The domain is assumed to be
+/// shifted by the offset used to compute `offset_powers`.
+pub fn evaluate_poly_with_offset_powers<B, E>(
+    p: &[E],
+    twiddles: &[B],
+    offset_powers: &[B],
+) -> Vec<E>
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+{
+    let mut result = unsafe { uninit_vector(offset_powers.len()) };
+
+    result
+        .as_mut_slice()
+        .chunks_mut(p.len())
+        .zip(offset_powers.chunks(p.len()))
+        .for_each(|(chunk, powers)| {
+            for ((d, c), &factor) in chunk.iter_mut().zip(p.iter()).zip(powers.iter()) {
+                *d = (*c).mul_base(factor);
+            }
+            chunk.fft_in_place(twiddles);
+        });
+
+    result.permute();
+    result
+}
+
+/// Evaluates polynomial `p` at the specified positions of the domain of length `domain_size`
+/// shifted by `domain_offset` in the field specified by `B` and returns the result.
+///
+/// The domain is split into cosets of the subgroup of size `p.len()`; positions falling into the
+/// same coset are evaluated together using the FFT algorithm if there are enough of them to make
+/// this cheaper than evaluating each of them individually using Horner's method.
+pub fn evaluate_poly_at_positions<B, E>(
+    p: &[E],
+    domain_size: usize,
+    domain_offset: B,
+    positions: &[usize],
+) -> Vec<E>
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+{
+    let blowup_factor = domain_size / p.len();
+    let g = B::get_root_of_unity(log2(domain_size));
+
+    // position i belongs to the coset of the subgroup of size p.len() shifted by g^(i % blowup);
+    // the index of the position within the coset is i / blowup
+    let mut cosets = BTreeMap::new();
+    for (i, &position) in positions.iter().enumerate() {
+        cosets
+            .entry(position % blowup_factor)
+            .or_insert_with(Vec::new)
+            .push(i);
+    }
+
+    // an FFT over a coset costs about p.len() * log2(p.len()) / 2 multiplications, while
+    // evaluating the polynomial at a single point costs p.len() multiplications; constant
+    // polynomials are always evaluated directly
+    let min_fft_positions = match p.len() {
+        1 => usize::MAX,
+        n => log2(n) as usize / 2,
+    };
+
+    let mut result = vec![E::ZERO; positions.len()];
+    let mut twiddles = None;
+    for (coset_idx, indexes) in cosets {
+        let coset_offset = domain_offset * g.exp((coset_idx as u64).into());
+        if indexes.len() > min_fft_positions {
+            let twiddles = twiddles.get_or_insert_with(|| super::get_twiddles::<B>(p.len()));
+            let evaluations = evaluate_poly_with_offset(p, twiddles, coset_offset, 1);
+            for i in indexes {
+                result[i] = evaluations[positions[i] / blowup_factor];
+            }
+        } else {
+            let coset_g = g.exp((blowup_factor as u64).into());
+            for i in indexes {
+                let x = coset_offset * coset_g.exp(((positions[i] / blowup_factor) as u64).into());
+                result[i] = polynom::eval(p, E::from(x));
+            }
+        }
+    }
+
+    result
+}
+
 // POLYNOMIAL INTERPOLATION
 // ================================================================================================
 
@@ -91,3 +183,47 @@ pub fn interpolate_poly_with_offset<B, E>(
 
     evaluations.shift_by_series(offset, domain_offset);
 }
+
+/// Interpolates `evaluations` over a domain of length `evaluations.len()` in the field specified
+/// by `B` into a polynomial in coefficient form using the FFT algorithm. The domain is assumed to
+/// be shifted by the offset used to compute `inv_offset_powers`.
+pub fn interpolate_poly_with_offset_powers<B, E>(
+    evaluations: &mut [E],
+    inv_twiddles: &[B],
+    inv_offset_powers: &[B],
+) where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+{
+    evaluations.fft_in_place(inv_twiddles);
+    evaluations.permute();
+
+    for (d, &factor) in evaluations.iter_mut().zip(inv_offset_powers.iter()) {
+        *d = (*d).mul_base(factor);
+    }
+}
+
+/// Interpolates `evaluations` over a domain of length `evaluations.len()` and shifted by
+/// `domain_offset` in the field specified by `B` into a polynomial in coefficient form using
+/// the FFT algorithm. All evaluations outside of the `nonzero` range must be ZEROs.
+pub fn interpolate_poly_sparse<B, E>(
+    evaluations: &mut [E],
+    inv_twiddles: &[B],
+    domain_offset: B,
+    nonzero: Range<usize>,
+) where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+{
+    evaluations.sparse_fft_in_place(inv_twiddles, nonzero);
+    evaluations.permute();
+
+    let domain_offset = B::inv(domain_offset);
+    let offset = B::inv((evaluations.len() as u64).into());
+
+    if domain_offset == B::ONE {
+        evaluations.shift_by(offset);
+    } else {
+        evaluations.shift_by_series(offset, domain_offset);
+    }
+}
@@ -5,12 +5,12 @@
 
 use crate::{
     fft::fft_inputs::FftInputs,
-    field::{f128::BaseElement, StarkField},
+    field::{f128::BaseElement, FieldElement, StarkField},
     polynom,
     utils::{get_power_series, log2},
 };
-use rand_utils::rand_vector;
-use utils::collections::Vec;
+use rand_utils::{rand_value, rand_vector};
+use utils::{collections::Vec, ConcurrencyConfig};
 
 // CORE ALGORITHMS
 // ================================================================================================
@@ -60,7 +60,7 @@ fn fft_in_place() {
 
 #[test]
 fn fft_get_twiddles() {
-    let n = super::MIN_CONCURRENT_SIZE * 2;
+    let n = ConcurrencyConfig::DEFAULT.min_fft_size() * 2;
     let g = BaseElement::get_root_of_unity(log2(n));
 
     let mut expected = get_power_series(g, n / 2);
@@ -70,6 +70,203 @@ fn fft_get_twiddles() {
     assert_eq!(expected, twiddles);
 }
 
+#[test]
+fn fft_evaluate_poly_with_offset_powers() {
+    let offset = BaseElement::GENERATOR;
+    for &(n, blowup_factor) in &[
+        (4, 2),
+        (16, 8),
+        (ConcurrencyConfig::DEFAULT.min_fft_size() * 2, 4),
+    ] {
+        let p: Vec<BaseElement> = rand_vector(n);
+        let twiddles = super::get_twiddles::<BaseElement>(n);
+        let expected = super::evaluate_poly_with_offset(&p, &twiddles, offset, blowup_factor);
+
+        let offset_powers = super::get_offset_powers(n, blowup_factor, offset);
+        let actual = super::evaluate_poly_with_offset_powers(&p, &twiddles, &offset_powers);
+        assert_eq!(expected, actual);
+    }
+}
+
+#[test]
+fn fft_get_offset_powers() {
+    let offset = BaseElement::GENERATOR;
+    let check = || {
+        for &(n, blowup_factor) in &[(4, 2), (512, 64), (4096, 4)] {
+            let g = BaseElement::get_root_of_unity(log2(n * blowup_factor));
+            let expected = (0..blowup_factor)
+                .flat_map(|i| {
+                    let idx = super::permute_index(blowup_factor, i) as u64;
+                    get_power_series(g.exp(idx.into()) * offset, n)
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(expected, super::get_offset_powers(n, blowup_factor, offset));
+        }
+    };
+
+    // when `concurrent` feature is enabled, make sure the powers are split into batches which
+    // are both smaller and larger than the polynomial size
+    #[cfg(feature = "concurrent")]
+    utils::rayon::ThreadPoolBuilder::new()
+        .num_threads(16)
+        .build()
+        .unwrap()
+        .install(check);
+
+    #[cfg(not(feature = "concurrent"))]
+    check();
+}
+
+#[test]
+fn fft_evaluate_poly_at_positions() {
+    type QuadElement = crate::fields::QuadExtension<BaseElement>;
+
+    let offset = BaseElement::GENERATOR;
+    for &(n, blowup_factor) in &[(1, 4), (4, 2), (64, 8), (256, 1)] {
+        let domain_size = n * blowup_factor;
+        let p: Vec<QuadElement> = rand_vector(n);
+        let g = BaseElement::get_root_of_unity(log2(domain_size));
+        let domain = get_power_series(g, domain_size)
+            .into_iter()
+            .map(|x| QuadElement::from(x * offset))
+            .collect::<Vec<_>>();
+        let evaluations = polynom::eval_many(&p, &domain);
+
+        // a few scattered positions are evaluated one by one, while densely queried cosets are
+        // evaluated via FFT; duplicate positions are allowed
+        let mut positions = vec![0, domain_size - 1, domain_size / 2, 0];
+        positions.extend((0..domain_size).step_by(blowup_factor));
+        let expected = positions
+            .iter()
+            .map(|&i| evaluations[i])
+            .collect::<Vec<_>>();
+        let actual = super::evaluate_poly_at_positions(&p, domain_size, offset, &positions);
+        assert_eq!(
+            expected, actual,
+            "failed for n = {n}, blowup factor = {blowup_factor}"
+        );
+    }
+
+    // evaluating at no positions yields no values
+    let p: Vec<BaseElement> = rand_vector(8);
+    assert!(super::evaluate_poly_at_positions(&p, 64, offset, &[]).is_empty());
+}
+
+#[test]
+fn fft_interpolate_poly_with_offset_powers() {
+    let offset = BaseElement::GENERATOR;
+    for &n in &[4, 16, ConcurrencyConfig::DEFAULT.min_fft_size() * 2] {
+        let p: Vec<BaseElement> = rand_vector(n);
+        let twiddles = super::get_twiddles::<BaseElement>(n);
+        let mut evaluations = super::evaluate_poly_with_offset(&p, &twiddles, offset, 1);
+
+        let inv_twiddles = super::get_inv_twiddles::<BaseElement>(n);
+        let inv_offset_powers = super::get_inv_offset_powers(n, offset);
+        super::interpolate_poly_with_offset_powers(
+            &mut evaluations,
+            &inv_twiddles,
+            &inv_offset_powers,
+        );
+        assert_eq!(p, evaluations);
+    }
+}
+
+#[test]
+fn fft_interpolate_poly_sparse() {
+    let n = 1024;
+    let inv_twiddles = super::get_inv_twiddles::<BaseElement>(n);
+    let ranges = [0..0, 0..1, 3..4, 0..n / 2, 5..17, 100..612, n - 1..n, 0..n];
+    for offset in [BaseElement::ONE, BaseElement::GENERATOR] {
+        for range in ranges.iter() {
+            let mut evaluations = vec![BaseElement::ZERO; n];
+            if !range.is_empty() {
+                let values = rand_vector::<BaseElement>(range.len());
+                evaluations[range.clone()].copy_from_slice(&values);
+            }
+
+            let mut expected = evaluations.clone();
+            super::interpolate_poly_with_offset(&mut expected, &inv_twiddles, offset);
+
+            super::interpolate_poly_sparse(&mut evaluations, &inv_twiddles, offset, range.clone());
+            assert_eq!(expected, evaluations, "failed for range {range:?}");
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "concurrent")]
+fn fft_concurrent_small_inputs() {
+    // concurrent versions of FFT operations should produce the same results as the serial ones
+    // even when the number of elements is smaller than the number of threads
+    let offset = BaseElement::GENERATOR;
+    let check = || {
+        for n in [2, 4, 8, 16, 32] {
+            let p: Vec<BaseElement> = rand_vector(n);
+            let twiddles = super::get_twiddles::<BaseElement>(n);
+            let inv_twiddles = super::get_inv_twiddles::<BaseElement>(n);
+
+            let mut expected = p.clone();
+            super::serial::evaluate_poly(&mut expected, &twiddles);
+            let mut actual = p.clone();
+            super::concurrent::evaluate_poly(&mut actual, &twiddles);
+            assert_eq!(expected, actual, "evaluation failed for n = {n}");
+
+            let expected = super::serial::evaluate_poly_with_offset(&p, &twiddles, offset, 2);
+            let actual = super::concurrent::evaluate_poly_with_offset(&p, &twiddles, offset, 2);
+            assert_eq!(
+                expected, actual,
+                "evaluation with offset failed for n = {n}"
+            );
+
+            let mut expected = p.clone();
+            super::serial::interpolate_poly(&mut expected, &inv_twiddles);
+            let mut actual = p.clone();
+            super::concurrent::interpolate_poly(&mut actual, &inv_twiddles);
+            assert_eq!(expected, actual, "interpolation failed for n = {n}");
+
+            let mut expected = p.clone();
+            super::serial::interpolate_poly_with_offset(&mut expected, &inv_twiddles, offset);
+            let mut actual = p.clone();
+            super::concurrent::interpolate_poly_with_offset(&mut actual, &inv_twiddles, offset);
+            assert_eq!(
+                expected, actual,
+                "interpolation with offset failed for n = {n}"
+            );
+        }
+    };
+
+    utils::rayon::ThreadPoolBuilder::new()
+        .num_threads(16)
+        .build()
+        .unwrap()
+        .install(check);
+}
+
+// DEGREE CHECK
+// ================================================================================================
+
+#[test]
+fn degree_check() {
+    let n = 64;
+    let offset = BaseElement::GENERATOR;
+    let g = BaseElement::get_root_of_unity(log2(n));
+    let domain = crate::get_power_series_with_offset(g, offset, n);
+
+    for degree in [0, 1, 7, 31, 62, 63] {
+        let p: Vec<BaseElement> = rand_vector(degree + 1);
+        let evaluations = polynom::eval_many(&p, &domain);
+
+        for check_degree in [0, 1, 7, 31, 62, 63] {
+            let check = super::DegreeCheck::new(n, offset, check_degree, rand_value());
+            assert_eq!(
+                degree == check_degree,
+                check.is_satisfied_by(&evaluations),
+                "failed for degree {degree} and check degree {check_degree}"
+            );
+        }
+    }
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
@@ -70,6 +70,11 @@ impl FieldElement for BaseElement {
 
     const IS_CANONICAL: bool = true;
 
+    #[inline]
+    fn mul_small(self, k: u32) -> Self {
+        BaseElement(mul_small(self.0, k))
+    }
+
     fn inv(self) -> Self {
         BaseElement(inv(self.0))
     }
@@ -124,6 +129,10 @@ impl FieldElement for BaseElement {
     fn as_base_elements(elements: &[Self]) -> &[Self::BaseField] {
         elements
     }
+
+    fn slice_from_base_elements(elements: &[Self::BaseField]) -> &[Self] {
+        elements
+    }
 }
 
 impl StarkField for BaseElement {
@@ -337,7 +346,7 @@ impl From<[u8; 16]> for BaseElement {
     }
 }
 
-impl<'a> TryFrom<&'a [u8]> for BaseElement {
+impl TryFrom<&[u8]> for BaseElement {
     type Error = String;
 
     /// Converts a slice of bytes into a field element; returns error if the value encoded in bytes
@@ -448,6 +457,32 @@ fn mul(a: u128, b: u128) -> u128 {
     ((z1 as u128) << 64) + (z0 as u128)
 }
 
+/// Computes (a * k) % m; a is assumed to be a valid field element.
+#[inline(always)]
+fn mul_small(a: u128, k: u32) -> u128 {
+    // 2^128 - m; since 2^128 = c mod m, the high part of the product can be folded into the low
+    // part by multiplying it by c
+    const C: u128 = 45 * (1 << 40) - 1;
+
+    // compute the product as a 160-bit value z = z_lo + z_hi * 2^128
+    let lo = (a as u64 as u128) * (k as u128);
+    let hi = (a >> 64) * (k as u128);
+    let (z_lo, carry) = lo.overflowing_add(hi << 64);
+    let z_hi = (hi >> 64) + (carry as u128);
+
+    // fold z_hi into the low part; z_hi * c < 2^79, and thus, after an overflow, the result is
+    // small enough for c to be added without another overflow
+    let (r, carry) = z_lo.overflowing_add(z_hi * C);
+    let r = if carry { r + C } else { r };
+
+    // make sure the result is smaller than m
+    if r >= M {
+        r - M
+    } else {
+        r
+    }
+}
+
 /// Computes y such that (x * y) % m = 1 except for when when x = 0; in such a case,
 /// 0 is returned; x is assumed to be a valid field element.
 fn inv(x: u128) -> u128 {
@@ -8,7 +8,6 @@ use super::{
     StarkField, M,
 };
 use crate::field::{ExtensionOf, QuadExtension};
-use core::convert::TryFrom;
 use num_bigint::BigUint;
 use rand_utils::{rand_value, rand_vector};
 use utils::SliceReader;
@@ -101,6 +100,30 @@ fn mul() {
     }
 }
 
+#[test]
+fn mul_small() {
+    // identity
+    let r: BaseElement = rand_value();
+    assert_eq!(BaseElement::ZERO, r.mul_small(0));
+    assert_eq!(r, r.mul_small(1));
+    assert_eq!(r.double(), r.mul_small(2));
+    assert_eq!(r.double() + r, r.triple());
+
+    // test overflow
+    let m = BaseElement::MODULUS;
+    let t = BaseElement::from(m - 1);
+    assert_eq!(BaseElement::from(m - 2), t.mul_small(2));
+    assert_eq!(-BaseElement::from(u32::MAX), t.mul_small(u32::MAX));
+
+    // test random values
+    let v: Vec<BaseElement> = rand_vector(1000);
+    let k: Vec<u32> = rand_vector(1000);
+    for (&v, &k) in v.iter().zip(k.iter()) {
+        let expected = (v.to_big_uint() * BigUint::from(k)) % BigUint::from(M);
+        assert_eq!(BaseElement::from_big_uint(expected), v.mul_small(k));
+    }
+}
+
 #[test]
 fn inv() {
     // identity
@@ -163,6 +186,7 @@ fn quad_mul_base() {
 // ================================================================================================
 
 #[test]
+#[cfg(target_endian = "little")]
 fn elements_as_bytes() {
     let source = vec![
         BaseElement::new(1),
@@ -181,6 +205,7 @@ fn elements_as_bytes() {
 }
 
 #[test]
+#[cfg(target_endian = "little")]
 fn bytes_as_elements() {
     let bytes: Vec<u8> = vec![
         1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
@@ -267,6 +292,6 @@ impl BaseElement {
         let bytes = value.to_bytes_le();
         let mut buffer = [0u8; 16];
         buffer[0..bytes.len()].copy_from_slice(&bytes);
-        BaseElement::try_from(buffer).unwrap()
+        BaseElement::from(buffer)
     }
 }
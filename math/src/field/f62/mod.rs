@@ -66,6 +66,18 @@ impl BaseElement {
         let z = mul(value, R2);
         BaseElement(z)
     }
+
+    /// Returns a new field element from the provided `value`. Assumes that `value` is already
+    /// in Montgomery representation and is in the range [0, 2M).
+    pub const fn from_mont(value: u64) -> BaseElement {
+        BaseElement(value)
+    }
+
+    /// Returns the inner value of this element in Montgomery representation; the value is in the
+    /// range [0, 2M) and is not necessarily canonical.
+    pub const fn inner(&self) -> u64 {
+        self.0
+    }
 }
 
 impl FieldElement for BaseElement {
@@ -158,6 +170,10 @@ impl FieldElement for BaseElement {
     fn as_base_elements(elements: &[Self]) -> &[Self::BaseField] {
         elements
     }
+
+    fn slice_from_base_elements(elements: &[Self::BaseField]) -> &[Self] {
+        elements
+    }
 }
 
 impl StarkField for BaseElement {
@@ -432,7 +448,7 @@ impl From<[u8; 8]> for BaseElement {
     }
 }
 
-impl<'a> TryFrom<&'a [u8]> for BaseElement {
+impl TryFrom<&[u8]> for BaseElement {
     type Error = DeserializationError;
 
     /// Converts a slice of bytes into a field element; returns error if the value encoded in bytes
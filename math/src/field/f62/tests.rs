@@ -255,6 +255,7 @@ fn try_from_slice() {
 }
 
 #[test]
+#[cfg(target_endian = "little")]
 fn elements_as_bytes() {
     let source = vec![
         BaseElement::new(1),
@@ -273,6 +274,7 @@ fn elements_as_bytes() {
 }
 
 #[test]
+#[cfg(target_endian = "little")]
 fn bytes_as_elements() {
     let elements = vec![
         BaseElement::new(1),
@@ -100,6 +100,20 @@ impl FieldElement for BaseElement {
         Self(result.wrapping_sub(M * over))
     }
 
+    #[inline]
+    fn mul_small(self, k: u32) -> Self {
+        // multiplying a value in Montgomery form by an integer yields a value in Montgomery form,
+        // and thus, we only need to reduce a 96-bit product; this relies on 2^64 = 2^32 - 1 mod M
+        let z = (self.0 as u128) * (k as u128);
+        let (lo, hi) = (z as u64, (z >> 64) as u64);
+        let (r, c) = lo.overflowing_add((hi << 32) - hi);
+        let r = r.wrapping_add(0u32.wrapping_sub(c as u32) as u64);
+
+        // the result is in [0, 2^64) range; reduce it to [0, M) range
+        let (x1, c1) = r.overflowing_sub(M);
+        Self(if c1 { r } else { x1 })
+    }
+
     #[inline]
     fn exp(self, power: Self::PositiveInteger) -> Self {
         let mut b: Self;
@@ -197,6 +211,10 @@ impl FieldElement for BaseElement {
     fn as_base_elements(elements: &[Self]) -> &[Self::BaseField] {
         elements
     }
+
+    fn slice_from_base_elements(elements: &[Self::BaseField]) -> &[Self] {
+        elements
+    }
 }
 
 impl StarkField for BaseElement {
@@ -234,7 +252,10 @@ impl StarkField for BaseElement {
         let x = self.0;
         let (r, c) = x.overflowing_add(x << 32);
         let res = r.wrapping_sub(r >> 32).wrapping_sub(c as u64);
-        M - res
+        // M - res is in the range [1, M]; M is the only value which is not in canonical form
+        // (this happens for ZERO), and thus, it needs to be reduced to 0
+        let res = M - res;
+        res - M * ((res == M) as u64)
     }
 }
 
@@ -484,7 +505,7 @@ impl From<[u8; 8]> for BaseElement {
     }
 }
 
-impl<'a> TryFrom<&'a [u8]> for BaseElement {
+impl TryFrom<&[u8]> for BaseElement {
     type Error = DeserializationError;
 
     /// Converts a slice of bytes into a field element; returns error if the value encoded in bytes
@@ -8,7 +8,7 @@ use crate::field::{CubeExtension, ExtensionOf, QuadExtension};
 use core::convert::TryFrom;
 use num_bigint::BigUint;
 use proptest::prelude::*;
-use rand_utils::rand_value;
+use rand_utils::{rand_value, rand_vector};
 
 // MANUAL TESTS
 // ================================================================================================
@@ -84,6 +84,29 @@ fn mul() {
     );
 }
 
+#[test]
+fn mul_small() {
+    // identity
+    let r: BaseElement = rand_value();
+    assert_eq!(BaseElement::ZERO, r.mul_small(0));
+    assert_eq!(r, r.mul_small(1));
+    assert_eq!(r.double(), r.mul_small(2));
+    assert_eq!(r.double() + r, r.triple());
+
+    // test overflow
+    let m = BaseElement::MODULUS;
+    let t = BaseElement::from(m - 1);
+    assert_eq!(BaseElement::from(m - 2), t.mul_small(2));
+    assert_eq!(-BaseElement::from(u32::MAX), t.mul_small(u32::MAX));
+
+    // test random values
+    let v: Vec<BaseElement> = rand_vector(1000);
+    let k: Vec<u32> = rand_vector(1000);
+    for (&v, &k) in v.iter().zip(k.iter()) {
+        assert_eq!(v * BaseElement::from(k), v.mul_small(k));
+    }
+}
+
 #[test]
 fn exp() {
     let a = BaseElement::ZERO;
@@ -116,6 +139,21 @@ fn element_as_int() {
     assert_eq!(v % super::M, e.as_int());
 }
 
+#[test]
+fn zero_as_int() {
+    // zero must be in canonical form regardless of how it was obtained
+    let e = BaseElement::new(u64::MAX);
+    assert_eq!(0, BaseElement::ZERO.as_int());
+    assert_eq!(0, BaseElement::new(super::M).as_int());
+    assert_eq!(0, (e - e).as_int());
+    assert_eq!(0, (e * BaseElement::ZERO).as_int());
+    let bytes = BaseElement::ZERO.to_bytes();
+    assert_eq!(
+        BaseElement::ZERO,
+        BaseElement::try_from(bytes.as_slice()).unwrap()
+    );
+}
+
 #[test]
 fn equals() {
     let a = BaseElement::ONE;
@@ -173,6 +211,7 @@ fn try_from_slice() {
 }
 
 #[test]
+#[cfg(target_endian = "little")]
 fn elements_as_bytes() {
     let source = vec![
         BaseElement::new(1),
@@ -191,6 +230,7 @@ fn elements_as_bytes() {
 }
 
 #[test]
+#[cfg(target_endian = "little")]
 fn bytes_as_elements() {
     let elements = vec![
         BaseElement::new(1),
@@ -229,6 +269,13 @@ fn zeroed_vector() {
     }
 }
 
+#[test]
+fn extension_degree() {
+    assert_eq!(1, BaseElement::EXTENSION_DEGREE);
+    assert_eq!(2, QuadExtension::<BaseElement>::EXTENSION_DEGREE);
+    assert_eq!(3, CubeExtension::<BaseElement>::EXTENSION_DEGREE);
+}
+
 // QUADRATIC EXTENSION
 // ------------------------------------------------------------------------------------------------
 #[test]
@@ -421,6 +468,14 @@ proptest! {
         prop_assert_eq!(expected, result.as_int());
     }
 
+    #[test]
+    fn mul_small_proptest(a in any::<u64>(), b in any::<u32>()) {
+        let result = BaseElement::from(a).mul_small(b);
+
+        let expected = (((a as u128) * (b as u128)) % super::M as u128) as u64;
+        prop_assert_eq!(expected, result.as_int());
+    }
+
     #[test]
     fn double_proptest(x in any::<u64>()) {
         let v = BaseElement::from(x);
@@ -0,0 +1,706 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+#[cfg(test)]
+mod tests;
+
+// PRIME FIELD MACRO
+// ================================================================================================
+
+/// Defines a module with a STARK-friendly prime field with a modulus smaller than 2<sup>64</sup>.
+///
+/// The macro takes the field modulus, a multiplicative generator of the field, and the
+/// two-adicity of the field (the largest `k` such that 2<sup>k</sup> divides `modulus - 1`),
+/// and emits a module containing a `BaseElement` type which implements [FieldElement](crate::FieldElement)
+/// and [StarkField](crate::StarkField) traits along with all arithmetic operators, conversions,
+/// and serialization. The module also contains a set of unit tests for the field which are
+/// compiled in the `test` configuration of the crate invoking the macro.
+///
+/// All operations in the field are implemented using Montgomery arithmetic, and elements are
+/// always kept fully reduced. This is not as fast as hand-tuned implementations (e.g.,
+/// [f64](crate::fields::f64)), but makes it possible to experiment with new fields without
+/// writing any arithmetic code.
+///
+/// Quadratic extensions of the field are supported when a quadratic non-residue `n` is
+/// provided; the extension is then defined over an irreducible polynomial x<sup>2</sup> - n.
+/// Cubic extensions are not supported.
+///
+/// Field parameters are validated at compile time: the modulus must be a prime, the generator
+/// must be a quadratic non-residue (and thus, the derived root of unity has the expected order),
+/// and the two-adicity must match the modulus.
+///
+/// # Examples
+/// ```
+/// use winter_math::{define_prime_field, FieldElement, StarkField};
+///
+/// define_prime_field! {
+///     /// A 31-bit field with modulus 15 * 2^27 + 1.
+///     pub mod baby_bear {
+///         modulus: 2013265921,
+///         generator: 31,
+///         two_adicity: 27,
+///         quadratic_non_residue: 11,
+///     }
+/// }
+///
+/// use baby_bear::BaseElement;
+///
+/// let a = BaseElement::new(2013265920);
+/// assert_eq!(BaseElement::ZERO, a + BaseElement::ONE);
+/// assert_eq!(BaseElement::ONE, a * a);
+///
+/// let g = BaseElement::get_root_of_unity(27);
+/// assert_eq!(BaseElement::ONE, g.exp(1 << 27));
+/// ```
+#[macro_export]
+macro_rules! define_prime_field {
+    (
+        $(#[$meta:meta])*
+        $vis:vis mod $name:ident {
+            modulus: $modulus:expr,
+            generator: $generator:expr,
+            two_adicity: $two_adicity:expr
+            $(, quadratic_non_residue: $non_residue:expr)?
+            $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis mod $name {
+            use $crate::{
+                ExtensibleField, FieldElement, StarkField,
+                __private::{is_prime, mont_mul, mont_reduce, pow_mod, utils},
+            };
+            use core::{
+                convert::{TryFrom, TryInto},
+                fmt::{Display, Formatter},
+                mem,
+                ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+                slice,
+            };
+            use utils::{
+                collections::Vec, string::ToString, AsBytes, ByteReader, ByteWriter,
+                Deserializable, DeserializationError, Randomizable, Serializable,
+            };
+
+            // CONSTANTS
+            // ------------------------------------------------------------------------------------
+
+            /// Field modulus.
+            const M: u64 = $modulus;
+
+            /// Largest power of two which divides M - 1.
+            const TWO_ADICITY: u32 = $two_adicity;
+
+            /// M^{-1} mod 2^64; computed via Newton iteration which doubles the number of correct
+            /// bits on each step.
+            const M_INV: u64 = {
+                let mut inv = 1u64;
+                let mut i = 0;
+                while i < 6 {
+                    inv = inv.wrapping_mul(2u64.wrapping_sub(M.wrapping_mul(inv)));
+                    i += 1;
+                }
+                inv
+            };
+
+            /// 2^64 mod M.
+            const R: u64 = ((u64::MAX % M) + 1) % M;
+
+            /// 2^128 mod M; this is used for conversion of elements into Montgomery form.
+            const R2: u64 = ((R as u128 * R as u128) % M as u128) as u64;
+
+            /// Generator of the multiplicative group of the field in canonical form.
+            const GENERATOR: u64 = ($generator as u64) % M;
+
+            /// Root of unity of order 2^TWO_ADICITY in canonical form.
+            const G: u64 = pow_mod(GENERATOR, (M - 1) >> TWO_ADICITY, M);
+
+            /// Number of bytes needed to represent field element.
+            const ELEMENT_BYTES: usize = core::mem::size_of::<u64>();
+
+            const _: () = assert!(is_prime(M), "field modulus must be an odd prime");
+            const _: () = assert!(
+                TWO_ADICITY > 0
+                    && TWO_ADICITY < 64
+                    && (M - 1) % (1u64 << TWO_ADICITY) == 0
+                    && ((M - 1) >> TWO_ADICITY) % 2 == 1,
+                "two-adicity does not match the field modulus"
+            );
+            const _: () = assert!(
+                pow_mod(GENERATOR, (M - 1) / 2, M) == M - 1,
+                "field generator must be a quadratic non-residue"
+            );
+
+            // FIELD ELEMENT
+            // ------------------------------------------------------------------------------------
+
+            /// Represents base field element in the field.
+            ///
+            /// Internal values are stored in Montgomery representation and are always in the
+            /// range [0, M). The backing type is `u64`.
+            #[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+            #[repr(transparent)]
+            pub struct BaseElement(u64);
+
+            impl BaseElement {
+                /// Creates a new field element from the provided `value`; the value is converted
+                /// into Montgomery representation. If the value is greater than or equal to the
+                /// field modulus, modular reduction is silently performed.
+                pub const fn new(value: u64) -> BaseElement {
+                    BaseElement(mont_mul(value, R2, M, M_INV))
+                }
+            }
+
+            impl FieldElement for BaseElement {
+                type PositiveInteger = u64;
+                type BaseField = Self;
+
+                const ZERO: Self = BaseElement::new(0);
+                const ONE: Self = BaseElement::new(1);
+
+                const ELEMENT_BYTES: usize = ELEMENT_BYTES;
+                const IS_CANONICAL: bool = false;
+
+                #[inline]
+                fn double(self) -> Self {
+                    self + self
+                }
+
+                fn inv(self) -> Self {
+                    // by Fermat's little theorem x^(M - 2) = x^{-1} for all x != 0; for x = 0
+                    // the result is 0 as well
+                    self.exp(M - 2)
+                }
+
+                fn conjugate(&self) -> Self {
+                    BaseElement(self.0)
+                }
+
+                fn elements_as_bytes(elements: &[Self]) -> &[u8] {
+                    let p = elements.as_ptr();
+                    let len = elements.len() * Self::ELEMENT_BYTES;
+                    unsafe { slice::from_raw_parts(p as *const u8, len) }
+                }
+
+                unsafe fn bytes_as_elements(
+                    bytes: &[u8],
+                ) -> Result<&[Self], DeserializationError> {
+                    if bytes.len() % Self::ELEMENT_BYTES != 0 {
+                        return Err(DeserializationError::InvalidValue(
+                            "number of bytes does not divide into whole number of field elements"
+                                .to_string(),
+                        ));
+                    }
+
+                    let p = bytes.as_ptr();
+                    let len = bytes.len() / Self::ELEMENT_BYTES;
+
+                    if (p as usize) % mem::align_of::<u64>() != 0 {
+                        return Err(DeserializationError::InvalidValue(
+                            "slice memory alignment is not valid for this field element type"
+                                .to_string(),
+                        ));
+                    }
+
+                    Ok(slice::from_raw_parts(p as *const Self, len))
+                }
+
+                fn as_base_elements(elements: &[Self]) -> &[Self::BaseField] {
+                    elements
+                }
+
+                fn slice_from_base_elements(elements: &[Self::BaseField]) -> &[Self] {
+                    elements
+                }
+            }
+
+            impl StarkField for BaseElement {
+                const MODULUS: Self::PositiveInteger = M;
+                const MODULUS_BITS: u32 = 64 - M.leading_zeros();
+                const GENERATOR: Self = BaseElement::new(GENERATOR);
+                const TWO_ADICITY: u32 = TWO_ADICITY;
+                const TWO_ADIC_ROOT_OF_UNITY: Self = BaseElement::new(G);
+
+                fn get_modulus_le_bytes() -> Vec<u8> {
+                    Self::MODULUS.to_le_bytes().to_vec()
+                }
+
+                #[inline]
+                fn as_int(&self) -> Self::PositiveInteger {
+                    // convert from Montgomery representation by multiplying by 1
+                    mont_reduce(self.0 as u128, M, M_INV)
+                }
+            }
+
+            impl Randomizable for BaseElement {
+                const VALUE_SIZE: usize = Self::ELEMENT_BYTES;
+
+                fn from_random_bytes(bytes: &[u8]) -> Option<Self> {
+                    Self::try_from(bytes).ok()
+                }
+            }
+
+            impl Display for BaseElement {
+                fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+                    write!(f, "{}", self.as_int())
+                }
+            }
+
+            // OVERLOADED OPERATORS
+            // ------------------------------------------------------------------------------------
+
+            impl Add for BaseElement {
+                type Output = Self;
+
+                fn add(self, rhs: Self) -> Self {
+                    let (sum, overflow) = self.0.overflowing_add(rhs.0);
+                    let (reduced, underflow) = sum.overflowing_sub(M);
+                    if overflow || !underflow {
+                        Self(reduced)
+                    } else {
+                        Self(sum)
+                    }
+                }
+            }
+
+            impl AddAssign for BaseElement {
+                fn add_assign(&mut self, rhs: Self) {
+                    *self = *self + rhs
+                }
+            }
+
+            impl Sub for BaseElement {
+                type Output = Self;
+
+                fn sub(self, rhs: Self) -> Self {
+                    let (diff, underflow) = self.0.overflowing_sub(rhs.0);
+                    if underflow {
+                        Self(diff.wrapping_add(M))
+                    } else {
+                        Self(diff)
+                    }
+                }
+            }
+
+            impl SubAssign for BaseElement {
+                fn sub_assign(&mut self, rhs: Self) {
+                    *self = *self - rhs;
+                }
+            }
+
+            impl Mul for BaseElement {
+                type Output = Self;
+
+                fn mul(self, rhs: Self) -> Self {
+                    Self(mont_mul(self.0, rhs.0, M, M_INV))
+                }
+            }
+
+            impl MulAssign for BaseElement {
+                fn mul_assign(&mut self, rhs: Self) {
+                    *self = *self * rhs
+                }
+            }
+
+            impl Div for BaseElement {
+                type Output = Self;
+
+                #[allow(clippy::suspicious_arithmetic_impl)]
+                fn div(self, rhs: Self) -> Self {
+                    self * rhs.inv()
+                }
+            }
+
+            impl DivAssign for BaseElement {
+                fn div_assign(&mut self, rhs: Self) {
+                    *self = *self / rhs
+                }
+            }
+
+            impl Neg for BaseElement {
+                type Output = Self;
+
+                fn neg(self) -> Self {
+                    Self::ZERO - self
+                }
+            }
+
+            // EXTENSIONS
+            // ------------------------------------------------------------------------------------
+
+            $crate::define_prime_field!(@quad_extension $($non_residue)?);
+
+            impl ExtensibleField<3> for BaseElement {
+                fn mul(_a: [Self; 3], _b: [Self; 3]) -> [Self; 3] {
+                    unimplemented!()
+                }
+
+                fn mul_base(_a: [Self; 3], _b: Self) -> [Self; 3] {
+                    unimplemented!()
+                }
+
+                fn frobenius(_x: [Self; 3]) -> [Self; 3] {
+                    unimplemented!()
+                }
+
+                fn is_supported() -> bool {
+                    false
+                }
+            }
+
+            // TYPE CONVERSIONS
+            // ------------------------------------------------------------------------------------
+
+            impl From<u128> for BaseElement {
+                /// Converts a 128-bit value into a field element. If the value is greater than or
+                /// equal to the field modulus, modular reduction is silently performed.
+                fn from(value: u128) -> Self {
+                    BaseElement::new((value % M as u128) as u64)
+                }
+            }
+
+            impl From<u64> for BaseElement {
+                /// Converts a 64-bit value into a field element. If the value is greater than or
+                /// equal to the field modulus, modular reduction is silently performed.
+                fn from(value: u64) -> Self {
+                    BaseElement::new(value)
+                }
+            }
+
+            impl From<u32> for BaseElement {
+                /// Converts a 32-bit value into a field element.
+                fn from(value: u32) -> Self {
+                    BaseElement::new(value as u64)
+                }
+            }
+
+            impl From<u16> for BaseElement {
+                /// Converts a 16-bit value into a field element.
+                fn from(value: u16) -> Self {
+                    BaseElement::new(value as u64)
+                }
+            }
+
+            impl From<u8> for BaseElement {
+                /// Converts an 8-bit value into a field element.
+                fn from(value: u8) -> Self {
+                    BaseElement::new(value as u64)
+                }
+            }
+
+            impl From<[u8; 8]> for BaseElement {
+                /// Converts the value encoded in an array of 8 bytes into a field element. The
+                /// bytes are assumed to encode the element in the canonical representation in
+                /// little-endian byte order. If the value is greater than or equal to the field
+                /// modulus, modular reduction is silently performed.
+                fn from(bytes: [u8; 8]) -> Self {
+                    BaseElement::new(u64::from_le_bytes(bytes))
+                }
+            }
+
+            impl<'a> TryFrom<&'a [u8]> for BaseElement {
+                type Error = DeserializationError;
+
+                /// Converts a slice of bytes into a field element; returns error if the value
+                /// encoded in bytes is not a valid field element. The bytes are assumed to encode
+                /// the element in the canonical representation in little-endian byte order.
+                fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+                    let bytes: [u8; ELEMENT_BYTES] = bytes.try_into().map_err(|_| {
+                        DeserializationError::InvalidValue(
+                            "invalid number of bytes for a field element".to_string(),
+                        )
+                    })?;
+                    let value = u64::from_le_bytes(bytes);
+                    if value >= M {
+                        return Err(DeserializationError::InvalidValue(
+                            "invalid field element: value is greater than or equal to the field modulus"
+                                .to_string(),
+                        ));
+                    }
+                    Ok(BaseElement::new(value))
+                }
+            }
+
+            impl AsBytes for BaseElement {
+                fn as_bytes(&self) -> &[u8] {
+                    let self_ptr: *const BaseElement = self;
+                    unsafe { slice::from_raw_parts(self_ptr as *const u8, ELEMENT_BYTES) }
+                }
+            }
+
+            // SERIALIZATION / DESERIALIZATION
+            // ------------------------------------------------------------------------------------
+
+            impl Serializable for BaseElement {
+                fn write_into<W: ByteWriter>(&self, target: &mut W) {
+                    // convert from Montgomery representation into canonical representation
+                    target.write_u8_slice(&self.as_int().to_le_bytes());
+                }
+            }
+
+            impl Deserializable for BaseElement {
+                fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+                    let value = source.read_u64()?;
+                    if value >= M {
+                        return Err(DeserializationError::InvalidValue(
+                            "invalid field element: value is greater than or equal to the field modulus"
+                                .to_string(),
+                        ));
+                    }
+                    Ok(BaseElement::new(value))
+                }
+            }
+
+            // TESTS
+            // ------------------------------------------------------------------------------------
+
+            #[cfg(test)]
+            mod tests {
+                use super::*;
+
+                /// Returns a deterministic sequence of pseudo-random values in [0, M).
+                fn test_values(n: usize) -> Vec<u64> {
+                    let mut state = 0x9e3779b97f4a7c15u64;
+                    let mut result = Vec::with_capacity(n + 4);
+                    result.extend_from_slice(&[0, 1, M - 2, M - 1]);
+                    for _ in 0..n {
+                        // splitmix64
+                        state = state.wrapping_add(0x9e3779b97f4a7c15);
+                        let mut z = state;
+                        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+                        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+                        result.push((z ^ (z >> 31)) % M);
+                    }
+                    result
+                }
+
+                #[test]
+                fn arithmetic() {
+                    let values = test_values(64);
+                    for &a in values.iter() {
+                        for &b in values.iter() {
+                            let (x, y) = (BaseElement::new(a), BaseElement::new(b));
+                            let m = M as u128;
+                            assert_eq!(((a as u128 + b as u128) % m) as u64, (x + y).as_int());
+                            assert_eq!(((a as u128 + m - b as u128) % m) as u64, (x - y).as_int());
+                            assert_eq!(((a as u128 * b as u128) % m) as u64, (x * y).as_int());
+                        }
+                        let x = BaseElement::new(a);
+                        assert_eq!(a, x.as_int());
+                        assert_eq!(BaseElement::ZERO, x + (-x));
+                        if a != 0 {
+                            assert_eq!(BaseElement::ONE, x * x.inv());
+                        }
+                    }
+                    assert_eq!(BaseElement::ZERO, BaseElement::ZERO.inv());
+                    assert_eq!(BaseElement::ZERO, BaseElement::new(M));
+                    assert_eq!(BaseElement::new(5), BaseElement::from(M as u128 * 3 + 5));
+                }
+
+                #[test]
+                fn roots_of_unity() {
+                    let root = BaseElement::TWO_ADIC_ROOT_OF_UNITY;
+                    assert_eq!(BaseElement::ONE, root.exp(1 << TWO_ADICITY));
+                    assert_ne!(BaseElement::ONE, root.exp(1 << (TWO_ADICITY - 1)));
+                    assert_eq!(-BaseElement::ONE, BaseElement::get_root_of_unity(1));
+                    assert_eq!(
+                        -BaseElement::ONE,
+                        BaseElement::GENERATOR.exp((M - 1) / 2)
+                    );
+                }
+
+                #[test]
+                fn serialization() {
+                    for value in test_values(64) {
+                        let element = BaseElement::new(value);
+                        let bytes = element.to_bytes();
+                        assert_eq!(value.to_le_bytes().to_vec(), bytes);
+                        assert_eq!(element, BaseElement::try_from(bytes.as_slice()).unwrap());
+                        let mut reader = utils::SliceReader::new(&bytes);
+                        assert_eq!(element, BaseElement::read_from(&mut reader).unwrap());
+
+                        let elements = [element];
+                        let bytes = BaseElement::elements_as_bytes(&elements);
+                        assert_eq!(element.as_bytes(), bytes);
+                    }
+                    assert!(BaseElement::try_from(M.to_le_bytes().as_slice()).is_err());
+                    assert!(BaseElement::try_from([0u8; 7].as_slice()).is_err());
+                }
+
+                $crate::define_prime_field!(@quad_extension_tests $($non_residue)?);
+            }
+        }
+    };
+
+    // QUADRATIC EXTENSION
+    // --------------------------------------------------------------------------------------------
+
+    (@quad_extension) => {
+        impl ExtensibleField<2> for BaseElement {
+            fn mul(_a: [Self; 2], _b: [Self; 2]) -> [Self; 2] {
+                unimplemented!()
+            }
+
+            fn mul_base(_a: [Self; 2], _b: Self) -> [Self; 2] {
+                unimplemented!()
+            }
+
+            fn frobenius(_x: [Self; 2]) -> [Self; 2] {
+                unimplemented!()
+            }
+
+            fn is_supported() -> bool {
+                false
+            }
+        }
+    };
+
+    (@quad_extension $non_residue:expr) => {
+        /// Quadratic non-residue defining the quadratic extension of the field.
+        const NON_RESIDUE: u64 = ($non_residue as u64) % M;
+
+        const _: () = assert!(
+            pow_mod(NON_RESIDUE, (M - 1) / 2, M) == M - 1,
+            "quadratic extension must be defined by a quadratic non-residue"
+        );
+
+        /// Defines a quadratic extension of the base field over an irreducible polynomial
+        /// x<sup>2</sup> - n, where n is a quadratic non-residue. Thus, an extension element is
+        /// defined as α + β * φ, where φ is a root of this polynomial, and α and β are base field
+        /// elements.
+        impl ExtensibleField<2> for BaseElement {
+            #[inline(always)]
+            fn mul(a: [Self; 2], b: [Self; 2]) -> [Self; 2] {
+                let a0b0 = a[0] * b[0];
+                let a1b1 = a[1] * b[1];
+                [
+                    a0b0 + a1b1 * BaseElement::new(NON_RESIDUE),
+                    (a[0] + a[1]) * (b[0] + b[1]) - a0b0 - a1b1,
+                ]
+            }
+
+            #[inline(always)]
+            fn mul_base(a: [Self; 2], b: Self) -> [Self; 2] {
+                [a[0] * b, a[1] * b]
+            }
+
+            #[inline(always)]
+            fn frobenius(x: [Self; 2]) -> [Self; 2] {
+                // φ^M = φ * n^((M - 1) / 2) = -φ
+                [x[0], -x[1]]
+            }
+        }
+    };
+
+    (@quad_extension_tests) => {};
+
+    (@quad_extension_tests $non_residue:expr) => {
+        #[test]
+        fn quad_extension() {
+            use $crate::fields::QuadExtension;
+            type QuadElement = QuadExtension<BaseElement>;
+
+            let values = test_values(16);
+            for pair in values.chunks(2) {
+                let a = QuadElement::new(BaseElement::new(pair[0]), BaseElement::new(pair[1]));
+                if a != QuadElement::ZERO {
+                    assert_eq!(QuadElement::ONE, a * a.inv());
+                }
+                // frobenius endomorphism is the same as raising to the power of M
+                assert_eq!(a.exp(M), a.conjugate());
+            }
+        }
+    };
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Computes a * b * 2^{-64} mod m; a * b is assumed to be smaller than m * 2^64, and m_inv is
+/// assumed to be m^{-1} mod 2^64.
+#[doc(hidden)]
+#[inline(always)]
+pub const fn mont_mul(a: u64, b: u64, m: u64, m_inv: u64) -> u64 {
+    mont_reduce(a as u128 * b as u128, m, m_inv)
+}
+
+/// Computes z * 2^{-64} mod m for z < m * 2^64; the result is in [0, m) range.
+#[doc(hidden)]
+#[inline(always)]
+pub const fn mont_reduce(z: u128, m: u64, m_inv: u64) -> u64 {
+    // the low 64 bits of z and q * m are the same, and thus, (z - q * m) / 2^64 is just the
+    // difference of the high 64 bits; this difference is in (-m, m) range
+    let q = (z as u64).wrapping_mul(m_inv);
+    let qm_hi = ((q as u128 * m as u128) >> 64) as u64;
+    let (r, underflow) = ((z >> 64) as u64).overflowing_sub(qm_hi);
+    if underflow {
+        r.wrapping_add(m)
+    } else {
+        r
+    }
+}
+
+/// Computes b^e mod m using canonical (non-Montgomery) arithmetic.
+#[doc(hidden)]
+pub const fn pow_mod(b: u64, e: u64, m: u64) -> u64 {
+    let m = m as u128;
+    let mut result = 1 % m;
+    let mut base = b as u128 % m;
+    let mut e = e;
+    while e > 0 {
+        if e & 1 == 1 {
+            result = result * base % m;
+        }
+        base = base * base % m;
+        e >>= 1;
+    }
+    result as u64
+}
+
+/// Returns true if n is a prime; this uses a deterministic variant of the Miller-Rabin test
+/// which is exact for all 64-bit integers.
+#[doc(hidden)]
+pub const fn is_prime(n: u64) -> bool {
+    const BASES: [u64; 7] = [2, 325, 9375, 28178, 450775, 9780504, 1795265022];
+
+    if n < 2 {
+        return false;
+    }
+    if n % 2 == 0 {
+        return n == 2;
+    }
+
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+
+    let mut i = 0;
+    while i < BASES.len() {
+        let a = BASES[i] % n;
+        i += 1;
+        if a == 0 {
+            continue;
+        }
+
+        let mut x = pow_mod(a, d, n);
+        if x == 1 || x == n - 1 {
+            continue;
+        }
+
+        let mut j = 1;
+        let mut composite = true;
+        while j < s {
+            x = (x as u128 * x as u128 % n as u128) as u64;
+            if x == n - 1 {
+                composite = false;
+                break;
+            }
+            j += 1;
+        }
+        if composite {
+            return false;
+        }
+    }
+    true
+}
@@ -0,0 +1,83 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{is_prime, pow_mod};
+use crate::{fields::f64, FieldElement, StarkField};
+use rand_utils::rand_vector;
+
+crate::define_prime_field! {
+    /// The same field as [f64](crate::fields::f64), defined via the macro.
+    mod goldilocks {
+        modulus: 18446744069414584321,
+        generator: 7,
+        two_adicity: 32,
+        quadratic_non_residue: 7,
+    }
+}
+
+crate::define_prime_field! {
+    /// A 31-bit field with modulus 15 * 2^27 + 1 and no extensions.
+    mod baby_bear {
+        modulus: 2013265921,
+        generator: 31,
+        two_adicity: 27,
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn macro_field_matches_f64() {
+    let a: Vec<f64::BaseElement> = rand_vector(256);
+    let b: Vec<f64::BaseElement> = rand_vector(256);
+    for (&a, &b) in a.iter().zip(b.iter()) {
+        let x = goldilocks::BaseElement::new(a.as_int());
+        let y = goldilocks::BaseElement::new(b.as_int());
+        assert_eq!((a + b).as_int(), (x + y).as_int());
+        assert_eq!((a - b).as_int(), (x - y).as_int());
+        assert_eq!((a * b).as_int(), (x * y).as_int());
+        assert_eq!(a.inv().as_int(), x.inv().as_int());
+    }
+
+    assert_eq!(
+        f64::BaseElement::TWO_ADIC_ROOT_OF_UNITY.as_int(),
+        goldilocks::BaseElement::TWO_ADIC_ROOT_OF_UNITY.as_int()
+    );
+    assert_eq!(
+        f64::BaseElement::MODULUS_BITS,
+        goldilocks::BaseElement::MODULUS_BITS
+    );
+}
+
+#[test]
+fn macro_field_without_extensions() {
+    use crate::ExtensibleField;
+    assert!(!<baby_bear::BaseElement as ExtensibleField<2>>::is_supported());
+    assert!(!<baby_bear::BaseElement as ExtensibleField<3>>::is_supported());
+    assert_eq!(31, baby_bear::BaseElement::MODULUS_BITS);
+}
+
+#[test]
+fn primality() {
+    assert!(is_prime(2));
+    assert!(is_prime(2013265921));
+    assert!(is_prime(f64::BaseElement::MODULUS));
+    assert!(is_prime(u64::MAX - 58));
+
+    assert!(!is_prime(1));
+    assert!(!is_prime(2013265923));
+    assert!(!is_prime(3215031751)); // strong pseudoprime to bases 2, 3, 5, and 7
+    assert!(!is_prime(u64::MAX));
+}
+
+#[test]
+fn modular_exponentiation() {
+    let m = f64::BaseElement::MODULUS;
+    for (b, e) in [(7, 0), (7, 1), (3, m - 1), (12345, 67890), (m - 1, m - 2)] {
+        let expected = f64::BaseElement::new(b).exp(e).as_int();
+        assert_eq!(expected, pow_mod(b, e, m));
+    }
+}
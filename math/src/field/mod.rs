@@ -12,3 +12,7 @@ pub mod f64;
 
 mod extensions;
 pub use extensions::{CubeExtension, QuadExtension};
+
+mod macros;
+#[doc(hidden)]
+pub use macros::{is_prime, mont_mul, mont_reduce, pow_mod};
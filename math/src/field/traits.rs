@@ -10,6 +10,7 @@ use core::{
         Add, AddAssign, BitAnd, Div, DivAssign, Mul, MulAssign, Neg, Shl, Shr, ShrAssign, Sub,
         SubAssign,
     },
+    slice,
 };
 use utils::{
     collections::Vec, AsBytes, Deserializable, DeserializationError, Randomizable, Serializable,
@@ -78,7 +79,17 @@ pub trait FieldElement:
     /// Number of bytes needed to encode an element
     const ELEMENT_BYTES: usize;
 
+    /// Degree of this field over `Self::BaseField`; this is 1 for prime fields.
+    ///
+    /// Since this is a constant, generic code can branch on it (e.g., to skip work which is
+    /// needed only for field extensions) without any runtime cost.
+    const EXTENSION_DEGREE: usize =
+        Self::ELEMENT_BYTES / <Self::BaseField as FieldElement>::ELEMENT_BYTES;
+
     /// True if internal representation of the element is the same as its canonical representation.
+    ///
+    /// Note that even when this is true, in-memory bytes of an element match its serialized
+    /// bytes only on little-endian targets.
     const IS_CANONICAL: bool;
 
     /// The additive identity.
@@ -97,6 +108,25 @@ pub trait FieldElement:
         self + self
     }
 
+    /// Returns this field element multiplied by three.
+    #[inline]
+    #[must_use]
+    fn triple(self) -> Self {
+        self.double() + self
+    }
+
+    /// Returns this field element multiplied by the specified small integer.
+    ///
+    /// Multiplying by small constants (e.g., round constants or coefficients of constraint
+    /// polynomials) is very common during constraint evaluation. Fields may override this method
+    /// with an implementation which is cheaper than a multiplication of two arbitrary field
+    /// elements; the default implementation converts `k` into a field element first.
+    #[inline]
+    #[must_use]
+    fn mul_small(self, k: u32) -> Self {
+        self * Self::from(k)
+    }
+
     /// Returns this field element raised to power 2.
     #[inline]
     #[must_use]
@@ -161,14 +191,17 @@ pub trait FieldElement:
     ///
     /// The elements may be in the internal representation rather than in the canonical
     /// representation. This conversion is intended to be zero-copy (i.e. by re-interpreting the
-    /// underlying memory).
+    /// underlying memory), and thus, the bytes are in the native byte order of the target. The
+    /// result must not be used as a portable encoding of elements; use
+    /// [Serializable](utils::Serializable) for that.
     fn elements_as_bytes(elements: &[Self]) -> &[u8];
 
     /// Converts a list of bytes into a list of field elements.
     ///
     /// The elements are assumed to encoded in the internal representation rather than in the
     /// canonical representation. The conversion is intended to be zero-copy (i.e. by
-    /// re-interpreting the underlying memory).
+    /// re-interpreting the underlying memory), and thus, the bytes are assumed to be in the native
+    /// byte order of the target.
     ///
     /// # Errors
     /// An error is returned if:
@@ -196,6 +229,31 @@ pub trait FieldElement:
     /// output list will contain decompositions of each extension element into underlying base
     /// elements.
     fn as_base_elements(elements: &[Self]) -> &[Self::BaseField];
+
+    /// Converts a list of elements in the underlying base field into a list of field elements.
+    ///
+    /// This is the inverse of [as_base_elements()](FieldElement::as_base_elements): for base
+    /// STARK fields, the input and output lists are the same; for extension fields, each
+    /// consecutive group of `EXTENSION_DEGREE` base elements is interpreted as a single extension
+    /// element.
+    ///
+    /// The default implementation re-interprets `elements` in place, and thus, relies on field
+    /// elements being laid out in memory in the same way as their decompositions returned by
+    /// [as_base_elements()](FieldElement::as_base_elements).
+    ///
+    /// # Panics
+    /// Panics if the length of `elements` is not divisible by `EXTENSION_DEGREE`.
+    fn slice_from_base_elements(elements: &[Self::BaseField]) -> &[Self] {
+        assert!(
+            elements.len() % Self::EXTENSION_DEGREE == 0,
+            "number of base elements must be divisible by {}, but was {}",
+            Self::EXTENSION_DEGREE,
+            elements.len()
+        );
+        let ptr = elements.as_ptr();
+        let len = elements.len() / Self::EXTENSION_DEGREE;
+        unsafe { slice::from_raw_parts(ptr as *const Self, len) }
+    }
 }
 
 // STARK FIELD
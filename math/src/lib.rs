@@ -33,6 +33,10 @@
 //!   quadratic extension of this field. For higher levels of security, a cubic extension field
 //!   should be used.
 //!
+//! New prime fields with moduli smaller than 2<sup>64</sup> can be defined using the
+//! [define_prime_field] macro. Such fields are intended mostly for experimentation as their
+//! arithmetic is not as optimized as that of the fields listed above.
+//!
 //! ## Extension fields
 //!
 //! Currently, the library provides a generic way to create quadratic and cubic extensions of
@@ -82,8 +86,12 @@
 //!   - [evaluate_poly_with_offset()](fft::evaluate_poly_with_offset())
 //!   - [interpolate_poly()](fft::interpolate_poly())
 //!   - [interpolate_poly_with_offset()][fft::interpolate_poly_with_offset()]
+//!   - [evaluate_poly_with_offset_powers()](fft::evaluate_poly_with_offset_powers())
+//!   - [interpolate_poly_with_offset_powers()](fft::interpolate_poly_with_offset_powers())
 //!   - [get_twiddles()](fft::get_twiddles())
 //!   - [get_inv_twiddles()](fft::get_twiddles())
+//!   - [get_offset_powers()](fft::get_offset_powers())
+//!   - [get_inv_offset_powers()](fft::get_inv_offset_powers())
 //!
 //! Number of threads can be configured via `RAYON_NUM_THREADS` environment variable
 
@@ -111,7 +119,16 @@ pub mod fields {
     pub use super::field::QuadExtension;
 }
 
+#[doc(hidden)]
+pub mod __private {
+    //! Re-exports used by the [define_prime_field](crate::define_prime_field) macro.
+
+    pub use super::field::{is_prime, mont_mul, mont_reduce, pow_mod};
+    pub use utils;
+}
+
 mod utils;
 pub use crate::utils::{
-    add_in_place, batch_inversion, get_power_series, get_power_series_with_offset, log2, mul_acc,
+    add_in_place, batch_exp, batch_inversion, get_power_series, get_power_series_with_offset, log2,
+    mul_acc, multi_exp,
 };
@@ -6,11 +6,14 @@
 //! Basic polynomial operations.
 //!
 //! This module provides a set of function for basic polynomial operations, including:
-//! - Polynomial evaluation using Horner method.
+//! - Polynomial evaluation using Horner method, including batched evaluation of many
+//!   polynomials at the same point.
 //! - Polynomial interpolation using Lagrange method.
 //! - Polynomial addition, subtraction, multiplication, and division.
 //! - Synthetic polynomial division for efficient division by polynomials of the form
 //!   `x`^`a` - `b`.
+//! - Power series inversion of polynomials modulo `x`^`n` using Newton iteration, and
+//!   polynomial division via such inversion.
 //!
 //! In the context of this module any slice of field elements is considered to be a polynomial
 //! in reverse coefficient form. A few examples:
@@ -24,7 +27,11 @@
 //! let p = [BaseElement::new(3), BaseElement::ZERO, BaseElement::new(4)];
 //! ```
 
-use crate::{field::FieldElement, utils::batch_inversion};
+use crate::{
+    fft,
+    field::{FieldElement, StarkField},
+    utils::{batch_inversion, log2},
+};
 use core::mem;
 use utils::{collections::Vec, group_vector_elements};
 
@@ -85,6 +92,48 @@ where
     xs.iter().map(|x| eval(p, *x)).collect()
 }
 
+/// Evaluates a set of polynomials at a single point and returns a vector of results.
+///
+/// The result is the same as evaluating each polynomial via [eval()], but polynomials are
+/// processed in groups of four. Within a group, Horner's method is applied to all polynomials
+/// simultaneously so that the multiplications for different polynomials are independent of each
+/// other; this allows the CPU to execute them in parallel (and the compiler to vectorize them).
+/// Polynomials do not need to be of the same length.
+///
+/// # Examples
+/// ```
+/// # use winter_math::polynom::*;
+/// # use winter_math::{fields::{f128::BaseElement}, FieldElement};
+/// # use rand_utils::rand_vector;
+/// let polys = (1..10).map(|n| rand_vector::<BaseElement>(n * 4)).collect::<Vec<_>>();
+/// let x = BaseElement::new(11);
+///
+/// let expected = polys.iter().map(|p| eval(p, x)).collect::<Vec<_>>();
+/// assert_eq!(expected, eval_batch(&polys, x));
+/// ```
+pub fn eval_batch<B, E, P>(polys: &[P], x: E) -> Vec<E>
+where
+    B: FieldElement,
+    E: FieldElement + From<B>,
+    P: AsRef<[B]>,
+{
+    let mut result = Vec::with_capacity(polys.len());
+    let mut groups = polys.chunks_exact(4);
+    for group in groups.by_ref() {
+        let group = [
+            group[0].as_ref(),
+            group[1].as_ref(),
+            group[2].as_ref(),
+            group[3].as_ref(),
+        ];
+        result.extend_from_slice(&eval_group(group, x));
+    }
+    for p in groups.remainder() {
+        result.push(eval(p.as_ref(), x));
+    }
+    result
+}
+
 // POLYNOMIAL INTERPOLATION
 // ================================================================================================
 
@@ -561,6 +610,119 @@ where
     }
 }
 
+/// Returns the inverse of a polynomial modulo `x`^`n`.
+///
+/// Specifically, computes polynomial `q` of degree smaller than `n` such that
+/// `p` * `q` = 1 mod `x`^`n`, that is, the first `n` terms of the power series 1 / `p`(x). The
+/// inverse is computed using [Newton iteration](https://en.wikipedia.org/wiki/Newton%27s_method):
+/// starting with `q` = 1 / `p`(0), every iteration computes `q` = `q` * (2 - `p` * `q`) which
+/// doubles the number of correct terms. Large products are computed using FFT, and thus, the
+/// complexity of this function is O(`n` log(`n`)). The length of the returned vector will be `n`.
+///
+/// # Panics
+/// Panics if:
+/// * Polynomial `p` is empty or its constant coefficient is ZERO.
+/// * The base field of `E` does not contain a multiplicative subgroup large enough to compute
+///   the required products.
+///
+/// # Examples
+/// ```
+/// # use winter_math::polynom::*;
+/// # use winter_math::{fields::{f128::BaseElement}, FieldElement};
+/// // p(x) = 1 - x
+/// let p = [BaseElement::ONE, -BaseElement::ONE];
+///
+/// // 1 / (1 - x) = 1 + x + x^2 + x^3 + ...
+/// let expected = vec![BaseElement::ONE; 4];
+/// assert_eq!(expected, inv_series(&p, 4));
+/// ```
+pub fn inv_series<E>(p: &[E], n: usize) -> Vec<E>
+where
+    E: FieldElement,
+{
+    assert!(!p.is_empty(), "cannot invert empty polynomial");
+    assert!(
+        p[0] != E::ZERO,
+        "cannot invert polynomial with zero constant coefficient"
+    );
+
+    let mut result = vec![p[0].inv()];
+    let mut num_terms = 1;
+    while num_terms < n {
+        num_terms = core::cmp::min(num_terms * 2, n);
+
+        // compute e = p * q mod x^num_terms; the first half of e is known to be 1, 0, 0, ...
+        let p_trunc = &p[..core::cmp::min(p.len(), num_terms)];
+        let mut e = mul_mod(p_trunc, &result, num_terms);
+
+        // compute q = q * (2 - e) mod x^num_terms
+        for coeff in e.iter_mut() {
+            *coeff = -*coeff;
+        }
+        e[0] += E::ONE.double();
+        result = mul_mod(&result, &e, num_terms);
+    }
+
+    result.truncate(n);
+    result
+}
+
+/// Returns a polynomial resulting from dividing one polynomial by another using power series
+/// inversion.
+///
+/// This function produces the same result as [div()], but instead of long division it reverses
+/// coefficients of both polynomials and computes the quotient as rev(`a`) / rev(`b`) modulo
+/// `x`^(deg(`a`) - deg(`b`) + 1) via [inv_series()]. Thus, the complexity of this function is
+/// O(`n` log(`n`)) rather than O(`n`^2), which makes it much faster for large divisors.
+///
+/// If polynomial `a` is empty, an empty vector is returned.
+///
+/// # Panics
+/// Panics if:
+/// * Polynomial `b` is empty.
+/// * Degree of polynomial `b` is zero and the constant coefficient is ZERO.
+/// * Polynomial `a` is not empty and the degree of polynomial `b` is greater than the degree of
+///   polynomial `a`.
+///
+/// # Examples
+/// ```
+/// # use winter_math::polynom::*;
+/// # use winter_math::{fields::{f128::BaseElement}, FieldElement};
+/// # use rand_utils::rand_vector;
+/// let a: Vec<BaseElement> = rand_vector(1024);
+/// let b: Vec<BaseElement> = rand_vector(300);
+///
+/// assert_eq!(div(&a, &b), fast_div(&a, &b));
+/// ```
+pub fn fast_div<E>(a: &[E], b: &[E]) -> Vec<E>
+where
+    E: FieldElement,
+{
+    assert!(!b.is_empty(), "cannot divide by empty polynomial");
+    let bpos = degree_of(b);
+    if bpos == 0 {
+        assert!(b[0] != E::ZERO, "cannot divide polynomial by zero");
+    }
+
+    // dividing an empty polynomial results in an empty polynomial
+    if a.is_empty() {
+        return Vec::new();
+    }
+
+    let apos = degree_of(a);
+    assert!(apos >= bpos, "cannot divide by polynomial of higher degree");
+
+    // rev(q) = rev(a) * rev(b)^-1 mod x^(deg(a) - deg(b) + 1)
+    let n = apos - bpos + 1;
+    let rev_a = a[..=apos].iter().rev().copied().collect::<Vec<_>>();
+    let rev_b = b[..=bpos].iter().rev().copied().collect::<Vec<_>>();
+    let inv_rev_b = inv_series(&rev_b, n);
+
+    let mut result = mul_mod(&rev_a[..core::cmp::min(n, rev_a.len())], &inv_rev_b, n);
+    result.reverse();
+    result
+}
+
 // DEGREE INFERENCE
 // ================================================================================================
 
@@ -635,6 +797,74 @@ where
 
 // HELPER FUNCTIONS
 // ================================================================================================
+
+/// Evaluates a group of polynomials at a single point using interleaved Horner's method.
+#[inline(always)]
+fn eval_group<B, E, const N: usize>(polys: [&[B]; N], x: E) -> [E; N]
+where
+    B: FieldElement,
+    E: FieldElement + From<B>,
+{
+    let max_len = polys.iter().map(|p| p.len()).max().unwrap_or(0);
+    let min_len = polys.iter().map(|p| p.len()).min().unwrap_or(0);
+    let mut acc = [E::ZERO; N];
+
+    // coefficients which are not present in all polynomials; missing coefficients are treated
+    // as zeros
+    for i in (min_len..max_len).rev() {
+        for (acc, p) in acc.iter_mut().zip(polys.iter()) {
+            let coeff = p.get(i).copied().unwrap_or(B::ZERO);
+            *acc = *acc * x + E::from(coeff);
+        }
+    }
+
+    // coefficients present in all polynomials
+    for i in (0..min_len).rev() {
+        for (acc, p) in acc.iter_mut().zip(polys.iter()) {
+            *acc = *acc * x + E::from(p[i]);
+        }
+    }
+
+    acc
+}
+
+/// Polynomials with fewer coefficients than this are multiplied using schoolbook multiplication
+/// rather than FFT.
+const MIN_FFT_MUL_SIZE: usize = 64;
+
+/// Returns a product of polynomials `a` and `b` modulo `x`^`n`; the length of the returned vector
+/// is always `n`.
+fn mul_mod<E: FieldElement>(a: &[E], b: &[E], n: usize) -> Vec<E> {
+    let mut result = if core::cmp::min(a.len(), b.len()) < MIN_FFT_MUL_SIZE {
+        mul(a, b)
+    } else {
+        let domain_size = (a.len() + b.len() - 1).next_power_of_two();
+        assert!(
+            log2(domain_size) <= E::BaseField::TWO_ADICITY,
+            "multiplicative subgroup of size {domain_size} does not exist in the specified base field"
+        );
+        let twiddles = fft::get_twiddles::<E::BaseField>(domain_size);
+
+        let mut a_evals = E::zeroed_vector(domain_size);
+        a_evals[..a.len()].copy_from_slice(a);
+        fft::evaluate_poly(&mut a_evals, &twiddles);
+
+        let mut b_evals = E::zeroed_vector(domain_size);
+        b_evals[..b.len()].copy_from_slice(b);
+        fft::evaluate_poly(&mut b_evals, &twiddles);
+
+        for (a, b) in a_evals.iter_mut().zip(b_evals) {
+            *a *= b;
+        }
+
+        let inv_twiddles = fft::get_inv_twiddles::<E::BaseField>(domain_size);
+        fft::interpolate_poly(&mut a_evals, &inv_twiddles);
+        a_evals
+    };
+    result.resize(n, E::ZERO);
+    result
+}
+
 fn get_zero_roots<E: FieldElement>(xs: &[E]) -> Vec<E> {
     let mut result = unsafe { utils::uninit_vector(xs.len() + 1) };
     fill_zero_roots(xs, &mut result);
@@ -8,6 +8,7 @@ use crate::{
     field::{f128::BaseElement, FieldElement, StarkField},
     utils::{get_power_series, log2},
 };
+use rand_utils::rand_vector;
 use utils::collections::Vec;
 
 #[test]
@@ -43,6 +44,28 @@ fn eval() {
     );
 }
 
+#[test]
+fn eval_batch() {
+    let x = BaseElement::from(11269864713250585702u128);
+
+    // polynomials of the same length
+    let polys = (0..9)
+        .map(|_| rand_vector(16))
+        .collect::<Vec<Vec<BaseElement>>>();
+    let expected = polys.iter().map(|p| super::eval(p, x)).collect::<Vec<_>>();
+    assert_eq!(expected, super::eval_batch(&polys, x));
+
+    // polynomials of different lengths, including empty polynomials
+    let mut polys = (1..11).map(rand_vector).collect::<Vec<Vec<BaseElement>>>();
+    polys.insert(2, Vec::new());
+    let expected = polys.iter().map(|p| super::eval(p, x)).collect::<Vec<_>>();
+    assert_eq!(expected, super::eval_batch(&polys, x));
+
+    // no polynomials
+    let polys: Vec<Vec<BaseElement>> = Vec::new();
+    assert!(super::eval_batch(&polys, x).is_empty());
+}
+
 #[test]
 fn add() {
     let poly1: [BaseElement; 3] = [
@@ -174,6 +197,60 @@ fn div() {
     );
 }
 
+#[test]
+fn inv_series() {
+    for n in [1, 2, 3, 17, 64, 200] {
+        for len in [1, 2, 5, 100] {
+            let mut p: Vec<BaseElement> = rand_vector(len);
+            if p[0] == BaseElement::ZERO {
+                p[0] = BaseElement::ONE;
+            }
+            let q = super::inv_series(&p, n);
+            assert_eq!(n, q.len());
+
+            // p * q should be 1 mod x^n
+            let mut product = super::mul(&p, &q);
+            product.truncate(n);
+            let mut expected = vec![BaseElement::ZERO; n];
+            expected[0] = BaseElement::ONE;
+            assert_eq!(expected, product, "failed for n = {n}, len = {len}");
+        }
+    }
+}
+
+#[test]
+fn fast_div() {
+    for (a_len, b_len) in [(1, 1), (10, 3), (10, 10), (300, 7), (300, 150), (1000, 999)] {
+        let a: Vec<BaseElement> = rand_vector(a_len);
+        let b: Vec<BaseElement> = rand_vector(b_len);
+        assert_eq!(super::div(&a, &b), super::fast_div(&a, &b));
+    }
+
+    // exact division, with leading zeros in both operands
+    let poly1: Vec<BaseElement> = rand_vector(150);
+    let poly2: Vec<BaseElement> = rand_vector(100);
+    let mut poly3 = super::mul(&poly1, &poly2);
+    poly3.resize(poly3.len() + 5, BaseElement::ZERO);
+    let mut poly2 = poly2;
+    poly2.push(BaseElement::ZERO);
+    assert_eq!(poly1, super::fast_div(&poly3, &poly2));
+}
+
+#[test]
+fn fast_div_empty() {
+    // dividing an empty polynomial results in an empty polynomial
+    let b: Vec<BaseElement> = rand_vector(3);
+    assert!(super::fast_div(&[], &b).is_empty());
+    assert!(super::fast_div(&[], &[BaseElement::ONE]).is_empty());
+}
+
+#[test]
+#[should_panic(expected = "cannot divide by empty polynomial")]
+fn fast_div_by_empty() {
+    let a: Vec<BaseElement> = rand_vector(3);
+    super::fast_div(&a, &[]);
+}
+
 #[test]
 fn syn_div() {
     // ----- division by degree 1 polynomial ------------------------------------------------------
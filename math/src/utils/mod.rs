@@ -187,6 +187,131 @@ where
     result
 }
 
+/// Raises a single base to each of the specified powers.
+///
+/// More precisely, for base `b` and powers `[p_0, p_1, ..., p_(n-1)]`, returns a vector with
+/// values [b^p_0, b^p_1, ..., b^p_(n-1)].
+///
+/// Powers are processed in ascending order and each result is derived from the previous one by
+/// multiplying it by `b` raised to the difference between the two powers. Thus, when powers are
+/// close to each other (e.g., degree adjustment factors of constraint groups), the total cost is
+/// close to that of a single exponentiation. Powers do not need to be sorted or distinct.
+///
+/// # Examples
+/// ```
+/// # use winter_math::batch_exp;
+/// # use winter_math::{fields::{f128::BaseElement}, FieldElement};
+/// let b = BaseElement::from(3u8);
+/// let powers = [1024_u64, 7, 1031, 7, 0];
+///
+/// let expected = powers.iter().map(|&p| b.exp(p.into())).collect::<Vec<_>>();
+/// assert_eq!(expected, batch_exp(b, &powers));
+/// ```
+pub fn batch_exp<E>(base: E, powers: &[u64]) -> Vec<E>
+where
+    E: FieldElement,
+{
+    let mut order = (0..powers.len()).collect::<Vec<_>>();
+    order.sort_unstable_by_key(|&i| powers[i]);
+
+    let mut result = E::zeroed_vector(powers.len());
+    let mut last_power = 0;
+    let mut last_value = E::ONE;
+    for i in order {
+        let power = powers[i];
+        if power != last_power {
+            last_value *= base.exp_vartime((power - last_power).into());
+            last_power = power;
+        }
+        result[i] = last_value;
+    }
+    result
+}
+
+/// Computes a product of the provided bases each raised to the corresponding power.
+///
+/// More precisely, for bases `[b_0, b_1, ..., b_(n-1)]` and powers `[p_0, p_1, ..., p_(n-1)]`,
+/// computes b_0^p_0 * b_1^p_1 * ... * b_(n-1)^p_(n-1).
+///
+/// The computation uses the bucket method of
+/// [Pippenger](https://cr.yp.to/papers/pippenger.pdf): powers are split into windows of `c`
+/// bits, and for every window the bases are sorted into buckets according to the value of the
+/// window. This requires roughly `64 / c * (n + 2^c)` multiplications, as compared to roughly
+/// `n * 64` multiplications needed to compute all exponentiations independently.
+///
+/// # Panics
+/// Panics if lengths of `bases` and `powers` slices are not the same.
+///
+/// # Examples
+/// ```
+/// # use winter_math::multi_exp;
+/// # use winter_math::{fields::{f128::BaseElement}, FieldElement};
+/// # use rand_utils::rand_vector;
+/// let bases: Vec<BaseElement> = rand_vector(100);
+/// let powers: Vec<u64> = rand_vector(100);
+///
+/// let expected = bases
+///     .iter()
+///     .zip(powers.iter())
+///     .fold(BaseElement::ONE, |acc, (&b, &p)| acc * b.exp(p.into()));
+///
+/// assert_eq!(expected, multi_exp(&bases, &powers));
+/// ```
+pub fn multi_exp<E>(bases: &[E], powers: &[u64]) -> E
+where
+    E: FieldElement,
+{
+    assert!(
+        bases.len() == powers.len(),
+        "number of bases must be the same as the number of powers"
+    );
+
+    let max_power = powers.iter().copied().max().unwrap_or(0);
+    if max_power == 0 {
+        return E::ONE;
+    }
+    let num_bits = (u64::BITS - max_power.leading_zeros()) as usize;
+
+    // pick the window size so that the cost of combining buckets is roughly in line with the
+    // cost of sorting bases into buckets
+    let window_size = match bases.len() {
+        0..=3 => 1,
+        4..=31 => 3,
+        n => (log2(n.next_power_of_two()) as usize) - 2,
+    };
+    let num_windows = (num_bits + window_size - 1) / window_size;
+    let mask = (1u64 << window_size) - 1;
+
+    let mut result = E::ONE;
+    let mut buckets = vec![E::ONE; mask as usize];
+    for window in (0..num_windows).rev() {
+        for _ in 0..window_size {
+            result = result.square();
+        }
+
+        // put each base into a bucket determined by the value of the current window of its power
+        buckets.fill(E::ONE);
+        let shift = window * window_size;
+        for (&base, &power) in bases.iter().zip(powers.iter()) {
+            let digit = ((power >> shift) & mask) as usize;
+            if digit != 0 {
+                buckets[digit - 1] *= base;
+            }
+        }
+
+        // compute product of bucket[j]^(j + 1) for all j using running products
+        let mut running = E::ONE;
+        let mut window_result = E::ONE;
+        for &bucket in buckets.iter().rev() {
+            running *= bucket;
+            window_result *= running;
+        }
+        result *= window_result;
+    }
+
+    result
+}
+
 /// Returns base 2 logarithm of `n`, where `n` is a power of two.
 ///
 /// # Panics
@@ -209,7 +334,7 @@ pub fn log2(n: usize) -> u32 {
 // ------------------------------------------------------------------------------------------------
 
 #[inline(always)]
-fn fill_power_series<E: FieldElement>(result: &mut [E], base: E, start: E) {
+pub(crate) fn fill_power_series<E: FieldElement>(result: &mut [E], base: E, start: E) {
     result[0] = start;
     for i in 1..result.len() {
         result[i] = result[i - 1] * base;
@@ -8,7 +8,7 @@ use air::{
     Air, ConstraintCompositionCoefficients, DeepCompositionCoefficients,
 };
 use core::marker::PhantomData;
-use crypto::{ElementHasher, RandomCoin};
+use crypto::{ElementHasher, RandomCoin, Transcript};
 use fri::{self, FriProof};
 use math::FieldElement;
 use utils::{collections::Vec, Serializable};
@@ -30,6 +30,7 @@ where
     context: Context,
     commitments: Commitments,
     ood_frame: OodFrame,
+    num_fri_layers: usize,
     pow_nonce: u64,
     _field_element: PhantomData<E>,
 }
@@ -47,25 +48,70 @@ where
     // --------------------------------------------------------------------------------------------
     /// Creates a new prover channel for the specified `air` and public inputs.
     pub fn new(air: &'a A, pub_inputs_bytes: Vec<u8>) -> Self {
-        let context = Context::new::<A::BaseField>(air.trace_info(), air.options().clone());
+        let pub_inputs_digest = H::hash(&pub_inputs_bytes).to_bytes();
+        let context = Context::new::<A::BaseField>(
+            air.trace_info(),
+            air.options().clone(),
+            H::ID,
+            pub_inputs_digest,
+        );
 
         // build a seed for the public coin; the initial seed is the hash of public inputs and proof
-        // context, but as the protocol progresses, the coin will be reseeded with the info sent to
-        // the verifier
+        // context (which includes a digest of public inputs), but as the protocol progresses, the
+        // coin will be reseeded with the info sent to the verifier
         let mut coin_seed = pub_inputs_bytes;
         context.write_into(&mut coin_seed);
 
         ProverChannel {
             air,
-            public_coin: RandomCoin::new(&coin_seed),
+            public_coin: RandomCoin::with_mode(&coin_seed, air.options().transcript_mode()),
             context,
             commitments: Commitments::default(),
             ood_frame: OodFrame::default(),
+            num_fri_layers: 0,
             pow_nonce: 0,
             _field_element: PhantomData,
         }
     }
 
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the context of the proof being generated.
+    pub fn context(&self) -> &Context {
+        &self.context
+    }
+
+    /// Returns all commitments the prover has committed to so far.
+    pub fn commitments(&self) -> &Commitments {
+        &self.commitments
+    }
+
+    /// Returns the out-of-domain evaluation frame sent to the verifier.
+    pub fn ood_frame(&self) -> &OodFrame {
+        &self.ood_frame
+    }
+
+    /// Returns the proof-of-work nonce determined by grinding the query seed.
+    pub fn pow_nonce(&self) -> u64 {
+        self.pow_nonce
+    }
+
+    // TRANSCRIPT
+    // --------------------------------------------------------------------------------------------
+
+    /// Starts recording all operations performed on the public coin of this channel.
+    pub fn start_transcript(&mut self) {
+        self.public_coin.start_transcript();
+    }
+
+    /// Returns the transcript of the public coin recorded since
+    /// [start_transcript()](Self::start_transcript) was called, or None if the transcript was not
+    /// being recorded.
+    pub fn take_transcript(&mut self) -> Option<Transcript> {
+        self.public_coin.take_transcript()
+    }
+
     // COMMITMENT METHODS
     // --------------------------------------------------------------------------------------------
 
@@ -121,6 +167,7 @@ where
 
     /// Returns an out-of-domain point drawn uniformly at random from the public coin.
     pub fn get_ood_point(&mut self) -> E {
+        self.public_coin.reseed_with_label(b"ood-point");
         self.public_coin.draw().expect("failed to draw OOD point")
     }
 
@@ -140,6 +187,7 @@ where
     pub fn get_query_positions(&mut self) -> Vec<usize> {
         let num_queries = self.context.options().num_queries();
         let lde_domain_size = self.context.lde_domain_size();
+        self.public_coin.reseed_with_label(b"query-seed");
         self.public_coin
             .draw_integers(num_queries, lde_domain_size)
             .expect("failed to draw query position")
@@ -151,15 +199,19 @@ where
     pub fn grind_query_seed(&mut self) {
         let grinding_factor = self.context.options().grinding_factor();
 
+        // make sure values absorbed by the coin are not merged into its seed for every nonce
+        self.public_coin.absorb_pending();
+
         #[cfg(not(feature = "concurrent"))]
         let nonce = (1..u64::MAX)
             .find(|&nonce| self.public_coin.check_leading_zeros(nonce) >= grinding_factor)
             .expect("nonce not found");
 
+        // the smallest valid nonce is selected so that proofs do not depend on thread scheduling
         #[cfg(feature = "concurrent")]
         let nonce = (1..u64::MAX)
             .into_par_iter()
-            .find_any(|&nonce| self.public_coin.check_leading_zeros(nonce) >= grinding_factor)
+            .find_first(|&nonce| self.public_coin.check_leading_zeros(nonce) >= grinding_factor)
             .expect("nonce not found");
 
         self.pow_nonce = nonce;
@@ -203,10 +255,13 @@ where
     fn commit_fri_layer(&mut self, layer_root: H::Digest) {
         self.commitments.add::<H>(&layer_root);
         self.public_coin.reseed(layer_root);
+        self.num_fri_layers += 1;
     }
 
     /// Returns a new alpha drawn from the public coin.
     fn draw_fri_alpha(&mut self) -> E {
+        let label = format!("fri-layer-{}", self.num_fri_layers - 1);
+        self.public_coin.reseed_with_label(label.as_bytes());
         self.public_coin.draw().expect("failed to draw FRI alpha")
     }
 }
@@ -17,7 +17,6 @@ pub struct DeepCompositionPoly<E: FieldElement> {
     coefficients: Vec<E>,
     cc: DeepCompositionCoefficients<E>,
     z: E,
-    field_extension: bool,
 }
 
 impl<E: FieldElement> DeepCompositionPoly<E> {
@@ -30,11 +29,17 @@ impl<E: FieldElement> DeepCompositionPoly<E> {
     where
         A: Air<BaseField = E::BaseField>,
     {
+        // whether a field extension is used is implied by the type of E; this way, all branches
+        // specific to field extensions are eliminated at compile time when E is the base field
+        debug_assert_eq!(
+            air.options().field_extension().degree() as usize,
+            E::EXTENSION_DEGREE,
+            "field extension of proof options does not match the extension degree of E"
+        );
         DeepCompositionPoly {
             coefficients: vec![],
             cc,
             z,
-            field_extension: !air.options().field_extension().is_none(),
         }
     }
 
@@ -85,7 +90,7 @@ impl<E: FieldElement> DeepCompositionPoly<E> {
         // we are using a field extension, also T'''(x)
         let mut t1_composition = E::zeroed_vector(trace_length);
         let mut t2_composition = E::zeroed_vector(trace_length);
-        let mut t3_composition = if self.field_extension {
+        let mut t3_composition = if E::EXTENSION_DEGREE > 1 {
             E::zeroed_vector(trace_length)
         } else {
             Vec::new()
@@ -117,7 +122,7 @@ impl<E: FieldElement> DeepCompositionPoly<E> {
 
             // when extension field is enabled, compute T'''(x) = T(x) - T(z_conjugate), multiply
             // it by a pseudo-random coefficient, and add the result into composition polynomial
-            if self.field_extension {
+            if E::EXTENSION_DEGREE > 1 {
                 acc_trace_poly::<E::BaseField, E>(
                     &mut t3_composition,
                     poly,
@@ -204,9 +209,16 @@ impl<E: FieldElement> DeepCompositionPoly<E> {
             });
 
         // add H'_i(x) * cc_i for all i into the DEEP composition polynomial
-        for (i, poly) in column_polys.into_iter().enumerate() {
-            mul_acc::<E, E>(&mut self.coefficients, &poly, self.cc.constraints[i]);
+        for (i, poly) in column_polys.iter().enumerate() {
+            mul_acc::<E, E>(&mut self.coefficients, poly, self.cc.constraints[i]);
         }
+
+        // the columns were moved out of the composition polynomial matrix, and thus, they are
+        // not zeroized when the matrix is dropped
+        #[cfg(feature = "secret-hygiene")]
+        column_polys
+            .iter_mut()
+            .for_each(|poly| utils::zeroize(poly));
         assert_eq!(self.poly_size() - 2, self.degree());
     }
 
@@ -241,11 +253,10 @@ impl<E: FieldElement> DeepCompositionPoly<E> {
     // --------------------------------------------------------------------------------------------
     /// Evaluates DEEP composition polynomial over the specified LDE domain and returns the result.
     pub fn evaluate(self, domain: &StarkDomain<E::BaseField>) -> Vec<E> {
-        fft::evaluate_poly_with_offset(
+        fft::evaluate_poly_with_offset_powers(
             &self.coefficients,
             domain.trace_twiddles(),
-            domain.offset(),
-            domain.trace_to_lde_blowup(),
+            domain.trace_offset_powers(),
         )
     }
 }
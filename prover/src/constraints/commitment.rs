@@ -5,8 +5,9 @@
 
 use super::Matrix;
 use air::proof::Queries;
-use crypto::{ElementHasher, MerkleTree};
-use math::FieldElement;
+use core::marker::PhantomData;
+use crypto::{BatchMerkleProof, ElementHasher, MerkleTree, VectorCommitment};
+use math::{log2, FieldElement};
 use utils::collections::Vec;
 
 // CONSTRAINT COMMITMENT
@@ -16,46 +17,59 @@ use utils::collections::Vec;
 ///
 /// The commitment consists of two components:
 /// * Evaluations of composition polynomial columns over the LDE domain.
-/// * Merkle tree where each leaf in the tree corresponds to a row in the composition polynomial
-///   evaluation matrix.
-pub struct ConstraintCommitment<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> {
+/// * Vector commitment (a Merkle tree by default) where each committed item corresponds to a row
+///   in the composition polynomial evaluation matrix. Openings of the commitment must be batch
+///   Merkle proofs, and thus, only Merkle-based vector commitments are supported.
+pub struct ConstraintCommitment<E, H, V = MerkleTree<H>>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    V: VectorCommitment<H, MultiProof = BatchMerkleProof<H>>,
+{
     evaluations: Matrix<E>,
-    commitment: MerkleTree<H>,
+    commitment: V,
+    _hasher: PhantomData<H>,
 }
 
-impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> ConstraintCommitment<E, H> {
+impl<E, H, V> ConstraintCommitment<E, H, V>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    V: VectorCommitment<H, MultiProof = BatchMerkleProof<H>>,
+{
     /// Creates a new constraint evaluation commitment from the provided composition polynomial
-    /// evaluations and the corresponding Merkle tree commitment.
-    pub fn new(evaluations: Matrix<E>, commitment: MerkleTree<H>) -> ConstraintCommitment<E, H> {
+    /// evaluations and the corresponding vector commitment.
+    pub fn new(evaluations: Matrix<E>, commitment: V) -> Self {
         assert_eq!(
             evaluations.num_rows(),
-            commitment.leaves().len(),
+            commitment.domain_len(),
             "number of rows in constraint evaluation matrix must be the same as number of leaves in constraint commitment"
         );
         ConstraintCommitment {
             evaluations,
             commitment,
+            _hasher: PhantomData,
         }
     }
 
     /// Returns the root of the commitment Merkle tree.
     pub fn root(&self) -> H::Digest {
-        *self.commitment.root()
+        self.commitment.commitment()
     }
 
     /// Returns the depth of the commitment Merkle tree.
     #[allow(unused)]
     pub fn tree_depth(&self) -> usize {
-        self.commitment.depth()
+        log2(self.commitment.domain_len()) as usize
     }
 
     /// Returns constraint evaluations at the specified positions along with Merkle authentication
     /// paths from the root of the commitment to these evaluations.
     pub fn query(self, positions: &[usize]) -> Queries {
         // build Merkle authentication paths to the leaves specified by positions
-        let merkle_proof = self
+        let (_, merkle_proof) = self
             .commitment
-            .prove_batch(positions)
+            .open_many(positions)
             .expect("failed to generate a Merkle proof for constraint queries");
 
         // determine a set of evaluations corresponding to each position
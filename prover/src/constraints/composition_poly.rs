@@ -5,7 +5,7 @@
 
 use super::{Matrix, StarkDomain};
 use math::{polynom, FieldElement, StarkField};
-use utils::{collections::Vec, uninit_vector};
+use utils::{collections::Vec, UninitBuffer};
 
 // COMPOSITION POLYNOMIAL
 // ================================================================================================
@@ -67,6 +67,11 @@ impl<E: FieldElement> CompositionPoly<E> {
         self.column_len() - 1
     }
 
+    /// Returns the coefficients of the individual column polynomials.
+    pub fn data(&self) -> &Matrix<E> {
+        &self.data
+    }
+
     // LOW-DEGREE EXTENSION
     // --------------------------------------------------------------------------------------------
     /// Evaluates the columns of the composition polynomial over the specified LDE domain and
@@ -110,20 +115,18 @@ impl<E: FieldElement> CompositionPoly<E> {
 fn transpose<E: FieldElement>(coefficients: Vec<E>, num_columns: usize) -> Vec<Vec<E>> {
     let column_len = coefficients.len() / num_columns;
 
-    let mut result = unsafe {
-        (0..num_columns)
-            .map(|_| uninit_vector(column_len))
-            .collect::<Vec<_>>()
-    };
-
     // TODO: implement multi-threaded version
-    for (i, coeff) in coefficients.into_iter().enumerate() {
-        let row_idx = i / num_columns;
-        let col_idx = i % num_columns;
-        result[col_idx][row_idx] = coeff;
-    }
-
-    result
+    (0..num_columns)
+        .map(|col_idx| {
+            UninitBuffer::new(column_len).fill(
+                coefficients
+                    .iter()
+                    .skip(col_idx)
+                    .step_by(num_columns)
+                    .copied(),
+            )
+        })
+        .collect()
 }
 
 // TESTS
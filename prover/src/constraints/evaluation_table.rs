@@ -4,11 +4,18 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{CompositionPoly, ConstraintDivisor, ProverError, StarkDomain};
+use core::mem::MaybeUninit;
 use math::{batch_inversion, fft, FieldElement, StarkField};
-use utils::{batch_iter_mut, collections::Vec, iter_mut, uninit_vector};
+use utils::{batch_iter_mut, collections::Vec, iter_mut, UninitBuffer};
 
-#[cfg(debug_assertions)]
+#[cfg(any(debug_assertions, feature = "validate-degrees"))]
 use air::TransitionConstraints;
+#[cfg(any(debug_assertions, feature = "validate-degrees"))]
+use crypto::{hashers::Blake3_256, Digest, ElementHasher, RandomCoin};
+#[cfg(any(debug_assertions, feature = "validate-degrees"))]
+use math::fft::DegreeCheck;
+#[cfg(any(debug_assertions, feature = "validate-degrees"))]
+use utils::collections::BTreeMap;
 
 #[cfg(feature = "concurrent")]
 use utils::iterators::*;
@@ -16,7 +23,7 @@ use utils::iterators::*;
 // CONSTANTS
 // ================================================================================================
 
-const MIN_FRAGMENT_SIZE: usize = 16;
+pub const MIN_FRAGMENT_SIZE: usize = 16;
 
 // CONSTRAINT EVALUATION TABLE
 // ================================================================================================
@@ -26,11 +33,11 @@ pub struct ConstraintEvaluationTable<'a, E: FieldElement> {
     divisors: Vec<ConstraintDivisor<E::BaseField>>,
     domain: &'a StarkDomain<E::BaseField>,
 
-    #[cfg(debug_assertions)]
+    #[cfg(any(debug_assertions, feature = "validate-degrees"))]
     main_transition_evaluations: Vec<Vec<E::BaseField>>,
-    #[cfg(debug_assertions)]
+    #[cfg(any(debug_assertions, feature = "validate-degrees"))]
     aux_transition_evaluations: Vec<Vec<E>>,
-    #[cfg(debug_assertions)]
+    #[cfg(any(debug_assertions, feature = "validate-degrees"))]
     expected_transition_degrees: Vec<usize>,
 }
 
@@ -39,15 +46,44 @@ impl<'a, E: FieldElement> ConstraintEvaluationTable<'a, E> {
     // --------------------------------------------------------------------------------------------
     /// Returns a new constraint evaluation table with number of columns equal to the number of
     /// specified divisors, and number of rows equal to the size of constraint evaluation domain.
-    #[cfg(not(debug_assertions))]
-    pub fn new(
+    ///
+    /// Memory for the table is allocated without being initialized. The table is broken into
+    /// `num_fragments` fragments, and every fragment is filled by `evaluate`; fragments can be
+    /// filled independently, and when `concurrent` feature is enabled, they are filled in
+    /// multiple threads.
+    ///
+    /// # Panics
+    /// Panics if `evaluate` does not update all rows of a fragment.
+    #[cfg(not(any(debug_assertions, feature = "validate-degrees")))]
+    pub fn new<F>(
         domain: &'a StarkDomain<E::BaseField>,
         divisors: Vec<ConstraintDivisor<E::BaseField>>,
-    ) -> Self {
+        num_fragments: usize,
+        evaluate: F,
+    ) -> Self
+    where
+        F: Fn(&mut EvaluationTableFragment<E>) + Send + Sync,
+    {
         let num_columns = divisors.len();
         let num_rows = domain.ce_domain_size();
+        let fragment_size = get_fragment_size(num_rows, num_fragments);
+
+        let mut evaluations = uninit_matrix(num_columns, num_rows);
+        let mut fragments = make_fragments(&mut evaluations, num_fragments)
+            .into_iter()
+            .enumerate()
+            .map(|(i, evaluations)| EvaluationTableFragment {
+                offset: i * fragment_size,
+                num_updated_rows: 0,
+                evaluations,
+            })
+            .collect::<Vec<_>>();
+        fill_fragments(&mut fragments, evaluate);
+
         ConstraintEvaluationTable {
-            evaluations: uninit_matrix(num_columns, num_rows),
+            // SAFETY: fragments cover all rows of the table, and fill_fragments() made sure that
+            // all rows of every fragment have been updated
+            evaluations: unsafe { assume_init_matrix(evaluations) },
             divisors,
             domain,
         }
@@ -56,29 +92,61 @@ impl<'a, E: FieldElement> ConstraintEvaluationTable<'a, E> {
     /// Similar to the as above constructor but used in debug mode. In debug mode we also want
     /// to keep track of all evaluated transition constraints so that we can verify that their
     /// expected degrees match their actual degrees.
-    #[cfg(debug_assertions)]
-    pub fn new(
+    #[cfg(any(debug_assertions, feature = "validate-degrees"))]
+    pub fn new<F>(
         domain: &'a StarkDomain<E::BaseField>,
         divisors: Vec<ConstraintDivisor<E::BaseField>>,
         transition_constraints: &TransitionConstraints<E>,
-    ) -> Self {
+        num_fragments: usize,
+        evaluate: F,
+    ) -> Self
+    where
+        F: Fn(&mut EvaluationTableFragment<E>) + Send + Sync,
+    {
         let num_columns = divisors.len();
         let num_rows = domain.ce_domain_size();
         let num_tm_columns = transition_constraints.num_main_constraints();
         let num_ta_columns = transition_constraints.num_aux_constraints();
+        let fragment_size = get_fragment_size(num_rows, num_fragments);
 
         // collect expected degrees for all transition constraints to compare them against actual
         // degrees; we do this in debug mode only because this comparison is expensive
         let expected_transition_degrees =
             build_transition_constraint_degrees(transition_constraints, domain.trace_length());
 
-        ConstraintEvaluationTable {
-            evaluations: uninit_matrix(num_columns, num_rows),
-            divisors,
-            domain,
-            main_transition_evaluations: uninit_matrix(num_tm_columns, num_rows),
-            aux_transition_evaluations: uninit_matrix(num_ta_columns, num_rows),
-            expected_transition_degrees,
+        // in debug mode, also break individual transition evaluations into fragments
+        let mut evaluations = uninit_matrix(num_columns, num_rows);
+        let mut tm_evaluations = uninit_matrix(num_tm_columns, num_rows);
+        let mut ta_evaluations = uninit_matrix(num_ta_columns, num_rows);
+        let mut fragments = make_fragments(&mut evaluations, num_fragments)
+            .into_iter()
+            .zip(make_fragments(&mut tm_evaluations, num_fragments))
+            .zip(make_fragments(&mut ta_evaluations, num_fragments))
+            .enumerate()
+            .map(
+                |(i, ((evaluations, tm_evaluations), ta_evaluations))| EvaluationTableFragment {
+                    offset: i * fragment_size,
+                    num_updated_rows: 0,
+                    evaluations,
+                    num_updated_transition_rows: 0,
+                    tm_evaluations,
+                    ta_evaluations,
+                },
+            )
+            .collect::<Vec<_>>();
+        fill_fragments(&mut fragments, evaluate);
+
+        // SAFETY: fragments cover all rows of the table, and fill_fragments() made sure that all
+        // rows of every fragment have been updated
+        unsafe {
+            ConstraintEvaluationTable {
+                evaluations: assume_init_matrix(evaluations),
+                divisors,
+                domain,
+                main_transition_evaluations: assume_init_matrix(tm_evaluations),
+                aux_transition_evaluations: assume_init_matrix(ta_evaluations),
+                expected_transition_degrees,
+            }
         }
     }
 
@@ -99,56 +167,26 @@ impl<'a, E: FieldElement> ConstraintEvaluationTable<'a, E> {
         self.evaluations.len()
     }
 
-    // TABLE FRAGMENTS
-    // --------------------------------------------------------------------------------------------
-
-    /// Break the table into the number of specified fragments. All fragments can be updated
-    /// independently - e.g. in different threads.
-    pub fn fragments(&mut self, num_fragments: usize) -> Vec<EvaluationTableFragment<E>> {
-        let fragment_size = self.num_rows() / num_fragments;
-        assert!(
-            fragment_size >= MIN_FRAGMENT_SIZE,
-            "fragment size must be at least {MIN_FRAGMENT_SIZE}, but was {fragment_size}"
-        );
-
-        // break evaluations into fragments
-        let evaluation_data = make_fragments(&mut self.evaluations, num_fragments);
-
-        #[cfg(debug_assertions)]
-        let result = {
-            // in debug mode, also break individual transition evaluations into fragments
-            let tm_evaluation_data =
-                make_fragments(&mut self.main_transition_evaluations, num_fragments);
-            let ta_evaluation_data =
-                make_fragments(&mut self.aux_transition_evaluations, num_fragments);
+    /// Returns columns of this table; evaluations in the columns have not been divided by
+    /// constraint divisors yet.
+    pub fn columns(&self) -> &[Vec<E>] {
+        &self.evaluations
+    }
 
-            evaluation_data
-                .into_iter()
-                .zip(tm_evaluation_data)
-                .zip(ta_evaluation_data)
-                .enumerate()
-                .map(|(i, ((evaluations, tm_evaluations), ta_evaluations))| {
-                    EvaluationTableFragment {
-                        offset: i * fragment_size,
-                        evaluations,
-                        tm_evaluations,
-                        ta_evaluations,
-                    }
-                })
-                .collect()
-        };
-
-        #[cfg(not(debug_assertions))]
-        let result = evaluation_data
-            .into_iter()
-            .enumerate()
-            .map(|(i, evaluations)| EvaluationTableFragment {
-                offset: i * fragment_size,
-                evaluations,
+    /// Returns columns of this table divided by their respective divisors.
+    ///
+    /// This duplicates the division performed by [into_poly()](Self::into_poly), and thus, should
+    /// be used for debugging purposes only.
+    pub fn divided_columns(&self) -> Vec<Vec<E>> {
+        self.evaluations
+            .iter()
+            .zip(self.divisors.iter())
+            .map(|(column, divisor)| {
+                let mut result = E::zeroed_vector(column.len());
+                acc_column(column.clone(), divisor, self.domain, &mut result);
+                result
             })
-            .collect();
-
-        result
+            .collect()
     }
 
     // CONSTRAINT COMPOSITION
@@ -166,7 +204,7 @@ impl<'a, E: FieldElement> ConstraintEvaluationTable<'a, E> {
         for (column, divisor) in self.evaluations.into_iter().zip(self.divisors.iter()) {
             // in debug mode, make sure post-division degree of each column matches the expected
             // degree
-            #[cfg(debug_assertions)]
+            #[cfg(any(debug_assertions, feature = "validate-degrees"))]
             validate_column_degree(&column, divisor, self.domain, column.len() - 1)?;
 
             // divide the column by the divisor and accumulate the result into combined_poly
@@ -185,46 +223,78 @@ impl<'a, E: FieldElement> ConstraintEvaluationTable<'a, E> {
     // DEBUG HELPERS
     // --------------------------------------------------------------------------------------------
 
-    #[cfg(debug_assertions)]
-    pub fn validate_transition_degrees(&mut self) {
+    /// Makes sure that the degrees of all transition constraints match their expected degrees.
+    ///
+    /// The degrees are first checked using a probabilistic degree check which does not require
+    /// interpolating transition constraint evaluations. Only if this check fails, the evaluations
+    /// are interpolated to determine the actual degrees for the error message.
+    #[cfg(any(debug_assertions, feature = "validate-degrees"))]
+    pub fn validate_transition_degrees(&self) {
         // evaluate transition constraint divisor (which is assumed to be the first one in the
-        // divisor list) over the constraint evaluation domain. this is used later to compute
-        // actual degrees of transition constraint evaluations.
-        let div_values = evaluate_divisor::<E::BaseField>(
+        // divisor list) over the constraint evaluation domain. this is used later to divide out
+        // the divisor from transition constraint evaluations.
+        let inv_div_values = batch_inversion(&evaluate_divisor::<E::BaseField>(
             &self.divisors[0],
             self.num_rows(),
             self.domain.offset(),
-        );
+        ));
 
-        // collect actual degrees for all transition constraints by interpolating saved
-        // constraint evaluations into polynomials and checking their degree; also
-        // determine max transition constraint degree
-        let mut actual_degrees = Vec::with_capacity(self.expected_transition_degrees.len());
-        let mut max_degree = 0;
-        let inv_twiddles = fft::get_inv_twiddles::<E::BaseField>(self.num_rows());
-
-        // first process transition constraint evaluations for the main trace segment
-        for evaluations in self.main_transition_evaluations.iter() {
-            let degree = get_transition_poly_degree(evaluations, &inv_twiddles, &div_values);
-            actual_degrees.push(degree);
-            max_degree = core::cmp::max(max_degree, degree);
+        // divide out the divisor from all transition constraint evaluations; this way, the
+        // degree of the resulting polynomials will be smaller than the size of the domain
+        let main_quotients = self
+            .main_transition_evaluations
+            .iter()
+            .map(|evaluations| divide_by_divisor(evaluations, &inv_div_values))
+            .collect::<Vec<_>>();
+        let aux_quotients = self
+            .aux_transition_evaluations
+            .iter()
+            .map(|evaluations| divide_by_divisor(evaluations, &inv_div_values))
+            .collect::<Vec<_>>();
+
+        // check the degree of every quotient probabilistically; degree checks are cached by
+        // degree since many constraints usually share the same degree
+        let r = draw_degree_check_point::<E>(&main_quotients, &aux_quotients);
+        let mut checks = BTreeMap::new();
+        let mut degrees_match = true;
+        for (i, &expected_degree) in self.expected_transition_degrees.iter().enumerate() {
+            let check = checks.entry(expected_degree).or_insert_with(|| {
+                DegreeCheck::new(self.num_rows(), self.domain.offset(), expected_degree, r)
+            });
+            degrees_match &= if i < main_quotients.len() {
+                check.is_satisfied_by(&main_quotients[i])
+            } else {
+                check.is_satisfied_by(&aux_quotients[i - main_quotients.len()])
+            };
         }
 
-        // then process transition constraint evaluations for auxiliary trace segments
-        for evaluations in self.aux_transition_evaluations.iter() {
-            let degree = get_transition_poly_degree(evaluations, &inv_twiddles, &div_values);
-            actual_degrees.push(degree);
-            max_degree = core::cmp::max(max_degree, degree);
+        // if some of the degrees didn't match, interpolate all quotients to get their actual
+        // degrees and report them
+        if !degrees_match {
+            let inv_twiddles = fft::get_inv_twiddles::<E::BaseField>(self.num_rows());
+            let mut actual_degrees = main_quotients
+                .into_iter()
+                .map(|quotient| get_poly_degree(quotient, &inv_twiddles))
+                .collect::<Vec<_>>();
+            actual_degrees.extend(
+                aux_quotients
+                    .into_iter()
+                    .map(|quotient| get_poly_degree(quotient, &inv_twiddles)),
+            );
+
+            panic!(
+                "transition constraint degrees didn't match\nexpected: {:>3?}\nactual:   {:>3?}",
+                self.expected_transition_degrees, actual_degrees
+            );
         }
 
-        // make sure expected and actual degrees are equal
-        assert_eq!(
-            self.expected_transition_degrees, actual_degrees,
-            "transition constraint degrees didn't match\nexpected: {:>3?}\nactual:   {:>3?}",
-            self.expected_transition_degrees, actual_degrees
-        );
-
         // make sure evaluation domain size does not exceed the size required by max degree
+        let max_degree = self
+            .expected_transition_degrees
+            .iter()
+            .copied()
+            .max()
+            .unwrap_or(0);
         let expected_domain_size =
             core::cmp::max(max_degree, self.domain.trace_length() + 1).next_power_of_two();
         assert_eq!(
@@ -242,12 +312,15 @@ impl<'a, E: FieldElement> ConstraintEvaluationTable<'a, E> {
 
 pub struct EvaluationTableFragment<'a, E: FieldElement> {
     offset: usize,
-    evaluations: Vec<&'a mut [E]>,
-
-    #[cfg(debug_assertions)]
-    tm_evaluations: Vec<&'a mut [E::BaseField]>,
-    #[cfg(debug_assertions)]
-    ta_evaluations: Vec<&'a mut [E]>,
+    num_updated_rows: usize,
+    evaluations: Vec<&'a mut [MaybeUninit<E>]>,
+
+    #[cfg(any(debug_assertions, feature = "validate-degrees"))]
+    num_updated_transition_rows: usize,
+    #[cfg(any(debug_assertions, feature = "validate-degrees"))]
+    tm_evaluations: Vec<&'a mut [MaybeUninit<E::BaseField>]>,
+    #[cfg(any(debug_assertions, feature = "validate-degrees"))]
+    ta_evaluations: Vec<&'a mut [MaybeUninit<E>]>,
 }
 
 impl<'a, E: FieldElement> EvaluationTableFragment<'a, E> {
@@ -267,26 +340,75 @@ impl<'a, E: FieldElement> EvaluationTableFragment<'a, E> {
     }
 
     /// Updates a single row in the fragment with provided data.
+    ///
+    /// # Panics
+    /// Panics if rows are not updated in order, or if `row_data` does not contain a value for
+    /// every column of the fragment.
     pub fn update_row(&mut self, row_idx: usize, row_data: &[E]) {
+        assert_eq!(
+            self.num_updated_rows, row_idx,
+            "rows of a fragment must be updated in order"
+        );
+        assert_eq!(
+            self.num_columns(),
+            row_data.len(),
+            "expected {} values in a row, but received {}",
+            self.num_columns(),
+            row_data.len()
+        );
         for (column, &value) in self.evaluations.iter_mut().zip(row_data) {
-            column[row_idx] = value;
+            column[row_idx].write(value);
         }
+        self.num_updated_rows += 1;
     }
 
     /// Updates transition evaluations row with the provided data; available only in debug mode.
-    #[cfg(debug_assertions)]
+    ///
+    /// # Panics
+    /// Panics if rows are not updated in order, or if the provided evaluations do not contain a
+    /// value for every transition constraint.
+    #[cfg(any(debug_assertions, feature = "validate-degrees"))]
     pub fn update_transition_evaluations(
         &mut self,
         row_idx: usize,
         main_evaluations: &[E::BaseField],
         aux_evaluations: &[E],
     ) {
+        assert_eq!(
+            self.num_updated_transition_rows, row_idx,
+            "rows of a fragment must be updated in order"
+        );
+        assert_eq!(self.tm_evaluations.len(), main_evaluations.len());
+        assert_eq!(self.ta_evaluations.len(), aux_evaluations.len());
         for (column, &value) in self.tm_evaluations.iter_mut().zip(main_evaluations) {
-            column[row_idx] = value;
+            column[row_idx].write(value);
         }
         for (column, &value) in self.ta_evaluations.iter_mut().zip(aux_evaluations) {
-            column[row_idx] = value;
+            column[row_idx].write(value);
         }
+        self.num_updated_transition_rows += 1;
+    }
+
+    /// Makes sure that all rows of this fragment have been updated.
+    fn assert_filled(&self) {
+        assert_eq!(
+            self.num_rows(),
+            self.num_updated_rows,
+            "expected all {} rows of the fragment at offset {} to be updated, but only {} were",
+            self.num_rows(),
+            self.offset,
+            self.num_updated_rows
+        );
+        #[cfg(any(debug_assertions, feature = "validate-degrees"))]
+        assert_eq!(
+            self.num_rows(),
+            self.num_updated_transition_rows,
+            "expected transition evaluations for all {} rows of the fragment at offset {} to be \
+            updated, but only {} were",
+            self.num_rows(),
+            self.offset,
+            self.num_updated_transition_rows
+        );
     }
 }
 
@@ -294,8 +416,43 @@ impl<'a, E: FieldElement> EvaluationTableFragment<'a, E> {
 // ================================================================================================
 
 /// Allocates memory for a two-dimensional data structure without initializing it.
-fn uninit_matrix<E: FieldElement>(num_cols: usize, num_rows: usize) -> Vec<Vec<E>> {
-    unsafe { (0..num_cols).map(|_| uninit_vector(num_rows)).collect() }
+fn uninit_matrix<T>(num_cols: usize, num_rows: usize) -> Vec<UninitBuffer<T>> {
+    (0..num_cols).map(|_| UninitBuffer::new(num_rows)).collect()
+}
+
+/// Converts columns of a two-dimensional data structure into vectors of initialized elements.
+///
+/// # Safety
+/// All elements of all columns must have been initialized.
+unsafe fn assume_init_matrix<T>(columns: Vec<UninitBuffer<T>>) -> Vec<Vec<T>> {
+    columns
+        .into_iter()
+        .map(|column| column.assume_init())
+        .collect()
+}
+
+/// Returns the number of rows in each fragment when a table with the specified number of rows is
+/// broken into `num_fragments` fragments.
+fn get_fragment_size(num_rows: usize, num_fragments: usize) -> usize {
+    let fragment_size = num_rows / num_fragments;
+    assert!(
+        fragment_size >= MIN_FRAGMENT_SIZE,
+        "fragment size must be at least {MIN_FRAGMENT_SIZE}, but was {fragment_size}"
+    );
+    fragment_size
+}
+
+/// Fills all fragments using the `evaluate` function and makes sure that every row of every
+/// fragment has been updated.
+fn fill_fragments<E, F>(fragments: &mut [EvaluationTableFragment<E>], evaluate: F)
+where
+    E: FieldElement,
+    F: Fn(&mut EvaluationTableFragment<E>) + Send + Sync,
+{
+    iter_mut!(fragments).for_each(|fragment| {
+        evaluate(fragment);
+        fragment.assert_filled();
+    });
 }
 
 /// Breaks the source data into a mutable set of fragments such that each fragment has the same
@@ -304,14 +461,15 @@ fn uninit_matrix<E: FieldElement>(num_cols: usize, num_rows: usize) -> Vec<Vec<E
 ///
 /// If the source data is empty, the returned vector will contain number of empty vectors equal
 /// to `num_fragments` parameter.
-fn make_fragments<E: FieldElement>(
-    source: &mut [Vec<E>],
+fn make_fragments<T>(
+    source: &mut [UninitBuffer<T>],
     num_fragments: usize,
-) -> Vec<Vec<&mut [E]>> {
+) -> Vec<Vec<&mut [MaybeUninit<T>]>> {
     let mut result = (0..num_fragments).map(|_| Vec::new()).collect::<Vec<_>>();
     if !source.is_empty() {
         let fragment_size = source[0].len() / num_fragments;
         source.iter_mut().for_each(|column| {
+            let column = column.as_uninit_slice_mut();
             for (i, fragment) in column.chunks_mut(fragment_size).enumerate() {
                 result[i].push(fragment);
             }
@@ -396,16 +554,9 @@ fn get_inv_evaluation<B: StarkField>(
     let domain_offset_exp = domain.offset().exp(a.into());
 
     // compute x^a - b for all x
-    let mut evaluations = unsafe { uninit_vector(n) };
-    batch_iter_mut!(
-        &mut evaluations,
+    let evaluations = UninitBuffer::new(n).fill_batches(
         128, // min batch size
-        |batch: &mut [B], batch_offset: usize| {
-            for (i, evaluation) in batch.iter_mut().enumerate() {
-                let x = domain.get_ce_x_power_at(batch_offset + i, a, domain_offset_exp);
-                *evaluation = x - b;
-            }
-        }
+        |batch| batch.map(|step| domain.get_ce_x_power_at(step, a, domain_offset_exp) - b),
     );
 
     // compute 1 / (x^a - b)
@@ -424,7 +575,7 @@ fn get_inv_evaluation<B: StarkField>(
 ///
 /// The general idea is that evaluation degree is the degree of rational function `C(x) / z(x)`,
 /// where `C(x)` is the constraint polynomial and `z(x)` is the divisor polynomial.
-#[cfg(debug_assertions)]
+#[cfg(any(debug_assertions, feature = "validate-degrees"))]
 fn build_transition_constraint_degrees<E: FieldElement>(
     constraints: &TransitionConstraints<E>,
     trace_length: usize,
@@ -442,34 +593,64 @@ fn build_transition_constraint_degrees<E: FieldElement>(
     result
 }
 
-/// Computes the actual degree of a transition polynomial described by the provided evaluations.
+/// Divides transition constraint evaluations by the evaluations of transition constraint divisor.
+///
+/// This is needed because it is possible for the numerator portions of transition constraints
+/// to have a degree which is larger than the size of the evaluation domain (and thus,
+/// interpolating the numerator would yield an incorrect result). However, once the divisor values
+/// are divided out, the degree of the resulting polynomial should be smaller than the size of the
+/// evaluation domain.
 ///
-/// The degree is computed as follows:
-/// - First, we divide the polynomial evaluations by the evaluations of transition constraint
-///   divisor (`div_values`). This is needed because it is possible for the numerator portions of
-///   transition constraints to have a degree which is larger than the size of the evaluation
-///   domain (and thus, interpolating the numerator would yield an incorrect result). However,
-///   once the divisor values are divided out, the degree of the resulting polynomial should be
-///   smaller than the size of the evaluation domain, and thus, we can interpolate safely.
-/// - Then, we interpolate the polynomial over the domain specified by `inv_twiddles`.
-/// - And finally, we get the degree from the interpolated polynomial.
-#[cfg(debug_assertions)]
-fn get_transition_poly_degree<E: FieldElement>(
+/// `inv_div_values` must contain inverses of the divisor evaluations; this way, every division
+/// is replaced with a single multiplication by a base field element.
+#[cfg(any(debug_assertions, feature = "validate-degrees"))]
+fn divide_by_divisor<E: FieldElement>(
     evaluations: &[E],
+    inv_div_values: &[E::BaseField],
+) -> Vec<E> {
+    evaluations
+        .iter()
+        .zip(inv_div_values)
+        .map(|(&c, &d)| c.mul_base(d))
+        .collect()
+}
+
+/// Computes the actual degree of a polynomial described by the provided evaluations by
+/// interpolating it over the domain specified by `inv_twiddles`.
+#[cfg(any(debug_assertions, feature = "validate-degrees"))]
+fn get_poly_degree<E: FieldElement>(
+    mut evaluations: Vec<E>,
     inv_twiddles: &[E::BaseField],
-    div_values: &[E::BaseField],
 ) -> usize {
-    let mut evaluations = evaluations
-        .iter()
-        .zip(div_values)
-        .map(|(&c, &d)| c / E::from(d))
-        .collect::<Vec<_>>();
     fft::interpolate_poly(&mut evaluations, inv_twiddles);
     math::polynom::degree_of(&evaluations)
 }
 
+/// Draws a point for probabilistic degree checks of the specified polynomials.
+///
+/// The point is derived from a hash of polynomial evaluations so that it is independent of
+/// the polynomials for all practical purposes.
+#[cfg(any(debug_assertions, feature = "validate-degrees"))]
+fn draw_degree_check_point<E: FieldElement>(
+    main_evaluations: &[Vec<E::BaseField>],
+    aux_evaluations: &[Vec<E>],
+) -> E::BaseField {
+    let mut seed = Vec::new();
+    for evaluations in main_evaluations {
+        seed.extend_from_slice(&Blake3_256::hash_elements(evaluations).as_bytes());
+    }
+    for evaluations in aux_evaluations {
+        seed.extend_from_slice(&Blake3_256::hash_elements(evaluations).as_bytes());
+    }
+
+    // the probability of drawing a point in the domain is negligible; if this does happen,
+    // construction of degree checks will fail
+    let mut coin = RandomCoin::<E::BaseField, Blake3_256<E::BaseField>>::new(&seed);
+    coin.draw().expect("failed to draw degree check point")
+}
+
 /// Makes sure that the post-division degree of the polynomial matches the expected degree
-#[cfg(debug_assertions)]
+#[cfg(any(debug_assertions, feature = "validate-degrees"))]
 fn validate_column_degree<B: StarkField, E: FieldElement<BaseField = B>>(
     column: &[E],
     divisor: &ConstraintDivisor<B>,
@@ -477,33 +658,41 @@ fn validate_column_degree<B: StarkField, E: FieldElement<BaseField = B>>(
     expected_degree: usize,
 ) -> Result<(), ProverError> {
     // build domain for divisor evaluation, and evaluate it over this domain
-    let div_values = evaluate_divisor(divisor, column.len(), domain.offset());
+    let inv_div_values = batch_inversion(&evaluate_divisor::<B>(
+        divisor,
+        column.len(),
+        domain.offset(),
+    ));
 
     // divide column values by the divisor
-    let mut evaluations = column
-        .iter()
-        .zip(div_values)
-        .map(|(&c, d)| c / d)
-        .collect::<Vec<_>>();
+    let mut evaluations = divide_by_divisor(column, &inv_div_values);
+
+    // check the degree of the quotient probabilistically; the expected degree is one less than
+    // the domain size, and thus, only the leading coefficient is checked and the random point
+    // is not used by the check
+    let check = DegreeCheck::new(
+        evaluations.len(),
+        domain.offset(),
+        expected_degree,
+        B::GENERATOR,
+    );
+    if check.is_satisfied_by(&evaluations) {
+        return Ok(());
+    }
 
-    // interpolate evaluations into a polynomial in coefficient form
+    // interpolate evaluations into a polynomial in coefficient form to get the actual degree
     let inv_twiddles = fft::get_inv_twiddles::<B>(evaluations.len());
     fft::interpolate_poly_with_offset(&mut evaluations, &inv_twiddles, domain.offset());
-    let poly = evaluations;
-
-    if expected_degree != math::polynom::degree_of(&poly) {
-        return Err(ProverError::MismatchedConstraintPolynomialDegree(
-            expected_degree,
-            math::polynom::degree_of(&poly),
-        ));
-    }
-    Ok(())
+    Err(ProverError::MismatchedConstraintPolynomialDegree(
+        expected_degree,
+        math::polynom::degree_of(&evaluations),
+    ))
 }
 
 /// Evaluates constraint divisor over the specified domain. This is similar to [get_inv_evaluation]
 /// function above but uses a more straight-forward but less efficient evaluation methodology and
 /// also does not invert the results.
-#[cfg(debug_assertions)]
+#[cfg(any(debug_assertions, feature = "validate-degrees"))]
 fn evaluate_divisor<E: FieldElement>(
     divisor: &ConstraintDivisor<E::BaseField>,
     domain_size: usize,
@@ -9,19 +9,23 @@ use super::{
 };
 use air::{
     Air, AuxTraceRandElements, ConstraintCompositionCoefficients, EvaluationFrame,
-    TransitionConstraints,
+    TransitionConstraints, TransitionFragment,
 };
-use math::FieldElement;
-use utils::iter_mut;
+use math::{FieldElement, StarkField};
+use utils::{boxed::Box, collections::Vec};
 
 #[cfg(feature = "concurrent")]
-use utils::{iterators::*, rayon};
+use super::evaluation_table::MIN_FRAGMENT_SIZE;
+
+#[cfg(feature = "concurrent")]
+use utils::{rayon, ConcurrencyConfig};
 
 // CONSTANTS
 // ================================================================================================
 
-#[cfg(feature = "concurrent")]
-const MIN_CONCURRENT_DOMAIN_SIZE: usize = 8192;
+/// Number of steps of the constraint evaluation domain for which main transition constraints are
+/// evaluated at once by a custom constraint evaluator provided by the AIR.
+const CUSTOM_EVALUATOR_BATCH_SIZE: usize = 1024;
 
 // CONSTRAINT EVALUATOR
 // ================================================================================================
@@ -31,7 +35,8 @@ pub struct ConstraintEvaluator<'a, A: Air, E: FieldElement<BaseField = A::BaseFi
     boundary_constraints: BoundaryConstraints<E>,
     transition_constraints: TransitionConstraints<E>,
     aux_rand_elements: AuxTraceRandElements<E>,
-    periodic_values: PeriodicValueTable<E::BaseField>,
+    periodic_values: &'a PeriodicValueTable<E::BaseField>,
+    custom_evaluator: Option<Box<dyn air::ConstraintEvaluator<E::BaseField> + 'a>>,
 }
 
 impl<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> ConstraintEvaluator<'a, A, E> {
@@ -39,19 +44,25 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> ConstraintEvaluator<
     // --------------------------------------------------------------------------------------------
     /// Returns a new evaluator which can be used to evaluate transition and boundary constraints
     /// over extended execution trace.
+    ///
+    /// The `periodic_values` table must have been built for the specified AIR; it is shared
+    /// read-only between all fragments of the evaluation.
     pub fn new(
         air: &'a A,
         aux_rand_elements: AuxTraceRandElements<E>,
         composition_coefficients: ConstraintCompositionCoefficients<E>,
+        periodic_values: &'a PeriodicValueTable<E::BaseField>,
     ) -> Self {
+        debug_assert!(
+            periodic_values.is_compatible_with(air),
+            "periodic value table was not built for the specified AIR"
+        );
+
         // build transition constraint groups; these will be used to compose transition constraint
         // evaluations
         let transition_constraints =
             air.get_transition_constraints(&composition_coefficients.transition);
 
-        // build periodic value table
-        let periodic_values = PeriodicValueTable::new(air);
-
         // build boundary constraint groups; these will be used to evaluate and compose boundary
         // constraint evaluations.
         let boundary_constraints =
@@ -63,6 +74,7 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> ConstraintEvaluator<
             transition_constraints,
             aux_rand_elements,
             periodic_values,
+            custom_evaluator: air.get_constraint_evaluator(),
         }
     }
 
@@ -88,15 +100,6 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> ConstraintEvaluator<
         let mut divisors = vec![self.transition_constraints.divisor().clone()];
         divisors.append(&mut self.boundary_constraints.get_divisors());
 
-        // allocate space for constraint evaluations; when we are in debug mode, we also allocate
-        // memory to hold all transition constraint evaluations (before they are merged into a
-        // single value) so that we can check their degrees later
-        #[cfg(not(debug_assertions))]
-        let mut evaluation_table = ConstraintEvaluationTable::<E>::new(domain, divisors);
-        #[cfg(debug_assertions)]
-        let mut evaluation_table =
-            ConstraintEvaluationTable::<E>::new(domain, divisors, &self.transition_constraints);
-
         // when `concurrent` feature is enabled, break the evaluation table into multiple fragments
         // to evaluate them into multiple threads; unless the constraint evaluation domain is small,
         // then don't bother with concurrent evaluation
@@ -105,8 +108,15 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> ConstraintEvaluator<
         let num_fragments = 1;
 
         #[cfg(feature = "concurrent")]
-        let num_fragments = if domain.ce_domain_size() >= MIN_CONCURRENT_DOMAIN_SIZE {
-            rayon::current_num_threads().next_power_of_two()
+        let num_fragments = if domain.ce_domain_size()
+            >= ConcurrencyConfig::global().min_constraint_domain_size()
+        {
+            // domains smaller than a single fragment are still evaluated as one fragment
+            let max_fragments = (domain.ce_domain_size() / MIN_FRAGMENT_SIZE).max(1);
+            core::cmp::min(
+                rayon::current_num_threads().next_power_of_two(),
+                max_fragments,
+            )
         } else {
             1
         };
@@ -114,18 +124,32 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> ConstraintEvaluator<
         // evaluate constraints for each fragment; if the trace consist of multiple segments
         // we evaluate constraints for all segments. otherwise, we evaluate constraints only
         // for the main segment.
-        let mut fragments = evaluation_table.fragments(num_fragments);
-        iter_mut!(fragments).for_each(|fragment| {
+        let evaluate_fragment = |fragment: &mut EvaluationTableFragment<E>| {
             if self.air.trace_info().is_multi_segment() {
                 self.evaluate_fragment_full(trace, domain, fragment);
             } else {
                 self.evaluate_fragment_main(trace, domain, fragment);
             }
-        });
+        };
+
+        // allocate space for constraint evaluations and fill it; when we are in debug mode, we
+        // also allocate memory to hold all transition constraint evaluations (before they are
+        // merged into a single value) so that we can check their degrees later
+        #[cfg(not(any(debug_assertions, feature = "validate-degrees")))]
+        let evaluation_table =
+            ConstraintEvaluationTable::<E>::new(domain, divisors, num_fragments, evaluate_fragment);
+        #[cfg(any(debug_assertions, feature = "validate-degrees"))]
+        let evaluation_table = ConstraintEvaluationTable::<E>::new(
+            domain,
+            divisors,
+            &self.transition_constraints,
+            num_fragments,
+            evaluate_fragment,
+        );
 
         // when in debug mode, make sure expected transition constraint degrees align with
         // actual degrees we got during constraint evaluation
-        #[cfg(debug_assertions)]
+        #[cfg(any(debug_assertions, feature = "validate-degrees"))]
         evaluation_table.validate_transition_degrees();
 
         evaluation_table
@@ -152,6 +176,11 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> ConstraintEvaluator<
         // LDE domain
         let lde_shift = domain.ce_to_lde_blowup().trailing_zeros();
 
+        // if the AIR provides a custom evaluator, main transition constraints are evaluated by it
+        // in batches
+        let mut batch = self.build_transition_batch(trace, domain);
+        let fragment_end = fragment.offset() + fragment.num_rows();
+
         for i in 0..fragment.num_rows() {
             let step = i + fragment.offset();
 
@@ -163,11 +192,20 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> ConstraintEvaluator<
 
             // evaluate transition constraints and save the merged result the first slot of the
             // evaluations buffer
-            evaluations[0] =
-                self.evaluate_main_transition(&main_frame, domain, step, &mut t_evaluations);
+            evaluations[0] = match batch.as_mut() {
+                Some(batch) => {
+                    batch.read_evaluations_into(step, fragment_end, &mut t_evaluations);
+                    #[cfg(debug_assertions)]
+                    self.validate_custom_evaluations(&main_frame, step, &t_evaluations);
+                    self.merge_main_transition(&t_evaluations, domain, step)
+                }
+                None => {
+                    self.evaluate_main_transition(&main_frame, domain, step, &mut t_evaluations)
+                }
+            };
 
             // when in debug mode, save transition constraint evaluations
-            #[cfg(debug_assertions)]
+            #[cfg(any(debug_assertions, feature = "validate-degrees"))]
             fragment.update_transition_evaluations(i, &t_evaluations, &[]);
 
             // evaluate boundary constraints; the results go into remaining slots of the
@@ -206,6 +244,11 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> ConstraintEvaluator<
         // LDE domain
         let lde_shift = domain.ce_to_lde_blowup().trailing_zeros();
 
+        // if the AIR provides a custom evaluator, main transition constraints are evaluated by it
+        // in batches
+        let mut batch = self.build_transition_batch(trace, domain);
+        let fragment_end = fragment.offset() + fragment.num_rows();
+
         for i in 0..fragment.num_rows() {
             let step = i + fragment.offset();
 
@@ -216,8 +259,17 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> ConstraintEvaluator<
             // evaluate transition constraints and save the merged result the first slot of the
             // evaluations buffer; we evaluate and compose constraints in the same function, we
             // can just add up the results of evaluating main and auxiliary constraints.
-            evaluations[0] =
-                self.evaluate_main_transition(&main_frame, domain, step, &mut tm_evaluations);
+            evaluations[0] = match batch.as_mut() {
+                Some(batch) => {
+                    batch.read_evaluations_into(step, fragment_end, &mut tm_evaluations);
+                    #[cfg(debug_assertions)]
+                    self.validate_custom_evaluations(&main_frame, step, &tm_evaluations);
+                    self.merge_main_transition(&tm_evaluations, domain, step)
+                }
+                None => {
+                    self.evaluate_main_transition(&main_frame, domain, step, &mut tm_evaluations)
+                }
+            };
             evaluations[0] += self.evaluate_aux_transition(
                 &main_frame,
                 &aux_frame,
@@ -227,7 +279,7 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> ConstraintEvaluator<
             );
 
             // when in debug mode, save transition constraint evaluations
-            #[cfg(debug_assertions)]
+            #[cfg(any(debug_assertions, feature = "validate-degrees"))]
             fragment.update_transition_evaluations(i, &tm_evaluations, &ta_evaluations);
 
             // evaluate boundary constraints; the results go into remaining slots of the
@@ -273,6 +325,18 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> ConstraintEvaluator<
         // the results into evaluations buffer
         self.air.evaluate_transition(main_frame, periodic_values, evaluations);
 
+        self.merge_main_transition(evaluations, domain, step)
+    }
+
+    /// Merges evaluations of transition constraints of the main execution trace at the specified
+    /// step of the constraint evaluation domain into a single value.
+    #[rustfmt::skip]
+    fn merge_main_transition(
+        &self,
+        evaluations: &[E::BaseField],
+        domain: &StarkDomain<A::BaseField>,
+        step: usize,
+    ) -> E {
         // merge transition constraint evaluations into a single value and return it;
         // we can do this here because all transition constraints have the same divisor.
         self.transition_constraints.main_constraints().iter().fold(E::ZERO, |result, group| {
@@ -321,6 +385,54 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> ConstraintEvaluator<
         })
     }
 
+    // CUSTOM EVALUATOR HELPERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a batch through which main transition constraints are evaluated by the custom
+    /// evaluator provided by the AIR, or None if the AIR does not provide a custom evaluator.
+    fn build_transition_batch<'b>(
+        &'b self,
+        trace: &'b TraceLde<E>,
+        domain: &StarkDomain<A::BaseField>,
+    ) -> Option<TransitionBatch<'b, A::BaseField>> {
+        let evaluator = self.custom_evaluator.as_deref()?;
+        let columns = trace.get_main_segment().columns().collect();
+        let fragment = TransitionFragment::new(
+            columns,
+            domain.ce_to_lde_blowup(),
+            domain.trace_to_lde_blowup(),
+        )
+        .with_domain_offset(domain.offset())
+        .with_periodic_values(
+            self.periodic_values.values(),
+            self.periodic_values.num_columns(),
+        );
+        Some(TransitionBatch::new(
+            evaluator,
+            fragment,
+            self.num_main_transition_constraints(),
+        ))
+    }
+
+    /// Makes sure evaluations of main transition constraints computed by the custom evaluator at
+    /// the specified step are the same as the evaluations computed by the AIR.
+    #[cfg(debug_assertions)]
+    fn validate_custom_evaluations(
+        &self,
+        main_frame: &EvaluationFrame<E::BaseField>,
+        step: usize,
+        evaluations: &[E::BaseField],
+    ) {
+        let mut expected = vec![E::BaseField::ZERO; evaluations.len()];
+        let periodic_values = self.periodic_values.get_row(step);
+        self.air
+            .evaluate_transition(main_frame, periodic_values, &mut expected);
+        assert_eq!(
+            expected, evaluations,
+            "custom constraint evaluator is inconsistent with evaluate_transition() at step {step}"
+        );
+    }
+
     // ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -335,3 +447,53 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> ConstraintEvaluator<
         self.transition_constraints.num_aux_constraints()
     }
 }
+
+// TRANSITION BATCH
+// ================================================================================================
+
+/// Evaluations of main transition constraints computed by a custom constraint evaluator for a
+/// batch of consecutive steps of the constraint evaluation domain.
+struct TransitionBatch<'a, B: StarkField> {
+    evaluator: &'a dyn air::ConstraintEvaluator<B>,
+    fragment: TransitionFragment<'a, B>,
+    evaluations: Vec<B>,
+    num_constraints: usize,
+    end: usize,
+}
+
+impl<'a, B: StarkField> TransitionBatch<'a, B> {
+    fn new(
+        evaluator: &'a dyn air::ConstraintEvaluator<B>,
+        fragment: TransitionFragment<'a, B>,
+        num_constraints: usize,
+    ) -> Self {
+        TransitionBatch {
+            evaluator,
+            fragment,
+            evaluations: Vec::new(),
+            num_constraints,
+            end: 0,
+        }
+    }
+
+    /// Copies evaluations at the specified step of the constraint evaluation domain into
+    /// `result`.
+    ///
+    /// If the step is not covered by the current batch, constraints are evaluated for the next
+    /// batch starting at this step and ending no later than at `end_step`.
+    fn read_evaluations_into(&mut self, step: usize, end_step: usize, result: &mut [B]) {
+        if step < self.fragment.offset() || step >= self.end {
+            let num_rows = core::cmp::min(CUSTOM_EVALUATOR_BATCH_SIZE, end_step - step);
+            self.fragment.set_rows(step, num_rows);
+            self.evaluations.clear();
+            self.evaluations
+                .resize(num_rows * self.num_constraints, B::ZERO);
+            self.evaluator
+                .evaluate_main_transitions(&self.fragment, &mut self.evaluations);
+            self.end = step + num_rows;
+        }
+
+        let start = (step - self.fragment.offset()) * self.num_constraints;
+        result.copy_from_slice(&self.evaluations[start..start + self.num_constraints]);
+    }
+}
@@ -9,7 +9,7 @@ mod boundary;
 use boundary::BoundaryConstraints;
 
 mod periodic_table;
-use periodic_table::PeriodicValueTable;
+pub use periodic_table::PeriodicValueTable;
 
 mod evaluator;
 pub use evaluator::ConstraintEvaluator;
@@ -7,13 +7,35 @@ use air::Air;
 use math::{fft, StarkField};
 use utils::{
     collections::{BTreeMap, Vec},
-    uninit_vector,
+    UninitBuffer,
 };
 
+// PERIODIC VALUE TABLE
+// ================================================================================================
+
+/// Values of all periodic columns of a computation expanded over the constraint evaluation
+/// domain.
+///
+/// The table contains values of all periodic columns normalized to the same length, and thus, it
+/// is only as long as the longest periodic column multiplied by the constraint evaluation blowup
+/// factor; values for a given step of the constraint evaluation domain are looked up via
+/// [get_row()](PeriodicValueTable::get_row).
+///
+/// The prover builds this table once per proof and shares it between all fragments of the
+/// constraint evaluation table. The table depends only on the periodic columns of the AIR, the
+/// length of the execution trace, and the proof options; thus, provers which generate many proofs
+/// for the same AIR can build the table once and return it from
+/// [Prover::periodic_values()](crate::Prover::periodic_values) to avoid re-computing it for every
+/// proof.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PeriodicValueTable<B: StarkField> {
     values: Vec<B>,
     length: usize,
     width: usize,
+    columns: Vec<Vec<B>>,
+    trace_length: usize,
+    ce_blowup_factor: usize,
+    domain_offset: B,
 }
 
 impl<B: StarkField> PeriodicValueTable<B> {
@@ -23,6 +45,11 @@ impl<B: StarkField> PeriodicValueTable<B> {
     /// values of all periodic columns normalized to the same length. This enables simple lookup
     /// into the able using step index of the constraint evaluation domain.
     pub fn new<A: Air<BaseField = B>>(air: &A) -> PeriodicValueTable<B> {
+        let columns = air.get_periodic_column_values();
+        let trace_length = air.trace_length();
+        let ce_blowup_factor = air.ce_blowup_factor();
+        let domain_offset = air.domain_offset();
+
         // get a list of polynomials describing periodic columns from AIR. if there are no
         // periodic columns return an empty table
         let polys = air.get_periodic_column_polys();
@@ -31,6 +58,10 @@ impl<B: StarkField> PeriodicValueTable<B> {
                 values: Vec::new(),
                 length: 0,
                 width: 0,
+                columns,
+                trace_length,
+                ce_blowup_factor,
+                domain_offset,
             };
         }
 
@@ -46,41 +77,59 @@ impl<B: StarkField> PeriodicValueTable<B> {
             .iter()
             .map(|poly| {
                 let poly_size = poly.len();
-                let num_cycles = (air.trace_length() / poly_size) as u64;
-                let offset = air.domain_offset().exp(num_cycles.into());
+                let num_cycles = (trace_length / poly_size) as u64;
+                let offset = domain_offset.exp(num_cycles.into());
                 let twiddles = twiddle_map
                     .entry(poly_size)
                     .or_insert_with(|| fft::get_twiddles(poly_size));
 
-                fft::evaluate_poly_with_offset(poly, twiddles, offset, air.ce_blowup_factor())
+                fft::evaluate_poly_with_offset(poly, twiddles, offset, ce_blowup_factor)
             })
             .collect::<Vec<_>>();
 
         // allocate memory to hold all expanded values and copy polynomial evaluations into the
         // table in such a way that values for the same row are adjacent to each other.
         let row_width = polys.len();
-        let column_length = max_poly_size * air.ce_blowup_factor();
-        let mut values = unsafe { uninit_vector(row_width * column_length) };
-        for i in 0..column_length {
-            for (j, column) in evaluations.iter().enumerate() {
-                values[i * row_width + j] = column[i % column.len()];
-            }
-        }
+        let column_length = max_poly_size * ce_blowup_factor;
+        let values =
+            UninitBuffer::new(row_width * column_length).fill((0..column_length).flat_map(|i| {
+                evaluations
+                    .iter()
+                    .map(move |column| column[i % column.len()])
+            }));
 
         PeriodicValueTable {
             values,
             length: column_length,
             width: row_width,
+            columns,
+            trace_length,
+            ce_blowup_factor,
+            domain_offset,
         }
     }
 
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
+    /// Returns true if the computation for which this table was built has no periodic columns.
     pub fn is_empty(&self) -> bool {
         self.width == 0
     }
 
+    /// Returns the number of periodic columns in this table.
+    pub fn num_columns(&self) -> usize {
+        self.width
+    }
+
+    /// Returns values of all periodic columns for one period of the longest periodic column, with
+    /// values for the same step of the constraint evaluation domain adjacent to each other.
+    pub(crate) fn values(&self) -> &[B] {
+        &self.values
+    }
+
+    /// Returns values of all periodic columns at the specified step of the constraint evaluation
+    /// domain.
     pub fn get_row(&self, ce_step: usize) -> &[B] {
         if self.is_empty() {
             &[]
@@ -89,6 +138,18 @@ impl<B: StarkField> PeriodicValueTable<B> {
             &self.values[start..start + self.width]
         }
     }
+
+    /// Returns true if this table can be used to evaluate constraints of the specified AIR.
+    ///
+    /// This is the case when the AIR defines the same periodic columns as the AIR for which this
+    /// table was built, and when the trace length, the constraint evaluation blowup factor, and
+    /// the domain offset of the two AIRs are the same.
+    pub fn is_compatible_with<A: Air<BaseField = B>>(&self, air: &A) -> bool {
+        self.trace_length == air.trace_length()
+            && self.ce_blowup_factor == air.ce_blowup_factor()
+            && self.domain_offset == air.domain_offset()
+            && self.columns == air.get_periodic_column_values()
+    }
 }
 
 // TESTS
@@ -154,6 +215,29 @@ mod tests {
         assert_eq!(expected, actual);
     }
 
+    #[test]
+    fn periodic_value_table_compatibility() {
+        let col1 = vec![BaseElement::new(1), BaseElement::new(2)];
+        let col2 = vec![BaseElement::new(3), BaseElement::new(4)];
+        let air = MockAir::with_periodic_columns(vec![col1.clone()], 32);
+        let table = super::PeriodicValueTable::new(&air);
+        assert!(table.is_compatible_with(&air));
+
+        // tables cannot be shared between AIRs with different trace lengths or periodic columns
+        let air = MockAir::with_periodic_columns(vec![col1.clone()], 64);
+        assert!(!table.is_compatible_with(&air));
+        let air = MockAir::with_periodic_columns(vec![col2], 32);
+        assert!(!table.is_compatible_with(&air));
+        let air = MockAir::with_periodic_columns(vec![col1.clone(), col1], 32);
+        assert!(!table.is_compatible_with(&air));
+
+        // an empty table is compatible only with AIRs without periodic columns
+        let air = MockAir::with_trace_length(32);
+        let table = super::PeriodicValueTable::new(&air);
+        assert!(table.is_empty());
+        assert!(table.is_compatible_with(&air));
+    }
+
     fn build_ce_domain(domain_size: usize, domain_offset: BaseElement) -> Vec<BaseElement> {
         let g = BaseElement::get_root_of_unity(log2(domain_size));
         get_power_series_with_offset(g, domain_offset, domain_size)
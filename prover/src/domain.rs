@@ -3,10 +3,13 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use air::Air;
+use air::{Air, AirContext, DomainError};
 use math::{fft, get_power_series, log2, StarkField};
 use utils::collections::Vec;
 
+#[cfg(feature = "concurrent")]
+use utils::iterators::*;
+
 // TYPES AND INTERFACES
 // ================================================================================================
 
@@ -28,6 +31,12 @@ pub struct StarkDomain<B: StarkField> {
 
     /// Offset of the low-degree extension domain.
     domain_offset: B,
+
+    /// Powers of the domain offset which can be used to evaluate polynomials of trace length
+    /// over the LDE domain. Length of this vector is equal to the LDE domain size: evaluating a
+    /// polynomial reads a distinct chunk of trace length powers for every coset of the trace
+    /// domain in the LDE domain.
+    trace_offset_powers: Vec<B>,
 }
 
 // STARK DOMAIN IMPLEMENTATION
@@ -35,6 +44,10 @@ pub struct StarkDomain<B: StarkField> {
 
 impl<B: StarkField> StarkDomain<B> {
     /// Returns a new STARK domain initialized with the provided `context`.
+    ///
+    /// # Panics
+    /// Panics if the constraint evaluation domain of the computation cannot be built in the base
+    /// field.
     pub fn new<A: Air<BaseField = B>>(air: &A) -> Self {
         let trace_twiddles = fft::get_twiddles(air.trace_length());
 
@@ -42,15 +55,38 @@ impl<B: StarkField> StarkDomain<B> {
         let domain_gen = B::get_root_of_unity(log2(air.ce_domain_size()));
         let ce_domain = get_power_series(domain_gen, air.ce_domain_size());
 
+        // pre-compute offset powers for the trace-to-LDE extension since this extension is
+        // performed for every column of every trace segment as well as for the DEEP composition
+        // polynomial
+        let trace_offset_powers = fft::get_offset_powers(
+            air.trace_length(),
+            air.lde_domain_size() / air.trace_length(),
+            air.domain_offset(),
+        );
+
         StarkDomain {
             trace_twiddles,
             ce_domain,
             ce_to_lde_blowup: air.lde_domain_size() / air.ce_domain_size(),
             ce_domain_mod_mask: air.ce_domain_size() - 1,
             domain_offset: air.domain_offset(),
+            trace_offset_powers,
         }
     }
 
+    /// Returns a new STARK domain initialized with the provided `context`.
+    ///
+    /// This is a fallible version of [StarkDomain::new()].
+    ///
+    /// # Errors
+    /// Returns an error if domains of the computation described by `air` cannot be built in the
+    /// base field (e.g., the low-degree extension domain is too large, or the domain offset is an
+    /// element of the domain).
+    pub fn try_new<A: Air<BaseField = B>>(air: &A) -> Result<Self, DomainError> {
+        AirContext::<B>::validate_domains(air.trace_info(), air.options())?;
+        Ok(Self::new(air))
+    }
+
     // EXECUTION TRACE
     // --------------------------------------------------------------------------------------------
 
@@ -64,6 +100,11 @@ impl<B: StarkField> StarkDomain<B> {
         &self.trace_twiddles
     }
 
+    /// Returns offset powers which can be used to evaluate trace polynomials over the LDE domain.
+    pub fn trace_offset_powers(&self) -> &[B] {
+        &self.trace_offset_powers
+    }
+
     /// Returns blowup factor from trace to constraint evaluation domain.
     pub fn trace_to_ce_blowup(&self) -> usize {
         self.ce_domain_size() / self.trace_length()
@@ -100,6 +141,48 @@ impl<B: StarkField> StarkDomain<B> {
         self.ce_domain[step] * self.domain_offset
     }
 
+    /// Returns an iterator over all points of the constraint evaluation domain; the i-th point is
+    /// s * g^i where g is the constraint evaluation domain generator and s is the domain offset.
+    pub fn ce_domain_points(&self) -> DomainPoints<B> {
+        DomainPoints::new(
+            self.domain_offset,
+            self.ce_domain_generator(),
+            self.ce_domain_size(),
+        )
+    }
+
+    /// Returns an iterator over chunks of consecutive points of the constraint evaluation domain.
+    ///
+    /// Every chunk contains `chunk_size` points, except for the last chunk which may be shorter.
+    /// Concatenating the chunks yields the same sequence as [ce_domain_points()](Self::ce_domain_points).
+    ///
+    /// # Panics
+    /// Panics if `chunk_size` is zero.
+    pub fn ce_domain_chunks(&self, chunk_size: usize) -> impl Iterator<Item = DomainPoints<B>> {
+        let (offset, generator) = (self.domain_offset, self.ce_domain_generator());
+        let domain_size = self.ce_domain_size();
+        (0..num_chunks(domain_size, chunk_size))
+            .map(move |i| DomainPoints::chunk(offset, generator, domain_size, chunk_size, i))
+    }
+
+    /// Returns a parallel iterator over chunks of consecutive points of the constraint evaluation
+    /// domain; chunks are the same as the ones returned by
+    /// [ce_domain_chunks()](Self::ce_domain_chunks).
+    ///
+    /// # Panics
+    /// Panics if `chunk_size` is zero.
+    #[cfg(feature = "concurrent")]
+    pub fn par_ce_domain_chunks(
+        &self,
+        chunk_size: usize,
+    ) -> impl IndexedParallelIterator<Item = DomainPoints<B>> {
+        let (offset, generator) = (self.domain_offset, self.ce_domain_generator());
+        let domain_size = self.ce_domain_size();
+        (0..num_chunks(domain_size, chunk_size))
+            .into_par_iter()
+            .map(move |i| DomainPoints::chunk(offset, generator, domain_size, chunk_size, i))
+    }
+
     /// Returns (s * g^step)^power where g is the constraint evaluation domain generator and s is
     /// the domain offset.
     ///
@@ -124,8 +207,215 @@ impl<B: StarkField> StarkDomain<B> {
         self.ce_domain_size() * self.ce_to_lde_blowup()
     }
 
+    /// Returns the generator of the low-degree extension domain.
+    pub fn lde_domain_generator(&self) -> B {
+        B::get_root_of_unity(log2(self.lde_domain_size()))
+    }
+
     /// Returns LDE domain offset.
     pub fn offset(&self) -> B {
         self.domain_offset
     }
+
+    /// Returns an iterator over all points of the low-degree extension domain; the i-th point is
+    /// s * g^i where g is the LDE domain generator and s is the domain offset.
+    pub fn lde_domain_points(&self) -> DomainPoints<B> {
+        DomainPoints::new(
+            self.domain_offset,
+            self.lde_domain_generator(),
+            self.lde_domain_size(),
+        )
+    }
+
+    /// Returns an iterator over chunks of consecutive points of the low-degree extension domain.
+    ///
+    /// Every chunk contains `chunk_size` points, except for the last chunk which may be shorter.
+    /// Concatenating the chunks yields the same sequence as [lde_domain_points()](Self::lde_domain_points).
+    ///
+    /// # Panics
+    /// Panics if `chunk_size` is zero.
+    pub fn lde_domain_chunks(&self, chunk_size: usize) -> impl Iterator<Item = DomainPoints<B>> {
+        let (offset, generator) = (self.domain_offset, self.lde_domain_generator());
+        let domain_size = self.lde_domain_size();
+        (0..num_chunks(domain_size, chunk_size))
+            .map(move |i| DomainPoints::chunk(offset, generator, domain_size, chunk_size, i))
+    }
+
+    /// Returns a parallel iterator over chunks of consecutive points of the low-degree extension
+    /// domain; chunks are the same as the ones returned by
+    /// [lde_domain_chunks()](Self::lde_domain_chunks).
+    ///
+    /// # Panics
+    /// Panics if `chunk_size` is zero.
+    #[cfg(feature = "concurrent")]
+    pub fn par_lde_domain_chunks(
+        &self,
+        chunk_size: usize,
+    ) -> impl IndexedParallelIterator<Item = DomainPoints<B>> {
+        let (offset, generator) = (self.domain_offset, self.lde_domain_generator());
+        let domain_size = self.lde_domain_size();
+        (0..num_chunks(domain_size, chunk_size))
+            .into_par_iter()
+            .map(move |i| DomainPoints::chunk(offset, generator, domain_size, chunk_size, i))
+    }
+}
+
+// DOMAIN POINTS
+// ================================================================================================
+
+/// An iterator over consecutive points of a domain of a STARK computation.
+///
+/// The iterator yields s * g^i for i in a range of consecutive steps, where g is the generator of
+/// the domain and s is the domain offset. Each point is computed from the previous one using a
+/// single multiplication, and thus, iterating over the domain is about as cheap as reading the
+/// points from a pre-computed table.
+///
+/// Instances of this iterator are returned by [StarkDomain] methods (e.g.,
+/// [StarkDomain::ce_domain_points()] and [StarkDomain::lde_domain_chunks()]).
+#[derive(Debug, Clone)]
+pub struct DomainPoints<B: StarkField> {
+    next: B,
+    generator: B,
+    start: usize,
+    remaining: usize,
+}
+
+impl<B: StarkField> DomainPoints<B> {
+    /// Returns an iterator over all points of a domain of the specified size.
+    fn new(offset: B, generator: B, domain_size: usize) -> Self {
+        DomainPoints {
+            next: offset,
+            generator,
+            start: 0,
+            remaining: domain_size,
+        }
+    }
+
+    /// Returns an iterator over the points of the `index`-th chunk of a domain of the specified
+    /// size split into chunks of `chunk_size` points.
+    fn chunk(offset: B, generator: B, domain_size: usize, chunk_size: usize, index: usize) -> Self {
+        let start = index * chunk_size;
+        DomainPoints {
+            next: offset * generator.exp((start as u64).into()),
+            generator,
+            start,
+            remaining: core::cmp::min(chunk_size, domain_size - start),
+        }
+    }
+
+    /// Returns the position within the domain of the next point yielded by this iterator.
+    pub fn step(&self) -> usize {
+        self.start
+    }
+}
+
+impl<B: StarkField> Iterator for DomainPoints<B> {
+    type Item = B;
+
+    fn next(&mut self) -> Option<B> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let x = self.next;
+        self.next *= self.generator;
+        self.start += 1;
+        self.remaining -= 1;
+        Some(x)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<B: StarkField> ExactSizeIterator for DomainPoints<B> {}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the number of chunks of the specified size needed to cover a domain.
+fn num_chunks(domain_size: usize, chunk_size: usize) -> usize {
+    assert!(chunk_size > 0, "chunk size must be greater than zero");
+    (domain_size + chunk_size - 1) / chunk_size
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::StarkDomain;
+    use crate::tests::MockAir;
+    use air::Air;
+    use math::{fields::f128::BaseElement, get_power_series_with_offset, log2, StarkField};
+    use utils::collections::Vec;
+
+    #[test]
+    fn domain_points() {
+        let air = MockAir::with_trace_length(32);
+        let domain = StarkDomain::new(&air);
+
+        let expected = build_domain(domain.ce_domain_size(), air.domain_offset());
+        let points = domain.ce_domain_points();
+        assert_eq!(expected.len(), points.len());
+        assert_eq!(expected, points.collect::<Vec<_>>());
+        for (step, &x) in expected.iter().enumerate() {
+            assert_eq!(domain.get_ce_x_at(step), x);
+        }
+
+        let expected = build_domain(domain.lde_domain_size(), air.domain_offset());
+        assert_eq!(expected, domain.lde_domain_points().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn domain_chunks() {
+        let air = MockAir::with_trace_length(32);
+        let domain = StarkDomain::new(&air);
+
+        // chunks should cover the domain even when the chunk size does not divide domain size
+        for chunk_size in [
+            1,
+            5,
+            16,
+            domain.lde_domain_size(),
+            domain.lde_domain_size() + 1,
+        ] {
+            let mut points = Vec::new();
+            for chunk in domain.lde_domain_chunks(chunk_size) {
+                assert_eq!(points.len(), chunk.step());
+                assert!(chunk.len() <= chunk_size);
+                points.extend(chunk);
+            }
+            assert_eq!(domain.lde_domain_points().collect::<Vec<_>>(), points);
+
+            let points = domain.ce_domain_chunks(chunk_size).flatten();
+            assert!(points.eq(domain.ce_domain_points()));
+
+            #[cfg(feature = "concurrent")]
+            {
+                use utils::iterators::*;
+                let chunks = domain.par_lde_domain_chunks(chunk_size);
+                let points = chunks
+                    .map(|chunk| chunk.collect::<Vec<_>>())
+                    .collect::<Vec<_>>();
+                assert_eq!(
+                    domain.lde_domain_points().collect::<Vec<_>>(),
+                    points.concat()
+                );
+                let chunks = domain.par_ce_domain_chunks(chunk_size);
+                let points = chunks
+                    .map(|chunk| chunk.collect::<Vec<_>>())
+                    .collect::<Vec<_>>();
+                assert_eq!(
+                    domain.ce_domain_points().collect::<Vec<_>>(),
+                    points.concat()
+                );
+            }
+        }
+    }
+
+    fn build_domain(domain_size: usize, domain_offset: BaseElement) -> Vec<BaseElement> {
+        let g = BaseElement::get_root_of_unity(log2(domain_size));
+        get_power_series_with_offset(g, domain_offset, domain_size)
+    }
 }
@@ -0,0 +1,242 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::ProverError;
+use core::fmt::Write;
+use math::FieldElement;
+use utils::{
+    collections::{BTreeMap, Vec},
+    string::{String, ToString},
+};
+
+#[cfg(feature = "std")]
+use std::path::PathBuf;
+
+// ARTIFACT SINK
+// ================================================================================================
+
+/// A destination into which intermediate artifacts of proof generation are written.
+///
+/// Artifacts are written by [Prover::prove_with_artifacts()](crate::Prover::prove_with_artifacts)
+/// in the order in which they are computed. Each artifact is a table of field elements encoded
+/// as text; the following artifacts are written:
+///
+/// * `trace_polys_main` - coefficients of the polynomials interpolated from the columns of the
+///   main trace segment; there is one column per polynomial, and rows contain coefficients
+///   starting with the constant term.
+/// * `trace_polys_aux_{i}` - same as above, but for the i-th auxiliary trace segment.
+/// * `constraint_evaluations` - columns of the constraint evaluation table over the constraint
+///   evaluation domain before division by constraint divisors; the first column contains merged
+///   evaluations of transition constraints, and the remaining columns contain merged evaluations
+///   of boundary constraints sharing the same divisor.
+/// * `constraint_evaluations_divided` - the same columns after division by their divisors.
+/// * `composition_poly` - coefficients of the columns of the constraint composition polynomial.
+/// * `fri_layer_{i}` - evaluations of the i-th FRI layer over its domain in natural order; the
+///   0-th layer contains evaluations of the DEEP composition polynomial over the LDE domain.
+/// * `fri_remainder` - evaluations of the FRI remainder.
+///
+/// The text encoding of an artifact starts with two header lines of the form:
+/// ```text
+/// # artifact: constraint_evaluations
+/// # columns: 2, rows: 64, extension degree: 2
+/// ```
+/// followed by one line per table row. Values in a row are separated by a single space, and each
+/// value is written as a decimal integer in canonical form; elements of an extension field are
+/// written as their coefficients over the base field separated by commas (e.g., `12,7`).
+///
+/// When the `std` feature is enabled, this trait is implemented by [ArtifactDirectory] which
+/// writes every artifact into a separate file; it is also implemented for `BTreeMap<String,
+/// String>` which collects artifacts in memory keyed by their names.
+pub trait ArtifactSink {
+    /// Writes an artifact with the specified `name` and text `contents` into this sink.
+    ///
+    /// # Errors
+    /// Returns an error if the artifact could not be written.
+    fn write_artifact(&mut self, name: &str, contents: &str) -> Result<(), ProverError>;
+}
+
+impl ArtifactSink for BTreeMap<String, String> {
+    fn write_artifact(&mut self, name: &str, contents: &str) -> Result<(), ProverError> {
+        self.insert(name.to_string(), contents.to_string());
+        Ok(())
+    }
+}
+
+// ARTIFACT DIRECTORY
+// ================================================================================================
+
+/// An [ArtifactSink] which writes every artifact into a file named `{name}.txt` in a directory.
+#[cfg(feature = "std")]
+pub struct ArtifactDirectory {
+    path: PathBuf,
+}
+
+#[cfg(feature = "std")]
+impl ArtifactDirectory {
+    /// Returns a sink writing artifacts into the directory at the specified `path`; the directory
+    /// is created when the first artifact is written, if it does not exist yet. Existing files
+    /// with the same names as the artifacts are overwritten.
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+#[cfg(feature = "std")]
+impl ArtifactSink for ArtifactDirectory {
+    fn write_artifact(&mut self, name: &str, contents: &str) -> Result<(), ProverError> {
+        std::fs::create_dir_all(&self.path)
+            .and_then(|_| std::fs::write(self.path.join(format!("{name}.txt")), contents))
+            .map_err(|err| ProverError::ArtifactWriteFailed(err.to_string()))
+    }
+}
+
+// ARTIFACT WRITER
+// ================================================================================================
+
+/// Encodes artifacts of proof generation and writes them into an optional [ArtifactSink].
+///
+/// If no sink was provided, artifacts are not encoded, and thus, the prover should check whether
+/// the writer [is_enabled()](ArtifactWriter::is_enabled) before computing artifacts which it does
+/// not need otherwise.
+pub(crate) struct ArtifactWriter<'a> {
+    sink: Option<&'a mut dyn ArtifactSink>,
+}
+
+impl<'a> ArtifactWriter<'a> {
+    /// Returns a new writer which writes artifacts into the specified `sink`.
+    pub fn new(sink: Option<&'a mut dyn ArtifactSink>) -> Self {
+        Self { sink }
+    }
+
+    /// Returns true if artifacts are written into a sink.
+    pub fn is_enabled(&self) -> bool {
+        self.sink.is_some()
+    }
+
+    /// Encodes a table with the specified columns and writes it into the sink under the specified
+    /// name; all columns must have the same length.
+    pub fn write_columns<'c, E, I>(&mut self, name: &str, columns: I) -> Result<(), ProverError>
+    where
+        E: FieldElement + 'c,
+        I: IntoIterator<Item = &'c [E]>,
+    {
+        let sink = match self.sink.as_mut() {
+            Some(sink) => sink,
+            None => return Ok(()),
+        };
+
+        let columns: Vec<&[E]> = columns.into_iter().collect();
+        let num_rows = columns.first().map_or(0, |column| column.len());
+        debug_assert!(columns.iter().all(|column| column.len() == num_rows));
+
+        let mut contents = String::new();
+        writeln!(contents, "# artifact: {name}").unwrap();
+        writeln!(
+            contents,
+            "# columns: {}, rows: {}, extension degree: {}",
+            columns.len(),
+            num_rows,
+            E::EXTENSION_DEGREE
+        )
+        .unwrap();
+        for row_idx in 0..num_rows {
+            for (col_idx, column) in columns.iter().enumerate() {
+                if col_idx > 0 {
+                    contents.push(' ');
+                }
+                let coefficients = E::as_base_elements(&column[row_idx..row_idx + 1]);
+                for (i, coefficient) in coefficients.iter().enumerate() {
+                    if i > 0 {
+                        contents.push(',');
+                    }
+                    write!(contents, "{coefficient}").unwrap();
+                }
+            }
+            contents.push('\n');
+        }
+
+        sink.write_artifact(name, &contents)
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::ArtifactDirectory;
+    use crate::{tests::FibProver, FieldExtension, ProofOptions, Prover};
+    use crypto::hashers::Sha3_256;
+    use math::fields::f64::BaseElement;
+    use utils::collections::{BTreeMap, Vec};
+
+    #[test]
+    fn proof_artifacts() {
+        let options = ProofOptions::new(28, 8, 0, FieldExtension::Quadratic, 4, 32);
+        let prover = FibProver::<Sha3_256<BaseElement>>::new(options.clone());
+        let mut artifacts = BTreeMap::new();
+        let proof = prover
+            .prove_with_artifacts(prover.build_trace(64), &mut artifacts)
+            .unwrap();
+
+        // writing artifacts should not affect the proof
+        assert_eq!(prover.prove(prover.build_trace(64)).unwrap(), proof);
+
+        // trace polynomials are in the base field; everything else is in the extension field
+        let header = |name: &str| {
+            artifacts[name]
+                .lines()
+                .take(2)
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        assert_eq!(
+            "# artifact: trace_polys_main\n# columns: 2, rows: 32, extension degree: 1",
+            header("trace_polys_main")
+        );
+        assert_eq!(
+            "# artifact: fri_layer_0\n# columns: 1, rows: 256, extension degree: 2",
+            header("fri_layer_0")
+        );
+
+        // constraint evaluations before and after division have the same shape
+        let shape = |name: &str| artifacts[name].lines().nth(1).unwrap().to_string();
+        assert_eq!(
+            shape("constraint_evaluations"),
+            shape("constraint_evaluations_divided")
+        );
+        for name in ["composition_poly", "fri_remainder"] {
+            assert!(artifacts.contains_key(name));
+        }
+        let num_fri_layers = options.to_fri_options().num_fri_layers(256);
+        assert!(num_fri_layers > 0);
+        for i in 0..num_fri_layers {
+            assert!(artifacts.contains_key(&format!("fri_layer_{i}")));
+        }
+        assert!(!artifacts.contains_key(&format!("fri_layer_{num_fri_layers}")));
+
+        // every row contains one value per column, and every value consists of base field
+        // elements
+        let fri_layer = &artifacts["fri_layer_0"];
+        for row in fri_layer.lines().skip(2) {
+            let coefficients = row.split(',').collect::<Vec<_>>();
+            assert_eq!(2, coefficients.len());
+            assert!(coefficients.iter().all(|c| c.parse::<u64>().is_ok()));
+        }
+
+        // artifacts written into a directory should be the same as artifacts collected in memory
+        let path =
+            std::env::temp_dir().join(format!("winter_prover_artifacts_{}", std::process::id()));
+        let mut directory = ArtifactDirectory::new(&path);
+        prover
+            .prove_with_artifacts(prover.build_trace(64), &mut directory)
+            .unwrap();
+        for (name, contents) in artifacts.iter() {
+            let file = std::fs::read_to_string(path.join(format!("{name}.txt"))).unwrap();
+            assert_eq!(contents, &file);
+        }
+        std::fs::remove_dir_all(&path).unwrap();
+    }
+}
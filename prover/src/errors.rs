@@ -5,7 +5,9 @@
 
 //! Contains common error types for prover and verifier.
 
+use air::DomainError;
 use core::fmt;
+use utils::string::String;
 
 // PROVER ERROR
 // ================================================================================================
@@ -21,6 +23,63 @@ pub enum ProverError {
     /// This error occurs when the base field specified by the AIR does not support field extension
     /// of degree specified by proof options.
     UnsupportedFieldExtension(usize),
+    /// This error occurs when domains of the computation cannot be built in the base field
+    /// specified by the AIR (e.g., because the low-degree extension domain is too large).
+    InvalidDomain(DomainError),
+    /// This error occurs when the digest size specified by proof options is different from the
+    /// size of digests produced by the hash function used by the prover.
+    InconsistentDigestSize(usize, usize),
+    /// This error occurs when a proof could not be written into the sink specified for proof
+    /// generation.
+    ProofWriteFailed(String),
+    /// This error occurs when the input state of the step at the specified index of a proof chain
+    /// is different from the output state of the previous step (or from the input state of the
+    /// chain for the first step).
+    BrokenChainLink(usize),
+    /// This error occurs when an intermediate artifact of proof generation could not be written
+    /// into the artifact sink specified for proof generation.
+    ArtifactWriteFailed(String),
+}
+
+impl ProverError {
+    /// Returns the step of the execution trace at which proof generation failed, if the error is
+    /// specific to a single step.
+    pub fn step(&self) -> Option<usize> {
+        match self {
+            Self::UnsatisfiedTransitionConstraintError(step) => Some(*step),
+            _ => None,
+        }
+    }
+
+    /// Returns the degree which the prover expected a polynomial to have, if the error was caused
+    /// by a degree mismatch.
+    pub fn expected_degree(&self) -> Option<usize> {
+        match self {
+            Self::MismatchedConstraintPolynomialDegree(expected, _) => Some(*expected),
+            _ => None,
+        }
+    }
+
+    /// Returns the actual degree of a polynomial, if the error was caused by a degree mismatch.
+    pub fn actual_degree(&self) -> Option<usize> {
+        match self {
+            Self::MismatchedConstraintPolynomialDegree(_, actual) => Some(*actual),
+            _ => None,
+        }
+    }
+
+    /// Returns true if the error was caused by the environment rather than by the computation
+    /// or the proof parameters, and thus, proof generation may succeed if retried.
+    ///
+    /// Currently, only failures to write a proof or its intermediate artifacts into a sink are
+    /// transient; all other errors are definitive: generating a proof for the same trace with the
+    /// same options will fail in the same way.
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            Self::ProofWriteFailed(_) | Self::ArtifactWriteFailed(_)
+        )
+    }
 }
 
 impl fmt::Display for ProverError {
@@ -36,6 +95,21 @@ impl fmt::Display for ProverError {
             Self::UnsupportedFieldExtension(degree) => {
                 write!(f, "field extension of degree {degree} is not supported for the specified base field")
             }
+            Self::InvalidDomain(err) => {
+                write!(f, "invalid computation domain: {err}")
+            }
+            Self::InconsistentDigestSize(expected, actual) => {
+                write!(f, "proof options require {expected}-byte digests, but the hash function produces {actual}-byte digests")
+            }
+            Self::ProofWriteFailed(msg) => {
+                write!(f, "failed to write proof: {msg}")
+            }
+            Self::BrokenChainLink(index) => {
+                write!(f, "input state of step {index} of the proof chain does not match output state of the previous step")
+            }
+            Self::ArtifactWriteFailed(msg) => {
+                write!(f, "failed to write proof generation artifact: {msg}")
+            }
         }
     }
 }
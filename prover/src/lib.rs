@@ -44,18 +44,24 @@
 extern crate alloc;
 
 pub use air::{
-    proof::StarkProof, Air, AirContext, Assertion, AuxTraceRandElements, BoundaryConstraint,
-    BoundaryConstraintGroup, ConstraintCompositionCoefficients, ConstraintDivisor,
-    DeepCompositionCoefficients, EvaluationFrame, FieldExtension, ProofOptions, TraceInfo,
-    TraceLayout, TransitionConstraintDegree, TransitionConstraintGroup,
+    proof::{
+        ChannelMessage, EthStarkProof, MessageKind, OptionsTuner, ProofBatch, ProofChain,
+        ProofStats, StarkProof, StepPublicInputs, TuningObjective, LEGACY_PROOF_FORMAT_VERSION,
+    },
+    Air, AirContext, Assertion, AuxTraceRandElements, BoundaryConstraint, BoundaryConstraintGroup,
+    ConstraintCompositionCoefficients, ConstraintDivisor, ConstraintEvaluator,
+    DeepCompositionCoefficients, DomainError, EvaluationFrame, FieldExtension, ProofOptions,
+    ProofOptionsBuilder, ProofOptionsError, RationalFunction, TraceError, TraceInfo, TraceLayout,
+    TransitionConstraintDegree, TransitionConstraintGroup, TransitionFragment, TuningError,
+    VerifierParams,
 };
 pub use utils::{
-    iterators, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
-    SliceReader,
+    iterators, ByteReader, ByteWriter, ConcurrencyConfig, Deserializable, DeserializationError,
+    Serializable, SliceReader,
 };
 
 use fri::FriProver;
-use utils::collections::Vec;
+use utils::{collections::Vec, enter_span};
 
 pub use math;
 use math::{
@@ -65,29 +71,32 @@ use math::{
 };
 
 pub use crypto;
-use crypto::{ElementHasher, MerkleTree};
+use crypto::{ElementHasher, Hasher, MerkleTree, Transcript};
 
 #[cfg(feature = "std")]
 use log::debug;
 #[cfg(feature = "std")]
 use math::log2;
-#[cfg(feature = "std")]
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
 use std::time::Instant;
+#[cfg(all(feature = "std", target_family = "wasm"))]
+use timer::Instant;
 
 mod domain;
-pub use domain::StarkDomain;
+pub use domain::{DomainPoints, StarkDomain};
 
 mod matrix;
 pub use matrix::Matrix;
 
 mod constraints;
-use constraints::{CompositionPoly, ConstraintCommitment, ConstraintEvaluator};
+pub use constraints::PeriodicValueTable;
+use constraints::{CompositionPoly, ConstraintCommitment};
 
 mod composer;
 use composer::DeepCompositionPoly;
 
 mod trace;
-pub use trace::{Trace, TraceTable, TraceTableFragment};
+pub use trace::{Trace, TraceBuilder, TraceTable, TraceTableFragment, TraceTableView};
 use trace::{TraceCommitment, TraceLde, TracePolyTable};
 
 mod channel;
@@ -96,6 +105,23 @@ use channel::ProverChannel;
 mod errors;
 pub use errors::ProverError;
 
+mod sink;
+pub use sink::ProofSink;
+use sink::ProofStream;
+
+mod dump;
+#[cfg(feature = "std")]
+pub use dump::ArtifactDirectory;
+pub use dump::ArtifactSink;
+use dump::ArtifactWriter;
+
+mod stats;
+use stats::Stopwatch;
+pub use stats::{ProvingStats, StageStats};
+
+#[cfg(all(feature = "std", target_family = "wasm"))]
+mod timer;
+
 #[cfg(test)]
 pub mod tests;
 
@@ -150,6 +176,22 @@ pub trait Prover {
     // PROVIDED METHODS
     // --------------------------------------------------------------------------------------------
 
+    /// Returns a table of periodic column values which the prover can use instead of building
+    /// a new one for every proof.
+    ///
+    /// Periodic columns of the AIR are expanded over the constraint evaluation domain once per
+    /// proof. Provers which generate many proofs for the same AIR can build a
+    /// [PeriodicValueTable] once (e.g., for an instance of the AIR with the trace length and proof
+    /// options used for all proofs), keep it in the prover, and return it from this method. A
+    /// table which is not compatible with the AIR of a given proof (see
+    /// [PeriodicValueTable::is_compatible_with()]) is ignored, and a new table is built for the
+    /// proof.
+    ///
+    /// The default implementation returns None, and thus, the table is built for every proof.
+    fn periodic_values(&self) -> Option<&PeriodicValueTable<Self::BaseField>> {
+        None
+    }
+
     /// Returns a STARK proof attesting to a correct execution of a computation defined by the
     /// provided trace.
     ///
@@ -157,38 +199,250 @@ pub trait Prover {
     /// the computation described by [Self::Air](Prover::Air) and generated using some set of
     /// secret and public inputs. Public inputs must match the value returned from
     /// [Self::get_pub_inputs()](Prover::get_pub_inputs) for the provided trace.
-    #[rustfmt::skip]
+    ///
+    /// This method carries out all steps of proof generation: it validates proof options against
+    /// the trace and the hash function, instantiates the AIR and the prover channel, commits to
+    /// the trace (validating it against the AIR in debug builds), evaluates constraints, and
+    /// builds the DEEP composition polynomial and the FRI proof. Thus, implementing the required
+    /// methods of this trait is sufficient to generate proofs for a computation.
+    ///
+    /// # Errors
+    /// Returns an error if the proof options cannot be used with the trace or the hash function
+    /// (e.g., the LDE domain is too large for the base field), or if the selected field extension
+    /// is not supported by the base field.
     fn prove(&self, trace: Self::Trace) -> Result<StarkProof, ProverError> {
+        let proof = self.prove_with_sink(trace, None, None, None, &mut ProvingStats::default())?;
+        Ok(proof.expect("proof must be built when no sink is specified"))
+    }
+
+    /// Generates a STARK proof attesting to a correct execution of a computation defined by the
+    /// provided trace, and writes the proof into the specified `sink`.
+    ///
+    /// The bytes written into the `sink` are the same as the bytes returned by
+    /// [StarkProof::to_bytes()] for the proof generated by [prove()](Prover::prove). However,
+    /// proof components are written into the `sink` as soon as they are generated: the proof
+    /// context is written before the prover starts working on the trace, commitments are written
+    /// as soon as they are computed, and query openings (including every FRI layer) are written
+    /// one by one and discarded afterwards. Thus, the full proof is never assembled in memory,
+    /// and the proof can be sent over the network while the prover is still running.
+    ///
+    /// # Errors
+    /// In addition to the errors returned by [prove()](Prover::prove), returns an error if the
+    /// proof could not be written into the `sink`; in such a case, the `sink` may contain a
+    /// partially written proof.
+    fn prove_into<S: ProofSink>(
+        &self,
+        trace: Self::Trace,
+        sink: &mut S,
+    ) -> Result<(), ProverError> {
+        self.prove_with_sink(trace, Some(sink), None, None, &mut ProvingStats::default())?;
+        Ok(())
+    }
+
+    /// Returns a STARK proof attesting to a correct execution of a computation defined by the
+    /// provided trace together with a report describing the work performed to generate it.
+    ///
+    /// The returned proof is the same as the proof returned by [prove()](Prover::prove) for the
+    /// same trace. The [ProvingStats] report contains wall time of each stage of proof
+    /// generation, the number of FFTs performed, the number of bytes hashed, and an estimate of
+    /// the peak memory used by the prover; collecting these does not add any measurable overhead.
+    ///
+    /// # Errors
+    /// Returns the same errors as [prove()](Prover::prove).
+    fn prove_with_stats(
+        &self,
+        trace: Self::Trace,
+    ) -> Result<(StarkProof, ProvingStats), ProverError> {
+        let mut stats = ProvingStats::default();
+        let proof = self.prove_with_sink(trace, None, None, None, &mut stats)?;
+        let proof = proof.expect("proof must be built when no sink is specified");
+        Ok((proof, stats))
+    }
+
+    /// Returns a STARK proof attesting to a correct execution of a computation defined by the
+    /// provided trace, and writes intermediate artifacts of proof generation into the specified
+    /// `artifacts` sink.
+    ///
+    /// The returned proof is the same as the proof returned by [prove()](Prover::prove) for the
+    /// same trace. The artifacts include trace polynomials, constraint evaluations before and
+    /// after division by constraint divisors, the constraint composition polynomial, and
+    /// evaluations of every FRI layer (see [ArtifactSink] for the list of artifacts and their
+    /// format). Thus, they can be used to analyze failed proofs offline, or to compare the prover
+    /// against other implementations of the protocol. Encoding of the artifacts is expensive, and
+    /// thus, this method should be used for debugging purposes only.
+    ///
+    /// # Errors
+    /// In addition to the errors returned by [prove()](Prover::prove), returns an error if an
+    /// artifact could not be written into the sink.
+    fn prove_with_artifacts(
+        &self,
+        trace: Self::Trace,
+        artifacts: &mut dyn ArtifactSink,
+    ) -> Result<StarkProof, ProverError> {
+        let proof = self.prove_with_sink(
+            trace,
+            None,
+            Some(artifacts),
+            None,
+            &mut ProvingStats::default(),
+        )?;
+        Ok(proof.expect("proof must be built when no sink is specified"))
+    }
+
+    /// Returns a STARK proof attesting to a correct execution of a computation defined by the
+    /// provided trace together with the transcript of the public coin used to generate it.
+    ///
+    /// The returned proof is the same as the proof returned by [prove()](Prover::prove) for the
+    /// same trace. The [Transcript] contains every seed of the public coin and every challenge
+    /// drawn from it (see [RandomCoin::start_transcript()](crypto::RandomCoin::start_transcript)).
+    /// A valid proof produces the same transcript when it is replayed through the verifier's
+    /// public coin by the `replay_transcript()` function of the verifier; thus, if the verifier
+    /// rejects a proof, [Transcript::diff()] of the two transcripts points to the first operation
+    /// on which the prover and the verifier disagree.
+    ///
+    /// # Errors
+    /// Returns the same errors as [prove()](Prover::prove).
+    fn prove_with_transcript(
+        &self,
+        trace: Self::Trace,
+    ) -> Result<(StarkProof, Transcript), ProverError> {
+        let mut transcript = Transcript::new();
+        let proof = self.prove_with_sink(
+            trace,
+            None,
+            None,
+            Some(&mut transcript),
+            &mut ProvingStats::default(),
+        )?;
+        let proof = proof.expect("proof must be built when no sink is specified");
+        Ok((proof, transcript))
+    }
+
+    /// Returns a chain of STARK proofs attesting to `num_steps` consecutive executions of a step
+    /// function starting with the specified `input_state`.
+    ///
+    /// For every step, `build_trace` is invoked with the input state of the step, and must return
+    /// an execution trace of the step function for this state. The output state of the step
+    /// (as defined by public inputs of the trace) becomes the input state of the next step. Each
+    /// trace is proved via [prove()](Prover::prove), and the resulting proofs are collected
+    /// together with their public inputs into a [ProofChain], which can be verified via
+    /// `verify_chain()` function of the verifier.
+    ///
+    /// # Errors
+    /// In addition to the errors returned by [prove()](Prover::prove), returns an error if the
+    /// input state defined by public inputs of a trace returned by `build_trace` is different
+    /// from the state which was passed to `build_trace`.
+    fn prove_chain<F>(
+        &self,
+        input_state: <<Self::Air as Air>::PublicInputs as StepPublicInputs>::State,
+        num_steps: usize,
+        mut build_trace: F,
+    ) -> Result<ProofChain<<Self::Air as Air>::PublicInputs>, ProverError>
+    where
+        <Self::Air as Air>::PublicInputs: StepPublicInputs,
+        F: FnMut(&<<Self::Air as Air>::PublicInputs as StepPublicInputs>::State) -> Self::Trace,
+    {
+        let mut chain = ProofChain::new();
+        let mut state = input_state;
+        for i in 0..num_steps {
+            let trace = build_trace(&state);
+            let pub_inputs = self.get_pub_inputs(&trace);
+            if pub_inputs.input_state() != state {
+                return Err(ProverError::BrokenChainLink(i));
+            }
+            state = pub_inputs.output_state();
+            chain.push(self.prove(trace)?, pub_inputs);
+        }
+        Ok(chain)
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Validates proof parameters and dispatches proof generation to the generic proof generation
+    /// procedure for the extension field specified by proof options.
+    ///
+    /// If a `sink` is specified, the proof is written into the sink and None is returned;
+    /// otherwise, the generated proof is returned. Intermediate artifacts are written into the
+    /// `artifacts` sink (if specified), the transcript of the public coin is recorded into
+    /// `transcript` (if specified), and statistics of proof generation are recorded into `stats`.
+    #[doc(hidden)]
+    #[rustfmt::skip]
+    fn prove_with_sink(
+        &self,
+        trace: Self::Trace,
+        sink: Option<&mut dyn ProofSink>,
+        artifacts: Option<&mut dyn ArtifactSink>,
+        transcript: Option<&mut Transcript>,
+        stats: &mut ProvingStats,
+    ) -> Result<Option<StarkProof>, ProverError> {
+        // make sure all domains of the computation can be built in the base field before doing
+        // any work
+        AirContext::<Self::BaseField>::validate_domains(&trace.get_info(), self.options())
+            .map_err(ProverError::InvalidDomain)?;
+
+        // make sure the hash function produces digests of the size specified by proof options
+        if let Some(digest_size) = self.options().digest_size() {
+            let hash_digest_size = <Self::HashFn as Hasher>::Digest::default().to_bytes().len();
+            if digest_size != hash_digest_size {
+                return Err(ProverError::InconsistentDigestSize(
+                    digest_size,
+                    hash_digest_size,
+                ));
+            }
+        }
+
         // figure out which version of the generic proof generation procedure to run. this is a sort
         // of static dispatch for selecting two generic parameter: extension field and hash function.
         match self.options().field_extension() {
-            FieldExtension::None => self.generate_proof::<Self::BaseField>(trace),
+            FieldExtension::None => {
+                self.generate_proof::<Self::BaseField>(trace, sink, artifacts, transcript, stats)
+            }
             FieldExtension::Quadratic => {
                 if !<QuadExtension<Self::BaseField>>::is_supported() {
                     return Err(ProverError::UnsupportedFieldExtension(2));
                 }
-                self.generate_proof::<QuadExtension<Self::BaseField>>(trace)
+                self.generate_proof::<QuadExtension<Self::BaseField>>(trace, sink, artifacts, transcript, stats)
             }
             FieldExtension::Cubic => {
                 if !<CubeExtension<Self::BaseField>>::is_supported() {
                     return Err(ProverError::UnsupportedFieldExtension(3));
                 }
-                self.generate_proof::<CubeExtension<Self::BaseField>>(trace)
+                self.generate_proof::<CubeExtension<Self::BaseField>>(trace, sink, artifacts, transcript, stats)
             }
         }
     }
 
-    // HELPER METHODS
-    // --------------------------------------------------------------------------------------------
-
     /// Performs the actual proof generation procedure, generating the proof that the provided
     /// execution `trace` is valid against this prover's AIR.
+    ///
+    /// If a `sink` is specified, proof components are written into the sink as they are generated
+    /// and None is returned; otherwise, the generated proof is returned. Intermediate artifacts
+    /// are written into the `artifacts` sink (if specified), the transcript of the public coin is
+    /// recorded into `transcript` (if specified), and statistics of proof generation are recorded
+    /// into `stats`.
     /// TODO: make this function un-callable externally?
     #[doc(hidden)]
-    fn generate_proof<E>(&self, mut trace: Self::Trace) -> Result<StarkProof, ProverError>
+    fn generate_proof<E>(
+        &self,
+        mut trace: Self::Trace,
+        sink: Option<&mut dyn ProofSink>,
+        artifacts: Option<&mut dyn ArtifactSink>,
+        transcript: Option<&mut Transcript>,
+        stats: &mut ProvingStats,
+    ) -> Result<Option<StarkProof>, ProverError>
     where
         E: FieldElement<BaseField = Self::BaseField>,
     {
+        let _span = enter_span!(
+            "prove",
+            trace_width = trace.main_trace_width(),
+            trace_length = trace.length(),
+            blowup_factor = self.options().blowup_factor(),
+            num_queries = self.options().num_queries(),
+            field_extension = self.options().field_extension().degree()
+        );
+
         // 0 ----- instantiate AIR and prover channel ---------------------------------------------
 
         // serialize public inputs; these will be included in the seed for the public coin
@@ -205,8 +459,30 @@ pub trait Prover {
         // verifier; the channel will be used to commit to values and to draw randomness that
         // should come from the verifier.
         let mut channel = ProverChannel::<Self::Air, E, Self::HashFn>::new(&air, pub_inputs_bytes);
+        if transcript.is_some() {
+            channel.start_transcript();
+        }
+        let merkle_arity = air.options().merkle_arity();
+
+        // if the proof is streamed into a sink, write the proof context right away; commitments
+        // are written as soon as they are added to the channel. there is one commitment for each
+        // trace segment, one for constraint evaluations, and one for each FRI layer (including
+        // the remainder)
+        let mut stream = ProofStream::new(sink);
+        let mut artifacts = ArtifactWriter::new(artifacts);
+        if stream.is_enabled() {
+            let num_commitments = air.trace_layout().num_segments()
+                + air
+                    .options()
+                    .to_fri_options()
+                    .num_fri_layers(air.lde_domain_size())
+                + 2;
+            let digest_size = <Self::HashFn as Hasher>::Digest::default().to_bytes().len();
+            stream.write_header(channel.context(), num_commitments * digest_size)?;
+        }
 
         // 1 ----- Commit to the execution trace --------------------------------------------------
+        let stopwatch = Stopwatch::start();
 
         // build computation domain; this is used later for polynomial evaluations
         #[cfg(feature = "std")]
@@ -222,10 +498,18 @@ pub trait Prover {
         // extend the main execution trace and build a Merkle tree from the extended trace
         let (main_trace_lde, main_trace_tree, main_trace_polys) =
             self.build_trace_commitment::<Self::BaseField>(trace.main_segment(), &domain);
+        stats.add_trace_segment::<_, Self::HashFn>(
+            &main_trace_polys,
+            &main_trace_lde,
+            merkle_arity,
+            air.options().trace_rows_per_leaf(),
+        );
+        artifacts.write_columns("trace_polys_main", main_trace_polys.columns())?;
 
         // commit to the LDE of the main trace by writing the root of its Merkle tree into
         // the channel
         channel.commit_trace(*main_trace_tree.root());
+        stream.write_commitments(channel.commitments())?;
 
         // initialize trace commitment and trace polynomial table structs with the main trace
         // data; for multi-segment traces these structs will be used as accumulators of all
@@ -234,6 +518,7 @@ pub trait Prover {
             main_trace_lde,
             main_trace_tree,
             domain.trace_to_lde_blowup(),
+            air.options().trace_rows_per_leaf(),
         );
         let mut trace_polys = TracePolyTable::new(main_trace_polys);
 
@@ -242,6 +527,7 @@ pub trait Prover {
         let mut aux_trace_segments = Vec::new();
         let mut aux_trace_rand_elements = AuxTraceRandElements::new();
         for i in 0..trace.layout().num_aux_segments() {
+            let _span = enter_span!("aux_trace_segment", segment = i);
             #[cfg(feature = "std")]
             let now = Instant::now();
 
@@ -263,10 +549,19 @@ pub trait Prover {
             // extend the auxiliary trace segment and build a Merkle tree from the extended trace
             let (aux_segment_lde, aux_segment_tree, aux_segment_polys) =
                 self.build_trace_commitment::<E>(&aux_segment, &domain);
+            stats.add_trace_segment::<_, Self::HashFn>(
+                &aux_segment_polys,
+                &aux_segment_lde,
+                merkle_arity,
+                air.options().trace_rows_per_leaf(),
+            );
+            artifacts
+                .write_columns(&format!("trace_polys_aux_{i}"), aux_segment_polys.columns())?;
 
             // commit to the LDE of the extended auxiliary trace segment  by writing the root of
             // its Merkle tree into the channel
             channel.commit_trace(*aux_segment_tree.root());
+            stream.write_commitments(channel.commitments())?;
 
             // append the segment to the trace commitment and trace polynomial table structs
             trace_commitment.add_segment(aux_segment_lde, aux_segment_tree);
@@ -280,6 +575,7 @@ pub trait Prover {
         // mode only because this is a very expensive operation.
         #[cfg(debug_assertions)]
         trace.validate(&air, &aux_trace_segments, &aux_trace_rand_elements);
+        stats.add_stage("trace_commitment", stopwatch);
 
         // 2 ----- evaluate constraints -----------------------------------------------------------
         // evaluate constraints specified by the AIR over the constraint evaluation domain, and
@@ -288,19 +584,52 @@ pub trait Prover {
         // identical denominators are merged together. the results are saved into a constraint
         // evaluation table where each column contains merged evaluations of constraints with
         // identical denominators.
+        let stopwatch = Stopwatch::start();
         #[cfg(feature = "std")]
         let now = Instant::now();
-        let constraint_coeffs = channel.get_constraint_composition_coeffs();
-        let evaluator = ConstraintEvaluator::new(&air, aux_trace_rand_elements, constraint_coeffs);
-        let constraint_evaluations = evaluator.evaluate(trace_commitment.trace_table(), &domain);
+        let built_periodic_values;
+        let periodic_values = match self.periodic_values() {
+            Some(table) if table.is_compatible_with(&air) => table,
+            _ => {
+                built_periodic_values = PeriodicValueTable::new(&air);
+                &built_periodic_values
+            }
+        };
+        let constraint_evaluations = {
+            let _span = enter_span!(
+                "evaluate_constraints",
+                domain_size = domain.ce_domain_size()
+            );
+            let constraint_coeffs = channel.get_constraint_composition_coeffs();
+            let evaluator = constraints::ConstraintEvaluator::new(
+                &air,
+                aux_trace_rand_elements,
+                constraint_coeffs,
+                periodic_values,
+            );
+            evaluator.evaluate(trace_commitment.trace_table(), &domain)
+        };
         #[cfg(feature = "std")]
         debug!(
             "Evaluated constraints over domain of 2^{} elements in {} ms",
             log2(constraint_evaluations.num_rows()),
             now.elapsed().as_millis()
         );
+        let constraint_evaluations_size = constraint_evaluations.num_rows()
+            * constraint_evaluations.num_columns()
+            * E::ELEMENT_BYTES;
+        stats.allocate(constraint_evaluations_size);
+        stats.add_stage("constraint_evaluation", stopwatch);
+        if artifacts.is_enabled() {
+            let columns = constraint_evaluations.columns().iter();
+            artifacts.write_columns("constraint_evaluations", columns.map(|c| c.as_slice()))?;
+            let columns = constraint_evaluations.divided_columns();
+            let columns = columns.iter().map(|c| c.as_slice());
+            artifacts.write_columns("constraint_evaluations_divided", columns)?;
+        }
 
         // 3 ----- commit to constraint evaluations -----------------------------------------------
+        let stopwatch = Stopwatch::start();
 
         // first, build constraint composition polynomial from the constraint evaluation table:
         // - divide all constraint evaluation columns by their respective divisors
@@ -310,7 +639,15 @@ pub trait Prover {
         //   trace_length - 1
         #[cfg(feature = "std")]
         let now = Instant::now();
-        let composition_poly = constraint_evaluations.into_poly()?;
+        let composition_poly = {
+            let _span = enter_span!("build_composition_poly");
+            constraint_evaluations.into_poly()?
+        };
+        let composition_poly_size =
+            composition_poly.num_columns() * composition_poly.column_len() * E::ELEMENT_BYTES;
+        stats.add_ffts(1);
+        stats.allocate(composition_poly_size);
+        stats.release(constraint_evaluations_size);
         #[cfg(feature = "std")]
         debug!(
             "Converted constraint evaluations into {} composition polynomial columns of degree {} in {} ms",
@@ -318,16 +655,26 @@ pub trait Prover {
             composition_poly.column_degree(),
             now.elapsed().as_millis()
         );
+        artifacts.write_columns("composition_poly", composition_poly.data().columns())?;
 
         // then, build a commitment to the evaluations of the composition polynomial columns
         let constraint_commitment =
             self.build_constraint_commitment::<E>(&composition_poly, &domain);
+        stats.add_constraint_commitment::<E, Self::HashFn>(
+            composition_poly.num_columns(),
+            domain.lde_domain_size(),
+            merkle_arity,
+        );
 
         // then, commit to the evaluations of constraints by writing the root of the constraint
         // Merkle tree into the channel
         channel.commit_constraints(constraint_commitment.root());
+        stream.write_commitments(channel.commitments())?;
+        stats.add_stage("constraint_commitment", stopwatch);
 
         // 4 ----- build DEEP composition polynomial ----------------------------------------------
+        let span = enter_span!("build_deep_composition_poly");
+        let stopwatch = Stopwatch::start();
         #[cfg(feature = "std")]
         let now = Instant::now();
 
@@ -365,6 +712,18 @@ pub trait Prover {
         // trace_length - 1
         deep_composition_poly.adjust_degree();
 
+        // trace polynomials and composition polynomial columns have been merged into the DEEP
+        // composition polynomial
+        let trace_layout = air.trace_layout();
+        let deep_poly_size = domain.trace_length() * E::ELEMENT_BYTES;
+        stats.allocate(deep_poly_size);
+        stats.release(
+            domain.trace_length()
+                * (trace_layout.main_trace_width() * Self::BaseField::ELEMENT_BYTES
+                    + trace_layout.aux_trace_width() * E::ELEMENT_BYTES),
+        );
+        stats.release(composition_poly_size);
+
         #[cfg(feature = "std")]
         debug!(
             "Built DEEP composition polynomial of degree {} in {} ms",
@@ -375,11 +734,18 @@ pub trait Prover {
         // make sure the degree of the DEEP composition polynomial is equal to trace polynomial
         // degree
         assert_eq!(domain.trace_length() - 1, deep_composition_poly.degree());
+        span.exit();
 
         // 5 ----- evaluate DEEP composition polynomial over LDE domain ---------------------------
         #[cfg(feature = "std")]
         let now = Instant::now();
-        let deep_evaluations = deep_composition_poly.evaluate(&domain);
+        let deep_evaluations = {
+            let _span = enter_span!(
+                "evaluate_deep_composition_poly",
+                domain_size = domain.lde_domain_size()
+            );
+            deep_composition_poly.evaluate(&domain)
+        };
         // we check the following condition in debug mode only because infer_degree is an expensive
         // operation
         debug_assert_eq!(
@@ -392,12 +758,38 @@ pub trait Prover {
             log2(domain.lde_domain_size()),
             now.elapsed().as_millis()
         );
+        let deep_evaluations_size = deep_evaluations.len() * E::ELEMENT_BYTES;
+        stats.add_ffts(1);
+        stats.allocate(deep_evaluations_size);
+        stats.release(deep_poly_size);
+        stats.add_stage("deep_composition", stopwatch);
 
         // 6 ----- compute FRI layers for the composition polynomial ------------------------------
+        let stopwatch = Stopwatch::start();
         #[cfg(feature = "std")]
         let now = Instant::now();
-        let mut fri_prover = FriProver::new(air.options().to_fri_options());
-        fri_prover.build_layers(&mut channel, deep_evaluations);
+        let fri_options = air.options().to_fri_options();
+        let fri_domain_size = deep_evaluations.len();
+        let mut fri_prover = FriProver::new(fri_options.clone());
+        {
+            let _span = enter_span!("build_fri_layers", domain_size = fri_domain_size);
+            fri_prover.build_layers(&mut channel, deep_evaluations);
+        }
+        stream.write_commitments(channel.commitments())?;
+        stats.add_fri_layers::<E, Self::HashFn>(
+            &fri_options,
+            fri_domain_size,
+            fri_prover.num_layers(),
+        );
+        stats.release(deep_evaluations_size);
+        stats.add_stage("fri_layers", stopwatch);
+        if artifacts.is_enabled() {
+            for i in 0..fri_prover.num_layers() {
+                let evaluations = fri_prover.layer_evaluations(i);
+                artifacts.write_columns(&format!("fri_layer_{i}"), [evaluations.as_slice()])?;
+            }
+            artifacts.write_columns("fri_remainder", [fri_prover.remainder()])?;
+        }
         #[cfg(feature = "std")]
         debug!(
             "Computed {} FRI layers from composition polynomial evaluations in {} ms",
@@ -406,25 +798,65 @@ pub trait Prover {
         );
 
         // 7 ----- determine query positions ------------------------------------------------------
+        let stopwatch = Stopwatch::start();
         #[cfg(feature = "std")]
         let now = Instant::now();
 
-        // apply proof-of-work to the query seed
-        channel.grind_query_seed();
+        let query_positions = {
+            let _span = enter_span!(
+                "determine_query_positions",
+                grinding_factor = air.options().grinding_factor()
+            );
+
+            // apply proof-of-work to the query seed
+            channel.grind_query_seed();
 
-        // generate pseudo-random query positions
-        let query_positions = channel.get_query_positions();
+            // generate pseudo-random query positions
+            channel.get_query_positions()
+        };
         #[cfg(feature = "std")]
         debug!(
             "Determined {} query positions in {} ms",
             query_positions.len(),
             now.elapsed().as_millis()
         );
+        stats.add_stage("query_positions", stopwatch);
+
+        // all values have been drawn from the public coin at this point
+        if let Some(transcript) = transcript {
+            *transcript = channel
+                .take_transcript()
+                .expect("transcript must be recorded when requested");
+        }
 
         // 8 ----- build proof object -------------------------------------------------------------
+        let _span = enter_span!("build_proof", num_queries = query_positions.len());
+        let stopwatch = Stopwatch::start();
         #[cfg(feature = "std")]
         let now = Instant::now();
 
+        // if the proof is streamed into a sink, write the remaining proof components one by one
+        // in the order in which they appear in a serialized proof
+        if stream.is_enabled() {
+            for queries in trace_commitment.query(&query_positions) {
+                queries.write_into(&mut stream);
+            }
+            constraint_commitment
+                .query(&query_positions)
+                .write_into(&mut stream);
+            channel.ood_frame().write_into(&mut stream);
+            fri_prover.write_proof(&query_positions, &mut stream);
+            stream.write_u64(channel.pow_nonce());
+            stream.check()?;
+            #[cfg(feature = "std")]
+            debug!(
+                "Wrote remaining proof components in {} ms",
+                now.elapsed().as_millis()
+            );
+            stats.add_stage("proof_assembly", stopwatch);
+            return Ok(None);
+        }
+
         // generate FRI proof
         let fri_proof = fri_prover.build_proof(&query_positions);
 
@@ -441,8 +873,9 @@ pub trait Prover {
         let proof = channel.build_proof(trace_queries, constraint_queries, fri_proof);
         #[cfg(feature = "std")]
         debug!("Built proof object in {} ms", now.elapsed().as_millis());
+        stats.add_stage("proof_assembly", stopwatch);
 
-        Ok(proof)
+        Ok(Some(proof))
     }
 
     /// Computes a low-degree extension (LDE) of the provided execution trace over the specified
@@ -462,11 +895,21 @@ pub trait Prover {
     where
         E: FieldElement<BaseField = Self::BaseField>,
     {
+        let _span = enter_span!(
+            "commit_trace_segment",
+            num_columns = trace.num_cols(),
+            trace_length = trace.num_rows()
+        );
+
         // extend the execution trace
         #[cfg(feature = "std")]
         let now = Instant::now();
-        let trace_polys = trace.interpolate_columns();
-        let trace_lde = trace_polys.evaluate_columns_over(domain);
+        let (trace_polys, trace_lde) = {
+            let _span = enter_span!("extend_trace", domain_size = domain.lde_domain_size());
+            let trace_polys = trace.interpolate_columns();
+            let trace_lde = trace_polys.evaluate_columns_over(domain);
+            (trace_polys, trace_lde)
+        };
         #[cfg(feature = "std")]
         debug!(
             "Extended execution trace of {} columns from 2^{} to 2^{} steps ({}x blowup) in {} ms",
@@ -480,7 +923,13 @@ pub trait Prover {
         // build trace commitment
         #[cfg(feature = "std")]
         let now = Instant::now();
-        let trace_tree = trace_lde.commit_to_rows();
+        let trace_tree: MerkleTree<Self::HashFn> = {
+            let _span = enter_span!("commit_to_rows", num_rows = trace_lde.num_rows());
+            trace_lde.commit_to_row_groups(
+                self.options().trace_rows_per_leaf(),
+                self.options().merkle_arity(),
+            )
+        };
         #[cfg(feature = "std")]
         debug!(
             "Computed execution trace commitment (Merkle tree of depth {}) in {} ms",
@@ -498,7 +947,8 @@ pub trait Prover {
     /// domain.
     ///
     /// The commitment is computed by hashing each row in the evaluation matrix, and then building
-    /// a Merkle tree from the resulting hashes.
+    /// a Merkle tree from the resulting hashes. Rows consist of extension field elements, and
+    /// they are hashed natively via [ElementHasher::hash_iter()] without being serialized first.
     fn build_constraint_commitment<E>(
         &self,
         composition_poly: &CompositionPoly<E>,
@@ -507,6 +957,12 @@ pub trait Prover {
     where
         E: FieldElement<BaseField = Self::BaseField>,
     {
+        let _span = enter_span!(
+            "commit_constraints",
+            num_columns = composition_poly.num_columns(),
+            domain_size = domain.lde_domain_size()
+        );
+
         // evaluate composition polynomial columns over the LDE domain
         #[cfg(feature = "std")]
         let now = Instant::now();
@@ -522,7 +978,8 @@ pub trait Prover {
         // build constraint evaluation commitment
         #[cfg(feature = "std")]
         let now = Instant::now();
-        let commitment = composed_evaluations.commit_to_rows();
+        let commitment: MerkleTree<Self::HashFn> =
+            composed_evaluations.commit_to_rows(self.options().merkle_arity());
         let constraint_commitment = ConstraintCommitment::new(composed_evaluations, commitment);
         #[cfg(feature = "std")]
         debug!(
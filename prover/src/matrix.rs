@@ -5,12 +5,24 @@
 
 use super::StarkDomain;
 use core::{iter::FusedIterator, slice};
-use crypto::{ElementHasher, MerkleTree};
+use crypto::{ElementHasher, Hashable, VectorCommitment};
 use math::{fft, polynom, FieldElement};
-use utils::{batch_iter_mut, collections::Vec, iter, iter_mut, uninit_vector};
+use utils::{collections::Vec, iter, iter_mut, UninitBuffer};
 
 #[cfg(feature = "concurrent")]
-use utils::iterators::*;
+use utils::{iterators::*, rayon};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Maximum number of columns evaluated together when evaluating column polynomials at a single
+/// point.
+const EVAL_BATCH_SIZE: usize = 8;
+
+/// Minimum number of coefficients in a batch of columns evaluated at a single point; smaller
+/// batches are not worth evaluating in a separate thread.
+#[cfg(feature = "concurrent")]
+const MIN_EVAL_BATCH_COEFFICIENTS: usize = 1 << 12;
 
 // MATRIX
 // ================================================================================================
@@ -27,6 +39,12 @@ use utils::iterators::*;
 /// - A matrix must consist of at least 1 column and at least 2 rows.
 /// - All columns must be of the same length.
 /// - Number of rows must be a power of two.
+///
+/// When `secret-hygiene` feature is enabled, all values of a matrix are overwritten with ZEROs
+/// when the matrix is dropped. Execution traces, their low-degree extensions, trace polynomials,
+/// and constraint composition polynomials are all stored in matrixes. Columns moved out of a
+/// matrix via [Matrix::into_columns()] are not covered by this, and neither are other buffers
+/// derived from matrix values (e.g., the DEEP composition polynomial and FRI layers).
 #[derive(Debug, Clone)]
 pub struct Matrix<E: FieldElement> {
     columns: Vec<Vec<E>>,
@@ -131,12 +149,12 @@ impl<E: FieldElement> Matrix<E> {
     // --------------------------------------------------------------------------------------------
 
     /// Returns an iterator over the columns of this matrix.
-    pub fn columns(&self) -> ColumnIter<E> {
+    pub fn columns(&self) -> ColumnIter<'_, E> {
         ColumnIter::new(self)
     }
 
     /// Returns a mutable iterator over the columns of this matrix.
-    pub fn columns_mut(&mut self) -> ColumnIterMut<E> {
+    pub fn columns_mut(&mut self) -> ColumnIterMut<'_, E> {
         ColumnIterMut::new(self)
     }
 
@@ -194,11 +212,36 @@ impl<E: FieldElement> Matrix<E> {
     pub fn evaluate_columns_over(&self, domain: &StarkDomain<E::BaseField>) -> Self {
         let columns = iter!(self.columns)
             .map(|poly| {
-                fft::evaluate_poly_with_offset(
+                fft::evaluate_poly_with_offset_powers(
                     poly,
                     domain.trace_twiddles(),
+                    domain.trace_offset_powers(),
+                )
+            })
+            .collect();
+        Self { columns }
+    }
+
+    /// Evaluates polynomials contained in the columns of this matrix at the specified positions
+    /// of the LDE domain defined by the specified [StarkDomain] and returns the result.
+    ///
+    /// Row `i` of the returned matrix is the same as the row at position `positions[i]` of the
+    /// matrix returned by [evaluate_columns_over()](Matrix::evaluate_columns_over), but the
+    /// polynomials are not evaluated over the entire LDE domain (see
+    /// [fft::evaluate_poly_at_positions()]). This is useful when only a few rows of the extended
+    /// matrix are needed, e.g., rows at positions queried by the verifier.
+    pub fn evaluate_columns_at_positions(
+        &self,
+        domain: &StarkDomain<E::BaseField>,
+        positions: &[usize],
+    ) -> Self {
+        let columns = iter!(self.columns)
+            .map(|poly| {
+                fft::evaluate_poly_at_positions(
+                    poly,
+                    domain.lde_domain_size(),
                     domain.offset(),
-                    domain.trace_to_lde_blowup(),
+                    positions,
                 )
             })
             .collect();
@@ -206,11 +249,19 @@ impl<E: FieldElement> Matrix<E> {
     }
 
     /// Evaluates polynomials contained in the columns of this matrix at a single point `x`.
+    ///
+    /// Columns are evaluated in batches of up to 8 columns using [polynom::eval_batch()]; when
+    /// `concurrent` feature is enabled, the batches are evaluated in multiple threads, and the
+    /// columns are spread across batches so that all available threads are used.
     pub fn evaluate_columns_at<F>(&self, x: F) -> Vec<F>
     where
         F: FieldElement + From<E>,
     {
-        iter!(self.columns).map(|p| polynom::eval(p, x)).collect()
+        let batch_size = get_eval_batch_size(self.num_cols(), self.num_rows());
+        let batches = self.columns.chunks(batch_size).collect::<Vec<_>>();
+        iter!(batches)
+            .flat_map(|batch| polynom::eval_batch(batch, x))
+            .collect()
     }
 
     // COMMITMENTS
@@ -220,34 +271,56 @@ impl<E: FieldElement> Matrix<E> {
     ///
     /// The commitment is built as follows:
     /// * Each row of the matrix is hashed into a single digest of the specified hash function.
-    /// * The resulting values are used to built a binary Merkle tree such that each row digest
-    ///   becomes a leaf in the tree. Thus, the number of leaves in the tree is equal to the
-    ///   number of rows in the matrix.
-    /// * The resulting Merkle tree is return as the commitment to the entire matrix.
-    pub fn commit_to_rows<H>(&self) -> MerkleTree<H>
+    /// * The resulting values are committed to using the vector commitment scheme `V`
+    ///   instantiated with the specified `options` (e.g., a Merkle tree in which each row digest
+    ///   becomes a leaf). Thus, the length of the committed vector is equal to the number of rows
+    ///   in the matrix.
+    /// * The resulting vector commitment is returned as the commitment to the entire matrix.
+    pub fn commit_to_rows<H, V>(&self, options: V::Options) -> V
     where
         H: ElementHasher<BaseField = E::BaseField>,
+        V: VectorCommitment<H>,
     {
-        // allocate vector to store row hashes
-        let mut row_hashes = unsafe { uninit_vector::<H::Digest>(self.num_rows()) };
-
-        // iterate though matrix rows, hashing each row; the hashing is done by first copying a
-        // row into row_buf to avoid heap allocations, and then by applying the hash function to
-        // the buffer.
-        batch_iter_mut!(
-            &mut row_hashes,
+        self.commit_to_row_groups(1, options)
+    }
+
+    /// Returns a commitment to this matrix in which each committed item corresponds to a group
+    /// of `rows_per_group` adjacent rows.
+    ///
+    /// Item `i` is a hash of rows `i * rows_per_group..(i + 1) * rows_per_group` of the matrix
+    /// laid out one after another (i.e., the elements are hashed in row-major order). Thus, the
+    /// length of the committed vector is equal to the number of rows divided by
+    /// `rows_per_group`.
+    ///
+    /// # Panics
+    /// Panics if `rows_per_group` is zero or does not divide the number of rows in this matrix.
+    pub fn commit_to_row_groups<H, V>(&self, rows_per_group: usize, options: V::Options) -> V
+    where
+        H: ElementHasher<BaseField = E::BaseField>,
+        V: VectorCommitment<H>,
+    {
+        assert!(
+            rows_per_group > 0 && self.num_rows() % rows_per_group == 0,
+            "number of rows must be divisible by the number of rows per group"
+        );
+
+        // hash groups of matrix rows; elements of each row are fed into the hash function
+        // directly from the columns, and thus, rows do not need to be copied into a separate
+        // buffer first
+        let num_groups = self.num_rows() / rows_per_group;
+        let row_hashes = UninitBuffer::<H::Digest>::new(num_groups).fill_batches(
             128, // min batch size
-            |batch: &mut [H::Digest], batch_offset: usize| {
-                let mut row_buf = vec![E::ZERO; self.num_cols()];
-                for (i, row_hash) in batch.iter_mut().enumerate() {
-                    self.read_row_into(i + batch_offset, &mut row_buf);
-                    *row_hash = H::hash_elements(&row_buf);
-                }
-            }
+            |batch| {
+                batch.map(|group_idx| {
+                    let group =
+                        RowGroup::new(&self.columns, group_idx * rows_per_group, rows_per_group);
+                    Hashable::<H>::hash_leaf(&group)
+                })
+            },
         );
 
-        // build Merkle tree out of hashed rows
-        MerkleTree::new(row_hashes).expect("failed to construct trace Merkle tree")
+        // commit to the vector of hashed row groups
+        V::with_options(row_hashes, options).expect("failed to construct trace commitment")
     }
 
     // CONVERSIONS
@@ -255,12 +328,110 @@ impl<E: FieldElement> Matrix<E> {
 
     /// Returns the columns of this matrix as a list of vectors.
     ///
+    /// When `secret-hygiene` feature is enabled, the caller is responsible for zeroizing the
+    /// returned columns.
+    ///
     /// TODO: replace this with an iterator.
-    pub fn into_columns(self) -> Vec<Vec<E>> {
-        self.columns
+    pub fn into_columns(mut self) -> Vec<Vec<E>> {
+        core::mem::take(&mut self.columns)
+    }
+}
+
+#[cfg(feature = "secret-hygiene")]
+impl<E: FieldElement> Drop for Matrix<E> {
+    fn drop(&mut self) {
+        for column in self.columns.iter_mut() {
+            utils::zeroize(column);
+        }
     }
 }
 
+// ROW GROUP
+// ================================================================================================
+
+/// A group of adjacent matrix rows committed to as a single leaf of a vector commitment.
+///
+/// A row group is hashed by feeding its elements into the hash function in row-major order
+/// directly from the columns of the matrix.
+struct RowGroup<'a, E: FieldElement> {
+    columns: &'a [Vec<E>],
+    first_row: usize,
+    num_rows: usize,
+}
+
+impl<'a, E: FieldElement> RowGroup<'a, E> {
+    fn new(columns: &'a [Vec<E>], first_row: usize, num_rows: usize) -> Self {
+        Self {
+            columns,
+            first_row,
+            num_rows,
+        }
+    }
+}
+
+impl<'a, E, H> Hashable<H> for RowGroup<'a, E>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    fn hash_leaf(&self) -> H::Digest {
+        match self.num_rows {
+            1 => H::hash_iter(self.columns.iter().map(|column| column[self.first_row])),
+            _ => H::hash_iter(RowGroupIter::new(
+                self.columns,
+                self.first_row,
+                self.num_rows,
+            )),
+        }
+    }
+}
+
+// ROW GROUP ITERATOR
+// ================================================================================================
+
+/// Iterates over elements of a group of adjacent matrix rows in row-major order.
+struct RowGroupIter<'a, E: FieldElement> {
+    columns: &'a [Vec<E>],
+    row_idx: usize,
+    col_idx: usize,
+    remaining: usize,
+}
+
+impl<'a, E: FieldElement> RowGroupIter<'a, E> {
+    fn new(columns: &'a [Vec<E>], first_row: usize, num_rows: usize) -> Self {
+        Self {
+            columns,
+            row_idx: first_row,
+            col_idx: 0,
+            remaining: num_rows * columns.len(),
+        }
+    }
+}
+
+impl<'a, E: FieldElement> Iterator for RowGroupIter<'a, E> {
+    type Item = E;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let value = self.columns[self.col_idx][self.row_idx];
+        self.col_idx += 1;
+        if self.col_idx == self.columns.len() {
+            self.col_idx = 0;
+            self.row_idx += 1;
+        }
+        self.remaining -= 1;
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, E: FieldElement> ExactSizeIterator for RowGroupIter<'a, E> {}
+
 // COLUMN ITERATOR
 // ================================================================================================
 
@@ -401,3 +572,27 @@ impl<'a, E: FieldElement> ExactSizeIterator for MultiColumnIter<'a, E> {
 }
 
 impl<'a, E: FieldElement> FusedIterator for MultiColumnIter<'a, E> {}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the number of columns which should be evaluated together when evaluating polynomials
+/// in `num_cols` columns of `num_rows` coefficients each at a single point.
+///
+/// When `concurrent` feature is enabled, columns are spread evenly across all available threads
+/// unless this would make a batch smaller than [MIN_EVAL_BATCH_COEFFICIENTS]; in either case,
+/// a batch contains at most [EVAL_BATCH_SIZE] columns.
+#[cfg(feature = "concurrent")]
+fn get_eval_batch_size(num_cols: usize, num_rows: usize) -> usize {
+    let num_threads = rayon::current_num_threads();
+    let batch_size = (num_cols + num_threads - 1) / num_threads;
+    let min_batch_size = (MIN_EVAL_BATCH_COEFFICIENTS + num_rows - 1) / num_rows.max(1);
+    batch_size.max(min_batch_size).clamp(1, EVAL_BATCH_SIZE)
+}
+
+/// Returns the number of columns which should be evaluated together when evaluating polynomials
+/// at a single point; without `concurrent` feature, this is always [EVAL_BATCH_SIZE].
+#[cfg(not(feature = "concurrent"))]
+fn get_eval_batch_size(_num_cols: usize, _num_rows: usize) -> usize {
+    EVAL_BATCH_SIZE
+}
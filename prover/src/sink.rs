@@ -0,0 +1,189 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::ProverError;
+use air::proof::{Commitments, Context, PROOF_FORMAT_VERSION};
+use utils::{ByteWriter, Serializable};
+
+#[cfg(not(feature = "std"))]
+use utils::collections::Vec;
+
+#[cfg(feature = "std")]
+use utils::string::ToString;
+
+// PROOF SINK
+// ================================================================================================
+
+/// A destination into which a serialized STARK proof is written while it is being generated.
+///
+/// A sink receives the bytes of a proof in the order in which they appear in the output of
+/// [StarkProof::to_bytes()](air::proof::StarkProof::to_bytes). When the `std` feature is enabled,
+/// this trait is implemented for all types which implement `std::io::Write`; otherwise, it is
+/// implemented for `Vec<u8>`.
+pub trait ProofSink {
+    /// Writes the specified `bytes` into this sink.
+    ///
+    /// # Errors
+    /// Returns an error if the bytes could not be written.
+    fn write_proof_bytes(&mut self, bytes: &[u8]) -> Result<(), ProverError>;
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> ProofSink for W {
+    fn write_proof_bytes(&mut self, bytes: &[u8]) -> Result<(), ProverError> {
+        self.write_all(bytes)
+            .map_err(|err| ProverError::ProofWriteFailed(err.to_string()))
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl ProofSink for Vec<u8> {
+    fn write_proof_bytes(&mut self, bytes: &[u8]) -> Result<(), ProverError> {
+        self.extend_from_slice(bytes);
+        Ok(())
+    }
+}
+
+// PROOF STREAM
+// ================================================================================================
+
+/// Writes proof components into an optional [ProofSink] as they are generated by the prover.
+///
+/// If no sink was provided, all writes are ignored. Otherwise, the first error returned by the
+/// sink is retained and all subsequent writes are ignored; the error is reported by the next call
+/// to [check()](ProofStream::check).
+pub(crate) struct ProofStream<'a> {
+    sink: Option<&'a mut dyn ProofSink>,
+    num_commitment_bytes: usize,
+    failed: bool,
+    error: Option<ProverError>,
+}
+
+impl<'a> ProofStream<'a> {
+    /// Returns a new stream which writes proof components into the specified `sink`.
+    pub fn new(sink: Option<&'a mut dyn ProofSink>) -> Self {
+        ProofStream {
+            sink,
+            num_commitment_bytes: 0,
+            failed: false,
+            error: None,
+        }
+    }
+
+    /// Returns true if proof components are written into a sink.
+    pub fn is_enabled(&self) -> bool {
+        self.sink.is_some()
+    }
+
+    /// Writes proof format version, proof context, and the length of serialized commitments.
+    ///
+    /// The commitments themselves are written as they are added to the proof via
+    /// [write_commitments()](ProofStream::write_commitments).
+    pub fn write_header(
+        &mut self,
+        context: &Context,
+        commitments_size: usize,
+    ) -> Result<(), ProverError> {
+        self.write_u8(PROOF_FORMAT_VERSION);
+        context.write_into(self);
+        self.write_usize(commitments_size);
+        self.check()
+    }
+
+    /// Writes commitments which were added to the specified `commitments` since the last time
+    /// this method was called.
+    pub fn write_commitments(&mut self, commitments: &Commitments) -> Result<(), ProverError> {
+        let bytes = commitments.as_bytes();
+        if self.num_commitment_bytes < bytes.len() {
+            self.write_u8_slice(&bytes[self.num_commitment_bytes..]);
+            self.num_commitment_bytes = bytes.len();
+        }
+        self.check()
+    }
+
+    /// Returns the first error encountered while writing into the sink (if any).
+    pub fn check(&mut self) -> Result<(), ProverError> {
+        match self.error.take() {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<'a> ByteWriter for ProofStream<'a> {
+    fn write_u8(&mut self, value: u8) {
+        self.write_u8_slice(&[value]);
+    }
+
+    fn write_u8_slice(&mut self, values: &[u8]) {
+        if self.failed {
+            return;
+        }
+        if let Some(sink) = self.sink.as_mut() {
+            if let Err(err) = sink.write_proof_bytes(values) {
+                self.failed = true;
+                self.error = Some(err);
+            }
+        }
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use crate::{tests::FibProver, FieldExtension, ProofOptions, ProofSink, Prover, ProverError};
+    use crypto::{
+        hashers::{Blake3_256, Rp64_256, Sha3_256},
+        ElementHasher, TranscriptMode,
+    };
+    use math::fields::f64::BaseElement;
+    use utils::collections::Vec;
+
+    #[test]
+    fn streamed_proofs() {
+        // a streamed proof should be the same as a serialized proof assembled in memory
+        let options = ProofOptions::new(28, 8, 0, FieldExtension::None, 4, 256);
+        check_streamed_proof::<Blake3_256<BaseElement>>(options.clone());
+        check_streamed_proof::<Sha3_256<BaseElement>>(ProofOptions::new(
+            28,
+            8,
+            0,
+            FieldExtension::Quadratic,
+            4,
+            256,
+        ));
+        check_streamed_proof::<Rp64_256>(options.clone().with_merkle_arity(4));
+        check_streamed_proof::<Blake3_256<BaseElement>>(
+            options
+                .clone()
+                .with_transcript_mode(TranscriptMode::Blake3Xof),
+        );
+
+        // errors returned by the sink should be propagated
+        let mut sink = [0u8; 64];
+        let result = prove_into::<Blake3_256<BaseElement>>(options, &mut &mut sink[..]);
+        assert!(matches!(result, Err(ProverError::ProofWriteFailed(_))));
+    }
+
+    fn check_streamed_proof<H: ElementHasher<BaseField = BaseElement>>(options: ProofOptions) {
+        let prover = FibProver::<H>::new(options.clone());
+        let expected = prover.prove(prover.build_trace(64)).unwrap().to_bytes();
+
+        let mut streamed = Vec::new();
+        prove_into::<H>(options, &mut streamed).unwrap();
+        assert_eq!(expected, streamed);
+    }
+
+    fn prove_into<H: ElementHasher<BaseField = BaseElement>>(
+        options: ProofOptions,
+        sink: &mut impl ProofSink,
+    ) -> Result<(), ProverError> {
+        let prover = FibProver::<H>::new(options);
+        let trace = prover.build_trace(64);
+        prover.prove_into(trace, sink)
+    }
+}
@@ -0,0 +1,340 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::Matrix;
+use core::time::Duration;
+use crypto::Hasher;
+use fri::FriOptions;
+use math::FieldElement;
+use utils::{collections::Vec, Serializable};
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+use std::time::Instant;
+
+#[cfg(all(feature = "std", target_family = "wasm"))]
+use crate::timer::Instant;
+
+// PROVING STATS
+// ================================================================================================
+
+/// A report describing the work performed by the prover to generate a single proof.
+///
+/// The report is returned by [Prover::prove_with_stats()](crate::Prover::prove_with_stats), and
+/// contains:
+/// * Wall time of each stage of proof generation. Durations are measured only when the `std`
+///   feature is enabled (and are zero on WebAssembly targets); otherwise, they are always zero.
+/// * The number of FFTs performed by the prover; interpolation or evaluation of a single
+///   polynomial counts as one FFT.
+/// * The number of bytes hashed to build commitments to the extended execution trace, the
+///   constraint evaluations, and the FRI layers (including hashing of internal Merkle tree nodes).
+/// * An estimate of the peak memory used by the prover. The estimate accounts only for the large
+///   data structures built by the prover (the execution trace, its extension and polynomials,
+///   constraint evaluations, commitment trees, FRI layers etc.), and thus, the resident set size
+///   of the process is usually somewhat larger.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProvingStats {
+    stages: Vec<StageStats>,
+    num_ffts: usize,
+    bytes_hashed: usize,
+    memory: usize,
+    peak_memory: usize,
+}
+
+impl ProvingStats {
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns stages of proof generation in the order in which they were executed.
+    pub fn stages(&self) -> &[StageStats] {
+        &self.stages
+    }
+
+    /// Returns the wall time of the stage with the specified name, or None if no such stage was
+    /// executed.
+    pub fn stage_duration(&self, name: &str) -> Option<Duration> {
+        self.stages
+            .iter()
+            .find(|stage| stage.name == name)
+            .map(|stage| stage.duration)
+    }
+
+    /// Returns the total wall time of all stages of proof generation.
+    pub fn total_duration(&self) -> Duration {
+        self.stages.iter().map(|stage| stage.duration).sum()
+    }
+
+    /// Returns the number of FFTs performed by the prover.
+    pub fn num_ffts(&self) -> usize {
+        self.num_ffts
+    }
+
+    /// Returns the number of bytes hashed by the prover to build commitments.
+    pub fn bytes_hashed(&self) -> usize {
+        self.bytes_hashed
+    }
+
+    /// Returns an estimate of the peak memory (in bytes) used by the prover.
+    pub fn peak_memory_estimate(&self) -> usize {
+        self.peak_memory
+    }
+
+    // RECORDING
+    // --------------------------------------------------------------------------------------------
+
+    /// Records a stage with the specified name which was started when the `stopwatch` was.
+    pub(crate) fn add_stage(&mut self, name: &'static str, stopwatch: Stopwatch) {
+        self.stages.push(StageStats {
+            name,
+            duration: stopwatch.elapsed(),
+        });
+    }
+
+    /// Records the specified number of FFTs.
+    pub(crate) fn add_ffts(&mut self, num_ffts: usize) {
+        self.num_ffts += num_ffts;
+    }
+
+    /// Records hashing of a vector commitment to `num_leaves` leaves of `leaf_size` bytes each;
+    /// the leaves are committed to via a Merkle tree of the specified arity.
+    pub(crate) fn add_commitment(
+        &mut self,
+        num_leaves: usize,
+        leaf_size: usize,
+        arity: usize,
+        digest_size: usize,
+    ) {
+        // every node of the tree except for the root is hashed exactly once as a child of its
+        // parent node
+        self.bytes_hashed += num_leaves * leaf_size;
+        self.bytes_hashed += merkle_tree_size(num_leaves, arity, digest_size) - digest_size;
+    }
+
+    /// Records commitment to a trace segment: interpolation of the segment columns into
+    /// `trace_polys`, their evaluation into `trace_lde`, and hashing of the extended segment into
+    /// a Merkle tree of the specified arity with `rows_per_leaf` rows in each leaf.
+    ///
+    /// The segment itself is assumed to remain in memory together with its polynomials, extension,
+    /// and the commitment tree.
+    pub(crate) fn add_trace_segment<E: FieldElement, H: Hasher>(
+        &mut self,
+        trace_polys: &Matrix<E>,
+        trace_lde: &Matrix<E>,
+        arity: usize,
+        rows_per_leaf: usize,
+    ) {
+        let num_cols = trace_lde.num_cols();
+        let num_leaves = trace_lde.num_rows() / rows_per_leaf;
+        let digest_size = digest_size::<H>();
+        self.add_ffts(2 * num_cols);
+        self.add_commitment(
+            num_leaves,
+            rows_per_leaf * num_cols * E::ELEMENT_BYTES,
+            arity,
+            digest_size,
+        );
+        self.allocate(2 * trace_polys.num_rows() * num_cols * E::ELEMENT_BYTES);
+        self.allocate(trace_lde.num_rows() * num_cols * E::ELEMENT_BYTES);
+        self.allocate(merkle_tree_size(num_leaves, arity, digest_size));
+    }
+
+    /// Records commitment to constraint evaluations: evaluation of `num_columns` composition
+    /// polynomial columns over the LDE domain, and hashing of the resulting evaluations into a
+    /// Merkle tree of the specified arity.
+    pub(crate) fn add_constraint_commitment<E: FieldElement, H: Hasher>(
+        &mut self,
+        num_columns: usize,
+        lde_domain_size: usize,
+        arity: usize,
+    ) {
+        let digest_size = digest_size::<H>();
+        self.add_ffts(num_columns);
+        self.add_commitment(
+            lde_domain_size,
+            num_columns * E::ELEMENT_BYTES,
+            arity,
+            digest_size,
+        );
+        self.allocate(lde_domain_size * num_columns * E::ELEMENT_BYTES);
+        self.allocate(merkle_tree_size(lde_domain_size, arity, digest_size));
+    }
+
+    /// Records construction of `num_layers` FRI layers from evaluations over a domain of the
+    /// specified size, as well as the commitment to the FRI remainder.
+    pub(crate) fn add_fri_layers<E: FieldElement, H: Hasher>(
+        &mut self,
+        options: &FriOptions,
+        mut domain_size: usize,
+        num_layers: usize,
+    ) {
+        let digest_size = digest_size::<H>();
+        let folding_factor = options.folding_factor();
+        for _ in 0..num_layers {
+            let num_leaves = domain_size / folding_factor;
+            self.add_commitment(
+                num_leaves,
+                folding_factor * E::ELEMENT_BYTES,
+                options.merkle_arity(),
+                digest_size,
+            );
+            self.allocate(domain_size * E::ELEMENT_BYTES);
+            self.allocate(merkle_tree_size(
+                num_leaves,
+                options.merkle_arity(),
+                digest_size,
+            ));
+            domain_size = num_leaves;
+        }
+
+        // the remainder is committed to by hashing all of its elements sequentially
+        self.add_bytes_hashed(domain_size * E::ELEMENT_BYTES);
+        self.allocate(domain_size * E::ELEMENT_BYTES);
+    }
+
+    /// Records hashing of `num_bytes` bytes outside of Merkle trees.
+    pub(crate) fn add_bytes_hashed(&mut self, num_bytes: usize) {
+        self.bytes_hashed += num_bytes;
+    }
+
+    /// Records allocation of a data structure of the specified size.
+    pub(crate) fn allocate(&mut self, num_bytes: usize) {
+        self.memory += num_bytes;
+        self.peak_memory = core::cmp::max(self.peak_memory, self.memory);
+    }
+
+    /// Records release of a data structure of the specified size.
+    pub(crate) fn release(&mut self, num_bytes: usize) {
+        self.memory = self.memory.saturating_sub(num_bytes);
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the number of bytes occupied by all nodes (including leaves and the root) of a Merkle
+/// tree of the specified arity with `num_leaves` leaves.
+pub(crate) fn merkle_tree_size(num_leaves: usize, arity: usize, digest_size: usize) -> usize {
+    let mut result = digest_size;
+    let mut num_nodes = num_leaves;
+    while num_nodes > 1 {
+        result += num_nodes * digest_size;
+        num_nodes /= core::cmp::min(arity, num_nodes);
+    }
+    result
+}
+
+/// Returns the number of bytes in a digest produced by the hash function `H`.
+fn digest_size<H: Hasher>() -> usize {
+    H::Digest::default().to_bytes().len()
+}
+
+// STAGE STATS
+// ================================================================================================
+
+/// Wall time of a single stage of proof generation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StageStats {
+    name: &'static str,
+    duration: Duration,
+}
+
+impl StageStats {
+    /// Returns the name of this stage; this is one of: `trace_commitment`,
+    /// `constraint_evaluation`, `constraint_commitment`, `deep_composition`, `fri_layers`,
+    /// `query_positions`, or `proof_assembly`.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the wall time of this stage.
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+}
+
+// STOPWATCH
+// ================================================================================================
+
+/// Measures wall time when the `std` feature is enabled; otherwise, always reports zero duration.
+pub(crate) struct Stopwatch {
+    #[cfg(feature = "std")]
+    start: Instant,
+}
+
+impl Stopwatch {
+    /// Returns a new stopwatch started at the current instant.
+    pub fn start() -> Self {
+        Self {
+            #[cfg(feature = "std")]
+            start: Instant::now(),
+        }
+    }
+
+    /// Returns the time elapsed since this stopwatch was started.
+    pub fn elapsed(&self) -> Duration {
+        #[cfg(feature = "std")]
+        return self.start.elapsed();
+
+        #[cfg(not(feature = "std"))]
+        Duration::ZERO
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use crate::{tests::FibProver, FieldExtension, ProofOptions, Prover, ProvingStats};
+    use crypto::hashers::Blake3_256;
+    use math::{fields::f64::BaseElement, FieldElement};
+    use utils::collections::Vec;
+
+    #[test]
+    fn proving_stats() {
+        let options = ProofOptions::new(28, 8, 0, FieldExtension::None, 4, 256);
+        let prover = FibProver::<Blake3_256<BaseElement>>::new(options.clone());
+        let trace = prover.build_trace(64);
+        let trace_width = trace.width();
+        let (proof, stats) = prover.prove_with_stats(trace).unwrap();
+
+        // collecting statistics should not affect the proof
+        assert_eq!(prover.prove(prover.build_trace(64)).unwrap(), proof);
+
+        // all stages should be reported in the order in which they were executed
+        let stages = stats
+            .stages()
+            .iter()
+            .map(|stage| stage.name())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                "trace_commitment",
+                "constraint_evaluation",
+                "constraint_commitment",
+                "deep_composition",
+                "fri_layers",
+                "query_positions",
+                "proof_assembly"
+            ],
+            stages
+        );
+        assert!(stats.stage_duration("fri_layers").is_some());
+        assert!(stats.stage_duration("unknown").is_none());
+        assert!(stats.total_duration() >= stats.stage_duration("trace_commitment").unwrap());
+
+        // the trace is interpolated and extended column by column; constraint evaluations are
+        // interpolated once and composition columns are extended, and the DEEP composition
+        // polynomial is evaluated once
+        assert!(stats.num_ffts() > 2 * trace_width + 2);
+
+        // at the very least, every row of the extended trace is hashed and the extended trace is
+        // kept in memory
+        let lde_domain_size = 64 / 2 * options.blowup_factor();
+        let lde_size = lde_domain_size * trace_width * BaseElement::ELEMENT_BYTES;
+        assert!(stats.bytes_hashed() > lde_size);
+        assert!(stats.peak_memory_estimate() > lde_size);
+        assert_ne!(ProvingStats::default(), stats);
+    }
+}
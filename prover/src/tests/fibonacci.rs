@@ -0,0 +1,113 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{Prover, Trace, TraceTable};
+use air::{
+    Air, AirContext, Assertion, EvaluationFrame, ProofOptions, TraceInfo,
+    TransitionConstraintDegree,
+};
+use core::marker::PhantomData;
+use crypto::ElementHasher;
+use math::{fields::f64::BaseElement, FieldElement};
+use utils::collections::Vec;
+
+// FIBONACCI AIR
+// ================================================================================================
+
+/// AIR for a Fibonacci sequence which advances by two terms per trace row.
+pub struct FibAir {
+    context: AirContext<BaseElement>,
+    result: BaseElement,
+}
+
+impl Air for FibAir {
+    type BaseField = BaseElement;
+    type PublicInputs = BaseElement;
+
+    fn new(trace_info: TraceInfo, pub_inputs: BaseElement, options: ProofOptions) -> Self {
+        let degrees = vec![
+            TransitionConstraintDegree::new(1),
+            TransitionConstraintDegree::new(1),
+        ];
+        FibAir {
+            context: AirContext::new(trace_info, degrees, 3, options),
+            result: pub_inputs,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        result[0] = next[0] - (current[0] + current[1]);
+        result[1] = next[1] - (current[1] + next[0]);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        let last_step = self.trace_length() - 1;
+        vec![
+            Assertion::single(0, 0, BaseElement::ONE),
+            Assertion::single(1, 0, BaseElement::ONE),
+            Assertion::single(1, last_step, self.result),
+        ]
+    }
+}
+
+// FIBONACCI PROVER
+// ================================================================================================
+
+/// Prover for [FibAir] instantiated with hash function `H`.
+pub struct FibProver<H: ElementHasher> {
+    options: ProofOptions,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> FibProver<H> {
+    pub fn new(options: ProofOptions) -> Self {
+        Self {
+            options,
+            _hasher: PhantomData,
+        }
+    }
+
+    /// Returns a trace for computing the first `sequence_length` terms of the sequence.
+    pub fn build_trace(&self, sequence_length: usize) -> TraceTable<BaseElement> {
+        let mut trace = TraceTable::new(2, sequence_length / 2);
+        trace.fill(
+            |state| {
+                state[0] = BaseElement::ONE;
+                state[1] = BaseElement::ONE;
+            },
+            |_, state| {
+                state[0] += state[1];
+                state[1] += state[0];
+            },
+        );
+        trace
+    }
+}
+
+impl<H: ElementHasher<BaseField = BaseElement>> Prover for FibProver<H> {
+    type BaseField = BaseElement;
+    type Air = FibAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = H;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> BaseElement {
+        trace.get(1, trace.length() - 1)
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+}
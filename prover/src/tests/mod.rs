@@ -3,13 +3,20 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::TraceTable;
+use crate::{Prover, TraceTable};
 use air::{
     Air, AirContext, Assertion, EvaluationFrame, FieldExtension, ProofOptions, TraceInfo,
     TransitionConstraintDegree,
 };
-use math::{fields::f128::BaseElement, FieldElement, StarkField};
-use utils::collections::Vec;
+use crypto::hashers::Blake3_256;
+use math::{
+    fields::{f128::BaseElement, f64},
+    FieldElement, StarkField,
+};
+use utils::{collections::Vec, ConcurrencyConfig};
+
+mod fibonacci;
+pub use fibonacci::{FibAir, FibProver};
 
 // FIBONACCI TRACE BUILDER
 // ================================================================================================
@@ -104,6 +111,41 @@ impl Air for MockAir {
     }
 }
 
+// PROVER TESTS
+// ================================================================================================
+
+#[test]
+fn prove_with_zero_concurrency_thresholds() {
+    // with all thresholds set to 0, every multi-threaded code path is taken even for inputs
+    // smaller than the number of threads
+    ConcurrencyConfig::default()
+        .with_min_fft_size(0)
+        .with_min_merkle_leaves(0)
+        .with_min_constraint_domain_size(0)
+        .set_global();
+
+    let options = ProofOptions::new(28, 8, 0, FieldExtension::None, 4, 256);
+    let prover = FibProver::<Blake3_256<f64::BaseElement>>::new(options);
+    let trace = prover.build_trace(64);
+    let pub_inputs = prover.get_pub_inputs(&trace);
+
+    // run the prover in a pool with more threads than rows in some of the processed tables
+    #[cfg(feature = "concurrent")]
+    let result = utils::rayon::ThreadPoolBuilder::new()
+        .num_threads(16)
+        .build()
+        .unwrap()
+        .install(|| prover.prove(trace));
+    #[cfg(not(feature = "concurrent"))]
+    let result = prover.prove(trace);
+
+    ConcurrencyConfig::DEFAULT.set_global();
+
+    let proof = result.expect("failed to generate proof");
+    verifier::verify::<FibAir, Blake3_256<f64::BaseElement>>(proof, pub_inputs)
+        .expect("failed to verify proof");
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
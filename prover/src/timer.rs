@@ -0,0 +1,29 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use core::time::Duration;
+
+// INSTANT
+// ================================================================================================
+
+/// A stand-in for `std::time::Instant` on WebAssembly targets.
+///
+/// The system clock is not available on `wasm32-unknown-unknown`, and `std::time::Instant::now()`
+/// panics there. Durations measured by the prover are used only for logging, and thus, this
+/// stand-in reports zero elapsed time instead.
+#[derive(Debug, Clone, Copy)]
+pub struct Instant;
+
+impl Instant {
+    /// Returns a new instant.
+    pub fn now() -> Self {
+        Instant
+    }
+
+    /// Returns zero duration.
+    pub fn elapsed(&self) -> Duration {
+        Duration::ZERO
+    }
+}
@@ -4,8 +4,9 @@
 // LICENSE file in the root directory of this source tree.
 
 use crate::Matrix;
-use air::proof::Queries;
-use crypto::{ElementHasher, MerkleTree};
+use air::proof::{get_leaf_positions, Queries};
+use core::marker::PhantomData;
+use crypto::{BatchMerkleProof, ElementHasher, MerkleTree, VectorCommitment};
 use math::FieldElement;
 use utils::collections::Vec;
 
@@ -18,32 +19,51 @@ use super::TraceLde;
 ///
 /// The describes one or more trace segments, each consisting of the following components:
 /// * Evaluations of a trace segment's polynomials over the LDE domain.
-/// * Merkle tree where each leaf in the tree corresponds to a row in the trace LDE matrix.
-pub struct TraceCommitment<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> {
+/// * Vector commitment (a Merkle tree by default) where each committed item corresponds to a row
+///   in the trace LDE matrix, or to a group of adjacent rows if more than one row is committed to
+///   per leaf. Openings of the commitment must be batch Merkle proofs, and thus, only Merkle-based
+///   vector commitments are supported.
+pub struct TraceCommitment<E, H, V = MerkleTree<H>>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    V: VectorCommitment<H, MultiProof = BatchMerkleProof<H>>,
+{
     trace_lde: TraceLde<E>,
-    main_segment_tree: MerkleTree<H>,
-    aux_segment_trees: Vec<MerkleTree<H>>,
+    main_segment_tree: V,
+    aux_segment_trees: Vec<V>,
+    rows_per_leaf: usize,
+    _hasher: PhantomData<H>,
 }
 
-impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> TraceCommitment<E, H> {
+impl<E, H, V> TraceCommitment<E, H, V>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    V: VectorCommitment<H, MultiProof = BatchMerkleProof<H>>,
+{
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
     /// Creates a new trace commitment from the provided main trace low-degree extension and the
-    /// corresponding Merkle tree commitment.
+    /// corresponding Merkle tree commitment in which each leaf contains `rows_per_leaf` adjacent
+    /// rows of the trace LDE.
     pub fn new(
         main_trace_lde: Matrix<E::BaseField>,
-        main_trace_tree: MerkleTree<H>,
+        main_trace_tree: V,
         blowup: usize,
+        rows_per_leaf: usize,
     ) -> Self {
         assert_eq!(
             main_trace_lde.num_rows(),
-            main_trace_tree.leaves().len(),
-            "number of rows in trace LDE must be the same as number of leaves in trace commitment"
+            main_trace_tree.domain_len() * rows_per_leaf,
+            "number of rows in trace LDE must be consistent with number of leaves in trace commitment"
         );
         Self {
             trace_lde: TraceLde::new(main_trace_lde, blowup),
             main_segment_tree: main_trace_tree,
             aux_segment_trees: Vec::new(),
+            rows_per_leaf,
+            _hasher: PhantomData,
         }
     }
 
@@ -51,11 +71,11 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> TraceCommitmen
     // --------------------------------------------------------------------------------------------
 
     /// Adds the provided auxiliary segment trace LDE and Merkle tree to this trace commitment.
-    pub fn add_segment(&mut self, aux_segment_lde: Matrix<E>, aux_segment_tree: MerkleTree<H>) {
+    pub fn add_segment(&mut self, aux_segment_lde: Matrix<E>, aux_segment_tree: V) {
         assert_eq!(
             aux_segment_lde.num_rows(),
-            aux_segment_tree.leaves().len(),
-            "number of rows in trace LDE must be the same as number of leaves in trace commitment"
+            aux_segment_tree.domain_len() * self.rows_per_leaf,
+            "number of rows in trace LDE must be consistent with number of leaves in trace commitment"
         );
 
         self.trace_lde.add_aux_segment(aux_segment_lde);
@@ -76,18 +96,30 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> TraceCommitmen
     // --------------------------------------------------------------------------------------------
     /// Returns trace table rows at the specified positions along with Merkle authentication paths
     /// from the commitment root to these rows.
+    ///
+    /// If more than one row is committed to per leaf, entire leaves containing the rows at the
+    /// specified positions are returned; the leaves are listed in the order in which they are
+    /// first referenced by the positions (see [get_leaf_positions()](air::proof::get_leaf_positions)).
     pub fn query(&self, positions: &[usize]) -> Vec<Queries> {
+        let leaf_positions = get_leaf_positions(positions, self.rows_per_leaf);
+
         // build queries for the main trace segment
         let mut result = vec![build_segment_queries(
             self.trace_lde.get_main_segment(),
             &self.main_segment_tree,
-            positions,
+            &leaf_positions,
+            self.rows_per_leaf,
         )];
 
         // build queries for auxiliary trace segments
         for (i, segment_tree) in self.aux_segment_trees.iter().enumerate() {
             let segment_lde = self.trace_lde.get_aux_segment(i);
-            result.push(build_segment_queries(segment_lde, segment_tree, positions));
+            result.push(build_segment_queries(
+                segment_lde,
+                segment_tree,
+                &leaf_positions,
+                self.rows_per_leaf,
+            ));
         }
 
         result
@@ -99,7 +131,7 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> TraceCommitmen
     /// Returns the root of the commitment Merkle tree.
     #[cfg(test)]
     pub fn main_trace_root(&self) -> H::Digest {
-        *self.main_segment_tree.root()
+        self.main_segment_tree.commitment()
     }
 
     /// Returns the entire trace for the column at the specified index.
@@ -112,28 +144,33 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> TraceCommitmen
 // HELPER FUNCTIONS
 // ================================================================================================
 
-fn build_segment_queries<E, H>(
+fn build_segment_queries<E, H, V>(
     segment_lde: &Matrix<E>,
-    segment_tree: &MerkleTree<H>,
-    positions: &[usize],
+    segment_tree: &V,
+    leaf_positions: &[usize],
+    rows_per_leaf: usize,
 ) -> Queries
 where
     E: FieldElement,
     H: ElementHasher<BaseField = E::BaseField>,
+    V: VectorCommitment<H, MultiProof = BatchMerkleProof<H>>,
 {
     // allocate memory for queried trace states
-    let mut trace_states = Vec::with_capacity(positions.len());
-
-    // copy values from the trace segment LDE at the specified positions into rows
-    // and append the rows to trace_states
-    for &i in positions.iter() {
-        let row = segment_lde.columns().map(|column| column[i]).collect();
-        trace_states.push(row);
+    let mut trace_states = Vec::with_capacity(leaf_positions.len());
+
+    // copy values of all rows in the leaves at the specified positions from the trace segment
+    // LDE, and append them to trace_states; the rows of each leaf are laid out one after another
+    for &leaf_idx in leaf_positions.iter() {
+        let rows = leaf_idx * rows_per_leaf..(leaf_idx + 1) * rows_per_leaf;
+        let leaf = rows
+            .flat_map(|i| segment_lde.columns().map(move |column| column[i]))
+            .collect();
+        trace_states.push(leaf);
     }
 
     // build Merkle authentication paths to the leaves specified by positions
-    let trace_proof = segment_tree
-        .prove_batch(positions)
+    let (_, trace_proof) = segment_tree
+        .open_many(leaf_positions)
         .expect("failed to generate a Merkle proof for trace queries");
 
     Queries::new(trace_proof, trace_states)
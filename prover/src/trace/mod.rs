@@ -6,6 +6,7 @@
 use super::{matrix::MultiColumnIter, Matrix};
 use air::{Air, AuxTraceRandElements, EvaluationFrame, TraceInfo, TraceLayout};
 use math::{polynom, FieldElement, StarkField};
+use utils::collections::Vec;
 
 mod trace_lde;
 pub use trace_lde::TraceLde;
@@ -14,7 +15,10 @@ mod poly_table;
 pub use poly_table::TracePolyTable;
 
 mod trace_table;
-pub use trace_table::{TraceTable, TraceTableFragment};
+pub use trace_table::{TraceTable, TraceTableFragment, TraceTableView};
+
+mod trace_builder;
+pub use trace_builder::TraceBuilder;
 
 mod commitment;
 pub use commitment::TraceCommitment;
@@ -66,6 +70,9 @@ pub trait Trace: Sized {
     /// of prior invocations of this function. Thus, for example, on the first invocation,
     /// `aux_segments` will be empty; on the second invocation, it will contain a single matrix
     /// (the one built during the first invocation) etc.
+    ///
+    /// The `rand_elements` slice is empty for segments which do not require any random elements
+    /// (e.g., segments which hold parts of the trace defined over the extension field).
     fn build_aux_segment<E: FieldElement<BaseField = Self::BaseField>>(
         &mut self,
         aux_segments: &[Matrix<E>],
@@ -221,6 +228,108 @@ pub trait Trace: Sized {
             x *= g;
         }
     }
+
+    /// Returns a list of cells in the main segment of this trace which are not constrained by
+    /// the specified AIR.
+    ///
+    /// This is intended to be used in tests to catch under-constrained AIRs: starting with a valid
+    /// trace, every cell of the main trace segment is perturbed one at a time (by adding the
+    /// generator of the base field to its value), and the perturbed trace is checked against the
+    /// assertions and main transition constraints of the AIR. A cell is reported if none of them
+    /// detects the perturbation. Cells are reported as `(column, step)` tuples, ordered by step
+    /// and then by column.
+    ///
+    /// Only the main trace segment is perturbed, and only the assertions and transition
+    /// constraints against the main trace segment are evaluated; thus, cells constrained solely
+    /// by constraints against auxiliary segments are reported as unconstrained. Some cells may be
+    /// legitimately unconstrained (e.g., cells in steps exempt from transition constraints);
+    /// every reported cell should be reviewed to make sure any value in it results in a valid
+    /// execution of the computation.
+    ///
+    /// NOTE: this is a very expensive operation and is intended for use only in tests.
+    ///
+    /// # Panics
+    /// Panics if this trace is not valid against the specified AIR.
+    fn find_unconstrained_cells<A>(&self, air: &A) -> Vec<(usize, usize)>
+    where
+        A: Air<BaseField = Self::BaseField>,
+    {
+        let num_checked_steps = self.length() - air.context().num_transition_exemptions();
+        let assertions = air.get_assertions();
+        let delta = Self::BaseField::GENERATOR;
+
+        // collect the info needed to build periodic values for a specific step
+        let g = air.trace_domain_generator();
+        let periodic_values_polys = air.get_periodic_column_polys();
+        let get_periodic_values = |step: usize| {
+            let x = g.exp((step as u32).into());
+            periodic_values_polys
+                .iter()
+                .map(|p| {
+                    let num_cycles = air.trace_length() / p.len();
+                    polynom::eval(p, x.exp((num_cycles as u32).into()))
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // returns true if main transition constraints are satisfied by the specified frame
+        let mut evaluations =
+            vec![Self::BaseField::ZERO; air.context().num_main_transition_constraints()];
+        let mut is_satisfied = |frame: &EvaluationFrame<Self::BaseField>, periodic: &[_]| {
+            air.evaluate_transition(frame, periodic, &mut evaluations);
+            evaluations.iter().all(|&e| e == Self::BaseField::ZERO)
+        };
+
+        let mut result = Vec::new();
+        let mut frame = EvaluationFrame::new(self.main_trace_width());
+        let mut prev_frame = EvaluationFrame::new(self.main_trace_width());
+        for step in 0..self.length() {
+            let periodic_values = get_periodic_values(step);
+            let prev_periodic_values = if step > 0 {
+                get_periodic_values(step - 1)
+            } else {
+                Vec::new()
+            };
+
+            // make sure the unperturbed trace is valid at this step
+            self.read_main_frame(step, &mut frame);
+            if step > 0 {
+                self.read_main_frame(step - 1, &mut prev_frame);
+            }
+            if step < num_checked_steps {
+                assert!(
+                    is_satisfied(&frame, &periodic_values),
+                    "main transition constraints are not satisfied at step {step}"
+                );
+            }
+
+            for column in 0..self.main_trace_width() {
+                // the perturbation is detected by an assertion against this cell
+                let mut is_detected = false;
+                for assertion in assertions.iter().filter(|a| a.column() == column) {
+                    assertion.apply(self.length(), |s, _| is_detected |= s == step);
+                }
+
+                // the perturbation is detected by transition constraints at this step, where the
+                // cell is in the current row, or at the previous step, where it is in the next row
+                if !is_detected && step < num_checked_steps {
+                    frame.current_mut()[column] += delta;
+                    is_detected = !is_satisfied(&frame, &periodic_values);
+                    frame.current_mut()[column] -= delta;
+                }
+                if !is_detected && step > 0 && step - 1 < num_checked_steps {
+                    prev_frame.next_mut()[column] += delta;
+                    is_detected = !is_satisfied(&prev_frame, &prev_periodic_values);
+                    prev_frame.next_mut()[column] -= delta;
+                }
+
+                if !is_detected {
+                    result.push((column, step));
+                }
+            }
+        }
+        result
+    }
 }
 
 // HELPER FUNCTIONS
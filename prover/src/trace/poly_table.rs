@@ -72,12 +72,12 @@ impl<E: FieldElement> TracePolyTable<E> {
     }
 
     /// Returns an iterator over the polynomials of the main trace segment.
-    pub fn main_trace_polys(&self) -> ColumnIter<E::BaseField> {
+    pub fn main_trace_polys(&self) -> ColumnIter<'_, E::BaseField> {
         self.main_segment_polys.columns()
     }
 
     /// Returns an iterator over the polynomials of all auxiliary trace segments.
-    pub fn aux_trace_polys(&self) -> MultiColumnIter<E> {
+    pub fn aux_trace_polys(&self) -> MultiColumnIter<'_, E> {
         MultiColumnIter::new(self.aux_segment_polys.as_slice())
     }
 
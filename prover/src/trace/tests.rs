@@ -6,8 +6,9 @@
 use crate::{
     tests::{build_fib_trace, MockAir},
     trace::TracePolyTable,
-    StarkDomain, Trace, TraceCommitment,
+    StarkDomain, Trace, TraceBuilder, TraceCommitment, TraceTable, TraceTableFragment,
 };
+use air::{Assertion, TraceError};
 use crypto::{hashers::Blake3_256, ElementHasher, MerkleTree};
 use math::{
     fields::f128::BaseElement, get_power_series, get_power_series_with_offset, log2, polynom,
@@ -38,6 +39,133 @@ fn new_trace_table() {
     assert_eq!(expected, trace.get_column(1));
 }
 
+#[test]
+fn new_trace_table_errors() {
+    type Table = TraceTable<BaseElement>;
+    assert_eq!(
+        Some(TraceError::InvalidTraceWidth(0)),
+        Table::try_new(0, 8).err()
+    );
+    assert_eq!(
+        Some(TraceError::InvalidTraceWidth(256)),
+        Table::try_new(256, 8).err()
+    );
+    assert_eq!(
+        Some(TraceError::TraceLengthTooShort(8, 4)),
+        Table::try_new(2, 4).err()
+    );
+    assert_eq!(
+        Some(TraceError::TraceLengthNotPowerOfTwo(12)),
+        Table::try_new(2, 12).err()
+    );
+    assert_eq!(
+        Some(TraceError::MetadataTooLong(65535, 65536)),
+        Table::try_with_meta(2, 8, vec![0; 65536]).err()
+    );
+
+    let columns = vec![vec![BaseElement::ONE; 8], vec![BaseElement::ONE; 16]];
+    assert_eq!(
+        Some(TraceError::InconsistentColumnLengths(8, 16)),
+        Table::try_init(columns).err()
+    );
+    assert_eq!(
+        Some(TraceError::InvalidTraceWidth(0)),
+        Table::try_init(vec![]).err()
+    );
+
+    assert!(Table::try_new(2, 8).is_ok());
+}
+
+#[test]
+fn trace_table_views() {
+    let mut trace = TraceTable::<BaseElement>::new(4, 16);
+    trace.fill(|state| state.fill(BaseElement::ZERO), |_, _| ());
+
+    // a view over columns 1 and 2 at steps 4..8 should update only these cells
+    let mut view = trace.view_mut(1..3, 4..8);
+    assert_eq!((1, 4), (view.column_offset(), view.step_offset()));
+    assert_eq!((1, 4, 2), (view.stride(), view.length(), view.width()));
+    view.fill(
+        |state| state.copy_from_slice(&[BaseElement::ONE, BaseElement::ONE]),
+        |_, state| state[0] += state[1],
+    );
+    let expected = [0u32, 0, 0, 0, 1, 2, 3, 4, 0, 0, 0, 0, 0, 0, 0, 0];
+    assert_eq!(to_elements(&expected), trace.get_column(1));
+    assert_eq!(BaseElement::ONE, trace.get(2, 7));
+    assert_eq!(BaseElement::ZERO, trace.get(2, 8));
+    assert_eq!(BaseElement::ZERO, trace.get(3, 4));
+
+    // a strided view should cover only every stride-th step of the range
+    let mut view = trace.strided_view_mut(0..1, 3..16, 4);
+    assert_eq!(4, view.length());
+    assert_eq!(15, view.step(3));
+    view.for_each_row(|i, row| row[0] = BaseElement::from(i as u32 + 1));
+    let expected = [0u32, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 4];
+    assert_eq!(to_elements(&expected), trace.get_column(0));
+
+    // split views should cover disjoint regions and keep track of their offsets
+    let view = trace.strided_view_mut(0..4, 0..16, 2);
+    let (top, bottom) = view.split_at_row(3);
+    let (mut left, mut right) = bottom.split_at_column(3);
+    assert_eq!((0, 3, 4), (top.step_offset(), top.length(), top.width()));
+    assert_eq!(
+        (6, 5, 0),
+        (left.step_offset(), left.length(), left.column_offset())
+    );
+    assert_eq!((6, 3), (right.step_offset(), right.column_offset()));
+    left.set(2, 4, BaseElement::from(7u32));
+    right.update_row(0, &[BaseElement::from(9u32)]);
+    assert_eq!(BaseElement::from(1u32), top.get(1, 2));
+    let mut row = vec![BaseElement::ZERO; 4];
+    trace.read_row_into(14, &mut row);
+    assert_eq!(BaseElement::from(7u32), row[2]);
+    assert_eq!(BaseElement::from(9u32), trace.get(3, 6));
+}
+
+#[test]
+fn trace_builder() {
+    let builder = SquaresBuilder {
+        length: 16,
+        fragment_length: 4,
+    };
+    assert_eq!(4, builder.num_fragments());
+
+    // the trace built fragment by fragment should be the same as the trace built sequentially
+    let trace = builder.build_trace();
+    let steps = (0..16u32).collect::<Vec<_>>();
+    let squares = steps.iter().map(|&i| i * i).collect::<Vec<_>>();
+    assert_eq!(to_elements(&steps), trace.get_column(0));
+    assert_eq!(to_elements(&squares), trace.get_column(1));
+
+    // any fragment can be built on its own
+    let fragment = builder.build_fragment(2);
+    assert_eq!(to_elements(&steps[8..12]), fragment[0]);
+    assert_eq!(to_elements(&squares[8..12]), fragment[1]);
+}
+
+#[test]
+fn find_unconstrained_cells() {
+    let trace_length = 8;
+    let trace = build_fib_trace(trace_length * 2);
+
+    // the mock AIR has no transition constraints; thus, only asserted cells are constrained
+    let air = MockAir::with_assertions(
+        vec![
+            Assertion::single(0, 0, BaseElement::ONE),
+            Assertion::periodic(1, 1, 4, trace.get(1, 1)),
+        ],
+        trace_length,
+    );
+
+    let cells = trace.find_unconstrained_cells(&air);
+    assert_eq!(2 * trace_length - 3, cells.len());
+    assert!(!cells.contains(&(0, 0)));
+    assert!(!cells.contains(&(1, 1)));
+    assert!(!cells.contains(&(1, 5)));
+    assert_eq!((1, 0), cells[0]);
+    assert_eq!((0, 1), cells[1]);
+}
+
 #[test]
 fn extend_trace_table() {
     // build the trace and the domain
@@ -49,12 +177,14 @@ fn extend_trace_table() {
     // build extended trace commitment
     let trace_polys = trace.main_segment().interpolate_columns();
     let trace_lde = trace_polys.evaluate_columns_over(&domain);
-    let trace_tree = trace_lde.commit_to_rows::<Blake3>();
+    let trace_tree = trace_lde.commit_to_rows::<Blake3, MerkleTree<Blake3>>(2);
     let trace_comm = TraceCommitment::<BaseElement, Blake3>::new(
         trace_lde,
         trace_tree,
         domain.trace_to_lde_blowup(),
+        1,
     );
+    let trace_polys_matrix = trace_polys.clone();
     let trace_polys = TracePolyTable::<BaseElement>::new(trace_polys);
 
     assert_eq!(2, trace_comm.trace_table().main_trace_width());
@@ -89,6 +219,19 @@ fn extend_trace_table() {
         trace_polys.get_main_trace_poly(1),
         polynom::interpolate(&lde_domain, trace_comm.get_main_trace_column(1), true)
     );
+
+    // evaluating trace polynomials at a few positions should yield the same rows as the LDE
+    let positions = [0, 7, 21, 63, 7];
+    let trace_rows = trace_polys_matrix.evaluate_columns_at_positions(&domain, &positions);
+    assert_eq!(positions.len(), trace_rows.num_rows());
+    for (i, &position) in positions.iter().enumerate() {
+        for j in 0..2 {
+            assert_eq!(
+                trace_comm.get_main_trace_column(j)[position],
+                trace_rows.get(j, i)
+            );
+        }
+    }
 }
 
 #[test]
@@ -102,11 +245,12 @@ fn commit_trace_table() {
     // build extended trace commitment
     let trace_polys = trace.main_segment().interpolate_columns();
     let trace_lde = trace_polys.evaluate_columns_over(&domain);
-    let trace_tree = trace_lde.commit_to_rows::<Blake3>();
+    let trace_tree = trace_lde.commit_to_rows::<Blake3, MerkleTree<Blake3>>(2);
     let trace_comm = TraceCommitment::<BaseElement, Blake3>::new(
         trace_lde,
         trace_tree,
         domain.trace_to_lde_blowup(),
+        1,
     );
 
     // build Merkle tree from trace rows
@@ -130,7 +274,48 @@ fn commit_trace_table() {
 // HELPER FUNCTIONS
 // ================================================================================================
 
+/// Builds a trace in which the first column contains step indexes, and the second column
+/// contains their squares.
+struct SquaresBuilder {
+    length: usize,
+    fragment_length: usize,
+}
+
+impl TraceBuilder for SquaresBuilder {
+    type BaseField = BaseElement;
+
+    fn width(&self) -> usize {
+        2
+    }
+
+    fn length(&self) -> usize {
+        self.length
+    }
+
+    fn fragment_length(&self) -> usize {
+        self.fragment_length
+    }
+
+    fn fill(&self, fragment: &mut TraceTableFragment<BaseElement>) {
+        let offset = BaseElement::from(fragment.offset() as u32);
+        fragment.fill(
+            |state| {
+                state[0] = offset;
+                state[1] = offset.square();
+            },
+            |_, state| {
+                state[1] += state[0].double() + BaseElement::ONE;
+                state[0] += BaseElement::ONE;
+            },
+        );
+    }
+}
+
 fn build_lde_domain<B: StarkField>(domain_size: usize) -> Vec<B> {
     let g = B::get_root_of_unity(log2(domain_size));
     get_power_series_with_offset(g, B::GENERATOR, domain_size)
 }
+
+fn to_elements(values: &[u32]) -> Vec<BaseElement> {
+    values.iter().map(|&v| BaseElement::from(v)).collect()
+}
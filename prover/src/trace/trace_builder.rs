@@ -0,0 +1,125 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{TraceTable, TraceTableFragment};
+use math::{FieldElement, StarkField};
+use utils::collections::Vec;
+
+#[cfg(feature = "concurrent")]
+use utils::iterators::*;
+
+// TRACE BUILDER
+// ================================================================================================
+/// Defines how an execution trace of a computation is generated.
+///
+/// A trace builder describes the shape of the trace (its width, length, and metadata), and knows
+/// how to fill any fragment of the trace with data: i.e., the builder must be able to fill
+/// consecutive rows of the trace starting at an arbitrary step given by
+/// [TraceTableFragment::offset()]. This separates the description of how each part of the trace
+/// is generated from the allocation of the trace:
+/// * [build_trace()](TraceBuilder::build_trace) allocates a [TraceTable] and fills all of its
+///   fragments; when `concurrent` feature is enabled, the fragments are filled in multiple
+///   threads.
+/// * [build_fragment()](TraceBuilder::build_fragment) generates a single fragment without
+///   allocating memory for the rest of the trace; this can be used to re-generate any part of a
+///   trace on demand (e.g., to validate or to extend a huge trace part by part).
+///
+/// The results of filling the same fragment must be identical regardless of the order in which
+/// fragments are filled.
+///
+/// The prover does not invoke trace builders: to generate a proof, the trace returned by
+/// [build_trace()](TraceBuilder::build_trace) must be passed to
+/// [Prover::prove()](crate::Prover::prove), and thus, the entire trace is still held in memory
+/// during proof generation.
+pub trait TraceBuilder: Sync {
+    /// Base field for the execution trace generated by this builder.
+    type BaseField: StarkField;
+
+    // REQUIRED METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of columns in the execution trace.
+    fn width(&self) -> usize;
+
+    /// Returns the number of rows in the execution trace; this must be a power of two.
+    fn length(&self) -> usize;
+
+    /// Fills all rows of the specified fragment of the execution trace.
+    ///
+    /// The fragment covers [TraceTableFragment::length()] consecutive steps of the trace starting
+    /// with [TraceTableFragment::offset()]. All cells of the fragment are initialized to zeros.
+    fn fill(&self, fragment: &mut TraceTableFragment<Self::BaseField>);
+
+    // PROVIDED METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns metadata associated with the execution trace; by default, metadata is empty.
+    fn meta(&self) -> &[u8] {
+        &[]
+    }
+
+    /// Returns the number of rows in fragments into which the execution trace is broken when it
+    /// is generated; by default, the whole trace is generated as a single fragment.
+    ///
+    /// Fragments are filled independently; thus, shorter fragments allow generating the trace in
+    /// more threads at the expense of computing the initial state of more fragments.
+    fn fragment_length(&self) -> usize {
+        self.length()
+    }
+
+    /// Returns the number of fragments into which the execution trace is broken when it is
+    /// generated.
+    fn num_fragments(&self) -> usize {
+        self.length() / self.fragment_length()
+    }
+
+    /// Generates the entire execution trace and returns it as a [TraceTable].
+    ///
+    /// When `concurrent` feature is enabled, fragments of the trace are filled in multiple
+    /// threads.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The dimensions or metadata of the trace are not valid for a [TraceTable].
+    /// * The fragment length is smaller than 2, greater than the length of the trace, or is not
+    ///   a power of two.
+    fn build_trace(&self) -> TraceTable<Self::BaseField> {
+        let mut trace = TraceTable::with_meta(self.width(), self.length(), self.meta().to_vec());
+        trace
+            .fragments(self.fragment_length())
+            .for_each(|mut fragment| self.fill(&mut fragment));
+        trace
+    }
+
+    /// Generates the fragment of the execution trace at the specified index, and returns it as a
+    /// vector of columns each containing [fragment_length()](TraceBuilder::fragment_length)
+    /// values.
+    ///
+    /// The returned columns are the same as the corresponding parts of columns of the trace
+    /// generated by [build_trace()](TraceBuilder::build_trace).
+    ///
+    /// # Panics
+    /// Panics if `index` is greater than or equal to the number of fragments.
+    fn build_fragment(&self, index: usize) -> Vec<Vec<Self::BaseField>> {
+        assert!(
+            index < self.num_fragments(),
+            "fragment index must be smaller than {}, but was {}",
+            self.num_fragments(),
+            index
+        );
+
+        let fragment_length = self.fragment_length();
+        let mut columns = (0..self.width())
+            .map(|_| vec![Self::BaseField::ZERO; fragment_length])
+            .collect::<Vec<_>>();
+        let data = columns
+            .iter_mut()
+            .map(|column| column.as_mut_slice())
+            .collect();
+        let mut fragment = TraceTableFragment::new(index, index * fragment_length, data);
+        self.fill(&mut fragment);
+        columns
+    }
+}
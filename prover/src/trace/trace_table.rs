@@ -4,9 +4,10 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{Matrix, Trace};
-use air::{EvaluationFrame, TraceInfo, TraceLayout};
+use air::{EvaluationFrame, TraceError, TraceInfo, TraceLayout};
+use core::ops::Range;
 use math::{log2, FieldElement, StarkField};
-use utils::{collections::Vec, uninit_vector};
+use utils::collections::Vec;
 
 #[cfg(not(feature = "concurrent"))]
 use utils::collections::vec;
@@ -59,6 +60,15 @@ const MIN_FRAGMENT_LENGTH: usize = 2;
 /// [fill()](TraceTableFragment::fill) method to fill all fragments with data in parallel.
 /// The semantics of the fragment's [TraceTableFragment::fill()] method are identical to the
 /// semantics of the [TraceTable::fill()] method.
+///
+/// # Trace views
+/// For computations in which different regions of the trace are filled by different
+/// sub-computations (e.g., chiplets occupying specific columns over specific ranges of steps),
+/// `TraceTable` exposes [view_mut()](TraceTable::view_mut) and
+/// [strided_view_mut()](TraceTable::strided_view_mut) methods. These return a
+/// [TraceTableView] over a rectangular region of the trace, which can be filled row by row
+/// using indexes relative to the region, and can be split into disjoint views to fill several
+/// regions independently.
 pub struct TraceTable<B: StarkField> {
     layout: TraceLayout,
     trace: Matrix<B>,
@@ -71,7 +81,7 @@ impl<B: StarkField> TraceTable<B> {
 
     /// Creates a new execution trace of the specified width and length.
     ///
-    /// This allocates all the required memory for the trace, but does not initialize it. It is
+    /// This allocates all the required memory for the trace and sets all cells to ZERO. It is
     /// expected that the trace will be filled using one of the data mutator methods.
     ///
     /// # Panics
@@ -86,7 +96,7 @@ impl<B: StarkField> TraceTable<B> {
     /// Creates a new execution trace of the specified width and length, and with the specified
     /// metadata.
     ///
-    /// This allocates all the required memory for the trace, but does not initialize it. It is
+    /// This allocates all the required memory for the trace and sets all cells to ZERO. It is
     /// expected that the trace will be filled using one of the data mutator methods.
     ///
     /// # Panics
@@ -96,96 +106,93 @@ impl<B: StarkField> TraceTable<B> {
     ///   field `B`, or is not a power of two.
     /// * Length of `meta` is greater than 65535;
     pub fn with_meta(width: usize, length: usize, meta: Vec<u8>) -> Self {
-        assert!(
-            width > 0,
-            "execution trace must consist of at least one column"
-        );
-        assert!(
-            width <= TraceInfo::MAX_TRACE_WIDTH,
-            "execution trace width cannot be greater than {}, but was {}",
-            TraceInfo::MAX_TRACE_WIDTH,
-            width
-        );
-        assert!(
-            length >= TraceInfo::MIN_TRACE_LENGTH,
-            "execution trace must be at least {} steps long, but was {}",
-            TraceInfo::MIN_TRACE_LENGTH,
-            length
-        );
-        assert!(
-            length.is_power_of_two(),
-            "execution trace length must be a power of 2"
-        );
-        assert!(
-            log2(length) <= B::TWO_ADICITY,
-            "execution trace length cannot exceed 2^{} steps, but was 2^{}",
-            B::TWO_ADICITY,
-            log2(length)
-        );
-        assert!(
-            meta.len() <= TraceInfo::MAX_META_LENGTH,
-            "number of metadata bytes cannot be greater than {}, but was {}",
-            TraceInfo::MAX_META_LENGTH,
-            meta.len()
-        );
+        Self::try_with_meta(width, length, meta).unwrap_or_else(|err| panic!("{}", err))
+    }
 
-        let columns = unsafe { (0..width).map(|_| uninit_vector(length)).collect() };
-        Self {
+    /// Creates a new execution trace from a list of provided trace columns.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The `columns` vector is empty or has over 255 columns.
+    /// * Number of elements in any of the columns is smaller than 8, greater than the biggest
+    ///   multiplicative subgroup in the field `B`, or is not a power of two.
+    /// * Number of elements is not identical for all columns.
+    pub fn init(columns: Vec<Vec<B>>) -> Self {
+        Self::try_init(columns).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Creates a new execution trace of the specified width and length.
+    ///
+    /// This is a fallible version of [TraceTable::new()] which can be used to build traces with
+    /// dimensions coming from untrusted input.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * `width` is zero or greater than 255.
+    /// * `length` is smaller than 8, greater than biggest multiplicative subgroup in the field
+    ///   `B`, or is not a power of two.
+    pub fn try_new(width: usize, length: usize) -> Result<Self, TraceError> {
+        Self::try_with_meta(width, length, vec![])
+    }
+
+    /// Creates a new execution trace of the specified width and length, and with the specified
+    /// metadata.
+    ///
+    /// This is a fallible version of [TraceTable::with_meta()].
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * `width` is zero or greater than 255.
+    /// * `length` is smaller than 8, greater than the biggest multiplicative subgroup in the
+    ///   field `B`, or is not a power of two.
+    /// * Length of `meta` is greater than 65535;
+    pub fn try_with_meta(width: usize, length: usize, meta: Vec<u8>) -> Result<Self, TraceError> {
+        validate_dimensions::<B>(width, length)?;
+        if meta.len() > TraceInfo::MAX_META_LENGTH {
+            return Err(TraceError::MetadataTooLong(
+                TraceInfo::MAX_META_LENGTH,
+                meta.len(),
+            ));
+        }
+
+        // the memory cannot be left un-initialized here: the table is filled by user code via
+        // methods such as set() or fragments(), none of which guarantees that every cell is
+        // written, and cells can be read via get() or read_row_into() at any time
+        let columns = (0..width).map(|_| B::zeroed_vector(length)).collect();
+        Ok(Self {
             layout: TraceLayout::new(width, [0], [0]),
             trace: Matrix::new(columns),
             meta,
-        }
+        })
     }
 
     /// Creates a new execution trace from a list of provided trace columns.
     ///
-    /// # Panics
-    /// Panics if:
+    /// This is a fallible version of [TraceTable::init()].
+    ///
+    /// # Errors
+    /// Returns an error if:
     /// * The `columns` vector is empty or has over 255 columns.
     /// * Number of elements in any of the columns is smaller than 8, greater than the biggest
     ///   multiplicative subgroup in the field `B`, or is not a power of two.
     /// * Number of elements is not identical for all columns.
-    pub fn init(columns: Vec<Vec<B>>) -> Self {
-        assert!(
-            !columns.is_empty(),
-            "execution trace must consist of at least one column"
-        );
-        assert!(
-            columns.len() <= TraceInfo::MAX_TRACE_WIDTH,
-            "execution trace width cannot be greater than {}, but was {}",
-            TraceInfo::MAX_TRACE_WIDTH,
-            columns.len()
-        );
-        let trace_length = columns[0].len();
-        assert!(
-            trace_length >= TraceInfo::MIN_TRACE_LENGTH,
-            "execution trace must be at least {} steps long, but was {}",
-            TraceInfo::MIN_TRACE_LENGTH,
-            trace_length
-        );
-        assert!(
-            trace_length.is_power_of_two(),
-            "execution trace length must be a power of 2"
-        );
-        assert!(
-            log2(trace_length) <= B::TWO_ADICITY,
-            "execution trace length cannot exceed 2^{} steps, but was 2^{}",
-            B::TWO_ADICITY,
-            log2(trace_length)
-        );
+    pub fn try_init(columns: Vec<Vec<B>>) -> Result<Self, TraceError> {
+        let trace_length = columns.first().map_or(0, |column| column.len());
+        validate_dimensions::<B>(columns.len(), trace_length)?;
         for column in columns.iter().skip(1) {
-            assert_eq!(
-                column.len(),
-                trace_length,
-                "all columns traces must have the same length"
-            );
+            if column.len() != trace_length {
+                return Err(TraceError::InconsistentColumnLengths(
+                    trace_length,
+                    column.len(),
+                ));
+            }
         }
 
-        Self {
+        Ok(Self {
             layout: TraceLayout::new(columns.len(), [0], [0]),
             trace: Matrix::new(columns),
             meta: vec![],
-        }
+        })
     }
 
     // DATA MUTATORS
@@ -259,7 +266,10 @@ impl<B: StarkField> TraceTable<B> {
     /// Panics if `fragment_length` is smaller than 2, greater than the length of the trace,
     /// or is not a power of two.
     #[cfg(not(feature = "concurrent"))]
-    pub fn fragments(&mut self, fragment_length: usize) -> vec::IntoIter<TraceTableFragment<B>> {
+    pub fn fragments(
+        &mut self,
+        fragment_length: usize,
+    ) -> vec::IntoIter<TraceTableFragment<'_, B>> {
         self.build_fragments(fragment_length).into_iter()
     }
 
@@ -281,7 +291,7 @@ impl<B: StarkField> TraceTable<B> {
 
     /// Returns a vector of trace fragments each covering the number of steps specified by the
     /// `fragment_length` parameter.
-    fn build_fragments(&mut self, fragment_length: usize) -> Vec<TraceTableFragment<B>> {
+    fn build_fragments(&mut self, fragment_length: usize) -> Vec<TraceTableFragment<'_, B>> {
         assert!(
             fragment_length >= MIN_FRAGMENT_LENGTH,
             "fragment length must be at least {MIN_FRAGMENT_LENGTH}, but was {fragment_length}"
@@ -308,14 +318,81 @@ impl<B: StarkField> TraceTable<B> {
         fragment_data
             .into_iter()
             .enumerate()
-            .map(|(i, data)| TraceTableFragment {
-                index: i,
-                offset: i * fragment_length,
-                data,
-            })
+            .map(|(i, data)| TraceTableFragment::new(i, i * fragment_length, data))
             .collect()
     }
 
+    // VIEWS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a mutable view over the specified range of `columns` at the specified range of
+    /// `steps` of this execution trace.
+    ///
+    /// Row `i` of the view corresponds to step `steps.start + i` of the trace, and column `j` of
+    /// the view corresponds to column `columns.start + j` of the trace.
+    ///
+    /// # Panics
+    /// Panics if either of the ranges is empty or out of bounds for this execution trace.
+    pub fn view_mut(
+        &mut self,
+        columns: Range<usize>,
+        steps: Range<usize>,
+    ) -> TraceTableView<'_, B> {
+        self.strided_view_mut(columns, steps, 1)
+    }
+
+    /// Returns a mutable view over the specified range of `columns` at every `stride`-th step
+    /// of the specified range of `steps` of this execution trace.
+    ///
+    /// Row `i` of the view corresponds to step `steps.start + i * stride` of the trace, and
+    /// column `j` of the view corresponds to column `columns.start + j` of the trace. For
+    /// example, a view over steps `3..16` with stride 4 contains steps 3, 7, 11, and 15.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * Either of the ranges is empty or out of bounds for this execution trace.
+    /// * `stride` is zero.
+    pub fn strided_view_mut(
+        &mut self,
+        columns: Range<usize>,
+        steps: Range<usize>,
+        stride: usize,
+    ) -> TraceTableView<'_, B> {
+        assert!(
+            !columns.is_empty() && columns.end <= self.width(),
+            "column range {:?} is invalid for a trace of width {}",
+            columns,
+            self.width()
+        );
+        assert!(
+            !steps.is_empty() && steps.end <= self.length(),
+            "step range {:?} is invalid for a trace of length {}",
+            steps,
+            self.length()
+        );
+        assert!(stride > 0, "stride must be greater than zero");
+
+        // the view covers steps from the start of the range up to and including the last step
+        // reachable with the specified stride
+        let length = (steps.len() - 1) / stride + 1;
+        let last_step = steps.start + (length - 1) * stride;
+        let data = self
+            .trace
+            .columns_mut()
+            .skip(columns.start)
+            .take(columns.len())
+            .map(|column| &mut column[steps.start..=last_step])
+            .collect();
+
+        TraceTableView {
+            column_offset: columns.start,
+            step_offset: steps.start,
+            stride,
+            length,
+            data,
+        }
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -399,6 +476,19 @@ pub struct TraceTableFragment<'a, B: StarkField> {
 }
 
 impl<'a, B: StarkField> TraceTableFragment<'a, B> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a fragment with the specified index which starts at the specified step and
+    /// consists of the provided column slices.
+    pub(super) fn new(index: usize, offset: usize, data: Vec<&'a mut [B]>) -> Self {
+        Self {
+            index,
+            offset,
+            data,
+        }
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -459,3 +549,257 @@ impl<'a, B: StarkField> TraceTableFragment<'a, B> {
         }
     }
 }
+
+// TRACE VIEWS
+// ================================================================================================
+/// A mutable view over a rectangular region of an execution trace.
+///
+/// The region consists of a range of consecutive columns at a set of steps which are evenly
+/// spaced within a range of steps (i.e., every step of the range, or every `stride`-th step of
+/// it). Rows and columns of the view are indexed relative to the region: row 0 of the view is the
+/// first step of the region, and column 0 of the view is the first column of the region. Updating
+/// data in the view directly updates the data in the underlying execution trace.
+///
+/// A view cannot be instantiated directly but is created by executing [TraceTable::view_mut()]
+/// or [TraceTable::strided_view_mut()] methods. A view can be split into two disjoint views via
+/// [split_at_row()](TraceTableView::split_at_row) and
+/// [split_at_column()](TraceTableView::split_at_column) methods; the resulting views can be
+/// filled independently (including from different threads).
+pub struct TraceTableView<'a, B: StarkField> {
+    column_offset: usize,
+    step_offset: usize,
+    stride: usize,
+    length: usize,
+    data: Vec<&'a mut [B]>,
+}
+
+impl<'a, B: StarkField> TraceTableView<'a, B> {
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the index of the trace column corresponding to the first column of this view.
+    pub fn column_offset(&self) -> usize {
+        self.column_offset
+    }
+
+    /// Returns the trace step corresponding to the first row of this view.
+    pub fn step_offset(&self) -> usize {
+        self.step_offset
+    }
+
+    /// Returns the number of trace steps between two consecutive rows of this view.
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    /// Returns the number of rows in this view.
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    /// Returns the number of columns in this view.
+    pub fn width(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns the trace step corresponding to the specified row of this view.
+    pub fn step(&self, row_idx: usize) -> usize {
+        self.step_offset + row_idx * self.stride
+    }
+
+    /// Returns value of the cell in the specified column at the specified row of this view.
+    ///
+    /// # Panics
+    /// Panics if either `column` or `row_idx` are out of bounds for this view.
+    pub fn get(&self, column: usize, row_idx: usize) -> B {
+        self.check_row(row_idx);
+        self.data[column][row_idx * self.stride]
+    }
+
+    /// Reads a single row of this view into the provided target.
+    ///
+    /// # Panics
+    /// Panics if `row_idx` is out of bounds for this view.
+    pub fn read_row_into(&self, row_idx: usize, target: &mut [B]) {
+        self.check_row(row_idx);
+        for (value, column) in target.iter_mut().zip(self.data.iter()) {
+            *value = column[row_idx * self.stride];
+        }
+    }
+
+    // DATA MUTATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Updates a value in a single cell of this view.
+    ///
+    /// # Panics
+    /// Panics if either `column` or `row_idx` are out of bounds for this view.
+    pub fn set(&mut self, column: usize, row_idx: usize, value: B) {
+        self.check_row(row_idx);
+        self.data[column][row_idx * self.stride] = value;
+    }
+
+    /// Updates a single row of this view with provided data.
+    ///
+    /// # Panics
+    /// Panics if `row_idx` is out of bounds for this view.
+    pub fn update_row(&mut self, row_idx: usize, row_data: &[B]) {
+        self.check_row(row_idx);
+        for (column, &value) in self.data.iter_mut().zip(row_data) {
+            column[row_idx * self.stride] = value;
+        }
+    }
+
+    /// Fills all rows of this view.
+    ///
+    /// The semantics of this method are identical to the semantics of the [TraceTable::fill()]
+    /// method, except that the closures receive states containing only the columns of this view,
+    /// and row indexes are relative to this view.
+    pub fn fill<I, U>(&mut self, init: I, update: U)
+    where
+        I: Fn(&mut [B]),
+        U: Fn(usize, &mut [B]),
+    {
+        let mut state = vec![B::ZERO; self.width()];
+        init(&mut state);
+        self.update_row(0, &state);
+
+        for i in 0..self.length() - 1 {
+            update(i, &mut state);
+            self.update_row(i + 1, &state);
+        }
+    }
+
+    /// Applies the provided closure to every row of this view.
+    ///
+    /// For each row, the closure receives the index of the row and a mutable slice containing
+    /// the values of the row; the contents of the slice are copied back into the row after the
+    /// closure returns.
+    pub fn for_each_row<F>(&mut self, mut f: F)
+    where
+        F: FnMut(usize, &mut [B]),
+    {
+        let mut row = vec![B::ZERO; self.width()];
+        for i in 0..self.length() {
+            self.read_row_into(i, &mut row);
+            f(i, &mut row);
+            self.update_row(i, &row);
+        }
+    }
+
+    // SPLITTING
+    // --------------------------------------------------------------------------------------------
+
+    /// Splits this view into two views such that the first view contains rows `[0, row_idx)`
+    /// and the second view contains all remaining rows of this view.
+    ///
+    /// # Panics
+    /// Panics if `row_idx` is zero or is not smaller than the length of this view.
+    pub fn split_at_row(self, row_idx: usize) -> (Self, Self) {
+        assert!(
+            row_idx > 0 && row_idx < self.length,
+            "row index must be in range [1, {}), but was {}",
+            self.length,
+            row_idx
+        );
+
+        let mut top = Vec::with_capacity(self.data.len());
+        let mut bottom = Vec::with_capacity(self.data.len());
+        for column in self.data {
+            let (first, second) = column.split_at_mut(row_idx * self.stride);
+            top.push(first);
+            bottom.push(second);
+        }
+
+        (
+            Self {
+                column_offset: self.column_offset,
+                step_offset: self.step_offset,
+                stride: self.stride,
+                length: row_idx,
+                data: top,
+            },
+            Self {
+                column_offset: self.column_offset,
+                step_offset: self.step_offset + row_idx * self.stride,
+                stride: self.stride,
+                length: self.length - row_idx,
+                data: bottom,
+            },
+        )
+    }
+
+    /// Splits this view into two views such that the first view contains columns `[0, column)`
+    /// and the second view contains all remaining columns of this view.
+    ///
+    /// # Panics
+    /// Panics if `column` is zero or is not smaller than the width of this view.
+    pub fn split_at_column(mut self, column: usize) -> (Self, Self) {
+        assert!(
+            column > 0 && column < self.width(),
+            "column index must be in range [1, {}), but was {}",
+            self.width(),
+            column
+        );
+
+        let right = self.data.split_off(column);
+        (
+            Self {
+                column_offset: self.column_offset,
+                step_offset: self.step_offset,
+                stride: self.stride,
+                length: self.length,
+                data: self.data,
+            },
+            Self {
+                column_offset: self.column_offset + column,
+                step_offset: self.step_offset,
+                stride: self.stride,
+                length: self.length,
+                data: right,
+            },
+        )
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Makes sure the specified row is within the bounds of this view; for strided views,
+    /// indexing into the underlying column slices directly would report positions in the trace
+    /// rather than rows of the view.
+    fn check_row(&self, row_idx: usize) {
+        assert!(
+            row_idx < self.length,
+            "row index must be smaller than {}, but was {}",
+            self.length,
+            row_idx
+        );
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Makes sure an execution trace with the specified number of columns and steps can be built in
+/// the field `B`.
+fn validate_dimensions<B: StarkField>(width: usize, length: usize) -> Result<(), TraceError> {
+    if width == 0 || width > TraceInfo::MAX_TRACE_WIDTH {
+        return Err(TraceError::InvalidTraceWidth(width));
+    }
+    if length < TraceInfo::MIN_TRACE_LENGTH {
+        return Err(TraceError::TraceLengthTooShort(
+            TraceInfo::MIN_TRACE_LENGTH,
+            length,
+        ));
+    }
+    if !length.is_power_of_two() {
+        return Err(TraceError::TraceLengthNotPowerOfTwo(length));
+    }
+    if log2(length) > B::TWO_ADICITY {
+        return Err(TraceError::TraceLengthTooLarge(
+            B::TWO_ADICITY,
+            log2(length),
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,15 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Feature-based re-export of the clone-on-write smart pointer.
+//!
+//! When `std` feature is enabled, this module exports [Cow] from the Rust standard library.
+//! Otherwise, the same type is provided without relying on the Rust standard library.
+
+#[cfg(not(feature = "std"))]
+pub use alloc::borrow::Cow;
+
+#[cfg(feature = "std")]
+pub use std::borrow::Cow;
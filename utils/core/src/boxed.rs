@@ -0,0 +1,15 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Feature-based re-export of the heap-allocated box type.
+//!
+//! When `std` feature is enabled, this module exports [Box] from the Rust standard library.
+//! Otherwise, the same type is provided without relying on the Rust standard library.
+
+#[cfg(not(feature = "std"))]
+pub use alloc::boxed::Box;
+
+#[cfg(feature = "std")]
+pub use std::boxed::Box;
@@ -0,0 +1,121 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+// GLOBAL THRESHOLDS
+// ================================================================================================
+
+static MIN_FFT_SIZE: AtomicUsize = AtomicUsize::new(ConcurrencyConfig::DEFAULT.min_fft_size);
+static MIN_MERKLE_LEAVES: AtomicUsize =
+    AtomicUsize::new(ConcurrencyConfig::DEFAULT.min_merkle_leaves);
+static MIN_CONSTRAINT_DOMAIN_SIZE: AtomicUsize =
+    AtomicUsize::new(ConcurrencyConfig::DEFAULT.min_constraint_domain_size);
+
+// CONCURRENCY CONFIG
+// ================================================================================================
+
+/// Thresholds which determine when multi-threaded code paths are used.
+///
+/// When `concurrent` feature is enabled, operations over inputs smaller than the corresponding
+/// threshold (for Merkle trees, not larger than the threshold) are executed in a single thread,
+/// as the overhead of distributing the work across threads would outweigh the gains. The optimal
+/// thresholds depend on the number of available cores: on machines with many cores, lowering the
+/// thresholds may speed up proof generation for small computations, while on machines with few
+/// cores, raising them may reduce overhead.
+///
+/// The thresholds are global: they are read via [global()](ConcurrencyConfig::global) by all
+/// crates of the library (polynomial evaluation and interpolation, Merkle tree construction,
+/// constraint evaluation), and can be updated at any time via
+/// [set_global()](ConcurrencyConfig::set_global). Thresholds affect only performance; proofs
+/// generated with any thresholds are identical. When `concurrent` feature is disabled, the
+/// thresholds are ignored.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct ConcurrencyConfig {
+    min_fft_size: usize,
+    min_merkle_leaves: usize,
+    min_constraint_domain_size: usize,
+}
+
+impl ConcurrencyConfig {
+    /// Default thresholds: 1024 elements for FFT-based polynomial operations, 1024 leaves for
+    /// Merkle trees, and 8192 elements for the constraint evaluation domain.
+    pub const DEFAULT: Self = Self {
+        min_fft_size: 1024,
+        min_merkle_leaves: 1024,
+        min_constraint_domain_size: 8192,
+    };
+
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the thresholds currently used by the library.
+    pub fn global() -> Self {
+        Self {
+            min_fft_size: MIN_FFT_SIZE.load(Ordering::Relaxed),
+            min_merkle_leaves: MIN_MERKLE_LEAVES.load(Ordering::Relaxed),
+            min_constraint_domain_size: MIN_CONSTRAINT_DOMAIN_SIZE.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Returns a new config with the minimum number of elements for which polynomial evaluation
+    /// and interpolation (as well as other FFT-related operations) are performed in multiple
+    /// threads set to the specified value.
+    pub fn with_min_fft_size(mut self, size: usize) -> Self {
+        self.min_fft_size = size;
+        self
+    }
+
+    /// Returns a new config with the number of leaves above which internal nodes of a Merkle tree
+    /// are built in multiple threads set to the specified value.
+    pub fn with_min_merkle_leaves(mut self, num_leaves: usize) -> Self {
+        self.min_merkle_leaves = num_leaves;
+        self
+    }
+
+    /// Returns a new config with the minimum size of the constraint evaluation domain for which
+    /// constraints are evaluated in multiple threads set to the specified value.
+    pub fn with_min_constraint_domain_size(mut self, size: usize) -> Self {
+        self.min_constraint_domain_size = size;
+        self
+    }
+
+    /// Makes the thresholds of this config the ones used by the library.
+    ///
+    /// The new thresholds apply to all operations started after this method returns, including
+    /// operations performed by provers running in other threads.
+    pub fn set_global(self) {
+        MIN_FFT_SIZE.store(self.min_fft_size, Ordering::Relaxed);
+        MIN_MERKLE_LEAVES.store(self.min_merkle_leaves, Ordering::Relaxed);
+        MIN_CONSTRAINT_DOMAIN_SIZE.store(self.min_constraint_domain_size, Ordering::Relaxed);
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the minimum number of elements for which FFT-related operations are performed in
+    /// multiple threads.
+    pub const fn min_fft_size(&self) -> usize {
+        self.min_fft_size
+    }
+
+    /// Returns the number of leaves above which Merkle trees are built in multiple threads; trees
+    /// with exactly this many leaves are built in a single thread.
+    pub const fn min_merkle_leaves(&self) -> usize {
+        self.min_merkle_leaves
+    }
+
+    /// Returns the minimum size of the constraint evaluation domain for which constraints are
+    /// evaluated in multiple threads.
+    pub const fn min_constraint_domain_size(&self) -> usize {
+        self.min_constraint_domain_size
+    }
+}
+
+impl Default for ConcurrencyConfig {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
@@ -14,6 +14,10 @@ extern crate alloc;
 
 use core::{convert::TryInto, mem, slice};
 
+pub mod borrow;
+
+pub mod boxed;
+
 pub mod collections;
 use collections::Vec;
 
@@ -25,6 +29,18 @@ pub mod iterators;
 mod errors;
 pub use errors::DeserializationError;
 
+mod uninit;
+pub use uninit::UninitBuffer;
+
+mod spans;
+pub use spans::DisabledSpan;
+
+mod concurrency;
+pub use concurrency::ConcurrencyConfig;
+
+mod secret;
+pub use secret::{ct_eq, zeroize};
+
 #[cfg(test)]
 mod tests;
 
@@ -34,6 +50,13 @@ use iterators::*;
 #[cfg(feature = "concurrent")]
 pub use rayon;
 
+#[cfg(feature = "tracing")]
+#[doc(hidden)]
+pub use tracing;
+
+#[cfg(all(feature = "concurrent", target_family = "wasm"))]
+compile_error!("concurrent feature is not supported on WebAssembly targets");
+
 // SERIALIZABLE
 // ================================================================================================
 
@@ -207,6 +230,42 @@ pub trait ByteReader {
 
     /// Returns true if there are more bytes left to be read from `self`.
     fn has_more_bytes(&self) -> bool;
+
+    // PROVIDED METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a usize value encoded as a variable-length integer read from `self`.
+    ///
+    /// The value is expected to be encoded using unsigned LEB128 encoding as produced by
+    /// [ByteWriter::write_usize()].
+    ///
+    /// # Errors
+    /// Returns a [DeserializationError] if:
+    /// * A variable-length integer could not be read from `self`.
+    /// * The encoded value does not fit into a usize, or the encoding is not minimal.
+    fn read_usize(&mut self) -> Result<usize, DeserializationError> {
+        let mut result = 0usize;
+        let mut shift = 0;
+        loop {
+            let byte = self.read_u8()?;
+            let value = (byte & 0x7f) as usize;
+            if shift >= usize::BITS || (value << shift) >> shift != value {
+                return Err(DeserializationError::InvalidValue(
+                    "variable-length integer does not fit into usize".to_string(),
+                ));
+            }
+            result |= value << shift;
+            if byte & 0x80 == 0 {
+                if byte == 0 && shift > 0 {
+                    return Err(DeserializationError::InvalidValue(
+                        "variable-length integer is not minimally encoded".to_string(),
+                    ));
+                }
+                return Ok(result);
+            }
+            shift += 7;
+        }
+    }
 }
 
 // SLICE READER
@@ -301,10 +360,11 @@ impl<'a> ByteReader for SliceReader<'a> {
     }
 
     fn read_u8_vec(&mut self, len: usize) -> Result<Vec<u8>, DeserializationError> {
-        let end_pos = self.pos + len;
-        if end_pos > self.source.len() {
+        // len may come from untrusted input, and thus, we need to guard against overflow
+        if len > self.source.len() - self.pos {
             return Err(DeserializationError::UnexpectedEOF);
         }
+        let end_pos = self.pos + len;
         let result = self.source[self.pos..end_pos].to_vec();
         self.pos = end_pos;
         Ok(result)
@@ -374,6 +434,23 @@ pub trait ByteWriter: Sized {
         self.write_u8_slice(&value.to_le_bytes());
     }
 
+    /// Writes a usize value into `self` as a variable-length integer.
+    ///
+    /// The value is encoded using unsigned LEB128 encoding: 7 bits per byte starting with the
+    /// least significant bits, with the most significant bit of each byte indicating whether more
+    /// bytes follow. Thus, values smaller than 128 are encoded in a single byte.
+    ///
+    /// # Panics
+    /// Panics if the value could not be written into `self`.
+    fn write_usize(&mut self, value: usize) {
+        let mut value = value;
+        while value >= 0x80 {
+            self.write_u8((value as u8) | 0x80);
+            value >>= 7;
+        }
+        self.write_u8(value as u8);
+    }
+
     /// Writes a serializable value into `self`.
     ///
     /// # Panics
@@ -423,13 +500,51 @@ impl<const N: usize> AsBytes for [[u8; N]] {
     }
 }
 
+// ENCODING SELF-TEST
+// ================================================================================================
+
+/// Checks that `value` survives a round trip through its binary encoding.
+///
+/// The value is serialized, deserialized back, and serialized again; the check succeeds if the
+/// deserialized value is equal to the original one, all serialized bytes have been consumed, and
+/// both serializations are identical. All serialization in this crate uses explicit little-endian
+/// byte order and fixed-width or variable-length integers which do not depend on the size of
+/// `usize`; thus, a value passing this check on one target is encoded into the same bytes on all
+/// other targets.
+///
+/// # Errors
+/// Returns an error if any of the above conditions does not hold.
+pub fn check_encoding<T>(value: &T) -> Result<(), DeserializationError>
+where
+    T: Serializable + Deserializable + PartialEq,
+{
+    let bytes = value.to_bytes();
+    let mut source = SliceReader::new(&bytes);
+    let decoded = T::read_from(&mut source)?;
+    if source.has_more_bytes() {
+        return Err(DeserializationError::UnconsumedBytes);
+    }
+    if decoded != *value {
+        return Err(DeserializationError::InvalidValue(
+            "decoded value is not equal to the encoded value".to_string(),
+        ));
+    }
+    if decoded.to_bytes() != bytes {
+        return Err(DeserializationError::InvalidValue(
+            "re-encoding a decoded value resulted in different bytes".to_string(),
+        ));
+    }
+    Ok(())
+}
+
 // VECTOR FUNCTIONS
 // ================================================================================================
 
 /// Returns a vector of the specified length with un-initialized memory.
 ///
 /// This is usually faster than requesting a vector with initialized memory and is useful when we
-/// overwrite all contents of the vector immediately after memory allocation.
+/// overwrite all contents of the vector immediately after memory allocation. Prefer using
+/// [UninitBuffer] which provides the same performance without handing out un-initialized values.
 ///
 /// # Safety
 /// Using values from the returned vector before initializing them will lead to undefined behavior.
@@ -0,0 +1,71 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use core::{
+    ptr,
+    sync::atomic::{self, Ordering},
+};
+
+// ZEROIZATION
+// ================================================================================================
+
+/// Overwrites all values in the specified slice with their default values (i.e., ZEROs for field
+/// elements and integers).
+///
+/// Unlike a regular assignment, the writes are volatile, and thus, cannot be optimized away by
+/// the compiler even if the slice is never read again (e.g., when the slice is about to be
+/// de-allocated). This can be used to make sure sensitive data (e.g., witnesses of a computation)
+/// does not linger in memory after it is no longer needed.
+///
+/// Note that this does not clear copies of the data which may have been made before (e.g., when a
+/// vector was re-allocated as it grew).
+///
+/// # Examples
+/// ```
+/// # use winter_utils::zeroize;
+/// let mut secret = vec![1u64, 2, 3, 4];
+/// zeroize(&mut secret);
+///
+/// assert_eq!(vec![0, 0, 0, 0], secret);
+/// ```
+pub fn zeroize<T: Copy + Default>(values: &mut [T]) {
+    for value in values.iter_mut() {
+        // SAFETY: the pointer is derived from a mutable reference, and thus, is valid for writes
+        // and properly aligned
+        unsafe { ptr::write_volatile(value, T::default()) };
+    }
+    atomic::compiler_fence(Ordering::SeqCst);
+}
+
+// CONSTANT-TIME COMPARISON
+// ================================================================================================
+
+/// Returns true if the two byte slices are equal.
+///
+/// If the slices have the same length, the time it takes to compare them depends only on their
+/// length, but not on their contents (i.e., it does not depend on the position of the first
+/// byte at which the slices differ). Lengths of the slices are not considered to be secret.
+///
+/// # Examples
+/// ```
+/// # use winter_utils::ct_eq;
+/// assert!(ct_eq(b"secret", b"secret"));
+/// assert!(!ct_eq(b"secret", b"secreT"));
+/// assert!(!ct_eq(b"secret", b"secrets"));
+/// ```
+pub fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut diff = 0u8;
+    for (&x, &y) in a.iter().zip(b.iter()) {
+        diff |= x ^ y;
+    }
+    // prevent the compiler from short-circuiting the loop above based on the value of diff
+    // SAFETY: the pointer is derived from a reference to a local variable, and thus, is valid
+    // for reads and properly aligned
+    let diff = unsafe { ptr::read_volatile(&diff) };
+    diff == 0
+}
@@ -0,0 +1,36 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+/// Enters a `tracing` span with the specified name and fields if `tracing` feature is enabled.
+///
+/// When `tracing` feature is enabled, creates an info-level span and enters it; the span is
+/// exited when the returned guard is dropped (or when its `exit()` method is called), and thus,
+/// a subscriber can measure the duration of the code executed while the guard is alive. Fields
+/// are specified using the syntax of `tracing::info_span!` macro (e.g.,
+/// `enter_span!("commit", num_columns = 4)`).
+///
+/// When `tracing` feature is disabled, the fields are not evaluated and a [DisabledSpan] guard is
+/// returned.
+#[macro_export]
+macro_rules! enter_span {
+    ($name: literal $(, $($fields: tt)*)?) => {{
+        #[cfg(feature = "tracing")]
+        let result = $crate::tracing::info_span!($name $(, $($fields)*)?).entered();
+
+        #[cfg(not(feature = "tracing"))]
+        let result = $crate::DisabledSpan;
+
+        result
+    }};
+}
+
+/// A guard returned by [enter_span!] when `tracing` feature is disabled; it does nothing.
+pub struct DisabledSpan;
+
+impl DisabledSpan {
+    /// Does nothing; this mirrors `tracing::span::EnteredSpan::exit()` so that a span can be
+    /// exited explicitly regardless of whether `tracing` feature is enabled.
+    pub fn exit(self) {}
+}
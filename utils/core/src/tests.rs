@@ -3,11 +3,56 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{collections::Vec, ByteReader, ByteWriter, Serializable, SliceReader};
+use super::{
+    check_encoding, collections::Vec, ct_eq, zeroize, ByteReader, ByteWriter, ConcurrencyConfig,
+    Deserializable, DeserializationError, Serializable, SliceReader, UninitBuffer,
+};
 
 // VECTOR UTILS TESTS
 // ================================================================================================
 
+#[test]
+fn uninit_buffer_fill() {
+    let values = UninitBuffer::<u64>::new(16).fill(0..16);
+    assert_eq!((0..16).collect::<Vec<_>>(), values);
+
+    let values = UninitBuffer::<u64>::new(0).fill(0..16);
+    assert!(values.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "expected 16 values to initialize range 0..16, but received 15")]
+fn uninit_buffer_fill_too_few_values() {
+    UninitBuffer::<u64>::new(16).fill(0..15);
+}
+
+#[test]
+fn uninit_buffer_fill_batches() {
+    let n = 1 << 12;
+    let fill = || {
+        UninitBuffer::<u64>::new(n).fill_batches(16, |batch| {
+            // the first value is derived from the start of the batch, and all other values are
+            // derived from the first one; thus, every value is correct only if batch ranges are
+            let first = batch.start as u64 * 3;
+            (0..batch.len() as u64).map(move |i| first + i * 3)
+        })
+    };
+    let expected = (0..n as u64).map(|i| i * 3).collect::<Vec<_>>();
+
+    // when `concurrent` feature is enabled, make sure the buffer is split into many batches
+    #[cfg(feature = "concurrent")]
+    let values = rayon::ThreadPoolBuilder::new()
+        .num_threads(8)
+        .build()
+        .unwrap()
+        .install(fill);
+
+    #[cfg(not(feature = "concurrent"))]
+    let values = fill();
+
+    assert_eq!(expected, values);
+}
+
 #[test]
 fn group_vector_elements() {
     let n = 16;
@@ -86,6 +131,38 @@ fn read_u8_vec() {
     assert_eq!(vec![6, 7], a.read_u8_vec(2).unwrap());
     assert_eq!(vec![8], a.read_u8_vec(1).unwrap());
     assert!(a.read_u8_vec(2).is_err());
+
+    // lengths which would overflow the read position should be rejected
+    a.read_u8_vec(0).unwrap();
+    let mut b = SliceReader::new(&source);
+    b.read_u8().unwrap();
+    assert!(b.read_u8_vec(usize::MAX).is_err());
+}
+
+#[test]
+fn read_usize() {
+    let mut target = Vec::new();
+    for value in [0, 1, 127, 128, 300, 16383, 16384, usize::MAX] {
+        target.write_usize(value);
+    }
+    assert_eq!(
+        vec![0, 1, 127, 0x80, 1, 0xac, 2, 0xff, 0x7f, 0x80, 0x80, 1],
+        target[..12]
+    );
+
+    let mut a = SliceReader::new(&target);
+    for value in [0, 1, 127, 128, 300, 16383, 16384, usize::MAX] {
+        assert_eq!(value, a.read_usize().unwrap());
+    }
+    assert!(a.read_usize().is_err());
+
+    // truncated, overlong, and overflowing encodings should be rejected
+    assert!(SliceReader::new(&[0x80]).read_usize().is_err());
+    assert!(SliceReader::new(&[0x80, 0]).read_usize().is_err());
+    assert!(SliceReader::new(&[0xff; 11]).read_usize().is_err());
+    let mut overflow = vec![0xff; (usize::BITS / 7) as usize];
+    overflow.push(0x7f);
+    assert!(SliceReader::new(&overflow).read_usize().is_err());
 }
 
 // SERIALIZATION TESTS
@@ -97,6 +174,29 @@ impl Serializable for u128 {
     }
 }
 
+impl Deserializable for u128 {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        source.read_u128()
+    }
+}
+
+/// A value with an encoding which does not survive a round trip: only the lower four bits of
+/// the value are serialized.
+#[derive(Debug, PartialEq)]
+struct LossyValue(u8);
+
+impl Serializable for LossyValue {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(self.0 & 0xf);
+    }
+}
+
+impl Deserializable for LossyValue {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Ok(LossyValue(source.read_u8()?))
+    }
+}
+
 #[test]
 fn write_serializable() {
     let mut target: Vec<u8> = Vec::new();
@@ -147,3 +247,72 @@ fn write_serializable_array_batch() {
         assert_eq!(i, reader.read_u128().unwrap());
     }
 }
+
+#[test]
+fn check_encoding_round_trip() {
+    // integers are always encoded in little-endian byte order
+    let value = 0x0102030405060708090a0b0c0d0e0f10u128;
+    assert_eq!((1..=16).rev().collect::<Vec<u8>>(), value.to_bytes());
+    assert!(check_encoding(&value).is_ok());
+
+    // usize values are encoded as variable-length integers independent of pointer width
+    let mut target: Vec<u8> = Vec::new();
+    target.write_usize(300);
+    assert_eq!(vec![0xac, 0x02], target);
+
+    assert!(check_encoding(&LossyValue(0x2)).is_ok());
+    assert!(matches!(
+        check_encoding(&LossyValue(0x12)),
+        Err(DeserializationError::InvalidValue(_))
+    ));
+}
+
+// SECRET HYGIENE TESTS
+// ================================================================================================
+
+#[test]
+fn zeroize_values() {
+    let mut values = vec![1u64, 2, 3, u64::MAX];
+    zeroize(&mut values[1..3]);
+    assert_eq!(vec![1, 0, 0, u64::MAX], values);
+
+    zeroize(&mut values);
+    assert_eq!(vec![0; 4], values);
+
+    let mut empty: [u8; 0] = [];
+    zeroize(&mut empty);
+}
+
+#[test]
+fn ct_eq_bytes() {
+    assert!(ct_eq(&[], &[]));
+    assert!(ct_eq(&[1, 2, 3], &[1, 2, 3]));
+    assert!(!ct_eq(&[1, 2, 3], &[0, 2, 3]));
+    assert!(!ct_eq(&[1, 2, 3], &[1, 2, 4]));
+    assert!(!ct_eq(&[1, 2, 3], &[1, 2]));
+    assert!(!ct_eq(&[], &[0]));
+}
+
+// CONCURRENCY CONFIG TESTS
+// ================================================================================================
+
+#[test]
+fn concurrency_config() {
+    assert_eq!(ConcurrencyConfig::DEFAULT, ConcurrencyConfig::global());
+
+    let config = ConcurrencyConfig::default()
+        .with_min_fft_size(64)
+        .with_min_merkle_leaves(128)
+        .with_min_constraint_domain_size(256);
+    config.set_global();
+    assert_eq!(config, ConcurrencyConfig::global());
+    assert_eq!(64, ConcurrencyConfig::global().min_fft_size());
+    assert_eq!(128, ConcurrencyConfig::global().min_merkle_leaves());
+    assert_eq!(
+        256,
+        ConcurrencyConfig::global().min_constraint_domain_size()
+    );
+
+    ConcurrencyConfig::DEFAULT.set_global();
+    assert_eq!(ConcurrencyConfig::DEFAULT, ConcurrencyConfig::global());
+}
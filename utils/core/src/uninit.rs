@@ -0,0 +1,182 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::collections::Vec;
+use core::{mem::MaybeUninit, ops::Range};
+
+#[cfg(feature = "concurrent")]
+use super::iterators::*;
+
+// UNINIT BUFFER
+// ================================================================================================
+
+/// A buffer of un-initialized memory which can be safely converted into a vector.
+///
+/// This is a safe alternative to [uninit_vector()](crate::uninit_vector): the memory of the
+/// buffer is allocated without being initialized, but the only way to get a `Vec<T>` out of the
+/// buffer without `unsafe` code is to write every element of the buffer via one of the `fill`
+/// methods. Thus, reading un-initialized memory is impossible without explicitly calling
+/// [UninitBuffer::assume_init()].
+///
+/// # Examples
+/// ```
+/// # use winter_utils::UninitBuffer;
+/// let buffer = UninitBuffer::<u64>::new(8);
+/// let squares = buffer.fill((0..8).map(|i| i * i));
+///
+/// assert_eq!(vec![0, 1, 4, 9, 16, 25, 36, 49], squares);
+/// ```
+pub struct UninitBuffer<T> {
+    data: Vec<MaybeUninit<T>>,
+}
+
+impl<T> UninitBuffer<T> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new buffer capable of holding `length` elements; memory for the elements is
+    /// allocated but not initialized.
+    pub fn new(length: usize) -> Self {
+        let mut data = Vec::with_capacity(length);
+        data.resize_with(length, MaybeUninit::uninit);
+        Self { data }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of elements in this buffer.
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns true if this buffer cannot hold any elements.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Returns a mutable slice of possibly un-initialized elements of this buffer.
+    ///
+    /// Values can be written into the slice via [MaybeUninit::write()]. Once all values have
+    /// been written, the buffer can be converted into a vector via
+    /// [UninitBuffer::assume_init()].
+    pub fn as_uninit_slice_mut(&mut self) -> &mut [MaybeUninit<T>] {
+        &mut self.data
+    }
+
+    // INITIALIZERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Writes values from the provided iterator into this buffer and returns the resulting
+    /// vector.
+    ///
+    /// # Panics
+    /// Panics if the iterator yields fewer elements than the length of this buffer. Any extra
+    /// elements yielded by the iterator are ignored.
+    pub fn fill<I>(mut self, values: I) -> Vec<T>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let length = self.len();
+        write_batch(&mut self.data, 0..length, values);
+        // SAFETY: all elements of the buffer were initialized by write_batch()
+        unsafe { self.assume_init() }
+    }
+
+    /// Splits this buffer into batches, writes values produced by `init` into each batch, and
+    /// returns the resulting vector.
+    ///
+    /// For every batch, `init` is invoked with the range of indexes covered by the batch, and
+    /// must return exactly as many values as there are indexes in the range. This makes it
+    /// possible to amortize per-batch work (e.g., allocating scratch space or computing the
+    /// first element of a sequence) across all elements of a batch.
+    ///
+    /// When `concurrent` feature is enabled, batches are initialized in multiple threads, but
+    /// each batch will contain at least `min_batch_size` elements. Otherwise, the entire buffer
+    /// is initialized as a single batch.
+    ///
+    /// # Panics
+    /// Panics if `init` returns fewer values than the length of a batch.
+    ///
+    /// # Examples
+    /// ```
+    /// # use winter_utils::UninitBuffer;
+    /// let buffer = UninitBuffer::<u64>::new(1024);
+    /// let values = buffer.fill_batches(128, |batch| batch.map(|i| i as u64 * 2));
+    ///
+    /// assert_eq!((0..1024).map(|i| i * 2).collect::<Vec<u64>>(), values);
+    /// ```
+    pub fn fill_batches<I, F>(mut self, min_batch_size: usize, init: F) -> Vec<T>
+    where
+        T: Send,
+        I: IntoIterator<Item = T>,
+        F: Fn(Range<usize>) -> I + Send + Sync,
+    {
+        let length = self.len();
+
+        #[cfg(feature = "concurrent")]
+        {
+            let batch_size = length / rayon_num_threads().next_power_of_two();
+            if batch_size < min_batch_size.max(1) {
+                write_batch(&mut self.data, 0..length, init(0..length));
+            } else {
+                self.data
+                    .par_chunks_mut(batch_size)
+                    .enumerate()
+                    .for_each(|(i, batch)| {
+                        let start = i * batch_size;
+                        let range = start..(start + batch.len());
+                        write_batch(batch, range.clone(), init(range));
+                    });
+            }
+        }
+
+        #[cfg(not(feature = "concurrent"))]
+        {
+            let _ = min_batch_size;
+            write_batch(&mut self.data, 0..length, init(0..length));
+        }
+
+        // SAFETY: all elements of the buffer were initialized by write_batch()
+        unsafe { self.assume_init() }
+    }
+
+    /// Converts this buffer into a vector of initialized elements.
+    ///
+    /// # Safety
+    /// All elements of the buffer must have been initialized (e.g., via
+    /// [UninitBuffer::as_uninit_slice_mut()]); otherwise, using the returned vector will lead to
+    /// undefined behavior.
+    pub unsafe fn assume_init(self) -> Vec<T> {
+        let mut data = core::mem::ManuallyDrop::new(self.data);
+        let p = data.as_mut_ptr();
+        let len = data.len();
+        let cap = data.capacity();
+        Vec::from_raw_parts(p as *mut T, len, cap)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Writes values into all slots of the `batch` and makes sure that the number of values is
+/// sufficient to initialize the entire batch.
+fn write_batch<T, I>(batch: &mut [MaybeUninit<T>], range: Range<usize>, values: I)
+where
+    I: IntoIterator<Item = T>,
+{
+    let mut num_written = 0;
+    for (slot, value) in batch.iter_mut().zip(values) {
+        slot.write(value);
+        num_written += 1;
+    }
+    assert!(
+        num_written == batch.len(),
+        "expected {} values to initialize range {:?}, but received {}",
+        batch.len(),
+        range,
+        num_written
+    );
+}
@@ -5,7 +5,7 @@
 
 use crate::VerifierError;
 use air::{
-    proof::{Queries, StarkProof, Table},
+    proof::{get_leaf_positions, Queries, StarkProof, Table},
     Air, EvaluationFrame,
 };
 use crypto::{BatchMerkleProof, ElementHasher, MerkleTree};
@@ -64,6 +64,12 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> VerifierChanne
             return Err(VerifierError::InconsistentBaseField);
         }
 
+        // make sure the proof is interpreted in the extension field it was generated in
+        let extension_degree = context.options().field_extension().degree() as usize;
+        if E::ELEMENT_BYTES != E::BaseField::ELEMENT_BYTES * extension_degree {
+            return Err(VerifierError::InconsistentFieldExtension(extension_degree));
+        }
+
         let num_trace_segments = air.trace_layout().num_segments();
         let main_trace_width = air.trace_layout().main_trace_width();
         let aux_trace_width = air.trace_layout().aux_trace_width();
@@ -88,7 +94,11 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> VerifierChanne
             .parse_remainder()
             .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
         let (fri_layer_queries, fri_layer_proofs) = fri_proof
-            .parse_layers::<H, E>(lde_domain_size, fri_options.folding_factor())
+            .parse_layers::<H, E>(
+                lde_domain_size,
+                fri_options.folding_factor(),
+                fri_options.merkle_arity(),
+            )
             .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
 
         // --- parse out-of-domain evaluation frame -----------------------------------------------
@@ -171,13 +181,57 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> VerifierChanne
     ) -> Result<(Table<E::BaseField>, Option<Table<E>>), VerifierError> {
         let queries = self.trace_queries.take().expect("already read");
 
+        // determine which leaves of the trace commitment contain rows at the queried positions;
+        // the proof must contain exactly these leaves
+        let rows_per_leaf = queries.rows_per_leaf;
+        let leaf_positions = get_leaf_positions(positions, rows_per_leaf);
+        if queries.main_states.num_rows() != leaf_positions.len() {
+            return Err(VerifierError::TraceQueryDoesNotMatchCommitment);
+        }
+
         // make sure the states included in the proof correspond to the trace commitment
         for (root, proof) in self.trace_roots.iter().zip(queries.query_proofs.iter()) {
-            MerkleTree::verify_batch(root, positions, proof)
+            MerkleTree::verify_batch(root, &leaf_positions, proof)
                 .map_err(|_| VerifierError::TraceQueryDoesNotMatchCommitment)?;
         }
 
-        Ok((queries.main_states, queries.aux_states))
+        // merge tables for each auxiliary segment into a single table
+        let merge_aux_states = |aux_states: Vec<Table<E>>| {
+            if aux_states.is_empty() {
+                None
+            } else {
+                Some(Table::merge(aux_states))
+            }
+        };
+
+        if rows_per_leaf == 1 {
+            let aux_states = merge_aux_states(queries.aux_states);
+            return Ok((queries.main_states, aux_states));
+        }
+
+        // unpack the rows at the queried positions from the opened leaves; this must be done for
+        // each segment separately because rows of a leaf are laid out one after another
+        let row_indexes = positions
+            .iter()
+            .map(|&position| {
+                let leaf_idx = position / rows_per_leaf;
+                let leaf_offset = leaf_positions.iter().position(|&i| i == leaf_idx).unwrap();
+                leaf_offset * rows_per_leaf + position % rows_per_leaf
+            })
+            .collect::<Vec<_>>();
+        let main_states = queries
+            .main_states
+            .split_rows(rows_per_leaf)
+            .select_rows(&row_indexes);
+        let aux_states = merge_aux_states(
+            queries
+                .aux_states
+                .into_iter()
+                .map(|states| states.split_rows(rows_per_leaf).select_rows(&row_indexes))
+                .collect(),
+        );
+
+        Ok((main_states, aux_states))
     }
 
     /// Returns constraint evaluations at the specified positions of the LDE domain. This also
@@ -234,11 +288,13 @@ where
 /// * Queried states for all trace segments.
 /// * Merkle authentication paths for all queries.
 ///
-/// Trace states for all auxiliary segments are stored in a single table.
+/// Trace states for each auxiliary segment are stored in a separate table; these tables are
+/// merged into a single table when the states are read.
 struct TraceQueries<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> {
     query_proofs: Vec<BatchMerkleProof<H>>,
     main_states: Table<E::BaseField>,
-    aux_states: Option<Table<E>>,
+    aux_states: Vec<Table<E>>,
+    rows_per_leaf: usize,
 }
 
 impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> TraceQueries<E, H> {
@@ -256,14 +312,38 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> TraceQueries<E
             queries.len()
         );
 
-        let num_queries = air.options().num_queries();
+        let merkle_arity = air.options().merkle_arity();
 
-        // parse main trace segment queries; parsing also validates that hashes of each table row
-        // form the leaves of Merkle authentication paths in the proofs
+        // when multiple adjacent rows are committed to in a single leaf, every table row contains
+        // all rows of a leaf, and several queries may fall into the same leaf
+        let rows_per_leaf = air.options().trace_rows_per_leaf();
+        let num_leaves = air.lde_domain_size() / rows_per_leaf;
+
+        // the number of opened leaves depends on query positions which are not known yet; so, we
+        // infer it from the size of the main segment queries and validate it when the states are
+        // read
         let main_segment_width = air.trace_layout().main_trace_width();
         let main_segment_queries = queries.remove(0);
+        let leaf_bytes = E::BaseField::ELEMENT_BYTES * main_segment_width * rows_per_leaf;
+        let num_queries = main_segment_queries.num_value_bytes() / leaf_bytes;
+        if num_queries == 0 || num_queries > air.options().num_queries() {
+            return Err(VerifierError::ProofDeserializationError(format!(
+                "main trace segment query deserialization failed: expected at most {} queried \
+                leaves, but was {}",
+                air.options().num_queries(),
+                num_queries
+            )));
+        }
+
+        // parse main trace segment queries; parsing also validates that hashes of each table row
+        // form the leaves of Merkle authentication paths in the proofs
         let (main_segment_query_proofs, main_segment_states) = main_segment_queries
-            .parse::<H, E::BaseField>(air.lde_domain_size(), num_queries, main_segment_width)
+            .parse::<H, E::BaseField>(
+                num_leaves,
+                num_queries,
+                main_segment_width * rows_per_leaf,
+                merkle_arity,
+            )
             .map_err(|err| {
                 VerifierError::ProofDeserializationError(format!(
                     "main trace segment query deserialization failed: {err}"
@@ -273,35 +353,33 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> TraceQueries<E
         // all query proofs will be aggregated into a single vector
         let mut query_proofs = vec![main_segment_query_proofs];
 
-        // parse auxiliary trace segment queries (if any), and merge resulting tables into a
-        // single table; parsing also validates that hashes of each table row form the leaves
-        // of Merkle authentication paths in the proofs
-        let aux_trace_states = if air.trace_info().is_multi_segment() {
-            let mut aux_trace_states = Vec::new();
-            for (i, segment_queries) in queries.into_iter().enumerate() {
-                let segment_width = air.trace_layout().get_aux_segment_width(i);
-                let (segment_query_proof, segment_trace_states) = segment_queries
-                    .parse::<H, E>(air.lde_domain_size(), num_queries, segment_width)
-                    .map_err(|err| {
-                        VerifierError::ProofDeserializationError(format!(
-                            "auxiliary trace segment query deserialization failed: {err}"
-                        ))
-                    })?;
-
-                query_proofs.push(segment_query_proof);
-                aux_trace_states.push(segment_trace_states);
-            }
-
-            // merge tables for each auxiliary segment into a single table
-            Some(Table::merge(aux_trace_states))
-        } else {
-            None
-        };
+        // parse auxiliary trace segment queries (if any); parsing also validates that hashes of
+        // each table row form the leaves of Merkle authentication paths in the proofs
+        let mut aux_trace_states = Vec::new();
+        for (i, segment_queries) in queries.into_iter().enumerate() {
+            let segment_width = air.trace_layout().get_aux_segment_width(i);
+            let (segment_query_proof, segment_trace_states) = segment_queries
+                .parse::<H, E>(
+                    num_leaves,
+                    num_queries,
+                    segment_width * rows_per_leaf,
+                    merkle_arity,
+                )
+                .map_err(|err| {
+                    VerifierError::ProofDeserializationError(format!(
+                        "auxiliary trace segment query deserialization failed: {err}"
+                    ))
+                })?;
+
+            query_proofs.push(segment_query_proof);
+            aux_trace_states.push(segment_trace_states);
+        }
 
         Ok(Self {
             query_proofs,
             main_states: main_segment_states,
             aux_states: aux_trace_states,
+            rows_per_leaf,
         })
     }
 }
@@ -326,7 +404,12 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> ConstraintQuer
     ) -> Result<Self, VerifierError> {
         let num_queries = air.options().num_queries();
         let (query_proofs, evaluations) = queries
-            .parse::<H, E>(air.lde_domain_size(), num_queries, air.ce_blowup_factor())
+            .parse::<H, E>(
+                air.lde_domain_size(),
+                num_queries,
+                air.ce_blowup_factor(),
+                air.options().merkle_arity(),
+            )
             .map_err(|err| {
                 VerifierError::ProofDeserializationError(format!(
                     "constraint evaluation query deserialization failed: {err}"
@@ -5,8 +5,10 @@
 
 //! Contains common error types for prover and verifier.
 
+use air::{DomainError, PolicyError};
 use core::fmt;
-use utils::string::String;
+use crypto::HashFunction;
+use utils::{boxed::Box, string::String};
 
 // VERIFIER ERROR
 // ================================================================================================
@@ -16,9 +18,31 @@ pub enum VerifierError {
     /// This error occurs when base field read by a verifier from a proof does not match the
     /// base field of AIR with which the verifier was instantiated.
     InconsistentBaseField,
+    /// This error occurs when the extension field in which a verifier interprets a proof is not
+    /// of the degree specified by the proof.
+    InconsistentFieldExtension(usize),
     /// This error occurs when the base field in which the proof was generated does not support
     /// field extension of degree specified by the proof.
     UnsupportedFieldExtension(usize),
+    /// This error occurs when domains of the computation described by the proof cannot be built
+    /// in the base field of AIR with which the verifier was instantiated.
+    InvalidDomain(DomainError),
+    /// This error occurs when the digest of public inputs included in the proof does not match
+    /// the digest of public inputs with which the verifier was invoked.
+    InconsistentPublicInputs,
+    /// This error occurs when the digest size specified by the proof is different from the size
+    /// of digests produced by the hash function with which the verifier was instantiated.
+    InconsistentDigestSize(usize, usize),
+    /// This error occurs when the hash function recorded in the proof is different from the hash
+    /// function with which the verifier was instantiated.
+    InconsistentHashFunction(HashFunction, HashFunction),
+    /// This error occurs when the context of the proof does not match the computation described
+    /// by the AIR instantiated for the proof; the error describes the first mismatched
+    /// parameter.
+    InconsistentProofContext(ContextMismatch),
+    /// This error occurs when a proof does not satisfy the policy with which the verifier was
+    /// invoked.
+    PolicyViolation(PolicyError),
     /// This error occurs when a verifier cannot deserialize the specified proof.
     ProofDeserializationError(String),
     /// This error occurs when a verifier fails to draw a random value from a random coin
@@ -41,6 +65,79 @@ pub enum VerifierError {
     /// constraint evaluation queries do not represent a polynomial of the degree expected by the
     /// verifier.
     FriVerificationFailed(fri::VerifierError),
+    /// This error occurs when the number of public inputs provided for a batch of proofs is
+    /// different from the number of proofs in the batch.
+    InconsistentBatchSize(usize, usize),
+    /// This error occurs when a proof at the specified index in a batch of proofs fails
+    /// verification.
+    InvalidBatchProof(usize, Box<VerifierError>),
+    /// This error occurs when a chain of proofs does not contain any proofs.
+    EmptyProofChain,
+    /// This error occurs when the input state of the first proof in a chain of proofs is
+    /// different from the input state with which the verifier was invoked.
+    InconsistentChainInputState,
+    /// This error occurs when the output state of the last proof in a chain of proofs is
+    /// different from the output state with which the verifier was invoked.
+    InconsistentChainOutputState,
+    /// This error occurs when the input state of a proof at the specified index in a chain of
+    /// proofs is different from the output state of the previous proof.
+    BrokenChainLink(usize),
+}
+
+impl VerifierError {
+    /// Returns true if the error means that the proof does not attest to a correct execution of
+    /// the computation against the specified public inputs.
+    ///
+    /// Otherwise, the error means that the proof could not be verified in the configuration with
+    /// which the verifier was invoked (e.g., the verifier was instantiated with a different base
+    /// field or hash function, or the proof does not satisfy the verifier policy); such a proof
+    /// may still be valid. For errors in a batch of proofs, this refers to the failed proof.
+    pub fn is_invalid_proof(&self) -> bool {
+        match self {
+            Self::InconsistentBaseField
+            | Self::InconsistentFieldExtension(_)
+            | Self::UnsupportedFieldExtension(_)
+            | Self::InvalidDomain(_)
+            | Self::InconsistentDigestSize(_, _)
+            | Self::InconsistentHashFunction(_, _)
+            | Self::PolicyViolation(_)
+            | Self::InconsistentBatchSize(_, _) => false,
+            Self::InvalidBatchProof(_, err) => err.is_invalid_proof(),
+            _ => true,
+        }
+    }
+
+    /// Returns the index of the proof which failed verification, if the error was returned for a
+    /// batch or a chain of proofs.
+    pub fn batch_index(&self) -> Option<usize> {
+        match self {
+            Self::InvalidBatchProof(index, _) | Self::BrokenChainLink(index) => Some(*index),
+            _ => None,
+        }
+    }
+
+    /// Returns the depth of the FRI layer at which verification failed, if the error is specific
+    /// to a single layer of the low-degree proof.
+    pub fn fri_layer(&self) -> Option<usize> {
+        match self {
+            Self::FriVerificationFailed(err) => err.layer(),
+            Self::InvalidBatchProof(_, err) => err.fri_layer(),
+            _ => None,
+        }
+    }
+
+    /// Returns the query position at which verification failed, if the error is specific to a
+    /// single query.
+    ///
+    /// See [fri::VerifierError::query_position()] for the domain in which the position is
+    /// defined.
+    pub fn query_position(&self) -> Option<usize> {
+        match self {
+            Self::FriVerificationFailed(err) => err.query_position(),
+            Self::InvalidBatchProof(_, err) => err.query_position(),
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Display for VerifierError {
@@ -50,9 +147,30 @@ impl fmt::Display for VerifierError {
             Self::InconsistentBaseField =>  {
                 write!(f, "base field of the proof does not match base field of the specified AIR")
             }
+            Self::InconsistentFieldExtension(degree) => {
+                write!(f, "proof was generated in a field extension of degree {degree}, but is verified in a different field")
+            }
             Self::UnsupportedFieldExtension(degree) => {
                 write!(f, "field extension of degree {degree} is not supported for the proof base field")
             }
+            Self::InvalidDomain(err) => {
+                write!(f, "invalid computation domain: {err}")
+            }
+            Self::InconsistentPublicInputs => {
+                write!(f, "proof was generated for different public inputs")
+            }
+            Self::InconsistentDigestSize(expected, actual) => {
+                write!(f, "proof requires {expected}-byte digests, but the hash function produces {actual}-byte digests")
+            }
+            Self::InconsistentHashFunction(expected, actual) => {
+                write!(f, "proof was generated with hash function {expected:?}, but the verifier was instantiated with hash function {actual:?}")
+            }
+            Self::InconsistentProofContext(mismatch) => {
+                write!(f, "proof context does not match the AIR: {mismatch}")
+            }
+            Self::PolicyViolation(err) => {
+                write!(f, "proof does not satisfy verifier policy: {err}")
+            }
             Self::ProofDeserializationError(msg) => {
                 write!(f, "proof deserialization failed: {msg}")
             }
@@ -74,6 +192,69 @@ impl fmt::Display for VerifierError {
             Self::FriVerificationFailed(err) => {
                 write!(f, "verification of low-degree proof failed: {err}")
             }
+            Self::InconsistentBatchSize(num_proofs, num_inputs) => {
+                write!(f, "batch contains {num_proofs} proofs, but public inputs were provided for {num_inputs} proofs")
+            }
+            Self::InvalidBatchProof(index, err) => {
+                write!(f, "verification of proof {index} in the batch failed: {err}")
+            }
+            Self::EmptyProofChain => {
+                write!(f, "proof chain does not contain any proofs")
+            }
+            Self::InconsistentChainInputState => {
+                write!(f, "input state of the proof chain does not match the expected input state")
+            }
+            Self::InconsistentChainOutputState => {
+                write!(f, "output state of the proof chain does not match the expected output state")
+            }
+            Self::BrokenChainLink(index) => {
+                write!(f, "input state of proof {index} in the chain does not match output state of the previous proof")
+            }
+        }
+    }
+}
+
+// CONTEXT MISMATCH
+// ================================================================================================
+/// Describes a parameter of a proof context which does not match the computation described by an
+/// AIR instantiated for the proof.
+///
+/// Where values are included, the first one is implied by the AIR and the second one is recorded
+/// in the proof.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContextMismatch {
+    /// The width of the main trace segment is different.
+    MainTraceWidth(usize, usize),
+    /// The number or the widths of auxiliary trace segments, or the numbers of random elements
+    /// used to build them, are different.
+    AuxTraceLayout,
+    /// The length of the execution trace is different.
+    TraceLength(usize, usize),
+    /// The trace metadata is different.
+    TraceMeta,
+    /// The proof options are different.
+    Options,
+}
+
+impl fmt::Display for ContextMismatch {
+    #[rustfmt::skip]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MainTraceWidth(expected, actual) => {
+                write!(f, "expected main trace width of {expected}, but was {actual}")
+            }
+            Self::AuxTraceLayout => {
+                write!(f, "layout of auxiliary trace segments is different")
+            }
+            Self::TraceLength(expected, actual) => {
+                write!(f, "expected trace length of {expected}, but was {actual}")
+            }
+            Self::TraceMeta => {
+                write!(f, "trace metadata is different")
+            }
+            Self::Options => {
+                write!(f, "proof options are different")
+            }
         }
     }
 }
@@ -4,7 +4,7 @@
 // LICENSE file in the root directory of this source tree.
 
 use air::{Air, AuxTraceRandElements, ConstraintCompositionCoefficients, EvaluationFrame};
-use math::{polynom, FieldElement};
+use math::{batch_exp, polynom, FieldElement};
 use utils::collections::Vec;
 
 // CONSTRAINT EVALUATION
@@ -62,20 +62,22 @@ pub fn evaluate_constraints<A: Air, E: FieldElement<BaseField = A::BaseField>>(
     let b_constraints =
         air.get_boundary_constraints(&aux_rand_elements, &composition_coefficients.boundary);
 
-    // cache power of x here so that we only re-compute it when degree_adjustment changes
-    let mut degree_adjustment = b_constraints.main_constraints()[0].degree_adjustment();
-    let mut xp = x.exp_vartime(degree_adjustment.into());
+    // compute all powers of x needed for degree adjustment at once; boundary constraint groups
+    // tend to share only a few distinct degree adjustments, so this is much cheaper than
+    // exponentiating x for every group
+    let main_xp = batch_exp(
+        x,
+        &b_constraints
+            .main_constraints()
+            .iter()
+            .map(|group| group.degree_adjustment())
+            .collect::<Vec<_>>(),
+    );
 
     // iterate over boundary constraint groups for the main trace segment (each group has a
     // distinct divisor), evaluate constraints in each group and add their combination to the
     // result
-    for group in b_constraints.main_constraints().iter() {
-        // if adjustment degree hasn't changed, no need to recompute `xp` - so just reuse the
-        // previous value; otherwise, compute new `xp`
-        if group.degree_adjustment() != degree_adjustment {
-            degree_adjustment = group.degree_adjustment();
-            xp = x.exp_vartime(degree_adjustment.into());
-        }
+    for (group, &xp) in b_constraints.main_constraints().iter().zip(main_xp.iter()) {
         // evaluate all constraints in the group, and add the evaluation to the result
         result += group.evaluate_at(main_trace_frame.current(), x, xp);
     }
@@ -84,13 +86,15 @@ pub fn evaluate_constraints<A: Air, E: FieldElement<BaseField = A::BaseField>>(
     // distinct divisor), evaluate constraints in each group and add their combination to the
     // result
     if let Some(aux_trace_frame) = aux_trace_frame {
-        for group in b_constraints.aux_constraints().iter() {
-            // if adjustment degree hasn't changed, no need to recompute `xp` - so just reuse the
-            // previous value; otherwise, compute new `xp`
-            if group.degree_adjustment() != degree_adjustment {
-                degree_adjustment = group.degree_adjustment();
-                xp = x.exp_vartime(degree_adjustment.into());
-            }
+        let aux_xp = batch_exp(
+            x,
+            &b_constraints
+                .aux_constraints()
+                .iter()
+                .map(|group| group.degree_adjustment())
+                .collect::<Vec<_>>(),
+        );
+        for (group, &xp) in b_constraints.aux_constraints().iter().zip(aux_xp.iter()) {
             // evaluate all constraints in the group, and add the evaluation to the result
             result += group.evaluate_at(aux_trace_frame.current(), x, xp);
         }
@@ -15,6 +15,12 @@
 //! 2. Execute [verify()] function and supply the AIR of your computation together with the
 //!    [StarkProof] and related public inputs as parameters.
 //!
+//! Alternatively, a proof can be verified step by step using the [Verifier] struct. Running all
+//! steps performs the same checks as [verify()] does, but each step also returns the intermediate
+//! values computed by the verifier (e.g., the out-of-domain point, queried positions, and DEEP
+//! composition evaluations). This can be useful for comparing this implementation against other
+//! implementations of the protocol.
+//!
 //! # Performance
 //! Proof verification is extremely fast and is nearly independent of the complexity of the
 //! computation being verified. In vast majority of cases proofs can be verified in 3 - 5 ms
@@ -32,40 +38,44 @@
 #[macro_use]
 extern crate alloc;
 
+use air::proof::Context;
 pub use air::{
-    proof::StarkProof, Air, AirContext, Assertion, AuxTraceRandElements, BoundaryConstraint,
-    BoundaryConstraintGroup, ConstraintCompositionCoefficients, ConstraintDivisor,
-    DeepCompositionCoefficients, EvaluationFrame, FieldExtension, ProofOptions, TraceInfo,
-    TransitionConstraintDegree, TransitionConstraintGroup,
+    proof::{ProofBatch, ProofChain, StarkProof, StepPublicInputs, Table, VerifierPolicy},
+    Air, AirContext, Assertion, AuxTraceRandElements, BoundaryConstraint, BoundaryConstraintGroup,
+    ConstraintCompositionCoefficients, ConstraintDivisor, DeepCompositionCoefficients, DomainError,
+    EvaluationFrame, FieldExtension, PolicyError, ProofOptions, ProofOptionsBuilder,
+    ProofOptionsError, TraceInfo, TransitionConstraintDegree, TransitionConstraintGroup,
 };
 
 pub use math;
 use math::{
     fields::{CubeExtension, QuadExtension},
-    FieldElement,
+    FieldElement, StarkField,
 };
 
-use utils::collections::Vec;
+use utils::{boxed::Box, collections::Vec, enter_span};
 pub use utils::{
     ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable, SliceReader,
 };
 
 pub use crypto;
-use crypto::{ElementHasher, RandomCoin};
-
-use fri::FriVerifier;
+use crypto::{ElementHasher, Hasher, RandomCoin, Transcript};
 
 mod channel;
 use channel::VerifierChannel;
 
 mod evaluator;
-use evaluator::evaluate_constraints;
 
 mod composer;
-use composer::DeepComposer;
+
+mod steps;
+pub use steps::{DeepValues, OodValues, QueryValues, Verifier};
 
 mod errors;
-pub use errors::VerifierError;
+pub use errors::{ContextMismatch, VerifierError};
+
+#[cfg(test)]
+mod tests;
 
 // VERIFIER
 // ================================================================================================
@@ -80,219 +90,299 @@ pub use errors::VerifierError;
 /// a correct execution of the computation. This could happen for many various reasons, including:
 /// - The specified proof was generated for a different computation.
 /// - The specified proof was generated for this computation but for different public inputs.
-#[rustfmt::skip]
 pub fn verify<AIR: Air, HashFn: ElementHasher<BaseField = AIR::BaseField>>(
     proof: StarkProof,
     pub_inputs: AIR::PublicInputs,
 ) -> Result<(), VerifierError> {
-    // build a seed for the public coin; the initial seed is the hash of public inputs and proof
-    // context, but as the protocol progresses, the coin will be reseeded with the info received
-    // from the prover
-    let mut public_coin_seed = Vec::new();
-    pub_inputs.write_into(&mut public_coin_seed);
-    proof.context.write_into(&mut public_coin_seed);
+    verify_proof::<AIR, HashFn>(proof, pub_inputs, None)
+}
 
-    // create AIR instance for the computation specified in the proof
-    let air = AIR::new(proof.get_trace_info(), pub_inputs, proof.options().clone());
+/// Replays the specified proof through the verifier's public coin and returns the transcript of
+/// the coin together with the result of verifying the proof against the specified inputs.
+///
+/// The proof is verified as in [verify()], but every operation performed on the public coin is
+/// recorded into a [Transcript] (see [RandomCoin::start_transcript()]). The transcript contains
+/// all operations performed until verification finished or failed; if verification failed before
+/// the public coin was instantiated (e.g., because the proof was generated for different public
+/// inputs), the transcript is empty.
+///
+/// The transcript can be compared against the transcript recorded by the prover (via
+/// `Prover::prove_with_transcript()`) using [Transcript::diff()]; for a valid proof, the two
+/// transcripts are identical.
+pub fn replay_transcript<AIR: Air, HashFn: ElementHasher<BaseField = AIR::BaseField>>(
+    proof: StarkProof,
+    pub_inputs: AIR::PublicInputs,
+) -> (Transcript, Result<(), VerifierError>) {
+    let mut transcript = Transcript::new();
+    let result = verify_proof::<AIR, HashFn>(proof, pub_inputs, Some(&mut transcript));
+    (transcript, result)
+}
+
+/// Verifies the specified proof against the specified public inputs, recording operations
+/// performed on the public coin into the `transcript` (if specified).
+#[rustfmt::skip]
+fn verify_proof<AIR: Air, HashFn: ElementHasher<BaseField = AIR::BaseField>>(
+    proof: StarkProof,
+    pub_inputs: AIR::PublicInputs,
+    transcript: Option<&mut Transcript>,
+) -> Result<(), VerifierError> {
+    let _span = enter_span!(
+        "verify",
+        trace_length = proof.context.trace_length(),
+        num_queries = proof.options().num_queries()
+    );
+
+    let (air, public_coin_seed) = prepare_verification::<AIR, HashFn>(&proof, pub_inputs)?;
 
     // figure out which version of the generic proof verification procedure to run. this is a sort
     // of static dispatch for selecting two generic parameter: extension field and hash function.
     match air.options().field_extension() {
         FieldExtension::None => {
-            let public_coin = RandomCoin::new(&public_coin_seed);
-            let channel = VerifierChannel::new(&air, proof)?;
-            perform_verification::<AIR, AIR::BaseField, HashFn>(air, channel, public_coin)
+            verify_in_field::<AIR, AIR::BaseField, HashFn>(air, proof, &public_coin_seed, transcript)
         },
         FieldExtension::Quadratic => {
-            if !<QuadExtension<AIR::BaseField>>::is_supported() {
-                return Err(VerifierError::UnsupportedFieldExtension(2));
-            }
-            let public_coin = RandomCoin::new(&public_coin_seed);
-            let channel = VerifierChannel::new(&air, proof)?;
-            perform_verification::<AIR, QuadExtension<AIR::BaseField>, HashFn>(air, channel, public_coin)
+            verify_in_field::<AIR, QuadExtension<AIR::BaseField>, HashFn>(air, proof, &public_coin_seed, transcript)
         },
         FieldExtension::Cubic => {
-            if !<CubeExtension<AIR::BaseField>>::is_supported() {
-                return Err(VerifierError::UnsupportedFieldExtension(3));
-            }
-            let public_coin = RandomCoin::new(&public_coin_seed);
-            let channel = VerifierChannel::new(&air, proof)?;
-            perform_verification::<AIR, CubeExtension<AIR::BaseField>, HashFn>(air, channel, public_coin)
+            verify_in_field::<AIR, CubeExtension<AIR::BaseField>, HashFn>(air, proof, &public_coin_seed, transcript)
         },
     }
 }
 
-// VERIFICATION PROCEDURE
-// ================================================================================================
-/// Performs the actual verification by reading the data from the `channel` and making sure it
-/// attests to a correct execution of the computation specified by the provided `air`.
-fn perform_verification<A, E, H>(
+/// Performs all checks of the specified proof which do not depend on the extension field in which
+/// the proof is verified, and instantiates the AIR of the computation described by the proof.
+///
+/// Returns the AIR together with the seed for the public coin.
+fn prepare_verification<AIR: Air, HashFn: ElementHasher<BaseField = AIR::BaseField>>(
+    proof: &StarkProof,
+    pub_inputs: AIR::PublicInputs,
+) -> Result<(AIR, Vec<u8>), VerifierError> {
+    // make sure the proof was generated with the hash function the verifier was instantiated
+    // with; this needs to be done first as all other checks rely on the hash function
+    if proof.context.hash_function() != HashFn::ID {
+        return Err(VerifierError::InconsistentHashFunction(
+            proof.context.hash_function(),
+            HashFn::ID,
+        ));
+    }
+
+    // build a seed for the public coin; the initial seed is the hash of public inputs and proof
+    // context, but as the protocol progresses, the coin will be reseeded with the info received
+    // from the prover
+    let mut public_coin_seed = Vec::new();
+    pub_inputs.write_into(&mut public_coin_seed);
+
+    // make sure the proof was generated for the specified public inputs
+    let pub_inputs_digest = HashFn::hash(&public_coin_seed).to_bytes();
+    if proof.context.pub_inputs_digest() != pub_inputs_digest {
+        return Err(VerifierError::InconsistentPublicInputs);
+    }
+    proof.context.write_into(&mut public_coin_seed);
+
+    // make sure the proof was generated in the base field of the specified AIR; this needs to be
+    // done before anything else is derived from the proof context as field parameters (e.g.,
+    // two-adicity) may be different for different fields
+    if AIR::BaseField::get_modulus_le_bytes() != proof.context.field_modulus_bytes() {
+        return Err(VerifierError::InconsistentBaseField);
+    }
+
+    // make sure all domains of the computation can be built in the base field; this needs to be
+    // done before AIR is instantiated as AIR construction panics for invalid domains
+    AirContext::<AIR::BaseField>::validate_domains(&proof.get_trace_info(), proof.options())
+        .map_err(VerifierError::InvalidDomain)?;
+
+    // make sure the hash function produces digests of the size specified by the proof
+    if let Some(digest_size) = proof.options().digest_size() {
+        let hash_digest_size = <HashFn as Hasher>::Digest::default().to_bytes().len();
+        if digest_size != hash_digest_size {
+            return Err(VerifierError::InconsistentDigestSize(
+                digest_size,
+                hash_digest_size,
+            ));
+        }
+    }
+
+    // create AIR instance for the computation specified in the proof, and make sure the AIR
+    // describes the same computation as the proof context; otherwise, the proof would fail
+    // verification later on without a clear explanation
+    let air = AIR::new(proof.get_trace_info(), pub_inputs, proof.options().clone());
+    check_context(&air, &proof.context).map_err(VerifierError::InconsistentProofContext)?;
+
+    // make sure the base field supports the field extension specified by the proof
+    let is_extension_supported = match air.options().field_extension() {
+        FieldExtension::None => true,
+        FieldExtension::Quadratic => <QuadExtension<AIR::BaseField>>::is_supported(),
+        FieldExtension::Cubic => <CubeExtension<AIR::BaseField>>::is_supported(),
+    };
+    if !is_extension_supported {
+        let degree = air.options().field_extension().degree() as usize;
+        return Err(VerifierError::UnsupportedFieldExtension(degree));
+    }
+
+    Ok((air, public_coin_seed))
+}
+
+/// Instantiates the public coin and the verifier channel for the specified proof, and verifies
+/// the proof in the field `E`.
+///
+/// If a `transcript` is specified, all operations performed on the public coin are recorded into
+/// it.
+fn verify_in_field<A, E, H>(
     air: A,
-    mut channel: VerifierChannel<E, H>,
-    mut public_coin: RandomCoin<A::BaseField, H>,
+    proof: StarkProof,
+    public_coin_seed: &[u8],
+    transcript: Option<&mut Transcript>,
 ) -> Result<(), VerifierError>
 where
     A: Air,
     E: FieldElement<BaseField = A::BaseField>,
     H: ElementHasher<BaseField = A::BaseField>,
 {
-    // 1 ----- trace commitment -------------------------------------------------------------------
-    // Read the commitments to evaluations of the trace polynomials over the LDE domain sent by the
-    // prover. The commitments are used to update the public coin, and draw sets of random elements
-    // from the coin (in the interactive version of the protocol the verifier sends these random
-    // elements to the prover after each commitment is made). When there are multiple trace
-    // commitments (i.e., the trace consists of more than one segment), each previous commitment is
-    // used to draw random elements needed to construct the next trace segment. The last trace
-    // commitment is used to draw a set of random coefficients which the prover uses to compute
-    // constraint composition polynomial.
-    let trace_commitments = channel.read_trace_commitments();
-
-    // reseed the coin with the commitment to the main trace segment
-    public_coin.reseed(trace_commitments[0]);
-
-    // process auxiliary trace segments (if any), to build a set of random elements for each segment
-    let mut aux_trace_rand_elements = AuxTraceRandElements::<E>::new();
-    for (i, commitment) in trace_commitments.iter().skip(1).enumerate() {
-        let rand_elements = air
-            .get_aux_trace_segment_random_elements(i, &mut public_coin)
-            .map_err(|_| VerifierError::RandomCoinError)?;
-        aux_trace_rand_elements.add_segment_elements(rand_elements);
-        public_coin.reseed(*commitment);
+    let mut public_coin = RandomCoin::with_mode(public_coin_seed, air.options().transcript_mode());
+    if transcript.is_some() {
+        public_coin.start_transcript();
+    }
+    let (result, mut public_coin) = match VerifierChannel::new(&air, proof) {
+        Ok(channel) => {
+            let mut verifier = Verifier::<A, E, H>::from_parts(air, channel, public_coin);
+            let result = verifier.verify_all();
+            (result, verifier.into_public_coin())
+        }
+        Err(err) => (Err(err), public_coin),
+    };
+    if let Some(transcript) = transcript {
+        *transcript = public_coin
+            .take_transcript()
+            .expect("transcript must be recorded when requested");
     }
+    result
+}
 
-    // build random coefficients for the composition polynomial
-    let constraint_coeffs = air
-        .get_constraint_composition_coefficients(&mut public_coin)
-        .map_err(|_| VerifierError::RandomCoinError)?;
-
-    // 2 ----- constraint commitment --------------------------------------------------------------
-    // read the commitment to evaluations of the constraint composition polynomial over the LDE
-    // domain sent by the prover, use it to update the public coin, and draw an out-of-domain point
-    // z from the coin; in the interactive version of the protocol, the verifier sends this point z
-    // to the prover, and the prover evaluates trace and constraint composition polynomials at z,
-    // and sends the results back to the verifier.
-    let constraint_commitment = channel.read_constraint_commitment();
-    public_coin.reseed(constraint_commitment);
-    let z = public_coin
-        .draw::<E>()
-        .map_err(|_| VerifierError::RandomCoinError)?;
-
-    // 3 ----- OOD consistency check --------------------------------------------------------------
-    // make sure that evaluations obtained by evaluating constraints over the out-of-domain frame
-    // are consistent with the evaluations of composition polynomial columns sent by the prover
-
-    // read the out-of-domain trace frames (the main trace frame and auxiliary trace frame, if
-    // provided) sent by the prover and evaluate constraints over them; also, reseed the public
-    // coin with the OOD frames received from the prover.
-    let (ood_main_trace_frame, ood_aux_trace_frame) = channel.read_ood_trace_frame();
-    let ood_constraint_evaluation_1 = evaluate_constraints(
-        &air,
-        constraint_coeffs,
-        &ood_main_trace_frame,
-        &ood_aux_trace_frame,
-        aux_trace_rand_elements,
-        z,
-    );
+/// Verifies that the specified proof satisfies the specified `policy` and attests to the correct
+/// execution of the computation against the specified public inputs.
+///
+/// The policy is checked before the proof is verified via the [verify()] function; thus, proofs
+/// which do not satisfy the policy are rejected without spending any time on verification.
+///
+/// # Errors
+/// Returns an error if the proof does not satisfy the `policy`, or if the proof does not attest
+/// to a correct execution of the computation against the specified public inputs.
+pub fn verify_with_policy<AIR: Air, HashFn: ElementHasher<BaseField = AIR::BaseField>>(
+    proof: StarkProof,
+    pub_inputs: AIR::PublicInputs,
+    policy: &VerifierPolicy,
+) -> Result<(), VerifierError> {
+    policy
+        .check::<HashFn>(&proof)
+        .map_err(VerifierError::PolicyViolation)?;
+    verify::<AIR, HashFn>(proof, pub_inputs)
+}
 
-    if let Some(ref aux_trace_frame) = ood_aux_trace_frame {
-        // when the trace contains auxiliary segments, append auxiliary trace elements at the
-        // end of main trace elements for both current and next rows in the frame. this is
-        // needed to be consistent with how the prover writes OOD frame into the channel.
-
-        let mut current = ood_main_trace_frame.current().to_vec();
-        current.extend_from_slice(aux_trace_frame.current());
-        public_coin.reseed(H::hash_elements(&current));
-
-        let mut next = ood_main_trace_frame.next().to_vec();
-        next.extend_from_slice(aux_trace_frame.next());
-        public_coin.reseed(H::hash_elements(&next));
-    } else {
-        public_coin.reseed(H::hash_elements(ood_main_trace_frame.current()));
-        public_coin.reseed(H::hash_elements(ood_main_trace_frame.next()));
+/// Verifies all proofs in the specified batch against the corresponding public inputs.
+///
+/// Proof at index `i` in the `batch` is verified against public inputs at index `i` in
+/// `pub_inputs` using the [verify()] function. Verification stops at the first proof which fails
+/// verification.
+///
+/// # Errors
+/// Returns an error if:
+/// - The number of public inputs is different from the number of proofs in the batch.
+/// - Any of the proofs in the batch does not attest to a correct execution of the computation
+///   against its public inputs; the error contains the index of the first such proof.
+pub fn verify_batch<AIR: Air, HashFn: ElementHasher<BaseField = AIR::BaseField>>(
+    batch: ProofBatch,
+    pub_inputs: Vec<AIR::PublicInputs>,
+) -> Result<(), VerifierError> {
+    if batch.len() != pub_inputs.len() {
+        return Err(VerifierError::InconsistentBatchSize(
+            batch.len(),
+            pub_inputs.len(),
+        ));
     }
-
-    // read evaluations of composition polynomial columns sent by the prover, and reduce them into
-    // a single value by computing sum(z^i * value_i), where value_i is the evaluation of the ith
-    // column polynomial at z^m, where m is the total number of column polynomials; also, reseed
-    // the public coin with the OOD constraint evaluations received from the prover.
-    let ood_constraint_evaluations = channel.read_ood_constraint_evaluations();
-    let ood_constraint_evaluation_2 = ood_constraint_evaluations
-        .iter()
-        .enumerate()
-        .fold(E::ZERO, |result, (i, &value)| {
-            result + z.exp_vartime((i as u32).into()) * value
-        });
-    public_coin.reseed(H::hash_elements(&ood_constraint_evaluations));
-
-    // finally, make sure the values are the same
-    if ood_constraint_evaluation_1 != ood_constraint_evaluation_2 {
-        return Err(VerifierError::InconsistentOodConstraintEvaluations);
+    for (i, (proof, inputs)) in batch.into_iter().zip(pub_inputs).enumerate() {
+        verify::<AIR, HashFn>(proof, inputs)
+            .map_err(|err| VerifierError::InvalidBatchProof(i, Box::new(err)))?;
     }
+    Ok(())
+}
 
-    // 4 ----- FRI commitments --------------------------------------------------------------------
-    // draw coefficients for computing DEEP composition polynomial from the public coin; in the
-    // interactive version of the protocol, the verifier sends these coefficients to the prover
-    // and the prover uses them to compute the DEEP composition polynomial. the prover, then
-    // applies FRI protocol to the evaluations of the DEEP composition polynomial.
-    let deep_coefficients = air
-        .get_deep_composition_coefficients::<E, H>(&mut public_coin)
-        .map_err(|_| VerifierError::RandomCoinError)?;
-
-    // instantiates a FRI verifier with the FRI layer commitments read from the channel. From the
-    // verifier's perspective, this is equivalent to executing the commit phase of the FRI protocol.
-    // The verifier uses these commitments to update the public coin and draw random points alpha
-    // from them; in the interactive version of the protocol, the verifier sends these alphas to
-    // the prover, and the prover uses them to compute and commit to the subsequent FRI layers.
-    let fri_verifier = FriVerifier::new(
-        &mut channel,
-        &mut public_coin,
-        air.options().to_fri_options(),
-        air.trace_poly_degree(),
-    )
-    .map_err(VerifierError::FriVerificationFailed)?;
-    // TODO: make sure air.lde_domain_size() == fri_verifier.domain_size()
-
-    // 5 ----- trace and constraint queries -------------------------------------------------------
-    // read proof-of-work nonce sent by the prover and update the public coin with it
-    let pow_nonce = channel.read_pow_nonce();
-    public_coin.reseed_with_int(pow_nonce);
-
-    // make sure the proof-of-work specified by the grinding factor is satisfied
-    if public_coin.leading_zeros() < air.options().grinding_factor() {
-        return Err(VerifierError::QuerySeedProofOfWorkVerificationFailed);
+/// Verifies that the specified chain of proofs attests to consecutive executions of a step
+/// function which transform `input_state` into `output_state`.
+///
+/// The chain is accepted if the input state of the first proof is `input_state`, the output state
+/// of the last proof is `output_state`, the output state of every proof is the input state of the
+/// next proof, and every proof in the chain is valid against its public inputs. Links between
+/// proofs are checked before any of the proofs are verified; proofs are then verified one by one
+/// as in [verify_batch()].
+///
+/// # Errors
+/// Returns an error if:
+/// - The chain is empty.
+/// - The input state or the output state of the chain is different from the specified state.
+/// - The input state of any proof is different from the output state of the previous proof; the
+///   error contains the index of the first such proof.
+/// - Any of the proofs in the chain does not attest to a correct execution of the computation
+///   against its public inputs; the error contains the index of the first such proof.
+pub fn verify_chain<AIR, HashFn>(
+    chain: ProofChain<AIR::PublicInputs>,
+    input_state: &<AIR::PublicInputs as StepPublicInputs>::State,
+    output_state: &<AIR::PublicInputs as StepPublicInputs>::State,
+) -> Result<(), VerifierError>
+where
+    AIR: Air,
+    AIR::PublicInputs: StepPublicInputs,
+    HashFn: ElementHasher<BaseField = AIR::BaseField>,
+{
+    match (chain.input_state(), chain.output_state()) {
+        (None, _) | (_, None) => return Err(VerifierError::EmptyProofChain),
+        (Some(state), _) if state != *input_state => {
+            return Err(VerifierError::InconsistentChainInputState)
+        }
+        (_, Some(state)) if state != *output_state => {
+            return Err(VerifierError::InconsistentChainOutputState)
+        }
+        _ => (),
+    }
+    if let Some(index) = chain.find_broken_link() {
+        return Err(VerifierError::BrokenChainLink(index));
     }
 
-    // draw pseudo-random query positions for the LDE domain from the public coin; in the
-    // interactive version of the protocol, the verifier sends these query positions to the prover,
-    // and the prover responds with decommitments against these positions for trace and constraint
-    // composition polynomial evaluations.
-    let query_positions = public_coin
-        .draw_integers(air.options().num_queries(), air.lde_domain_size())
-        .map_err(|_| VerifierError::RandomCoinError)?;
-
-    // read evaluations of trace and constraint composition polynomials at the queried positions;
-    // this also checks that the read values are valid against trace and constraint commitments
-    let (queried_main_trace_states, queried_aux_trace_states) =
-        channel.read_queried_trace_states(&query_positions)?;
-    let queried_constraint_evaluations = channel.read_constraint_evaluations(&query_positions)?;
-
-    // 6 ----- DEEP composition -------------------------------------------------------------------
-    // compute evaluations of the DEEP composition polynomial at the queried positions
-    let composer = DeepComposer::new(&air, &query_positions, z, deep_coefficients);
-    let t_composition = composer.compose_trace_columns(
-        queried_main_trace_states,
-        queried_aux_trace_states,
-        ood_main_trace_frame,
-        ood_aux_trace_frame,
-    );
-    let c_composition = composer
-        .compose_constraint_evaluations(queried_constraint_evaluations, ood_constraint_evaluations);
-    let deep_evaluations = composer.combine_compositions(t_composition, c_composition);
-
-    // 7 ----- Verify low-degree proof -------------------------------------------------------------
-    // make sure that evaluations of the DEEP composition polynomial we computed in the previous
-    // step are in fact evaluations of a polynomial of degree equal to trace polynomial degree
-    fri_verifier
-        .verify(&mut channel, &deep_evaluations, &query_positions)
-        .map_err(VerifierError::FriVerificationFailed)
+    let (proofs, pub_inputs) = chain.into_parts();
+    verify_batch::<AIR, HashFn>(proofs, pub_inputs)
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Checks that the trace and proof options implied by the specified AIR are the same as the ones
+/// recorded in the proof `context`.
+///
+/// An AIR is instantiated from the parameters recorded in the proof context; however, the AIR may
+/// derive its parameters differently (e.g., from public inputs), and thus, describe a different
+/// computation than the one for which the proof was generated.
+fn check_context<A: Air>(air: &A, context: &Context) -> Result<(), ContextMismatch> {
+    let air_trace = air.trace_info();
+    let proof_trace = context.get_trace_info();
+    if air_trace.layout().main_trace_width() != proof_trace.layout().main_trace_width() {
+        return Err(ContextMismatch::MainTraceWidth(
+            air_trace.layout().main_trace_width(),
+            proof_trace.layout().main_trace_width(),
+        ));
+    }
+    if air_trace.layout() != proof_trace.layout() {
+        return Err(ContextMismatch::AuxTraceLayout);
+    }
+    if air_trace.length() != proof_trace.length() {
+        return Err(ContextMismatch::TraceLength(
+            air_trace.length(),
+            proof_trace.length(),
+        ));
+    }
+    if air_trace.meta() != proof_trace.meta() {
+        return Err(ContextMismatch::TraceMeta);
+    }
+    if air.options() != context.options() {
+        return Err(ContextMismatch::Options);
+    }
+    Ok(())
 }
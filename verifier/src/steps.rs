@@ -0,0 +1,509 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{
+    channel::VerifierChannel, composer::DeepComposer, evaluator::evaluate_constraints,
+    prepare_verification, VerifierError,
+};
+use air::{
+    proof::{StarkProof, Table},
+    Air, AuxTraceRandElements, ConstraintCompositionCoefficients, DeepCompositionCoefficients,
+    EvaluationFrame,
+};
+use crypto::{ElementHasher, RandomCoin};
+use fri::FriVerifier;
+use math::FieldElement;
+use utils::{collections::Vec, enter_span};
+
+// TYPES
+// ================================================================================================
+
+/// FRI verifier which reads FRI proof from the channel of the STARK verifier.
+type ChannelFriVerifier<B, E, H> = FriVerifier<B, E, VerifierChannel<E, H>, H>;
+
+// STEP-BY-STEP VERIFIER
+// ================================================================================================
+/// A STARK verifier which verifies a proof step by step, and exposes values computed at each step.
+///
+/// The [verify()](crate::verify) function performs all steps of proof verification at once. This
+/// struct splits the same procedure into the following explicit steps, each of which returns the
+/// intermediate values computed by the verifier during that step:
+///
+/// 1. [check_ood()](Verifier::check_ood) - draws the out-of-domain point `z` and makes sure that
+///    constraints evaluated over the out-of-domain trace frame are consistent with out-of-domain
+///    evaluations of the constraint composition polynomial.
+/// 2. [read_queries()](Verifier::read_queries) - reads FRI layer commitments, checks the
+///    proof-of-work, draws query positions, and reads trace states and constraint evaluations at
+///    these positions (making sure they are valid against trace and constraint commitments).
+/// 3. [compose()](Verifier::compose) - computes evaluations of the DEEP composition polynomial at
+///    the queried positions.
+/// 4. [check_fri()](Verifier::check_fri) - makes sure that the DEEP composition evaluations are
+///    evaluations of a low-degree polynomial.
+///
+/// The steps must be executed in the order listed above, and a proof is valid only if all of
+/// the steps succeed. Executing all steps performs exactly the same checks as
+/// [verify()](crate::verify) does. Thus, values returned by the steps can be compared against
+/// values computed by other implementations of the protocol stage by stage.
+///
+/// Unlike [verify()](crate::verify), which selects the extension field based on proof options,
+/// this verifier must be instantiated with the extension field `E` specified by the proof (e.g.,
+/// `QuadExtension<A::BaseField>` for proofs generated using quadratic field extension).
+pub struct Verifier<A, E, H>
+where
+    A: Air,
+    E: FieldElement<BaseField = A::BaseField>,
+    H: ElementHasher<BaseField = A::BaseField>,
+{
+    air: A,
+    channel: VerifierChannel<E, H>,
+    public_coin: RandomCoin<A::BaseField, H>,
+    ood_values: Option<OodValues<E>>,
+    deep_coefficients: Option<DeepCompositionCoefficients<E>>,
+    fri_verifier: Option<ChannelFriVerifier<A::BaseField, E, H>>,
+    query_values: Option<QueryValues<E>>,
+    deep_evaluations: Option<Vec<E>>,
+}
+
+impl<A, E, H> Verifier<A, E, H>
+where
+    A: Air,
+    E: FieldElement<BaseField = A::BaseField>,
+    H: ElementHasher<BaseField = A::BaseField>,
+{
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new verifier for the specified proof and public inputs.
+    ///
+    /// This performs all checks which [verify()](crate::verify) performs before the protocol is
+    /// executed (e.g., that the proof was generated for the specified public inputs and with the
+    /// hash function `H`).
+    ///
+    /// # Errors
+    /// Returns an error if the proof cannot be verified against the specified public inputs by
+    /// this verifier; this includes the case when the proof was generated in an extension field
+    /// different from `E`.
+    pub fn new(proof: StarkProof, pub_inputs: A::PublicInputs) -> Result<Self, VerifierError> {
+        let (air, public_coin_seed) = prepare_verification::<A, H>(&proof, pub_inputs)?;
+        let public_coin = RandomCoin::with_mode(&public_coin_seed, air.options().transcript_mode());
+        let channel = VerifierChannel::new(&air, proof)?;
+        Ok(Self::from_parts(air, channel, public_coin))
+    }
+
+    /// Returns a new verifier instantiated with the specified AIR, channel, and public coin.
+    pub(crate) fn from_parts(
+        air: A,
+        channel: VerifierChannel<E, H>,
+        public_coin: RandomCoin<A::BaseField, H>,
+    ) -> Self {
+        Self {
+            air,
+            channel,
+            public_coin,
+            ood_values: None,
+            deep_coefficients: None,
+            fri_verifier: None,
+            query_values: None,
+            deep_evaluations: None,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the AIR of the computation described by the proof.
+    pub fn air(&self) -> &A {
+        &self.air
+    }
+
+    // VERIFICATION STEPS
+    // --------------------------------------------------------------------------------------------
+
+    /// Draws the out-of-domain point and checks consistency of the out-of-domain frame sent by
+    /// the prover with out-of-domain evaluations of the constraint composition polynomial.
+    ///
+    /// # Errors
+    /// Returns an error if random values could not be drawn from the public coin, or if the
+    /// out-of-domain values sent by the prover are not consistent.
+    ///
+    /// # Panics
+    /// Panics if this step has already been executed.
+    pub fn check_ood(&mut self) -> Result<&OodValues<E>, VerifierError> {
+        assert!(
+            self.ood_values.is_none(),
+            "out-of-domain consistency has already been checked"
+        );
+        let air = &self.air;
+        let public_coin = &mut self.public_coin;
+
+        // 1 ----- trace commitment ---------------------------------------------------------------
+        // Read the commitments to evaluations of the trace polynomials over the LDE domain sent
+        // by the prover. The commitments are used to update the public coin, and draw sets of
+        // random elements from the coin (in the interactive version of the protocol the verifier
+        // sends these random elements to the prover after each commitment is made). When there
+        // are multiple trace commitments (i.e., the trace consists of more than one segment),
+        // each previous commitment is used to draw random elements needed to construct the next
+        // trace segment. The last trace commitment is used to draw a set of random coefficients
+        // which the prover uses to compute constraint composition polynomial.
+        let span = enter_span!("read_trace_commitments");
+        let trace_commitments = self.channel.read_trace_commitments();
+
+        // reseed the coin with the commitment to the main trace segment
+        public_coin.reseed(trace_commitments[0]);
+
+        // process auxiliary trace segments (if any), to build a set of random elements for each
+        // segment
+        let mut aux_rand_elements = AuxTraceRandElements::<E>::new();
+        for (i, commitment) in trace_commitments.iter().skip(1).enumerate() {
+            let rand_elements = air
+                .get_aux_trace_segment_random_elements(i, public_coin)
+                .map_err(|_| VerifierError::RandomCoinError)?;
+            aux_rand_elements.add_segment_elements(rand_elements);
+            public_coin.reseed(*commitment);
+        }
+
+        // build random coefficients for the composition polynomial
+        let constraint_coeffs = air
+            .get_constraint_composition_coefficients(public_coin)
+            .map_err(|_| VerifierError::RandomCoinError)?;
+        span.exit();
+
+        // 2 ----- constraint commitment ----------------------------------------------------------
+        // read the commitment to evaluations of the constraint composition polynomial over the
+        // LDE domain sent by the prover, use it to update the public coin, and draw an
+        // out-of-domain point z from the coin; in the interactive version of the protocol, the
+        // verifier sends this point z to the prover, and the prover evaluates trace and
+        // constraint composition polynomials at z, and sends the results back to the verifier.
+        let constraint_commitment = self.channel.read_constraint_commitment();
+        public_coin.reseed(constraint_commitment);
+        public_coin.reseed_with_label(b"ood-point");
+        let z = public_coin
+            .draw::<E>()
+            .map_err(|_| VerifierError::RandomCoinError)?;
+
+        // 3 ----- OOD consistency check ----------------------------------------------------------
+        // make sure that evaluations obtained by evaluating constraints over the out-of-domain
+        // frame are consistent with the evaluations of composition polynomial columns sent by the
+        // prover
+
+        // read the out-of-domain trace frames (the main trace frame and auxiliary trace frame, if
+        // provided) sent by the prover and evaluate constraints over them; also, reseed the
+        // public coin with the OOD frames received from the prover.
+        let _span = enter_span!("check_ood_consistency");
+        let (main_frame, aux_frame) = self.channel.read_ood_trace_frame();
+        let composition_from_trace = evaluate_constraints(
+            air,
+            constraint_coeffs.clone(),
+            &main_frame,
+            &aux_frame,
+            aux_rand_elements.clone(),
+            z,
+        );
+
+        if let Some(ref aux_frame) = aux_frame {
+            // when the trace contains auxiliary segments, append auxiliary trace elements at the
+            // end of main trace elements for both current and next rows in the frame. this is
+            // needed to be consistent with how the prover writes OOD frame into the channel.
+
+            let mut current = main_frame.current().to_vec();
+            current.extend_from_slice(aux_frame.current());
+            public_coin.reseed(H::hash_elements(&current));
+
+            let mut next = main_frame.next().to_vec();
+            next.extend_from_slice(aux_frame.next());
+            public_coin.reseed(H::hash_elements(&next));
+        } else {
+            public_coin.reseed(H::hash_elements(main_frame.current()));
+            public_coin.reseed(H::hash_elements(main_frame.next()));
+        }
+
+        // read evaluations of composition polynomial columns sent by the prover, and reduce them
+        // into a single value by computing sum(z^i * value_i), where value_i is the evaluation of
+        // the ith column polynomial at z^m, where m is the total number of column polynomials;
+        // also, reseed the public coin with the OOD constraint evaluations received from the
+        // prover.
+        let constraint_evaluations = self.channel.read_ood_constraint_evaluations();
+        let composition_from_columns = constraint_evaluations
+            .iter()
+            .enumerate()
+            .fold(E::ZERO, |result, (i, &value)| {
+                result + z.exp_vartime((i as u32).into()) * value
+            });
+        public_coin.reseed(H::hash_elements(&constraint_evaluations));
+
+        // finally, make sure the values are the same
+        if composition_from_trace != composition_from_columns {
+            return Err(VerifierError::InconsistentOodConstraintEvaluations);
+        }
+
+        Ok(self.ood_values.insert(OodValues {
+            aux_rand_elements,
+            constraint_coeffs,
+            z,
+            main_frame,
+            aux_frame,
+            constraint_evaluations,
+            composition_from_trace,
+            composition_from_columns,
+        }))
+    }
+
+    /// Reads FRI layer commitments, draws query positions, and reads trace states and constraint
+    /// evaluations at the queried positions.
+    ///
+    /// # Errors
+    /// Returns an error if random values could not be drawn from the public coin, the
+    /// proof-of-work is not satisfied, FRI layer commitments are invalid, or the queried values
+    /// are not valid against the trace and constraint commitments.
+    ///
+    /// # Panics
+    /// Panics if [check_ood()](Verifier::check_ood) has not been successfully executed yet, or
+    /// if this step has already been executed.
+    pub fn read_queries(&mut self) -> Result<&QueryValues<E>, VerifierError> {
+        assert!(
+            self.ood_values.is_some(),
+            "out-of-domain consistency must be checked before reading queries"
+        );
+        assert!(
+            self.query_values.is_none(),
+            "queries have already been read"
+        );
+        let air = &self.air;
+        let public_coin = &mut self.public_coin;
+
+        // 4 ----- FRI commitments ----------------------------------------------------------------
+        // draw coefficients for computing DEEP composition polynomial from the public coin; in
+        // the interactive version of the protocol, the verifier sends these coefficients to the
+        // prover and the prover uses them to compute the DEEP composition polynomial. the prover,
+        // then applies FRI protocol to the evaluations of the DEEP composition polynomial.
+        let span = enter_span!("read_fri_commitments");
+        let deep_coefficients = air
+            .get_deep_composition_coefficients::<E, H>(public_coin)
+            .map_err(|_| VerifierError::RandomCoinError)?;
+
+        // instantiates a FRI verifier with the FRI layer commitments read from the channel. From
+        // the verifier's perspective, this is equivalent to executing the commit phase of the FRI
+        // protocol. The verifier uses these commitments to update the public coin and draw random
+        // points alpha from them; in the interactive version of the protocol, the verifier sends
+        // these alphas to the prover, and the prover uses them to compute and commit to the
+        // subsequent FRI layers.
+        let fri_verifier = FriVerifier::new(
+            &mut self.channel,
+            public_coin,
+            air.options().to_fri_options(),
+            air.trace_poly_degree(),
+        )
+        .map_err(VerifierError::FriVerificationFailed)?;
+        span.exit();
+        // TODO: make sure air.lde_domain_size() == fri_verifier.domain_size()
+
+        // 5 ----- trace and constraint queries ---------------------------------------------------
+        // read proof-of-work nonce sent by the prover and update the public coin with it
+        let _span = enter_span!("read_queries", num_queries = air.options().num_queries());
+        let pow_nonce = self.channel.read_pow_nonce();
+        public_coin.reseed_with_int(pow_nonce);
+
+        // make sure the proof-of-work specified by the grinding factor is satisfied
+        if public_coin.leading_zeros() < air.options().grinding_factor() {
+            return Err(VerifierError::QuerySeedProofOfWorkVerificationFailed);
+        }
+
+        // draw pseudo-random query positions for the LDE domain from the public coin; in the
+        // interactive version of the protocol, the verifier sends these query positions to the
+        // prover, and the prover responds with decommitments against these positions for trace
+        // and constraint composition polynomial evaluations.
+        public_coin.reseed_with_label(b"query-seed");
+        let positions = public_coin
+            .draw_integers(air.options().num_queries(), air.lde_domain_size())
+            .map_err(|_| VerifierError::RandomCoinError)?;
+
+        // read evaluations of trace and constraint composition polynomials at the queried
+        // positions; this also checks that the read values are valid against trace and
+        // constraint commitments
+        let (main_trace_states, aux_trace_states) =
+            self.channel.read_queried_trace_states(&positions)?;
+        let constraint_evaluations = self.channel.read_constraint_evaluations(&positions)?;
+
+        let fri_alphas = fri_verifier.layer_alphas().to_vec();
+        self.deep_coefficients = Some(deep_coefficients);
+        self.fri_verifier = Some(fri_verifier);
+        Ok(self.query_values.insert(QueryValues {
+            fri_alphas,
+            pow_nonce,
+            positions,
+            main_trace_states,
+            aux_trace_states,
+            constraint_evaluations,
+        }))
+    }
+
+    /// Computes evaluations of the DEEP composition polynomial at the queried positions.
+    ///
+    /// # Panics
+    /// Panics if [read_queries()](Verifier::read_queries) has not been successfully executed yet,
+    /// or if this step has already been executed.
+    pub fn compose(&mut self) -> DeepValues<E> {
+        let ood_values = self
+            .ood_values
+            .as_ref()
+            .expect("out-of-domain consistency must be checked before composing");
+        let query_values = self
+            .query_values
+            .as_ref()
+            .expect("queries must be read before composing");
+        assert!(
+            self.deep_evaluations.is_none(),
+            "DEEP composition has already been computed"
+        );
+        let deep_coefficients = self
+            .deep_coefficients
+            .clone()
+            .expect("DEEP composition coefficients must be drawn before composing");
+
+        // 6 ----- DEEP composition ---------------------------------------------------------------
+        // compute evaluations of the DEEP composition polynomial at the queried positions
+        let _span = enter_span!("compose_deep_evaluations");
+        let composer = DeepComposer::new(
+            &self.air,
+            &query_values.positions,
+            ood_values.z,
+            deep_coefficients.clone(),
+        );
+        let trace_composition = composer.compose_trace_columns(
+            query_values.main_trace_states.clone(),
+            query_values.aux_trace_states.clone(),
+            ood_values.main_frame.clone(),
+            ood_values.aux_frame.clone(),
+        );
+        let constraint_composition = composer.compose_constraint_evaluations(
+            query_values.constraint_evaluations.clone(),
+            ood_values.constraint_evaluations.clone(),
+        );
+        let evaluations = composer
+            .combine_compositions(trace_composition.clone(), constraint_composition.clone());
+        self.deep_evaluations = Some(evaluations.clone());
+
+        DeepValues {
+            coefficients: deep_coefficients,
+            trace_composition,
+            constraint_composition,
+            evaluations,
+        }
+    }
+
+    /// Makes sure that evaluations of the DEEP composition polynomial computed in the previous
+    /// step are evaluations of a polynomial of degree equal to trace polynomial degree.
+    ///
+    /// # Errors
+    /// Returns an error if verification of the FRI proof fails.
+    ///
+    /// # Panics
+    /// Panics if [compose()](Verifier::compose) has not been executed yet.
+    pub fn check_fri(&mut self) -> Result<(), VerifierError> {
+        let deep_evaluations = self
+            .deep_evaluations
+            .as_ref()
+            .expect("DEEP composition must be computed before checking FRI proof");
+        let positions = &self
+            .query_values
+            .as_ref()
+            .expect("queries must be read before checking FRI proof")
+            .positions;
+        let fri_verifier = self
+            .fri_verifier
+            .as_ref()
+            .expect("FRI commitments must be read before checking FRI proof");
+
+        // 7 ----- Verify low-degree proof --------------------------------------------------------
+        // make sure that evaluations of the DEEP composition polynomial we computed in the
+        // previous step are in fact evaluations of a polynomial of degree equal to trace
+        // polynomial degree
+        let _span = enter_span!("verify_fri_proof");
+        fri_verifier
+            .verify(&mut self.channel, deep_evaluations, positions)
+            .map_err(VerifierError::FriVerificationFailed)
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Executes all verification steps in order.
+    pub(crate) fn verify_all(&mut self) -> Result<(), VerifierError> {
+        self.check_ood()?;
+        self.read_queries()?;
+        self.compose();
+        self.check_fri()
+    }
+
+    /// Consumes this verifier and returns its public coin.
+    pub(crate) fn into_public_coin(self) -> RandomCoin<A::BaseField, H> {
+        self.public_coin
+    }
+}
+
+// INTERMEDIATE VALUES
+// ================================================================================================
+
+/// Values computed by [Verifier::check_ood()].
+#[derive(Debug, Clone)]
+pub struct OodValues<E: FieldElement> {
+    /// Random elements drawn from the public coin for building auxiliary trace segments.
+    pub aux_rand_elements: AuxTraceRandElements<E>,
+    /// Coefficients drawn from the public coin for combining constraints into the constraint
+    /// composition polynomial.
+    pub constraint_coeffs: ConstraintCompositionCoefficients<E>,
+    /// Out-of-domain point `z`.
+    pub z: E,
+    /// Evaluations of main trace polynomials at `z` (current row) and `z * g` (next row) sent by
+    /// the prover.
+    pub main_frame: EvaluationFrame<E>,
+    /// Evaluations of auxiliary trace polynomials at `z` and `z * g` sent by the prover; this is
+    /// None for single-segment traces.
+    pub aux_frame: Option<EvaluationFrame<E>>,
+    /// Evaluations of constraint composition column polynomials at `z^m` sent by the prover,
+    /// where `m` is the number of the columns.
+    pub constraint_evaluations: Vec<E>,
+    /// Value of the constraint composition polynomial at `z` obtained by evaluating constraints
+    /// over the out-of-domain trace frames.
+    pub composition_from_trace: E,
+    /// Value of the constraint composition polynomial at `z` obtained by combining evaluations
+    /// of the constraint composition columns.
+    pub composition_from_columns: E,
+}
+
+/// Values computed by [Verifier::read_queries()].
+#[derive(Debug, Clone)]
+pub struct QueryValues<E: FieldElement> {
+    /// Random values drawn from the public coin for folding FRI layers; one value per FRI layer.
+    pub fri_alphas: Vec<E>,
+    /// Proof-of-work nonce sent by the prover.
+    pub pow_nonce: u64,
+    /// Queried positions in the LDE domain.
+    pub positions: Vec<usize>,
+    /// States of the main trace segment at the queried positions; one row per position.
+    pub main_trace_states: Table<E::BaseField>,
+    /// States of all auxiliary trace segments at the queried positions; this is None for
+    /// single-segment traces.
+    pub aux_trace_states: Option<Table<E>>,
+    /// Evaluations of constraint composition columns at the queried positions.
+    pub constraint_evaluations: Table<E>,
+}
+
+/// Values computed by [Verifier::compose()].
+#[derive(Debug, Clone)]
+pub struct DeepValues<E: FieldElement> {
+    /// Coefficients drawn from the public coin for computing the DEEP composition polynomial;
+    /// these are drawn before FRI layer commitments are read.
+    pub coefficients: DeepCompositionCoefficients<E>,
+    /// Random linear combinations of trace column quotients at the queried positions.
+    pub trace_composition: Vec<E>,
+    /// Random linear combinations of constraint composition column quotients at the queried
+    /// positions.
+    pub constraint_composition: Vec<E>,
+    /// Evaluations of the DEEP composition polynomial at the queried positions; these are sums of
+    /// trace and constraint compositions with the degree raised by one to match the degree of
+    /// trace polynomials.
+    pub evaluations: Vec<E>,
+}
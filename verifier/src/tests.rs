@@ -0,0 +1,693 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    replay_transcript, verify, verify_batch, verify_with_policy, Air, AirContext, Assertion,
+    ByteReader, ByteWriter, Deserializable, EvaluationFrame, FieldExtension, PolicyError,
+    ProofBatch, ProofOptions, Serializable, SliceReader, StarkProof, TraceInfo,
+    TransitionConstraintDegree, Verifier, VerifierError, VerifierPolicy,
+};
+use air::proof::LEGACY_PROOF_FORMAT_VERSION;
+use core::marker::PhantomData;
+use crypto::{
+    hashers::{Blake3_256, Rp64_256, Sha3_256},
+    ElementHasher, HashFunction, Transcript, TranscriptMode, TranscriptOp,
+};
+use math::{
+    fields::{f128, f64::BaseElement, QuadExtension},
+    FieldElement, StarkField,
+};
+use prover::{Prover, Trace, TraceTable};
+use utils::collections::Vec;
+
+// PROOF DESERIALIZATION
+// ================================================================================================
+
+#[test]
+fn malformed_proofs() {
+    // a simple xorshift generator to deterministically corrupt proof bytes
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state as usize
+    };
+
+    check_malformed_proofs::<Blake3_256<BaseElement>>(base_options(), &mut next);
+    check_malformed_proofs::<Sha3_256<BaseElement>>(quadratic_options(), &mut next);
+    check_malformed_proofs::<Rp64_256>(base_options().with_merkle_arity(4), &mut next);
+    check_malformed_proofs::<Blake3_256<BaseElement>>(
+        base_options().with_transcript_mode(TranscriptMode::Blake3Xof),
+        &mut next,
+    );
+
+    // trace length is stored as a power of two right after the version byte and the trace
+    // layout; a length which does not fit into usize should be rejected
+    let (proof, _) = build_proof::<Blake3_256<BaseElement>>(base_options(), 64);
+    let mut bytes = proof.to_bytes();
+    bytes[4] = 0xff;
+    assert!(StarkProof::from_bytes(&bytes).is_err());
+}
+
+fn check_malformed_proofs<H: ElementHasher<BaseField = BaseElement>>(
+    options: ProofOptions,
+    next: &mut impl FnMut() -> usize,
+) {
+    let (proof, result) = build_proof::<H>(options, 64);
+    let bytes = proof.to_bytes();
+
+    // truncated proofs should be rejected
+    for len in 0..bytes.len() {
+        assert!(StarkProof::from_bytes(&bytes[..len]).is_err());
+    }
+
+    // corrupted proofs should either be rejected or fail verification, but never panic
+    for _ in 0..200 {
+        let mut corrupted = bytes.clone();
+        for _ in 0..1 + next() % 3 {
+            let position = next() % corrupted.len();
+            corrupted[position] = if next() % 2 == 0 { 0xff } else { next() as u8 };
+        }
+        if let Ok(proof) = StarkProof::from_bytes(&corrupted) {
+            let _ = verify::<FibAir, H>(proof, result);
+        }
+    }
+}
+
+#[test]
+fn non_canonical_commitments() {
+    // commitments have a unique encoding; here, a limb of the trace commitment of a proof
+    // generated with Rp64_256 is replaced with the field modulus (a non-canonical encoding of
+    // zero), and the resulting proof is rejected
+    let (proof, result) = build_proof::<Rp64_256>(base_options().with_merkle_arity(4), 64);
+    let mut bytes = proof.to_bytes();
+    let commitments = proof.commitments.as_bytes();
+    let position = bytes
+        .windows(commitments.len())
+        .position(|window| window == commitments)
+        .unwrap();
+    bytes[position..position + 8].copy_from_slice(&BaseElement::MODULUS.to_le_bytes());
+    let corrupted = StarkProof::from_bytes(&bytes).unwrap();
+    assert_ne!(
+        proof.digest::<Blake3_256<BaseElement>>(),
+        corrupted.digest::<Blake3_256<BaseElement>>()
+    );
+    assert!(matches!(
+        verify::<FibAir, Rp64_256>(corrupted, result),
+        Err(VerifierError::ProofDeserializationError(_))
+    ));
+}
+
+// CONTEXT CHECKS
+// ================================================================================================
+
+#[test]
+fn inconsistent_base_field() {
+    type H = Blake3_256<BaseElement>;
+    let (proof, result) = build_proof::<H>(base_options(), 64);
+    let bytes = proof.to_bytes();
+
+    // the field modulus is stored in the context right after the version byte, the trace layout,
+    // the trace length, empty trace metadata, and the modulus length; changing it makes the proof
+    // claim to be generated in a different field
+    let mut corrupted = bytes.clone();
+    assert_eq!(8, corrupted[6]);
+    corrupted[7] ^= 0x02;
+    let proof = StarkProof::from_bytes(&corrupted).unwrap();
+    assert_eq!(
+        Err(VerifierError::InconsistentBaseField),
+        verify::<FibAir, H>(proof, result)
+    );
+
+    let proof = StarkProof::from_bytes(&bytes).unwrap();
+    assert_eq!(Ok(()), verify::<FibAir, H>(proof, result));
+}
+
+#[test]
+fn inconsistent_hash_function() {
+    type H = Blake3_256<BaseElement>;
+    let (proof, result) = build_proof::<H>(base_options(), 64);
+    assert_eq!(HashFunction::Blake3_256, proof.context.hash_function());
+
+    // a proof generated with one hash function should be rejected by a verifier instantiated
+    // with a different hash function before anything else is checked
+    assert_eq!(
+        Err(VerifierError::InconsistentHashFunction(
+            HashFunction::Blake3_256,
+            HashFunction::Sha3_256
+        )),
+        verify::<FibAir, Sha3_256<BaseElement>>(proof.clone(), result)
+    );
+
+    // changing the identifier recorded in the proof should make the proof invalid
+    let mut bytes = proof.to_bytes();
+    let digest_len = proof.context.pub_inputs_digest().len();
+    let id_position = 1 + proof.context.to_bytes().len() - digest_len - 2;
+    assert_eq!(HashFunction::Blake3_256.id(), bytes[id_position]);
+    bytes[id_position] = HashFunction::Sha3_256.id();
+    let proof = StarkProof::from_bytes(&bytes).unwrap();
+    assert_eq!(HashFunction::Sha3_256, proof.context.hash_function());
+    assert!(verify::<FibAir, H>(proof, result).is_err());
+}
+
+#[test]
+fn verifier_policies() {
+    type H = Blake3_256<BaseElement>;
+    let (proof, result) = build_proof::<H>(base_options(), 64);
+    let security = proof.security_level::<H>(true);
+    let verify =
+        |policy: &VerifierPolicy| verify_with_policy::<FibAir, H>(proof.clone(), result, policy);
+
+    // a proof satisfying the policy should be verified
+    let policy = VerifierPolicy::new()
+        .with_min_conjectured_security(security)
+        .with_hash_functions(&[HashFunction::Blake3_256, HashFunction::Rp64_256])
+        .with_field::<BaseElement>()
+        .with_field_extensions(&[proof.options().field_extension()]);
+    assert_eq!(Ok(()), verify(&policy));
+    assert_eq!(Ok(()), verify(&VerifierPolicy::new()));
+
+    // a proof violating any of the requirements should be rejected
+    let violations = [
+        (
+            policy.clone().with_min_conjectured_security(security + 1),
+            PolicyError::InsufficientConjecturedSecurity(security + 1, security),
+        ),
+        (
+            policy
+                .clone()
+                .with_hash_functions(&[HashFunction::Sha3_256]),
+            PolicyError::HashFunctionNotAllowed(HashFunction::Blake3_256),
+        ),
+        (
+            VerifierPolicy::new().with_field::<f128::BaseElement>(),
+            PolicyError::FieldNotAllowed,
+        ),
+        (
+            policy
+                .clone()
+                .with_field_extensions(&[FieldExtension::Cubic]),
+            PolicyError::FieldExtensionNotAllowed(proof.options().field_extension()),
+        ),
+        (
+            policy.clone().with_min_proven_security(security),
+            PolicyError::InsufficientProvenSecurity(security, proof.security_level::<H>(false)),
+        ),
+    ];
+    for (policy, expected) in violations {
+        assert_eq!(
+            Err(VerifierError::PolicyViolation(expected)),
+            verify(&policy)
+        );
+    }
+}
+
+// BATCH VERIFICATION
+// ================================================================================================
+
+#[test]
+fn batch_verification() {
+    type H = Blake3_256<BaseElement>;
+    let (proof, result) = build_proof::<H>(base_options(), 64);
+
+    // all proofs in a batch should be verified against their own public inputs
+    let batch = ProofBatch::from(vec![proof.clone(), proof]);
+    assert_eq!(
+        Ok(()),
+        verify_batch::<FibAir, H>(batch.clone(), vec![result; 2])
+    );
+    assert_eq!(
+        Err(VerifierError::InconsistentBatchSize(2, 1)),
+        verify_batch::<FibAir, H>(batch.clone(), vec![result])
+    );
+    let err =
+        verify_batch::<FibAir, H>(batch, vec![result, result + BaseElement::ONE]).unwrap_err();
+    assert_eq!(
+        VerifierError::InvalidBatchProof(1, Box::new(VerifierError::InconsistentPublicInputs)),
+        err
+    );
+    assert_eq!(Some(1), err.batch_index());
+    assert!(err.is_invalid_proof());
+    assert!(!VerifierError::InconsistentBatchSize(2, 1).is_invalid_proof());
+}
+
+// PROOF MIGRATION
+// ================================================================================================
+
+#[test]
+fn legacy_proofs() {
+    type H = Blake3_256<BaseElement>;
+    let (proof, result) = build_proof::<H>(base_options(), 64);
+    let legacy_bytes = to_legacy_bytes(&proof);
+
+    // all components of a legacy proof should be migrated exactly; legacy proofs did not include
+    // hash function identifier and public inputs digest, and these are derived from the hash
+    // function and public inputs supplied for the migration
+    let migrated = StarkProof::migrate_legacy::<H, _>(&legacy_bytes, &result).unwrap();
+    assert_eq!(proof.context, migrated.context);
+    assert_eq!(proof.commitments, migrated.commitments);
+    assert_eq!(proof.trace_queries, migrated.trace_queries);
+    assert_eq!(proof.constraint_queries, migrated.constraint_queries);
+    assert_eq!(proof.ood_frame, migrated.ood_frame);
+    assert_eq!(proof.fri_proof, migrated.fri_proof);
+    assert_eq!(proof.pow_nonce, migrated.pow_nonce);
+
+    // the migrated proof should survive a round trip in the current format, and it should be
+    // verified against the same public inputs
+    assert_eq!(
+        migrated,
+        StarkProof::from_bytes(&migrated.to_bytes()).unwrap()
+    );
+    assert_eq!(Ok(()), verify::<FibAir, H>(migrated, result));
+
+    // a proof migrated with different public inputs or a different hash function is rejected
+    let other_inputs = result + BaseElement::ONE;
+    let migrated = StarkProof::migrate_legacy::<H, _>(&legacy_bytes, &other_inputs).unwrap();
+    assert_eq!(
+        Err(VerifierError::InconsistentPublicInputs),
+        verify::<FibAir, H>(migrated, result)
+    );
+    let migrated =
+        StarkProof::migrate_legacy::<Sha3_256<BaseElement>, _>(&legacy_bytes, &result).unwrap();
+    assert_eq!(HashFunction::Sha3_256, migrated.context.hash_function());
+    assert!(verify::<FibAir, H>(migrated, result).is_err());
+
+    // proofs in the current format should be read as is, and unknown versions should be rejected;
+    // legacy proofs can be migrated only via migrate_legacy()
+    let bytes = proof.to_bytes();
+    assert_eq!(proof, StarkProof::migrate(&bytes, bytes[0]).unwrap());
+    assert!(StarkProof::migrate(&bytes, 7).is_err());
+    assert!(StarkProof::migrate(&legacy_bytes, LEGACY_PROOF_FORMAT_VERSION).is_err());
+    assert!(
+        StarkProof::migrate_legacy::<H, _>(&legacy_bytes[..legacy_bytes.len() - 1], &result)
+            .is_err()
+    );
+}
+
+#[test]
+fn format_v1_proofs() {
+    let v1_bytes = (0..FORMAT_V1_PROOF.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&FORMAT_V1_PROOF[i..i + 2], 16).unwrap())
+        .collect::<Vec<_>>();
+    assert!(StarkProof::from_bytes(&v1_bytes).is_err());
+
+    // proof options of proofs in format version 1 did not include the number of trace rows per
+    // leaf; the migrated proof should commit to one trace row per leaf
+    let options = ProofOptions::new(4, 4, 0, FieldExtension::None, 4, 32);
+    let migrated = StarkProof::migrate(&v1_bytes, 1).unwrap();
+    assert_eq!(1, migrated.options().trace_rows_per_leaf());
+    assert_eq!(&options, migrated.options());
+
+    // the migrated proof should survive a round trip in the current format; its context should
+    // be the same as the context of a proof generated now, but the proof itself was generated by
+    // an earlier version of the protocol, and thus, it is not expected to be valid
+    let migrated_bytes = migrated.to_bytes();
+    assert_eq!(migrated, StarkProof::from_bytes(&migrated_bytes).unwrap());
+    let (proof, _) = build_proof::<Blake3_256<BaseElement>>(options, 16);
+    assert_eq!(proof.context, migrated.context);
+
+    // apart from the version byte, migration should only insert the number of trace rows per
+    // leaf after the other proof options
+    assert_eq!(v1_bytes.len() + 1, migrated_bytes.len());
+    let rows_per_leaf_pos = migrated_bytes
+        .iter()
+        .zip(v1_bytes.iter())
+        .skip(1)
+        .position(|(a, b)| a != b)
+        .unwrap()
+        + 1;
+    assert_eq!(1, migrated_bytes[rows_per_leaf_pos]);
+    assert_eq!(
+        v1_bytes[1..rows_per_leaf_pos],
+        migrated_bytes[1..rows_per_leaf_pos]
+    );
+    assert_eq!(
+        v1_bytes[rows_per_leaf_pos..],
+        migrated_bytes[rows_per_leaf_pos + 1..]
+    );
+
+    // format version 2 differed from format version 1 only in the encoding of counts which are
+    // encoded identically in this proof
+    let mut v2_bytes = v1_bytes.clone();
+    v2_bytes[0] = 2;
+    assert_eq!(migrated, StarkProof::migrate(&v2_bytes, 2).unwrap());
+    assert!(StarkProof::migrate(&v1_bytes, 2).is_err());
+    assert!(StarkProof::migrate(&v1_bytes[..v1_bytes.len() - 1], 1).is_err());
+}
+
+#[test]
+fn wide_counts() {
+    type H = Blake3_256<BaseElement>;
+
+    // proofs with more than 255 queries should be generated, serialized, and verified
+    let options = ProofOptions::new(300, 4, 0, FieldExtension::None, 4, 32);
+    let (proof, result) = build_proof::<H>(options, 256);
+    assert_eq!(300, proof.options().num_queries());
+    assert_eq!(proof, StarkProof::from_bytes(&proof.to_bytes()).unwrap());
+    assert!(verify::<FibAir, H>(proof, result).is_ok());
+
+    // proofs in format version 1 encoded the number of queries as a single byte; such proofs
+    // should be migrated exactly, unless they contain too many queries
+    let (proof, _) = build_proof::<H>(base_options(), 64);
+    let narrow_bytes = to_narrow_counts_bytes(&proof);
+    assert!(StarkProof::from_bytes(&narrow_bytes).is_err());
+    assert_eq!(proof, StarkProof::migrate(&narrow_bytes, 1).unwrap());
+
+    let options = ProofOptions::new(128, 8, 0, FieldExtension::None, 4, 256);
+    let (proof, _) = build_proof::<H>(options, 64);
+    let narrow_bytes = to_narrow_counts_bytes(&proof);
+    assert!(StarkProof::migrate(&narrow_bytes, 1).is_err());
+}
+
+// STEP-BY-STEP VERIFICATION
+// ================================================================================================
+
+#[test]
+fn transcript_replay() {
+    type H = Sha3_256<BaseElement>;
+    let prover = FibProver::<H>::new(quadratic_options());
+    let trace = prover.build_trace(64);
+    let result = prover.get_pub_inputs(&trace);
+    let (proof, transcript) = prover.prove_with_transcript(trace).unwrap();
+
+    // recording the transcript should not affect the proof
+    assert_eq!(prover.prove(prover.build_trace(64)).unwrap(), proof);
+    assert_eq!(TranscriptOp::Start, transcript.entries()[0].op);
+    let labels = transcript
+        .entries()
+        .iter()
+        .filter(|entry| entry.op == TranscriptOp::Label)
+        .map(|entry| entry.data.as_slice())
+        .collect::<Vec<_>>();
+    assert!(labels.contains(&b"ood-point".as_slice()));
+    assert_eq!(Some(&b"query-seed".as_slice()), labels.last());
+
+    // a valid proof produces the same transcript on the verifier side
+    let (replayed, verified) = replay_transcript::<FibAir, H>(proof.clone(), result);
+    assert_eq!(Ok(()), verified);
+    assert_eq!(None, transcript.diff(&replayed));
+    assert_eq!(transcript, replayed);
+    let bytes = transcript.to_bytes();
+    assert_eq!(
+        transcript,
+        Transcript::read_from(&mut SliceReader::new(&bytes)).unwrap()
+    );
+
+    // the transcripts of a proof with a tampered proof-of-work nonce diverge at the nonce
+    let mut tampered = proof.clone();
+    tampered.pow_nonce += 1;
+    let (replayed, verified) = replay_transcript::<FibAir, H>(tampered, result);
+    assert!(verified.is_err());
+    let mismatch = transcript.diff(&replayed).unwrap();
+    assert_eq!(TranscriptOp::ReseedWithInt, mismatch.expected.unwrap().op);
+    assert_eq!(TranscriptOp::ReseedWithInt, mismatch.actual.unwrap().op);
+    assert_eq!(
+        transcript.entries()[..mismatch.index],
+        replayed.entries()[..mismatch.index]
+    );
+
+    // the public coin is not instantiated for proofs generated for different public inputs
+    let (replayed, verified) = replay_transcript::<FibAir, H>(proof, result + BaseElement::ONE);
+    assert_eq!(Err(VerifierError::InconsistentPublicInputs), verified);
+    assert!(replayed.is_empty());
+}
+
+#[test]
+fn step_by_step_verification() {
+    type H = Sha3_256<BaseElement>;
+    type E = QuadExtension<BaseElement>;
+    let (proof, result) = build_proof::<H>(quadratic_options(), 64);
+
+    // each step returns values consistent with the proof
+    let mut verifier = Verifier::<FibAir, E, H>::new(proof.clone(), result).unwrap();
+    let ood_values = verifier.check_ood().unwrap().clone();
+    assert_eq!(
+        ood_values.composition_from_trace,
+        ood_values.composition_from_columns
+    );
+    assert_eq!(2, ood_values.main_frame.current().len());
+    assert!(ood_values.aux_frame.is_none());
+
+    let query_values = verifier.read_queries().unwrap().clone();
+    let num_queries = query_values.positions.len();
+    assert!(num_queries > 0 && num_queries <= 28);
+    assert!(query_values.positions.iter().all(|&p| p < 64 * 8));
+    assert_eq!(num_queries, query_values.main_trace_states.num_rows());
+    assert_eq!(num_queries, query_values.constraint_evaluations.num_rows());
+    assert_eq!(proof.fri_proof.num_layers(), query_values.fri_alphas.len());
+
+    let deep_values = verifier.compose();
+    assert_eq!(num_queries, deep_values.trace_composition.len());
+    assert_eq!(num_queries, deep_values.constraint_composition.len());
+    assert_eq!(num_queries, deep_values.evaluations.len());
+    assert_eq!(Ok(()), verifier.check_fri());
+
+    // the verifier must be instantiated in the extension field of the proof
+    assert_eq!(
+        Some(VerifierError::InconsistentFieldExtension(2)),
+        Verifier::<FibAir, BaseElement, H>::new(proof.clone(), result).err()
+    );
+
+    // a tampered proof-of-work nonce changes query positions, and thus, the proof fails at the
+    // query step even though the out-of-domain values are consistent
+    let mut tampered = proof;
+    tampered.pow_nonce += 1;
+    let mut verifier = Verifier::<FibAir, E, H>::new(tampered, result).unwrap();
+    assert_eq!(ood_values.z, verifier.check_ood().unwrap().z);
+    assert!(verifier.read_queries().is_err());
+}
+
+// FIBONACCI AIR
+// ================================================================================================
+
+/// AIR for a Fibonacci sequence which advances by two terms per trace row.
+struct FibAir {
+    context: AirContext<BaseElement>,
+    result: BaseElement,
+}
+
+impl Air for FibAir {
+    type BaseField = BaseElement;
+    type PublicInputs = BaseElement;
+
+    fn new(trace_info: TraceInfo, pub_inputs: BaseElement, options: ProofOptions) -> Self {
+        let degrees = vec![
+            TransitionConstraintDegree::new(1),
+            TransitionConstraintDegree::new(1),
+        ];
+        FibAir {
+            context: AirContext::new(trace_info, degrees, 3, options),
+            result: pub_inputs,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        result[0] = next[0] - (current[0] + current[1]);
+        result[1] = next[1] - (current[1] + next[0]);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        let last_step = self.trace_length() - 1;
+        vec![
+            Assertion::single(0, 0, BaseElement::ONE),
+            Assertion::single(1, 0, BaseElement::ONE),
+            Assertion::single(1, last_step, self.result),
+        ]
+    }
+}
+
+// FIBONACCI PROVER
+// ================================================================================================
+
+struct FibProver<H: ElementHasher> {
+    options: ProofOptions,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> FibProver<H> {
+    fn new(options: ProofOptions) -> Self {
+        Self {
+            options,
+            _hasher: PhantomData,
+        }
+    }
+
+    fn build_trace(&self, sequence_length: usize) -> TraceTable<BaseElement> {
+        let mut trace = TraceTable::new(2, sequence_length / 2);
+        trace.fill(
+            |state| {
+                state[0] = BaseElement::ONE;
+                state[1] = BaseElement::ONE;
+            },
+            |_, state| {
+                state[0] += state[1];
+                state[1] += state[0];
+            },
+        );
+        trace
+    }
+}
+
+impl<H: ElementHasher<BaseField = BaseElement>> Prover for FibProver<H> {
+    type BaseField = BaseElement;
+    type Air = FibAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = H;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> BaseElement {
+        trace.get(1, trace.length() - 1)
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn base_options() -> ProofOptions {
+    ProofOptions::new(28, 8, 0, FieldExtension::None, 4, 256)
+}
+
+fn quadratic_options() -> ProofOptions {
+    ProofOptions::new(28, 8, 0, FieldExtension::Quadratic, 4, 32)
+}
+
+/// Generates a proof for the first `sequence_length` terms of the Fibonacci sequence, and returns
+/// it together with the last term.
+fn build_proof<H: ElementHasher<BaseField = BaseElement>>(
+    options: ProofOptions,
+    sequence_length: usize,
+) -> (StarkProof, BaseElement) {
+    let prover = FibProver::<H>::new(options);
+    let trace = prover.build_trace(sequence_length);
+    let result = prover.get_pub_inputs(&trace);
+    (prover.prove(trace).unwrap(), result)
+}
+
+/// Re-encodes a proof generated with default values of the options added after the 0.4 releases
+/// in the format used by these releases.
+fn to_legacy_bytes(proof: &StarkProof) -> Vec<u8> {
+    fn copy(source: &mut SliceReader, target: &mut Vec<u8>, prefix_size: usize) {
+        let num_bytes = source.read_usize().unwrap();
+        match prefix_size {
+            2 => target.write_u16(num_bytes as u16),
+            _ => target.write_u32(num_bytes as u32),
+        }
+        target.write_u8_slice(&source.read_u8_vec(num_bytes).unwrap());
+    }
+
+    let bytes = proof.to_bytes();
+    let mut source = SliceReader::new(&bytes[1..]);
+    let mut result = Vec::new();
+
+    // context: trace layout, trace length, trace meta, field modulus, and options
+    result.write_u8_slice(&source.read_u8_vec(4).unwrap());
+    copy(&mut source, &mut result, 2);
+    let num_modulus_bytes = source.read_u8().unwrap();
+    result.write_u8(num_modulus_bytes);
+    result.write_u8_slice(&source.read_u8_vec(num_modulus_bytes as usize).unwrap());
+    result.write_u8(source.read_usize().unwrap() as u8); // number of queries
+    result.write_u8_slice(&source.read_u8_vec(5).unwrap());
+    assert_eq!(vec![2, 1, 0, 0, 0], source.read_u8_vec(5).unwrap());
+    source.read_u8().unwrap(); // hash function
+    let digest_size = source.read_u8().unwrap();
+    source.read_u8_vec(digest_size as usize).unwrap();
+
+    // commitments, queries, and OOD frame
+    copy(&mut source, &mut result, 2);
+    for _ in 0..4 {
+        copy(&mut source, &mut result, 4);
+    }
+    copy(&mut source, &mut result, 2);
+    copy(&mut source, &mut result, 2);
+
+    // FRI proof and proof-of-work nonce
+    let num_fri_layers = source.read_usize().unwrap() as u8;
+    result.write_u8(num_fri_layers);
+    for _ in 0..2 * num_fri_layers {
+        copy(&mut source, &mut result, 4);
+    }
+    copy(&mut source, &mut result, 2);
+    result.write_u8(source.read_u8().unwrap());
+    result.write_u64(source.read_u64().unwrap());
+    assert!(!source.has_more_bytes());
+    result
+}
+
+/// Re-encodes the specified proof into format version 1, in which the number of queries and the
+/// number of FRI layers were single bytes, and proof options did not include the number of trace
+/// rows per Merkle tree leaf.
+fn to_narrow_counts_bytes(proof: &StarkProof) -> Vec<u8> {
+    let bytes = proof.to_bytes();
+    let mut source = SliceReader::new(&bytes[1..]);
+    let mut result = vec![1];
+
+    // context up to the number of queries: trace layout, trace length, trace meta, field modulus
+    result.write_u8_slice(&source.read_u8_vec(4).unwrap());
+    let num_meta_bytes = source.read_usize().unwrap();
+    result.write_usize(num_meta_bytes);
+    result.write_u8_slice(&source.read_u8_vec(num_meta_bytes).unwrap());
+    let num_modulus_bytes = source.read_u8().unwrap();
+    result.write_u8(num_modulus_bytes);
+    result.write_u8_slice(&source.read_u8_vec(num_modulus_bytes as usize).unwrap());
+    result.write_u8(source.read_usize().unwrap() as u8);
+
+    // proof options up to Merkle tree arity, followed by the number of trace rows per leaf
+    result.write_u8_slice(&source.read_u8_vec(6).unwrap());
+    assert_eq!(1, source.read_u8().unwrap());
+
+    // all other counts are encoded identically for proofs with fewer than 128 queries
+    while source.has_more_bytes() {
+        result.write_u8(source.read_u8().unwrap());
+    }
+    result
+}
+
+/// A proof serialized in format version 1 by the release which introduced this format; the proof
+/// was generated for a sequence of 16 terms with options `(4, 4, 0, None, 4, 32)` and Blake3_256.
+const FORMAT_V1_PROOF: &str = "\
+0102000003000801000000ffffffff040400010405020000000120d63d569be41b3ce36da6d753268b5243c82d3d4de6\
+7a112b66f0a87fb7911db960b60fc61a212e484c8ddf85816e77cd70aa672e0610d1a484ef2c1104fdf5ab86ce71061e\
+5146f0d9c055af85222f321c45ce08f422e1ec0790af36a8f1edc8f60c251263cc7735177455ed94dc9934739bcf98f6\
+b35ce9b031854273559150f140771ffa636023f9c7d2cc8e094e672ed3b33db9266fa122a750e42663e88366bbd29de6\
+8ca651c74610c8d86f87e205175182ca8a03bd620472b1483c65691645e5020402a0e5d01cbc0faec611159c2a69a27c\
+44ef58e923375ff3d250c7f77ee3d18104556fba6b27a56fd48b44dc8e469692bd8b40ccf5855e93fdb9eab38e3298fc\
+2c020bdd9b2c30181f9079efc74298caaf02ce1895f75f96720f720aaf36de0d17f7a1852f673cdde569515031235644\
+1b039b452fbf9c43c40403a2546f3864f42204648c9d93949891d348ff03aa8e822a9bb9e0533fe6c5d8ebeaac68f403\
+f11033471a9b4890d37196ce45e13c221abb445340edd1252100855cb66e037dd97880a4c2ff63772a0010eabe2a68a4\
+6ed492173dc63f62956d2904da2778021171d6d502ba2a7a1b2c8bb5c7e8501ca5632bd9f97cd807d1e6305d91840365\
+ed9aed03c151b6ded1624017c70c67d3f0d9ff3dec17fb4bc1e3cfd336e018d9abba6fe8555cc51f2510ef7cdc0b329f\
+a59e9cbe3f179bb9f75c8a67e444ed8236b7c26fb543a61616db173d157e44b40dd75ec4754f82cf5be29f78d27194ae\
+2860babc40088dc1143585cc22c1831cfd7113182c7fedfb6c57ebbf88a52f6e9c504a2e26deab45834ccc91eb3c3ed1\
+738a61b32386530e0fea9d00ac64bf4f7337f118f6e502040238d9f8b2e1978e0fc5d1e2cb921cd107c09a4d189c31b6\
+eccf963e3cf851b939ae9b76a1ccc8f43cb0e72323399d5163cae9b765ad673a695692c7c4128582f202a21a09309b6d\
+fc785ca98e9dd78b28e8e38d07ce4a4cd9c61d05d4babaf4de1d0aff4a971f06fbd3c4f0d237aa49da8e52be13a85881\
+a64d95fcd1def5c3b1c904744ff30c21abdbdfe790ebe2708a6fba77e930e3b5f62a174c27e68c695982c0d2f369b827\
+a2c5f1129807098335f9f2f675cfe2bb4355c36f6464b7be30a5de19a255d5cc63a5d41903ddd80deab0bf309368c146\
+b4c52ef2b3bb8f77a07a425f1b19c6aa032f6ef68c4338338ef197cb8ec9e6dab45e6078806d908ee8b6020356cc9e84\
+14b0abeb986f26650131cbc425ebd4d6e43072ac48279664ed9e69cfaf2fd52cf7ed82623bf66e4792aeb95f0513fdde\
+d9b0bdf833b384d7920da17495713b5576f36dad2d77faf39a6c1736927ce281fad38a244e23b7f56203d83b20740a2a\
+45a68755b067dd6c2fe094aa219fd52dc46a5ecc862b90aa757644f5791041ef9bd4c7bfc56fc2d99537a55a18030080\
+0294e0063f092ab85f9125764270c91b4a9c9e6a971af9dd78a950dfbd48728088419526e09ec00be6b0858c2170cd7c\
+8ebcd11db11c464baf8a5a0c30de1e054ae1e55a03a173730aae383fc88a913e8f15fe888ec8b8bd9153953636d58f22\
+46947a25e7b2c867d6900fec0e78a7652db1d9c2e143f43eb56e7931a98df900b6624f5f1fd138eac9f60cad6ea15a65\
+e1adbb6a946306d2266fc45689bc42686720d352910cc495a39d1e13d53c99813bd74617e93e6a3cbbf134fe92d03e2e\
+7d37ef8410beca1569a3149f01239c06d62ac296f74d435cd8c773018475bf0eff468bfafee4ed9519943f5249987ca0\
+8e7e66f29b473c3aed2e4c355cf0807c64000100000000000000";
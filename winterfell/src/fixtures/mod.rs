@@ -0,0 +1,440 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Golden test vectors for detecting changes in the proof format and the protocol transcript.
+//!
+//! This module defines a small set of canonical computations and proof options (see
+//! [canonical_cases()]). Proofs generated for these cases are deterministic: for the same version
+//! of the prover, the same sequence of bytes is produced on every run and on every platform. Thus,
+//! proofs generated by one version of Winterfell and stored as [GoldenVector]s can be checked
+//! against the current code using [check_vectors()]. A failed check means that either the wire
+//! format of proofs or the way the prover and the verifier interact with the public coin has
+//! changed.
+//!
+//! Digests of the proofs generated by the current version of the code are listed in
+//! [STORED_DIGESTS]; these can be checked using [check_stored_digests()].
+//!
+//! This module does not rely on the standard library; thus, golden vectors can also be checked in
+//! `no_std` environments (e.g., to make sure a prover running in an enclave produces the same
+//! proofs as a prover running on a regular host).
+
+use crate::{
+    crypto::{
+        hashers::{Blake3_256, Rp64_256, Sha3_256},
+        Digest, ElementHasher, Hasher, TranscriptMode,
+    },
+    math::{fields::f64::BaseElement, FieldElement},
+    Air, AirContext, Assertion, ByteReader, ByteWriter, Deserializable, DeserializationError,
+    EvaluationFrame, FieldExtension, ProofOptions, Prover, Serializable, StarkProof, Trace,
+    TraceInfo, TraceTable, TransitionConstraintDegree, VerifierError,
+};
+use core::{fmt, marker::PhantomData};
+
+#[cfg(not(feature = "std"))]
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+#[cfg(test)]
+mod tests;
+
+// STORED DIGESTS
+// ================================================================================================
+
+/// Names of canonical cases and hex-encoded BLAKE3 digests of the proofs generated for them by the
+/// current version of the code.
+///
+/// These values must be updated whenever the proof format or the protocol transcript are changed
+/// intentionally.
+pub const STORED_DIGESTS: [(&str, &str); 4] = [
+    (
+        "fib_blake3_base",
+        "0d76433ea6a04c2845c6fa08067867730de3b46c7ad697a907b92e7a232bf8eb",
+    ),
+    (
+        "fib_sha3_quadratic",
+        "fdc543027aa7cb12223b535ab73540db09aa00fe827ca94c1315cf2b49e69456",
+    ),
+    (
+        "fib_rp64_arity4",
+        "254ab7625a4d7b311fb1e8374101df9e18734baac067879290c5bfd14150e144",
+    ),
+    (
+        "fib_blake3_xof",
+        "e40d3712dde75c52b77418d8518a248c81d49ef2f2cfe6db022da722364e3ad4",
+    ),
+];
+
+// CANONICAL CASES
+// ================================================================================================
+
+/// Hash function used to generate a proof for a canonical case.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FixtureHashFn {
+    /// BLAKE3 hash function with 256-bit output.
+    Blake3_256,
+    /// SHA3 hash function with 256-bit output.
+    Sha3_256,
+    /// Rescue Prime hash function over a 64-bit field with 256-bit output.
+    Rp64_256,
+}
+
+/// A canonical computation together with the options used to prove it.
+///
+/// All canonical cases prove computation of a Fibonacci sequence (two terms per trace row) over
+/// the 64-bit field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixtureCase {
+    /// Unique name of this case.
+    pub name: &'static str,
+    /// Number of terms of the Fibonacci sequence to compute; must be a power of two.
+    pub sequence_length: usize,
+    /// Hash function used by the prover and the verifier.
+    pub hash_fn: FixtureHashFn,
+    /// Options used to generate the proof.
+    pub options: ProofOptions,
+}
+
+impl FixtureCase {
+    /// Returns the public inputs of this case; i.e., the last term of the Fibonacci sequence.
+    pub fn pub_inputs(&self) -> BaseElement {
+        let mut t0 = BaseElement::ONE;
+        let mut t1 = BaseElement::ONE;
+        for _ in 0..(self.sequence_length / 2 - 1) {
+            t0 += t1;
+            t1 += t0;
+        }
+        t1
+    }
+
+    /// Returns a proof generated for this case.
+    pub fn prove(&self) -> StarkProof {
+        match self.hash_fn {
+            FixtureHashFn::Blake3_256 => self.prove_with::<Blake3_256<BaseElement>>(),
+            FixtureHashFn::Sha3_256 => self.prove_with::<Sha3_256<BaseElement>>(),
+            FixtureHashFn::Rp64_256 => self.prove_with::<Rp64_256>(),
+        }
+    }
+
+    /// Verifies the specified proof against the public inputs of this case.
+    ///
+    /// # Errors
+    /// Returns an error if the proof is not valid.
+    pub fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let pub_inputs = self.pub_inputs();
+        match self.hash_fn {
+            FixtureHashFn::Blake3_256 => {
+                crate::verify::<FibAir, Blake3_256<BaseElement>>(proof, pub_inputs)
+            }
+            FixtureHashFn::Sha3_256 => {
+                crate::verify::<FibAir, Sha3_256<BaseElement>>(proof, pub_inputs)
+            }
+            FixtureHashFn::Rp64_256 => crate::verify::<FibAir, Rp64_256>(proof, pub_inputs),
+        }
+    }
+
+    fn prove_with<H: ElementHasher<BaseField = BaseElement>>(&self) -> StarkProof {
+        let prover = FibProver::<H>::new(self.options.clone());
+        let trace = prover.build_trace(self.sequence_length);
+        prover
+            .prove(trace)
+            .expect("failed to generate proof for a canonical case")
+    }
+}
+
+/// Returns the list of canonical cases.
+///
+/// Together, the cases cover the base field and a field extension, both byte-oriented and
+/// algebraic hash functions, binary and non-binary Merkle trees, and both hash-chain and XOF
+/// transcript modes.
+pub fn canonical_cases() -> Vec<FixtureCase> {
+    vec![
+        FixtureCase {
+            name: "fib_blake3_base",
+            sequence_length: 64,
+            hash_fn: FixtureHashFn::Blake3_256,
+            options: ProofOptions::new(28, 8, 0, FieldExtension::None, 4, 256),
+        },
+        FixtureCase {
+            name: "fib_sha3_quadratic",
+            sequence_length: 64,
+            hash_fn: FixtureHashFn::Sha3_256,
+            options: ProofOptions::new(28, 8, 0, FieldExtension::Quadratic, 4, 256),
+        },
+        FixtureCase {
+            name: "fib_rp64_arity4",
+            sequence_length: 64,
+            hash_fn: FixtureHashFn::Rp64_256,
+            options: ProofOptions::new(28, 8, 0, FieldExtension::None, 4, 256).with_merkle_arity(4),
+        },
+        FixtureCase {
+            name: "fib_blake3_xof",
+            sequence_length: 64,
+            hash_fn: FixtureHashFn::Blake3_256,
+            options: ProofOptions::new(28, 8, 0, FieldExtension::None, 4, 256)
+                .with_transcript_mode(TranscriptMode::Blake3Xof),
+        },
+    ]
+}
+
+// GOLDEN VECTORS
+// ================================================================================================
+
+/// A serialized proof generated for a canonical case.
+///
+/// Golden vectors can be serialized (e.g., to be stored in files), and can later be checked
+/// against the current version of the code using [check_vectors()].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GoldenVector {
+    /// Name of the canonical case for which the proof was generated.
+    pub name: String,
+    /// Proof serialized via [StarkProof::to_bytes()].
+    pub proof: Vec<u8>,
+}
+
+impl GoldenVector {
+    /// Returns a hex-encoded BLAKE3 digest of the serialized proof.
+    pub fn digest(&self) -> String {
+        let digest = Blake3_256::<BaseElement>::hash(&self.proof);
+        digest
+            .as_bytes()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+}
+
+impl Serializable for GoldenVector {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_usize(self.name.len());
+        target.write_u8_slice(self.name.as_bytes());
+        target.write_usize(self.proof.len());
+        target.write_u8_slice(&self.proof);
+    }
+}
+
+impl Deserializable for GoldenVector {
+    /// Reads a golden vector from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if a valid golden vector could not be read from the specified `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let name_len = source.read_usize()?;
+        let name = String::from_utf8(source.read_u8_vec(name_len)?).map_err(|_| {
+            DeserializationError::InvalidValue("vector name is not valid UTF-8".to_string())
+        })?;
+        let proof_len = source.read_usize()?;
+        let proof = source.read_u8_vec(proof_len)?;
+        Ok(Self { name, proof })
+    }
+}
+
+/// Returns golden vectors for all canonical cases generated by the current version of the code.
+pub fn generate_vectors() -> Vec<GoldenVector> {
+    canonical_cases()
+        .iter()
+        .map(|case| GoldenVector {
+            name: case.name.to_string(),
+            proof: case.prove().to_bytes(),
+        })
+        .collect()
+}
+
+/// Checks the provided golden vectors against the current version of the code.
+///
+/// For each vector, a proof for the corresponding canonical case is generated and compared to the
+/// stored proof byte-for-byte; the stored proof is also parsed and verified.
+///
+/// # Errors
+/// Returns an error if:
+/// * A vector refers to an unknown canonical case.
+/// * A stored proof could not be parsed or is not valid.
+/// * A stored proof differs from the proof generated by the current version of the code.
+pub fn check_vectors(vectors: &[GoldenVector]) -> Result<(), FixtureError> {
+    let cases = canonical_cases();
+    for vector in vectors {
+        let case = find_case(&cases, &vector.name)?;
+
+        let proof = StarkProof::from_bytes(&vector.proof)
+            .map_err(|err| FixtureError::MalformedProof(vector.name.clone(), err))?;
+        case.verify(proof)
+            .map_err(|err| FixtureError::InvalidProof(vector.name.clone(), err))?;
+
+        if case.prove().to_bytes() != vector.proof {
+            return Err(FixtureError::ProofMismatch(vector.name.clone()));
+        }
+    }
+    Ok(())
+}
+
+/// Checks that proofs generated by the current version of the code for all canonical cases match
+/// [STORED_DIGESTS].
+///
+/// # Errors
+/// Returns an error if a digest of a generated proof does not match the stored digest.
+pub fn check_stored_digests() -> Result<(), FixtureError> {
+    let vectors = generate_vectors();
+    for (name, digest) in STORED_DIGESTS {
+        let vector = vectors
+            .iter()
+            .find(|vector| vector.name == name)
+            .ok_or_else(|| FixtureError::UnknownCase(name.to_string()))?;
+        if vector.digest() != digest {
+            return Err(FixtureError::ProofMismatch(name.to_string()));
+        }
+    }
+    Ok(())
+}
+
+fn find_case<'a>(cases: &'a [FixtureCase], name: &str) -> Result<&'a FixtureCase, FixtureError> {
+    cases
+        .iter()
+        .find(|case| case.name == name)
+        .ok_or_else(|| FixtureError::UnknownCase(name.to_string()))
+}
+
+// FIXTURE ERROR
+// ================================================================================================
+
+/// Represents an error returned when checking golden vectors.
+#[derive(Debug, PartialEq, Eq)]
+pub enum FixtureError {
+    /// A vector refers to a canonical case which does not exist.
+    UnknownCase(String),
+    /// A stored proof could not be parsed.
+    MalformedProof(String, DeserializationError),
+    /// A stored proof could not be verified.
+    InvalidProof(String, VerifierError),
+    /// A stored proof differs from the proof generated by the current version of the code.
+    ProofMismatch(String),
+}
+
+impl fmt::Display for FixtureError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnknownCase(name) => {
+                write!(f, "canonical case '{}' does not exist", name)
+            }
+            Self::MalformedProof(name, err) => {
+                write!(
+                    f,
+                    "stored proof for '{}' could not be parsed: {}",
+                    name, err
+                )
+            }
+            Self::InvalidProof(name, err) => {
+                write!(f, "stored proof for '{}' is not valid: {}", name, err)
+            }
+            Self::ProofMismatch(name) => {
+                write!(
+                    f,
+                    "proof generated for '{}' differs from the stored proof",
+                    name
+                )
+            }
+        }
+    }
+}
+
+// FIBONACCI AIR
+// ================================================================================================
+
+const TRACE_WIDTH: usize = 2;
+
+/// AIR for a Fibonacci sequence which advances by two terms per trace row.
+struct FibAir {
+    context: AirContext<BaseElement>,
+    result: BaseElement,
+}
+
+impl Air for FibAir {
+    type BaseField = BaseElement;
+    type PublicInputs = BaseElement;
+
+    fn new(trace_info: TraceInfo, pub_inputs: BaseElement, options: ProofOptions) -> Self {
+        let degrees = vec![
+            TransitionConstraintDegree::new(1),
+            TransitionConstraintDegree::new(1),
+        ];
+        FibAir {
+            context: AirContext::new(trace_info, degrees, 3, options),
+            result: pub_inputs,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        result[0] = next[0] - (current[0] + current[1]);
+        result[1] = next[1] - (current[1] + next[0]);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        let last_step = self.trace_length() - 1;
+        vec![
+            Assertion::single(0, 0, BaseElement::ONE),
+            Assertion::single(1, 0, BaseElement::ONE),
+            Assertion::single(1, last_step, self.result),
+        ]
+    }
+}
+
+// FIBONACCI PROVER
+// ================================================================================================
+
+struct FibProver<H: ElementHasher> {
+    options: ProofOptions,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> FibProver<H> {
+    fn new(options: ProofOptions) -> Self {
+        Self {
+            options,
+            _hasher: PhantomData,
+        }
+    }
+
+    fn build_trace(&self, sequence_length: usize) -> TraceTable<BaseElement> {
+        let mut trace = TraceTable::new(TRACE_WIDTH, sequence_length / 2);
+        trace.fill(
+            |state| {
+                state[0] = BaseElement::ONE;
+                state[1] = BaseElement::ONE;
+            },
+            |_, state| {
+                state[0] += state[1];
+                state[1] += state[0];
+            },
+        );
+        trace
+    }
+}
+
+impl<H: ElementHasher<BaseField = BaseElement>> Prover for FibProver<H> {
+    type BaseField = BaseElement;
+    type Air = FibAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = H;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> BaseElement {
+        trace.get(1, trace.length() - 1)
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+}
@@ -0,0 +1,58 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    canonical_cases, check_stored_digests, check_vectors, generate_vectors, FixtureError,
+    GoldenVector, STORED_DIGESTS,
+};
+use crate::{Deserializable, Serializable, SliceReader};
+
+#[test]
+fn stored_digests() {
+    // if this test fails after an intentional change to the proof format or the transcript,
+    // STORED_DIGESTS must be updated with the digests listed in the failure message
+    let digests = generate_vectors()
+        .iter()
+        .map(|vector| format!("(\"{}\", \"{}\"),\n", vector.name, vector.digest()))
+        .collect::<String>();
+    assert_eq!(canonical_cases().len(), STORED_DIGESTS.len());
+    assert_eq!(
+        Ok(()),
+        check_stored_digests(),
+        "current digests are:\n{}",
+        digests
+    );
+}
+
+#[test]
+fn golden_vectors() {
+    let vectors = generate_vectors();
+    assert_eq!(vectors, generate_vectors());
+    assert_eq!(Ok(()), check_vectors(&vectors));
+
+    // vectors should survive a serialization round trip
+    let bytes = vectors[0].to_bytes();
+    let vector = GoldenVector::read_from(&mut SliceReader::new(&bytes)).unwrap();
+    assert_eq!(vectors[0], vector);
+
+    // a vector for an unknown case should be rejected
+    let mut vector = vectors[0].clone();
+    vector.name = "unknown".to_string();
+    assert_eq!(
+        Err(FixtureError::UnknownCase("unknown".to_string())),
+        check_vectors(&[vector])
+    );
+
+    // a valid proof for a different case should be rejected
+    let mut vector = vectors[1].clone();
+    vector.name = vectors[3].name.clone();
+    assert!(check_vectors(&[vector]).is_err());
+
+    // a tampered proof should be rejected
+    let mut vector = vectors[0].clone();
+    let last = vector.proof.len() - 1;
+    vector.proof[last] ^= 1;
+    assert!(check_vectors(&[vector]).is_err());
+}
@@ -39,6 +39,10 @@
 //! machine). The number of threads can be configured via `RAYON_NUM_THREADS` environment
 //! variable.
 //!
+//! All items needed to generate and verify proofs (including field and hash function
+//! implementations) are re-exported from this crate, and the most commonly used of them can be
+//! imported at once from the [prelude] module.
+//!
 //! ## Prof verification
 //! To verify a [StarkProof] generated as described in the previous sections, you'll need to
 //! do the following:
@@ -48,6 +52,9 @@
 //! 2. Execute [verify()] function and supply the AIR of your computation together with the
 //!    [StarkProof] and related public inputs as parameters.
 //!
+//! A proof can also be verified step by step using the [Verifier] struct, which exposes the
+//! intermediate values computed by the verifier at each step of the protocol.
+//!
 //! Proof verification is extremely fast and is nearly independent of the complexity of the
 //! computation being verified. In vast majority of cases proofs can be verified in 3 - 5 ms
 //! on a modern mid-range laptop CPU (using a single core).
@@ -526,12 +533,30 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(not(feature = "std"))]
+#[macro_use]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+pub use prover::ArtifactDirectory;
 pub use prover::{
-    crypto, iterators, math, Air, AirContext, Assertion, AuxTraceRandElements, BoundaryConstraint,
-    BoundaryConstraintGroup, ByteReader, ByteWriter, ConstraintCompositionCoefficients,
-    ConstraintDivisor, DeepCompositionCoefficients, Deserializable, DeserializationError,
-    EvaluationFrame, FieldExtension, Matrix, ProofOptions, Prover, ProverError, Serializable,
-    SliceReader, StarkProof, Trace, TraceInfo, TraceLayout, TraceTable, TraceTableFragment,
-    TransitionConstraintDegree, TransitionConstraintGroup,
+    crypto, iterators, math, Air, AirContext, ArtifactSink, Assertion, AuxTraceRandElements,
+    BoundaryConstraint, BoundaryConstraintGroup, ByteReader, ByteWriter, ChannelMessage,
+    ConcurrencyConfig, ConstraintCompositionCoefficients, ConstraintDivisor, ConstraintEvaluator,
+    DeepCompositionCoefficients, Deserializable, DeserializationError, DomainError, DomainPoints,
+    EthStarkProof, EvaluationFrame, FieldExtension, Matrix, MessageKind, OptionsTuner,
+    PeriodicValueTable, ProofBatch, ProofChain, ProofOptions, ProofOptionsBuilder,
+    ProofOptionsError, ProofSink, ProofStats, Prover, ProverError, ProvingStats, RationalFunction,
+    Serializable, SliceReader, StageStats, StarkDomain, StarkProof, StepPublicInputs, Trace,
+    TraceBuilder, TraceError, TraceInfo, TraceLayout, TraceTable, TraceTableFragment,
+    TraceTableView, TransitionConstraintDegree, TransitionConstraintGroup, TransitionFragment,
+    TuningError, TuningObjective, VerifierParams, LEGACY_PROOF_FORMAT_VERSION,
 };
-pub use verifier::{verify, VerifierError};
+pub use verifier::{
+    replay_transcript, verify, verify_batch, verify_chain, verify_with_policy, ContextMismatch,
+    DeepValues, OodValues, PolicyError, QueryValues, Verifier, VerifierError, VerifierPolicy,
+};
+
+pub mod prelude;
+
+pub mod fixtures;
@@ -0,0 +1,44 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Items needed by most applications of Winterfell.
+//!
+//! Importing everything from this module brings into scope all items needed to describe a
+//! computation, generate STARK proofs for it, and verify these proofs; this way, applications
+//! depend only on this crate rather than on its component crates:
+//!
+//! ```
+//! use winterfell::prelude::*;
+//!
+//! // build an execution trace of a computation over a 128-bit field
+//! let mut trace = TraceTable::new(2, 8);
+//! trace.fill(
+//!     |state| {
+//!         state[0] = f128::BaseElement::ONE;
+//!         state[1] = f128::BaseElement::ONE;
+//!     },
+//!     |_, state| {
+//!         state[0] += state[1];
+//!         state[1] += state[0];
+//!     },
+//! );
+//! assert_eq!(8, trace.length());
+//!
+//! // proofs generated with these options provide ~96 bits of conjectured security
+//! let options = ProofOptions::recommended_96_bit();
+//! assert_eq!(27, options.num_queries());
+//! ```
+
+pub use crate::{
+    crypto::{
+        hashers::{Blake3_192, Blake3_256, Rp64_256, Sha3_256},
+        ElementHasher, Hasher,
+    },
+    math::{fields::f128, fields::f62, fields::f64, FieldElement, StarkField},
+    verify, Air, AirContext, Assertion, ByteReader, ByteWriter, Deserializable,
+    DeserializationError, EvaluationFrame, FieldExtension, ProofOptions, Prover, ProverError,
+    Serializable, StarkProof, Trace, TraceInfo, TraceTable, TransitionConstraintDegree,
+    VerifierError,
+};